@@ -0,0 +1,187 @@
+//! Channel authorization callbacks
+//!
+//! Decide which users may listen on a broadcast channel. They are shared by the
+//! WebSocket server and the `/broadcasting/auth` endpoint, so both always agree.
+
+use anyhow::Result;
+use crate::database::DbPool;
+use super::websocket::WebSocketUserInfo;
+
+/// Load the roles and permissions channel callbacks authorize against
+pub fn channel_user(pool: &DbPool, user_id: &str) -> Result<WebSocketUserInfo> {
+    let user_id = user_id.to_string();
+
+    // Get user data with roles and permissions using UserService
+    let user_data = crate::app::services::user_service::UserService::find_by_id_with_organizations_and_user_roles(
+        pool,
+        user_id.clone()
+    ).map_err(|e| anyhow::anyhow!("Failed to lookup user: {}", e))?;
+
+    let (user_resource, user_orgs) = match user_data {
+        Some((user, orgs)) => (user, orgs),
+        None => return Err(anyhow::anyhow!("User not found: {}", user_id)),
+    };
+
+    // Extract user-level roles
+    let roles: Vec<String> = user_resource.roles.iter()
+        .map(|role| role.name.clone())
+        .collect();
+
+    // Extract user-level and organization-level permissions
+    let mut permissions: Vec<String> = user_resource.permissions.iter()
+        .map(|perm| perm.name.clone())
+        .collect();
+
+    // Add organization-level permissions
+    for org in &user_orgs {
+        for perm in &org.permissions {
+            if !permissions.contains(&perm.name) {
+                permissions.push(perm.name.clone());
+            }
+        }
+    }
+
+    Ok(WebSocketUserInfo {
+        user_id: user_id,
+        email: user_resource.email.clone(),
+        roles: roles,
+        permissions: permissions,
+    })
+}
+
+/// Check if a channel requires authentication
+pub fn requires_authentication(channel: &str) -> bool {
+    match channel {
+        "general" | "public" => false,
+        "notifications" | "user" | "admin" | "private" => true,
+        _ => {
+            // Channels starting with "user." require authentication
+            if channel.starts_with("user.") {
+                return true;
+            }
+            // Channels starting with "admin." require authentication
+            if channel.starts_with("admin.") {
+                return true;
+            }
+            // Default to requiring authentication for unknown channels
+            true
+        }
+    }
+}
+
+/// Check if user can access a specific channel
+pub fn can_access_channel(pool: &DbPool, user_info: &WebSocketUserInfo, channel: &str) -> bool {
+    match channel {
+        "general" | "public" => true,
+        "notifications" => true, // All authenticated users can access notifications
+        "admin" => user_info.roles.contains(&"admin".to_string()),
+        _ => {
+            // Presence channels (e.g., "presence:team.456") follow the channel they wrap
+            if let Some(inner) = channel.strip_prefix("presence:") {
+                return can_access_channel(pool, user_info, inner);
+            }
+
+            // User-specific channels (e.g., "user.123")
+            if let Some(user_id) = channel.strip_prefix("user.") {
+                return user_info.user_id == user_id;
+            }
+
+            // Admin channels
+            if channel.starts_with("admin.") {
+                return user_info.roles.contains(&"admin".to_string()) ||
+                       user_info.permissions.contains(&"admin_channels".to_string());
+            }
+
+            // Team channels (e.g., "team.456")
+            if let Some(team_id) = channel.strip_prefix("team.") {
+                // Check if user belongs to the team
+                return user_has_team_access(pool, &user_info.user_id, team_id);
+            }
+
+            // Conversation channels (e.g., "conversation.01ARZ3...")
+            if let Some(conversation_id) = channel.strip_prefix("conversation.") {
+                return user_has_conversation_access(pool, &user_info.user_id, conversation_id);
+            }
+
+            // Organization channels (e.g., "org.789")
+            if let Some(org_id) = channel.strip_prefix("org.") {
+                // Check if user belongs to the organization
+                return user_has_org_access(pool, &user_info.user_id, org_id);
+            }
+
+            // Default: deny access to unknown channel patterns
+            false
+        }
+    }
+}
+
+/// Check if user has access to a specific team
+fn user_has_team_access(pool: &DbPool, user_id: &str, team_id: &str) -> bool {
+    match crate::app::services::user_service::UserService::find_by_id_with_organizations_and_user_roles(
+        pool,
+        user_id.to_string()
+    ) {
+        Ok(Some((_, user_orgs))) => {
+            // Check if user belongs to any organization that has this team
+            for org in user_orgs {
+                if let Some(ref organization) = org.organization {
+                    if organization.name.to_lowercase().contains("team") &&
+                       organization.id == team_id {
+                        return true;
+                    }
+                }
+            }
+            false
+        },
+        Ok(None) => {
+            tracing::warn!("User {} not found during team access check", user_id);
+            false
+        },
+        Err(e) => {
+            tracing::error!("Failed to check team access for user {}: {}", user_id, e);
+            false
+        }
+    }
+}
+
+/// Check if user is an active participant of a conversation
+fn user_has_conversation_access(pool: &DbPool, user_id: &str, conversation_id: &str) -> bool {
+    match crate::app::services::conversation_service::ConversationService::find_participant(
+        pool,
+        conversation_id,
+        user_id,
+    ) {
+        Ok(participant) => participant.is_some(),
+        Err(e) => {
+            tracing::error!("Failed to check conversation access for user {}: {}", user_id, e);
+            false
+        }
+    }
+}
+
+/// Check if user has access to a specific organization
+fn user_has_org_access(pool: &DbPool, user_id: &str, org_id: &str) -> bool {
+    match crate::app::services::user_service::UserService::find_by_id_with_organizations_and_user_roles(
+        pool,
+        user_id.to_string()
+    ) {
+        Ok(Some((_, user_orgs))) => {
+            // Check if user belongs to the specified organization
+            user_orgs.iter().any(|org| {
+                if let Some(ref organization) = org.organization {
+                    organization.id == org_id
+                } else {
+                    false
+                }
+            })
+        },
+        Ok(None) => {
+            tracing::warn!("User {} not found during organization access check", user_id);
+            false
+        },
+        Err(e) => {
+            tracing::error!("Failed to check organization access for user {}: {}", user_id, e);
+            false
+        }
+    }
+}
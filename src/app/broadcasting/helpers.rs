@@ -19,8 +19,14 @@ pub async fn broadcast_to_channel(channel: &str, event: &str, data: serde_json::
         event: event.to_string(),
         data,
         timestamp: chrono::Utc::now(),
+        id: None,
     };
 
+    if let Some(fake) = super::fake_driver().await {
+        fake.record(message).await;
+        return Ok(());
+    }
+
     let ws_manager = super::websocket::websocket_manager().await;
     ws_manager.broadcast(message).await
 }
@@ -43,6 +49,12 @@ pub async fn broadcast_to_team(team_id: &str, event: &str, data: serde_json::Val
     broadcast_to_channel(&channel, event, data).await
 }
 
+/// Helper function to broadcast to a conversation's private channel
+pub async fn broadcast_to_conversation(conversation_id: &str, event: &str, data: serde_json::Value) -> Result<()> {
+    let channel = format!("conversation.{}", conversation_id);
+    broadcast_to_channel(&channel, event, data).await
+}
+
 /// Helper function to broadcast notifications
 pub async fn broadcast_notification(user_id: &str, title: &str, message: &str, action_url: Option<&str>) -> Result<()> {
     let data = serde_json::json!({
@@ -209,4 +221,62 @@ impl BroadcastFacade {
     pub async fn system_alert(level: &str, message: &str, action_required: bool) -> Result<()> {
         broadcast_system_alert(level, message, action_required).await
     }
+
+    /// Enable broadcast faking (Broadcast::fake())
+    pub async fn fake() {
+        super::fake().await;
+    }
+
+    /// Disable broadcast faking
+    pub async fn restore() {
+        super::restore().await;
+    }
+
+    /// Assert something was broadcast on a channel
+    pub async fn assert_broadcast_on(channel: &str) -> bool {
+        match super::fake_driver().await {
+            Some(fake) => fake.assert_broadcast_on(channel).await,
+            None => false,
+        }
+    }
+
+    /// Assert an event was broadcast on a channel
+    pub async fn assert_broadcast_event_on(channel: &str, event: &str) -> bool {
+        match super::fake_driver().await {
+            Some(fake) => fake.assert_broadcast_event_on(channel, event).await,
+            None => false,
+        }
+    }
+
+    /// Assert nothing was broadcast on a channel
+    pub async fn assert_not_broadcast_on(channel: &str) -> bool {
+        match super::fake_driver().await {
+            Some(fake) => fake.assert_not_broadcast_on(channel).await,
+            None => false,
+        }
+    }
+
+    /// Assert a channel received a specific number of broadcasts
+    pub async fn assert_broadcast_times(channel: &str, times: usize) -> bool {
+        match super::fake_driver().await {
+            Some(fake) => fake.assert_broadcast_times(channel, times).await,
+            None => false,
+        }
+    }
+
+    /// Assert nothing was broadcast
+    pub async fn assert_nothing_broadcast() -> bool {
+        match super::fake_driver().await {
+            Some(fake) => fake.assert_nothing_broadcast().await,
+            None => false,
+        }
+    }
+
+    /// Get all broadcast messages
+    pub async fn get_broadcasts() -> Vec<BroadcastMessage> {
+        match super::fake_driver().await {
+            Some(fake) => fake.broadcasts().await,
+            None => Vec::new(),
+        }
+    }
 }
\ No newline at end of file
@@ -1,5 +1,10 @@
+pub mod channels;
 pub mod websocket;
 pub mod helpers;
+pub mod redis_subscriber;
+pub mod monitor;
+pub mod sse;
+pub mod replay;
 
 use anyhow::Result;
 use async_trait::async_trait;
@@ -37,6 +42,11 @@ pub trait BroadcastDriver: Send + Sync {
     /// Broadcast to a private channel
     async fn broadcast_private(&self, channel: &str, data: serde_json::Value) -> Result<()>;
 
+    /// Broadcast to a presence channel, whose subscribers can see each other
+    async fn broadcast_presence(&self, channel: &str, data: serde_json::Value) -> Result<()> {
+        self.broadcast(&format!("{}{}", websocket::PRESENCE_PREFIX, channel), data).await
+    }
+
     /// Get the driver name
     fn driver_name(&self) -> &'static str;
 }
@@ -53,6 +63,10 @@ pub struct BroadcastMessage {
     pub event: String,
     pub data: serde_json::Value,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Per-channel sequence number assigned when the message is broadcast,
+    /// sent as the SSE event id so clients can resume with `Last-Event-ID`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
 }
 
 impl WebSocketDriver {
@@ -86,6 +100,7 @@ impl BroadcastDriver for WebSocketDriver {
             event: "broadcast".to_string(),
             data,
             timestamp: chrono::Utc::now(),
+            id: None,
         };
 
         self.manager.broadcast(message).await?;
@@ -110,16 +125,58 @@ pub struct RedisDriver {
     pub port: u16,
     pub password: Option<String>,
     pub database: u8,
+    pub channels_prefix: String,
+    /// Buffer numbering each message for replay, when enabled
+    pub replay: Option<replay::RedisReplayBuffer>,
+    /// Opened on first use and shared by clones of the driver
+    connection: RedisConnection,
+}
+
+/// Multiplexed Redis connection, dropped after a failed command so the next one reconnects
+#[derive(Clone, Default)]
+struct RedisConnection(Arc<tokio::sync::Mutex<Option<redis::aio::MultiplexedConnection>>>);
+
+impl std::fmt::Debug for RedisConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RedisConnection")
+    }
 }
 
 impl RedisDriver {
+    /// Times `broadcast` tries to publish before giving up
+    pub const PUBLISH_ATTEMPTS: u32 = 3;
+
+    /// How long to wait for Redis to accept a connection
+    const CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
     pub fn new(host: String, port: u16) -> Self {
         Self {
             host,
             port,
             password: None,
             database: 0,
+            channels_prefix: crate::config::broadcasting::DEFAULT_CHANNELS_PREFIX.to_string(),
+            replay: None,
+            connection: RedisConnection::default(),
+        }
+    }
+
+    /// Create a driver from the broadcasting configuration
+    pub fn from_config(config: &crate::config::broadcasting::BroadcastingConfig) -> Self {
+        let mut driver = Self::new(config.redis_host.clone(), config.redis_port)
+            .with_database(config.redis_database)
+            .with_prefix(config.channels_prefix.clone());
+
+        if let Some(password) = &config.redis_password {
+            driver = driver.with_password(password.clone());
+        }
+
+        let replay = replay::RedisReplayBuffer::from_config(config);
+        if replay.options().is_enabled() {
+            driver = driver.with_replay(replay);
         }
+
+        driver
     }
 
     pub fn with_password(mut self, password: String) -> Self {
@@ -131,31 +188,117 @@ impl RedisDriver {
         self.database = database;
         self
     }
+
+    /// A blank prefix falls back to the default, matching what subscribers listen on
+    pub fn with_prefix(mut self, prefix: String) -> Self {
+        self.channels_prefix = crate::config::broadcasting::channels_prefix_or_default(prefix);
+        self
+    }
+
+    pub fn with_replay(mut self, replay: replay::RedisReplayBuffer) -> Self {
+        self.replay = Some(replay);
+        self
+    }
+
+    /// Redis connection URL for this driver
+    pub fn redis_url(&self) -> String {
+        if let Some(ref password) = self.password {
+            format!("redis://:{}@{}:{}/{}", password, self.host, self.port, self.database)
+        } else {
+            format!("redis://{}:{}/{}", self.host, self.port, self.database)
+        }
+    }
+
+    /// PING the server, failing when it is unreachable or answers anything but PONG
+    pub async fn health_check(&self) -> Result<()> {
+        let result = async {
+            let mut conn = self.connection().await?;
+            let pong: String = redis::cmd("PING").query_async(&mut conn).await?;
+            anyhow::ensure!(pong == "PONG", "Unexpected PING reply from Redis: {}", pong);
+            Ok(())
+        }.await;
+
+        if result.is_err() {
+            self.disconnect().await;
+        }
+        result
+    }
+
+    /// The shared connection, opening it if there is none
+    async fn connection(&self) -> Result<redis::aio::MultiplexedConnection> {
+        let mut connection = self.connection.0.lock().await;
+        if let Some(conn) = connection.as_ref() {
+            return Ok(conn.clone());
+        }
+
+        let client = redis::Client::open(self.redis_url())?;
+        let conn = tokio::time::timeout(Self::CONNECT_TIMEOUT, client.get_multiplexed_async_connection())
+            .await
+            .map_err(|_| anyhow::anyhow!("Timed out connecting to Redis at {}:{}", self.host, self.port))??;
+
+        *connection = Some(conn.clone());
+        Ok(conn)
+    }
+
+    async fn disconnect(&self) {
+        *self.connection.0.lock().await = None;
+    }
+
+    /// PUBLISH `payload`, reconnecting between attempts, and return the number of subscribers reached
+    async fn publish(&self, channel: &str, payload: &str) -> Result<i64> {
+        use redis::AsyncCommands;
+
+        let mut last_error = None;
+        for attempt in 1..=Self::PUBLISH_ATTEMPTS {
+            let result = async {
+                let mut conn = self.connection().await?;
+                let receivers: i64 = conn.publish(channel, payload).await?;
+                Ok::<i64, anyhow::Error>(receivers)
+            }.await;
+
+            match result {
+                Ok(receivers) => return Ok(receivers),
+                Err(e) => {
+                    tracing::warn!("Publish to Redis channel {} failed (attempt {}/{}): {}", channel, attempt, Self::PUBLISH_ATTEMPTS, e);
+                    self.disconnect().await;
+                    last_error = Some(e);
+
+                    if attempt < Self::PUBLISH_ATTEMPTS {
+                        tokio::time::sleep(std::time::Duration::from_millis(100 * attempt as u64)).await;
+                    }
+                }
+            }
+        }
+
+        let error = last_error.unwrap_or_else(|| anyhow::anyhow!("no attempts made"));
+        Err(error.context(format!("Failed to publish to Redis channel {} after {} attempts", channel, Self::PUBLISH_ATTEMPTS)))
+    }
 }
 
 #[async_trait]
 impl BroadcastDriver for RedisDriver {
     async fn broadcast(&self, channel: &str, data: serde_json::Value) -> Result<()> {
-        // Production Redis implementation steps:
-        // 1. Establish Redis connection with connection pooling
-        // 2. Publish message using Redis PUBLISH command
-        // 3. Handle connection failures with retry logic
-        // 4. Implement message acknowledgment system
-        // 5. Add monitoring and metrics for broadcast health
-
-        let message = BroadcastMessage {
+        let mut message = BroadcastMessage {
             channel: channel.to_string(),
             event: "broadcast".to_string(),
             data,
             timestamp: chrono::Utc::now(),
+            id: None,
         };
 
-        println!("Redis: Publishing to channel '{}': {:?}", channel, message);
+        // Still deliver live when the buffer is unavailable, just without an id to resume from
+        if let Some(replay) = &self.replay {
+            if let Err(e) = replay.push(&mut message).await {
+                tracing::warn!("Broadcast on {} not kept for replay: {}", channel, e);
+            }
+        }
+
+        let payload = serde_json::to_string(&message)?;
+        let redis_channel = format!("{}{}", self.channels_prefix, channel);
 
-        // Simulate Redis publishing
-        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        let receivers = self.publish(&redis_channel, &payload).await?;
 
-        tracing::info!("Published to Redis channel: {}", channel);
+        tracing::info!("Published to Redis channel: {} ({} subscribers)", redis_channel, receivers);
         Ok(())
     }
 
@@ -193,6 +336,7 @@ impl BroadcastDriver for LogDriver {
             event: "broadcast".to_string(),
             data,
             timestamp: chrono::Utc::now(),
+            id: None,
         };
 
         println!("BROADCAST LOG: Channel '{}' - {:?}", channel, message);
@@ -210,10 +354,131 @@ impl BroadcastDriver for LogDriver {
     }
 }
 
+/// Broadcast driver that records messages instead of delivering them
+///
+/// Installed by `BroadcastManager::fake` so tests can assert what a code path
+/// broadcast without WebSocket or Redis subscribers.
+#[derive(Debug, Clone, Default)]
+pub struct FakeBroadcastDriver {
+    broadcasts: Arc<RwLock<Vec<BroadcastMessage>>>,
+}
+
+impl FakeBroadcastDriver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a message, as the WebSocket manager would have delivered it
+    pub async fn record(&self, message: BroadcastMessage) {
+        tracing::info!("Broadcast '{}' on {} was faked", message.event, message.channel);
+        self.broadcasts.write().await.push(message);
+    }
+
+    /// Every message broadcast since the fake was installed, in broadcast order
+    pub async fn broadcasts(&self) -> Vec<BroadcastMessage> {
+        self.broadcasts.read().await.clone()
+    }
+
+    /// Assert that something was broadcast on `channel`
+    pub async fn assert_broadcast_on(&self, channel: &str) -> bool {
+        self.broadcast_count(|message| message.channel == channel).await > 0
+    }
+
+    /// Assert that `event` was broadcast on `channel`
+    pub async fn assert_broadcast_event_on(&self, channel: &str, event: &str) -> bool {
+        self.broadcast_count(|message| message.channel == channel && message.event == event).await > 0
+    }
+
+    /// Assert that nothing was broadcast on `channel`
+    pub async fn assert_not_broadcast_on(&self, channel: &str) -> bool {
+        self.broadcast_count(|message| message.channel == channel).await == 0
+    }
+
+    /// Assert that exactly `times` messages were broadcast on `channel`
+    pub async fn assert_broadcast_times(&self, channel: &str, times: usize) -> bool {
+        self.broadcast_count(|message| message.channel == channel).await == times
+    }
+
+    /// Assert that nothing was broadcast
+    pub async fn assert_nothing_broadcast(&self) -> bool {
+        self.broadcasts.read().await.is_empty()
+    }
+
+    async fn broadcast_count(&self, matches: impl Fn(&BroadcastMessage) -> bool) -> usize {
+        self.broadcasts.read().await.iter().filter(|message| matches(message)).count()
+    }
+}
+
+#[async_trait]
+impl BroadcastDriver for FakeBroadcastDriver {
+    async fn broadcast(&self, channel: &str, data: serde_json::Value) -> Result<()> {
+        self.record(BroadcastMessage {
+            channel: channel.to_string(),
+            event: "broadcast".to_string(),
+            data,
+            timestamp: chrono::Utc::now(),
+            id: None,
+        }).await;
+        Ok(())
+    }
+
+    async fn broadcast_private(&self, channel: &str, data: serde_json::Value) -> Result<()> {
+        let private_channel = format!("private:{}", channel);
+        self.broadcast(&private_channel, data).await
+    }
+
+    fn driver_name(&self) -> &'static str {
+        "fake"
+    }
+}
+
+/// Outcome of `BroadcastManager::broadcast_to_many`, per channel in the order given
+#[derive(Debug, Default)]
+pub struct FanOutSummary {
+    pub results: Vec<(String, Result<()>)>,
+}
+
+impl FanOutSummary {
+    /// Channels the message was delivered to
+    pub fn succeeded(&self) -> Vec<&str> {
+        self.results.iter()
+            .filter(|(_, result)| result.is_ok())
+            .map(|(channel, _)| channel.as_str())
+            .collect()
+    }
+
+    /// Channels the message could not be delivered to, with why
+    pub fn failed(&self) -> Vec<(&str, &anyhow::Error)> {
+        self.results.iter()
+            .filter_map(|(channel, result)| result.as_ref().err().map(|e| (channel.as_str(), e)))
+            .collect()
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|(_, result)| result.is_ok())
+    }
+
+    /// `Ok` when every channel succeeded, otherwise one error naming each failed channel
+    pub fn into_result(self) -> Result<()> {
+        let failures: Vec<String> = self.failed()
+            .into_iter()
+            .map(|(channel, e)| format!("{}: {}", channel, e))
+            .collect();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Broadcast failed on {} of {} channels ({})", failures.len(), self.results.len(), failures.join("; ")))
+        }
+    }
+}
+
 /// Broadcast manager that handles different drivers
 pub struct BroadcastManager {
     drivers: HashMap<String, Box<dyn BroadcastDriver>>,
     default_driver: String,
+    /// While set, every broadcast goes here whichever driver was asked for
+    fake: Option<FakeBroadcastDriver>,
 }
 
 impl BroadcastManager {
@@ -221,6 +486,7 @@ impl BroadcastManager {
         Self {
             drivers: HashMap::new(),
             default_driver,
+            fake: None,
         }
     }
 
@@ -228,39 +494,52 @@ impl BroadcastManager {
         self.drivers.insert(name, driver);
     }
 
-    pub async fn broadcast(&self, broadcastable: &dyn Broadcastable) -> Result<()> {
-        let channel = broadcastable.broadcast_channel();
-        let data = broadcastable.broadcast_data();
-
-        let driver = self.drivers.get(&self.default_driver)
-            .ok_or_else(|| anyhow::anyhow!("Broadcast driver '{}' not found", self.default_driver))?;
-
-        if broadcastable.is_private() {
-            if let Some(private_channel) = broadcastable.private_channel() {
-                driver.broadcast_private(&private_channel, data).await?;
-            } else {
-                return Err(anyhow::anyhow!("Private broadcast requires a private channel"));
-            }
-        } else {
-            driver.broadcast(&channel, data).await?;
+    /// The named driver, or the fake while faking
+    fn driver(&self, driver_name: &str) -> Result<&dyn BroadcastDriver> {
+        if let Some(fake) = &self.fake {
+            return Ok(fake);
         }
 
-        Ok(())
+        self.drivers.get(driver_name)
+            .map(|driver| driver.as_ref())
+            .ok_or_else(|| anyhow::anyhow!("Broadcast driver '{}' not found", driver_name))
+    }
+
+    pub async fn broadcast(&self, broadcastable: &dyn Broadcastable) -> Result<()> {
+        self.broadcast_with_driver(broadcastable, &self.default_driver).await
     }
 
     pub async fn broadcast_to_channel(&self, channel: &str, data: serde_json::Value) -> Result<()> {
-        let driver = self.drivers.get(&self.default_driver)
-            .ok_or_else(|| anyhow::anyhow!("Broadcast driver '{}' not found", self.default_driver))?;
+        self.driver(&self.default_driver)?.broadcast(channel, data).await
+    }
+
+    /// Broadcast the same data to every channel through the default driver
+    ///
+    /// Channels are sent to concurrently and a failing channel does not stop
+    /// the others; only a missing driver fails the whole call.
+    pub async fn broadcast_to_many(&self, channels: &[String], data: serde_json::Value) -> Result<FanOutSummary> {
+        let driver = self.driver(&self.default_driver)?;
+
+        let sends = channels.iter().map(|channel| {
+            let data = data.clone();
+            async move { (channel.clone(), driver.broadcast(channel, data).await) }
+        });
+        let results = futures::future::join_all(sends).await;
+
+        for (channel, result) in &results {
+            if let Err(e) = result {
+                tracing::warn!("Broadcast to {} failed: {}", channel, e);
+            }
+        }
 
-        driver.broadcast(channel, data).await
+        Ok(FanOutSummary { results })
     }
 
     pub async fn broadcast_with_driver(&self, broadcastable: &dyn Broadcastable, driver_name: &str) -> Result<()> {
         let channel = broadcastable.broadcast_channel();
         let data = broadcastable.broadcast_data();
 
-        let driver = self.drivers.get(driver_name)
-            .ok_or_else(|| anyhow::anyhow!("Broadcast driver '{}' not found", driver_name))?;
+        let driver = self.driver(driver_name)?;
 
         if broadcastable.is_private() {
             if let Some(private_channel) = broadcastable.private_channel() {
@@ -274,6 +553,31 @@ impl BroadcastManager {
 
         Ok(())
     }
+
+    /// Enable broadcast faking for testing
+    ///
+    /// Installs a fresh `FakeBroadcastDriver` that records every broadcast,
+    /// including those sent through the `helpers` functions.
+    pub fn fake(&mut self) -> FakeBroadcastDriver {
+        let fake = FakeBroadcastDriver::new();
+        self.fake = Some(fake.clone());
+        fake
+    }
+
+    /// Disable broadcast faking, delivering through the registered drivers again
+    pub fn restore(&mut self) {
+        self.fake = None;
+    }
+
+    /// Check if broadcasts are being faked
+    pub fn is_faking(&self) -> bool {
+        self.fake.is_some()
+    }
+
+    /// The installed fake, if broadcasts are being faked
+    pub fn fake_driver(&self) -> Option<&FakeBroadcastDriver> {
+        self.fake.as_ref()
+    }
 }
 
 /// Global broadcast manager instance
@@ -305,4 +609,31 @@ pub async fn broadcast_to_channel(channel: &str, data: serde_json::Value) -> Res
     let manager = broadcast_manager().await;
     let manager = manager.read().await;
     manager.broadcast_to_channel(channel, data).await
-}
\ No newline at end of file
+}
+
+/// Broadcast to several channels using the global manager
+pub async fn broadcast_to_many(channels: &[String], data: serde_json::Value) -> Result<FanOutSummary> {
+    let manager = broadcast_manager().await;
+    let manager = manager.read().await;
+    manager.broadcast_to_many(channels, data).await
+}
+
+/// Enable broadcast faking for testing
+pub async fn fake() -> FakeBroadcastDriver {
+    broadcast_manager().await.write().await.fake()
+}
+
+/// Disable broadcast faking
+pub async fn restore() {
+    broadcast_manager().await.write().await.restore();
+}
+
+/// Check if broadcasts are being faked
+pub async fn is_faking() -> bool {
+    broadcast_manager().await.read().await.is_faking()
+}
+
+/// The installed fake, if broadcasts are being faked
+pub async fn fake_driver() -> Option<FakeBroadcastDriver> {
+    broadcast_manager().await.read().await.fake_driver().cloned()
+}
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+use super::BroadcastMessage;
+
+/// Messages seen on a single channel while monitoring
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelStats {
+    pub channel: String,
+    pub messages: u64,
+    pub last_event: String,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Per-channel message counts aggregated over a monitoring window
+#[derive(Debug, Default)]
+pub struct ChannelActivity {
+    channels: HashMap<String, ChannelStats>,
+}
+
+impl ChannelActivity {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a message against its channel
+    pub fn record(&mut self, message: &BroadcastMessage) {
+        let stats = self.channels.entry(message.channel.clone()).or_insert_with(|| ChannelStats {
+            channel: message.channel.clone(),
+            messages: 0,
+            last_event: message.event.clone(),
+            last_seen: message.timestamp,
+        });
+
+        stats.messages += 1;
+        if message.timestamp >= stats.last_seen {
+            stats.last_event = message.event.clone();
+            stats.last_seen = message.timestamp;
+        }
+    }
+
+    /// Total messages seen across all channels
+    pub fn total(&self) -> u64 {
+        self.channels.values().map(|stats| stats.messages).sum()
+    }
+
+    /// Channels ordered by message count, busiest first
+    pub fn channels(&self) -> Vec<ChannelStats> {
+        let mut channels: Vec<ChannelStats> = self.channels.values().cloned().collect();
+        channels.sort_by(|a, b| b.messages.cmp(&a.messages).then_with(|| a.channel.cmp(&b.channel)));
+        channels
+    }
+
+    /// Table of per-channel counts for printing at the end of a run
+    pub fn render_table(&self) -> String {
+        let channels = self.channels();
+        if channels.is_empty() {
+            return "No broadcast messages observed.".to_string();
+        }
+
+        let channel_width = channels.iter().map(|s| s.channel.len()).max().unwrap_or(0).max("Channel".len());
+        let event_width = channels.iter().map(|s| s.last_event.len()).max().unwrap_or(0).max("Last event".len());
+
+        let mut table = String::new();
+        let _ = writeln!(table, "{:<cw$}  {:>8}  {:<ew$}  Last seen", "Channel", "Messages", "Last event", cw = channel_width, ew = event_width);
+        let _ = writeln!(table, "{}  {}  {}  {}", "-".repeat(channel_width), "-".repeat(8), "-".repeat(event_width), "-".repeat(19));
+        for stats in &channels {
+            let _ = writeln!(
+                table,
+                "{:<cw$}  {:>8}  {:<ew$}  {}",
+                stats.channel,
+                stats.messages,
+                stats.last_event,
+                stats.last_seen.format("%Y-%m-%d %H:%M:%S"),
+                cw = channel_width,
+                ew = event_width
+            );
+        }
+        let _ = write!(table, "{:<cw$}  {:>8}", "Total", self.total(), cw = channel_width);
+
+        table
+    }
+}
+
+/// Outcome of a `broadcast ping` run
+///
+/// Connection counts are left out: the CLI process holds no WebSocket
+/// connections, and no instance shares its counts.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PingSummary {
+    pub sent: u64,
+    pub failed: u64,
+}
+
+impl PingSummary {
+    pub fn record_sent(&mut self) {
+        self.sent += 1;
+    }
+
+    pub fn record_failed(&mut self) {
+        self.failed += 1;
+    }
+
+    pub fn render(&self, elapsed: Duration) -> String {
+        format!(
+            "Pings sent: {} | Failed: {} | Duration: {}s",
+            self.sent,
+            self.failed,
+            elapsed.as_secs()
+        )
+    }
+}
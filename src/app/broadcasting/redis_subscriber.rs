@@ -0,0 +1,84 @@
+use anyhow::Result;
+use futures::StreamExt;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use super::websocket::WebSocketManager;
+use super::BroadcastMessage;
+use crate::config::broadcasting::channels_prefix_or_default;
+
+/// Subscribes to the Redis broadcast backplane and relays messages into a local
+/// `WebSocketManager`, so clients connected to this process receive broadcasts
+/// published by any other instance using the Redis driver.
+pub struct RedisSubscriber {
+    redis_url: String,
+    channels_prefix: String,
+    manager: Arc<WebSocketManager>,
+}
+
+impl RedisSubscriber {
+    /// A blank `channels_prefix` falls back to the default broadcast prefix
+    pub fn new(redis_url: String, channels_prefix: String, manager: Arc<WebSocketManager>) -> Self {
+        Self {
+            redis_url,
+            channels_prefix: channels_prefix_or_default(channels_prefix),
+            manager,
+        }
+    }
+
+    /// Create a subscriber from the broadcasting configuration
+    pub fn from_config(config: &crate::config::broadcasting::BroadcastingConfig, manager: Arc<WebSocketManager>) -> Self {
+        Self::new(config.redis_url(), config.channels_prefix.clone(), manager)
+    }
+
+    /// Listen for published messages until the connection closes
+    pub async fn listen(&self) -> Result<()> {
+        let client = redis::Client::open(self.redis_url.as_str())?;
+        let mut pubsub = client.get_async_pubsub().await?;
+
+        let pattern = format!("{}*", self.channels_prefix);
+        pubsub.psubscribe(&pattern).await?;
+        info!("Subscribed to Redis broadcast backplane with pattern: {}", pattern);
+
+        let mut messages = pubsub.on_message();
+        while let Some(msg) = messages.next().await {
+            let payload: String = match msg.get_payload() {
+                Ok(payload) => payload,
+                Err(e) => {
+                    warn!("Ignoring unreadable Redis broadcast payload: {}", e);
+                    continue;
+                }
+            };
+
+            match serde_json::from_str::<BroadcastMessage>(&payload) {
+                Ok(message) => {
+                    if let Err(e) = self.manager.broadcast(message).await {
+                        error!("Failed to relay Redis broadcast to WebSocket clients: {}", e);
+                    }
+                }
+                Err(e) => {
+                    warn!("Ignoring malformed Redis broadcast on {}: {}", msg.get_channel_name(), e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Keep listening, reconnecting with a short delay when the connection drops
+    pub async fn run(self) {
+        loop {
+            match self.listen().await {
+                Ok(()) => warn!("Redis broadcast subscription closed, reconnecting"),
+                Err(e) => error!("Redis broadcast subscription failed: {}", e),
+            }
+
+            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        }
+    }
+
+    /// Spawn the subscriber on the Tokio runtime
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+}
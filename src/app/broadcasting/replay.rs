@@ -0,0 +1,163 @@
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+use super::BroadcastMessage;
+
+/// How many messages each channel keeps for resuming clients, and for how long
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayOptions {
+    /// Messages kept per channel; 0 turns replay off
+    pub size: usize,
+    /// Age after which a message is no longer replayed; `None` keeps it until pushed out
+    pub ttl: Option<Duration>,
+}
+
+impl ReplayOptions {
+    pub fn new(size: usize, ttl: Option<Duration>) -> Self {
+        Self { size, ttl }
+    }
+
+    pub fn from_config(config: &crate::config::broadcasting::BroadcastingConfig) -> Self {
+        let ttl = (config.replay_ttl_secs > 0).then(|| Duration::from_secs(config.replay_ttl_secs));
+        Self::new(config.replay_buffer_size, ttl)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.size > 0
+    }
+
+    /// Whether `message` is too old to be replayed
+    pub fn is_expired(&self, message: &BroadcastMessage) -> bool {
+        let Some(ttl) = self.ttl else {
+            return false;
+        };
+
+        let age = chrono::Utc::now().signed_duration_since(message.timestamp);
+        age.to_std().is_ok_and(|age| age > ttl)
+    }
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self::new(100, Some(Duration::from_secs(300)))
+    }
+}
+
+/// Numbers a message and stores it in one step, so concurrent publishers
+/// cannot add ids out of order
+///
+/// KEYS: history, sequence. ARGV: payload, size, ttl seconds (0 for none).
+const PUSH_SCRIPT: &str = r#"
+local id = redis.call('INCR', KEYS[2])
+redis.call('ZADD', KEYS[1], id, ARGV[1])
+redis.call('ZREMRANGEBYRANK', KEYS[1], 0, -tonumber(ARGV[2]) - 1)
+local ttl = tonumber(ARGV[3])
+if ttl > 0 then
+    redis.call('EXPIRE', KEYS[1], ttl)
+    redis.call('EXPIRE', KEYS[2], ttl * 2)
+end
+return id
+"#;
+
+/// Replay buffer shared through Redis by every process using the Redis driver
+///
+/// The publisher numbers each message from a per-channel counter and keeps it
+/// in a sorted set scored by that id, so a client can resume on any instance.
+#[derive(Debug, Clone)]
+pub struct RedisReplayBuffer {
+    redis_url: String,
+    key_prefix: String,
+    options: ReplayOptions,
+    /// Opened on first use and shared by clones; reconnects on its own
+    connection: ReplayConnection,
+}
+
+#[derive(Clone, Default)]
+struct ReplayConnection(Arc<OnceCell<redis::aio::ConnectionManager>>);
+
+impl std::fmt::Debug for ReplayConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ReplayConnection")
+    }
+}
+
+impl RedisReplayBuffer {
+    pub fn new(redis_url: String, channels_prefix: &str, options: ReplayOptions) -> Self {
+        Self {
+            redis_url,
+            key_prefix: format!("{}broadcast_replay:", channels_prefix),
+            options,
+            connection: ReplayConnection::default(),
+        }
+    }
+
+    /// Create a buffer from the broadcasting configuration
+    pub fn from_config(config: &crate::config::broadcasting::BroadcastingConfig) -> Self {
+        Self::new(config.redis_url(), &config.channels_prefix, ReplayOptions::from_config(config))
+    }
+
+    pub fn options(&self) -> ReplayOptions {
+        self.options
+    }
+
+    fn history_key(&self, channel: &str) -> String {
+        format!("{}{}", self.key_prefix, channel)
+    }
+
+    fn sequence_key(&self, channel: &str) -> String {
+        format!("{}{}:seq", self.key_prefix, channel)
+    }
+
+    async fn connection(&self) -> Result<redis::aio::ConnectionManager> {
+        let connection = self.connection.0
+            .get_or_try_init(|| async {
+                let client = redis::Client::open(self.redis_url.as_str())?;
+                client.get_connection_manager().await
+            })
+            .await?;
+        Ok(connection.clone())
+    }
+
+    /// Assign the message the channel's next id and keep it for replay
+    ///
+    /// The id is the message's score, so the stored payload leaves it out.
+    pub async fn push(&self, message: &mut BroadcastMessage) -> Result<()> {
+        let mut conn = self.connection().await?;
+        message.id = None;
+        let payload = serde_json::to_string(&message)?;
+        let ttl = self.options.ttl.map(|ttl| ttl.as_secs().max(1)).unwrap_or(0);
+
+        let id: u64 = redis::Script::new(PUSH_SCRIPT)
+            .key(self.history_key(&message.channel))
+            .key(self.sequence_key(&message.channel))
+            .arg(payload)
+            .arg(self.options.size)
+            .arg(ttl)
+            .invoke_async(&mut conn)
+            .await?;
+        message.id = Some(id);
+
+        Ok(())
+    }
+
+    /// Buffered messages on `channel` after `last_id`, oldest first
+    pub async fn since(&self, channel: &str, last_id: u64) -> Result<Vec<BroadcastMessage>> {
+        use redis::AsyncCommands;
+
+        let mut conn = self.connection().await?;
+        let entries: Vec<(String, f64)> = conn
+            .zrangebyscore_withscores(self.history_key(channel), format!("({}", last_id), "+inf")
+            .await?;
+
+        Ok(entries.iter()
+            .filter_map(|(payload, id)| {
+                let mut message = serde_json::from_str::<BroadcastMessage>(payload).ok()?;
+                message.id = Some(*id as u64);
+                Some(message)
+            })
+            .filter(|message| !self.options.is_expired(message))
+            .collect())
+    }
+}
@@ -0,0 +1,141 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+use super::BroadcastMessage;
+use super::websocket::{authorize_channel, WebSocketManager};
+
+/// Query parameters for an SSE subscription
+///
+/// `EventSource` cannot send headers, so credentials travel in the query the
+/// same way they do for WebSockets.
+#[derive(Debug, Deserialize)]
+pub struct SseQuery {
+    pub auth_token: Option<String>,
+    /// Allow token from `/broadcasting/auth`, accepted instead of `auth_token`
+    pub channel_auth: Option<String>,
+    /// Fallback for clients that cannot set the `Last-Event-ID` header
+    #[serde(alias = "since")]
+    pub last_event_id: Option<u64>,
+}
+
+/// Create SSE routes
+pub fn sse_routes() -> Router<Arc<WebSocketManager>> {
+    Router::new()
+        .route("/{channel}", get(sse_handler))
+}
+
+/// Stream a broadcast channel as `text/event-stream`
+///
+/// Every broadcast is sent as an event named after `BroadcastMessage::event`
+/// with the full message as JSON data and its channel sequence as the id.
+/// A reconnecting client sends `Last-Event-ID` and first receives the
+/// messages it missed that are still in the channel's replay buffer.
+pub async fn sse_handler(
+    Path(channel): Path<String>,
+    Query(params): Query<SseQuery>,
+    headers: HeaderMap,
+    State(manager): State<Arc<WebSocketManager>>,
+) -> Response {
+    let has_credentials = params.auth_token.is_some() || params.channel_auth.is_some();
+    if let Err(e) = authorize_channel(&channel, params.auth_token.as_deref(), params.channel_auth.as_deref()).await {
+        warn!("SSE connection denied: {}", e);
+        let status = if has_credentials { StatusCode::FORBIDDEN } else { StatusCode::UNAUTHORIZED };
+        return (status, Json(serde_json::json!({
+            "error": "unauthorized",
+            "message": "Authentication required or insufficient permissions"
+        }))).into_response();
+    }
+
+    let last_event_id = headers.get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .or(params.last_event_id);
+
+    let mut subscription = manager.subscribe_since(&channel, last_event_id).await;
+    let connection = SseConnection::open(manager, channel.clone()).await;
+
+    let stream = async_stream::stream! {
+        // Dropped with the stream when the client disconnects
+        let connection = connection;
+
+        yield Event::default()
+            .event("connected")
+            .json_data(serde_json::json!({
+                "connection_id": connection.id,
+                "message": "Connected to channel successfully"
+            }));
+
+        for message in std::mem::take(&mut subscription.missed) {
+            yield event(&message);
+        }
+
+        loop {
+            match subscription.recv().await {
+                Ok(message) => yield event(&message),
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("SSE connection {} skipped {} messages on channel {}", connection.id, skipped, channel);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    };
+
+    let heartbeat = crate::config::broadcasting::BroadcastingConfig::from_env()
+        .map(|config| config.sse_heartbeat_secs)
+        .unwrap_or(15);
+
+    let mut response = Sse::new(stream)
+        .keep_alive(KeepAlive::new().interval(Duration::from_secs(heartbeat)).text("heartbeat"))
+        .into_response();
+    // Stop nginx from buffering the stream
+    response.headers_mut().insert("x-accel-buffering", HeaderValue::from_static("no"));
+    response
+}
+
+fn event(message: &BroadcastMessage) -> Result<Event, axum::Error> {
+    let event = Event::default().event(&message.event);
+    let event = match message.id {
+        Some(id) => event.id(id.to_string()),
+        None => event,
+    };
+    event.json_data(message)
+}
+
+/// Counts an SSE stream as a channel connection until the client goes away
+struct SseConnection {
+    id: String,
+    channel: String,
+    manager: Arc<WebSocketManager>,
+}
+
+impl SseConnection {
+    async fn open(manager: Arc<WebSocketManager>, channel: String) -> Self {
+        let id = ulid::Ulid::new().to_string();
+        manager.add_connection(&channel, id.clone()).await;
+        info!("New SSE connection {} for channel: {}", id, channel);
+        Self { id, channel, manager }
+    }
+}
+
+impl Drop for SseConnection {
+    fn drop(&mut self) {
+        let (manager, channel, id) = (self.manager.clone(), self.channel.clone(), self.id.clone());
+        tokio::spawn(async move {
+            manager.remove_connection(&channel, &id).await;
+            info!("SSE connection {} disconnected from channel: {}", id, channel);
+        });
+    }
+}
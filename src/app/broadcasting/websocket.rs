@@ -9,8 +9,8 @@ use axum::{
     Router,
 };
 use futures::{sink::SinkExt, stream::StreamExt};
-use serde::{Deserialize};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
 use tracing::{info, warn, error};
@@ -19,18 +19,112 @@ use crate::database::connection::get_connection;
 /// WebSocket connection manager for broadcasting
 #[derive(Debug, Clone)]
 pub struct WebSocketManager {
-    /// Channel broadcasters for each channel
-    channels: Arc<RwLock<HashMap<String, broadcast::Sender<BroadcastMessage>>>>,
+    /// Broadcaster and recent history for each channel
+    channels: Arc<RwLock<HashMap<String, ChannelState>>>,
     /// Connected clients for each channel
     connections: Arc<RwLock<HashMap<String, Vec<String>>>>,
+    /// Open connections per device, used to track device presence
+    device_connections: Arc<RwLock<HashMap<String, usize>>>,
+    /// Members of each `presence:` channel, keyed by user id
+    presence_members: Arc<RwLock<HashMap<String, HashMap<String, PresenceEntry>>>>,
+    /// How much history each channel keeps for resuming clients
+    replay: ReplayOptions,
+    /// Shared history read on resume instead of the local one, with the Redis driver
+    redis_replay: Option<RedisReplayBuffer>,
 }
 
 use super::BroadcastMessage;
+use super::replay::{RedisReplayBuffer, ReplayOptions};
+use super::channels::{can_access_channel, channel_user, requires_authentication};
 
 #[derive(Debug, Deserialize)]
 pub struct WebSocketQuery {
     pub channel: Option<String>,
     pub auth_token: Option<String>,
+    /// Device to mark online while the connection is open
+    pub device_id: Option<String>,
+    /// Allow token from `/broadcasting/auth`, accepted instead of `auth_token`
+    pub channel_auth: Option<String>,
+    /// Id of the last message seen before reconnecting; buffered messages after it are sent first
+    #[serde(alias = "since")]
+    pub last_event_id: Option<u64>,
+}
+
+/// Broadcaster for one channel with the messages clients can resume from
+#[derive(Debug)]
+struct ChannelState {
+    sender: broadcast::Sender<BroadcastMessage>,
+    history: VecDeque<BroadcastMessage>,
+    last_id: u64,
+}
+
+impl ChannelState {
+    fn new(channel: &str) -> Self {
+        let (sender, _) = broadcast::channel(1000);
+        info!("Created new broadcast channel: {}", channel);
+        Self { sender, history: VecDeque::new(), last_id: 0 }
+    }
+}
+
+/// Device whose presence follows an authenticated connection
+#[derive(Debug, Clone)]
+struct PresenceSession {
+    device_id: String,
+}
+
+/// Prefix of channels whose subscribers can see each other
+pub const PRESENCE_PREFIX: &str = "presence:";
+
+pub fn is_presence_channel(channel: &str) -> bool {
+    channel.starts_with(PRESENCE_PREFIX)
+}
+
+/// A user subscribed to a presence channel
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PresenceMember {
+    pub user_id: String,
+    /// Details shown to other members, such as a name
+    pub info: serde_json::Value,
+    /// Open connections, one per device or tab the user joined from
+    pub connections: usize,
+}
+
+/// A presence member's details and the connections keeping them in the channel
+#[derive(Debug)]
+struct PresenceEntry {
+    info: serde_json::Value,
+    connection_ids: HashSet<String>,
+}
+
+impl PresenceEntry {
+    fn member(&self, user_id: &str) -> PresenceMember {
+        PresenceMember {
+            user_id: user_id.to_string(),
+            info: self.info.clone(),
+            connections: self.connection_ids.len(),
+        }
+    }
+}
+
+/// Live receiver for a channel, with the buffered messages a resuming client missed
+pub struct Subscription {
+    /// Messages after the client's last event id, oldest first
+    pub missed: Vec<BroadcastMessage>,
+    receiver: broadcast::Receiver<BroadcastMessage>,
+    replayed_up_to: Option<u64>,
+}
+
+impl Subscription {
+    /// Next live message, skipping any already sent from `missed`
+    pub async fn recv(&mut self) -> Result<BroadcastMessage, broadcast::error::RecvError> {
+        loop {
+            let message = self.receiver.recv().await?;
+            match (message.id, self.replayed_up_to) {
+                (Some(id), Some(replayed_up_to)) if id <= replayed_up_to => continue,
+                _ => return Ok(message),
+            }
+        }
+    }
 }
 
 impl WebSocketManager {
@@ -38,27 +132,105 @@ impl WebSocketManager {
         Self {
             channels: Arc::new(RwLock::new(HashMap::new())),
             connections: Arc::new(RwLock::new(HashMap::new())),
+            device_connections: Arc::new(RwLock::new(HashMap::new())),
+            presence_members: Arc::new(RwLock::new(HashMap::new())),
+            replay: ReplayOptions::default(),
+            redis_replay: None,
+        }
+    }
+
+    /// Create a manager with the configured replay buffer, kept in Redis with the Redis driver
+    pub fn from_config(config: &crate::config::broadcasting::BroadcastingConfig) -> Self {
+        let manager = Self::new().with_replay(ReplayOptions::from_config(config));
+
+        if config.default_driver == "redis" && manager.replay.is_enabled() {
+            manager.with_redis_replay(RedisReplayBuffer::from_config(config))
+        } else {
+            manager
         }
     }
 
+    pub fn with_replay(mut self, replay: ReplayOptions) -> Self {
+        self.replay = replay;
+        self
+    }
+
+    /// Resume from the Redis buffer, whose ids the Redis driver assigns when publishing
+    pub fn with_redis_replay(mut self, buffer: RedisReplayBuffer) -> Self {
+        self.redis_replay = Some(buffer);
+        self
+    }
+
     /// Subscribe to a channel and get a receiver
     pub async fn subscribe(&self, channel: &str) -> broadcast::Receiver<BroadcastMessage> {
         let mut channels = self.channels.write().await;
-        let sender = channels.entry(channel.to_string())
-            .or_insert_with(|| {
-                let (tx, _) = broadcast::channel(1000);
-                info!("Created new broadcast channel: {}", channel);
-                tx
-            });
+        let state = channels.entry(channel.to_string())
+            .or_insert_with(|| ChannelState::new(channel));
 
-        sender.subscribe()
+        state.sender.subscribe()
+    }
+
+    /// Subscribe to a channel, also returning the buffered messages after `last_id`
+    ///
+    /// The receiver is created before the buffer is read, so a resuming client
+    /// misses nothing, and `Subscription::recv` skips what was already replayed.
+    /// Only messages within the configured size and TTL are replayed.
+    pub async fn subscribe_since(&self, channel: &str, last_id: Option<u64>) -> Subscription {
+        let (mut missed, receiver) = {
+            let mut channels = self.channels.write().await;
+            let state = channels.entry(channel.to_string())
+                .or_insert_with(|| ChannelState::new(channel));
+
+            let missed = match last_id {
+                Some(last_id) => state.history.iter()
+                    .filter(|message| message.id.is_some_and(|id| id > last_id))
+                    .filter(|message| !self.replay.is_expired(message))
+                    .cloned()
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            (missed, state.sender.subscribe())
+        };
+
+        // The shared buffer also holds messages published before this process subscribed
+        if let (Some(buffer), Some(last_id)) = (&self.redis_replay, last_id) {
+            match buffer.since(channel, last_id).await {
+                Ok(shared) => missed = shared,
+                Err(e) => warn!("Replaying channel '{}' from local history, Redis buffer unavailable: {}", channel, e),
+            }
+        }
+
+        let replayed_up_to = missed.iter().filter_map(|message| message.id).max();
+        Subscription { missed, receiver, replayed_up_to }
     }
 
     /// Broadcast a message to a channel
-    pub async fn broadcast(&self, message: BroadcastMessage) -> Result<()> {
-        let channels = self.channels.read().await;
-        if let Some(sender) = channels.get(&message.channel) {
-            match sender.send(message.clone()) {
+    ///
+    /// Messages relayed from Redis keep the id the publisher assigned. With a
+    /// Redis buffer, ids only come from there, so local-only messages get none.
+    pub async fn broadcast(&self, mut message: BroadcastMessage) -> Result<()> {
+        let mut channels = self.channels.write().await;
+        if let Some(state) = channels.get_mut(&message.channel) {
+            match message.id {
+                Some(id) => state.last_id = state.last_id.max(id),
+                None if self.redis_replay.is_none() => {
+                    state.last_id += 1;
+                    message.id = Some(state.last_id);
+                }
+                None => {}
+            }
+
+            if self.replay.is_enabled() && message.id.is_some() {
+                state.history.push_back(message.clone());
+                while state.history.len() > self.replay.size
+                    || state.history.front().is_some_and(|oldest| self.replay.is_expired(oldest))
+                {
+                    state.history.pop_front();
+                }
+            }
+
+            match state.sender.send(message.clone()) {
                 Ok(receiver_count) => {
                     info!("Broadcasted to channel '{}' with {} receivers", message.channel, receiver_count);
                 }
@@ -97,6 +269,104 @@ impl WebSocketManager {
         connections.get(channel).map(|v| v.len()).unwrap_or(0)
     }
 
+    /// Register a connection for a device, returning its open connection count
+    pub async fn add_device_connection(&self, device_id: &str) -> usize {
+        let mut device_connections = self.device_connections.write().await;
+        let count = device_connections.entry(device_id.to_string()).or_insert(0);
+        *count += 1;
+        *count
+    }
+
+    /// Unregister a connection for a device, returning its remaining connection count
+    pub async fn remove_device_connection(&self, device_id: &str) -> usize {
+        let mut device_connections = self.device_connections.write().await;
+        let Some(count) = device_connections.get_mut(device_id) else {
+            return 0;
+        };
+
+        *count = count.saturating_sub(1);
+        let remaining = *count;
+        if remaining == 0 {
+            device_connections.remove(device_id);
+        }
+        remaining
+    }
+
+    /// Add a connection's user to a presence channel
+    ///
+    /// Other subscribers get `member_added` only for the user's first
+    /// connection; further devices just raise the member's connection count.
+    pub async fn join_presence(&self, channel: &str, connection_id: &str, user_id: &str, info: serde_json::Value) -> PresenceMember {
+        let (member, added) = {
+            let mut presence = self.presence_members.write().await;
+            let entry = presence.entry(channel.to_string())
+                .or_default()
+                .entry(user_id.to_string())
+                .or_insert_with(|| PresenceEntry { info, connection_ids: HashSet::new() });
+
+            let added = entry.connection_ids.is_empty();
+            entry.connection_ids.insert(connection_id.to_string());
+            (entry.member(user_id), added)
+        };
+
+        if added {
+            self.broadcast_presence_event(channel, "member_added", &member).await;
+        }
+        member
+    }
+
+    /// Remove a connection's user from a presence channel
+    ///
+    /// The member stays while any of their other connections is open, and
+    /// `member_removed` goes out once the last one leaves. Leaving twice is a no-op.
+    pub async fn leave_presence(&self, channel: &str, connection_id: &str, user_id: &str) {
+        let removed = {
+            let mut presence = self.presence_members.write().await;
+            let Some(members) = presence.get_mut(channel) else {
+                return;
+            };
+            let Some(entry) = members.get_mut(user_id) else {
+                return;
+            };
+            if !entry.connection_ids.remove(connection_id) || !entry.connection_ids.is_empty() {
+                return;
+            }
+
+            let member = entry.member(user_id);
+            members.remove(user_id);
+            if members.is_empty() {
+                presence.remove(channel);
+            }
+            member
+        };
+
+        self.broadcast_presence_event(channel, "member_removed", &removed).await;
+    }
+
+    /// Members of a presence channel, ordered by user id
+    pub async fn members(&self, channel: &str) -> Vec<PresenceMember> {
+        let presence = self.presence_members.read().await;
+        let mut members: Vec<PresenceMember> = presence.get(channel)
+            .map(|members| members.iter().map(|(user_id, entry)| entry.member(user_id)).collect())
+            .unwrap_or_default();
+        members.sort_by(|a, b| a.user_id.cmp(&b.user_id));
+        members
+    }
+
+    async fn broadcast_presence_event(&self, channel: &str, event: &str, member: &PresenceMember) {
+        let message = BroadcastMessage {
+            channel: channel.to_string(),
+            event: event.to_string(),
+            data: serde_json::to_value(member).unwrap_or_default(),
+            timestamp: chrono::Utc::now(),
+            id: None,
+        };
+
+        if let Err(e) = self.broadcast(message).await {
+            warn!("Failed to broadcast {} on {}: {}", event, channel, e);
+        }
+    }
+
     /// Get all active channels
     pub async fn active_channels(&self) -> Vec<String> {
         let connections = self.connections.read().await;
@@ -117,45 +387,154 @@ pub async fn websocket_handler(
     State(manager): State<Arc<WebSocketManager>>,
 ) -> Response {
     let channel = params.channel.unwrap_or_else(|| "general".to_string());
-
-    // Validate JWT token and authorize user access
-    if let Some(token) = params.auth_token {
-        match validate_websocket_token(&token, &channel).await {
-            Ok(user_info) => {
-                info!("WebSocket connection authorized for user {} in channel: {}", user_info.user_id, channel);
+    let mut presence = None;
+    let mut member = None;
+
+    match authorize_channel(&channel, params.auth_token.as_deref(), params.channel_auth.as_deref()).await {
+        Ok(Some(user_info)) => {
+            if is_presence_channel(&channel) {
+                member = Some(ChannelMember {
+                    user_id: user_info.user_id.clone(),
+                    info: serde_json::json!({ "email": user_info.email }),
+                });
             }
-            Err(e) => {
-                warn!("WebSocket connection denied: {}", e);
-                return ws.on_upgrade(move |socket| handle_unauthorized_socket(socket));
+            if let Some(device_id) = params.device_id {
+                presence = presence_session(&user_info, device_id).await;
             }
         }
-    } else {
-        // Check if channel requires authentication
-        if requires_authentication(&channel) {
-            warn!("WebSocket connection denied: authentication required for channel {}", channel);
+        Ok(None) if is_presence_channel(&channel) => {
+            warn!("WebSocket connection denied: presence channel {} needs a user token", channel);
             return ws.on_upgrade(move |socket| handle_unauthorized_socket(socket));
         }
+        Ok(None) => {}
+        Err(e) => {
+            warn!("WebSocket connection denied: {}", e);
+            return ws.on_upgrade(move |socket| handle_unauthorized_socket(socket));
+        }
+    }
+
+    let last_event_id = params.last_event_id;
+    ws.on_upgrade(move |socket| handle_socket(socket, channel, manager, presence, member, last_event_id))
+}
+
+/// User a connection joins a presence channel as
+#[derive(Debug, Clone)]
+struct ChannelMember {
+    user_id: String,
+    info: serde_json::Value,
+}
+
+/// Authorize a subscription with a JWT or an allow token from `/broadcasting/auth`
+///
+/// Public channels need neither. Returns the user when a JWT identified one,
+/// or when an allow token opens a presence channel; shared by the WebSocket
+/// and SSE endpoints.
+pub(super) async fn authorize_channel(
+    channel: &str,
+    auth_token: Option<&str>,
+    channel_auth: Option<&str>,
+) -> Result<Option<WebSocketUserInfo>> {
+    if let Some(token) = auth_token {
+        let user_info = validate_websocket_token(token, channel).await?;
+        info!("Connection authorized for user {} in channel: {}", user_info.user_id, channel);
+        return Ok(Some(user_info));
+    }
+
+    if let Some(channel_auth) = channel_auth {
+        let authorized = crate::app::services::broadcast_auth_service::BroadcastAuthService::new().ok()
+            .and_then(|service| service.verify_native_token(channel, channel_auth));
+
+        let Some(user_id) = authorized else {
+            return Err(anyhow::anyhow!("invalid channel token for channel {}", channel));
+        };
+        info!("Connection authorized by channel token for user {} in channel: {}", user_id, channel);
+
+        // Presence channels list their members, so they need the user the token was issued to
+        if is_presence_channel(channel) {
+            let pool = get_connection().await?;
+            return Ok(Some(channel_user(pool, &user_id)?));
+        }
+        return Ok(None);
+    }
+
+    if requires_authentication(channel) {
+        return Err(anyhow::anyhow!("authentication required for channel {}", channel));
+    }
+
+    Ok(None)
+}
+
+/// Track presence only for devices that belong to the authenticated user
+async fn presence_session(user_info: &WebSocketUserInfo, device_id: String) -> Option<PresenceSession> {
+    let pool = get_connection().await.ok()?;
+
+    match crate::app::services::conversation_service::ConversationService::ensure_user_device(pool, &device_id, &user_info.user_id) {
+        Ok(_) => Some(PresenceSession { device_id }),
+        Err(e) => {
+            warn!("Not tracking presence for device {}: {}", device_id, e);
+            None
+        }
     }
+}
 
-    ws.on_upgrade(move |socket| handle_socket(socket, channel, manager))
+/// Update device presence from the connection lifecycle
+async fn update_presence(device_id: &str, event: PresenceEvent) {
+    use crate::app::services::device_presence_service::DevicePresenceService;
+
+    let result = async {
+        let pool = get_connection().await?;
+        let service = DevicePresenceService::new()?;
+        match event {
+            PresenceEvent::Connected => service.connect(pool, device_id).await,
+            PresenceEvent::Heartbeat(status) => service.heartbeat(pool, device_id, status).await,
+            PresenceEvent::Disconnected => service.disconnect(pool, device_id).await,
+        }
+    }.await;
+
+    if let Err(e) = result {
+        warn!("Failed to update presence for device {}: {}", device_id, e);
+    }
+}
+
+enum PresenceEvent {
+    Connected,
+    Heartbeat(crate::app::models::device_presence::PresenceStatus),
+    Disconnected,
 }
 
 /// Handle individual WebSocket connection
-async fn handle_socket(socket: WebSocket, channel: String, manager: Arc<WebSocketManager>) {
+async fn handle_socket(
+    socket: WebSocket,
+    channel: String,
+    manager: Arc<WebSocketManager>,
+    presence: Option<PresenceSession>,
+    member: Option<ChannelMember>,
+    last_event_id: Option<u64>,
+) {
     let connection_id = ulid::Ulid::new().to_string();
     info!("New WebSocket connection {} for channel: {}", connection_id, channel);
 
     // Add connection to manager
     manager.add_connection(&channel, connection_id.clone()).await;
 
-    // Subscribe to channel broadcasts
-    let mut receiver = manager.subscribe(&channel).await;
+    // Joining before subscribing sends member_added to the others but not to this connection
+    if let Some(member) = &member {
+        manager.join_presence(&channel, &connection_id, &member.user_id, member.info.clone()).await;
+    }
+
+    if let Some(session) = &presence {
+        manager.add_device_connection(&session.device_id).await;
+        update_presence(&session.device_id, PresenceEvent::Connected).await;
+    }
+
+    // Subscribe to channel broadcasts, catching up on what a reconnecting client missed
+    let mut subscription = manager.subscribe_since(&channel, last_event_id).await;
 
     // Split the socket into sender and receiver
     let (mut sender, mut receiver_ws) = socket.split();
 
     // Send welcome message
-    let welcome_msg = BroadcastMessage {
+    let mut welcome_msg = BroadcastMessage {
         channel: channel.clone(),
         event: "connected".to_string(),
         data: serde_json::json!({
@@ -163,16 +542,28 @@ async fn handle_socket(socket: WebSocket, channel: String, manager: Arc<WebSocke
             "message": "Connected to channel successfully"
         }),
         timestamp: chrono::Utc::now(),
+        id: None,
     };
+    // Presence channels start the client off with who is already here
+    if member.is_some() {
+        welcome_msg.data["members"] = serde_json::to_value(manager.members(&channel).await).unwrap_or_default();
+    }
 
     if let Ok(welcome_json) = serde_json::to_string(&welcome_msg) {
         let _ = sender.send(Message::Text(welcome_json.into())).await;
     }
 
+    for missed in std::mem::take(&mut subscription.missed) {
+        if let Ok(json) = serde_json::to_string(&missed) {
+            let _ = sender.send(Message::Text(json.into())).await;
+        }
+    }
+
     // Handle incoming messages from client
     let manager_clone = manager.clone();
     let channel_clone = channel.clone();
     let connection_id_clone = connection_id.clone();
+    let presence_clone = presence.clone();
 
     let receive_task = tokio::spawn(async move {
         while let Some(msg) = receiver_ws.next().await {
@@ -182,6 +573,16 @@ async fn handle_socket(socket: WebSocket, channel: String, manager: Arc<WebSocke
 
                     // Handle client messages (e.g., join different channels, send messages)
                     if let Ok(client_msg) = serde_json::from_str::<ClientMessage>(&text) {
+                        // Pings double as presence heartbeats, optionally reporting {"status": "away"}
+                        if let (Some(session), "ping") = (&presence_clone, client_msg.action.as_str()) {
+                            let status = client_msg.data.as_ref()
+                                .and_then(|data| data.get("status"))
+                                .and_then(|status| status.as_str())
+                                .map(|status| status.to_string().into())
+                                .unwrap_or(crate::app::models::device_presence::PresenceStatus::Online);
+                            update_presence(&session.device_id, PresenceEvent::Heartbeat(status)).await;
+                        }
+
                         handle_client_message(client_msg, &manager_clone, &channel_clone).await;
                     }
                 }
@@ -204,7 +605,7 @@ async fn handle_socket(socket: WebSocket, channel: String, manager: Arc<WebSocke
     // Handle outgoing broadcasts to client
     let connection_id_clone = connection_id.clone();
     let send_task = tokio::spawn(async move {
-        while let Ok(broadcast_msg) = receiver.recv().await {
+        while let Ok(broadcast_msg) = subscription.recv().await {
             if let Ok(json) = serde_json::to_string(&broadcast_msg) {
                 if sender.send(Message::Text(json.into())).await.is_err() {
                     error!("Failed to send message to connection {}", connection_id_clone);
@@ -222,6 +623,16 @@ async fn handle_socket(socket: WebSocket, channel: String, manager: Arc<WebSocke
 
     // Clean up connection
     manager.remove_connection(&channel, &connection_id).await;
+    if let Some(member) = &member {
+        manager.leave_presence(&channel, &connection_id, &member.user_id).await;
+    }
+
+    // A device stays online while any of its connections is open
+    if let Some(session) = &presence {
+        if manager.remove_device_connection(&session.device_id).await == 0 {
+            update_presence(&session.device_id, PresenceEvent::Disconnected).await;
+        }
+    }
     info!("WebSocket connection {} disconnected from channel: {}", connection_id, channel);
 }
 
@@ -251,6 +662,7 @@ async fn handle_client_message(
                     "echo_data": msg.data
                 }),
                 timestamp: chrono::Utc::now(),
+                id: None,
             };
             let _ = manager.broadcast(pong_msg).await;
         }
@@ -268,6 +680,7 @@ async fn handle_client_message(
                 event: "stats".to_string(),
                 data: stats,
                 timestamp: chrono::Utc::now(),
+                id: None,
             };
             let _ = manager.broadcast(stats_msg).await;
         }
@@ -302,21 +715,50 @@ async fn websocket_handler_with_channel(
         info!("WebSocket connection with auth token for channel: {}", final_channel);
     }
 
-    ws.on_upgrade(move |socket| handle_socket(socket, final_channel, manager))
+    let last_event_id = params.last_event_id;
+    ws.on_upgrade(move |socket| handle_socket(socket, final_channel, manager, None, None, last_event_id))
 }
 
 /// Create a complete WebSocket server
 pub async fn create_websocket_server(port: u16) -> Result<()> {
-    let manager = Arc::new(WebSocketManager::new());
+    let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", port)).await?;
+
+    info!("WebSocket server starting on port {}", port);
+
+    serve_websocket(listener, crate::config::Config::load()?.broadcasting).await
+}
+
+/// Serve the standalone WebSocket server on an already bound listener.
+///
+/// The standalone server runs in its own process, so it only sees broadcasts
+/// published by the API when both use the Redis driver (`BROADCAST_DRIVER=redis`).
+pub async fn serve_websocket(
+    listener: tokio::net::TcpListener,
+    config: crate::config::broadcasting::BroadcastingConfig,
+) -> Result<()> {
+    let manager = Arc::new(WebSocketManager::from_config(&config));
+
+    match (get_connection().await, crate::app::services::device_presence_service::DevicePresenceService::new()) {
+        (Ok(pool), Ok(presence)) => {
+            presence.spawn(pool.clone());
+        }
+        (Err(e), _) | (_, Err(e)) => warn!("Device presence sweep not started: {}", e),
+    }
+
+    if config.default_driver == "redis" {
+        super::redis_subscriber::RedisSubscriber::from_config(&config, manager.clone()).spawn();
+    } else {
+        warn!(
+            "BROADCAST_DRIVER is '{}'; broadcasts from other processes will not reach this server (use BROADCAST_DRIVER=redis)",
+            config.default_driver
+        );
+    }
 
     let app = websocket_routes()
+        .nest("/sse", super::sse::sse_routes())
         .with_state(manager.clone())
         .route("/health", get(|| async { "WebSocket server is running" }));
 
-    let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", port)).await?;
-
-    info!("WebSocket server starting on port {}", port);
-
     axum::serve(listener, app).await?;
 
     Ok(())
@@ -328,7 +770,12 @@ static WS_MANAGER: tokio::sync::OnceCell<Arc<WebSocketManager>> = tokio::sync::O
 /// Get the global WebSocket manager
 pub async fn websocket_manager() -> Arc<WebSocketManager> {
     WS_MANAGER.get_or_init(|| async {
-        Arc::new(WebSocketManager::new())
+        // This process broadcasts to it directly rather than relaying from Redis,
+        // so it numbers its own messages and keeps its history locally
+        let replay = crate::config::broadcasting::BroadcastingConfig::from_env()
+            .map(|config| ReplayOptions::from_config(&config))
+            .unwrap_or_default();
+        Arc::new(WebSocketManager::new().with_replay(replay))
     }).await.clone()
 }
 
@@ -373,112 +820,17 @@ async fn validate_websocket_token(token: &str, channel: &str) -> Result<WebSocke
         return Err(anyhow::anyhow!("Token has expired"));
     }
 
-    // Lookup user with roles and permissions
     let pool = get_connection().await?;
-    let user_id = claims.sub.clone();
-
-    // Get user data with roles and permissions using UserService
-    let user_data = crate::app::services::user_service::UserService::find_by_id_with_organizations_and_user_roles(
-        &pool,
-        user_id.clone()
-    ).map_err(|e| anyhow::anyhow!("Failed to lookup user: {}", e))?;
-
-    let (user_resource, user_orgs) = match user_data {
-        Some((user, orgs)) => (user, orgs),
-        None => return Err(anyhow::anyhow!("User not found: {}", user_id)),
-    };
-
-    // Extract user-level roles
-    let roles: Vec<String> = user_resource.roles.iter()
-        .map(|role| role.name.clone())
-        .collect();
-
-    // Extract user-level and organization-level permissions
-    let mut permissions: Vec<String> = user_resource.permissions.iter()
-        .map(|perm| perm.name.clone())
-        .collect();
-
-    // Add organization-level permissions
-    for org in &user_orgs {
-        for perm in &org.permissions {
-            if !permissions.contains(&perm.name) {
-                permissions.push(perm.name.clone());
-            }
-        }
-    }
-
-    // Check channel-specific permissions
-    let user_info = WebSocketUserInfo {
-        user_id: user_id,
-        email: user_resource.email.clone(),
-        roles: roles,
-        permissions: permissions,
-    };
+    let user_info = channel_user(pool, &claims.sub)?;
 
     // Check if user can access the specific channel
-    if !can_access_channel(&user_info, channel).await {
+    if !can_access_channel(pool, &user_info, channel) {
         return Err(anyhow::anyhow!("Insufficient permissions to access channel: {}", channel));
     }
 
     Ok(user_info)
 }
 
-/// Check if a channel requires authentication
-fn requires_authentication(channel: &str) -> bool {
-    match channel {
-        "general" | "public" => false,
-        "notifications" | "user" | "admin" | "private" => true,
-        _ => {
-            // Channels starting with "user." require authentication
-            if channel.starts_with("user.") {
-                return true;
-            }
-            // Channels starting with "admin." require authentication
-            if channel.starts_with("admin.") {
-                return true;
-            }
-            // Default to requiring authentication for unknown channels
-            true
-        }
-    }
-}
-
-/// Check if user can access a specific channel
-async fn can_access_channel(user_info: &WebSocketUserInfo, channel: &str) -> bool {
-    match channel {
-        "general" | "public" => true,
-        "notifications" => true, // All authenticated users can access notifications
-        "admin" => user_info.roles.contains(&"admin".to_string()),
-        _ => {
-            // User-specific channels (e.g., "user.123")
-            if let Some(user_id) = channel.strip_prefix("user.") {
-                return user_info.user_id == user_id;
-            }
-
-            // Admin channels
-            if channel.starts_with("admin.") {
-                return user_info.roles.contains(&"admin".to_string()) ||
-                       user_info.permissions.contains(&"admin_channels".to_string());
-            }
-
-            // Team channels (e.g., "team.456")
-            if let Some(team_id) = channel.strip_prefix("team.") {
-                // Check if user belongs to the team
-                return user_has_team_access(&user_info.user_id, team_id).await;
-            }
-
-            // Organization channels (e.g., "org.789")
-            if let Some(org_id) = channel.strip_prefix("org.") {
-                // Check if user belongs to the organization
-                return user_has_org_access(&user_info.user_id, org_id).await;
-            }
-
-            // Default: deny access to unknown channel patterns
-            false
-        }
-    }
-}
-
 /// Handle unauthorized WebSocket connections
 async fn handle_unauthorized_socket(socket: WebSocket) {
     let (mut sender, _) = socket.split();
@@ -496,75 +848,3 @@ async fn handle_unauthorized_socket(socket: WebSocket) {
     // Close the connection
     let _ = sender.close().await;
 }
-
-/// Check if user has access to a specific team
-async fn user_has_team_access(user_id: &str, team_id: &str) -> bool {
-    match get_connection().await {
-        Ok(pool) => {
-            match crate::app::services::user_service::UserService::find_by_id_with_organizations_and_user_roles(
-                &pool,
-                user_id.to_string()
-            ) {
-                Ok(Some((_, user_orgs))) => {
-                    // Check if user belongs to any organization that has this team
-                    for org in user_orgs {
-                        if let Some(ref organization) = org.organization {
-                            if organization.name.to_lowercase().contains("team") &&
-                               organization.id == team_id {
-                                return true;
-                            }
-                        }
-                    }
-                    false
-                },
-                Ok(None) => {
-                    tracing::warn!("User {} not found during team access check", user_id);
-                    false
-                },
-                Err(e) => {
-                    tracing::error!("Failed to check team access for user {}: {}", user_id, e);
-                    false
-                }
-            }
-        },
-        Err(e) => {
-            tracing::error!("Failed to get database connection: {}", e);
-            false
-        }
-    }
-}
-
-/// Check if user has access to a specific organization
-async fn user_has_org_access(user_id: &str, org_id: &str) -> bool {
-    match get_connection().await {
-        Ok(pool) => {
-            match crate::app::services::user_service::UserService::find_by_id_with_organizations_and_user_roles(
-                &pool,
-                user_id.to_string()
-            ) {
-                Ok(Some((_, user_orgs))) => {
-                    // Check if user belongs to the specified organization
-                    user_orgs.iter().any(|org| {
-                        if let Some(ref organization) = org.organization {
-                            organization.id == org_id
-                        } else {
-                            false
-                        }
-                    })
-                },
-                Ok(None) => {
-                    tracing::warn!("User {} not found during organization access check", user_id);
-                    false
-                },
-                Err(e) => {
-                    tracing::error!("Failed to check organization access for user {}: {}", user_id, e);
-                    false
-                }
-            }
-        },
-        Err(e) => {
-            tracing::error!("Failed to get database connection: {}", e);
-            false
-        }
-    }
-}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::time::Duration;
+
+use crate::app::models::country::Country;
+use crate::app::query_builder::{QueryCache, QueryParams};
+use crate::cache::manager::CacheDriver;
+use crate::cache::CacheWarmer;
+use crate::config::Config;
+use crate::database::DbPool;
+
+/// Primes the first page of the country index, as served by `QueryCache`
+pub struct CountriesWarmer {
+    pool: DbPool,
+}
+
+impl CountriesWarmer {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CacheWarmer for CountriesWarmer {
+    fn name(&self) -> &str {
+        "countries"
+    }
+
+    async fn warm(&self, cache: &CacheDriver) -> Result<()> {
+        let ttl = Duration::from_secs(Config::load()?.cache.query_ttl_secs);
+        QueryCache::with_cache(cache.clone(), ttl)
+            .index::<Country>(QueryParams::default(), &self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+pub mod countries_warmer;
+
+pub use countries_warmer::CountriesWarmer;
+
+use std::time::Duration;
+
+use crate::cache::CacheWarmerRegistry;
+use crate::config::cache::CacheConfig;
+use crate::database::DbPool;
+
+/// Warmers run at boot and by `cache:warm`
+pub fn registry(pool: &DbPool, config: &CacheConfig) -> CacheWarmerRegistry {
+    let mut registry = CacheWarmerRegistry::new(Duration::from_secs(config.warm_timeout_secs));
+    registry.register(CountriesWarmer::new(pool.clone()));
+    registry
+}
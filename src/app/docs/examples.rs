@@ -0,0 +1,83 @@
+//! OpenAPI examples generated from model factories
+//!
+//! Schemas of models with a `Factory` get a schema-level example built from
+//! the factory's definition. Hand-written examples are overrides: a schema
+//! with its own example is left alone, and field examples replace the
+//! generated value for that field.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::borrow::Cow;
+use utoipa::openapi::schema::{Object, Schema};
+use utoipa::openapi::{OpenApi, RefOr};
+use utoipa::{Modify, ToSchema};
+
+use crate::app::http::requests::auth_requests::RegisterRequest;
+use crate::app::http::requests::country_requests::{CreateCountryRequest, UpdateCountryRequest};
+use crate::app::models::country::{Country, CountryResponse, CreateCountry};
+use crate::app::models::user::UserResponse;
+use crate::config::app::AppConfig;
+use crate::database::factories::Factory;
+
+/// Adds factory examples to the component schemas when `DOCS_FACTORY_EXAMPLES` is on
+pub struct FactoryExamples;
+
+impl FactoryExamples {
+    /// Schema name and generated example for every factory-backed model
+    pub fn examples() -> Vec<(Cow<'static, str>, Value)> {
+        vec![
+            entry::<Country>(),
+            entry::<CountryResponse>(),
+            entry::<CreateCountry>(),
+            entry::<CreateCountryRequest>(),
+            entry::<UpdateCountryRequest>(),
+            entry::<UserResponse>(),
+            entry::<RegisterRequest>(),
+        ]
+    }
+
+    /// Set the example of a schema unless it already has a hand-written one
+    pub fn apply(object: &mut Object, mut example: Value) {
+        if manual_example(object).is_some() {
+            return;
+        }
+
+        if let Value::Object(fields) = &mut example {
+            for (property, schema) in &object.properties {
+                if let Some(manual) = manual_example(schema) {
+                    fields.insert(property.clone(), manual);
+                }
+            }
+        }
+
+        object.examples = vec![example];
+    }
+}
+
+impl Modify for FactoryExamples {
+    fn modify(&self, openapi: &mut OpenApi) {
+        let enabled = AppConfig::from_env()
+            .map(|config| config.docs_factory_examples)
+            .unwrap_or(true);
+        let Some(components) = openapi.components.as_mut().filter(|_| enabled) else {
+            return;
+        };
+
+        for (name, example) in Self::examples() {
+            if let Some(RefOr::T(Schema::Object(object))) = components.schemas.get_mut(name.as_ref()) {
+                Self::apply(object, example);
+            }
+        }
+    }
+}
+
+fn entry<T: Factory + ToSchema>() -> (Cow<'static, str>, Value) {
+    (T::name(), T::example())
+}
+
+/// Example written on a schema or field, in either OpenAPI spelling
+fn manual_example(schema: &impl Serialize) -> Option<Value> {
+    let schema = serde_json::to_value(schema).ok()?;
+    schema.get("example").cloned()
+        .or_else(|| schema.get("examples")?.as_array()?.first().cloned())
+}
@@ -1,6 +1,7 @@
 use utoipa::OpenApi;
 use utoipa_auto_discovery::utoipa_auto_discovery;
 
+pub mod examples;
 pub mod oauth;
 
 // Import only basic models to prevent circular dependencies
@@ -30,7 +31,7 @@ use crate::app::query_builder::{Pagination, PaginationResult, PaginationType};
 use crate::app::query_builder::pagination::{PaginationInfo, CursorData};
 
 // Auth controller models
-use crate::app::http::controllers::auth_controller::MfaLoginRequest;
+use crate::app::http::controllers::auth_controller::{MfaLoginRequest, ConfirmDeviceRequest};
 
 // Role and permission models - now enabled with ToSchema implemented
 use crate::app::models::role::{Role, CreateRole, UpdateRole, RoleResponse};
@@ -78,6 +79,15 @@ use crate::app::http::requests::notification_request::{CreateNotificationRequest
              (crate::app::http::controllers::activity_log_controller => ./src/app/http/controllers/activity_log_controller.rs);
              (crate::app::http::controllers::notification_controller => ./src/app/http/controllers/notification_controller.rs);
              (crate::app::http::controllers::message_controller => ./src/app/http/controllers/message_controller.rs);
+             (crate::app::http::controllers::conversation_controller => ./src/app/http/controllers/conversation_controller.rs);
+             (crate::app::http::controllers::poll_controller => ./src/app/http/controllers/poll_controller.rs);
+             (crate::app::http::controllers::presence_controller => ./src/app/http/controllers/presence_controller.rs);
+             (crate::app::http::controllers::prekey_controller => ./src/app/http/controllers/prekey_controller.rs);
+             (crate::app::http::controllers::security_incident_controller => ./src/app/http/controllers/security_incident_controller.rs);
+             (crate::app::http::controllers::session_backup_controller => ./src/app/http/controllers/session_backup_controller.rs);
+             (crate::app::http::controllers::broadcasting_controller => ./src/app/http/controllers/broadcasting_controller.rs);
+             (crate::app::http::controllers::log_level_controller => ./src/app/http/controllers/log_level_controller.rs);
+             (crate::app::http::controllers::impersonation_controller => ./src/app/http/controllers/impersonation_controller.rs);
              (crate::app::http::controllers::session_model_controller => ./src/app/http/controllers/session_model_controller.rs);
              (crate::app::http::controllers::oauth::oauth_controller => ./src/app/http/controllers/oauth/oauth_controller.rs);
              (crate::app::http::controllers::oauth::client_controller => ./src/app/http/controllers/oauth/client_controller.rs);
@@ -103,6 +113,7 @@ use crate::app::http::requests::notification_request::{CreateNotificationRequest
             url = "https://opensource.org/licenses/MIT"
         )
     ),
+    modifiers(&examples::FactoryExamples),
     servers(
         (url = "http://localhost:3000", description = "Development server"),
         (url = "https://api.rustaxum.dev", description = "Production server")
@@ -127,7 +138,7 @@ use crate::app::http::requests::notification_request::{CreateNotificationRequest
 
             // Authentication requests
             RegisterRequest, LoginRequest, ForgotPasswordRequest, ResetPasswordRequest, ChangePasswordRequest,
-            MfaLoginRequest,
+            MfaLoginRequest, ConfirmDeviceRequest,
 
             // Organization models - domain, type, and organization
             Organization, CreateOrganization, UpdateOrganization, OrganizationResponse,
@@ -205,6 +216,14 @@ use crate::app::http::requests::notification_request::{CreateNotificationRequest
         (name = "Model Permissions", description = "Polymorphic model permission assignments - assign permissions to any model type"),
         (name = "Model Roles", description = "Polymorphic model role assignments - assign roles to any model type"),
         (name = "Notifications", description = "Multi-channel notification system with priority-based delivery, read status tracking, retry logic, and scheduled notifications. Supports email, SMS, push, database, and webhook channels"),
+        (name = "Conversations", description = "Conversation participation, pinned messages, and real-time activity such as typing indicators"),
+        (name = "Polls", description = "Encrypted conversation polls with single or multiple choice voting and aggregated results"),
+        (name = "Presence", description = "Device presence tracked from WebSocket connections and heartbeats"),
+        (name = "Encryption Keys", description = "Signal protocol identity keys, signed prekeys, and one-time prekey bundle distribution"),
+        (name = "Security Incidents", description = "Recorded security incidents and administrator alerting"),
+        (name = "Session Backups", description = "Encrypted session backups that a user's new device can restore after proving it holds the backup key"),
+        (name = "Broadcasting", description = "Channel authorization for private and presence broadcast channels"),
+        (name = "Logging", description = "Runtime log filter overrides for live debugging"),
         (name = "Messages", description = "Secure messaging system with end-to-end encryption, conversation threading, message editing, forwarding, mentions, reactions, and ephemeral messages"),
         (name = "Session Models", description = "Database session management with user activity tracking, IP-based filtering, device fingerprinting, and security auditing for user sessions"),
         (name = "OAuth Core", description = "OAuth2 authentication and authorization core endpoints"),
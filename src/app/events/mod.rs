@@ -1,10 +1,14 @@
 pub mod user_registered_event;
+pub mod queued_listener;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use std::sync::Arc;
 use std::collections::HashMap;
 use tokio::sync::RwLock;
+use tracing::Instrument;
+
+use crate::app::http::middleware::correlation_middleware::current_correlation_id;
 
 /// Trait for event dispatching (Laravel's Dispatchable)
 pub trait Dispatchable {
@@ -252,6 +256,11 @@ pub trait EventListener: Send + Sync {
     /// Handle the event
     async fn handle(&self, event: Arc<dyn Event>) -> Result<()>;
 
+    /// Name the listener is known by on the queue, its type name by default
+    fn listener_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
     /// Handle the event failure (Laravel's failed method)
     async fn failed(&self, _event: Arc<dyn Event>, _exception: &anyhow::Error) -> Result<()> {
         // Default implementation does nothing
@@ -263,6 +272,14 @@ pub trait EventListener: Send + Sync {
         false
     }
 
+    /// The listener's queue routing and retry settings, if it implements `ShouldQueueListener`
+    ///
+    /// Return `Some(self)` there, or the listener is queued on `queue_name`
+    /// with `backoff` and none of its connection, delay or tries.
+    fn as_should_queue(&self) -> Option<&dyn ShouldQueueListener> {
+        None
+    }
+
     /// Get the queue name for this listener (if queued)
     fn queue_name(&self) -> Option<&str> {
         None
@@ -317,6 +334,7 @@ pub trait EventListener: Send + Sync {
 pub struct EventDispatcher {
     listeners: RwLock<HashMap<String, Vec<Arc<dyn EventListener>>>>,
     wildcard_listeners: RwLock<Vec<Arc<dyn EventListener>>>,
+    pattern_listeners: RwLock<Vec<(String, Arc<dyn EventListener>)>>,
     fake_events: RwLock<bool>,
     faked_events: RwLock<Vec<(String, serde_json::Value)>>,
     queueable_handler: RwLock<Option<Arc<dyn QueueableHandler>>>,
@@ -337,6 +355,7 @@ impl EventDispatcher {
         Self {
             listeners: RwLock::new(HashMap::new()),
             wildcard_listeners: RwLock::new(Vec::new()),
+            pattern_listeners: RwLock::new(Vec::new()),
             fake_events: RwLock::new(false),
             faked_events: RwLock::new(Vec::new()),
             queueable_handler: RwLock::new(None),
@@ -351,6 +370,7 @@ impl EventDispatcher {
 
     /// Register a listener for a specific event
     pub async fn listen<E: Event + 'static>(&self, listener: Arc<dyn EventListener>) {
+        queued_listener::remember_queued_listener(&listener);
         let event_name = std::any::type_name::<E>().to_string();
         let mut listeners = self.listeners.write().await;
         listeners.entry(event_name).or_insert_with(Vec::new).push(listener);
@@ -358,16 +378,49 @@ impl EventDispatcher {
 
     /// Register a listener for a specific event by name
     pub async fn listen_for(&self, event_name: String, listener: Arc<dyn EventListener>) {
+        queued_listener::remember_queued_listener(&listener);
         let mut listeners = self.listeners.write().await;
         listeners.entry(event_name).or_insert_with(Vec::new).push(listener);
     }
 
     /// Register a wildcard listener that receives all events
     pub async fn listen_wildcard(&self, listener: Arc<dyn EventListener>) {
+        queued_listener::remember_queued_listener(&listener);
         let mut wildcard_listeners = self.wildcard_listeners.write().await;
         wildcard_listeners.push(listener);
     }
 
+    /// Register a listener for every event whose name matches `pattern`, e.g. `billing.*`
+    pub async fn listen_pattern(&self, pattern: &str, listener: Arc<dyn EventListener>) {
+        queued_listener::remember_queued_listener(&listener);
+        let mut pattern_listeners = self.pattern_listeners.write().await;
+        pattern_listeners.push((pattern.to_string(), listener));
+    }
+
+    /// Whether an event name matches a listener pattern, `*` matching any run of characters
+    pub fn matches_pattern(pattern: &str, event_name: &str) -> bool {
+        let mut parts = pattern.split('*');
+        let first = parts.next().unwrap_or_default();
+        let Some(mut rest) = event_name.strip_prefix(first) else {
+            return false;
+        };
+
+        let parts: Vec<&str> = parts.collect();
+        let Some((last, middle)) = parts.split_last() else {
+            // No `*` in the pattern, so the name must match exactly
+            return rest.is_empty();
+        };
+
+        for part in middle {
+            match rest.find(part) {
+                Some(index) => rest = &rest[index + part.len()..],
+                None => return false,
+            }
+        }
+
+        rest.ends_with(last)
+    }
+
     /// Fire an event and notify all registered listeners
     pub async fn dispatch(&self, event: Arc<dyn Event>) -> Result<()> {
         // Check if events are being faked
@@ -404,6 +457,24 @@ impl EventDispatcher {
             }
         }
 
+        // Handle listeners whose pattern matches the event name
+        let pattern_listeners = self.pattern_listeners.read().await;
+        let matching = pattern_listeners
+            .iter()
+            .filter(|(pattern, _)| Self::matches_pattern(pattern, event.event_name()));
+        for (pattern, listener) in matching {
+            if let Err(e) = self.handle_listener(listener.clone(), event.clone()).await {
+                if let Err(failed_error) = listener.failed(event.clone(), &e).await {
+                    tracing::error!("Pattern listener failed method also failed: {}", failed_error);
+                }
+
+                if listener.halt_on_failure() {
+                    return Err(e);
+                }
+                tracing::error!("Event listener for '{}' failed: {}", pattern, e);
+            }
+        }
+
         // Handle wildcard listeners
         for listener in wildcard_listeners.iter() {
             if let Err(e) = self.handle_listener(listener.clone(), event.clone()).await {
@@ -459,17 +530,31 @@ impl EventDispatcher {
     }
 
     /// Handle a single listener
+    ///
+    /// Runs under an `event_listener` span carrying the event name and the
+    /// correlation ID of the request that fired it, so the listener's logs
+    /// can be traced back to that request.
     async fn handle_listener(&self, listener: Arc<dyn EventListener>, event: Arc<dyn Event>) -> Result<()> {
-        if listener.should_queue() {
-            // Use the queueable handler if available
-            if let Some(handler) = self.queueable_handler.read().await.as_ref() {
-                return handler.queue_listener(listener, event).await;
-            } else {
-                tracing::warn!("Listener should be queued but no queueable handler is set, handling synchronously");
+        let span = tracing::info_span!(
+            "event_listener",
+            event_name = %event.event_name(),
+            correlation_id = %current_correlation_id().map(|id| id.to_string()).unwrap_or_default(),
+        );
+
+        async move {
+            if listener.should_queue() {
+                // Use the queueable handler if available
+                if let Some(handler) = self.queueable_handler.read().await.as_ref() {
+                    return handler.queue_listener(listener, event).await;
+                } else {
+                    tracing::warn!("Listener should be queued but no queueable handler is set, handling synchronously");
+                }
             }
-        }
 
-        listener.handle(event).await
+            listener.handle(event).await
+        }
+        .instrument(span)
+        .await
     }
 
     /// Fire an event until one listener returns a non-empty result
@@ -507,8 +592,14 @@ impl EventDispatcher {
         results
     }
 
-    /// Remove all listeners for a specific event
+    /// Remove all listeners for a specific event, or for a pattern such as `billing.*`
     pub async fn forget(&self, event_name: &str) {
+        if event_name.contains('*') {
+            let mut pattern_listeners = self.pattern_listeners.write().await;
+            pattern_listeners.retain(|(pattern, _)| pattern != event_name);
+            return;
+        }
+
         let mut listeners = self.listeners.write().await;
         listeners.remove(event_name);
     }
@@ -517,8 +608,10 @@ impl EventDispatcher {
     pub async fn flush(&self) {
         let mut listeners = self.listeners.write().await;
         let mut wildcard_listeners = self.wildcard_listeners.write().await;
+        let mut pattern_listeners = self.pattern_listeners.write().await;
         listeners.clear();
         wildcard_listeners.clear();
+        pattern_listeners.clear();
     }
 
     /// Enable event faking for testing
@@ -579,8 +672,11 @@ impl EventDispatcher {
     pub async fn has_listeners(&self, event_name: &str) -> bool {
         let listeners = self.listeners.read().await;
         let wildcard_listeners = self.wildcard_listeners.read().await;
+        let pattern_listeners = self.pattern_listeners.read().await;
 
-        listeners.contains_key(event_name) || !wildcard_listeners.is_empty()
+        listeners.contains_key(event_name)
+            || !wildcard_listeners.is_empty()
+            || pattern_listeners.iter().any(|(pattern, _)| Self::matches_pattern(pattern, event_name))
     }
 }
 
@@ -625,6 +721,11 @@ pub async fn listen_wildcard(listener: Arc<dyn EventListener>) {
     event_dispatcher().await.listen_wildcard(listener).await;
 }
 
+/// Register a listener for events matching a pattern using the global dispatcher
+pub async fn listen_pattern(pattern: &str, listener: Arc<dyn EventListener>) {
+    event_dispatcher().await.listen_pattern(pattern, listener).await;
+}
+
 /// Enable event faking for testing
 pub async fn fake() {
     event_dispatcher().await.fake().await;
@@ -674,6 +775,11 @@ impl EventFacade {
         listen::<E>(listener).await;
     }
 
+    /// Register a listener for events matching a pattern (Event::listen('billing.*', ...))
+    pub async fn listen_pattern(pattern: &str, listener: Arc<dyn EventListener>) {
+        listen_pattern(pattern, listener).await;
+    }
+
     /// Dispatch an event (Event::dispatch)
     pub async fn dispatch_event(event: Arc<dyn Event>) -> Result<()> {
         dispatch(event).await
@@ -0,0 +1,108 @@
+//! Routing listeners that should queue through the job queue
+//!
+//! `EventDispatcher` hands a listener whose `should_queue()` is true to its
+//! `QueueableHandler`. `JobQueueHandler` pushes it as a `CallQueuedListenerJob`
+//! onto the listener's declared connection and queue, after its delay, and a
+//! queue worker later runs it with the listener's tries and backoff.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use tokio::sync::RwLock as AsyncRwLock;
+
+use crate::app::events::{Event, EventListener, QueueableHandler};
+use crate::app::jobs::call_queued_listener_job::CallQueuedListenerJob;
+use crate::app::jobs::JobDispatcher;
+
+static QUEUED_LISTENERS: OnceLock<RwLock<HashMap<String, Arc<dyn EventListener>>>> = OnceLock::new();
+
+fn queued_listeners() -> &'static RwLock<HashMap<String, Arc<dyn EventListener>>> {
+    QUEUED_LISTENERS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Remember a queued listener so a worker can run it by its `listener_name`
+///
+/// Called when the listener is registered, so a worker process that ran
+/// `register_listeners` can resolve it without having dispatched the event.
+pub fn remember_queued_listener(listener: &Arc<dyn EventListener>) {
+    if !listener.should_queue() {
+        return;
+    }
+
+    queued_listeners().write()
+        .expect("queued listener lock poisoned")
+        .insert(listener.listener_name().to_string(), listener.clone());
+}
+
+/// The queued listener registered under `name`
+pub fn queued_listener(name: &str) -> Option<Arc<dyn EventListener>> {
+    queued_listeners().read().expect("queued listener lock poisoned").get(name).cloned()
+}
+
+/// An event rebuilt from a queued payload, answering with the original name and data
+#[derive(Debug, Clone)]
+pub struct QueuedEvent {
+    name: &'static str,
+    payload: serde_json::Value,
+}
+
+impl QueuedEvent {
+    pub fn new(name: &str, payload: serde_json::Value) -> Self {
+        Self { name: intern(name), payload }
+    }
+}
+
+#[async_trait]
+impl Event for QueuedEvent {
+    fn event_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        self.payload.clone()
+    }
+}
+
+/// `Event::event_name` is `'static`; event names are a small fixed set, so each is leaked once
+fn intern(name: &str) -> &'static str {
+    static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+    let mut names = NAMES.get_or_init(|| Mutex::new(HashSet::new())).lock().expect("event name lock poisoned");
+    if let Some(interned) = names.get(name) {
+        return interned;
+    }
+
+    let interned: &'static str = Box::leak(name.to_string().into_boxed_str());
+    names.insert(interned);
+    interned
+}
+
+/// Queues listeners as `CallQueuedListenerJob`s on a job dispatcher
+pub struct JobQueueHandler {
+    jobs: Arc<AsyncRwLock<JobDispatcher>>,
+}
+
+impl JobQueueHandler {
+    pub fn new(jobs: Arc<AsyncRwLock<JobDispatcher>>) -> Self {
+        Self { jobs }
+    }
+}
+
+#[async_trait]
+impl QueueableHandler for JobQueueHandler {
+    async fn queue_event(&self, event: Arc<dyn Event>) -> Result<()> {
+        // Queued events run their listeners, which queue themselves as needed
+        crate::app::events::dispatch(event).await
+    }
+
+    async fn queue_listener(&self, listener: Arc<dyn EventListener>, event: Arc<dyn Event>) -> Result<()> {
+        remember_queued_listener(&listener);
+
+        let job = CallQueuedListenerJob::new(listener.as_ref(), event.as_ref());
+        let job_id = self.jobs.read().await.dispatch_on(job.connection.as_deref(), &job, job.delay()).await?;
+
+        tracing::info!("Queued listener {} for event {} as job {}", job.listener, job.event_name, job_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+//! Feature flag checks, like Laravel Pennant's `Feature` facade
+//!
+//! Flags live in the `feature_flags` table and are managed with the
+//! `feature:set` command. A flag is decided in this order: the user's
+//! override, the flag's master switch, then its rollout percentage.
+
+use crate::app::services::feature_flag_service::FeatureFlagService;
+use crate::database::DbPool;
+
+/// Who a flag is being checked for
+#[derive(Debug, Clone, Default)]
+pub struct FeatureContext {
+    pub user_id: Option<String>,
+}
+
+impl FeatureContext {
+    pub fn guest() -> Self {
+        Self::default()
+    }
+
+    pub fn for_user(user_id: impl ToString) -> Self {
+        Self { user_id: Some(user_id.to_string()) }
+    }
+}
+
+pub struct Feature;
+
+impl Feature {
+    /// Whether a feature is on; unknown flags and lookup failures count as off
+    pub async fn enabled(pool: &DbPool, name: &str, context: &FeatureContext) -> bool {
+        match Self::evaluate(pool, name, context).await {
+            Ok(enabled) => enabled,
+            Err(e) => {
+                tracing::warn!("Failed to check feature flag {}: {}", name, e);
+                false
+            },
+        }
+    }
+
+    pub async fn disabled(pool: &DbPool, name: &str, context: &FeatureContext) -> bool {
+        !Self::enabled(pool, name, context).await
+    }
+
+    async fn evaluate(pool: &DbPool, name: &str, context: &FeatureContext) -> anyhow::Result<bool> {
+        let Some(flag) = FeatureFlagService::cached(pool, name).await? else {
+            return Ok(false);
+        };
+
+        if let Some(user_id) = context.user_id.as_deref() {
+            if let Some(enabled) = FeatureFlagService::cached_override(pool, &flag, user_id).await? {
+                return Ok(enabled);
+            }
+        }
+
+        Ok(flag.is_enabled_for(context.user_id.as_deref()))
+    }
+}
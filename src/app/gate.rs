@@ -0,0 +1,252 @@
+//! Policy-based authorization, like Laravel's `Gate` facade
+//!
+//! Each model's policy is registered once, keyed by `HasModelType::model_type`,
+//! in `providers::auth_service_provider`. Controllers then check an ability
+//! with `gate().authorize(&auth_user, "update", &model).await?`, which answers
+//! 403 when the policy denies it or no policy is registered for the model.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::app::http::middleware::auth_guard::AuthUser;
+use crate::app::models::HasModelType;
+
+/// Abilities the gate dispatches, each to the `Policy` method of the same name
+pub const ABILITIES: [&str; 7] = ["view_any", "view", "create", "update", "delete", "restore", "force_delete"];
+
+/// Authorization rules for one model type
+///
+/// Every ability is denied unless the policy overrides it.
+#[async_trait]
+pub trait Policy<M: Sync>: Send + Sync {
+    /// Determine whether the user can list models
+    async fn view_any(&self, _user: &AuthUser) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Determine whether the user can view the model
+    async fn view(&self, _user: &AuthUser, _model: &M) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Determine whether the user can create models
+    async fn create(&self, _user: &AuthUser) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Determine whether the user can update the model
+    async fn update(&self, _user: &AuthUser, _model: &M) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Determine whether the user can delete the model
+    async fn delete(&self, _user: &AuthUser, _model: &M) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Determine whether the user can restore the soft-deleted model
+    async fn restore(&self, _user: &AuthUser, _model: &M) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Determine whether the user can permanently delete the model
+    async fn force_delete(&self, _user: &AuthUser, _model: &M) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthorizationError {
+    #[error("This action is unauthorized.")]
+    Denied { ability: String, model_type: &'static str },
+    #[error("Unknown ability '{0}'")]
+    UnknownAbility(String),
+    #[error(transparent)]
+    Failed(#[from] anyhow::Error),
+}
+
+impl AuthorizationError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            AuthorizationError::Denied { .. } => StatusCode::FORBIDDEN,
+            AuthorizationError::UnknownAbility(_) | AuthorizationError::Failed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn body(&self) -> Json<Value> {
+        match self {
+            AuthorizationError::Denied { .. } => Json(json!({ "error": self.to_string() })),
+            _ => Json(json!({ "error": "Authorization check failed" })),
+        }
+    }
+}
+
+impl IntoResponse for AuthorizationError {
+    fn into_response(self) -> Response {
+        if !matches!(self, AuthorizationError::Denied { .. }) {
+            tracing::error!("Authorization check failed: {}", self);
+        }
+        (self.status_code(), self.body()).into_response()
+    }
+}
+
+/// Lets `?` on `authorize` work in handlers returning `Result<_, Response>`
+impl From<AuthorizationError> for Response {
+    fn from(error: AuthorizationError) -> Self {
+        error.into_response()
+    }
+}
+
+/// Lets `?` on `authorize` work in handlers returning `Result<_, (StatusCode, Json<Value>)>`
+impl From<AuthorizationError> for (StatusCode, Json<Value>) {
+    fn from(error: AuthorizationError) -> Self {
+        if !matches!(error, AuthorizationError::Denied { .. }) {
+            tracing::error!("Authorization check failed: {}", error);
+        }
+        (error.status_code(), error.body())
+    }
+}
+
+/// Registry of policies keyed by model type
+#[derive(Clone, Default)]
+pub struct Gate {
+    /// Each value is an `Arc<dyn Policy<M>>` for the model type it is keyed by
+    policies: Arc<RwLock<HashMap<&'static str, Arc<dyn Any + Send + Sync>>>>,
+}
+
+impl Gate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the policy for model `M`, replacing any earlier one
+    pub fn policy<M, P>(&self, policy: P)
+    where
+        M: HasModelType + Sync + 'static,
+        P: Policy<M> + 'static,
+    {
+        let policy: Arc<dyn Policy<M>> = Arc::new(policy);
+        self.policies.write()
+            .expect("gate lock poisoned")
+            .insert(M::model_type(), Arc::new(policy));
+    }
+
+    pub fn has_policy<M: HasModelType>(&self) -> bool {
+        self.policies.read().expect("gate lock poisoned").contains_key(M::model_type())
+    }
+
+    fn policy_for<M: HasModelType + Sync + 'static>(&self) -> Option<Arc<dyn Policy<M>>> {
+        self.policies.read()
+            .expect("gate lock poisoned")
+            .get(M::model_type())
+            .and_then(|policy| policy.downcast_ref::<Arc<dyn Policy<M>>>())
+            .cloned()
+    }
+
+    /// Whether `user` may perform `ability` on `model`
+    pub async fn check<M>(&self, user: &AuthUser, ability: &str, model: &M) -> Result<bool, AuthorizationError>
+    where
+        M: HasModelType + Sync + 'static,
+    {
+        self.dispatch(user, ability, Some(model)).await
+    }
+
+    /// Whether `user` may perform an ability that needs no instance, `view_any` or `create`
+    pub async fn check_for<M>(&self, user: &AuthUser, ability: &str) -> Result<bool, AuthorizationError>
+    where
+        M: HasModelType + Sync + 'static,
+    {
+        self.dispatch::<M>(user, ability, None).await
+    }
+
+    /// Like `check`, but a failed check counts as denied
+    pub async fn allows<M>(&self, user: &AuthUser, ability: &str, model: &M) -> bool
+    where
+        M: HasModelType + Sync + 'static,
+    {
+        match self.check(user, ability, model).await {
+            Ok(allowed) => allowed,
+            Err(e) => {
+                tracing::warn!("Failed to check '{}' on {}: {}", ability, M::model_type(), e);
+                false
+            }
+        }
+    }
+
+    pub async fn denies<M>(&self, user: &AuthUser, ability: &str, model: &M) -> bool
+    where
+        M: HasModelType + Sync + 'static,
+    {
+        !self.allows(user, ability, model).await
+    }
+
+    /// Fail with `AuthorizationError::Denied` unless `user` may perform `ability` on `model`
+    pub async fn authorize<M>(&self, user: &AuthUser, ability: &str, model: &M) -> Result<(), AuthorizationError>
+    where
+        M: HasModelType + Sync + 'static,
+    {
+        Self::allowed_or_denied::<M>(self.check(user, ability, model).await?, ability)
+    }
+
+    /// `authorize` for `view_any` and `create`
+    pub async fn authorize_for<M>(&self, user: &AuthUser, ability: &str) -> Result<(), AuthorizationError>
+    where
+        M: HasModelType + Sync + 'static,
+    {
+        Self::allowed_or_denied::<M>(self.check_for::<M>(user, ability).await?, ability)
+    }
+
+    fn allowed_or_denied<M: HasModelType>(allowed: bool, ability: &str) -> Result<(), AuthorizationError> {
+        if allowed {
+            Ok(())
+        } else {
+            Err(AuthorizationError::Denied { ability: ability.to_string(), model_type: M::model_type() })
+        }
+    }
+
+    async fn dispatch<M>(&self, user: &AuthUser, ability: &str, model: Option<&M>) -> Result<bool, AuthorizationError>
+    where
+        M: HasModelType + Sync + 'static,
+    {
+        if !ABILITIES.contains(&ability) {
+            return Err(AuthorizationError::UnknownAbility(ability.to_string()));
+        }
+
+        // Like Laravel, a model without a policy denies everything
+        let Some(policy) = self.policy_for::<M>() else {
+            tracing::warn!("No policy registered for {}, denying '{}'", M::model_type(), ability);
+            return Ok(false);
+        };
+
+        let allowed = match (ability, model) {
+            ("view_any", _) => policy.view_any(user).await?,
+            ("create", _) => policy.create(user).await?,
+            ("view", Some(model)) => policy.view(user, model).await?,
+            ("update", Some(model)) => policy.update(user, model).await?,
+            ("delete", Some(model)) => policy.delete(user, model).await?,
+            ("restore", Some(model)) => policy.restore(user, model).await?,
+            ("force_delete", Some(model)) => policy.force_delete(user, model).await?,
+            (ability, None) => {
+                return Err(AuthorizationError::Failed(anyhow::anyhow!("Ability '{}' needs a model instance", ability)));
+            }
+            (ability, Some(_)) => return Err(AuthorizationError::UnknownAbility(ability.to_string())),
+        };
+
+        Ok(allowed)
+    }
+}
+
+static GATE: OnceLock<Gate> = OnceLock::new();
+
+/// The global gate, with policies registered by `create_app`
+pub fn gate() -> &'static Gate {
+    GATE.get_or_init(Gate::new)
+}
@@ -1,6 +1,6 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{Json, IntoResponse},
     Extension,
 };
@@ -8,10 +8,14 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use utoipa::ToSchema;
 use std::collections::HashMap;
+use std::time::Duration;
 
 use crate::app::activity_log::prelude::*;
+use crate::app::http::middleware::auth_guard::AuthUser;
 use crate::app::http::middleware::correlation_middleware::CorrelationContext;
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
+use crate::app::utils::CacheRateLimiter;
+use crate::config::activity_log::ActivityLogConfig;
 use crate::database::DbPool;
 
 /// Query parameters for activity log listing
@@ -170,6 +174,12 @@ impl From<ActivityLog> for ActivityLogResponse {
 }
 
 /// List activity logs with filtering and pagination
+///
+/// Filters on `subject_type`, `subject_id`, `causer_type`, `causer_id`,
+/// `event`, `correlation_id` and `created_at` ranges (e.g.
+/// `filter[created_at][gte]=2026-01-01T00:00:00Z`), and `include=causer,subject`
+/// attaches the related records. Each user may run `ACTIVITY_LOG_QUERY_RATE_LIMIT`
+/// queries per minute.
 #[utoipa::path(
     get,
     path = "/api/activity-logs",
@@ -177,26 +187,65 @@ impl From<ActivityLog> for ActivityLogResponse {
     responses(
         (status = 200, description = "Activity logs retrieved successfully"),
         (status = 400, description = "Invalid query parameters"),
+        (status = 401, description = "Not authenticated"),
+        (status = 429, description = "Too many queries (see Retry-After)"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Activity Logs"
 )]
 pub async fn list_activity_logs(
     State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    auth_user: AuthUser,
+    params: QueryParams,
 ) -> impl IntoResponse {
+    if let Some(retry_after) = throttle_query(&auth_user.user_id).await {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after.to_string())],
+            Json(serde_json::json!({
+                "error": format!("Too many activity log queries. Try again in {} seconds.", retry_after)
+            })),
+        ).into_response();
+    }
+
     match <ActivityLog as QueryBuilderService<ActivityLog>>::index(Query(params), &pool) {
         Ok(result) => {
             (StatusCode::OK, Json(serde_json::json!(result))).into_response()
         },
-        Err(_) => {
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
-                "error": "Failed to fetch activity logs"
-            }))).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
+/// Count a query against the user's per-minute limit, returning the seconds
+/// to wait once it is used up
+///
+/// Fails open: when the cache store is unreachable the query is allowed.
+async fn throttle_query(user_id: &str) -> Option<u64> {
+    let max_attempts = ActivityLogConfig::from_env()
+        .map(|config| config.query_rate_limit)
+        .unwrap_or(60);
+    if max_attempts <= 0 {
+        return None;
+    }
+
+    count_query(user_id, max_attempts).await.unwrap_or_else(|e| {
+        tracing::warn!("Activity log query throttling skipped, cache unavailable: {}", e);
+        None
+    })
+}
+
+async fn count_query(user_id: &str, max_attempts: i64) -> anyhow::Result<Option<u64>> {
+    let limiter = CacheRateLimiter::shared().await?;
+    let key = format!("activity_log_query:{}", user_id);
+
+    if limiter.too_many_attempts(&key, max_attempts).await? {
+        return Ok(Some(limiter.available_in(&key).await?.max(1)));
+    }
+
+    limiter.hit(&key, Duration::from_secs(60)).await?;
+    Ok(None)
+}
+
 /// Get a specific activity log by ID
 #[utoipa::path(
     get,
@@ -229,6 +278,9 @@ pub async fn get_activity_log(
         pagination_type: None,
         cursor: None,
         append: Default::default(),
+        format: None,
+        include_page: Default::default(),
+        include_per_page: Default::default(),
     };
 
     match <ActivityLog as QueryBuilderService<ActivityLog>>::first(Query(query_params), &pool) {
@@ -287,6 +339,9 @@ pub async fn get_activities_by_correlation(
         pagination_type: None,
         cursor: None,
         append: Default::default(),
+        format: None,
+        include_page: Default::default(),
+        include_per_page: Default::default(),
     };
 
     match <ActivityLog as QueryBuilderService<ActivityLog>>::all(Query(query_params), &pool) {
@@ -332,6 +387,9 @@ pub async fn get_activities_by_batch(
         pagination_type: None,
         cursor: None,
         append: Default::default(),
+        format: None,
+        include_page: Default::default(),
+        include_per_page: Default::default(),
     };
 
     match <ActivityLog as QueryBuilderService<ActivityLog>>::all(Query(query_params), &pool) {
@@ -450,7 +508,7 @@ pub async fn create_activity_log(
 pub async fn get_activities_by_subject(
     State(pool): State<DbPool>,
     Path((subject_type, subject_id)): Path<(String, String)>,
-    Query(mut query_params): Query<QueryParams>,
+    mut query_params: QueryParams,
 ) -> impl IntoResponse {
     // Add subject filters to existing query parameters
     query_params.filter.insert("subject_type".to_string(), serde_json::Value::String(subject_type));
@@ -465,6 +523,9 @@ pub async fn get_activities_by_subject(
         Ok(result) => {
             (StatusCode::OK, Json(result)).into_response()
         },
+        Err(e) if e.is::<QueryParamsError>() => {
+            (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e.to_string() }))).into_response()
+        }
         Err(_) => {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
                 "error": "Failed to fetch activities by subject"
@@ -490,7 +551,7 @@ pub async fn get_activities_by_subject(
 pub async fn get_activities_by_causer(
     State(pool): State<DbPool>,
     Path((causer_type, causer_id)): Path<(String, String)>,
-    Query(mut query_params): Query<QueryParams>,
+    mut query_params: QueryParams,
 ) -> impl IntoResponse {
     // Add causer filters to existing query parameters
     query_params.filter.insert("causer_type".to_string(), serde_json::Value::String(causer_type));
@@ -505,6 +566,9 @@ pub async fn get_activities_by_causer(
         Ok(result) => {
             (StatusCode::OK, Json(result)).into_response()
         },
+        Err(e) if e.is::<QueryParamsError>() => {
+            (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e.to_string() }))).into_response()
+        }
         Err(_) => {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
                 "error": "Failed to fetch activities by causer"
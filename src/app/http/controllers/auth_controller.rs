@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Json, State, Extension},
+    extract::{Json, State, Extension, Path, Query},
     http::{StatusCode, HeaderMap},
     response::{IntoResponse, Json as ResponseJson},
 };
@@ -20,7 +20,24 @@ pub struct MfaLoginRequest {
     pub user_id: String,
     pub mfa_code: String,
 }
+
+#[derive(Deserialize, ToSchema)]
+pub struct ConfirmDeviceRequest {
+    pub user_id: String,
+    /// `challenge_id` from the login response
+    pub challenge_id: String,
+    /// Emailed code, or an MFA code when MFA is enabled
+    pub code: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct VerifyEmailQuery {
+    /// Hash of the email address the link was sent to
+    pub hash: String,
+}
 use crate::app::services::auth_service::{AuthService, LoginResponse};
+use crate::app::services::login_fingerprint_service::ClientFingerprint;
+use crate::app::http::middleware::trusted_proxy_middleware::ClientInfo;
 use crate::app::utils::token_utils::TokenUtils;
 
 #[derive(Serialize, ToSchema)]
@@ -57,21 +74,31 @@ pub async fn register(State(pool): State<DbPool>, Json(payload): Json<CreateUser
     path = "/api/auth/login",
     tag = "Authentication",
     summary = "Login user",
-    description = "Authenticate user with email and password. May require MFA if enabled.",
+    description = "Authenticate user with email and password. May require MFA if enabled. Logins from a client (User-Agent, Accept headers and client hints) the user has not confirmed before return `requires_device_confirmation` with a `challenge_id`; finish them with `/api/auth/confirm-device`.",
     request_body = LoginRequest,
     responses(
-        (status = 200, description = "Login successful or MFA required"),
+        (status = 200, description = "Login successful, MFA required, or device confirmation required"),
         (status = 401, description = "Authentication failed", body = ErrorResponse)
     )
 )]
-pub async fn login(State(pool): State<DbPool>, Json(payload): Json<LoginRequest>) -> impl IntoResponse {
-    match AuthService::login(&pool, payload).await {
+pub async fn login(
+    State(pool): State<DbPool>,
+    client: Option<Extension<ClientInfo>>,
+    headers: HeaderMap,
+    Json(payload): Json<LoginRequest>,
+) -> impl IntoResponse {
+    let fingerprint = ClientFingerprint::from_headers(&headers, client.and_then(|Extension(client)| client.ip));
+
+    match AuthService::login(&pool, payload, &fingerprint).await {
         Ok(LoginResponse::Success(auth_response)) => {
             (StatusCode::OK, ResponseJson(auth_response)).into_response()
         },
         Ok(LoginResponse::MfaRequired(mfa_response)) => {
             (StatusCode::OK, ResponseJson(mfa_response)).into_response()
         },
+        Ok(LoginResponse::DeviceConfirmationRequired(confirmation_response)) => {
+            (StatusCode::OK, ResponseJson(confirmation_response)).into_response()
+        },
         Err(e) => {
             let error = ErrorResponse {
                 error: e.to_string(),
@@ -105,6 +132,68 @@ pub async fn forgot_password(State(pool): State<DbPool>, Json(payload): Json<For
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/email/verify/{id}",
+    tag = "Authentication",
+    summary = "Verify email address",
+    description = "Mark the user's email as verified from the signed link sent after registration",
+    params(
+        ("id" = String, Path, description = "User ID"),
+        ("hash" = String, Query, description = "Hash of the email address"),
+        ("expires" = i64, Query, description = "Unix timestamp after which the link is rejected"),
+        ("signature" = String, Query, description = "URL signature")
+    ),
+    responses(
+        (status = 200, description = "Email address verified"),
+        (status = 403, description = "Invalid or expired link", body = ErrorResponse)
+    )
+)]
+pub async fn verify_email(
+    State(pool): State<DbPool>,
+    Path(id): Path<String>,
+    Query(query): Query<VerifyEmailQuery>,
+) -> impl IntoResponse {
+    match AuthService::verify_email(&pool, &id, &query.hash) {
+        Ok(response) => (StatusCode::OK, ResponseJson(response)).into_response(),
+        Err(e) => {
+            let error = ErrorResponse {
+                error: e.to_string(),
+            };
+            (StatusCode::FORBIDDEN, ResponseJson(error)).into_response()
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/email/verification-notification",
+    tag = "Authentication",
+    summary = "Resend email verification",
+    description = "Send a new verification link to the authenticated user",
+    responses(
+        (status = 200, description = "Verification link sent"),
+        (status = 400, description = "Request failed", body = ErrorResponse)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn resend_email_verification(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<crate::app::http::middleware::auth_guard::AuthUser>,
+) -> impl IntoResponse {
+    match AuthService::resend_email_verification(&pool, &auth_user.user_id).await {
+        Ok(response) => (StatusCode::OK, ResponseJson(response)).into_response(),
+        Err(e) => {
+            let error = ErrorResponse {
+                error: e.to_string(),
+            };
+            (StatusCode::BAD_REQUEST, ResponseJson(error)).into_response()
+        }
+    }
+}
+
 pub async fn reset_password(State(pool): State<DbPool>, Json(payload): Json<ResetPasswordRequest>) -> impl IntoResponse {
     match AuthService::reset_password(&pool, payload) {
         Ok(response) => (StatusCode::OK, ResponseJson(response)).into_response(),
@@ -174,9 +263,13 @@ pub async fn refresh_token(State(pool): State<DbPool>, Json(payload): Json<Refre
 pub async fn login_session(
     State(pool): State<DbPool>,
     Extension(session): Extension<SessionStore>,
+    client: Option<Extension<ClientInfo>>,
+    headers: HeaderMap,
     Json(payload): Json<LoginRequest>
 ) -> impl IntoResponse {
-    match AuthService::login(&pool, payload).await {
+    let fingerprint = ClientFingerprint::from_headers(&headers, client.and_then(|Extension(client)| client.ip));
+
+    match AuthService::login(&pool, payload, &fingerprint).await {
         Ok(LoginResponse::Success(response)) => {
             // Store user ID in session instead of returning JWT
             session.put("user_id", Value::String(response.user.id.to_string())).await;
@@ -205,6 +298,9 @@ pub async fn login_session(
             });
             (StatusCode::OK, ResponseJson(mfa_session_response)).into_response()
         }
+        Ok(LoginResponse::DeviceConfirmationRequired(confirmation_response)) => {
+            (StatusCode::OK, ResponseJson(confirmation_response)).into_response()
+        }
         Err(e) => {
             let error = ErrorResponse {
                 error: e.to_string(),
@@ -305,6 +401,33 @@ pub async fn complete_mfa_login(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/confirm-device",
+    tag = "Authentication",
+    summary = "Confirm a login from a new device",
+    description = "Complete a login that returned `requires_device_confirmation`. The code is the one emailed to the user, or an MFA code when MFA is enabled. The client is remembered so later logins from it are not challenged.",
+    request_body = ConfirmDeviceRequest,
+    responses(
+        (status = 200, description = "Device confirmed and login completed"),
+        (status = 401, description = "Invalid code or unknown challenge", body = ErrorResponse)
+    )
+)]
+pub async fn confirm_device(
+    State(pool): State<DbPool>,
+    Json(payload): Json<ConfirmDeviceRequest>
+) -> impl IntoResponse {
+    match AuthService::confirm_new_device(&pool, payload.user_id, &payload.challenge_id, &payload.code).await {
+        Ok(response) => (StatusCode::OK, ResponseJson(response)).into_response(),
+        Err(e) => {
+            let error = ErrorResponse {
+                error: e.to_string(),
+            };
+            (StatusCode::UNAUTHORIZED, ResponseJson(error)).into_response()
+        }
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/api/me",
@@ -328,7 +451,8 @@ pub async fn me(
         Ok(Some((user, user_organizations))) => {
             let response = json!({
                 "user": user,
-                "organizations": user_organizations
+                "organizations": user_organizations,
+                "impersonator_id": auth_user.impersonator_id
             });
             (StatusCode::OK, ResponseJson(response)).into_response()
         }
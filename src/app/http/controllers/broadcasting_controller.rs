@@ -0,0 +1,43 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json as ResponseJson},
+    Extension, Form,
+};
+use crate::database::DbPool;
+
+use crate::app::http::middleware::auth_guard::AuthUser;
+use crate::app::services::broadcast_auth_service::{BroadcastAuthError, BroadcastAuthRequest, BroadcastAuthService};
+use crate::app::http::responses::ServiceErrorResponse;
+
+fn broadcast_auth_error_response(e: anyhow::Error) -> axum::response::Response {
+    ServiceErrorResponse::new(BroadcastAuthError::status_code(&e), e).into_response()
+}
+
+#[utoipa::path(
+    post,
+    path = "/broadcasting/auth",
+    tag = "Broadcasting",
+    summary = "Authorize channel subscription",
+    description = "Authorize the authenticated user for a private or presence channel before subscribing. The channel authorization callbacks decide access; the response is signed with the broadcasting app secret. With BROADCAST_AUTH_PROTOCOL=pusher it is a Pusher-compatible `key:signature` (plus `channel_data` for presence channels), otherwise a short-lived allow token the native WebSocket server accepts as `channel_auth`.",
+    request_body(content = BroadcastAuthRequest, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Signed channel authorization", body = crate::app::services::broadcast_auth_service::BroadcastAuthResponse),
+        (status = 401, description = "Unauthenticated", body = crate::app::docs::ErrorResponse),
+        (status = 403, description = "Not authorized for the channel", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Missing channel or socket ID", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn authorize(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Form(payload): Form<BroadcastAuthRequest>,
+) -> impl IntoResponse {
+    let result = BroadcastAuthService::new().and_then(|service| service.authorize(&pool, &auth_user.user_id, payload));
+
+    match result {
+        Ok(auth) => (StatusCode::OK, ResponseJson(auth)).into_response(),
+        Err(e) => broadcast_auth_error_response(e),
+    }
+}
@@ -10,7 +10,8 @@ use rust_decimal::Decimal;
 use crate::app::models::city::{CreateCity, UpdateCity, City};
 use crate::app::services::city_service::CityService;
 use crate::app::http::requests::{CreateCityRequest, UpdateCityRequest};
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::http::responses::ServiceErrorResponse;
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 
 #[derive(Serialize)]
 struct ErrorResponse {
@@ -52,18 +53,13 @@ pub struct NearbyQuery {
 )]
 pub async fn index(
     State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    params: QueryParams,
 ) -> impl IntoResponse {
     match <City as QueryBuilderService<City>>::index(Query(params), &pool) {
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
@@ -124,10 +120,7 @@ pub async fn store(State(pool): State<DbPool>, request: CreateCityRequest) -> im
     match CityService::create(&pool, payload, &system_user_id) {
         Ok(city) => (StatusCode::CREATED, ResponseJson(city.to_response())).into_response(),
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::BAD_REQUEST, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::BAD_REQUEST, e).into_response()
         }
     }
 }
@@ -148,10 +141,7 @@ pub async fn update(
     match CityService::update(&pool, id, payload) {
         Ok(city) => (StatusCode::OK, ResponseJson(city.to_response())).into_response(),
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::BAD_REQUEST, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::BAD_REQUEST, e).into_response()
         }
     }
 }
@@ -165,10 +155,7 @@ pub async fn destroy(State(pool): State<DbPool>, Path(id): Path<String>) -> impl
             (StatusCode::OK, ResponseJson(message)).into_response()
         }
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, e).into_response()
         }
     }
 }
@@ -198,7 +185,7 @@ pub async fn destroy(State(pool): State<DbPool>, Path(id): Path<String>) -> impl
 pub async fn by_province(
     State(pool): State<DbPool>,
     Path(province_id): Path<String>,
-    Query(mut params): Query<QueryParams>,
+    mut params: QueryParams,
 ) -> impl IntoResponse {
     // Add province_id filter to the query parameters
     params.filter.insert("province_id".to_string(), serde_json::json!(province_id));
@@ -207,12 +194,7 @@ pub async fn by_province(
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
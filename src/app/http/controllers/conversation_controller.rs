@@ -0,0 +1,186 @@
+use axum::{
+    extract::{State, Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Json as ResponseJson},
+    Extension, Json,
+};
+use crate::database::DbPool;
+
+use crate::app::http::middleware::auth_guard::AuthUser;
+use crate::app::models::conversation::Conversation;
+use crate::app::query_builder::{QueryBuilderService, QueryParams, QueryParamsError};
+use crate::app::services::conversation_service::{ConversationError, ConversationService};
+use crate::app::services::pinned_message_service::{PinError, PinMessageRequest, PinnedMessageService};
+use crate::app::services::typing_indicator_service::{TypingIndicatorService, TypingRequest};
+use crate::app::http::responses::ServiceErrorResponse;
+
+fn conversation_error_response(e: anyhow::Error) -> axum::response::Response {
+    ServiceErrorResponse::new(ConversationError::status_code(&e), e).into_response()
+}
+
+fn pin_error_response(e: anyhow::Error) -> axum::response::Response {
+    ServiceErrorResponse::new(PinError::status_code(&e), e).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/conversations",
+    tag = "Conversations",
+    summary = "List conversations",
+    description = "Retrieve the conversations the authenticated user actively participates in. Messages can be included a page at a time with `include=messages&page[messages]=2&per_page[messages]=20`; each conversation's `messages` then holds `data` and `pagination`.",
+    params(
+        ("page" = Option<u32>, Query, description = "Page number for pagination (default: 1)"),
+        ("per_page" = Option<u32>, Query, description = "Items per page (default: 15, max: 100)"),
+        ("sort" = Option<String>, Query, description = "Available fields: id, conversation_type, is_public, created_at, updated_at. Default: -updated_at"),
+        ("include" = Option<String>, Query, description = "Eager load relationships. Available: messages"),
+        ("filter" = Option<serde_json::Value>, Query, description = "Available filters: id, conversation_type, is_encrypted, creator_id, is_public, created_at, updated_at, deleted_at. Example: filter[conversation_type]=group"),
+        ("page[messages]" = Option<u32>, Query, description = "Page of included messages (default: 1)"),
+        ("per_page[messages]" = Option<u32>, Query, description = "Included messages per page (default: 20)"),
+    ),
+    responses(
+        (status = 200, description = "Conversations of the authenticated user", body = serde_json::Value),
+        (status = 400, description = "Invalid query parameters", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn index(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    mut params: QueryParams,
+) -> impl IntoResponse {
+    let mut conversation_ids = match ConversationService::conversation_ids_for_user(&pool, &auth_user.user_id) {
+        Ok(conversation_ids) => conversation_ids,
+        Err(e) => return conversation_error_response(e),
+    };
+    // An empty `in` list is rejected, so a user without conversations matches no id instead
+    if conversation_ids.is_empty() {
+        conversation_ids.push(String::new());
+    }
+    params.filter.insert("id[in]".to_string(), serde_json::json!(conversation_ids));
+
+    match <Conversation as QueryBuilderService<Conversation>>::index(Query(params), &pool) {
+        Ok(result) => (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response(),
+        Err(e) => QueryParamsError::response(&e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/conversations/{id}/typing",
+    tag = "Conversations",
+    summary = "Send typing indicator",
+    description = "Notify the other participants that the authenticated user is typing. The `typing` event is delivered on each other participant's private `user.{id}` channel so the sender does not receive it. The indicator expires after a few seconds unless refreshed; send `is_typing: false` to clear it early.",
+    params(
+        ("id" = String, Path, description = "Conversation unique identifier (ULID format)")
+    ),
+    request_body = TypingRequest,
+    responses(
+        (status = 200, description = "Typing indicator recorded", body = crate::app::services::typing_indicator_service::TypingState),
+        (status = 403, description = "Not a participant of the conversation", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Device does not belong to the user", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn typing(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(payload): Json<TypingRequest>,
+) -> impl IntoResponse {
+    match TypingIndicatorService::typing(&pool, &id, &auth_user.user_id, payload).await {
+        Ok(state) => (StatusCode::OK, ResponseJson(state)).into_response(),
+        Err(e) => conversation_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/conversations/{id}/pins",
+    tag = "Conversations",
+    summary = "List pinned messages",
+    description = "Retrieve the active pinned messages of a conversation, most recently pinned first",
+    params(
+        ("id" = String, Path, description = "Conversation unique identifier (ULID format)")
+    ),
+    responses(
+        (status = 200, description = "Pinned messages", body = Vec<crate::app::models::pinned_messages::PinnedMessageResponse>),
+        (status = 403, description = "Not a participant of the conversation", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn pins(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let result = PinnedMessageService::new().list(&pool, &id, &auth_user.user_id);
+
+    match result {
+        Ok(pins) => {
+            let responses: Vec<_> = pins.iter().map(|p| p.to_response()).collect();
+            (StatusCode::OK, ResponseJson(responses)).into_response()
+        }
+        Err(e) => pin_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/conversations/{id}/pins",
+    tag = "Conversations",
+    summary = "Pin a message",
+    description = "Pin a message in the conversation and notify participants. In group conversations and channels only owners, admins, and moderators may pin (configurable with MESSAGING_PIN_REQUIRES_MODERATOR). The number of active pins is capped by MESSAGING_MAX_PINNED_MESSAGES.",
+    params(
+        ("id" = String, Path, description = "Conversation unique identifier (ULID format)")
+    ),
+    request_body = PinMessageRequest,
+    responses(
+        (status = 201, description = "Message pinned", body = crate::app::models::pinned_messages::PinnedMessageResponse),
+        (status = 200, description = "Message was already pinned", body = crate::app::models::pinned_messages::PinnedMessageResponse),
+        (status = 403, description = "Insufficient role or not a participant", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Conversation or message not found", body = crate::app::docs::ErrorResponse),
+        (status = 409, description = "Pin limit reached", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Device does not belong to the user", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn pin(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(payload): Json<PinMessageRequest>,
+) -> impl IntoResponse {
+    match PinnedMessageService::new().pin(&pool, &id, &auth_user.user_id, payload).await {
+        Ok((pin, true)) => (StatusCode::CREATED, ResponseJson(pin.to_response())).into_response(),
+        Ok((pin, false)) => (StatusCode::OK, ResponseJson(pin.to_response())).into_response(),
+        Err(e) => pin_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/conversations/{id}/pins/{message_id}",
+    tag = "Conversations",
+    summary = "Unpin a message",
+    description = "Unpin a message in the conversation and notify participants",
+    params(
+        ("id" = String, Path, description = "Conversation unique identifier (ULID format)"),
+        ("message_id" = String, Path, description = "Pinned message unique identifier (ULID format)")
+    ),
+    responses(
+        (status = 204, description = "Message unpinned"),
+        (status = 403, description = "Insufficient role or not a participant", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Message is not pinned", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn unpin(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((id, message_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    match PinnedMessageService::new().unpin(&pool, &id, &message_id, &auth_user.user_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => pin_error_response(e),
+    }
+}
@@ -1,6 +1,6 @@
 use axum::{
     extract::{State, Path, Query},
-    http::StatusCode,
+    http::{HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Json as ResponseJson},
     Extension,
 };
@@ -10,7 +10,10 @@ use crate::database::DbPool;
 use crate::app::models::country::{CreateCountry, UpdateCountry, Country};
 use crate::app::services::country_service::CountryService;
 use crate::app::http::requests::{CreateCountryRequest, UpdateCountryRequest};
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::http::responses::{Envelope, ServiceErrorResponse};
+use crate::app::models::HasModelType;
+use crate::app::query_builder::{ExportFormat, QueryCache, QueryParams, QueryBuilderService, QueryParamsError};
+use crate::app::query_builder::cache::invalidate_model;
 
 #[derive(Serialize)]
 struct ErrorResponse {
@@ -37,6 +40,8 @@ struct MessageResponse {
         ("fields" = Option<String>, Query, description = "Field selection for optimized responses. Available: id, name, iso_code, phone_code, created_at, updated_at. Example: fields[countries]=id,name,iso_code"),
         ("cursor" = Option<String>, Query, description = "Cursor for high-performance pagination. Base64-encoded JSON cursor from previous response"),
         ("pagination_type" = Option<String>, Query, description = "Pagination strategy: 'offset' (traditional page/per_page) or 'cursor' (high-performance, default)"),
+        ("format" = Option<String>, Query, description = "Download every matching row instead of a page: 'csv' or 'ndjson'. 'Accept: text/csv' or 'Accept: application/x-ndjson' does the same"),
+        ("wrap" = Option<bool>, Query, description = "Set to false for a bare array; pagination is always sent in the Link and X-Total-Count headers. 'Accept: application/json; profile=bare' does the same"),
     ),
     responses(
         (status = 200, description = "List of countries", body = Vec<crate::app::models::country::CountryResponse>),
@@ -45,18 +50,29 @@ struct MessageResponse {
 )]
 pub async fn index(
     State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    headers: HeaderMap,
+    envelope: Envelope,
+    params: QueryParams,
 ) -> impl IntoResponse {
-    match <Country as QueryBuilderService<Country>>::index(Query(params), &pool) {
-        Ok(result) => {
-            (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
-        }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+    if let Some(format) = ExportFormat::negotiate(&params, &headers) {
+        return match <Country as QueryBuilderService<Country>>::export(Query(params), format, &pool) {
+            Ok(response) => response,
+            Err(e) => QueryParamsError::response(&e),
+        };
+    }
+
+    let result = match QueryCache::new().await {
+        Ok(cache) => cache.index::<Country>(params, &pool).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok((result, cache_status)) => {
+            let mut response = envelope.collection(result);
+            response.headers_mut().insert("x-cache-status", HeaderValue::from_static(cache_status.as_str()));
+            response
+        },
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
@@ -67,7 +83,8 @@ pub async fn index(
     summary = "Get country by ID",
     description = "Retrieve a specific country by its unique identifier",
     params(
-        ("id" = String, Path, description = "Country unique identifier (ULID format)")
+        ("id" = String, Path, description = "Country unique identifier (ULID format)"),
+        ("wrap" = Option<bool>, Query, description = "Set to true to wrap the country in a `data` key; it is returned bare by default"),
     ),
     responses(
         (status = 200, description = "Country details", body = crate::app::models::country::CountryResponse),
@@ -76,9 +93,9 @@ pub async fn index(
         (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
     )
 )]
-pub async fn show(State(pool): State<DbPool>, Path(id): Path<String>) -> impl IntoResponse {
+pub async fn show(State(pool): State<DbPool>, envelope: Envelope, Path(id): Path<String>) -> impl IntoResponse {
     match CountryService::find_by_id(&pool, id) {
-        Ok(Some(country)) => (StatusCode::OK, ResponseJson(country.to_response())).into_response(),
+        Ok(Some(country)) => envelope.resource(StatusCode::OK, country.to_response()),
         Ok(None) => {
             let error = ErrorResponse {
                 error: "Country not found".to_string(),
@@ -123,6 +140,7 @@ pub async fn show(State(pool): State<DbPool>, Path(id): Path<String>) -> impl In
     responses(
         (status = 201, description = "Country created successfully", body = crate::app::models::country::CountryResponse),
         (status = 400, description = "Validation error or bad request", body = crate::app::docs::ErrorResponse),
+        (status = 409, description = "A country with this ISO code already exists", body = crate::app::http::form_request::ValidationErrorResponse),
         (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
     )
 )]
@@ -164,10 +182,7 @@ pub async fn store(State(pool): State<DbPool>, request: CreateCountryRequest) ->
     match CountryService::create(&pool, payload, &system_user_id).await {
         Ok(country) => (StatusCode::CREATED, ResponseJson(country.to_response())).into_response(),
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::BAD_REQUEST, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::BAD_REQUEST, e).into_response()
         }
     }
 }
@@ -214,12 +229,12 @@ pub async fn update(
     };
 
     match CountryService::update(&pool, id, payload) {
-        Ok(country) => (StatusCode::OK, ResponseJson(country.to_response())).into_response(),
+        Ok(country) => {
+            invalidate_model(Country::model_type()).await;
+            (StatusCode::OK, ResponseJson(country.to_response())).into_response()
+        },
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::BAD_REQUEST, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::BAD_REQUEST, e).into_response()
         }
     }
 }
@@ -249,16 +264,14 @@ pub async fn update(
 pub async fn destroy(State(pool): State<DbPool>, Path(id): Path<String>) -> impl IntoResponse {
     match CountryService::delete(&pool, id) {
         Ok(_) => {
+            invalidate_model(Country::model_type()).await;
             let message = MessageResponse {
                 message: "Country deleted successfully".to_string(),
             };
             (StatusCode::OK, ResponseJson(message)).into_response()
         }
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, e).into_response()
         }
     }
 }
\ No newline at end of file
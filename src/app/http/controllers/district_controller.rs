@@ -9,7 +9,8 @@ use crate::database::DbPool;
 use crate::app::models::district::{CreateDistrict, UpdateDistrict, District};
 use crate::app::services::district_service::DistrictService;
 use crate::app::http::requests::{CreateDistrictRequest, UpdateDistrictRequest};
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::http::responses::ServiceErrorResponse;
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 
 #[derive(Serialize)]
 struct ErrorResponse {
@@ -45,18 +46,13 @@ struct MessageResponse {
 )]
 pub async fn index(
     State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    params: QueryParams,
 ) -> impl IntoResponse {
     match <District as QueryBuilderService<District>>::index(Query(params), &pool) {
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
@@ -163,10 +159,7 @@ pub async fn store(State(pool): State<DbPool>, request: CreateDistrictRequest) -
     match DistrictService::create(&pool, payload, &system_user_id).await {
         Ok(district) => (StatusCode::CREATED, ResponseJson(district.to_response())).into_response(),
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::BAD_REQUEST, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::BAD_REQUEST, e).into_response()
         }
     }
 }
@@ -215,10 +208,7 @@ pub async fn update(
     match DistrictService::update(&pool, id, payload) {
         Ok(district) => (StatusCode::OK, ResponseJson(district.to_response())).into_response(),
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::BAD_REQUEST, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::BAD_REQUEST, e).into_response()
         }
     }
 }
@@ -254,10 +244,7 @@ pub async fn destroy(State(pool): State<DbPool>, Path(id): Path<String>) -> impl
             (StatusCode::OK, ResponseJson(message)).into_response()
         }
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, e).into_response()
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,137 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use diesel::RunQueryDsl;
+use serde::Serialize;
+
+use crate::config::health::HealthConfig;
+use crate::database::migration_runner::MigrationRunner;
+use crate::database::DbPool;
+
+/// Outcome of one readiness check
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    /// `ok`, `pending` or `failed`
+    pub status: &'static str,
+    /// Migrations not yet applied
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pending: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl CheckResult {
+    fn ok() -> Self {
+        Self { status: "ok", pending: Vec::new(), error: None }
+    }
+
+    fn failed(error: impl ToString) -> Self {
+        Self { status: "failed", pending: Vec::new(), error: Some(error.to_string()) }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.status == "ok"
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessChecks {
+    pub database: CheckResult,
+    /// Left out when the migration check is turned off
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub migrations: Option<CheckResult>,
+}
+
+/// Body of `/ready`, answered with 200 when every check passed and 503 otherwise
+#[derive(Debug, Clone, Serialize)]
+pub struct Readiness {
+    pub status: &'static str,
+    pub checks: ReadinessChecks,
+}
+
+impl Readiness {
+    pub fn is_ready(&self) -> bool {
+        self.checks.database.is_ok() && self.checks.migrations.as_ref().is_none_or(CheckResult::is_ok)
+    }
+}
+
+impl IntoResponse for Readiness {
+    fn into_response(self) -> Response {
+        let status = if self.is_ready() { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+        (status, Json(self)).into_response()
+    }
+}
+
+/// Whether the app can take traffic: the database answers and, unless
+/// turned off, no migration is pending
+///
+/// Lets an orchestrator hold traffic while a deploy's migrations still run.
+#[derive(Debug, Clone)]
+pub struct ReadinessCheck {
+    check_migrations: bool,
+    migrations_path: String,
+}
+
+impl ReadinessCheck {
+    pub fn new(check_migrations: bool, migrations_path: impl Into<String>) -> Self {
+        Self {
+            check_migrations,
+            migrations_path: migrations_path.into(),
+        }
+    }
+
+    pub fn from_config(config: &HealthConfig) -> Self {
+        Self::new(config.ready_check_migrations, config.migrations_path.clone())
+    }
+
+    pub fn check(&self, pool: &DbPool) -> Readiness {
+        let database = match pool.get() {
+            Ok(mut conn) => match diesel::sql_query("SELECT 1").execute(&mut conn) {
+                Ok(_) => CheckResult::ok(),
+                Err(e) => CheckResult::failed(e),
+            },
+            Err(e) => CheckResult::failed(e),
+        };
+
+        // Migrations cannot be compared without the database
+        let migrations = match (self.check_migrations, database.is_ok()) {
+            (false, _) => None,
+            (true, true) => Some(self.migration_check(pool)),
+            (true, false) => Some(CheckResult::failed("database unavailable")),
+        };
+
+        let mut readiness = Readiness {
+            status: "ready",
+            checks: ReadinessChecks { database, migrations },
+        };
+        if !readiness.is_ready() {
+            readiness.status = "not_ready";
+        }
+        readiness
+    }
+
+    fn migration_check(&self, pool: &DbPool) -> CheckResult {
+        let runner = MigrationRunner::new(pool.clone(), self.migrations_path.clone());
+        match runner.status() {
+            Ok(status) if status.is_up_to_date() => CheckResult::ok(),
+            Ok(status) => CheckResult {
+                status: "pending",
+                pending: status.pending.into_iter().map(|migration| migration.name).collect(),
+                error: None,
+            },
+            Err(e) => CheckResult::failed(e),
+        }
+    }
+}
+
+/// Readiness probe: 503 until the database answers and migrations are applied
+pub async fn ready(State(pool): State<DbPool>) -> Response {
+    let check = match HealthConfig::from_env() {
+        Ok(config) => ReadinessCheck::from_config(&config),
+        Err(_) => ReadinessCheck::new(true, "./src/database/migrations"),
+    };
+
+    check.check(&pool).into_response()
+}
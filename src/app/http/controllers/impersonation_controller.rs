@@ -0,0 +1,75 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    response::{IntoResponse, Json as ResponseJson},
+    Extension,
+};
+use crate::database::DbPool;
+
+use crate::app::http::middleware::auth_guard::AuthUser;
+use crate::app::http::middleware::auth_middleware::extract_bearer_token;
+use crate::app::services::impersonation_service::{ImpersonationError, ImpersonationService};
+use crate::app::http::responses::ServiceErrorResponse;
+
+fn impersonation_error_response(e: anyhow::Error) -> axum::response::Response {
+    ServiceErrorResponse::new(ImpersonationError::status_code(&e), e).into_response()
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/impersonation/{user_id}",
+    tag = "Authentication",
+    summary = "Start impersonating a user",
+    description = "Issue an access token that authenticates an administrator as another user. The token carries an `impersonator_id` claim, and every request made with it is recorded in the activity log with both user ids. Administrators cannot be impersonated.",
+    params(
+        ("user_id" = String, Path, description = "User to impersonate")
+    ),
+    responses(
+        (status = 200, description = "Impersonation token", body = crate::app::services::impersonation_service::ImpersonationResponse),
+        (status = 403, description = "Not an administrator, or the target is an administrator", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "User not found", body = crate::app::docs::ErrorResponse),
+        (status = 409, description = "Already impersonating, or impersonating yourself", body = crate::app::docs::ErrorResponse)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn start(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(user_id): Path<String>,
+) -> impl IntoResponse {
+    match ImpersonationService::start(&pool, &auth_user, &user_id).await {
+        Ok(response) => {
+            tracing::warn!("User {} started impersonating {}", auth_user.user_id, user_id);
+            ResponseJson(response).into_response()
+        }
+        Err(e) => impersonation_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/impersonation",
+    tag = "Authentication",
+    summary = "Stop impersonating",
+    description = "End impersonation and issue a regular access token for the administrator. Must be called with the impersonation token, which is revoked and rejected on later requests.",
+    responses(
+        (status = 200, description = "Token for the administrator", body = crate::app::services::impersonation_service::ImpersonationResponse),
+        (status = 409, description = "Not impersonating", body = crate::app::docs::ErrorResponse)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn stop(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let token = extract_bearer_token(&headers).unwrap_or_default();
+    match ImpersonationService::stop(&pool, &auth_user, &token).await {
+        Ok(response) => ResponseJson(response).into_response(),
+        Err(e) => impersonation_error_response(e),
+    }
+}
@@ -0,0 +1,127 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json as ResponseJson},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::database::DbPool;
+
+use crate::app::http::middleware::auth_guard::AuthUser;
+use crate::app::services::security_incident_service::SecurityIncidentService;
+use crate::logging::level::{log_level, LogLevelError};
+use crate::app::http::responses::ServiceErrorResponse;
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+fn log_level_error_response(e: anyhow::Error) -> axum::response::Response {
+    ServiceErrorResponse::new(LogLevelError::status_code(&e), e).into_response()
+}
+
+/// Only administrators may inspect or change the log filter
+fn ensure_admin(pool: &DbPool, auth_user: &AuthUser) -> Option<axum::response::Response> {
+    match SecurityIncidentService::is_admin(pool, &auth_user.user_id) {
+        Ok(true) => None,
+        Ok(false) => Some((StatusCode::FORBIDDEN, ResponseJson(ErrorResponse {
+            error: "Only administrators can change log levels".to_string(),
+        })).into_response()),
+        Err(e) => Some(log_level_error_response(e)),
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateLogLevelRequest {
+    /// Filter directive such as `debug` or `rustaxum::app::services=trace`
+    pub directive: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/log-level",
+    tag = "Logging",
+    summary = "Show log filter",
+    description = "Show the startup log filter, the overrides applied since, and the effective filter",
+    responses(
+        (status = 200, description = "Log filter", body = crate::logging::level::LogLevelStatus),
+        (status = 403, description = "Not an administrator", body = crate::app::docs::ErrorResponse),
+        (status = 503, description = "Runtime log level changes are not enabled", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn show(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> impl IntoResponse {
+    if let Some(response) = ensure_admin(&pool, &auth_user) {
+        return response;
+    }
+
+    match log_level() {
+        Ok(filter) => (StatusCode::OK, ResponseJson(filter.status())).into_response(),
+        Err(e) => log_level_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/log-level",
+    tag = "Logging",
+    summary = "Override log level",
+    description = "Apply a `<level>` or `<target>=<level>` directive to the running process without a restart. It replaces any earlier override for the same target and lasts until reset or the process exits.",
+    request_body = UpdateLogLevelRequest,
+    responses(
+        (status = 200, description = "Override applied", body = crate::logging::level::LogLevelStatus),
+        (status = 403, description = "Not an administrator", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Invalid directive", body = crate::app::docs::ErrorResponse),
+        (status = 503, description = "Runtime log level changes are not enabled", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn update(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<UpdateLogLevelRequest>,
+) -> impl IntoResponse {
+    if let Some(response) = ensure_admin(&pool, &auth_user) {
+        return response;
+    }
+
+    match log_level().and_then(|filter| filter.set(&payload.directive)) {
+        Ok(status) => {
+            tracing::warn!("User {} set log directive {}", auth_user.user_id, payload.directive);
+            (StatusCode::OK, ResponseJson(status)).into_response()
+        }
+        Err(e) => log_level_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/log-level",
+    tag = "Logging",
+    summary = "Reset log level",
+    description = "Drop all runtime overrides and return to the startup log filter",
+    responses(
+        (status = 200, description = "Startup filter restored", body = crate::logging::level::LogLevelStatus),
+        (status = 403, description = "Not an administrator", body = crate::app::docs::ErrorResponse),
+        (status = 503, description = "Runtime log level changes are not enabled", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn destroy(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> impl IntoResponse {
+    if let Some(response) = ensure_admin(&pool, &auth_user) {
+        return response;
+    }
+
+    match log_level().and_then(|filter| filter.reset()) {
+        Ok(status) => {
+            tracing::warn!("User {} reset the log filter", auth_user.user_id);
+            (StatusCode::OK, ResponseJson(status)).into_response()
+        }
+        Err(e) => log_level_error_response(e),
+    }
+}
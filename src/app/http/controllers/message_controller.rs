@@ -2,12 +2,17 @@ use axum::{
     extract::{State, Path, Query},
     http::StatusCode,
     response::{IntoResponse, Json as ResponseJson},
+    Extension, Json,
 };
 use serde::Serialize;
 use crate::database::DbPool;
 
+use crate::app::http::middleware::auth_guard::{AuthUser, VerifiedUser};
 use crate::app::models::message::{Message};
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
+use crate::app::services::conversation_service::ConversationError;
+use crate::app::services::message_service::{AddReactionRequest, ForwardMessageRequest, MessageError, MessageReceiptRequest, MessageService, SendMessageRequest};
+use crate::app::http::responses::ServiceErrorResponse;
 
 #[derive(Serialize)]
 struct ErrorResponse {
@@ -43,18 +48,13 @@ struct MessageResponse {
 )]
 pub async fn index(
     State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    params: QueryParams,
 ) -> impl IntoResponse {
     match <Message as QueryBuilderService<Message>>::index(Query(params), &pool) {
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
@@ -108,4 +108,253 @@ pub async fn show(State(pool): State<DbPool>, Path(id): Path<String>) -> impl In
             (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
         }
     }
-}
\ No newline at end of file
+}
+
+fn conversation_error_response(e: anyhow::Error) -> axum::response::Response {
+    ServiceErrorResponse::new(ConversationError::status_code(&e), e).into_response()
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/conversations/{id}/messages",
+    tag = "Messages",
+    summary = "Send a message",
+    description = "Send a message to a conversation from one of the authenticated user's devices. Mentions are parsed from the body of unencrypted conversations, or taken from the `mentions` list for encrypted ones; mentioned participants are notified and receive a `message.mentioned` event on their private channel. Mentions of non-participants are ignored.",
+    params(
+        ("id" = String, Path, description = "Conversation unique identifier (ULID format)")
+    ),
+    request_body = SendMessageRequest,
+    responses(
+        (status = 201, description = "Message sent", body = crate::app::services::message_service::SentMessage),
+        (status = 403, description = "Not a participant of the conversation, or email address not verified", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Conversation or replied-to message not found", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Device does not belong to the user", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn store(
+    State(pool): State<DbPool>,
+    auth_user: VerifiedUser,
+    Path(id): Path<String>,
+    Json(payload): Json<SendMessageRequest>,
+) -> impl IntoResponse {
+    match MessageService::send(&pool, &id, &auth_user.user_id, payload).await {
+        Ok(sent) => (StatusCode::CREATED, ResponseJson(sent)).into_response(),
+        Err(e) => conversation_error_response(e),
+    }
+}
+
+fn message_error_response(e: anyhow::Error) -> axum::response::Response {
+    ServiceErrorResponse::new(MessageError::status_code(&e), e).into_response()
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/messages/{id}/forward",
+    tag = "Messages",
+    summary = "Forward a message",
+    description = "Copy a message into another conversation the authenticated user participates in. The new message references the forwarded one, and the forward chain is recorded with the original message, its sender, and the forward depth. When either conversation is encrypted the client must send the content re-encrypted for the destination.",
+    params(
+        ("id" = String, Path, description = "Message unique identifier (ULID format)")
+    ),
+    request_body = ForwardMessageRequest,
+    responses(
+        (status = 201, description = "Message forwarded", body = crate::app::services::message_service::ForwardedMessage),
+        (status = 403, description = "Not a participant of the source or destination conversation, or email address not verified", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Message or conversation not found", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Re-encrypted content missing or device does not belong to the user", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn forward(
+    State(pool): State<DbPool>,
+    auth_user: VerifiedUser,
+    Path(id): Path<String>,
+    Json(payload): Json<ForwardMessageRequest>,
+) -> impl IntoResponse {
+    match MessageService::forward(&pool, &id, &auth_user.user_id, payload).await {
+        Ok(forwarded) => (StatusCode::CREATED, ResponseJson(forwarded)).into_response(),
+        Err(e) => message_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/messages/{id}/delivered",
+    tag = "Messages",
+    summary = "Mark message as delivered",
+    description = "Record that a message was delivered to one of the authenticated user's devices and notify the conversation",
+    params(
+        ("id" = String, Path, description = "Message unique identifier (ULID format)")
+    ),
+    request_body = MessageReceiptRequest,
+    responses(
+        (status = 200, description = "Delivery status recorded", body = crate::app::models::message_delivery_status::MessageDeliveryStatus),
+        (status = 403, description = "Not a participant of the conversation", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Message not found", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Device does not belong to the user", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn mark_delivered(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(payload): Json<MessageReceiptRequest>,
+) -> impl IntoResponse {
+    match MessageService::mark_delivered(&pool, &id, &auth_user.user_id, &payload.device_id.to_string()).await {
+        Ok(receipt) => (StatusCode::OK, ResponseJson(receipt)).into_response(),
+        Err(e) => conversation_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/messages/{id}/read",
+    tag = "Messages",
+    summary = "Mark message as read",
+    description = "Record that a message was read on one of the authenticated user's devices, advance the user's read position, and notify the conversation",
+    params(
+        ("id" = String, Path, description = "Message unique identifier (ULID format)")
+    ),
+    request_body = MessageReceiptRequest,
+    responses(
+        (status = 200, description = "Read receipt recorded", body = crate::app::models::message_delivery_status::MessageDeliveryStatus),
+        (status = 403, description = "Not a participant of the conversation", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Message not found", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Device does not belong to the user", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn mark_read(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(payload): Json<MessageReceiptRequest>,
+) -> impl IntoResponse {
+    match MessageService::mark_read(&pool, &id, &auth_user.user_id, &payload.device_id.to_string()).await {
+        Ok(receipt) => (StatusCode::OK, ResponseJson(receipt)).into_response(),
+        Err(e) => conversation_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/messages/{id}/delivery-status",
+    tag = "Messages",
+    summary = "Get message delivery status",
+    description = "List delivery and read receipts for a message across all recipient devices",
+    params(
+        ("id" = String, Path, description = "Message unique identifier (ULID format)")
+    ),
+    responses(
+        (status = 200, description = "Delivery status per device", body = Vec<crate::app::models::message_delivery_status::MessageDeliveryStatus>),
+        (status = 403, description = "Not a participant of the conversation", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Message not found", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn delivery_status(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match MessageService::delivery_status(&pool, &id, &auth_user.user_id) {
+        Ok(statuses) => (StatusCode::OK, ResponseJson(statuses)).into_response(),
+        Err(e) => conversation_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/messages/{id}/reactions",
+    tag = "Messages",
+    summary = "List message reactions",
+    description = "Retrieve the encrypted reactions on a message",
+    params(
+        ("id" = String, Path, description = "Message unique identifier (ULID format)")
+    ),
+    responses(
+        (status = 200, description = "Reactions on the message", body = Vec<crate::app::models::message_reactions::MessageReactionResponse>),
+        (status = 403, description = "Not a participant of the conversation", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Message not found", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn reactions(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match MessageService::reactions(&pool, &id, &auth_user.user_id) {
+        Ok(reactions) => {
+            let responses: Vec<_> = reactions.iter().map(|r| r.to_response()).collect();
+            (StatusCode::OK, ResponseJson(responses)).into_response()
+        }
+        Err(e) => conversation_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/messages/{id}/reactions",
+    tag = "Messages",
+    summary = "Add a reaction to a message",
+    description = "Add an encrypted reaction to a message and notify the conversation. Adding the same reaction twice returns the existing reaction.",
+    params(
+        ("id" = String, Path, description = "Message unique identifier (ULID format)")
+    ),
+    request_body = AddReactionRequest,
+    responses(
+        (status = 201, description = "Reaction added", body = crate::app::models::message_reactions::MessageReactionResponse),
+        (status = 403, description = "Not a participant of the conversation", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Message not found", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Device does not belong to the user", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn add_reaction(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(payload): Json<AddReactionRequest>,
+) -> impl IntoResponse {
+    match MessageService::add_reaction(&pool, &id, &auth_user.user_id, payload).await {
+        Ok(reaction) => (StatusCode::CREATED, ResponseJson(reaction.to_response())).into_response(),
+        Err(e) => conversation_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/messages/{id}/reactions/{reaction_id}",
+    tag = "Messages",
+    summary = "Remove a reaction from a message",
+    description = "Remove one of the authenticated user's reactions from a message and notify the conversation",
+    params(
+        ("id" = String, Path, description = "Message unique identifier (ULID format)"),
+        ("reaction_id" = String, Path, description = "Reaction unique identifier (ULID format)")
+    ),
+    responses(
+        (status = 204, description = "Reaction removed"),
+        (status = 403, description = "Not a participant of the conversation", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Message or reaction not found", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn remove_reaction(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((id, reaction_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    match MessageService::remove_reaction(&pool, &id, &reaction_id, &auth_user.user_id).await {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => {
+            let error = ErrorResponse {
+                error: "Reaction not found".to_string(),
+            };
+            (StatusCode::NOT_FOUND, ResponseJson(error)).into_response()
+        }
+        Err(e) => conversation_error_response(e),
+    }
+}
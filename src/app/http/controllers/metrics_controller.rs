@@ -0,0 +1,18 @@
+use axum::response::Json;
+use serde_json::{json, Value};
+
+use crate::app::utils::PerformanceMetrics;
+use crate::config::performance::PerformanceConfig;
+
+/// Request and query counters, only routed when `APP_DEBUG` is on
+pub async fn show() -> Json<Value> {
+    let config = PerformanceConfig::from_env().ok();
+
+    Json(json!({
+        "performance": PerformanceMetrics::global().snapshot(),
+        "thresholds": {
+            "slow_request_ms": config.as_ref().map(|config| config.slow_request_ms),
+            "slow_query_ms": config.as_ref().map(|config| config.slow_query_ms),
+        },
+    }))
+}
@@ -27,4 +27,15 @@ pub mod session_model_controller;
 pub mod sys_model_has_role_controller;
 pub mod activity_log_controller;
 pub mod session_controller;
-pub mod csrf_controller;
\ No newline at end of file
+pub mod csrf_controller;
+pub mod conversation_controller;
+pub mod poll_controller;
+pub mod presence_controller;
+pub mod prekey_controller;
+pub mod security_incident_controller;
+pub mod session_backup_controller;
+pub mod broadcasting_controller;
+pub mod log_level_controller;
+pub mod impersonation_controller;
+pub mod metrics_controller;
+pub mod health_controller;
\ No newline at end of file
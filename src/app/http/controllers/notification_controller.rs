@@ -8,7 +8,7 @@ use crate::database::DbPool;
 
 use crate::app::models::notification::{UpdateNotification, Notification};
 use crate::app::http::requests::{CreateNotificationRequest, UpdateNotificationRequest};
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 
 #[derive(Serialize)]
 struct ErrorResponse {
@@ -44,18 +44,13 @@ struct MessageResponse {
 )]
 pub async fn index(
     State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    params: QueryParams,
 ) -> impl IntoResponse {
     match <Notification as QueryBuilderService<Notification>>::index(Query(params), &pool) {
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
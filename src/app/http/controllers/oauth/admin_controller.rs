@@ -68,6 +68,7 @@ pub struct TopClientInfo {
     pub client_name: String,
     pub token_count: i64,
     pub active_token_count: i64,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub last_used: Option<DateTime<Utc>>,
 }
 
@@ -122,6 +123,7 @@ pub struct ScopeUsageInfo {
 
 #[derive(Serialize)]
 pub struct ActivityItem {
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub timestamp: DateTime<Utc>,
     pub activity_type: String,
     pub description: String,
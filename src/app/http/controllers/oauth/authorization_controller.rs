@@ -49,8 +49,10 @@ pub struct AuthCodeResponse {
     pub client_id: String,
     pub scopes: Vec<String>,
     pub redirect_uri: String,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub expires_at: chrono::DateTime<Utc>,
     pub revoked: bool,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: chrono::DateTime<Utc>,
 }
 
@@ -346,7 +348,9 @@ pub async fn list_authorized_clients(
             struct AuthorizedClientInfo {
                 client_id: String,
                 scopes: Vec<String>,
+                #[serde(with = "crate::app::utils::timestamp")]
                 last_used: chrono::DateTime<Utc>,
+                #[serde(default, with = "crate::app::utils::timestamp::option")]
                 expires_at: Option<chrono::DateTime<Utc>>,
             }
 
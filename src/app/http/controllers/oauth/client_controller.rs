@@ -12,7 +12,7 @@ use crate::app::services::auth_service::AuthService;
 use crate::app::models::oauth::{CreateClient, UpdateClient, Client};
 use crate::app::models::DieselUlid;
 use crate::app::utils::token_utils::TokenUtils;
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 
 #[derive(Serialize, ToSchema)]
 struct ErrorResponse {
@@ -122,7 +122,7 @@ pub async fn create_client(
 )]
 pub async fn list_clients(
     State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    params: QueryParams,
     headers: HeaderMap,
 ) -> impl IntoResponse {
     let _user_id = match get_authenticated_user(&pool, &headers).await {
@@ -137,12 +137,7 @@ pub async fn list_clients(
 
     match <Client as QueryBuilderService<Client>>::index(Query(params), &pool) {
         Ok(result) => (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response(),
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
@@ -6,6 +6,8 @@ use axum::{
 use serde_json::{json, Value};
 use crate::database::DbPool;
 use crate::app::services::oauth::MTLSService;
+use crate::app::services::security_incident_service::{IncidentSubject, SecurityIncidentService};
+use crate::app::models::security_incidents::{IncidentSeverity, IncidentType};
 
 /// RFC 8705: OAuth 2.0 Mutual-TLS Client Authentication Controller
 ///
@@ -103,7 +105,7 @@ pub async fn get_certificate_info(
 /// POST /oauth/mtls/validate-bound-token
 pub async fn validate_certificate_bound_token(
     headers: HeaderMap,
-    State(_pool): State<DbPool>,
+    State(pool): State<DbPool>,
     Json(payload): Json<Value>,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
     let access_token_cnf = payload.get("cnf")
@@ -144,6 +146,22 @@ pub async fn validate_certificate_bound_token(
     // Validate token binding
     match MTLSService::validate_certificate_bound_token(access_token_cnf, &certificate) {
         Ok(is_bound) => {
+            // The token was issued to a different certificate than the one presented
+            if !is_bound {
+                SecurityIncidentService::report(
+                    &pool,
+                    IncidentType::CertificateMismatch,
+                    IncidentSeverity::High,
+                    IncidentSubject::default(),
+                    json!({
+                        "provided_cnf": access_token_cnf,
+                        "certificate_thumbprint": certificate.thumbprint_sha256,
+                        "certificate_subject": certificate.subject_dn,
+                        "certificate_issuer": certificate.issuer_dn,
+                    }),
+                ).await;
+            }
+
             Ok(Json(json!({
                 "token_bound": is_bound,
                 "certificate_thumbprint": MTLSService::generate_certificate_thumbprint(&certificate),
@@ -1,7 +1,8 @@
 use axum::{
     extract::{Query, Json, State, Form},
-    http::{StatusCode, HeaderMap},
-    response::{IntoResponse, Json as ResponseJson, Redirect},
+    http::{header, StatusCode, HeaderMap},
+    response::{IntoResponse, Json as ResponseJson, Redirect, Response},
+    Extension,
 };
 use serde::{Deserialize, Serialize};
 use crate::database::DbPool;
@@ -10,7 +11,8 @@ use ulid::Ulid;
 use chrono::{Duration, Utc};
 use std::collections::HashMap;
 
-use crate::app::services::oauth::{TokenService, ClientService, ScopeService, DPoPService};
+use crate::app::services::oauth::{TokenService, ClientService, ScopeService, DPoPService, PARService, PARError, TokenThrottleService, TokenThrottled};
+use crate::app::http::middleware::trusted_proxy_middleware::ClientInfo;
 use crate::app::services::auth_service::AuthService;
 use crate::app::models::oauth::{CreateAuthCode};
 use crate::app::utils::token_utils::TokenUtils;
@@ -24,13 +26,41 @@ struct ErrorResponse {
 
 #[derive(Deserialize)]
 pub struct AuthorizeQuery {
+    #[serde(default)]
     pub response_type: String,
     pub client_id: String,
+    #[serde(default)]
     pub redirect_uri: String,
     pub scope: Option<String>,
     pub state: Option<String>,
     pub code_challenge: Option<String>,
     pub code_challenge_method: Option<String>,
+    /// RFC 9126: Reference to parameters pushed to the PAR endpoint
+    pub request_uri: Option<String>,
+}
+
+impl AuthorizeQuery {
+    /// RFC 9126: Replace the request with the pushed parameters, or reject plain parameters
+    /// for clients that must use PAR
+    pub async fn resolve(self, pool: &DbPool) -> anyhow::Result<Self> {
+        let Some(request_uri) = self.request_uri else {
+            PARService::ensure_par_not_required(pool, &self.client_id).await?;
+            return Ok(self);
+        };
+
+        // Only client_id may accompany request_uri; everything else comes from the pushed request
+        let pushed = PARService::find_pushed_request(pool, &request_uri, &self.client_id).await?;
+        Ok(Self {
+            response_type: pushed.response_type,
+            client_id: self.client_id,
+            redirect_uri: pushed.redirect_uri,
+            scope: pushed.scope,
+            state: pushed.state,
+            code_challenge: pushed.code_challenge,
+            code_challenge_method: pushed.code_challenge_method,
+            request_uri: Some(request_uri),
+        })
+    }
 }
 
 #[derive(Deserialize, ToSchema)]
@@ -78,11 +108,12 @@ pub struct IntrospectResponse {
         ("scope" = Option<String>, Query, description = "Requested scopes"),
         ("state" = Option<String>, Query, description = "State parameter"),
         ("code_challenge" = Option<String>, Query, description = "PKCE code challenge"),
-        ("code_challenge_method" = Option<String>, Query, description = "PKCE challenge method")
+        ("code_challenge_method" = Option<String>, Query, description = "PKCE challenge method"),
+        ("request_uri" = Option<String>, Query, description = "Single-use request URI from the PAR endpoint; replaces all other parameters except client_id")
     ),
     responses(
         (status = 302, description = "Redirect to authorization page or back to client"),
-        (status = 400, description = "Invalid request", body = ErrorResponse)
+        (status = 400, description = "Invalid request, or unknown, expired, or reused request_uri", body = ErrorResponse)
     ),
     security(
         ("Bearer" = [])
@@ -93,6 +124,22 @@ pub async fn authorize(
     headers: HeaderMap,
     Query(params): Query<AuthorizeQuery>,
 ) -> impl IntoResponse {
+    // RFC 9126: The redirect URI is not trusted yet, so PAR errors are returned directly
+    let params = match params.resolve(&pool).await {
+        Ok(params) => params,
+        Err(e) => {
+            let error = match e.downcast_ref::<PARError>() {
+                Some(PARError::Required) => "invalid_request",
+                Some(_) => "invalid_request_uri",
+                None => "server_error",
+            };
+            return (StatusCode::BAD_REQUEST, ResponseJson(ErrorResponse {
+                error: error.to_string(),
+                error_description: Some(e.to_string()),
+            })).into_response();
+        }
+    };
+
     // OAuth 2.1 Compliance: Only authorization code flow is supported (implicit removed)
     if params.response_type != "code" {
         let error_url = format!(
@@ -170,6 +217,15 @@ pub async fn authorize(
     let auth_header = headers.get("authorization").and_then(|h| h.to_str().ok());
     let user_id = match get_user_from_token(&pool, auth_header).await {
         Ok(user_id) => user_id,
+        Err(_) if params.request_uri.is_some() => {
+            // The pushed request stays unused until the user comes back authenticated
+            let login_url = format!(
+                "/login?client_id={}&request_uri={}",
+                urlencoding::encode(&params.client_id),
+                urlencoding::encode(params.request_uri.as_deref().unwrap_or_default())
+            );
+            return Redirect::temporary(&login_url).into_response();
+        }
         Err(_) => {
             // Redirect to login with authorization request in query params
             let login_url = format!(
@@ -240,6 +296,16 @@ pub async fn authorize(
         }
     };
 
+    // RFC 9126: A request_uri authorizes exactly one code
+    if let Some(request_uri) = &params.request_uri {
+        if let Err(e) = PARService::consume_pushed_request(&pool, request_uri, &params.client_id).await {
+            return (StatusCode::BAD_REQUEST, ResponseJson(ErrorResponse {
+                error: "invalid_request_uri".to_string(),
+                error_description: Some(e.to_string()),
+            })).into_response();
+        }
+    }
+
     // Create authorization code with OAuth 2.1 compliant PKCE
     let challenge_method = params.code_challenge_method.clone()
         .or_else(|| Some("S256".to_string())); // Default to S256 per OAuth 2.1
@@ -286,11 +352,35 @@ pub async fn authorize(
     responses(
         (status = 200, description = "Token granted", body = crate::app::docs::oauth::TokenResponse),
         (status = 400, description = "Invalid request", body = ErrorResponse),
-        (status = 401, description = "Client authentication failed", body = ErrorResponse)
+        (status = 401, description = "Client authentication failed, or too many attempts (see Retry-After)", body = ErrorResponse)
     )
 )]
-pub async fn token(State(pool): State<DbPool>, headers: HeaderMap, Form(params): Form<TokenRequest>) -> impl IntoResponse {
-    match params.grant_type.as_str() {
+pub async fn token(
+    State(pool): State<DbPool>,
+    client: Option<Extension<ClientInfo>>,
+    headers: HeaderMap,
+    Form(params): Form<TokenRequest>,
+) -> impl IntoResponse {
+    let ip = client
+        .and_then(|Extension(client)| client.ip_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let client_id = params.client_id.clone();
+
+    let throttle = match TokenThrottleService::shared().await {
+        Ok(throttle) => Some(throttle),
+        Err(e) => {
+            tracing::warn!("Token throttling unavailable: {}", e);
+            None
+        }
+    };
+
+    if let Some(throttle) = &throttle {
+        if let Err(throttled) = throttle.attempt(&client_id, &ip).await {
+            return throttled_response(&throttled);
+        }
+    }
+
+    let response = match params.grant_type.as_str() {
         "authorization_code" => handle_authorization_code_grant(&pool, headers, params).await.into_response(),
         "refresh_token" => handle_refresh_token_grant(&pool, headers, params).await.into_response(),
         "client_credentials" => handle_client_credentials_grant(&pool, headers, params).await.into_response(),
@@ -309,7 +399,36 @@ pub async fn token(State(pool): State<DbPool>, headers: HeaderMap, Form(params):
             };
             (StatusCode::BAD_REQUEST, ResponseJson(error)).into_response()
         }
-    }.into_response()
+    };
+
+    if let Some(throttle) = &throttle {
+        match response.status() {
+            StatusCode::UNAUTHORIZED => {
+                throttle.record_failure(&pool, &client_id, &ip).await;
+            }
+            status if status.is_success() => throttle.record_success(&client_id, &ip).await,
+            _ => {}
+        }
+    }
+
+    response
+}
+
+/// RFC 6749 `invalid_client` for a throttled token request, with `Retry-After`
+fn throttled_response(throttled: &TokenThrottled) -> Response {
+    let error = ErrorResponse {
+        error: "invalid_client".to_string(),
+        error_description: Some(throttled.to_string()),
+    };
+
+    (
+        StatusCode::UNAUTHORIZED,
+        [
+            (header::RETRY_AFTER, throttled.retry_after.to_string()),
+            (header::WWW_AUTHENTICATE, "Basic realm=\"oauth\"".to_string()),
+        ],
+        ResponseJson(error),
+    ).into_response()
 }
 
 async fn handle_authorization_code_grant(pool: &DbPool, headers: HeaderMap, params: TokenRequest) -> impl IntoResponse + use<> {
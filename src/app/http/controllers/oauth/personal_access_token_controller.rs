@@ -10,7 +10,7 @@ use crate::database::DbPool;
 use crate::app::services::oauth::TokenService;
 use crate::app::services::auth_service::AuthService;
 use crate::app::utils::token_utils::TokenUtils;
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 use crate::app::models::oauth::{AccessToken};
 
 #[derive(Serialize, ToSchema)]
@@ -100,7 +100,7 @@ pub async fn create_personal_access_token(
 pub async fn list_personal_access_tokens(
     State(pool): State<DbPool>,
     headers: HeaderMap,
-    Query(mut params): Query<QueryParams>,
+    mut params: QueryParams,
 ) -> impl IntoResponse {
     let user_id = match get_authenticated_user(&pool, &headers).await {
         Ok(user_id) => user_id,
@@ -119,12 +119,7 @@ pub async fn list_personal_access_tokens(
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         },
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
@@ -11,7 +11,7 @@ use crate::app::services::oauth::ScopeService;
 use crate::app::services::auth_service::AuthService;
 use crate::app::models::oauth::{CreateScope, UpdateScope, Scope};
 use crate::app::utils::token_utils::TokenUtils;
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 
 #[derive(Serialize, ToSchema)]
 #[schema(description = "Error response for OAuth scope operations")]
@@ -125,7 +125,7 @@ pub async fn create_scope(
 pub async fn list_scopes(
     State(pool): State<DbPool>,
     headers: HeaderMap,
-    Query(params): Query<QueryParams>,
+    params: QueryParams,
 ) -> impl IntoResponse {
     // Verify authenticated access
     if let Err(e) = get_authenticated_user(&pool, &headers).await {
@@ -139,12 +139,7 @@ pub async fn list_scopes(
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         },
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
@@ -11,7 +11,7 @@ use chrono::{Utc};
 use crate::app::services::oauth::TokenService;
 use crate::app::services::auth_service::AuthService;
 use crate::app::utils::token_utils::TokenUtils;
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 use crate::app::models::oauth::{AccessToken};
 
 #[derive(Serialize, ToSchema)]
@@ -107,7 +107,7 @@ pub struct ExtendTokenRequest {
 pub async fn list_tokens(
     State(pool): State<DbPool>,
     headers: HeaderMap,
-    Query(params): Query<QueryParams>,
+    params: QueryParams,
 ) -> impl IntoResponse {
     // Verify admin access for listing all tokens
     if let Err(e) = verify_admin_access(&pool, &headers).await {
@@ -122,12 +122,13 @@ pub async fn list_tokens(
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         },
+        Err(e) if e.is::<QueryParamsError>() => QueryParamsError::response(&e),
         Err(e) => {
             let error = ErrorResponse {
                 error: "server_error".to_string(),
                 error_description: Some(e.to_string()),
             };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
+            (QueryParamsError::status_code(&e), ResponseJson(error)).into_response()
         }
     }
 }
@@ -487,7 +488,7 @@ pub async fn extend_token(
 pub async fn get_my_tokens(
     State(pool): State<DbPool>,
     headers: HeaderMap,
-    Query(mut params): Query<QueryParams>,
+    mut params: QueryParams,
 ) -> impl IntoResponse {
     let user_id = match get_authenticated_user(&pool, &headers).await {
         Ok(user_id) => user_id,
@@ -507,12 +508,13 @@ pub async fn get_my_tokens(
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         },
+        Err(e) if e.is::<QueryParamsError>() => QueryParamsError::response(&e),
         Err(e) => {
             let error = ErrorResponse {
                 error: "server_error".to_string(),
                 error_description: Some(e.to_string()),
             };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
+            (QueryParamsError::status_code(&e), ResponseJson(error)).into_response()
         }
     }
 }
@@ -7,7 +7,10 @@ use serde::Deserialize;
 use base64::Engine;
 use serde_json::{json, Value};
 use crate::database::DbPool;
-use crate::app::services::oauth::{TokenExchangeService, TokenExchangeRequest};
+use crate::app::services::oauth::{
+    TokenExchangeError, TokenExchangeService, TokenExchangeRequest, TOKEN_EXCHANGE_GRANT_TYPE,
+    SUPPORTED_ACTOR_TOKEN_TYPES, SUPPORTED_REQUESTED_TOKEN_TYPES, SUPPORTED_SUBJECT_TOKEN_TYPES,
+};
 
 /// RFC 8693: OAuth 2.0 Token Exchange Controller
 ///
@@ -37,7 +40,7 @@ pub async fn exchange_token(
     axum::extract::Form(form): axum::extract::Form<TokenExchangeFormRequest>,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
     // Validate grant type
-    if form.grant_type != "urn:ietf:params:oauth:grant-type:token-exchange" {
+    if form.grant_type != TOKEN_EXCHANGE_GRANT_TYPE {
         return Err((
             StatusCode::BAD_REQUEST,
             Json(json!({
@@ -92,7 +95,7 @@ pub async fn exchange_token(
             Err((
                 StatusCode::BAD_REQUEST,
                 Json(json!({
-                    "error": "invalid_grant",
+                    "error": TokenExchangeError::error_code(&err),
                     "error_description": err.to_string()
                 }))
             ))
@@ -106,19 +109,9 @@ pub async fn get_supported_token_types(
     State(_pool): State<DbPool>,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
     Ok(Json(json!({
-        "supported_subject_token_types": [
-            "urn:ietf:params:oauth:token-type:access_token",
-            "urn:ietf:params:oauth:token-type:refresh_token",
-            "urn:ietf:params:oauth:token-type:id_token",
-            "urn:ietf:params:oauth:token-type:jwt"
-        ],
-        "supported_actor_token_types": [
-            "urn:ietf:params:oauth:token-type:access_token",
-            "urn:ietf:params:oauth:token-type:jwt"
-        ],
-        "supported_requested_token_types": [
-            "urn:ietf:params:oauth:token-type:access_token"
-        ],
+        "supported_subject_token_types": SUPPORTED_SUBJECT_TOKEN_TYPES,
+        "supported_actor_token_types": SUPPORTED_ACTOR_TOKEN_TYPES,
+        "supported_requested_token_types": SUPPORTED_REQUESTED_TOKEN_TYPES,
         "supported_exchange_scenarios": [
             "delegation",
             "impersonation",
@@ -152,28 +145,18 @@ pub async fn validate_exchange_request(
     let mut validation_results = Vec::new();
 
     // Validate grant type
-    if request.grant_type != "urn:ietf:params:oauth:grant-type:token-exchange" {
+    if request.grant_type != TOKEN_EXCHANGE_GRANT_TYPE {
         validation_results.push("Invalid grant_type");
     }
 
     // Validate subject token type
-    let supported_subject_types = [
-        "urn:ietf:params:oauth:token-type:access_token",
-        "urn:ietf:params:oauth:token-type:refresh_token",
-        "urn:ietf:params:oauth:token-type:id_token",
-        "urn:ietf:params:oauth:token-type:jwt"
-    ];
-    if !supported_subject_types.contains(&request.subject_token_type.as_str()) {
+    if !SUPPORTED_SUBJECT_TOKEN_TYPES.contains(&request.subject_token_type.as_str()) {
         validation_results.push("Unsupported subject_token_type");
     }
 
     // Validate actor token type if present
     if let Some(actor_token_type) = &request.actor_token_type {
-        let supported_actor_types = [
-            "urn:ietf:params:oauth:token-type:access_token",
-            "urn:ietf:params:oauth:token-type:jwt"
-        ];
-        if !supported_actor_types.contains(&actor_token_type.as_str()) {
+        if !SUPPORTED_ACTOR_TOKEN_TYPES.contains(&actor_token_type.as_str()) {
             validation_results.push("Unsupported actor_token_type");
         }
 
@@ -185,7 +168,7 @@ pub async fn validate_exchange_request(
 
     // Validate requested token type
     if let Some(requested_type) = &request.requested_token_type {
-        if requested_type != "urn:ietf:params:oauth:token-type:access_token" {
+        if !SUPPORTED_REQUESTED_TOKEN_TYPES.contains(&requested_type.as_str()) {
             validation_results.push("Only access_token requested_token_type is supported");
         }
     }
@@ -219,39 +202,51 @@ async fn extract_client_credentials(
     form: &TokenExchangeFormRequest,
 ) -> Result<String, String> {
     // Try Authorization header first (Basic auth)
-    if let Ok(client_id) = extract_client_credentials_from_headers(headers) {
-        return Ok(client_id);
+    if let Ok((client_id, secret)) = extract_basic_credentials(headers) {
+        return verify_client_secret(pool, &client_id, &secret);
     }
 
     // Try form parameters
     if let Some(client_id) = &form.client_id {
         if let Some(secret) = &form.client_secret {
-            // Validate the client secret
-            use crate::app::services::oauth::{ClientService, ClientAuthService};
-            let client = ClientService::find_by_id(pool, client_id.clone())
-                .map_err(|_| "Database error".to_string())?
-                .ok_or_else(|| "Client not found".to_string())?;
-
-            if ClientAuthService::verify_client_secret(&client, secret)
-                .map_err(|_| "Invalid credentials".to_string())? {
-                return Ok(client_id.clone());
-            } else {
-                return Err("Invalid client credentials".to_string());
-            }
+            return verify_client_secret(pool, client_id, secret);
         }
-        // Public client (no secret required) - validate it exists
+        // Only public clients may authenticate without a secret
         use crate::app::services::oauth::ClientService;
-        if ClientService::find_by_id(pool, client_id.clone())
-            .map_err(|_| "Database error".to_string())?.is_some() {
-            return Ok(client_id.clone());
+        let client = ClientService::find_by_id(pool, client_id.clone())
+            .map_err(|_| "Database error".to_string())?
+            .ok_or_else(|| "Client not found".to_string())?;
+        if client.secret.is_some() {
+            return Err("Client secret required".to_string());
         }
+        return Ok(client_id.clone());
     }
 
     Err("Client authentication required".to_string())
 }
 
+/// Check a client secret, returning the authenticated client ID
+fn verify_client_secret(pool: &DbPool, client_id: &str, secret: &str) -> Result<String, String> {
+    use crate::app::services::oauth::{ClientService, ClientAuthService};
+    let client = ClientService::find_by_id(pool, client_id.to_string())
+        .map_err(|_| "Database error".to_string())?
+        .ok_or_else(|| "Client not found".to_string())?;
+
+    if ClientAuthService::verify_client_secret(&client, secret)
+        .map_err(|_| "Invalid credentials".to_string())? {
+        Ok(client_id.to_string())
+    } else {
+        Err("Invalid client credentials".to_string())
+    }
+}
+
 /// Extract client credentials from Authorization header
 fn extract_client_credentials_from_headers(headers: &HeaderMap) -> Result<String, String> {
+    extract_basic_credentials(headers).map(|(client_id, _)| client_id)
+}
+
+/// Extract client ID and secret from a Basic Authorization header
+fn extract_basic_credentials(headers: &HeaderMap) -> Result<(String, String), String> {
     let auth_header = headers
         .get("authorization")
         .ok_or_else(|| "Authorization header missing".to_string())?;
@@ -271,10 +266,9 @@ fn extract_client_credentials_from_headers(headers: &HeaderMap) -> Result<String
     let decoded_str = String::from_utf8(decoded_bytes)
         .map_err(|_| "Invalid UTF-8 in Authorization header".to_string())?;
 
-    let client_id = decoded_str
-        .split(':')
-        .next()
+    let (client_id, secret) = decoded_str
+        .split_once(':')
         .ok_or_else(|| "Invalid credential format".to_string())?;
 
-    Ok(client_id.to_string())
-}
\ No newline at end of file
+    Ok((client_id.to_string(), secret.to_string()))
+}
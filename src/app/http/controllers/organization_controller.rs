@@ -1,18 +1,20 @@
 use axum::{
-    extract::{State, Path, Query},
-    http::StatusCode,
+    extract::{Path, Query},
+    http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Json as ResponseJson},
     Extension,
 };
 use serde::Serialize;
 use ulid::Ulid;
-use crate::database::DbPool;
+use crate::app::http::middleware::tenant_middleware::TenantDb;
 
 use crate::app::models::organization::{CreateOrganization, UpdateOrganization, Organization};
 use crate::app::models::DieselUlid;
 use crate::app::services::organization_service::OrganizationService;
 use crate::app::http::requests::{CreateOrganizationRequest, UpdateOrganizationRequest};
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
+use crate::app::traits::{OptimisticLocking, StaleModelError};
+use crate::app::traits::optimistic_locking::if_match_version;
 
 #[derive(Serialize)]
 struct ErrorResponse {
@@ -46,19 +48,14 @@ struct MessageResponse {
     )
 )]
 pub async fn index(
-    State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    TenantDb(pool): TenantDb,
+    params: QueryParams,
 ) -> impl IntoResponse {
     match <Organization as QueryBuilderService<Organization>>::index(Query(params), &pool) {
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
@@ -78,7 +75,7 @@ pub async fn index(
         (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
     )
 )]
-pub async fn show(State(pool): State<DbPool>, Path(id): Path<String>) -> impl IntoResponse {
+pub async fn show(TenantDb(pool): TenantDb, Path(id): Path<String>) -> impl IntoResponse {
     let organization_id = match Ulid::from_string(&id) {
         Ok(id) => id,
         Err(_) => {
@@ -90,7 +87,11 @@ pub async fn show(State(pool): State<DbPool>, Path(id): Path<String>) -> impl In
     };
 
     match OrganizationService::find_by_id(&pool, organization_id.to_string()) {
-        Ok(Some(organization)) => (StatusCode::OK, ResponseJson(organization.to_response())).into_response(),
+        Ok(Some(organization)) => (
+            StatusCode::OK,
+            [(header::ETAG, organization.etag())],
+            ResponseJson(organization.to_response()),
+        ).into_response(),
         Ok(None) => {
             let error = ErrorResponse {
                 error: "Organization not found".to_string(),
@@ -120,7 +121,7 @@ pub async fn show(State(pool): State<DbPool>, Path(id): Path<String>) -> impl In
     )
 )]
 pub async fn store(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Extension(auth_user): Extension<crate::app::http::middleware::auth_guard::AuthUser>,
     request: CreateOrganizationRequest,
 ) -> impl IntoResponse {
@@ -176,22 +177,26 @@ pub async fn store(
     path = "/api/organizations/{id}",
     tag = "Organizations",
     summary = "Update organization",
-    description = "Update an existing organization with the provided information",
+    description = "Update an existing organization with the provided information. The update must name the version it is based on, either as `If-Match: \"<lock_version>\"` or as `lock_version` in the body; it is rejected with 409 if the organization has changed since.",
     params(
-        ("id" = String, Path, description = "Organization unique identifier (ULID format)")
+        ("id" = String, Path, description = "Organization unique identifier (ULID format)"),
+        ("If-Match" = Option<String>, Header, description = "ETag from a previous read, e.g. \"3\"")
     ),
     request_body = crate::app::http::requests::UpdateOrganizationRequest,
     responses(
         (status = 200, description = "Organization updated successfully", body = crate::app::models::organization::OrganizationResponse),
         (status = 400, description = "Invalid ID format or validation error", body = crate::app::docs::ErrorResponse),
         (status = 404, description = "Organization not found", body = crate::app::docs::ErrorResponse),
+        (status = 409, description = "Organization was modified since the given version", body = crate::app::docs::ErrorResponse),
+        (status = 428, description = "No lock version given", body = crate::app::docs::ErrorResponse),
         (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
     )
 )]
 pub async fn update(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Extension(auth_user): Extension<crate::app::http::middleware::auth_guard::AuthUser>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     request: UpdateOrganizationRequest,
 ) -> impl IntoResponse {
     let organization_id = match Ulid::from_string(&id) {
@@ -204,6 +209,16 @@ pub async fn update(
         }
     };
 
+    let lock_version = match if_match_version(&headers).or(request.lock_version) {
+        Some(lock_version) => lock_version,
+        None => {
+            let error = ErrorResponse {
+                error: "Send the organization's lock_version in an If-Match header or the request body".to_string(),
+            };
+            return (StatusCode::PRECONDITION_REQUIRED, ResponseJson(error)).into_response();
+        }
+    };
+
     // Convert parent_id from String to DieselUlid if provided
     let parent_id = match request.parent_id {
         Some(id_str) => {
@@ -239,15 +254,21 @@ pub async fn update(
         tax_number: None,
         website: None,
         is_active: request.is_active,
+        lock_version: Some(lock_version),
     };
 
     match OrganizationService::update(&pool, organization_id.to_string(), payload, &auth_user.user_id).await {
-        Ok(organization) => (StatusCode::OK, ResponseJson(organization.to_response())).into_response(),
+        Ok(organization) => (
+            StatusCode::OK,
+            [(header::ETAG, organization.etag())],
+            ResponseJson(organization.to_response()),
+        ).into_response(),
         Err(e) => {
+            let status = StaleModelError::status_code(&e).unwrap_or(StatusCode::BAD_REQUEST);
             let error = ErrorResponse {
                 error: e.to_string(),
             };
-            (StatusCode::BAD_REQUEST, ResponseJson(error)).into_response()
+            (status, ResponseJson(error)).into_response()
         }
     }
 }
@@ -269,7 +290,7 @@ pub async fn update(
     )
 )]
 pub async fn destroy(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Extension(auth_user): Extension<crate::app::http::middleware::auth_guard::AuthUser>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
@@ -304,7 +325,7 @@ pub async fn destroy(
         (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
     )
 )]
-pub async fn children(State(pool): State<DbPool>, Path(id): Path<String>) -> impl IntoResponse {
+pub async fn children(TenantDb(pool): TenantDb, Path(id): Path<String>) -> impl IntoResponse {
     match OrganizationService::find_children(&pool, id) {
         Ok(organizations) => {
             let responses: Vec<_> = organizations.into_iter().map(|o| o.to_response()).collect();
@@ -330,7 +351,7 @@ pub async fn children(State(pool): State<DbPool>, Path(id): Path<String>) -> imp
         (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
     )
 )]
-pub async fn roots(State(pool): State<DbPool>) -> impl IntoResponse {
+pub async fn roots(TenantDb(pool): TenantDb) -> impl IntoResponse {
     match OrganizationService::find_root_organizations(&pool) {
         Ok(organizations) => {
             let responses: Vec<_> = organizations.into_iter().map(|o| o.to_response()).collect();
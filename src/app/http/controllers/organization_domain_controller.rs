@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Path, State, Query},
+    extract::{Path, Query},
     http::StatusCode,
     response::IntoResponse,
     Json,
@@ -7,11 +7,13 @@ use axum::{
 };
 use serde_json::json;
 
+use crate::app::http::merge_patch::MergePatch;
 use crate::app::models::organization_domain::{CreateOrganizationDomain, UpdateOrganizationDomain};
 use crate::app::services::organization_domain_service::OrganizationDomainService;
-use crate::app::query_builder::{QueryBuilderService, QueryParams};
+use crate::app::query_builder::{QueryBuilderService, QueryParams, QueryParamsError};
 use crate::app::models::organization_domain::OrganizationDomain;
 use crate::database::DbPool;
+use crate::app::http::middleware::tenant_middleware::TenantDb;
 
 /// List all organization domains with query builder support
 ///
@@ -50,15 +52,12 @@ use crate::database::DbPool;
     tag = "Organization Domains"
 )]
 pub async fn index(
-    State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    TenantDb(pool): TenantDb,
+    params: QueryParams,
 ) -> impl IntoResponse {
     match <OrganizationDomain as QueryBuilderService<OrganizationDomain>>::index(Query(params), &pool) {
         Ok(result) => (StatusCode::OK, Json(result)).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({"error": e.to_string()}))
-        ).into_response(),
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
@@ -92,7 +91,7 @@ pub async fn index(
     tag = "Organization Domains"
 )]
 pub async fn store(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Extension(auth_user): Extension<crate::app::http::middleware::auth_guard::AuthUser>,
     Json(data): Json<CreateOrganizationDomain>,
 ) -> impl IntoResponse {
@@ -149,7 +148,7 @@ pub async fn store(
     tag = "Organization Domains"
 )]
 pub async fn show(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
     match OrganizationDomainService::find_by_id(&pool, id) {
@@ -168,10 +167,12 @@ pub async fn show(
 /// Update an organization domain
 ///
 /// Updates an existing organization domain with change tracking and activity logging.
-/// Only non-null fields in the request are updated.
+/// Omitted fields are left unchanged and nullable fields sent as `null` are
+/// cleared, the same as a merge patch.
 ///
 /// # Implementation Details
 /// - Partial updates supported (only provided fields are changed)
+/// - `null` clears `code` and `description`
 /// - Validates code uniqueness (excluding current record)
 /// - Logs changes with before/after values to `activity_log`
 /// - Auto-updates `updated_at` and `updated_by_id` fields
@@ -199,14 +200,57 @@ pub async fn show(
     tag = "Organization Domains"
 )]
 pub async fn update(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Extension(auth_user): Extension<crate::app::http::middleware::auth_guard::AuthUser>,
     Path(id): Path<String>,
     Json(data): Json<UpdateOrganizationDomain>,
 ) -> impl IntoResponse {
+    apply_update(&pool, &auth_user, id, data).await
+}
+
+/// Partially update an organization domain
+///
+/// Accepts a JSON Merge Patch (RFC 7396): omitted fields are left unchanged
+/// and `null` clears a nullable field. Only the fields sent are validated.
+#[utoipa::path(
+    patch,
+    path = "/api/organization-domains/{id}",
+    params(
+        ("id" = String, Path, description = "Organization domain ID in ULID format")
+    ),
+    request_body(content = UpdateOrganizationDomain, content_type = "application/merge-patch+json"),
+    responses(
+        (status = 200, description = "Organization domain updated successfully", body = crate::app::models::organization_domain::OrganizationDomainResponse),
+        (status = 400, description = "Duplicate code", body = serde_json::Value),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Organization domain not found or deleted", body = serde_json::Value),
+        (status = 415, description = "Body is not application/merge-patch+json", body = serde_json::Value),
+        (status = 422, description = "Validation error", body = crate::app::http::form_request::ValidationErrorResponse),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Organization Domains"
+)]
+pub async fn patch(
+    TenantDb(pool): TenantDb,
+    Extension(auth_user): Extension<crate::app::http::middleware::auth_guard::AuthUser>,
+    Path(id): Path<String>,
+    MergePatch(data): MergePatch<UpdateOrganizationDomain>,
+) -> impl IntoResponse {
+    apply_update(&pool, &auth_user, id, data).await
+}
+
+async fn apply_update(
+    pool: &DbPool,
+    auth_user: &crate::app::http::middleware::auth_guard::AuthUser,
+    id: String,
+    data: UpdateOrganizationDomain,
+) -> axum::response::Response {
     // Check if code is unique (if provided)
     if let Some(Some(ref code)) = data.code {
-        match OrganizationDomainService::is_code_unique(&pool, code, Some(&id)) {
+        match OrganizationDomainService::is_code_unique(pool, code, Some(&id)) {
             Ok(false) => {
                 return (
                     StatusCode::BAD_REQUEST,
@@ -223,7 +267,7 @@ pub async fn update(
         }
     }
 
-    match OrganizationDomainService::update(&pool, id, data, &auth_user.user_id).await {
+    match OrganizationDomainService::update(pool, id, data, &auth_user.user_id).await {
         Ok(domain) => (StatusCode::OK, Json(domain.to_response())).into_response(),
         Err(e) => {
             if e.to_string().contains("NotFound") || e.to_string().contains("not found") {
@@ -277,7 +321,7 @@ pub async fn update(
     tag = "Organization Domains"
 )]
 pub async fn destroy(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Extension(auth_user): Extension<crate::app::http::middleware::auth_guard::AuthUser>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Path, State, Query},
+    extract::{Path, Query},
     http::{StatusCode, HeaderMap},
     response::Json,
 };
@@ -9,9 +9,9 @@ use crate::app::http::requests::organization_position_requests::{
     CreateOrganizationPositionRequest, UpdateOrganizationPositionRequest
 };
 use crate::app::services::organization_position_service::OrganizationPositionService;
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 use crate::app::models::organization_position::OrganizationPosition;
-use crate::database::DbPool;
+use crate::app::http::middleware::tenant_middleware::TenantDb;
 
 /// List organization positions with filtering, sorting and pagination
 #[utoipa::path(
@@ -46,15 +46,15 @@ use crate::database::DbPool;
     )
 )]
 pub async fn index(
-    State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    TenantDb(pool): TenantDb,
+    params: QueryParams,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
     match <OrganizationPosition as QueryBuilderService<OrganizationPosition>>::index(Query(params), &pool) {
         Ok(result) => Ok(Json(serde_json::json!(result))),
         Err(e) => {
             tracing::error!("Failed to fetch organization positions: {}", e);
             Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
+                QueryParamsError::status_code(&e),
                 Json(json!({"error": "Failed to fetch organization positions"})),
             ))
         }
@@ -78,7 +78,7 @@ pub async fn index(
     )
 )]
 pub async fn show(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(id): Path<String>,
     headers: HeaderMap,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
@@ -128,7 +128,7 @@ pub async fn show(
     )
 )]
 pub async fn store(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     headers: HeaderMap,
     request: CreateOrganizationPositionRequest,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
@@ -197,7 +197,7 @@ pub async fn store(
     )
 )]
 pub async fn update(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(id): Path<String>,
     headers: HeaderMap,
     request: UpdateOrganizationPositionRequest,
@@ -272,7 +272,7 @@ pub async fn update(
     )
 )]
 pub async fn destroy(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(id): Path<String>,
     headers: HeaderMap,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
@@ -329,7 +329,7 @@ pub async fn destroy(
     )
 )]
 pub async fn activate(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(id): Path<String>,
     headers: HeaderMap,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
@@ -381,7 +381,7 @@ pub async fn activate(
     )
 )]
 pub async fn deactivate(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(id): Path<String>,
     headers: HeaderMap,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
@@ -441,9 +441,9 @@ pub async fn deactivate(
     )
 )]
 pub async fn by_level(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(organization_position_level_id): Path<String>,
-    Query(mut params): Query<QueryParams>,
+    mut params: QueryParams,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
     // Add organization_position_level_id filter to the query parameters
     params.filter.insert("organization_position_level_id".to_string(), serde_json::json!(organization_position_level_id));
@@ -453,7 +453,7 @@ pub async fn by_level(
         Err(e) => {
             tracing::error!("Failed to fetch organization positions by level: {}", e);
             Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
+                QueryParamsError::status_code(&e),
                 Json(json!({"error": "Failed to fetch organization positions"})),
             ))
         }
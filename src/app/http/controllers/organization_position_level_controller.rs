@@ -1,16 +1,16 @@
 use axum::{
-    extract::{State, Path, Query},
+    extract::{Path, Query},
     http::{StatusCode, HeaderMap},
     response::{IntoResponse, Json as ResponseJson},
 };
 use serde::Serialize;
 use ulid::Ulid;
-use crate::database::DbPool;
+use crate::app::http::middleware::tenant_middleware::TenantDb;
 
 use crate::app::models::organization_position_level::{CreateOrganizationPositionLevel, UpdateOrganizationPositionLevel, OrganizationPositionLevel};
 use crate::app::services::organization_position_level_service::OrganizationPositionLevelService;
 use crate::app::http::requests::{CreateOrganizationPositionLevelRequest, UpdateOrganizationPositionLevelRequest};
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 
 #[derive(Serialize)]
 struct ErrorResponse {
@@ -44,19 +44,14 @@ struct MessageResponse {
     )
 )]
 pub async fn index(
-    State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    TenantDb(pool): TenantDb,
+    params: QueryParams,
 ) -> impl IntoResponse {
     match <OrganizationPositionLevel as QueryBuilderService<OrganizationPositionLevel>>::index(Query(params), &pool) {
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
@@ -77,7 +72,7 @@ pub async fn index(
         (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
     )
 )]
-pub async fn show(State(pool): State<DbPool>, Path(id): Path<String>, headers: HeaderMap) -> impl IntoResponse {
+pub async fn show(TenantDb(pool): TenantDb, Path(id): Path<String>, headers: HeaderMap) -> impl IntoResponse {
     let organization_position_level_id = match Ulid::from_string(&id) {
         Ok(id) => id,
         Err(_) => {
@@ -124,7 +119,7 @@ pub async fn show(State(pool): State<DbPool>, Path(id): Path<String>, headers: H
         (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
     )
 )]
-pub async fn store(State(pool): State<DbPool>, headers: HeaderMap, request: CreateOrganizationPositionLevelRequest) -> impl IntoResponse {
+pub async fn store(TenantDb(pool): TenantDb, headers: HeaderMap, request: CreateOrganizationPositionLevelRequest) -> impl IntoResponse {
     let payload = CreateOrganizationPositionLevel {
         organization_id: request.organization_id,
         code: request.code,
@@ -168,7 +163,7 @@ pub async fn store(State(pool): State<DbPool>, headers: HeaderMap, request: Crea
     )
 )]
 pub async fn update(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(id): Path<String>,
     headers: HeaderMap,
     request: UpdateOrganizationPositionLevelRequest,
@@ -215,7 +210,7 @@ pub async fn update(
         (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
     )
 )]
-pub async fn destroy(State(pool): State<DbPool>, Path(id): Path<String>, headers: HeaderMap) -> impl IntoResponse {
+pub async fn destroy(TenantDb(pool): TenantDb, Path(id): Path<String>, headers: HeaderMap) -> impl IntoResponse {
     let organization_position_level_id = match Ulid::from_string(&id) {
         Ok(id) => id,
         Err(_) => {
@@ -270,7 +265,7 @@ pub async fn destroy(State(pool): State<DbPool>, Path(id): Path<String>, headers
         (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
     )
 )]
-pub async fn activate(State(pool): State<DbPool>, Path(id): Path<String>, headers: HeaderMap) -> impl IntoResponse {
+pub async fn activate(TenantDb(pool): TenantDb, Path(id): Path<String>, headers: HeaderMap) -> impl IntoResponse {
     // Get current organization position level and update its active status
     // Extract user ID from authentication context
     let user_id = crate::app::utils::token_utils::TokenUtils::extract_user_id_from_headers(&headers);
@@ -330,7 +325,7 @@ pub async fn activate(State(pool): State<DbPool>, Path(id): Path<String>, header
         (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
     )
 )]
-pub async fn deactivate(State(pool): State<DbPool>, Path(id): Path<String>, headers: HeaderMap) -> impl IntoResponse {
+pub async fn deactivate(TenantDb(pool): TenantDb, Path(id): Path<String>, headers: HeaderMap) -> impl IntoResponse {
     // Get current organization position level and update its active status
     // Extract user ID from authentication context
     let user_id = crate::app::utils::token_utils::TokenUtils::extract_user_id_from_headers(&headers);
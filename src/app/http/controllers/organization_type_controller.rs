@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Path, State, Query},
+    extract::Path,
     http::StatusCode,
     response::IntoResponse,
     Json,
@@ -9,9 +9,9 @@ use serde_json::json;
 
 use crate::app::models::organization_type::{CreateOrganizationType, UpdateOrganizationType};
 use crate::app::services::organization_type_service::OrganizationTypeService;
-use crate::app::query_builder::{QueryBuilderService, QueryParams};
+use crate::app::query_builder::{QueryCache, QueryParams};
 use crate::app::models::organization_type::OrganizationType;
-use crate::database::DbPool;
+use crate::app::http::middleware::tenant_middleware::{CurrentTenant, TenantDb};
 
 /// List all organization types with query builder support
 ///
@@ -26,9 +26,10 @@ use crate::database::DbPool;
 /// - `include=domain,organizations` - Eager load relationships
 ///
 /// # Implementation
-/// - Uses QueryBuilderService trait for consistent API
+/// - Uses the query builder through QueryCache for consistent API
 /// - Filters out soft-deleted records automatically
 /// - Returns paginated results with metadata
+/// - Caches results briefly; `X-Cache-Status` reports `hit` or `miss`
 #[utoipa::path(
     get,
     path = "/api/organization-types",
@@ -51,11 +52,22 @@ use crate::database::DbPool;
     tag = "Organization Types"
 )]
 pub async fn index(
-    State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    TenantDb(pool): TenantDb,
+    tenant: Option<Extension<CurrentTenant>>,
+    params: QueryParams,
 ) -> impl IntoResponse {
-    match <OrganizationType as QueryBuilderService<OrganizationType>>::index(Query(params), &pool) {
-        Ok(result) => (StatusCode::OK, Json(result)).into_response(),
+    let tenant = tenant.map(|Extension(tenant)| tenant.id);
+    let result = match QueryCache::new().await.map(|cache| cache.for_tenant(tenant)) {
+        Ok(cache) => cache.index::<OrganizationType>(params, &pool).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok((result, cache_status)) => (
+            StatusCode::OK,
+            [("x-cache-status", cache_status.as_str())],
+            Json(result),
+        ).into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({"error": e.to_string()}))
@@ -76,7 +88,7 @@ pub async fn index(
     tag = "Organization Types"
 )]
 pub async fn store(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Extension(auth_user): Extension<crate::app::http::middleware::auth_guard::AuthUser>,
     Json(data): Json<CreateOrganizationType>,
 ) -> impl IntoResponse {
@@ -123,7 +135,7 @@ pub async fn store(
     tag = "Organization Types"
 )]
 pub async fn show(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
     match OrganizationTypeService::find_by_id(&pool, id) {
@@ -156,7 +168,7 @@ pub async fn show(
     tag = "Organization Types"
 )]
 pub async fn update(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Extension(auth_user): Extension<crate::app::http::middleware::auth_guard::AuthUser>,
     Path(id): Path<String>,
     Json(data): Json<UpdateOrganizationType>,
@@ -231,7 +243,7 @@ pub async fn update(
     tag = "Organization Types"
 )]
 pub async fn destroy(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Extension(auth_user): Extension<crate::app::http::middleware::auth_guard::AuthUser>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
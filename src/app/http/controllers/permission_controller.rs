@@ -14,7 +14,7 @@ use crate::app::services::sys_model_has_permission_service::SysModelHasPermissio
 use crate::app::models::user::User;
 use crate::app::models::HasModelType;
 use crate::app::models::DieselUlid;
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 
 #[derive(Deserialize)]
 pub struct CreatePermissionRequest {
@@ -104,14 +104,15 @@ impl From<Permission> for PermissionData {
 )]
 pub async fn index(
     State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>
+    params: QueryParams
 ) -> impl IntoResponse {
     match <Permission as QueryBuilderService<Permission>>::index(Query(params), &pool) {
         Ok(result) => {
             (StatusCode::OK, Json(serde_json::json!(result))).into_response()
         }
+        Err(e) if e.is::<QueryParamsError>() => QueryParamsError::response(&e),
         Err(e) => {
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({
+            (QueryParamsError::status_code(&e), Json(json!({
                 "error": "Failed to fetch permissions",
                 "message": e.to_string()
             }))).into_response()
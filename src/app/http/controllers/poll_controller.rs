@@ -0,0 +1,130 @@
+use axum::{
+    extract::{State, Path},
+    http::StatusCode,
+    response::{IntoResponse, Json as ResponseJson},
+    Extension, Json,
+};
+use crate::database::DbPool;
+
+use crate::app::http::middleware::auth_guard::{AuthUser, VerifiedUser};
+use crate::app::services::poll_service::{CastVoteRequest, CreatePollRequest, PollError, PollService};
+use crate::app::http::responses::ServiceErrorResponse;
+
+fn poll_error_response(e: anyhow::Error) -> axum::response::Response {
+    ServiceErrorResponse::new(PollError::status_code(&e), e).into_response()
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/conversations/{id}/polls",
+    tag = "Polls",
+    summary = "Create a poll",
+    description = "Attach a poll to a message the authenticated user sent in the conversation. The question and options stay encrypted; `option_count` tells the server how many options votes may select from.",
+    params(
+        ("id" = String, Path, description = "Conversation unique identifier (ULID format)")
+    ),
+    request_body = CreatePollRequest,
+    responses(
+        (status = 201, description = "Poll created", body = crate::app::models::polls::PollResponse),
+        (status = 403, description = "Not a participant of the conversation, or email address not verified", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Message not found", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Invalid poll definition", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn store(
+    State(pool): State<DbPool>,
+    auth_user: VerifiedUser,
+    Path(id): Path<String>,
+    Json(payload): Json<CreatePollRequest>,
+) -> impl IntoResponse {
+    match PollService::create(&pool, &id, &auth_user.user_id, payload).await {
+        Ok(poll) => (StatusCode::CREATED, ResponseJson(poll.to_response())).into_response(),
+        Err(e) => poll_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/polls/{id}/votes",
+    tag = "Polls",
+    summary = "Cast or change a vote",
+    description = "Cast a vote on a poll, replacing any earlier vote by the same user. Single-choice polls accept exactly one option index.",
+    params(
+        ("id" = String, Path, description = "Poll unique identifier (ULID format)")
+    ),
+    request_body = CastVoteRequest,
+    responses(
+        (status = 200, description = "Vote recorded, updated results returned", body = crate::app::services::poll_service::PollResults),
+        (status = 403, description = "Not a participant of the conversation", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Poll not found", body = crate::app::docs::ErrorResponse),
+        (status = 409, description = "Poll is closed", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Invalid selection or device", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn vote(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(payload): Json<CastVoteRequest>,
+) -> impl IntoResponse {
+    match PollService::vote(&pool, &id, &auth_user.user_id, payload).await {
+        Ok(results) => (StatusCode::OK, ResponseJson(results)).into_response(),
+        Err(e) => poll_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/polls/{id}/close",
+    tag = "Polls",
+    summary = "Close a poll",
+    description = "Close a poll so no further votes are accepted. Allowed for the poll creator and conversation admins.",
+    params(
+        ("id" = String, Path, description = "Poll unique identifier (ULID format)")
+    ),
+    responses(
+        (status = 200, description = "Poll closed, final results returned", body = crate::app::services::poll_service::PollResults),
+        (status = 403, description = "Not allowed to close the poll", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Poll not found", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn close(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match PollService::close(&pool, &id, &auth_user.user_id).await {
+        Ok(results) => (StatusCode::OK, ResponseJson(results)).into_response(),
+        Err(e) => poll_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/polls/{id}/results",
+    tag = "Polls",
+    summary = "Get poll results",
+    description = "Retrieve per-option vote counts and the authenticated user's current selection",
+    params(
+        ("id" = String, Path, description = "Poll unique identifier (ULID format)")
+    ),
+    responses(
+        (status = 200, description = "Poll results", body = crate::app::services::poll_service::PollResults),
+        (status = 403, description = "Not a participant of the conversation", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Poll not found", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn results(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match PollService::results(&pool, &id, &auth_user.user_id) {
+        Ok(results) => (StatusCode::OK, ResponseJson(results)).into_response(),
+        Err(e) => poll_error_response(e),
+    }
+}
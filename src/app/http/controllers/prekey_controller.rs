@@ -0,0 +1,109 @@
+use axum::{
+    extract::{State, Path},
+    http::StatusCode,
+    response::{IntoResponse, Json as ResponseJson},
+    Extension, Json,
+};
+use crate::database::DbPool;
+
+use crate::app::http::middleware::auth_guard::AuthUser;
+use crate::app::services::prekey_service::{FetchBundleRequest, PrekeyError, PrekeyService, UploadKeysRequest};
+use crate::app::http::responses::ServiceErrorResponse;
+
+fn prekey_error_response(e: anyhow::Error) -> axum::response::Response {
+    ServiceErrorResponse::new(PrekeyError::status_code(&e), e).into_response()
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/devices/{id}/keys",
+    tag = "Encryption Keys",
+    summary = "Upload device keys",
+    description = "Publish a signed prekey and a batch of one-time prekeys for one of the authenticated user's devices. One-time prekey IDs may never be reused for a device, even after the earlier key was consumed. The identity key, if sent, must match the one the device registered with.",
+    params(
+        ("id" = String, Path, description = "Device unique identifier (ULID format)")
+    ),
+    request_body = UploadKeysRequest,
+    responses(
+        (status = 200, description = "Keys published", body = crate::app::services::prekey_service::PrekeyStatus),
+        (status = 409, description = "Prekey ID already used or identity key mismatch", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Malformed keys or device does not belong to the user", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn upload(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(payload): Json<UploadKeysRequest>,
+) -> impl IntoResponse {
+    let result = PrekeyService::new().and_then(|service| service.upload(&pool, &auth_user.user_id, &id, payload));
+
+    match result {
+        Ok(status) => (StatusCode::OK, ResponseJson(status)).into_response(),
+        Err(e) => prekey_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/devices/{id}/keys",
+    tag = "Encryption Keys",
+    summary = "Get prekey status",
+    description = "Report how many unused one-time prekeys one of the authenticated user's devices has left and whether it should upload more",
+    params(
+        ("id" = String, Path, description = "Device unique identifier (ULID format)")
+    ),
+    responses(
+        (status = 200, description = "Prekey supply", body = crate::app::services::prekey_service::PrekeyStatus),
+        (status = 422, description = "Device does not belong to the user", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn status(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let result = PrekeyService::new().and_then(|service| service.status(&pool, &auth_user.user_id, &id));
+
+    match result {
+        Ok(status) => (StatusCode::OK, ResponseJson(status)).into_response(),
+        Err(e) => prekey_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/devices/{id}/prekey-bundle",
+    tag = "Encryption Keys",
+    summary = "Fetch prekey bundle",
+    description = "Fetch the keys needed to start a session with a device. Each call atomically consumes one of the device's one-time prekeys, which is never handed out again; once they run out the bundle is returned without one. The bundle names the strongest cipher and key agreement both devices support that the algorithm compatibility matrix allows; the session must use it. The device owner receives a `prekeys.low` event on their private channel when the supply runs low.",
+    params(
+        ("id" = String, Path, description = "Target device unique identifier (ULID format)")
+    ),
+    request_body = FetchBundleRequest,
+    responses(
+        (status = 200, description = "Prekey bundle", body = crate::app::services::prekey_service::PrekeyBundleResponse),
+        (status = 404, description = "Device not found", body = crate::app::docs::ErrorResponse),
+        (status = 409, description = "Devices share no compatible encryption suite", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Requesting device does not belong to the user", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn bundle(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(payload): Json<FetchBundleRequest>,
+) -> impl IntoResponse {
+    let service = match PrekeyService::new() {
+        Ok(service) => service,
+        Err(e) => return prekey_error_response(e),
+    };
+
+    match service.fetch_bundle(&pool, &auth_user.user_id, &id, payload).await {
+        Ok(bundle) => (StatusCode::OK, ResponseJson(bundle)).into_response(),
+        Err(e) => prekey_error_response(e),
+    }
+}
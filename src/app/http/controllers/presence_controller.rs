@@ -0,0 +1,47 @@
+use axum::{
+    extract::{State, Path},
+    http::StatusCode,
+    response::{IntoResponse, Json as ResponseJson},
+    Extension,
+};
+use crate::database::DbPool;
+
+use crate::app::http::middleware::auth_guard::AuthUser;
+use crate::app::services::conversation_service::ConversationError;
+use crate::app::services::device_presence_service::DevicePresenceService;
+use crate::app::http::responses::ServiceErrorResponse;
+
+fn presence_error_response(e: anyhow::Error) -> axum::response::Response {
+    ServiceErrorResponse::new(ConversationError::status_code(&e), e).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/presence",
+    tag = "Presence",
+    summary = "Get user device presence",
+    description = "Retrieve the presence of each of a user's devices and the user's overall status. Presence is updated when a device connects to `/ws` with `device_id` set, on every `ping` heartbeat, and when its last connection closes; devices whose heartbeats stop are marked offline. Only the user and people sharing a conversation with them can view it, and invisible devices appear offline to others.",
+    params(
+        ("id" = String, Path, description = "User unique identifier (ULID format)")
+    ),
+    responses(
+        (status = 200, description = "User presence", body = crate::app::services::device_presence_service::UserPresence),
+        (status = 403, description = "No conversation shared with the user", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn show(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let service = match DevicePresenceService::new() {
+        Ok(service) => service,
+        Err(e) => return presence_error_response(e),
+    };
+
+    match service.for_user(&pool, &auth_user.user_id, &id) {
+        Ok(presence) => (StatusCode::OK, ResponseJson(presence)).into_response(),
+        Err(e) => presence_error_response(e),
+    }
+}
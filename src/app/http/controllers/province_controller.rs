@@ -10,7 +10,8 @@ use crate::database::DbPool;
 use crate::app::models::province::{CreateProvince, UpdateProvince, Province};
 use crate::app::services::province_service::ProvinceService;
 use crate::app::http::requests::{CreateProvinceRequest, UpdateProvinceRequest};
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::http::responses::ServiceErrorResponse;
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 
 #[derive(Serialize)]
 struct ErrorResponse {
@@ -45,18 +46,13 @@ struct MessageResponse {
 )]
 pub async fn index(
     State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    params: QueryParams,
 ) -> impl IntoResponse {
     match <Province as QueryBuilderService<Province>>::index(Query(params), &pool) {
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
@@ -115,10 +111,7 @@ pub async fn store(State(pool): State<DbPool>, request: CreateProvinceRequest) -
     match ProvinceService::create(&pool, payload, &system_user_id) {
         Ok(province) => (StatusCode::CREATED, ResponseJson(province.to_response())).into_response(),
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::BAD_REQUEST, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::BAD_REQUEST, e).into_response()
         }
     }
 }
@@ -137,10 +130,7 @@ pub async fn update(
     match ProvinceService::update(&pool, id, payload) {
         Ok(province) => (StatusCode::OK, ResponseJson(province.to_response())).into_response(),
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::BAD_REQUEST, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::BAD_REQUEST, e).into_response()
         }
     }
 }
@@ -164,10 +154,7 @@ pub async fn destroy(State(pool): State<DbPool>, Path(id): Path<String>) -> impl
             (StatusCode::OK, ResponseJson(message)).into_response()
         }
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, e).into_response()
         }
     }
 }
@@ -197,7 +184,7 @@ pub async fn destroy(State(pool): State<DbPool>, Path(id): Path<String>) -> impl
 pub async fn by_country(
     State(pool): State<DbPool>,
     Path(country_id): Path<String>,
-    Query(mut params): Query<QueryParams>,
+    mut params: QueryParams,
 ) -> impl IntoResponse {
     // Add country_id filter to the query parameters
     params.filter.insert("country_id".to_string(), serde_json::json!(country_id));
@@ -206,11 +193,6 @@ pub async fn by_country(
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
\ No newline at end of file
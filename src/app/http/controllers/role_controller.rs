@@ -14,7 +14,7 @@ use crate::app::services::sys_model_has_role_service::SysModelHasRoleService;
 use crate::app::models::user::User;
 use crate::app::models::HasModelType;
 use crate::app::models::DieselUlid;
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 
 #[derive(Deserialize)]
 pub struct CreateRoleRequest {
@@ -102,14 +102,15 @@ impl From<Role> for RoleData {
 )]
 pub async fn index(
     State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>
+    params: QueryParams
 ) -> impl IntoResponse {
     match <Role as QueryBuilderService<Role>>::index(Query(params), &pool) {
         Ok(result) => {
             (StatusCode::OK, Json(serde_json::json!(result))).into_response()
         }
+        Err(e) if e.is::<QueryParamsError>() => QueryParamsError::response(&e),
         Err(e) => {
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({
+            (QueryParamsError::status_code(&e), Json(json!({
                 "error": "Failed to fetch roles",
                 "message": e.to_string()
             }))).into_response()
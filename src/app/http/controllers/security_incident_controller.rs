@@ -0,0 +1,58 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json as ResponseJson},
+    Extension,
+};
+use serde::Serialize;
+use crate::database::DbPool;
+
+use crate::app::http::middleware::auth_guard::AuthUser;
+use crate::app::models::security_incidents::SecurityIncident;
+use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::services::security_incident_service::{SecurityIncidentError, SecurityIncidentService};
+use crate::app::http::responses::ServiceErrorResponse;
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+fn security_incident_error_response(e: anyhow::Error) -> axum::response::Response {
+    ServiceErrorResponse::new(SecurityIncidentError::status_code(&e), e).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/security-incidents",
+    tag = "Security Incidents",
+    summary = "List security incidents",
+    description = "Retrieve recorded security incidents such as account lockouts after repeated failed logins, refresh token reuse, and mTLS certificate mismatches. Newest first by default. High and critical incidents are also pushed to administrators as notifications and on the `admin.security` WebSocket channel. Only administrators can list incidents.",
+    params(
+        ("page" = Option<u32>, Query, description = "Page number for pagination (default: 1)"),
+        ("per_page" = Option<u32>, Query, description = "Items per page (default: 15, max: 100)"),
+        ("sort" = Option<String>, Query, description = "Sort fields. Available: id, incident_type, severity, is_resolved, resolved_at, created_at, updated_at. Example: '-created_at'"),
+        ("filter" = Option<serde_json::Value>, Query, description = "Filters. Available: id, device_id, user_id, conversation_id, incident_type, severity, is_resolved, resolved_at, created_at, updated_at. Examples: filter[severity][in]=high,critical, filter[is_resolved][eq]=false"),
+        ("fields" = Option<String>, Query, description = "Field selection. Available: id, device_id, user_id, conversation_id, incident_type, severity, is_resolved, resolved_at, resolution_notes, metadata, created_at, updated_at"),
+    ),
+    responses(
+        (status = 200, description = "List of security incidents", body = Vec<SecurityIncident>),
+        (status = 400, description = "Invalid query parameters", body = crate::app::docs::ErrorResponse),
+        (status = 403, description = "Not an administrator", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn index(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    params: QueryParams,
+) -> impl IntoResponse {
+    if let Err(e) = SecurityIncidentService::ensure_admin(&pool, &auth_user.user_id) {
+        return security_incident_error_response(e);
+    }
+
+    match <SecurityIncident as QueryBuilderService<SecurityIncident>>::index(Query(params), &pool) {
+        Ok(result) => (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, ResponseJson(ErrorResponse { error: e.to_string() })).into_response(),
+    }
+}
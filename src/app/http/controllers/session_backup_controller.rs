@@ -0,0 +1,166 @@
+use axum::{
+    extract::{State, Path},
+    http::StatusCode,
+    response::{IntoResponse, Json as ResponseJson},
+    Extension, Json,
+};
+use crate::database::DbPool;
+
+use crate::app::http::middleware::auth_guard::AuthUser;
+use crate::app::services::session_backup_service::{
+    BackupKeyRequest, RestoreSessionBackupRequest, SessionBackupError, SessionBackupService, UploadSessionBackupRequest,
+};
+use crate::app::http::responses::ServiceErrorResponse;
+
+fn session_backup_error_response(e: anyhow::Error) -> axum::response::Response {
+    ServiceErrorResponse::new(SessionBackupError::status_code(&e), e).into_response()
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/devices/{id}/session-backups",
+    tag = "Session Backups",
+    summary = "Upload session backup",
+    description = "Store an encrypted session backup from one of the authenticated user's devices. The sessions are sealed client-side with a backup key that is itself wrapped with the user's recovery secret; the server only keeps both ciphertexts and the SHA-256 of the backup key. Uploads are limited in size and in how many unexpired backups a user may keep.",
+    params(
+        ("id" = String, Path, description = "Device unique identifier (ULID format)")
+    ),
+    request_body = UploadSessionBackupRequest,
+    responses(
+        (status = 201, description = "Backup stored", body = crate::app::models::device_session_backups::DeviceSessionBackupResponse),
+        (status = 409, description = "Backup quota reached", body = crate::app::docs::ErrorResponse),
+        (status = 413, description = "Backup too large", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Malformed backup or device does not belong to the user", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn store(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(payload): Json<UploadSessionBackupRequest>,
+) -> impl IntoResponse {
+    let result = SessionBackupService::new().and_then(|service| service.upload(&pool, &auth_user.user_id, &id, payload));
+
+    match result {
+        Ok(backup) => (StatusCode::CREATED, ResponseJson(backup)).into_response(),
+        Err(e) => session_backup_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/session-backups",
+    tag = "Session Backups",
+    summary = "List session backups",
+    description = "List the authenticated user's unexpired session backups without their encrypted contents",
+    responses(
+        (status = 200, description = "Session backups", body = Vec<crate::app::models::device_session_backups::DeviceSessionBackupResponse>),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn index(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> impl IntoResponse {
+    let result = SessionBackupService::new().and_then(|service| service.list(&pool, &auth_user.user_id));
+
+    match result {
+        Ok(backups) => (StatusCode::OK, ResponseJson(backups)).into_response(),
+        Err(e) => session_backup_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/session-backups/{id}/key",
+    tag = "Session Backups",
+    summary = "Fetch wrapped backup key",
+    description = "Fetch the wrapped backup key of a session backup for one of the authenticated user's devices. The device unwraps it with the user's recovery secret before restoring.",
+    params(
+        ("id" = String, Path, description = "Session backup unique identifier (ULID format)")
+    ),
+    request_body = BackupKeyRequest,
+    responses(
+        (status = 200, description = "Wrapped backup key", body = crate::app::services::session_backup_service::BackupKeyResponse),
+        (status = 404, description = "Backup not found or expired", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Device does not belong to the user", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn key(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(payload): Json<BackupKeyRequest>,
+) -> impl IntoResponse {
+    let result = SessionBackupService::new().and_then(|service| service.backup_key(&pool, &auth_user.user_id, &id, payload));
+
+    match result {
+        Ok(key) => (StatusCode::OK, ResponseJson(key)).into_response(),
+        Err(e) => session_backup_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/session-backups/{id}/restore",
+    tag = "Session Backups",
+    summary = "Restore session backup",
+    description = "Return the encrypted sessions of a backup to one of the authenticated user's devices once it proves it unwrapped the backup key by sending the key's SHA-256. Failed proofs are recorded as security incidents.",
+    params(
+        ("id" = String, Path, description = "Session backup unique identifier (ULID format)")
+    ),
+    request_body = RestoreSessionBackupRequest,
+    responses(
+        (status = 200, description = "Encrypted sessions", body = crate::app::services::session_backup_service::RestoredSessionBackup),
+        (status = 403, description = "Backup key proof does not match", body = crate::app::docs::ErrorResponse),
+        (status = 404, description = "Backup not found or expired", body = crate::app::docs::ErrorResponse),
+        (status = 422, description = "Backup failed its integrity check or device does not belong to the user", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn restore(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(payload): Json<RestoreSessionBackupRequest>,
+) -> impl IntoResponse {
+    let service = match SessionBackupService::new() {
+        Ok(service) => service,
+        Err(e) => return session_backup_error_response(e),
+    };
+
+    match service.restore(&pool, &auth_user.user_id, &id, payload).await {
+        Ok(restored) => (StatusCode::OK, ResponseJson(restored)).into_response(),
+        Err(e) => session_backup_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/session-backups/{id}",
+    tag = "Session Backups",
+    summary = "Delete session backup",
+    description = "Delete one of the authenticated user's session backups and its wrapped key, freeing backup quota",
+    params(
+        ("id" = String, Path, description = "Session backup unique identifier (ULID format)")
+    ),
+    responses(
+        (status = 204, description = "Backup deleted"),
+        (status = 404, description = "Backup not found or expired", body = crate::app::docs::ErrorResponse),
+        (status = 500, description = "Internal server error", body = crate::app::docs::ErrorResponse)
+    )
+)]
+pub async fn destroy(
+    State(pool): State<DbPool>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let result = SessionBackupService::new().and_then(|service| service.delete(&pool, &auth_user.user_id, &id));
+
+    match result {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => session_backup_error_response(e),
+    }
+}
@@ -9,7 +9,7 @@ use crate::app::models::DieselUlid;
 use crate::app::models::sys_model_has_permission::{CreateSysModelHasPermission, UpdateSysModelHasPermission, SysModelHasPermissionResponse, SysModelHasPermission};
 use crate::app::services::sys_model_has_permission_service::SysModelHasPermissionService;
 use crate::app::http::requests::{CreateSysModelHasPermissionRequest, UpdateSysModelHasPermissionRequest};
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 
 use crate::app::docs::{ErrorResponse, MessageResponse};
 
@@ -36,18 +36,13 @@ use crate::app::docs::{ErrorResponse, MessageResponse};
 )]
 pub async fn index(
     State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    params: QueryParams,
 ) -> impl IntoResponse {
     match <SysModelHasPermission as QueryBuilderService<SysModelHasPermission>>::index(Query(params), &pool) {
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
@@ -321,7 +316,7 @@ pub async fn destroy(State(pool): State<DbPool>, Path(id): Path<String>) -> impl
 pub async fn by_model(
     State(pool): State<DbPool>,
     Path((model_type, model_id)): Path<(String, String)>,
-    Query(mut params): Query<QueryParams>,
+    mut params: QueryParams,
 ) -> impl IntoResponse {
     // Add model filters to the query parameters
     params.filter.insert("model_type".to_string(), serde_json::json!(model_type));
@@ -331,11 +326,6 @@ pub async fn by_model(
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
\ No newline at end of file
@@ -10,7 +10,7 @@ use crate::app::models::sys_model_has_role::{CreateSysModelHasRole, UpdateSysMod
 use crate::app::services::sys_model_has_role_service::SysModelHasRoleService;
 use crate::app::models::model_types;
 use crate::app::http::requests::{CreateSysModelHasRoleRequest, UpdateSysModelHasRoleRequest};
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 
 use crate::app::docs::{ErrorResponse, MessageResponse};
 
@@ -37,18 +37,13 @@ use crate::app::docs::{ErrorResponse, MessageResponse};
 )]
 pub async fn index(
     State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    params: QueryParams,
 ) -> impl IntoResponse {
     match <SysModelHasRole as QueryBuilderService<SysModelHasRole>>::index(Query(params), &pool) {
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
@@ -322,7 +317,7 @@ pub async fn destroy(State(pool): State<DbPool>, Path(id): Path<String>) -> impl
 pub async fn by_model(
     State(pool): State<DbPool>,
     Path((model_type, model_id)): Path<(String, String)>,
-    Query(mut params): Query<QueryParams>,
+    mut params: QueryParams,
 ) -> impl IntoResponse {
     // Validate model type
     if !model_types::is_valid_model_type(&model_type) {
@@ -340,11 +335,6 @@ pub async fn by_model(
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
\ No newline at end of file
@@ -5,7 +5,7 @@ use axum::{
 };
 use serde_json::json;
 use crate::database::DbPool;
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 use crate::app::models::user::User;
 use crate::app::services::user_service::UserService;
 use crate::app::http::middleware::activity_logging_middleware::activity_logger_from_request;
@@ -33,12 +33,15 @@ use crate::app::http::middleware::activity_logging_middleware::activity_logger_f
 )]
 pub async fn index(
     State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    params: QueryParams,
 ) -> impl IntoResponse {
     match <User as QueryBuilderService<User>>::index(Query(params), &pool) {
         Ok(result) => {
             (StatusCode::OK, Json(serde_json::json!(result))).into_response()
         },
+        Err(e) if e.is::<QueryParamsError>() => {
+            (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e.to_string() }))).into_response()
+        }
         Err(_) => {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
                 "error": "Failed to fetch users"
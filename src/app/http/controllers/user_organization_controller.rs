@@ -1,9 +1,9 @@
 use axum::{
-    extract::{Path, State, Query},
+    extract::{Path, Query},
     http::{StatusCode, HeaderMap},
     response::{IntoResponse, Json},
 };
-use crate::database::DbPool;
+use crate::app::http::middleware::tenant_middleware::TenantDb;
 use ulid::Ulid;
 use crate::app::models::DieselUlid;
 use crate::app::models::user_organization::UserOrganization;
@@ -12,7 +12,7 @@ use crate::app::http::requests::user_organization_requests::{
     UpdateUserOrganizationRequest,
 };
 use crate::app::services::user_organization_service::UserOrganizationService;
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 
 /// Get all user organization relationships with filtering and pagination
 #[utoipa::path(
@@ -37,8 +37,8 @@ use crate::app::query_builder::{QueryParams, QueryBuilderService};
     )
 )]
 pub async fn index(
-    State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    TenantDb(pool): TenantDb,
+    params: QueryParams,
 ) -> impl IntoResponse {
     // Authentication is handled by middleware
 
@@ -46,6 +46,9 @@ pub async fn index(
         Ok(result) => {
             (StatusCode::OK, Json(serde_json::json!(result))).into_response()
         },
+        Err(e) if e.is::<QueryParamsError>() => {
+            (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e.to_string() }))).into_response()
+        }
         Err(_) => {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
                 "error": "Failed to fetch user organization relationships"
@@ -71,7 +74,7 @@ pub async fn index(
     )
 )]
 pub async fn show(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
     // Authentication is handled by middleware
@@ -117,7 +120,7 @@ pub async fn show(
     )
 )]
 pub async fn store(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     headers: HeaderMap,
     request: CreateUserOrganizationRequest,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
@@ -172,7 +175,7 @@ pub async fn store(
     )
 )]
 pub async fn update(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(id): Path<String>,
     request: UpdateUserOrganizationRequest,
 ) -> impl IntoResponse {
@@ -267,7 +270,7 @@ pub async fn update(
     )
 )]
 pub async fn destroy(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
     // Authentication is handled by middleware
@@ -327,7 +330,7 @@ pub async fn destroy(
     )
 )]
 pub async fn transfer(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(id): Path<String>,
     Json(payload): Json<serde_json::Value>,
 ) -> impl IntoResponse {
@@ -422,7 +425,7 @@ pub async fn transfer(
     )
 )]
 pub async fn activate(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
     // Authentication is handled by middleware
@@ -481,7 +484,7 @@ pub async fn activate(
     )
 )]
 pub async fn deactivate(
-    State(pool): State<DbPool>,
+    TenantDb(pool): TenantDb,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
     // Authentication is handled by middleware
@@ -10,7 +10,8 @@ use crate::database::DbPool;
 use crate::app::models::village::{CreateVillage, UpdateVillage, Village};
 use crate::app::services::village_service::VillageService;
 use crate::app::http::requests::{CreateVillageRequest, UpdateVillageRequest};
-use crate::app::query_builder::{QueryParams, QueryBuilderService};
+use crate::app::http::responses::ServiceErrorResponse;
+use crate::app::query_builder::{QueryParams, QueryBuilderService, QueryParamsError};
 
 #[derive(Serialize)]
 struct ErrorResponse {
@@ -46,18 +47,13 @@ struct MessageResponse {
 )]
 pub async fn index(
     State(pool): State<DbPool>,
-    Query(params): Query<QueryParams>,
+    params: QueryParams,
 ) -> impl IntoResponse {
     match <Village as QueryBuilderService<Village>>::index(Query(params), &pool) {
         Ok(result) => {
             (StatusCode::OK, ResponseJson(serde_json::json!(result))).into_response()
         }
-        Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
-        }
+        Err(e) => QueryParamsError::response(&e),
     }
 }
 
@@ -147,10 +143,7 @@ pub async fn store(
     match VillageService::create(&pool, payload, &auth_user.user_id).await {
         Ok(village) => (StatusCode::CREATED, ResponseJson(village.to_response())).into_response(),
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::BAD_REQUEST, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::BAD_REQUEST, e).into_response()
         }
     }
 }
@@ -203,10 +196,7 @@ pub async fn update(
     match VillageService::update(&pool, id, payload) {
         Ok(village) => (StatusCode::OK, ResponseJson(village.to_response())).into_response(),
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::BAD_REQUEST, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::BAD_REQUEST, e).into_response()
         }
     }
 }
@@ -242,10 +232,7 @@ pub async fn destroy(State(pool): State<DbPool>, Path(id): Path<String>) -> impl
             (StatusCode::OK, ResponseJson(message)).into_response()
         }
         Err(e) => {
-            let error = ErrorResponse {
-                error: e.to_string(),
-            };
-            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(error)).into_response()
+            ServiceErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, e).into_response()
         }
     }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use axum::{
     extract::{State, Extension, Form, Path, Query},
+    http::HeaderMap,
     response::{IntoResponse, Redirect},
 };
 use serde::{Deserialize};
@@ -7,6 +8,8 @@ use serde_json::{json, Value};
 use crate::database::DbPool;
 use crate::app::services::session::SessionStore;
 use crate::app::services::auth_service::{AuthService, LoginResponse};
+use crate::app::services::login_fingerprint_service::ClientFingerprint;
+use crate::app::http::middleware::trusted_proxy_middleware::ClientInfo;
 use crate::app::services::user_service::UserService;
 use crate::app::http::responses::template_response::TemplateResponse;
 use crate::app::models::user::{LoginRequest, CreateUser, ForgotPasswordRequest, ResetPasswordRequest, ChangePasswordRequest};
@@ -60,6 +63,11 @@ pub struct TokenQuery {
     pub token: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ConfirmDeviceForm {
+    pub code: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct MfaVerifyForm {
     pub code: String,
@@ -156,6 +164,31 @@ pub async fn show_forgot_password(
         TemplateResponse::new("auth/forgot-password", &data).with_layout("layouts/auth").into_response()
     }
 
+pub async fn show_confirm_device(
+        Extension(session): Extension<SessionStore>,
+    ) -> impl IntoResponse {
+        if session.get_string("device_confirmation_challenge_id").await.is_none() {
+            return Redirect::to("/auth/login").into_response();
+        }
+
+        let methods = session.get("device_confirmation_methods").await.unwrap_or(Value::Null);
+        let uses_mfa = methods.as_array().is_some_and(|methods| !methods.iter().any(|method| method == "email"));
+
+        let data = json!({
+            "title": "Confirm Device - RustAxum",
+            "page_title": "Confirm This Device",
+            "subtitle": "We don't recognize this device yet",
+            "header_icon": "fas fa-shield-alt",
+            "uses_mfa": uses_mfa,
+            "error_message": session.get("error").await,
+            "csrf_token": session.token().await
+        });
+
+        session.forget("error").await;
+
+        TemplateResponse::new("auth/confirm-device", &data).with_layout("layouts/auth").into_response()
+    }
+
 pub async fn show_reset_password(
         Path(token): Path<String>,
         Extension(session): Extension<SessionStore>,
@@ -249,6 +282,8 @@ pub async fn show_change_password(
 pub async fn login(
         State(pool): State<DbPool>,
         Extension(session): Extension<SessionStore>,
+        client: Option<Extension<ClientInfo>>,
+        headers: HeaderMap,
         Form(form): Form<LoginForm>,
     ) -> impl IntoResponse {
         // Validate form
@@ -274,7 +309,9 @@ pub async fn login(
 
         tracing::info!("Attempting login for email: {}", form.email);
 
-        match AuthService::login(&pool, login_request).await {
+        let fingerprint = ClientFingerprint::from_headers(&headers, client.and_then(|Extension(client)| client.ip));
+
+        match AuthService::login(&pool, login_request, &fingerprint).await {
             Ok(LoginResponse::Success(response)) => {
                 tracing::info!("Login successful for user: {}", response.user.name);
 
@@ -342,6 +379,22 @@ pub async fn login(
                 // Redirect to MFA verification page
                 Redirect::to("/mfa/verify-page").into_response()
             }
+            Ok(LoginResponse::DeviceConfirmationRequired(confirmation_response)) => {
+                tracing::info!("Device confirmation required for user: {}", confirmation_response.user_id);
+
+                // Keep the challenge in the session for the confirm-device form
+                session.put("device_confirmation_user_id", Value::String(confirmation_response.user_id)).await;
+                session.put("device_confirmation_challenge_id", Value::String(confirmation_response.challenge_id)).await;
+                session.put("device_confirmation_methods", json!(confirmation_response.methods)).await;
+                if let Some(redirect) = form.redirect {
+                    session.put("device_confirmation_redirect", Value::String(redirect)).await;
+                }
+
+                // Regenerate session ID for security
+                session.regenerate().await.ok();
+
+                Redirect::to("/auth/confirm-device").into_response()
+            }
             Err(e) => {
                 session.flash("error", Value::String(e.to_string())).await;
                 session.flash("old_input", json!({"email": form.email})).await;
@@ -614,6 +667,50 @@ pub async fn dashboard(
         }
     }
 
+pub async fn confirm_device(
+    State(pool): State<DbPool>,
+    Extension(session): Extension<SessionStore>,
+    Form(form): Form<ConfirmDeviceForm>,
+) -> impl IntoResponse {
+    let (Some(user_id), Some(challenge_id)) = (
+        session.get_string("device_confirmation_user_id").await,
+        session.get_string("device_confirmation_challenge_id").await,
+    ) else {
+        session.flash("error", Value::String("Session expired. Please log in again.".to_string())).await;
+        return Redirect::to("/auth/login").into_response();
+    };
+
+    match AuthService::confirm_new_device(&pool, user_id.clone(), &challenge_id, &form.code).await {
+        Ok(response) => {
+            tracing::info!("Device confirmed for user: {}", user_id);
+
+            // Store user authentication in session
+            session.put("user_id", Value::String(response.user.id.to_string())).await;
+            session.put("authenticated", Value::Bool(true)).await;
+            session.put("user_name", Value::String(response.user.name.clone())).await;
+            session.put("user_email", Value::String(response.user.email.clone())).await;
+
+            // Clear the challenge
+            session.forget("device_confirmation_user_id").await;
+            session.forget("device_confirmation_challenge_id").await;
+            session.forget("device_confirmation_methods").await;
+            let redirect_url = session.forget("device_confirmation_redirect").await
+                .and_then(|redirect| redirect.as_str().map(str::to_string))
+                .unwrap_or_else(|| "/dashboard".to_string());
+
+            // Regenerate session ID for security
+            session.regenerate().await.ok();
+
+            Redirect::to(&redirect_url).into_response()
+        }
+        Err(e) => {
+            tracing::warn!("Device confirmation failed: {}", e);
+            session.flash("error", Value::String(e.to_string())).await;
+            Redirect::to("/auth/confirm-device").into_response()
+        }
+    }
+}
+
 pub async fn verify_mfa_web(
     State(pool): State<DbPool>,
     Extension(session): Extension<SessionStore>,
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use axum::{
-    extract::{FromRequest, Request},
+    extract::Request,
     http::StatusCode,
     response::{IntoResponse, Json},
 };
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use utoipa::ToSchema;
 
+use crate::app::http::json_body::form_body;
 use crate::app::validation::{ValidationRules, ValidationErrors, make_validator};
 
 /// Response format for validation errors
@@ -76,12 +77,7 @@ pub async fn extract_form_request<T>(req: Request) -> Result<T, ValidationErrorR
 where
     T: FormRequest + Serialize,
 {
-    let Json(mut payload): Json<T> = Json::from_request(req, &())
-        .await
-        .map_err(|_| ValidationErrorResponse {
-            message: "Invalid JSON format.".to_string(),
-            errors: HashMap::new(),
-        })?;
+    let mut payload: T = form_body(req, &(), &T::messages()).await?;
 
     // Check authorization first
     if !payload.authorize() {
@@ -124,12 +120,11 @@ macro_rules! impl_form_request_extractor {
                 state: &S,
             ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
                 async move {
-                let axum::Json(mut payload): axum::Json<$name> = axum::Json::from_request(req, state)
-                    .await
-                    .map_err(|_| $crate::app::http::form_request::ValidationErrorResponse {
-                        message: "Invalid JSON format.".to_string(),
-                        errors: std::collections::HashMap::new(),
-                    })?;
+                let mut payload: $name = $crate::app::http::json_body::form_body(
+                    req,
+                    state,
+                    &<$name as $crate::app::http::form_request::FormRequest>::messages(),
+                ).await?;
 
                 // Check authorization first
                 if !<$name as $crate::app::http::form_request::FormRequest>::authorize(&payload) {
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::de::DeserializeOwned;
+use serde_json::{error::Category, Value};
+use serde_path_to_error::Segment;
+
+use crate::app::http::form_request::ValidationErrorResponse;
+use crate::app::validation::ValidationErrors;
+
+/// JSON request body whose deserialization errors are answered as validation errors
+///
+/// Where `axum::Json` answers a wrong type or a missing field with plain
+/// text, this answers 422 with the validation envelope, keyed by the field's
+/// dot path: `{"errors": {"items.0.quantity": {"integer": "..."}}}`.
+pub struct JsonBody<T>(pub T);
+
+impl<T, S> FromRequest<S> for JsonBody<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !is_json(req.headers()) {
+            return Err((
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                Json(serde_json::json!({ "error": "Expected Content-Type: application/json" })),
+            ).into_response());
+        }
+
+        let body = Bytes::from_request(req, state).await.map_err(IntoResponse::into_response)?;
+        from_json_slice(&body, &HashMap::new())
+            .map(JsonBody)
+            .map_err(IntoResponse::into_response)
+    }
+}
+
+/// Read a form request body, answering parse failures with field-level errors
+///
+/// `messages` overrides the default message per `field.rule`, as
+/// `FormRequest::messages` does for validation rules.
+pub async fn form_body<T, S>(req: Request, state: &S, messages: &HashMap<&'static str, &'static str>) -> Result<T, ValidationErrorResponse>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    if !is_json(req.headers()) {
+        return Err(invalid_json());
+    }
+
+    let body = Bytes::from_request(req, state).await.map_err(|_| invalid_json())?;
+    from_json_slice(&body, messages)
+}
+
+/// Whether the request body is declared as JSON, including `+json` media types
+pub fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|media_type| media_type.trim().to_ascii_lowercase())
+        .is_some_and(|media_type| {
+            media_type == "application/json" || (media_type.starts_with("application/") && media_type.ends_with("+json"))
+        })
+}
+
+pub fn from_json_slice<T: DeserializeOwned>(body: &[u8], messages: &HashMap<&'static str, &'static str>) -> Result<T, ValidationErrorResponse> {
+    let mut deserializer = serde_json::Deserializer::from_slice(body);
+    let value = serde_path_to_error::deserialize(&mut deserializer).map_err(|e| body_errors(&e, messages))?;
+    deserializer.end().map_err(|_| invalid_json())?;
+    Ok(value)
+}
+
+pub fn from_json_value<T: DeserializeOwned>(value: Value, messages: &HashMap<&'static str, &'static str>) -> Result<T, ValidationErrorResponse> {
+    serde_path_to_error::deserialize(value).map_err(|e| body_errors(&e, messages))
+}
+
+/// Validation envelope for a body that failed to deserialize
+pub fn body_errors(
+    error: &serde_path_to_error::Error<serde_json::Error>,
+    messages: &HashMap<&'static str, &'static str>,
+) -> ValidationErrorResponse {
+    let inner = error.inner();
+    if !matches!(inner.classify(), Category::Data) {
+        return invalid_json();
+    }
+
+    let path = dot_path(error.path());
+    let (field, rule, message) = describe(&path, &serde_message(inner));
+    let message = messages.get(format!("{}.{}", field, rule).as_str())
+        .map(|message| message.to_string())
+        .unwrap_or(message);
+
+    let mut errors = ValidationErrors::new();
+    errors.add(&field, rule, &message);
+    errors.finalize();
+
+    ValidationErrorResponse {
+        message: errors.message,
+        errors: errors.errors,
+    }
+}
+
+fn invalid_json() -> ValidationErrorResponse {
+    ValidationErrorResponse {
+        message: "Invalid JSON format.".to_string(),
+        errors: HashMap::new(),
+    }
+}
+
+/// `items.0.quantity` for the value that failed, matching validation rule keys
+fn dot_path(path: &serde_path_to_error::Path) -> String {
+    path.iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(index.to_string()),
+            Segment::Map { key } => Some(key.clone()),
+            Segment::Enum { variant } => Some(variant.clone()),
+            Segment::Unknown => None,
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// serde's message without the ` at line 1 column 12` position
+fn serde_message(error: &serde_json::Error) -> String {
+    let message = error.to_string();
+    if error.line() == 0 {
+        return message;
+    }
+
+    let position = format!(" at line {} column {}", error.line(), error.column());
+    message.strip_suffix(&position).map(str::to_string).unwrap_or(message)
+}
+
+fn join(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_string()
+    } else {
+        format!("{}.{}", path, field)
+    }
+}
+
+/// Field, rule and message for one serde data error
+///
+/// Rule names follow the validator's, so `messages` keys such as
+/// `age.integer` apply whether a rule or deserialization rejected the value.
+fn describe(path: &str, message: &str) -> (String, &'static str, String) {
+    // serde names the missing or unknown member itself; the path is its parent
+    if let Some(name) = backticked(message, "missing field `") {
+        let field = join(path, name);
+        let message = format!("{} is required.", field);
+        return (field, "required", message);
+    }
+    if let Some(name) = backticked(message, "unknown field `") {
+        let field = join(path, name);
+        let message = format!("The {} field is not allowed.", field);
+        return (field, "unknown", message);
+    }
+
+    let field = if path.is_empty() { "body".to_string() } else { path.to_string() };
+
+    if let Some((actual, expected)) = message.strip_prefix("invalid type: ").and_then(|rest| rest.rsplit_once(", expected ")) {
+        let (rule, expected) = expected_type(expected);
+        let message = format!("The {} must be {}, got {}.", field, expected, actual);
+        return (field, rule, message);
+    }
+    if let Some((_, expected)) = message.strip_prefix("unknown variant ").and_then(|rest| rest.split_once(", expected ")) {
+        let message = format!("The {} must be {}.", field, expected);
+        return (field, "in", message);
+    }
+
+    let message = format!("The {} is invalid: {}.", field, message.trim_end_matches('.'));
+    (field, "invalid", message)
+}
+
+/// The member name in messages like ``missing field `name` ``
+fn backticked<'a>(message: &'a str, prefix: &str) -> Option<&'a str> {
+    message.strip_prefix(prefix)?.split('`').next()
+}
+
+/// Validation rule and description for serde's name of the expected type
+fn expected_type(expected: &str) -> (&'static str, String) {
+    match expected {
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => {
+            ("integer", "an integer".to_string())
+        }
+        "f32" | "f64" => ("numeric", "a number".to_string()),
+        "a string" | "a borrowed string" | "a character" | "char" => ("string", "a string".to_string()),
+        "a boolean" => ("boolean", "true or false".to_string()),
+        "a sequence" => ("array", "an array".to_string()),
+        "a map" => ("object", "an object".to_string()),
+        expected if expected.starts_with("struct ") => ("object", "an object".to_string()),
+        expected => ("type", expected.to_string()),
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::app::http::form_request::{FormRequest, ValidationErrorResponse};
+use crate::app::http::json_body::from_json_value;
+use crate::app::validation::{make_validator, ValidationRules};
+
+/// Media type of a JSON Merge Patch (RFC 7396)
+pub const MERGE_PATCH_CONTENT_TYPE: &str = "application/merge-patch+json";
+
+/// Partial update sent as `application/merge-patch+json`
+///
+/// Omitted members leave a field untouched and `null` clears it, which maps
+/// onto `UpdateX` payloads whose nullable columns are `Option<Option<T>>`
+/// deserialized with [`nullable`]. Only the members present in the patch are
+/// validated, so a `required` rule rejects `null` without requiring the field.
+pub struct MergePatch<T>(pub T);
+
+impl<T, S> FromRequest<S> for MergePatch<T>
+where
+    T: FormRequest,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !is_merge_patch(req.headers()) {
+            return Err((
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                Json(serde_json::json!({
+                    "error": format!("Expected Content-Type: {}", MERGE_PATCH_CONTENT_TYPE)
+                })),
+            ).into_response());
+        }
+
+        let body = Bytes::from_request(req, state).await.map_err(IntoResponse::into_response)?;
+        let patch: Value = serde_json::from_slice(&body)
+            .map_err(|_| invalid("Invalid JSON format.").into_response())?;
+        if !patch.is_object() {
+            return Err(invalid("A merge patch must be a JSON object.").into_response());
+        }
+
+        let mut payload: T = from_json_value(patch.clone(), &T::messages())
+            .map_err(IntoResponse::into_response)?;
+
+        if !payload.authorize() {
+            return Err(invalid("This action is unauthorized.").into_response());
+        }
+        payload.prepare_for_validation();
+
+        let data = serde_json::to_value(&payload)
+            .map_err(|_| invalid("Failed to serialize data for validation.").into_response())?;
+        if let Err(errors) = make_validator(data, provided_rules(T::rules(), &patch)).validate().await {
+            return Err(payload.failed_validation(errors).into_response());
+        }
+
+        Ok(MergePatch(payload))
+    }
+}
+
+/// Whether the request body is declared as a merge patch
+pub fn is_merge_patch(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case(MERGE_PATCH_CONTENT_TYPE))
+}
+
+/// Rules for the members present in `patch`, keyed by their top-level field
+pub fn provided_rules(rules: ValidationRules, patch: &Value) -> ValidationRules {
+    let Some(members) = patch.as_object() else {
+        return HashMap::new();
+    };
+
+    rules
+        .into_iter()
+        .filter(|(field, _)| {
+            let member = field.split(['.', '[']).next().unwrap_or(field);
+            members.contains_key(member)
+        })
+        .collect()
+}
+
+/// Apply `patch` to `target` as RFC 7396 describes
+///
+/// Objects merge member by member, `null` removes a member, and any other
+/// value replaces the target outright. Use it for JSON columns, where a patch
+/// should reach into the stored document rather than replace it.
+pub fn apply(target: &mut Value, patch: &Value) {
+    let Value::Object(members) = patch else {
+        *target = patch.clone();
+        return;
+    };
+
+    if !target.is_object() {
+        *target = Value::Object(serde_json::Map::new());
+    }
+
+    if let Value::Object(target_members) = target {
+        for (name, value) in members {
+            if value.is_null() {
+                target_members.remove(name);
+            } else {
+                apply(target_members.entry(name.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+/// Deserialize a nullable field so `null` becomes `Some(None)`
+///
+/// Pair with `#[serde(default)]`, which leaves an omitted field `None`:
+/// `#[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]`
+///
+/// The attribute is on the `UpdateX` payload itself, so a plain JSON `PUT`
+/// of the same payload also clears a field sent as `null` instead of
+/// ignoring it.
+pub fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+fn invalid(message: &str) -> ValidationErrorResponse {
+    ValidationErrorResponse {
+        message: message.to_string(),
+        errors: HashMap::new(),
+    }
+}
@@ -0,0 +1,287 @@
+use axum::{
+    body::{Body, HttpBody},
+    extract::{Request, State},
+    http::{header, HeaderMap},
+    middleware::Next,
+    response::Response,
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::app::http::middleware::auth_middleware::validate_jwt_claims;
+use crate::app::http::middleware::correlation_middleware::CorrelationContext;
+use crate::config::access_log::AccessLogConfig;
+use crate::logging::Log;
+
+/// Replacement for masked header and field values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// State shared by the access log middleware across requests
+#[derive(Clone, Debug)]
+pub struct AccessLogState {
+    config: Arc<AccessLogConfig>,
+}
+
+/// One structured access log line
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessLogEntry {
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub status: u16,
+    pub latency_ms: f64,
+    pub request_id: Option<String>,
+    pub user_id: Option<String>,
+    pub impersonator_id: Option<String>,
+    pub request_headers: Map<String, Value>,
+    pub request_body: Option<String>,
+    pub response_body: Option<String>,
+}
+
+impl AccessLogEntry {
+    fn write(&self) {
+        let context: HashMap<String, Value> = match serde_json::to_value(self) {
+            Ok(Value::Object(map)) => map.into_iter().collect(),
+            _ => HashMap::new(),
+        };
+        let message = format!("{} {} - {} in {:.2}ms", self.method, self.path, self.status, self.latency_ms);
+
+        if self.status >= 500 {
+            Log::error_with_context(&message, context);
+        } else if self.status >= 400 {
+            Log::warning_with_context(&message, context);
+        } else {
+            Log::info_with_context(&message, context);
+        }
+    }
+}
+
+impl AccessLogState {
+    pub fn new(config: AccessLogConfig) -> Self {
+        Self { config: Arc::new(config) }
+    }
+
+    pub fn from_config(config: &AccessLogConfig) -> Self {
+        Self::new(config.clone())
+    }
+
+    /// Whether request and response bodies are logged for a path
+    pub fn captures_bodies(&self, path: &str) -> bool {
+        self.config.capture_bodies
+            || self.config.capture_paths.iter().any(|prefix| path.starts_with(prefix.as_str()))
+    }
+
+    /// Headers with the configured names masked
+    pub fn redact_headers(&self, headers: &HeaderMap) -> Map<String, Value> {
+        headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if Self::matches(&self.config.redact_headers, name.as_str()) {
+                    REDACTED.to_string()
+                } else {
+                    String::from_utf8_lossy(value.as_bytes()).into_owned()
+                };
+                (name.to_string(), Value::String(value))
+            })
+            .collect()
+    }
+
+    /// Query string or form body with the configured fields masked
+    ///
+    /// Keys are percent-decoded before they are matched, so `pass%77ord`
+    /// is masked like `password`.
+    pub fn redact_query(&self, query: &str) -> String {
+        query
+            .split('&')
+            .map(|pair| match pair.split_once('=') {
+                Some((key, _)) if self.is_redacted_field(&Self::decode_key(key)) => format!("{}={}", key, REDACTED),
+                _ => pair.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    fn decode_key(key: &str) -> String {
+        url::form_urlencoded::parse(key.as_bytes())
+            .next()
+            .map(|(key, _)| key.into_owned())
+            .unwrap_or_default()
+    }
+
+    /// JSON value with the configured fields masked at any depth
+    pub fn redact_json(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if self.is_redacted_field(key) {
+                        *value = Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact_json(value);
+                    }
+                }
+            },
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_json(item)),
+            _ => {},
+        }
+    }
+
+    /// Body as it is logged: secrets masked first, then cut at `max_body_bytes`
+    ///
+    /// Only JSON and form bodies can have their fields masked; anything else,
+    /// including malformed JSON, is logged as `<unparsed N bytes>`.
+    pub fn body_for_log(&self, content_type: Option<&str>, body: &[u8]) -> String {
+        let parsed = match content_type {
+            Some(content_type) if content_type.contains("json") => {
+                serde_json::from_slice::<Value>(body).ok().map(|mut json| {
+                    self.redact_json(&mut json);
+                    json.to_string()
+                })
+            },
+            Some(content_type) if content_type.starts_with("application/x-www-form-urlencoded") => {
+                std::str::from_utf8(body).ok().map(|form| self.redact_query(form))
+            },
+            _ => None,
+        };
+
+        match parsed {
+            Some(text) => self.truncate(text),
+            None => format!("<unparsed {} bytes>", body.len()),
+        }
+    }
+
+    fn truncate(&self, mut text: String) -> String {
+        let max = self.config.max_body_bytes;
+        if text.len() <= max {
+            return text;
+        }
+
+        let total = text.len();
+        let mut end = max;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+        text.push_str(&format!("...[truncated {} bytes]", total - end));
+        text
+    }
+
+    fn is_redacted_field(&self, key: &str) -> bool {
+        Self::matches(&self.config.redact_fields, key)
+    }
+
+    fn matches(names: &[String], name: &str) -> bool {
+        names.iter().any(|candidate| candidate.eq_ignore_ascii_case(name))
+    }
+
+    /// Buffer a body so it can be logged and still passed on
+    ///
+    /// Only text-like bodies are buffered; uploads and event streams are left
+    /// untouched. Bodies without a known size or larger than
+    /// `max_capture_bytes` are passed on unread and logged as a marker.
+    pub async fn capture(&self, headers: &HeaderMap, body: Body) -> (Body, Option<String>) {
+        let content_type = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+
+        let capturable = content_type.as_deref().is_some_and(|content_type| {
+            (content_type.contains("json")
+                || content_type.starts_with("application/x-www-form-urlencoded")
+                || content_type.starts_with("text/"))
+                && !content_type.starts_with("text/event-stream")
+        });
+        if !capturable {
+            return (body, None);
+        }
+
+        let limit = self.config.max_capture_bytes;
+        match body.size_hint().exact() {
+            Some(size) if size <= limit as u64 => {},
+            Some(size) => return (body, Some(format!("[body not captured: {} bytes]", size))),
+            None => return (body, Some("[body not captured: streamed]".to_string())),
+        }
+
+        match axum::body::to_bytes(body, limit).await {
+            Ok(bytes) => {
+                let logged = self.body_for_log(content_type.as_deref(), &bytes);
+                (Body::from(bytes), Some(logged))
+            },
+            Err(e) => {
+                tracing::warn!("Failed to read body for access log: {}", e);
+                (Body::empty(), None)
+            },
+        }
+    }
+}
+
+/// Structured access log with optional, redacted body capture
+///
+/// Logs method, path, status, latency, correlation id and the user of a
+/// bearer token for every request. Bodies are only logged when
+/// `ACCESS_LOG_CAPTURE_BODIES` is on or the path starts with one of
+/// `ACCESS_LOG_CAPTURE_PATHS`.
+pub async fn access_log_middleware(
+    State(access_log): State<AccessLogState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !access_log.config.enabled {
+        return next.run(request).await;
+    }
+
+    let start = Instant::now();
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+    let query = request.uri().query().map(|query| access_log.redact_query(query));
+    let request_id = request.extensions().get::<CorrelationContext>().map(|context| context.id_string());
+    let request_headers = access_log.redact_headers(request.headers());
+
+    let claims = request.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .and_then(validate_jwt_claims);
+    let user_id = claims.as_ref().map(|claims| claims.sub.clone());
+    let impersonator_id = claims.and_then(|claims| claims.impersonator_id);
+
+    let capture = access_log.captures_bodies(&path);
+
+    let (request, request_body) = if capture {
+        let (parts, body) = request.into_parts();
+        let (body, logged) = access_log.capture(&parts.headers, body).await;
+        (Request::from_parts(parts, body), logged)
+    } else {
+        (request, None)
+    };
+
+    let response = next.run(request).await;
+    let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    let (response, response_body) = if capture {
+        let (parts, body) = response.into_parts();
+        let (body, logged) = access_log.capture(&parts.headers, body).await;
+        (Response::from_parts(parts, body), logged)
+    } else {
+        (response, None)
+    };
+
+    AccessLogEntry {
+        method,
+        path,
+        query,
+        status: response.status().as_u16(),
+        latency_ms,
+        request_id,
+        user_id,
+        impersonator_id,
+        request_headers,
+        request_body,
+        response_body,
+    }
+    .write();
+
+    response
+}
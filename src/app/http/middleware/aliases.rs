@@ -0,0 +1,101 @@
+use anyhow::{anyhow, Result};
+use axum::{
+    extract::Request,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::OnceLock;
+
+use super::auth_guard::{auth_guard, guest_guard, mfa_guard};
+use super::signed_middleware::validate_signature;
+
+/// Line `make:middleware` inserts new alias registrations above
+pub const REGISTRATION_MARKER: &str = "// make:middleware registrations go above this line";
+
+pub type MiddlewareFuture = Pin<Box<dyn Future<Output = Response> + Send>>;
+
+/// A middleware usable with `axum::middleware::from_fn`
+pub type MiddlewareFn = fn(Request, Next) -> MiddlewareFuture;
+
+static ALIASES: OnceLock<MiddlewareAliases> = OnceLock::new();
+
+/// Named middleware, like the `$middlewareAliases` of Laravel's HTTP kernel
+///
+/// Routes pick middleware by alias instead of importing each function:
+/// `MiddlewareAliases::global().apply(router, &["auth", "signed"])?`.
+#[derive(Clone, Default)]
+pub struct MiddlewareAliases {
+    aliases: HashMap<String, MiddlewareFn>,
+}
+
+impl MiddlewareAliases {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry holding every alias from `register_aliases`
+    pub fn global() -> &'static Self {
+        ALIASES.get_or_init(|| {
+            let mut aliases = Self::new();
+            register_aliases(&mut aliases);
+            aliases
+        })
+    }
+
+    /// Register `middleware` under `name`, replacing any previous registration
+    pub fn alias(&mut self, name: &str, middleware: MiddlewareFn) -> &mut Self {
+        self.aliases.insert(name.to_string(), middleware);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<MiddlewareFn> {
+        self.aliases.get(name).copied()
+    }
+
+    pub fn has(&self, name: &str) -> bool {
+        self.aliases.contains_key(name)
+    }
+
+    /// Registered alias names, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.aliases.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Layer the aliased middleware onto the routes of `router`
+    ///
+    /// Middleware run in the order given, so `&["auth", "signed"]` checks the
+    /// user before the signature. Fails without touching the router when any
+    /// alias is unknown.
+    pub fn apply<S>(&self, router: Router<S>, names: &[&str]) -> Result<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let handlers = names
+            .iter()
+            .map(|name| self.get(name).ok_or_else(|| anyhow!("Middleware alias [{}] is not registered", name)))
+            .collect::<Result<Vec<_>>>()?;
+
+        // The last layer added runs first
+        Ok(handlers
+            .into_iter()
+            .rev()
+            .fold(router, |router, handler| router.route_layer(middleware::from_fn(handler))))
+    }
+}
+
+/// Register the app's middleware aliases
+///
+/// `make:middleware` adds new middleware here under its snake_case name.
+pub fn register_aliases(aliases: &mut MiddlewareAliases) {
+    aliases.alias("auth", |request, next| Box::pin(async move { auth_guard(request, next).await.into_response() }));
+    aliases.alias("guest", |request, next| Box::pin(async move { guest_guard(request, next).await.into_response() }));
+    aliases.alias("mfa", |request, next| Box::pin(async move { mfa_guard(request, next).await.into_response() }));
+    aliases.alias("signed", |request, next| Box::pin(async move { validate_signature(request, next).await.into_response() }));
+    // make:middleware registrations go above this line
+}
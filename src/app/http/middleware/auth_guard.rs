@@ -1,17 +1,217 @@
 use axum::{
-    extract::Request,
-    http::{StatusCode},
+    extract::{FromRef, FromRequestParts, Request, State},
+    http::{request::Parts, StatusCode},
     middleware::Next,
     response::{Response, Json, Redirect, IntoResponse},
 };
 use serde_json::json;
-use crate::app::http::middleware::auth_middleware::{validate_jwt_token, extract_bearer_token};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::OnceLock;
+use crate::app::http::middleware::auth_middleware::{validate_jwt_token, validate_active_jwt_claims, extract_bearer_token};
+use crate::app::services::impersonation_service::ImpersonationService;
+use crate::app::services::oauth::TokenClaims;
 use crate::app::services::session::SessionStore;
+use crate::app::services::user_service::UserService;
+use crate::config::auth::{AuthConfig, AuthGuard};
+use crate::database::DbPool;
 
+static GUARDS: OnceLock<Vec<AuthGuard>> = OnceLock::new();
+
+/// The authenticated user of a request
+///
+/// Set by `auth_guard` and friends, or taken directly as a handler argument,
+/// in which case the guards from `AUTH_GUARDS` are tried in order and the
+/// request is answered with 401 when none of them accepts it.
 #[derive(Debug, Clone)]
 pub struct AuthUser {
     pub user_id: String,
-    pub auth_method: String, // "jwt" or "session"
+    pub auth_method: String, // "jwt", "oauth" or "session"
+    /// Administrator acting as `user_id`, when the token came from impersonation
+    pub impersonator_id: Option<String>,
+}
+
+impl AuthUser {
+    pub fn is_impersonated(&self) -> bool {
+        self.impersonator_id.is_some()
+    }
+
+    /// Guard that authenticated this user, `None` for a session still waiting on MFA
+    pub fn guard(&self) -> Option<AuthGuard> {
+        match self.auth_method.as_str() {
+            "jwt" | "oauth" => Some(AuthGuard::Token),
+            "session" => Some(AuthGuard::Session),
+            _ => None,
+        }
+    }
+
+    /// Resolve the user from the first of `guards` that accepts the request
+    ///
+    /// A user already placed on the request by a middleware is reused when
+    /// one of `guards` authenticated it.
+    pub async fn resolve(parts: &Parts, guards: &[AuthGuard]) -> Option<Self> {
+        if let Some(auth_user) = parts.extensions.get::<AuthUser>() {
+            if auth_user.guard().is_some_and(|guard| guards.contains(&guard)) {
+                return Some(auth_user.clone());
+            }
+        }
+
+        for guard in guards {
+            let auth_user = match guard {
+                AuthGuard::Token => Self::from_token(parts).await,
+                AuthGuard::Session => Self::from_session(parts).await,
+            };
+            if auth_user.is_some() {
+                return auth_user;
+            }
+        }
+
+        None
+    }
+
+    /// OAuth claims left by `oauth_middleware`, otherwise a first-party JWT bearer token
+    async fn from_token(parts: &Parts) -> Option<Self> {
+        if let Some(claims) = parts.extensions.get::<TokenClaims>() {
+            return Some(AuthUser {
+                user_id: claims.sub.to_string(),
+                auth_method: "oauth".to_string(),
+                impersonator_id: None,
+            });
+        }
+
+        let token = extract_bearer_token(&parts.headers).ok()?;
+        let claims = validate_active_jwt_claims(&token).await?;
+        Some(AuthUser {
+            user_id: claims.sub,
+            auth_method: "jwt".to_string(),
+            impersonator_id: claims.impersonator_id,
+        })
+    }
+
+    async fn from_session(parts: &Parts) -> Option<Self> {
+        let session = parts.extensions.get::<SessionStore>()?;
+        let user_id = session.get_string("user_id").await?;
+        if !session.get_bool("authenticated").await.unwrap_or(false) {
+            return None;
+        }
+
+        Some(AuthUser {
+            user_id,
+            auth_method: "session".to_string(),
+            impersonator_id: None,
+        })
+    }
+}
+
+/// Guards the `AuthUser` extractor tries, from `AUTH_GUARDS`
+pub fn configured_guards() -> &'static [AuthGuard] {
+    GUARDS.get_or_init(|| {
+        AuthConfig::from_env()
+            .map(|config| config.guards)
+            .unwrap_or_else(|_| vec![AuthGuard::Token, AuthGuard::Session])
+    })
+}
+
+/// No guard accepted the request; `Some` when the handler required that guard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unauthenticated(pub Option<AuthGuard>);
+
+impl IntoResponse for Unauthenticated {
+    fn into_response(self) -> Response {
+        let message = match self.0 {
+            None => "Authentication required. Please provide a valid Bearer token or valid session.",
+            Some(AuthGuard::Token) => "Authentication required. Please provide a valid Bearer token.",
+            Some(AuthGuard::Session) => "Authentication required. Please sign in.",
+        };
+
+        (StatusCode::UNAUTHORIZED, Json(json!({
+            "error": "Unauthorized",
+            "message": message
+        }))).into_response()
+    }
+}
+
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = Unauthenticated;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Self::resolve(parts, configured_guards()).await.ok_or(Unauthenticated(None))
+    }
+}
+
+/// Guard an `AuthUserVia` extractor requires
+pub trait Guard: Send + Sync + 'static {
+    const GUARD: AuthGuard;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TokenGuard;
+
+impl Guard for TokenGuard {
+    const GUARD: AuthGuard = AuthGuard::Token;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SessionGuard;
+
+impl Guard for SessionGuard {
+    const GUARD: AuthGuard = AuthGuard::Session;
+}
+
+/// `AuthUser` that must come from guard `G`, whatever `AUTH_GUARDS` says
+///
+/// `AuthUserVia<TokenGuard>` answers a request carrying only a session with
+/// 401, and the other way around.
+#[derive(Debug, Clone)]
+pub struct AuthUserVia<G: Guard> {
+    pub user: AuthUser,
+    guard: PhantomData<G>,
+}
+
+impl<G: Guard> AuthUserVia<G> {
+    pub fn into_inner(self) -> AuthUser {
+        self.user
+    }
+}
+
+impl<G: Guard> Deref for AuthUserVia<G> {
+    type Target = AuthUser;
+
+    fn deref(&self) -> &AuthUser {
+        &self.user
+    }
+}
+
+impl<S, G> FromRequestParts<S> for AuthUserVia<G>
+where
+    S: Send + Sync,
+    G: Guard,
+{
+    type Rejection = Unauthenticated;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let user = AuthUser::resolve(parts, &[G::GUARD]).await.ok_or(Unauthenticated(Some(G::GUARD)))?;
+        Ok(Self { user, guard: PhantomData })
+    }
+}
+
+/// Run the request as `auth_user`, recording it in the activity log when impersonated
+async fn run_as(auth_user: AuthUser, mut request: Request, next: Next) -> Response {
+    if !auth_user.is_impersonated() {
+        request.extensions_mut().insert(auth_user);
+        return next.run(request).await;
+    }
+
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+    request.extensions_mut().insert(auth_user.clone());
+
+    let response = next.run(request).await;
+    ImpersonationService::log_request(&auth_user, &method, &path, response.status()).await;
+    response
 }
 
 /// Unified auth guard middleware that supports both JWT and Session authentication
@@ -24,13 +224,13 @@ pub async fn auth_guard(
 
     // Try JWT authentication first
     if let Ok(token) = extract_bearer_token(&headers) {
-        if let Some(user_id) = validate_jwt_token(&token) {
+        if let Some(claims) = validate_active_jwt_claims(&token).await {
             let auth_user = AuthUser {
-                user_id,
+                user_id: claims.sub,
                 auth_method: "jwt".to_string(),
+                impersonator_id: claims.impersonator_id,
             };
-            request.extensions_mut().insert(auth_user);
-            return Ok(next.run(request).await);
+            return Ok(run_as(auth_user, request, next).await);
         }
     }
 
@@ -41,6 +241,7 @@ pub async fn auth_guard(
                 let auth_user = AuthUser {
                     user_id,
                     auth_method: "session".to_string(),
+                    impersonator_id: None,
                 };
                 request.extensions_mut().insert(auth_user);
                 return Ok(next.run(request).await);
@@ -114,13 +315,13 @@ pub async fn optional_auth(
 
     // Try JWT authentication first
     if let Ok(token) = extract_bearer_token(&headers) {
-        if let Some(user_id) = validate_jwt_token(&token) {
+        if let Some(claims) = validate_active_jwt_claims(&token).await {
             let auth_user = AuthUser {
-                user_id,
+                user_id: claims.sub,
                 auth_method: "jwt".to_string(),
+                impersonator_id: claims.impersonator_id,
             };
-            request.extensions_mut().insert(auth_user);
-            return next.run(request).await;
+            return run_as(auth_user, request, next).await;
         }
     }
 
@@ -131,6 +332,7 @@ pub async fn optional_auth(
                 let auth_user = AuthUser {
                     user_id,
                     auth_method: "session".to_string(),
+                    impersonator_id: None,
                 };
                 request.extensions_mut().insert(auth_user);
             }
@@ -150,13 +352,13 @@ pub async fn mfa_guard(
 
     // Allow if valid JWT
     if let Ok(token) = extract_bearer_token(&headers) {
-        if let Some(user_id) = validate_jwt_token(&token) {
+        if let Some(claims) = validate_active_jwt_claims(&token).await {
             let auth_user = AuthUser {
-                user_id,
+                user_id: claims.sub,
                 auth_method: "jwt".to_string(),
+                impersonator_id: claims.impersonator_id,
             };
-            request.extensions_mut().insert(auth_user);
-            return Ok(next.run(request).await);
+            return Ok(run_as(auth_user, request, next).await);
         }
     }
 
@@ -167,6 +369,7 @@ pub async fn mfa_guard(
                 let auth_user = AuthUser {
                     user_id,
                     auth_method: "session".to_string(),
+                    impersonator_id: None,
                 };
                 request.extensions_mut().insert(auth_user);
                 return Ok(next.run(request).await);
@@ -179,6 +382,7 @@ pub async fn mfa_guard(
                 let auth_user = AuthUser {
                     user_id: mfa_user_id,
                     auth_method: "session-mfa".to_string(),
+                    impersonator_id: None,
                 };
                 request.extensions_mut().insert(auth_user);
                 return Ok(next.run(request).await);
@@ -194,6 +398,71 @@ pub async fn mfa_guard(
     Err((StatusCode::UNAUTHORIZED, Json(error_response)))
 }
 
+/// Verified guard that allows only users who have confirmed their email address
+/// Similar to Laravel's 'verified' middleware; must run after `auth_guard`
+pub async fn verified_guard(
+    State(pool): State<DbPool>,
+    request: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
+    let Some(auth_user) = request.extensions().get::<AuthUser>() else {
+        return Err((StatusCode::UNAUTHORIZED, Json(json!({
+            "error": "Unauthorized",
+            "message": "Authentication required."
+        }))));
+    };
+
+    ensure_verified(&pool, &auth_user.user_id)?;
+    Ok(next.run(request).await)
+}
+
+/// `AuthUser` whose email address is verified
+///
+/// The `verified_guard` check for routes built without the pool as state:
+/// answers 401 without a user and 403 while the email is unverified.
+#[derive(Debug, Clone)]
+pub struct VerifiedUser(pub AuthUser);
+
+impl Deref for VerifiedUser {
+    type Target = AuthUser;
+
+    fn deref(&self) -> &AuthUser {
+        &self.0
+    }
+}
+
+impl<S> FromRequestParts<S> for VerifiedUser
+where
+    DbPool: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let auth_user = AuthUser::from_request_parts(parts, state).await.map_err(IntoResponse::into_response)?;
+        ensure_verified(&DbPool::from_ref(state), &auth_user.user_id).map_err(IntoResponse::into_response)?;
+        Ok(Self(auth_user))
+    }
+}
+
+fn ensure_verified(pool: &DbPool, user_id: &str) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    let user = UserService::find_by_id(pool, user_id.to_string()).map_err(|e| {
+        tracing::error!("Failed to load user for email verification check: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({
+            "error": "Internal Server Error",
+            "message": "Could not check email verification."
+        })))
+    })?;
+
+    match user {
+        Some(user) if user.email_verified_at.is_some() => Ok(()),
+        _ => Err((StatusCode::FORBIDDEN, Json(json!({
+            "error": "Forbidden",
+            "message": "Your email address is not verified."
+        })))),
+    }
+}
+
 /// Helper function to get authenticated user from request extensions
 pub fn get_auth_user(request: &Request) -> Option<&AuthUser> {
     request.extensions().get::<AuthUser>()
@@ -11,6 +11,7 @@ use crate::database::DbPool;
 use sha2::{Digest, Sha256};
 
 use crate::config::Config;
+use crate::app::services::auth_service::AuthService;
 use crate::app::http::middleware::activity_logging_middleware::activity_logger_from_request;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -19,6 +20,9 @@ pub struct Claims {
     pub exp: usize,
     pub iat: usize,
     pub jti: String,
+    /// Set while an administrator is impersonating `sub`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonator_id: Option<String>,
 }
 
 pub async fn auth_middleware(
@@ -142,6 +146,11 @@ pub async fn is_valid_token(_pool: &DbPool, token: &str) -> Option<String> {
 
 /// Validate JWT token without database lookup (synchronous version)
 pub fn validate_jwt_token(token: &str) -> Option<String> {
+    validate_jwt_claims(token).map(|claims| claims.sub)
+}
+
+/// Validate a JWT token and return all of its claims
+pub fn validate_jwt_claims(token: &str) -> Option<Claims> {
     // Load config for JWT secret
     let config = match Config::load() {
         Ok(config) => config,
@@ -159,7 +168,18 @@ pub fn validate_jwt_token(token: &str) -> Option<String> {
         Err(_) => return None,
     };
 
-    Some(decoded.claims.sub)
+    Some(decoded.claims)
+}
+
+/// Claims of a valid JWT that has not been revoked with `AuthService::revoke_access_token`
+pub async fn validate_active_jwt_claims(token: &str) -> Option<Claims> {
+    let claims = validate_jwt_claims(token)?;
+    if AuthService::is_access_token_revoked(&claims).await {
+        tracing::warn!("Attempted use of revoked token: {}", claims.jti);
+        return None;
+    }
+
+    Some(claims)
 }
 
 /// Check if the current user has admin privileges
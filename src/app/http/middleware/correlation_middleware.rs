@@ -1,19 +1,34 @@
 use axum::{
-    extract::{Request, ConnectInfo},
+    extract::Request,
     http::{HeaderMap, HeaderName, HeaderValue},
     middleware::Next,
     response::Response,
 };
 use std::collections::HashMap;
-use std::net::SocketAddr;
 use std::str::FromStr;
 use uuid::Uuid;
 
+use crate::app::http::middleware::trusted_proxy_middleware::client_ip;
 use crate::app::models::DieselUlid;
 
 pub const CORRELATION_ID_HEADER: &str = "X-Correlation-ID";
 pub const CORRELATION_ID_HEADER_LOWERCASE: &str = "x-correlation-id";
 
+tokio::task_local! {
+    /// Correlation ID of the request the current task is serving
+    static CURRENT_CORRELATION_ID: DieselUlid;
+}
+
+/// Correlation ID of the request being handled by the current task, if any
+pub fn current_correlation_id() -> Option<DieselUlid> {
+    CURRENT_CORRELATION_ID.try_with(|correlation_id| *correlation_id).ok()
+}
+
+/// Run a future with a correlation ID in scope, e.g. for jobs spawned off a request
+pub async fn with_correlation_id<F: std::future::Future>(correlation_id: DieselUlid, future: F) -> F::Output {
+    CURRENT_CORRELATION_ID.scope(correlation_id, future).await
+}
+
 #[derive(Clone, Debug)]
 pub struct CorrelationContext {
     pub correlation_id: DieselUlid,
@@ -76,12 +91,8 @@ pub async fn correlation_middleware(
 ) -> Response {
     let correlation_id = extract_or_generate_correlation_id(&request);
 
-    // Extract socket address from extensions (if available)
-    let socket_addr = request.extensions().get::<ConnectInfo<SocketAddr>>()
-        .map(|connect_info| connect_info.0);
-
     // Extract comprehensive request data
-    let request_data = extract_request_data(&request, socket_addr);
+    let request_data = extract_request_data(&request);
 
     // Add correlation context with request data to request extensions
     request.extensions_mut().insert(CorrelationContext::with_id_and_request_data(
@@ -89,8 +100,8 @@ pub async fn correlation_middleware(
         request_data,
     ));
 
-    // Call the next handler
-    let mut response = next.run(request).await;
+    // Call the next handler with the correlation ID in scope for outbound calls
+    let mut response = with_correlation_id(correlation_id, next.run(request)).await;
 
     // Add correlation ID to response headers
     if let Ok(header_value) = HeaderValue::from_str(&correlation_id.to_string()) {
@@ -134,7 +145,7 @@ fn extract_or_generate_correlation_id(request: &Request) -> DieselUlid {
 }
 
 /// Extract comprehensive request data for logging
-fn extract_request_data(request: &Request, socket_addr: Option<SocketAddr>) -> RequestData {
+fn extract_request_data(request: &Request) -> RequestData {
     let method = request.method().to_string();
     let uri = request.uri().to_string();
     let path = request.uri().path().to_string();
@@ -160,9 +171,8 @@ fn extract_request_data(request: &Request, socket_addr: Option<SocketAddr>) -> R
         .and_then(|v| v.to_str().ok())
         .map(|s| s.to_string());
 
-    // Extract client IP (check headers first, then fallback to socket address)
-    let remote_ip = extract_client_ip_from_headers(request.headers())
-        .or_else(|| socket_addr.map(|addr| addr.ip().to_string()));
+    // Forwarding headers only count when they came through a trusted proxy
+    let remote_ip = client_ip(request).map(|ip| ip.to_string());
 
     RequestData {
         method,
@@ -199,31 +209,6 @@ fn extract_safe_headers(headers: &HeaderMap) -> HashMap<String, String> {
         .collect()
 }
 
-/// Extract client IP from headers
-fn extract_client_ip_from_headers(headers: &HeaderMap) -> Option<String> {
-    let headers_to_check = [
-        "cf-connecting-ip",      // Cloudflare
-        "x-real-ip",            // Nginx
-        "x-forwarded-for",      // Standard proxy header
-        "x-client-ip",          // Alternative
-        "x-cluster-client-ip",  // Cluster environments
-    ];
-
-    for header_name in &headers_to_check {
-        if let Some(header_value) = headers.get(*header_name) {
-            if let Ok(ip_str) = header_value.to_str() {
-                // For X-Forwarded-For, take the first IP (client)
-                let ip = ip_str.split(',').next().unwrap_or(ip_str).trim();
-                if !ip.is_empty() && ip != "unknown" {
-                    return Some(ip.to_string());
-                }
-            }
-        }
-    }
-
-    None
-}
-
 // Extension trait to easily get correlation context from request
 pub trait CorrelationExt {
     fn correlation_id(&self) -> Option<DieselUlid>;
@@ -0,0 +1,57 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::json;
+
+use crate::app::features::{Feature, FeatureContext};
+use crate::app::http::middleware::auth_guard::AuthUser;
+use crate::app::http::middleware::auth_middleware::validate_jwt_claims;
+use crate::database::DbPool;
+
+/// Flag a group of routes is gated behind
+#[derive(Clone)]
+pub struct FeatureGate {
+    pool: DbPool,
+    feature: String,
+}
+
+impl FeatureGate {
+    pub fn new(pool: DbPool, feature: &str) -> Self {
+        Self { pool, feature: feature.to_string() }
+    }
+}
+
+/// Answer 404 unless the gate's feature is on for the requesting user
+///
+/// Use as `route_layer(middleware::from_fn_with_state(FeatureGate::new(pool, "name"), require_feature))`.
+/// The user comes from `auth_guard` when it ran first, otherwise from a
+/// bearer token; guests only see fully rolled out features.
+pub async fn require_feature(
+    State(gate): State<FeatureGate>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let user_id = request
+        .extensions()
+        .get::<AuthUser>()
+        .map(|auth_user| auth_user.user_id.clone())
+        .or_else(|| {
+            request.headers()
+                .get(header::AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+                .and_then(validate_jwt_claims)
+                .map(|claims| claims.sub)
+        });
+
+    if Feature::enabled(&gate.pool, &gate.feature, &FeatureContext { user_id }).await {
+        return next.run(request).await;
+    }
+
+    (StatusCode::NOT_FOUND, Json(json!({
+        "error": "Not Found"
+    }))).into_response()
+}
@@ -0,0 +1,39 @@
+use axum::{
+    extract::Request,
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+
+use crate::app::models::diesel_ulid::with_id_format;
+use crate::config::response::IdFormat;
+
+pub const ID_FORMAT_HEADER: &str = "x-id-format";
+
+/// Id format a request asks for with `?id_format=` or the `X-Id-Format` header
+pub fn requested_id_format(query: Option<&str>, headers: &HeaderMap) -> Option<IdFormat> {
+    let from_query = query.and_then(|query| {
+        url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == "id_format")
+            .and_then(|(_, value)| IdFormat::parse(&value))
+    });
+
+    from_query.or_else(|| {
+        headers
+            .get(ID_FORMAT_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(IdFormat::parse)
+    })
+}
+
+/// Write response ids in the format the request asks for
+///
+/// Only scopes the choice to the request's task; response types pick it up
+/// through `diesel_ulid::response`. Requests that do not ask keep the
+/// `RESPONSE_ID_FORMAT` default.
+pub async fn id_format_middleware(request: Request, next: Next) -> Response {
+    match requested_id_format(request.uri().query(), request.headers()) {
+        Some(format) => with_id_format(format, next.run(request)).await,
+        None => next.run(request).await,
+    }
+}
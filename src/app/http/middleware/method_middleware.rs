@@ -0,0 +1,78 @@
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::json;
+
+/// Consistent answers for methods a route does not define
+///
+/// - `HEAD` runs the `GET` handler and always returns an empty body, even from
+///   services that ignore the method.
+/// - A plain `OPTIONS` request (not a CORS preflight) to a known path answers
+///   `204 No Content` with the allowed methods.
+/// - Any other unsupported method answers `405 Method Not Allowed` with an
+///   `Allow` header such as `GET, HEAD`, like Laravel's
+///   `MethodNotAllowedHttpException`.
+///
+/// Unknown paths still answer 404.
+pub async fn method_middleware(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let is_preflight = request.headers().contains_key(header::ACCESS_CONTROL_REQUEST_METHOD);
+
+    let mut response = next.run(request).await;
+
+    if response.status() == StatusCode::METHOD_NOT_ALLOWED {
+        let mut allowed = allowed_methods(&response);
+
+        response = if method == Method::OPTIONS && !is_preflight {
+            allowed.push(Method::OPTIONS.to_string());
+            StatusCode::NO_CONTENT.into_response()
+        } else {
+            (StatusCode::METHOD_NOT_ALLOWED, Json(json!({
+                "error": "Method Not Allowed",
+                "message": format!(
+                    "The {} method is not supported for this route. Supported methods: {}.",
+                    method,
+                    allowed.join(", ")
+                )
+            }))).into_response()
+        };
+        insert_allow(&mut response, &allowed);
+    }
+
+    if method == Method::HEAD {
+        let (parts, _) = response.into_parts();
+        return Response::from_parts(parts, Body::empty());
+    }
+
+    response
+}
+
+/// Methods listed in the router's `Allow` header, with `HEAD` wherever `GET` is
+fn allowed_methods(response: &Response) -> Vec<String> {
+    let mut allowed: Vec<String> = response
+        .headers()
+        .get_all(header::ALLOW)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|method| method.trim().to_ascii_uppercase())
+        .filter(|method| !method.is_empty())
+        .collect();
+
+    if allowed.iter().any(|method| method == "GET") && !allowed.iter().any(|method| method == "HEAD") {
+        let position = allowed.iter().position(|method| method == "GET").unwrap_or(0);
+        allowed.insert(position + 1, "HEAD".to_string());
+    }
+    allowed.dedup();
+    allowed
+}
+
+fn insert_allow(response: &mut Response, allowed: &[String]) {
+    if let Ok(value) = HeaderValue::from_str(&allowed.join(", ")) {
+        response.headers_mut().insert(header::ALLOW, value);
+    }
+}
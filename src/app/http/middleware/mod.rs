@@ -9,4 +9,15 @@ pub mod correlation_middleware;
 pub mod activity_logging_middleware;
 pub mod dpop_middleware;
 pub mod session_middleware;
-pub mod csrf_middleware;
\ No newline at end of file
+pub mod csrf_middleware;
+pub mod tenant_middleware;
+pub mod signed_middleware;
+pub mod access_log_middleware;
+pub mod id_format_middleware;
+pub mod trusted_proxy_middleware;
+pub mod feature_middleware;
+pub mod method_middleware;
+pub mod request_timing_middleware;
+pub mod timeout_middleware;
+pub mod aliases;
+pub mod timestamp_format_middleware;
\ No newline at end of file
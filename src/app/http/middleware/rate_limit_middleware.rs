@@ -1,6 +1,6 @@
 use axum::{
     extract::{Request, ConnectInfo},
-    http::{StatusCode, HeaderMap},
+    http::StatusCode,
     middleware::Next,
     response::{Response, IntoResponse},
     Json,
@@ -9,6 +9,7 @@ use serde_json::json;
 use std::{sync::Arc, net::SocketAddr};
 use crate::app::utils::{RateLimiter, RateLimitError};
 use crate::app::http::middleware::activity_logging_middleware::activity_logger_from_request;
+use crate::app::http::middleware::trusted_proxy_middleware::client_ip;
 
 /// Rate limiting middleware state
 #[derive(Clone)]
@@ -37,7 +38,6 @@ impl Default for RateLimitState {
 /// Web push subscription rate limiting middleware
 pub async fn web_push_subscription_rate_limit(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
-    headers: HeaderMap,
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
@@ -45,7 +45,7 @@ pub async fn web_push_subscription_rate_limit(
     let rate_limiter = RateLimiter::for_web_push_subscription();
 
     // Use IP address as identifier, with user ID as fallback if available
-    let identifier = extract_identifier(&addr, &headers).await;
+    let identifier = extract_identifier(&request, &addr);
 
     // Create activity logger for this request
     let logger = activity_logger_from_request(&request, "rate_limiting");
@@ -61,7 +61,7 @@ pub async fn web_push_subscription_rate_limit(
             let properties = json!({
                 "rate_limit_type": "web_push_subscription",
                 "identifier": identifier,
-                "client_ip": addr.ip().to_string(),
+                "client_ip": identifier,
                 "retry_after_seconds": seconds,
                 "path": request.uri().path(),
                 "method": request.method().as_str()
@@ -97,7 +97,7 @@ pub async fn web_push_subscription_rate_limit(
                 "rate_limit_type": "web_push_subscription",
                 "error": msg,
                 "identifier": identifier,
-                "client_ip": addr.ip().to_string()
+                "client_ip": identifier
             });
 
             tokio::spawn(async move {
@@ -118,12 +118,11 @@ pub async fn web_push_subscription_rate_limit(
 /// Web push notification sending rate limiting middleware
 pub async fn web_push_notification_rate_limit(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
-    headers: HeaderMap,
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
     let rate_limiter = RateLimiter::for_notification_sending();
-    let identifier = extract_identifier(&addr, &headers).await;
+    let identifier = extract_identifier(&request, &addr);
 
     match rate_limiter.check_rate_limit(&identifier) {
         Ok(_) => {
@@ -153,12 +152,11 @@ pub async fn web_push_notification_rate_limit(
 /// General API rate limiting middleware
 pub async fn api_rate_limit(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
-    headers: HeaderMap,
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
     let rate_limiter = RateLimiter::for_api_endpoints();
-    let identifier = extract_identifier(&addr, &headers).await;
+    let identifier = extract_identifier(&request, &addr);
 
     match rate_limiter.check_rate_limit(&identifier) {
         Ok(_) => {
@@ -185,25 +183,10 @@ pub async fn api_rate_limit(
     }
 }
 
-/// Extract identifier for rate limiting from IP and headers
-async fn extract_identifier(addr: &SocketAddr, headers: &HeaderMap) -> String {
-    // Check for forwarded IP headers (for reverse proxy setups)
-    if let Some(forwarded_for) = headers.get("X-Forwarded-For") {
-        if let Ok(forwarded_str) = forwarded_for.to_str() {
-            if let Some(first_ip) = forwarded_str.split(',').next() {
-                return first_ip.trim().to_string();
-            }
-        }
-    }
-
-    if let Some(real_ip) = headers.get("X-Real-IP") {
-        if let Ok(real_ip_str) = real_ip.to_str() {
-            return real_ip_str.to_string();
-        }
-    }
-
-    // Fallback to direct connection IP
-    addr.ip().to_string()
+/// Extract identifier for rate limiting: the client IP resolved through
+/// trusted proxies, falling back to the direct connection IP
+fn extract_identifier(request: &Request, addr: &SocketAddr) -> String {
+    client_ip(request).unwrap_or(addr.ip()).to_string()
 }
 
 /// Rate limiting statistics endpoint handler
@@ -225,35 +208,42 @@ pub async fn rate_limit_stats() -> Result<Json<serde_json::Value>, StatusCode> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::http::middleware::trusted_proxy_middleware::TrustedProxies;
+    use axum::body::Body;
     use std::net::{IpAddr, Ipv4Addr};
 
-    #[tokio::test]
-    async fn test_extract_identifier_with_forwarded_for() {
-        let mut headers = HeaderMap::new();
-        headers.insert("X-Forwarded-For", "192.168.1.1, 10.0.0.1".parse().unwrap());
+    fn request(forwarded_for: Option<&str>) -> Request {
+        let mut builder = Request::builder();
+        if let Some(forwarded_for) = forwarded_for {
+            builder = builder.header("X-Forwarded-For", forwarded_for);
+        }
+        builder.body(Body::empty()).unwrap()
+    }
 
-        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
-        let identifier = extract_identifier(&addr, &headers).await;
+    #[test]
+    fn test_extract_identifier_with_trusted_forwarded_for() {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 8080);
+        let mut request = request(Some("192.168.1.1, 10.0.0.2"));
+        let client = TrustedProxies::new(&["10.0.0.0/8"]).resolve(Some(addr.ip()), request.headers());
+        request.extensions_mut().insert(client);
 
-        assert_eq!(identifier, "192.168.1.1");
+        assert_eq!(extract_identifier(&request, &addr), "192.168.1.1");
     }
 
-    #[tokio::test]
-    async fn test_extract_identifier_with_real_ip() {
-        let mut headers = HeaderMap::new();
-        headers.insert("X-Real-IP", "203.0.113.1".parse().unwrap());
-
-        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
-        let identifier = extract_identifier(&addr, &headers).await;
+    #[test]
+    fn test_extract_identifier_ignores_untrusted_forwarded_for() {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(203, 0, 113, 1)), 8080);
+        let mut request = request(Some("192.168.1.1"));
+        let client = TrustedProxies::new(&["10.0.0.0/8"]).resolve(Some(addr.ip()), request.headers());
+        request.extensions_mut().insert(client);
 
-        assert_eq!(identifier, "203.0.113.1");
+        assert_eq!(extract_identifier(&request, &addr), "203.0.113.1");
     }
 
-    #[tokio::test]
-    async fn test_extract_identifier_fallback_to_addr() {
-        let headers = HeaderMap::new();
+    #[test]
+    fn test_extract_identifier_fallback_to_addr() {
         let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 100)), 8080);
-        let identifier = extract_identifier(&addr, &headers).await;
+        let identifier = extract_identifier(&request(Some("198.51.100.7")), &addr);
 
         assert_eq!(identifier, "192.168.1.100");
     }
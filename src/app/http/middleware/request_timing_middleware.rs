@@ -0,0 +1,75 @@
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use std::time::{Duration, Instant};
+
+use crate::app::utils::PerformanceMetrics;
+use crate::config::performance::PerformanceConfig;
+
+pub const SERVER_TIMING_HEADER: &str = "server-timing";
+
+/// Threshold above which the request timing middleware warns
+#[derive(Clone, Debug)]
+pub struct RequestTimingState {
+    slow_request: Option<Duration>,
+}
+
+impl RequestTimingState {
+    pub fn new(slow_request: Option<Duration>) -> Self {
+        Self { slow_request }
+    }
+
+    pub fn from_config(config: &PerformanceConfig) -> Self {
+        Self::new(config.slow_request_threshold())
+    }
+
+    pub fn is_slow(&self, elapsed: Duration) -> bool {
+        self.slow_request.is_some_and(|threshold| elapsed >= threshold)
+    }
+}
+
+/// Time every request, exposing it as `Server-Timing` and warning when it is slow
+///
+/// Slow requests are logged with their route template rather than the raw
+/// path, so ids in the URL do not split one slow route into many entries.
+pub async fn request_timing_middleware(
+    State(timing): State<RequestTimingState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let start = Instant::now();
+    let method = request.method().clone();
+    let route = request.extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+
+    let mut response = next.run(request).await;
+
+    let elapsed = start.elapsed();
+    let duration_ms = elapsed.as_secs_f64() * 1000.0;
+    let slow = timing.is_slow(elapsed);
+    PerformanceMetrics::global().record_request(slow);
+
+    if slow {
+        tracing::warn!(
+            method = %method,
+            route = %route,
+            status = response.status().as_u16(),
+            duration_ms,
+            "Slow request: {} {} took {:.2}ms",
+            method,
+            route,
+            duration_ms,
+        );
+    }
+
+    if let Ok(value) = HeaderValue::from_str(&format!("app;dur={:.2}", duration_ms)) {
+        response.headers_mut().insert(SERVER_TIMING_HEADER, value);
+    }
+
+    response
+}
@@ -0,0 +1,39 @@
+use axum::{
+    extract::Request,
+    http::StatusCode,
+    middleware::Next,
+    response::{Json, Response},
+};
+use serde_json::json;
+
+use crate::app::http::middleware::trusted_proxy_middleware::client_ip;
+use crate::app::utils::UrlSigner;
+use crate::config::Config;
+
+/// Reject requests whose URL was not signed by `UrlSigner` or has expired
+/// Similar to Laravel's 'signed' middleware
+pub async fn validate_signature(
+    request: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
+    let signer = Config::load()
+        .and_then(|config| UrlSigner::from_config(&config.app))
+        .map_err(|e| {
+            tracing::error!("Cannot validate signed URL: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({
+                "error": "Internal Server Error",
+                "message": "Signed URLs are not configured."
+            })))
+        })?;
+
+    if let Err(e) = signer.verify(request.uri().path(), request.uri().query()) {
+        let ip = client_ip(&request).map(|ip| ip.to_string()).unwrap_or_else(|| "unknown".to_string());
+        tracing::warn!("Rejected signed URL {} from {}: {}", request.uri().path(), ip, e);
+        return Err((StatusCode::FORBIDDEN, Json(json!({
+            "error": "Forbidden",
+            "message": e.to_string()
+        }))));
+    }
+
+    Ok(next.run(request).await)
+}
@@ -0,0 +1,103 @@
+use axum::{
+    extract::{FromRef, FromRequestParts, Request, State},
+    http::request::Parts,
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::json;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use crate::app::http::middleware::auth_guard::{configured_guards, AuthUser};
+use crate::config::tenancy::TenancyConfig;
+use crate::database::tenancy::{TenantConnectionManager, TenantError, TenantResolver};
+use crate::database::DbPool;
+
+/// State shared by the tenant middleware across requests
+#[derive(Clone)]
+pub struct TenancyState {
+    pub resolver: TenantResolver,
+    pub connections: Arc<TenantConnectionManager>,
+}
+
+impl TenancyState {
+    pub fn new(resolver: TenantResolver, connections: Arc<TenantConnectionManager>) -> Self {
+        Self { resolver, connections }
+    }
+
+    pub fn from_config(central: DbPool, config: &TenancyConfig) -> Self {
+        Self::new(
+            TenantResolver::from_config(config),
+            Arc::new(TenantConnectionManager::from_config(central, config)),
+        )
+    }
+}
+
+/// Tenant resolved for the current request, stored in request extensions
+#[derive(Clone)]
+pub struct CurrentTenant {
+    pub id: String,
+    pub pool: DbPool,
+}
+
+/// Resolve the request's tenant and attach its connection pool
+///
+/// Requests without a tenant pass through untouched and keep using the
+/// central pool. Runs after the session middleware so the signed-in user can
+/// be checked against the tenant; naming a tenant the user does not belong to
+/// is answered with 403.
+pub async fn tenant_middleware(
+    State(tenancy): State<TenancyState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if tenancy.resolver.identify(request.headers()).is_none() {
+        return next.run(request).await;
+    }
+
+    let (mut parts, body) = request.into_parts();
+    let user = AuthUser::resolve(&parts, configured_guards()).await;
+    let resolved = tenancy.resolver.resolve(
+        tenancy.connections.central(),
+        &parts.headers,
+        user.as_ref().map(|user| user.user_id.as_str()),
+    );
+
+    match resolved {
+        Ok(Some(tenant)) => {
+            let pool = tenancy.connections.pool_for(&tenant);
+            parts.extensions.insert(CurrentTenant { id: tenant, pool });
+        }
+        Ok(None) => {}
+        Err(e) => {
+            return (TenantError::status_code(&e), Json(json!({"error": e.to_string()}))).into_response();
+        }
+    }
+
+    next.run(Request::from_parts(parts, body)).await
+}
+
+/// Database pool for the request: the tenant's pool when one was resolved,
+/// otherwise the central pool from router state
+///
+/// Handlers working on tenant data take `TenantDb` instead of `State<DbPool>`
+/// and pass the pool on to services and the query builder as usual.
+pub struct TenantDb(pub DbPool);
+
+impl<S> FromRequestParts<S> for TenantDb
+where
+    DbPool: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let pool = parts
+            .extensions
+            .get::<CurrentTenant>()
+            .map(|tenant| tenant.pool.clone())
+            .unwrap_or_else(|| DbPool::from_ref(state));
+
+        Ok(Self(pool))
+    }
+}
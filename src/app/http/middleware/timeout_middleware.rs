@@ -0,0 +1,154 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::app::http::responses::ServiceErrorResponse;
+use crate::config::request_timeout::RequestTimeoutConfig;
+
+/// A request that ran past its limit
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("Request timed out after {} seconds", .0.as_secs_f64())]
+pub struct RequestTimedOut(pub Duration);
+
+/// Cancelled when a request ends early: it timed out or its client went away
+///
+/// Dropping the handler future stops the handler itself; work it hands to
+/// `tokio::spawn` keeps running unless it watches this token, e.g. with
+/// `tokio::select!` on `token.cancelled()`.
+#[derive(Clone, Debug)]
+pub struct RequestCancellation(pub CancellationToken);
+
+/// Timeout limits shared by the request timeout middleware
+///
+/// The longest matching override prefix wins over the global limit, which is
+/// how a route group gets its own timeout. Excluded prefixes and event
+/// stream requests are never timed out.
+#[derive(Clone, Debug)]
+pub struct RequestTimeout {
+    default: Option<Duration>,
+    overrides: Arc<Vec<(String, Option<Duration>)>>,
+    exclude: Arc<Vec<String>>,
+}
+
+impl RequestTimeout {
+    pub fn new(default: Option<Duration>) -> Self {
+        Self {
+            default,
+            overrides: Arc::new(Vec::new()),
+            exclude: Arc::new(Vec::new()),
+        }
+    }
+
+    pub fn from_config(config: &RequestTimeoutConfig) -> Self {
+        let mut timeout = Self::new(Self::seconds(config.timeout_secs));
+        for (prefix, secs) in &config.overrides {
+            timeout = timeout.with_override(prefix, Self::seconds(*secs));
+        }
+        for prefix in &config.exclude {
+            timeout = timeout.exclude(prefix);
+        }
+        timeout
+    }
+
+    /// Give requests under `prefix` their own limit, `None` meaning no limit
+    pub fn with_override(mut self, prefix: &str, timeout: Option<Duration>) -> Self {
+        Arc::make_mut(&mut self.overrides).push((prefix.to_string(), timeout));
+        self
+    }
+
+    /// Never time out requests under `prefix`
+    pub fn exclude(mut self, prefix: &str) -> Self {
+        Arc::make_mut(&mut self.exclude).push(prefix.to_string());
+        self
+    }
+
+    /// Limit for a request, if it has one
+    pub fn timeout_for(&self, path: &str, headers: &HeaderMap) -> Option<Duration> {
+        let streaming = headers
+            .get(header::ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|accept| accept.contains("text/event-stream"));
+        if streaming || self.exclude.iter().any(|prefix| path.starts_with(prefix.as_str())) {
+            return None;
+        }
+
+        self.overrides
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, timeout)| *timeout)
+            .unwrap_or(self.default)
+    }
+
+    /// Warn when a query can outlive the shortest request limit
+    ///
+    /// Diesel queries block their worker thread, so the timeout cannot fire
+    /// until a query returns. Postgres `statement_timeout` is what cancels the
+    /// query itself, answered with 504 through `ServiceErrorResponse`, so it
+    /// should be shorter than every request limit.
+    pub fn check_statement_timeout(self, statement_timeout: Option<Duration>) -> Self {
+        let shortest = std::iter::once(self.default)
+            .chain(self.overrides.iter().map(|(_, timeout)| *timeout))
+            .flatten()
+            .min();
+
+        if let Some(shortest) = shortest {
+            match statement_timeout {
+                Some(statement_timeout) if statement_timeout < shortest => {},
+                _ => tracing::warn!(
+                    "DB_STATEMENT_TIMEOUT_MS ({:?}) is not shorter than the shortest request timeout ({:?}); blocking queries can outlive it",
+                    statement_timeout,
+                    shortest,
+                ),
+            }
+        }
+        self
+    }
+
+    fn seconds(secs: u64) -> Option<Duration> {
+        (secs > 0).then(|| Duration::from_secs(secs))
+    }
+}
+
+/// Abort handlers that run past their limit and answer 504
+///
+/// The handler future is dropped at its next await once the limit passes,
+/// and the request's `RequestCancellation` token is cancelled so spawned work
+/// that watches it stops too. A blocking query is bounded by
+/// `statement_timeout` instead; see `RequestTimeout::check_statement_timeout`.
+pub async fn timeout_middleware(
+    State(timeout): State<RequestTimeout>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let token = CancellationToken::new();
+    request.extensions_mut().insert(RequestCancellation(token.clone()));
+    // Cancels when the client disconnects and this future is dropped
+    let guard = token.clone().drop_guard();
+
+    let Some(limit) = timeout.timeout_for(request.uri().path(), request.headers()) else {
+        let response = next.run(request).await;
+        guard.disarm();
+        return response;
+    };
+
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+
+    match tokio::time::timeout(limit, next.run(request)).await {
+        Ok(response) => {
+            guard.disarm();
+            response
+        }
+        Err(_) => {
+            tracing::warn!("Request timed out after {:?}: {} {}", limit, method, path);
+            ServiceErrorResponse::new(StatusCode::GATEWAY_TIMEOUT, RequestTimedOut(limit).into()).into_response()
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use axum::{
+    extract::Request,
+    http::{header, HeaderMap},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::app::utils::timestamp::with_timestamp_format;
+use crate::config::response::TimestampFormat;
+
+/// Timestamp format a request asks for with `?timestamp_format=` or an
+/// `Accept` profile such as `application/json; profile=epoch_millis`
+pub fn requested_timestamp_format(query: Option<&str>, headers: &HeaderMap) -> Option<TimestampFormat> {
+    let from_query = query.and_then(|query| {
+        url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == "timestamp_format")
+            .and_then(|(_, value)| TimestampFormat::parse(&value))
+    });
+
+    from_query.or_else(|| requested_by_accept(headers))
+}
+
+fn requested_by_accept(headers: &HeaderMap) -> Option<TimestampFormat> {
+    let accept = headers.get(header::ACCEPT)?.to_str().ok()?;
+    accept.split(',').find_map(|media_type| {
+        media_type.split(';').skip(1).find_map(|parameter| {
+            let (name, value) = parameter.split_once('=')?;
+            if name.trim() != "profile" {
+                return None;
+            }
+            // A profile may list several space-separated values, e.g. "bare epoch_millis"
+            value.trim().trim_matches('"').split_whitespace().find_map(TimestampFormat::parse)
+        })
+    })
+}
+
+/// Write response timestamps in the format the request asks for
+///
+/// Only scopes the choice to the request's task; response types pick it up
+/// through `timestamp::response`. Requests that do not ask keep the
+/// `RESPONSE_TIMESTAMP_FORMAT` default.
+pub async fn timestamp_format_middleware(request: Request, next: Next) -> Response {
+    match requested_timestamp_format(request.uri().query(), request.headers()) {
+        Some(format) => with_timestamp_format(format, next.run(request)).await,
+        None => next.run(request).await,
+    }
+}
@@ -0,0 +1,210 @@
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap},
+    middleware::Next,
+    response::Response,
+};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use crate::config::trusted_proxies::TrustedProxiesConfig;
+
+/// Client address, scheme and host of a request, resolved through trusted proxies
+///
+/// Inserted into the request extensions by `trusted_proxy_middleware`. When
+/// the socket peer is not a trusted proxy, forwarding headers are ignored and
+/// the peer itself is the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientInfo {
+    pub ip: Option<IpAddr>,
+    pub scheme: String,
+    pub host: Option<String>,
+}
+
+impl ClientInfo {
+    pub fn ip_string(&self) -> Option<String> {
+        self.ip.map(|ip| ip.to_string())
+    }
+
+    /// Scheme and host as seen by the client, e.g. `https://example.com`
+    pub fn root_url(&self) -> Option<String> {
+        self.host.as_ref().map(|host| format!("{}://{}", self.scheme, host))
+    }
+}
+
+/// Address or CIDR range of a trusted proxy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ProxyRange {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl ProxyRange {
+    fn parse(value: &str) -> Option<Self> {
+        let (address, prefix) = match value.split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (value, None),
+        };
+        let network: IpAddr = address.trim().parse().ok()?;
+        let max = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix.trim().parse().ok().filter(|prefix| *prefix <= max)?,
+            None => max,
+        };
+        Some(Self { network, prefix })
+    }
+
+    fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, Self::canonical(ip)) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                Self::masked(u32::from(network) as u128, 32, self.prefix) == Self::masked(u32::from(ip) as u128, 32, self.prefix)
+            },
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                Self::masked(u128::from(network), 128, self.prefix) == Self::masked(u128::from(ip), 128, self.prefix)
+            },
+            _ => false,
+        }
+    }
+
+    fn masked(bits: u128, width: u8, prefix: u8) -> u128 {
+        if prefix == 0 {
+            return 0;
+        }
+        bits >> (width - prefix)
+    }
+
+    /// IPv4 peers accepted on a dual-stack socket arrive as `::ffff:a.b.c.d`
+    fn canonical(ip: IpAddr) -> IpAddr {
+        match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+            ip => ip,
+        }
+    }
+}
+
+/// Proxies whose `X-Forwarded-*` headers are believed, like Laravel's `TrustProxies`
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    ranges: Arc<Vec<ProxyRange>>,
+    trust_all: bool,
+}
+
+impl TrustedProxies {
+    pub fn new<S: AsRef<str>>(proxies: &[S]) -> Self {
+        let mut ranges = Vec::new();
+        let mut trust_all = false;
+
+        for proxy in proxies {
+            let proxy = proxy.as_ref().trim();
+            if proxy == "*" {
+                trust_all = true;
+            } else if let Some(range) = ProxyRange::parse(proxy) {
+                ranges.push(range);
+            } else {
+                tracing::warn!("Ignoring invalid trusted proxy: {}", proxy);
+            }
+        }
+
+        Self { ranges: Arc::new(ranges), trust_all }
+    }
+
+    pub fn from_config(config: &TrustedProxiesConfig) -> Self {
+        Self::new(&config.proxies)
+    }
+
+    pub fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.trust_all || self.ranges.iter().any(|range| range.contains(ip))
+    }
+
+    /// Resolve the client behind the socket peer
+    ///
+    /// The `X-Forwarded-For` chain is walked from the right, skipping trusted
+    /// proxies; the first untrusted address is the client. Anything to its
+    /// left was supplied by the client and is not believed.
+    pub fn resolve(&self, peer: Option<IpAddr>, headers: &HeaderMap) -> ClientInfo {
+        let direct_host = Self::header(headers, header::HOST.as_str());
+
+        let peer = match peer {
+            Some(peer) if self.is_trusted(peer) => peer,
+            peer => {
+                return ClientInfo { ip: peer.map(ProxyRange::canonical), scheme: "http".to_string(), host: direct_host };
+            },
+        };
+
+        let mut ip = ProxyRange::canonical(peer);
+        let forwarded_for: Vec<&str> = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .collect();
+
+        for candidate in forwarded_for.iter().rev() {
+            match candidate.parse::<IpAddr>() {
+                Ok(candidate) => {
+                    ip = ProxyRange::canonical(candidate);
+                    if !self.is_trusted(candidate) {
+                        break;
+                    }
+                },
+                // A malformed hop ends the chain; the last valid address stands
+                Err(_) => break,
+            }
+        }
+
+        let scheme = Self::header(headers, "x-forwarded-proto")
+            .map(|scheme| scheme.to_ascii_lowercase())
+            .filter(|scheme| scheme == "http" || scheme == "https")
+            .unwrap_or_else(|| "http".to_string());
+        let host = Self::header(headers, "x-forwarded-host").or(direct_host);
+
+        ClientInfo { ip: Some(ip), scheme, host }
+    }
+
+    /// First comma-separated value of a header
+    fn header(headers: &HeaderMap, name: &str) -> Option<String> {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(',').next())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+/// Resolve the real client address, scheme and host behind trusted proxies
+///
+/// Stores a `ClientInfo` in the request extensions for rate limiting,
+/// signed URL checks and activity logging.
+pub async fn trusted_proxy_middleware(
+    State(proxies): State<TrustedProxies>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|connect_info| connect_info.0.ip());
+
+    let client = proxies.resolve(peer, request.headers());
+    request.extensions_mut().insert(client);
+
+    next.run(request).await
+}
+
+/// Client IP of a request: the resolved `ClientInfo` when the middleware ran,
+/// otherwise the socket peer
+pub fn client_ip(request: &Request) -> Option<IpAddr> {
+    request
+        .extensions()
+        .get::<ClientInfo>()
+        .and_then(|client| client.ip)
+        .or_else(|| {
+            request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|connect_info| connect_info.0.ip())
+        })
+}
@@ -1,9 +1,13 @@
 pub mod controllers;
 pub mod form_request;
+pub mod json_body;
+pub mod merge_patch;
 pub mod middleware;
 pub mod requests;
 pub mod responses;
 
 pub use form_request::{FormRequest, ValidationErrorResponse};
+pub use json_body::JsonBody;
+pub use merge_patch::MergePatch;
 pub use requests::*;
 pub use responses::*;
\ No newline at end of file
@@ -6,6 +6,7 @@ pub mod city_requests;
 pub mod district_requests;
 pub mod village_requests;
 pub mod organization_requests;
+pub mod organization_domain_requests;
 pub mod user_organization_requests;
 pub mod organization_position_level_requests;
 pub mod organization_position_requests;
@@ -25,5 +25,6 @@ pub struct CreateNotificationRequest {
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct UpdateNotificationRequest {
     /// When the notification was read
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub read_at: Option<DateTime<Utc>>,
 }
\ No newline at end of file
@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+
+use crate::app::http::form_request::FormRequest;
+use crate::app::models::organization_domain::UpdateOrganizationDomain;
+use crate::app::validation::ValidationRules;
+use crate::validation_rules;
+
+/// Validation for partial updates of an organization domain
+///
+/// Used through `MergePatch`, which checks only the members a patch sends,
+/// so `name` may be omitted but not cleared with `null`.
+#[async_trait]
+impl FormRequest for UpdateOrganizationDomain {
+    fn rules() -> ValidationRules {
+        validation_rules! {
+            "code" => ["string", "max:50"],
+            "name" => ["required", "string", "min:2", "max:100"],
+            "description" => ["string", "max:500"]
+        }
+    }
+
+    fn messages() -> HashMap<&'static str, &'static str> {
+        let mut messages = HashMap::new();
+        messages.insert("code.max", "Domain code cannot exceed 50 characters");
+        messages.insert("name.required", "Domain name cannot be cleared");
+        messages.insert("name.min", "Domain name must be at least 2 characters");
+        messages.insert("name.max", "Domain name cannot exceed 100 characters");
+        messages.insert("description.max", "Description cannot exceed 500 characters");
+        messages
+    }
+
+    fn attributes() -> HashMap<&'static str, &'static str> {
+        let mut attributes = HashMap::new();
+        attributes.insert("code", "domain code");
+        attributes.insert("name", "domain name");
+        attributes.insert("description", "domain description");
+        attributes
+    }
+}
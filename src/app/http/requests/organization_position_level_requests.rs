@@ -88,7 +88,7 @@ pub struct UpdateOrganizationPositionLevelRequest {
     #[schema(example = "Senior Level")]
     pub name: Option<String>,
     /// Position level description (optional, max 500 characters)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "crate::app::http::merge_patch::nullable")]
     #[schema(example = "Senior level position with 5+ years experience")]
     pub description: Option<Option<String>>,
     /// Numeric level for hierarchy (optional, 1-20)
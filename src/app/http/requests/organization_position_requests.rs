@@ -117,7 +117,7 @@ pub struct UpdateOrganizationPositionRequest {
     #[schema(example = "Senior Software Engineer")]
     pub name: Option<String>,
     /// Position description (optional, max 500 characters)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "crate::app::http::merge_patch::nullable")]
     #[schema(example = "Senior software engineer responsible for system architecture")]
     pub description: Option<Option<String>>,
     /// Active status
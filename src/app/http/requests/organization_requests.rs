@@ -158,6 +158,9 @@ pub struct UpdateOrganizationRequest {
     /// Whether the organization is active (optional)
     #[schema(example = true)]
     pub is_active: Option<bool>,
+    /// Version the update is based on; required unless sent in `If-Match`
+    #[schema(example = 1)]
+    pub lock_version: Option<i32>,
 }
 
 #[async_trait]
@@ -25,6 +25,7 @@ pub struct CreateUserOrganizationRequest {
     /// Start date of the relationship (defaults to current time)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[schema(example = "2024-01-01T00:00:00Z")]
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub started_at: Option<DateTime<Utc>>,
 }
 
@@ -81,10 +82,12 @@ pub struct UpdateUserOrganizationRequest {
     /// Start date of the relationship
     #[serde(skip_serializing_if = "Option::is_none")]
     #[schema(example = "2024-01-01T00:00:00Z")]
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub started_at: Option<DateTime<Utc>>,
     /// End date of the relationship
     #[serde(skip_serializing_if = "Option::is_none")]
     #[schema(example = "2024-12-31T23:59:59Z")]
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub ended_at: Option<DateTime<Utc>>,
 }
 
@@ -233,6 +236,7 @@ pub struct TransferUserOrganizationRequest {
     /// Transfer date (defaults to current time)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[schema(example = "2024-01-01T00:00:00Z")]
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub transfer_date: Option<DateTime<Utc>>,
     /// Transfer reason
     #[serde(skip_serializing_if = "Option::is_none")]
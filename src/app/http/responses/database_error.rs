@@ -0,0 +1,165 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use diesel::result::{DatabaseErrorInformation, DatabaseErrorKind, Error as DieselError};
+use std::collections::HashMap;
+
+use crate::app::http::form_request::ValidationErrorResponse;
+
+/// Constraint violations that are the client's fault rather than a server error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintViolation {
+    /// SQLSTATE 23505, answered with 409
+    Unique,
+    /// SQLSTATE 23503, answered with 422
+    ForeignKey,
+    /// SQLSTATE 23502, answered with 422
+    NotNull,
+}
+
+impl ConstraintViolation {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::Unique => StatusCode::CONFLICT,
+            Self::ForeignKey | Self::NotNull => StatusCode::UNPROCESSABLE_ENTITY,
+        }
+    }
+
+    /// Validation rule the violation corresponds to, as in Laravel's messages
+    pub fn rule(&self) -> &'static str {
+        match self {
+            Self::Unique => "unique",
+            Self::ForeignKey => "exists",
+            Self::NotNull => "required",
+        }
+    }
+
+    fn message(&self, field: &str) -> String {
+        let attribute = field.replace('_', " ");
+        match self {
+            Self::Unique => format!("The {} has already been taken.", attribute),
+            Self::ForeignKey => format!("The selected {} is invalid.", attribute),
+            Self::NotNull => format!("The {} field is required.", attribute),
+        }
+    }
+
+    fn generic_message(&self) -> &'static str {
+        match self {
+            Self::Unique => "A record with these values already exists.",
+            Self::ForeignKey => "A referenced record does not exist or is still in use.",
+            Self::NotNull => "A required field is missing.",
+        }
+    }
+}
+
+/// Postgres constraint violation mapped to the validation error envelope
+///
+/// Raw Diesel errors would otherwise surface as 500s. The offending fields
+/// are read from the error detail, column or constraint name; the values in
+/// the detail are never echoed back.
+#[derive(Debug)]
+pub struct DatabaseErrorResponse {
+    pub violation: ConstraintViolation,
+    pub fields: Vec<String>,
+}
+
+impl DatabaseErrorResponse {
+    /// Find a constraint violation anywhere in an error's chain
+    pub fn from_error(error: &anyhow::Error) -> Option<Self> {
+        error.chain()
+            .find_map(|cause| cause.downcast_ref::<DieselError>())
+            .and_then(Self::from_diesel)
+    }
+
+    pub fn from_diesel(error: &DieselError) -> Option<Self> {
+        let DieselError::DatabaseError(kind, info) = error else {
+            return None;
+        };
+
+        let violation = match kind {
+            DatabaseErrorKind::UniqueViolation => ConstraintViolation::Unique,
+            DatabaseErrorKind::ForeignKeyViolation => ConstraintViolation::ForeignKey,
+            DatabaseErrorKind::NotNullViolation => ConstraintViolation::NotNull,
+            _ => return None,
+        };
+
+        Some(Self {
+            violation,
+            fields: Self::fields(info.as_ref()),
+        })
+    }
+
+    /// Columns named by `Key (a, b)=(...)`, then the column, then the constraint name
+    fn fields(info: &dyn DatabaseErrorInformation) -> Vec<String> {
+        if let Some(columns) = info.details().and_then(Self::key_columns) {
+            return columns;
+        }
+
+        if let Some(column) = info.column_name() {
+            return vec![column.to_string()];
+        }
+
+        if let Some(column) = Self::null_column(info.message()) {
+            return vec![column];
+        }
+
+        info.constraint_name()
+            .and_then(|constraint| Self::constraint_column(constraint, info.table_name()))
+            .into_iter()
+            .collect()
+    }
+
+    fn key_columns(details: &str) -> Option<Vec<String>> {
+        let start = details.find("Key (")? + "Key (".len();
+        let end = start + details[start..].find(")=(")?;
+        Some(details[start..end].split(',').map(|column| column.trim().to_string()).collect())
+    }
+
+    /// Column of `null value in column "name" of relation ...`
+    fn null_column(message: &str) -> Option<String> {
+        let start = message.find("column \"")? + "column \"".len();
+        let end = start + message[start..].find('"')?;
+        Some(message[start..end].to_string())
+    }
+
+    /// `users_email_key` or `users_email_fkey` on table `users` names `email`
+    fn constraint_column(constraint: &str, table: Option<&str>) -> Option<String> {
+        let name = ["_fkey", "_key", "_unique"]
+            .iter()
+            .find_map(|suffix| constraint.strip_suffix(suffix))?;
+        let name = table
+            .and_then(|table| name.strip_prefix(table))
+            .and_then(|name| name.strip_prefix('_'))
+            .unwrap_or(name);
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    pub fn message(&self) -> String {
+        match self.fields.first() {
+            Some(field) => self.violation.message(field),
+            None => self.violation.generic_message().to_string(),
+        }
+    }
+
+    pub fn body(&self) -> ValidationErrorResponse {
+        let errors = self.fields
+            .iter()
+            .map(|field| {
+                let rule = HashMap::from([(self.violation.rule().to_string(), self.violation.message(field))]);
+                (field.clone(), rule)
+            })
+            .collect();
+
+        ValidationErrorResponse {
+            message: self.message(),
+            errors,
+        }
+    }
+}
+
+impl IntoResponse for DatabaseErrorResponse {
+    fn into_response(self) -> Response {
+        (self.violation.status(), Json(self.body())).into_response()
+    }
+}
@@ -0,0 +1,181 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use std::convert::Infallible;
+use std::sync::OnceLock;
+
+use crate::app::query_builder::{PaginationResult, PaginationType};
+use crate::app::query_builder::pagination::PaginationInfo;
+use crate::config::response::ResponseConfig;
+
+static WRAP_DATA: OnceLock<Option<bool>> = OnceLock::new();
+
+/// Whether a response body is wrapped in a `data` key
+///
+/// Wrapped lists keep the `pagination` object in the body; bare lists are
+/// plain arrays. Both send pagination in `Link` and `X-Total-Count` headers.
+/// A request chooses with `?wrap=true|false` or an `Accept` profile such as
+/// `application/json; profile=bare`, falling back to `RESPONSE_WRAP_DATA`.
+/// With neither set, responses keep the shapes clients already rely on:
+/// lists wrapped with their `pagination`, single resources bare.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envelope {
+    /// `None` when nothing asked for a shape
+    pub wrap: Option<bool>,
+    path: String,
+    query: Option<String>,
+}
+
+impl Envelope {
+    pub fn new(wrap: Option<bool>, path: impl Into<String>, query: Option<String>) -> Self {
+        Self {
+            wrap,
+            path: path.into(),
+            query,
+        }
+    }
+
+    /// Pick the envelope for a request, falling back to `default_wrap`
+    pub fn negotiate(path: &str, query: Option<&str>, headers: &HeaderMap, default_wrap: Option<bool>) -> Self {
+        let wrap = Self::requested_by_query(query)
+            .or_else(|| Self::requested_by_accept(headers))
+            .or(default_wrap);
+
+        Self::new(wrap, path, query.map(str::to_string))
+    }
+
+    fn requested_by_query(query: Option<&str>) -> Option<bool> {
+        url::form_urlencoded::parse(query?.as_bytes())
+            .find(|(key, _)| key == "wrap")
+            .and_then(|(_, value)| match value.as_ref() {
+                "true" | "1" => Some(true),
+                "false" | "0" => Some(false),
+                _ => None,
+            })
+    }
+
+    fn requested_by_accept(headers: &HeaderMap) -> Option<bool> {
+        let accept = headers.get(header::ACCEPT)?.to_str().ok()?;
+        accept.split(',').find_map(|media_type| {
+            media_type.split(';').skip(1).find_map(|parameter| {
+                let (name, value) = parameter.split_once('=')?;
+                if name.trim() != "profile" {
+                    return None;
+                }
+                match value.trim().trim_matches('"') {
+                    "bare" => Some(false),
+                    "wrapped" => Some(true),
+                    _ => None,
+                }
+            })
+        })
+    }
+
+    /// Default from `RESPONSE_WRAP_DATA`, read once
+    pub fn default_wrap() -> Option<bool> {
+        *WRAP_DATA.get_or_init(|| ResponseConfig::from_env().ok().and_then(|config| config.wrap_data))
+    }
+
+    /// Response for a single resource, bare unless wrapping was asked for
+    pub fn resource<T: Serialize>(&self, status: StatusCode, resource: T) -> Response {
+        if self.wrap.unwrap_or(false) {
+            (status, Json(serde_json::json!({ "data": resource }))).into_response()
+        } else {
+            (status, Json(resource)).into_response()
+        }
+    }
+
+    /// Response for a page of a collection, with pagination headers; wrapped
+    /// unless a bare list was asked for
+    pub fn collection<T: Serialize>(&self, result: PaginationResult<T>) -> Response {
+        let headers = self.pagination_headers(&result.pagination);
+
+        let mut response = if self.wrap.unwrap_or(true) {
+            (StatusCode::OK, Json(result)).into_response()
+        } else {
+            (StatusCode::OK, Json(result.data)).into_response()
+        };
+        response.headers_mut().extend(headers);
+        response
+    }
+
+    /// `Link` and `X-Total-Count` headers for a page
+    pub fn pagination_headers(&self, pagination: &PaginationInfo) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+
+        if let Some(total) = pagination.total {
+            headers.insert("x-total-count", HeaderValue::from(total));
+        }
+
+        let links: Vec<String> = self
+            .page_links(pagination)
+            .into_iter()
+            .map(|(rel, url)| format!("<{}>; rel=\"{}\"", url, rel))
+            .collect();
+        if !links.is_empty() {
+            if let Ok(link) = HeaderValue::from_str(&links.join(", ")) {
+                headers.insert(header::LINK, link);
+            }
+        }
+
+        headers
+    }
+
+    fn page_links(&self, pagination: &PaginationInfo) -> Vec<(&'static str, String)> {
+        let mut links = Vec::new();
+
+        match pagination.pagination_type {
+            PaginationType::Offset => {
+                links.push(("first", self.url_with("page", "1")));
+                if let Some(prev) = pagination.prev_page {
+                    links.push(("prev", self.url_with("page", &prev.to_string())));
+                }
+                if let Some(next) = pagination.next_page {
+                    links.push(("next", self.url_with("page", &next.to_string())));
+                }
+                if let Some(last) = pagination.total_pages {
+                    links.push(("last", self.url_with("page", &last.max(1).to_string())));
+                }
+            },
+            PaginationType::Cursor => {
+                if let Some(prev) = &pagination.prev_cursor {
+                    links.push(("prev", self.url_with("cursor", prev)));
+                }
+                if let Some(next) = &pagination.next_cursor {
+                    links.push(("next", self.url_with("cursor", next)));
+                }
+            },
+        }
+
+        links
+    }
+
+    /// The request's URL with one query parameter replaced
+    fn url_with(&self, key: &str, value: &str) -> String {
+        let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+        if let Some(query) = &self.query {
+            for (name, existing) in url::form_urlencoded::parse(query.as_bytes()) {
+                if name != key {
+                    serializer.append_pair(&name, &existing);
+                }
+            }
+        }
+        serializer.append_pair(key, value);
+
+        format!("{}?{}", self.path, serializer.finish())
+    }
+}
+
+impl<S> FromRequestParts<S> for Envelope
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::negotiate(parts.uri.path(), parts.uri.query(), &parts.headers, Self::default_wrap()))
+    }
+}
@@ -1,3 +1,9 @@
 pub mod template_response;
+pub mod envelope;
+pub mod database_error;
+pub mod service_error;
 
-pub use template_response::*;
\ No newline at end of file
+pub use template_response::*;
+pub use envelope::Envelope;
+pub use database_error::{ConstraintViolation, DatabaseErrorResponse};
+pub use service_error::ServiceErrorResponse;
\ No newline at end of file
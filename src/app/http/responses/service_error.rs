@@ -0,0 +1,44 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::json;
+
+use super::DatabaseErrorResponse;
+use crate::database::StatementTimedOut;
+
+/// Error returned by a service, answered with the status its error type maps to
+///
+/// A constraint violation anywhere in the chain is answered as a
+/// `DatabaseErrorResponse` instead, so duplicates and dangling references are
+/// 409s and 422s on every endpoint that goes through here. A query cancelled
+/// by `statement_timeout` is a 504.
+#[derive(Debug)]
+pub struct ServiceErrorResponse {
+    pub status: StatusCode,
+    pub error: anyhow::Error,
+}
+
+impl ServiceErrorResponse {
+    pub fn new(status: StatusCode, error: anyhow::Error) -> Self {
+        Self { status, error }
+    }
+}
+
+impl From<anyhow::Error> for ServiceErrorResponse {
+    fn from(error: anyhow::Error) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, error)
+    }
+}
+
+impl IntoResponse for ServiceErrorResponse {
+    fn into_response(self) -> Response {
+        if let Some(response) = DatabaseErrorResponse::from_error(&self.error) {
+            return response.into_response();
+        }
+        if let Some(timed_out) = StatementTimedOut::from_error(&self.error) {
+            return (StatusCode::GATEWAY_TIMEOUT, Json(json!({ "error": timed_out.to_string() }))).into_response();
+        }
+        (self.status, Json(json!({ "error": self.error.to_string() }))).into_response()
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Serialize, Deserialize};
+use std::sync::Arc;
+
+use crate::app::events::queued_listener::{queued_listener, QueuedEvent};
+use crate::app::events::{Event, EventListener};
+use crate::app::jobs::{Job, NamedJob};
+
+/// Runs one queued listener for one event on a queue worker (Laravel's `CallQueuedListener`)
+///
+/// Carries the listener's queue routing and retry settings, taken from its
+/// `ShouldQueueListener` implementation when it has one, so the worker runs
+/// it with its own tries, timeout and backoff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallQueuedListenerJob {
+    /// `EventListener::listener_name` of the listener to run
+    pub listener: String,
+    pub event_name: String,
+    pub event: serde_json::Value,
+    pub connection: Option<String>,
+    pub queue: String,
+    pub delay_seconds: Option<i64>,
+    pub tries: u32,
+    pub timeout_seconds: Option<u64>,
+    /// Delay before each retry, the last one repeating for any further retries
+    pub backoff_seconds: Vec<i64>,
+}
+
+impl CallQueuedListenerJob {
+    pub fn new(listener: &dyn EventListener, event: &dyn Event) -> Self {
+        let listener_name = listener.listener_name().to_string();
+
+        let Some(options) = listener.as_should_queue() else {
+            return Self {
+                listener: listener_name,
+                event_name: event.event_name().to_string(),
+                event: event.to_json(),
+                connection: None,
+                queue: listener.queue_name().unwrap_or("default").to_string(),
+                delay_seconds: None,
+                tries: listener.max_exceptions().unwrap_or(1),
+                timeout_seconds: None,
+                backoff_seconds: listener.backoff().iter().map(|delay| delay.num_seconds()).collect(),
+            };
+        };
+
+        let tries = options.tries().or(listener.max_exceptions()).unwrap_or(1);
+        Self {
+            listener: listener_name,
+            event_name: event.event_name().to_string(),
+            event: event.to_json(),
+            connection: options.queue_connection().map(str::to_string),
+            queue: options.queue().or(listener.queue_name()).unwrap_or("default").to_string(),
+            delay_seconds: options.delay().map(|delay| delay.num_seconds()),
+            tries,
+            timeout_seconds: options.timeout().map(|timeout| timeout.num_seconds().max(0) as u64),
+            backoff_seconds: (1..=tries)
+                .filter_map(|attempt| options.retry_after(attempt))
+                .map(|delay| delay.num_seconds())
+                .collect(),
+        }
+    }
+
+    pub fn delay(&self) -> Option<chrono::Duration> {
+        self.delay_seconds.map(chrono::Duration::seconds)
+    }
+
+    fn resolve(&self) -> Result<(Arc<dyn EventListener>, Arc<dyn Event>)> {
+        let listener = queued_listener(&self.listener)
+            .ok_or_else(|| anyhow::anyhow!("Queued listener '{}' is not registered", self.listener))?;
+        let event = QueuedEvent::new(&self.event_name, self.event.clone());
+        Ok((listener, Arc::new(event)))
+    }
+}
+
+impl NamedJob for CallQueuedListenerJob {
+    const JOB_NAME: &'static str = "CallQueuedListener";
+}
+
+#[async_trait]
+impl Job for CallQueuedListenerJob {
+    fn job_name(&self) -> &'static str {
+        Self::JOB_NAME
+    }
+
+    async fn handle(&self) -> Result<()> {
+        let (listener, event) = self.resolve()?;
+        tracing::info!("Running queued listener {} for event {}", self.listener, self.event_name);
+        listener.handle(event).await
+    }
+
+    fn max_attempts(&self) -> u32 {
+        self.tries
+    }
+
+    fn backoff(&self, attempt: u32) -> Option<chrono::Duration> {
+        let index = (attempt.max(1) as usize - 1).min(self.backoff_seconds.len().checked_sub(1)?);
+        Some(chrono::Duration::seconds(self.backoff_seconds[index]))
+    }
+
+    fn queue_name(&self) -> &str {
+        &self.queue
+    }
+
+    fn timeout(&self) -> Option<u64> {
+        self.timeout_seconds.or(Some(300))
+    }
+
+    fn serialize(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    async fn failed(&self, error: &anyhow::Error) {
+        tracing::error!("Queued listener {} failed permanently: {}", self.listener, error);
+
+        match self.resolve() {
+            Ok((listener, event)) => {
+                if let Err(e) = listener.failed(event, error).await {
+                    tracing::error!("Listener failed method also failed: {}", e);
+                }
+            }
+            Err(e) => tracing::error!("{}", e),
+        }
+    }
+}
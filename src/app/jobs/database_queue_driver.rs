@@ -3,6 +3,7 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde_json;
 use diesel::prelude::*;
+use std::time::Duration;
 use crate::database::DbPool;
 use crate::schema::jobs;
 use crate::app::jobs::{QueueDriver, JobMetadata, JobStatus};
@@ -27,17 +28,26 @@ struct JobRow {
     timeout_seconds: Option<i32>,        // Nullable<Int4> maps to Option<i32>
     created_at: DateTime<Utc>,           // Timestamptz maps to DateTime<Utc>
     updated_at: DateTime<Utc>,           // Timestamptz maps to DateTime<Utc>
+    correlation_id: Option<String>,      // Nullable<Bpchar> maps to Option<String>
 }
 
 /// Database-backed queue driver using PostgreSQL
 #[derive(Debug, Clone)]
 pub struct DatabaseQueueDriver {
     pool: DbPool,
+    /// How long a job may stay reserved before it is treated as abandoned
+    retry_after: Duration,
 }
 
 impl DatabaseQueueDriver {
     pub fn new(pool: DbPool) -> Self {
-        Self { pool }
+        Self { pool, retry_after: Duration::from_secs(360) }
+    }
+
+    /// Reclaim reservations older than `retry_after`, e.g. from a crashed worker
+    pub fn retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = retry_after;
+        self
     }
 
     /// Convert database row to JobMetadata
@@ -68,6 +78,7 @@ impl DatabaseQueueDriver {
             reserved_at: row.reserved_at,
             processed_at: row.processed_at,
             timeout_seconds: row.timeout_seconds,
+            correlation_id: row.correlation_id.clone(),
         })
     }
 }
@@ -98,8 +109,10 @@ impl QueueDriver for DatabaseQueueDriver {
                 jobs::available_at.eq(metadata.scheduled_at.unwrap_or(metadata.created_at)),
                 jobs::failed_at.eq(metadata.failed_at),
                 jobs::error_message.eq(&metadata.error_message),
+                jobs::timeout_seconds.eq(metadata.timeout_seconds),
                 jobs::created_at.eq(metadata.created_at),
                 jobs::updated_at.eq(metadata.updated_at),
+                jobs::correlation_id.eq(&metadata.correlation_id),
             ))
             .execute(&mut conn)?;
 
@@ -111,29 +124,58 @@ impl QueueDriver for DatabaseQueueDriver {
         let mut conn = self.pool.get()?;
 
         // For this complex query with FOR UPDATE SKIP LOCKED, we use raw SQL
-        // since Diesel doesn't directly support these PostgreSQL-specific features
+        // since Diesel doesn't directly support these PostgreSQL-specific features.
+        // SKIP LOCKED lets concurrent workers each claim a different row instead
+        // of waiting on, or both claiming, the same one.
         use diesel::sql_query;
+        use diesel::sql_types::{BigInt, Text};
 
-        let job_row: Option<JobRow> = sql_query(
+        // A job still reserved after retry_after (or its own longer timeout) was
+        // abandoned by its worker, and that run counts as an attempt. Jobs with
+        // no attempts left are failed rather than handed out again.
+        let stale = "status = 'processing' \
+            AND reserved_at <= NOW() - make_interval(secs => GREATEST(COALESCE(timeout_seconds, 0), $2))";
+        let retry_after = self.retry_after.as_secs().min(i64::MAX as u64) as i64;
+
+        sql_query(format!(
             r#"
             UPDATE jobs
-            SET status = 'processing',
+            SET status = 'failed',
+                attempts = attempts + 1,
+                failed_at = NOW(),
+                error_message = 'Job reservation expired on its final attempt',
+                reserved_at = NULL,
+                updated_at = NOW()
+            WHERE queue_name = $1
+              AND {stale}
+              AND attempts + 1 >= max_attempts
+            "#
+        ))
+        .bind::<Text, _>(queue_name)
+        .bind::<BigInt, _>(retry_after)
+        .execute(&mut conn)?;
+
+        let job_row: Option<JobRow> = sql_query(format!(
+            r#"
+            UPDATE jobs
+            SET attempts = attempts + CASE WHEN status = 'processing' THEN 1 ELSE 0 END,
+                status = 'processing',
                 reserved_at = NOW(),
                 updated_at = NOW()
             WHERE id = (
                 SELECT id
                 FROM jobs
                 WHERE queue_name = $1
-                  AND status = 'pending'
-                  AND available_at <= NOW()
-                ORDER BY priority ASC, created_at ASC
-                FOR UPDATE SKIP LOCKED
+                  AND ((status = 'pending' AND available_at <= NOW()) OR ({stale}))
+                ORDER BY priority ASC, available_at ASC, created_at ASC
                 LIMIT 1
+                FOR UPDATE SKIP LOCKED
             )
             RETURNING *
             "#
-        )
-        .bind::<diesel::sql_types::Text, _>(queue_name)
+        ))
+        .bind::<Text, _>(queue_name)
+        .bind::<BigInt, _>(retry_after)
         .get_result(&mut conn)
         .optional()?;
 
@@ -201,6 +243,26 @@ impl QueueDriver for DatabaseQueueDriver {
         Ok(())
     }
 
+    async fn release(&self, metadata: JobMetadata) -> Result<()> {
+        let mut conn = self.pool.get()?;
+        let now = chrono::Utc::now();
+
+        // The row is still there from when it was popped, so make it pending again
+        diesel::update(jobs::table.filter(jobs::id.eq(&metadata.id)))
+            .set((
+                jobs::status.eq("pending"),
+                jobs::attempts.eq(metadata.attempts as i32),
+                jobs::error_message.eq(&metadata.error_message),
+                jobs::reserved_at.eq::<Option<DateTime<Utc>>>(None),
+                jobs::available_at.eq(metadata.scheduled_at.unwrap_or(now)),
+                jobs::updated_at.eq(now),
+            ))
+            .execute(&mut conn)?;
+
+        tracing::info!("Job {} released back to database queue '{}'", metadata.id, metadata.queue_name);
+        Ok(())
+    }
+
     async fn failed_jobs(&self, limit: Option<u32>) -> Result<Vec<JobMetadata>> {
         let limit_val = limit.unwrap_or(100) as i64;
 
@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+
+use crate::app::jobs::queue_worker::JobFactory;
+use crate::app::jobs::{Job, NamedJob};
+
+/// Turns a popped job's name and payload back into a runnable `Job`
+///
+/// Each job type is registered under its `job_name()`; a worker looks up the
+/// `JobMetadata::job_name` of every job it pops to deserialize the payload.
+#[derive(Default)]
+pub struct JobRegistry {
+    factories: HashMap<String, Box<dyn JobFactory>>,
+}
+
+impl JobRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a job type under its `JOB_NAME`, deserializing its payload with serde
+    pub fn register<T: NamedJob>(&mut self) -> &mut Self {
+        self.register_factory(T::JOB_NAME, |payload: &str| -> Result<Box<dyn Job>> {
+            Ok(Box::new(serde_json::from_str::<T>(payload)?))
+        })
+    }
+
+    /// Register a custom factory for a job name
+    pub fn register_factory(&mut self, job_name: &str, factory: impl JobFactory + 'static) -> &mut Self {
+        self.factories.insert(job_name.to_string(), Box::new(factory));
+        tracing::debug!("Registered job factory for: {}", job_name);
+        self
+    }
+
+    /// Rebuild the job queued as `job_name` from its payload
+    pub fn deserialize(&self, job_name: &str, payload: &str) -> Result<Box<dyn Job>> {
+        let factory = self.factories.get(job_name)
+            .ok_or_else(|| anyhow::anyhow!("No factory registered for job type: {}", job_name))?;
+
+        factory.create_job(payload)
+            .with_context(|| format!("Failed to deserialize {} payload", job_name))
+    }
+
+    pub fn contains(&self, job_name: &str) -> bool {
+        self.factories.contains_key(job_name)
+    }
+
+    /// Registered job names, sorted
+    pub fn job_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.factories.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl std::fmt::Debug for JobRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobRegistry")
+            .field("jobs", &self.job_names())
+            .finish()
+    }
+}
@@ -2,15 +2,21 @@ pub mod send_email_job;
 pub mod database_queue_driver;
 pub mod queue_worker;
 pub mod activity_logged_job;
+pub mod send_notification_batch_job;
+pub mod call_queued_listener_job;
+pub mod process_payment_job;
+pub mod job_registry;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{RwLock, mpsc};
+use tokio::sync::RwLock;
 use chrono::{DateTime, Utc};
 
+use crate::app::http::middleware::correlation_middleware::current_correlation_id;
+
 /// Base trait that all jobs must implement
 #[async_trait]
 pub trait Job: Send + Sync + std::fmt::Debug {
@@ -25,11 +31,16 @@ pub trait Job: Send + Sync + std::fmt::Debug {
         3
     }
 
-    /// Get the delay between retries in seconds
+    /// Base delay in seconds before a retry, multiplied by the attempt number
     fn retry_delay(&self) -> u64 {
         60
     }
 
+    /// Delay before retry number `attempt`, or `None` to wait `retry_delay` times the attempt
+    fn backoff(&self, _attempt: u32) -> Option<chrono::Duration> {
+        None
+    }
+
     /// Determine if this job should be queued
     fn should_queue(&self) -> bool {
         true
@@ -59,6 +70,14 @@ pub trait Job: Send + Sync + std::fmt::Debug {
     }
 }
 
+/// A job type that can be rebuilt from its queued payload
+///
+/// `JOB_NAME` is what `job_name()` returns, available without an instance so
+/// `JobRegistry::register` can key the type by it.
+pub trait NamedJob: Job + serde::de::DeserializeOwned + 'static {
+    const JOB_NAME: &'static str;
+}
+
 /// Job status enumeration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum JobStatus {
@@ -88,6 +107,9 @@ pub struct JobMetadata {
     pub reserved_at: Option<DateTime<Utc>>,
     pub processed_at: Option<DateTime<Utc>>,
     pub timeout_seconds: Option<i32>,
+    /// Correlation ID of the request that dispatched the job
+    #[serde(default)]
+    pub correlation_id: Option<String>,
 }
 
 impl JobMetadata {
@@ -110,9 +132,20 @@ impl JobMetadata {
             reserved_at: None,
             processed_at: None,
             timeout_seconds: None,
+            correlation_id: current_correlation_id().map(|id| id.to_string()),
         }
     }
 
+    /// Span that logs emitted while the job runs are recorded under
+    pub fn span(&self) -> tracing::Span {
+        tracing::info_span!(
+            "job",
+            job_name = %self.job_name,
+            job_id = %self.id,
+            correlation_id = self.correlation_id.as_deref().unwrap_or(""),
+        )
+    }
+
     pub fn mark_processing(&mut self) {
         self.status = JobStatus::Processing;
         self.reserved_at = Some(Utc::now());
@@ -138,8 +171,17 @@ impl JobMetadata {
         self.updated_at = Utc::now();
     }
 
+    /// Whether the run that just failed leaves any of `max_attempts` to retry with
+    ///
+    /// `attempts` counts the failed runs before this one, so the job has run
+    /// `attempts + 1` times.
     pub fn can_retry(&self) -> bool {
-        self.attempts < self.max_attempts
+        self.attempts + 1 < self.max_attempts
+    }
+
+    /// Whether the job may be handed to a worker yet
+    pub fn is_available(&self) -> bool {
+        self.scheduled_at.is_none_or(|available_at| available_at <= Utc::now())
     }
 }
 
@@ -161,6 +203,11 @@ pub trait QueueDriver: Send + Sync {
     /// Update job metadata
     async fn update(&self, metadata: &JobMetadata) -> Result<()>;
 
+    /// Put a job that failed back on its queue, available again at its `scheduled_at`
+    async fn release(&self, metadata: JobMetadata) -> Result<()> {
+        self.push(metadata).await
+    }
+
     /// Get failed jobs
     async fn failed_jobs(&self, limit: Option<u32>) -> Result<Vec<JobMetadata>>;
 
@@ -172,7 +219,9 @@ pub trait QueueDriver: Send + Sync {
 }
 
 /// In-memory queue driver for development and testing
-#[derive(Debug)]
+///
+/// Clones share the same queues.
+#[derive(Debug, Clone)]
 pub struct MemoryQueueDriver {
     queues: Arc<RwLock<HashMap<String, Vec<JobMetadata>>>>,
     failed_jobs: Arc<RwLock<Vec<JobMetadata>>>,
@@ -211,8 +260,10 @@ impl QueueDriver for MemoryQueueDriver {
         let mut queues = self.queues.write().await;
         let queue = queues.entry(queue_name.to_string()).or_insert_with(Vec::new);
 
-        // Pop the highest priority job (first in the list)
-        Ok(if queue.is_empty() { None } else { Some(queue.remove(0)) })
+        // Pop the highest priority job (first in the list) whose delay has passed
+        Ok(queue.iter()
+            .position(JobMetadata::is_available)
+            .map(|index| queue.remove(index)))
     }
 
     async fn size(&self, queue_name: &str) -> Result<u64> {
@@ -264,102 +315,311 @@ impl QueueDriver for MemoryQueueDriver {
     }
 }
 
+/// Queue driver that records pushed jobs without ever handing them to a worker
+///
+/// Installed by `JobDispatcher::fake` so tests can assert which jobs a code
+/// path pushed without running them. `FakeQueueDriver::recording` instead
+/// passes every call on to a real driver, so a worker can run the jobs while
+/// tests inspect what happened to them.
+#[derive(Clone, Default)]
+pub struct FakeQueueDriver {
+    pushed: Arc<RwLock<Vec<JobMetadata>>>,
+    /// Latest metadata of every job pushed, released or updated
+    jobs: Arc<RwLock<HashMap<String, JobMetadata>>>,
+    driver: Option<Arc<dyn QueueDriver>>,
+}
+
+impl std::fmt::Debug for FakeQueueDriver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FakeQueueDriver")
+            .field("driver", &self.driver.as_ref().map(|driver| driver.driver_name()))
+            .finish_non_exhaustive()
+    }
+}
+
+impl FakeQueueDriver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fake that records jobs while `driver` queues and hands them out
+    pub fn recording(driver: impl QueueDriver + 'static) -> Self {
+        Self { driver: Some(Arc::new(driver)), ..Self::default() }
+    }
+
+    /// The latest metadata of a job, whether queued, running or finished
+    pub async fn job(&self, job_id: &str) -> Option<JobMetadata> {
+        self.jobs.read().await.get(job_id).cloned()
+    }
+
+    /// Every job pushed since the fake was installed, in push order
+    pub async fn pushed(&self) -> Vec<JobMetadata> {
+        self.pushed.read().await.clone()
+    }
+
+    /// Assert that a job was pushed
+    pub async fn assert_pushed(&self, job_name: &str) -> bool {
+        self.pushed_count(|job| job.job_name == job_name).await > 0
+    }
+
+    /// Assert that a job was not pushed
+    pub async fn assert_not_pushed(&self, job_name: &str) -> bool {
+        self.pushed_count(|job| job.job_name == job_name).await == 0
+    }
+
+    /// Assert that a job was pushed exactly `times` times
+    pub async fn assert_pushed_times(&self, job_name: &str, times: usize) -> bool {
+        self.pushed_count(|job| job.job_name == job_name).await == times
+    }
+
+    /// Assert that a job was pushed onto a specific queue
+    pub async fn assert_pushed_on(&self, queue_name: &str, job_name: &str) -> bool {
+        self.pushed_count(|job| job.queue_name == queue_name && job.job_name == job_name).await > 0
+    }
+
+    /// Assert that no jobs were pushed
+    pub async fn assert_nothing_pushed(&self) -> bool {
+        self.pushed.read().await.is_empty()
+    }
+
+    async fn pushed_count(&self, matches: impl Fn(&JobMetadata) -> bool) -> usize {
+        self.pushed.read().await.iter().filter(|job| matches(job)).count()
+    }
+
+    async fn record(&self, metadata: &JobMetadata) {
+        self.jobs.write().await.insert(metadata.id.clone(), metadata.clone());
+    }
+}
+
+#[async_trait]
+impl QueueDriver for FakeQueueDriver {
+    async fn push(&self, metadata: JobMetadata) -> Result<()> {
+        tracing::info!("Job {} was faked", metadata.job_name);
+        self.record(&metadata).await;
+        self.pushed.write().await.push(metadata.clone());
+        match &self.driver {
+            Some(driver) => driver.push(metadata).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn pop(&self, queue_name: &str) -> Result<Option<JobMetadata>> {
+        match &self.driver {
+            Some(driver) => driver.pop(queue_name).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn size(&self, queue_name: &str) -> Result<u64> {
+        match &self.driver {
+            Some(driver) => driver.size(queue_name).await,
+            None => Ok(self.pushed_count(|job| job.queue_name == queue_name).await as u64),
+        }
+    }
+
+    async fn delete(&self, job_id: &str) -> Result<()> {
+        self.pushed.write().await.retain(|job| job.id != job_id);
+        match &self.driver {
+            Some(driver) => driver.delete(job_id).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn update(&self, metadata: &JobMetadata) -> Result<()> {
+        self.record(metadata).await;
+        match &self.driver {
+            Some(driver) => driver.update(metadata).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn release(&self, metadata: JobMetadata) -> Result<()> {
+        self.record(&metadata).await;
+        match &self.driver {
+            Some(driver) => driver.release(metadata).await,
+            None => {
+                self.pushed.write().await.push(metadata);
+                Ok(())
+            },
+        }
+    }
+
+    async fn failed_jobs(&self, limit: Option<u32>) -> Result<Vec<JobMetadata>> {
+        match &self.driver {
+            Some(driver) => driver.failed_jobs(limit).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn retry_job(&self, job_id: &str) -> Result<()> {
+        match &self.driver {
+            Some(driver) => driver.retry_job(job_id).await,
+            None => Ok(()),
+        }
+    }
+
+    fn driver_name(&self) -> &'static str {
+        "fake"
+    }
+}
+
 /// Job dispatcher for managing job execution
 pub struct JobDispatcher {
     driver: Box<dyn QueueDriver>,
-    workers: HashMap<String, QueueWorker>,
+    /// Named connections besides the default driver, like Laravel's `queue.connections`
+    connections: HashMap<String, Box<dyn QueueDriver>>,
+    /// Driver set aside while faking, put back by `restore`
+    real_driver: Option<Box<dyn QueueDriver>>,
+    fake: Option<FakeQueueDriver>,
 }
 
 impl JobDispatcher {
     pub fn new(driver: Box<dyn QueueDriver>) -> Self {
         Self {
             driver,
-            workers: HashMap::new(),
+            connections: HashMap::new(),
+            real_driver: None,
+            fake: None,
         }
     }
 
+    /// Register a named connection that jobs can be dispatched onto
+    pub fn add_connection(&mut self, name: &str, driver: Box<dyn QueueDriver>) {
+        self.connections.insert(name.to_string(), driver);
+    }
+
     /// Dispatch a job to the queue
     pub async fn dispatch(&self, job: &dyn Job) -> Result<String> {
+        self.dispatch_on(None, job, None).await
+    }
+
+    /// Dispatch a job that becomes available after `delay`
+    pub async fn dispatch_after(&self, job: &dyn Job, delay: chrono::Duration) -> Result<String> {
+        self.dispatch_on(None, job, Some(delay)).await
+    }
+
+    /// Dispatch a job that becomes available at `when`
+    pub async fn dispatch_at(&self, job: &dyn Job, when: DateTime<Utc>) -> Result<String> {
+        self.push_job(self.driver.as_ref(), job, Some(when)).await
+    }
+
+    /// Dispatch a job onto a named connection, or the default driver for `None`
+    ///
+    /// While faking, every connection records onto the fake.
+    pub async fn dispatch_on(&self, connection: Option<&str>, job: &dyn Job, delay: Option<chrono::Duration>) -> Result<String> {
+        let driver = match connection {
+            Some(name) if !self.is_faking() => self.connections.get(name)
+                .ok_or_else(|| anyhow::anyhow!("Queue connection '{}' not found", name))?
+                .as_ref(),
+            _ => self.driver.as_ref(),
+        };
+
+        self.push_job(driver, job, delay.map(|delay| Utc::now() + delay)).await
+    }
+
+    /// Push a job, leaving it for workers to skip until `available_at` has passed
+    async fn push_job(&self, driver: &dyn QueueDriver, job: &dyn Job, available_at: Option<DateTime<Utc>>) -> Result<String> {
         let payload = job.serialize()?;
-        let metadata = JobMetadata::new(
+        let mut metadata = JobMetadata::new(
             job.job_name().to_string(),
             job.queue_name().to_string(),
             payload,
             job.priority(),
             job.max_attempts(),
         );
+        metadata.scheduled_at = available_at;
 
         let job_id = metadata.id.clone();
-        self.driver.push(metadata).await?;
+        driver.push(metadata).await?;
 
         tracing::info!("Job {} dispatched to queue '{}'", job_id, job.queue_name());
         Ok(job_id)
     }
 
-    /// Start a worker for a specific queue
-    pub async fn start_worker(&mut self, queue_name: String, concurrency: usize) -> Result<()> {
-        let worker = QueueWorker::new(queue_name.clone(), concurrency, self.driver.as_ref()).await?;
-        self.workers.insert(queue_name.clone(), worker);
-
-        tracing::info!("Started worker for queue '{}' with concurrency {}", queue_name, concurrency);
-        Ok(())
+    /// Enable job faking for testing
+    ///
+    /// Swaps the driver for a fresh `FakeQueueDriver`, so dispatched jobs are
+    /// recorded instead of queued and never run.
+    pub fn fake(&mut self) -> FakeQueueDriver {
+        let fake = FakeQueueDriver::new();
+        let driver = std::mem::replace(&mut self.driver, Box::new(fake.clone()));
+        // Faking again keeps the driver set aside the first time
+        self.real_driver.get_or_insert(driver);
+        self.fake = Some(fake.clone());
+        fake
     }
 
-    /// Stop a worker
-    pub async fn stop_worker(&mut self, queue_name: &str) -> Result<()> {
-        if let Some(worker) = self.workers.remove(queue_name) {
-            worker.stop().await?;
-            tracing::info!("Stopped worker for queue '{}'", queue_name);
+    /// Disable job faking, putting the real driver back
+    pub fn restore(&mut self) {
+        if let Some(driver) = self.real_driver.take() {
+            self.driver = driver;
         }
-        Ok(())
+        self.fake = None;
     }
 
-    /// Get queue statistics
-    pub async fn stats(&self, queue_name: &str) -> Result<QueueStats> {
-        let size = self.driver.size(queue_name).await?;
-        let failed_count = self.driver.failed_jobs(None).await?.len() as u64;
+    /// Check if jobs are being faked
+    pub fn is_faking(&self) -> bool {
+        self.fake.is_some()
+    }
 
-        Ok(QueueStats {
-            queue_name: queue_name.to_string(),
-            pending_jobs: size,
-            failed_jobs: failed_count,
-            workers_count: self.workers.get(queue_name).map(|w| w.concurrency).unwrap_or(0),
-        })
+    /// Get faked jobs for testing assertions
+    pub async fn get_pushed_jobs(&self) -> Vec<JobMetadata> {
+        match &self.fake {
+            Some(fake) => fake.pushed().await,
+            None => Vec::new(),
+        }
     }
-}
 
-/// Queue worker for processing jobs
-pub struct QueueWorker {
-    queue_name: String,
-    concurrency: usize,
-    shutdown_tx: Option<mpsc::Sender<()>>,
-}
+    /// Assert that a job was pushed (Laravel-style testing)
+    pub async fn assert_pushed(&self, job_name: &str) -> bool {
+        match &self.fake {
+            Some(fake) => fake.assert_pushed(job_name).await,
+            None => false,
+        }
+    }
 
-impl QueueWorker {
-    pub async fn new(queue_name: String, concurrency: usize, _driver: &dyn QueueDriver) -> Result<Self> {
-        Ok(Self {
-            queue_name,
-            concurrency,
-            shutdown_tx: None,
-        })
+    /// Assert that a job was not pushed
+    pub async fn assert_not_pushed(&self, job_name: &str) -> bool {
+        match &self.fake {
+            Some(fake) => fake.assert_not_pushed(job_name).await,
+            None => false,
+        }
     }
 
-    pub async fn stop(mut self) -> Result<()> {
-        if let Some(tx) = self.shutdown_tx.take() {
-            let _ = tx.send(()).await;
+    /// Assert that a job was pushed exactly `times` times
+    pub async fn assert_pushed_times(&self, job_name: &str, times: usize) -> bool {
+        match &self.fake {
+            Some(fake) => fake.assert_pushed_times(job_name, times).await,
+            None => false,
         }
-        tracing::info!("Stopping worker for queue '{}'", self.queue_name);
-        Ok(())
     }
 
-    /// Get the name of the queue this worker is processing
-    pub fn queue_name(&self) -> &str {
-        &self.queue_name
+    /// Assert that a job was pushed onto a specific queue
+    pub async fn assert_pushed_on(&self, queue_name: &str, job_name: &str) -> bool {
+        match &self.fake {
+            Some(fake) => fake.assert_pushed_on(queue_name, job_name).await,
+            None => false,
+        }
     }
 
-    /// Get the concurrency level of this worker
-    pub fn concurrency(&self) -> usize {
-        self.concurrency
+    /// Assert that no jobs were pushed
+    pub async fn assert_nothing_pushed(&self) -> bool {
+        match &self.fake {
+            Some(fake) => fake.assert_nothing_pushed().await,
+            None => false,
+        }
+    }
+
+    /// Get queue statistics
+    pub async fn stats(&self, queue_name: &str) -> Result<QueueStats> {
+        let size = self.driver.size(queue_name).await?;
+        let failed_count = self.driver.failed_jobs(None).await?.len() as u64;
+
+        Ok(QueueStats {
+            queue_name: queue_name.to_string(),
+            pending_jobs: size,
+            failed_jobs: failed_count,
+        })
     }
 }
 
@@ -369,7 +629,6 @@ pub struct QueueStats {
     pub queue_name: String,
     pub pending_jobs: u64,
     pub failed_jobs: u64,
-    pub workers_count: usize,
 }
 
 /// Global job dispatcher instance
@@ -396,10 +655,92 @@ pub async fn dispatch_job(job: &dyn Job) -> Result<String> {
     dispatcher.dispatch(job).await
 }
 
+/// Dispatch a job using the global dispatcher, available after `delay`
+pub async fn dispatch_job_after(job: &dyn Job, delay: chrono::Duration) -> Result<String> {
+    let dispatcher = job_dispatcher().await;
+    let dispatcher = dispatcher.read().await;
+    dispatcher.dispatch_after(job, delay).await
+}
+
+/// Dispatch a job using the global dispatcher, available at `when`
+pub async fn dispatch_job_at(job: &dyn Job, when: DateTime<Utc>) -> Result<String> {
+    let dispatcher = job_dispatcher().await;
+    let dispatcher = dispatcher.read().await;
+    dispatcher.dispatch_at(job, when).await
+}
+
+/// Enable job faking for testing
+pub async fn fake() {
+    job_dispatcher().await.write().await.fake();
+}
+
+/// Disable job faking
+pub async fn restore() {
+    job_dispatcher().await.write().await.restore();
+}
+
+/// Check if jobs are being faked
+pub async fn is_faking() -> bool {
+    job_dispatcher().await.read().await.is_faking()
+}
+
+/// Laravel-style Queue facade
+pub struct QueueFacade;
+
+impl QueueFacade {
+    /// Enable job faking (Queue::fake())
+    pub async fn fake() {
+        fake().await;
+    }
+
+    /// Disable job faking
+    pub async fn restore() {
+        restore().await;
+    }
+
+    /// Assert a job was pushed (Queue::assertPushed)
+    pub async fn assert_pushed(job_name: &str) -> bool {
+        job_dispatcher().await.read().await.assert_pushed(job_name).await
+    }
+
+    /// Assert a job was not pushed
+    pub async fn assert_not_pushed(job_name: &str) -> bool {
+        job_dispatcher().await.read().await.assert_not_pushed(job_name).await
+    }
+
+    /// Assert a job was pushed a specific number of times
+    pub async fn assert_pushed_times(job_name: &str, times: usize) -> bool {
+        job_dispatcher().await.read().await.assert_pushed_times(job_name, times).await
+    }
+
+    /// Assert a job was pushed onto a specific queue (Queue::assertPushedOn)
+    pub async fn assert_pushed_on(queue_name: &str, job_name: &str) -> bool {
+        job_dispatcher().await.read().await.assert_pushed_on(queue_name, job_name).await
+    }
+
+    /// Assert no jobs were pushed
+    pub async fn assert_nothing_pushed() -> bool {
+        job_dispatcher().await.read().await.assert_nothing_pushed().await
+    }
+
+    /// Get all pushed jobs
+    pub async fn get_pushed_jobs() -> Vec<JobMetadata> {
+        job_dispatcher().await.read().await.get_pushed_jobs().await
+    }
+}
+
 /// Helper macro to dispatch jobs
 #[macro_export]
 macro_rules! dispatch {
     ($job:expr_2021) => {
         $crate::app::jobs::dispatch_job(&$job).await
     };
+}
+
+/// Helper macro to dispatch jobs that become available after a delay
+#[macro_export]
+macro_rules! dispatch_after {
+    ($job:expr_2021, $delay:expr_2021) => {
+        $crate::app::jobs::dispatch_job_after(&$job, $delay).await
+    };
 }
\ No newline at end of file
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Serialize, Deserialize};
+use crate::app::jobs::{Job, NamedJob};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessPaymentJob {
+    pub payment_id: String,
+    pub amount: f64,
+    pub currency: String,
+    pub customer_id: String,
+}
+
+impl ProcessPaymentJob {
+    pub fn new(payment_id: String, amount: f64, currency: String, customer_id: String) -> Self {
+        Self {
+            payment_id,
+            amount,
+            currency,
+            customer_id,
+        }
+    }
+}
+
+impl NamedJob for ProcessPaymentJob {
+    const JOB_NAME: &'static str = "ProcessPaymentJob";
+}
+
+#[async_trait]
+impl Job for ProcessPaymentJob {
+    fn job_name(&self) -> &'static str {
+        Self::JOB_NAME
+    }
+
+    async fn handle(&self) -> Result<()> {
+        tracing::info!("Processing payment {} of {} {} for customer {}", self.payment_id, self.amount, self.currency, self.customer_id);
+
+        if self.amount <= 0.0 {
+            return Err(anyhow::anyhow!("Payment {} has a non-positive amount: {}", self.payment_id, self.amount));
+        }
+
+        tracing::info!("Payment {} processed successfully", self.payment_id);
+        Ok(())
+    }
+
+    fn max_attempts(&self) -> u32 {
+        5 // Payment jobs should retry more often
+    }
+
+    fn retry_delay(&self) -> u64 {
+        120 // 2 minutes between retries
+    }
+
+    fn queue_name(&self) -> &str {
+        "payments"
+    }
+
+    fn priority(&self) -> i32 {
+        -5 // High priority for payments
+    }
+
+    fn timeout(&self) -> Option<u64> {
+        Some(600) // 10 minutes timeout
+    }
+
+    fn serialize(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    async fn failed(&self, error: &anyhow::Error) {
+        tracing::error!("Payment job {} failed permanently: {}", self.payment_id, error);
+    }
+}
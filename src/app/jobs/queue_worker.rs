@@ -1,12 +1,14 @@
 use anyhow::Result;
-use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{mpsc, RwLock};
 use tokio::time::{interval, sleep};
-use tracing::{info, warn, error};
+use tracing::{info, warn, error, Instrument};
 
-use crate::app::jobs::{QueueDriver, Job};
+use crate::app::http::middleware::correlation_middleware::with_correlation_id;
+use crate::app::jobs::job_registry::JobRegistry;
+use crate::app::jobs::{QueueDriver, Job, JobMetadata, NamedJob};
+use crate::app::models::DieselUlid;
 
 /// Worker configuration
 #[derive(Debug, Clone)]
@@ -15,7 +17,8 @@ pub struct WorkerConfig {
     pub concurrency: usize,
     pub max_runtime: Duration,
     pub sleep_duration: Duration,
-    pub retry_delay: Duration,
+    /// Ceiling on the delay before a failed job is retried
+    pub max_retry_delay: Duration,
     pub max_memory_usage: Option<u64>, // In bytes
 }
 
@@ -26,7 +29,7 @@ impl Default for WorkerConfig {
             concurrency: 1,
             max_runtime: Duration::from_secs(300), // 5 minutes
             sleep_duration: Duration::from_secs(1),
-            retry_delay: Duration::from_secs(60),
+            max_retry_delay: Duration::from_secs(3600), // 1 hour
             max_memory_usage: Some(500 * 1024 * 1024), // 500MB
         }
     }
@@ -36,7 +39,7 @@ impl Default for WorkerConfig {
 pub struct QueueWorker {
     config: WorkerConfig,
     driver: Arc<dyn QueueDriver>,
-    job_registry: Arc<RwLock<HashMap<String, Box<dyn JobFactory>>>>,
+    job_registry: Arc<RwLock<JobRegistry>>,
     shutdown_tx: Option<mpsc::Sender<()>>,
     is_running: Arc<RwLock<bool>>,
     stats: Arc<RwLock<WorkerStats>>,
@@ -47,6 +50,15 @@ pub trait JobFactory: Send + Sync {
     fn create_job(&self, payload: &str) -> Result<Box<dyn Job>>;
 }
 
+impl<F> JobFactory for F
+where
+    F: Fn(&str) -> Result<Box<dyn Job>> + Send + Sync,
+{
+    fn create_job(&self, payload: &str) -> Result<Box<dyn Job>> {
+        self(payload)
+    }
+}
+
 /// Worker statistics
 #[derive(Debug, Clone, Default)]
 pub struct WorkerStats {
@@ -61,10 +73,15 @@ pub struct WorkerStats {
 
 impl QueueWorker {
     pub fn new(config: WorkerConfig, driver: Arc<dyn QueueDriver>) -> Self {
+        Self::with_registry(config, driver, JobRegistry::new())
+    }
+
+    /// Create a worker that runs the job types already in `registry`
+    pub fn with_registry(config: WorkerConfig, driver: Arc<dyn QueueDriver>, registry: JobRegistry) -> Self {
         Self {
             config,
             driver,
-            job_registry: Arc::new(RwLock::new(HashMap::new())),
+            job_registry: Arc::new(RwLock::new(registry)),
             shutdown_tx: None,
             is_running: Arc::new(RwLock::new(false)),
             stats: Arc::new(RwLock::new(WorkerStats::default())),
@@ -73,11 +90,16 @@ impl QueueWorker {
 
     /// Register a job factory for a specific job type
     pub async fn register_job<T: Job + 'static>(&self, job_name: &str, factory: impl JobFactory + 'static) {
-        let mut registry = self.job_registry.write().await;
-        registry.insert(job_name.to_string(), Box::new(factory));
+        self.job_registry.write().await.register_factory(job_name, factory);
         info!("Registered job factory for: {}", job_name);
     }
 
+    /// Register a job type under its `JOB_NAME`
+    pub async fn register<T: NamedJob>(&self) {
+        self.job_registry.write().await.register::<T>();
+        info!("Registered job factory for: {}", T::JOB_NAME);
+    }
+
     /// Start the worker
     pub async fn start(&mut self) -> Result<()> {
         if *self.is_running.read().await {
@@ -200,18 +222,45 @@ impl QueueWorker {
         })
     }
 
+    /// Process the next job on the queue, returning whether there was one
+    pub async fn work_once(&self) -> Result<bool> {
+        Self::process_next_job(&self.config, &self.driver, &self.job_registry, &self.stats).await
+    }
+
     async fn process_next_job(
         config: &WorkerConfig,
         driver: &Arc<dyn QueueDriver>,
-        job_registry: &Arc<RwLock<HashMap<String, Box<dyn JobFactory>>>>,
+        job_registry: &Arc<RwLock<JobRegistry>>,
         stats: &Arc<RwLock<WorkerStats>>,
     ) -> Result<bool> {
         // Try to get next job from queue
-        let mut job_metadata = match driver.pop(&config.queue_name).await? {
+        let job_metadata = match driver.pop(&config.queue_name).await? {
             Some(metadata) => metadata,
             None => return Ok(false), // No jobs available
         };
 
+        // Run under the job's span, with the dispatching request's correlation ID back in scope
+        let span = job_metadata.span();
+        let correlation_id = job_metadata.correlation_id
+            .as_deref()
+            .and_then(|id| DieselUlid::from_string(id).ok());
+        let run = Self::run_job(config, driver, job_registry, stats, job_metadata).instrument(span);
+
+        match correlation_id {
+            Some(correlation_id) => with_correlation_id(correlation_id, run).await?,
+            None => run.await?,
+        }
+
+        Ok(true)
+    }
+
+    async fn run_job(
+        config: &WorkerConfig,
+        driver: &Arc<dyn QueueDriver>,
+        job_registry: &Arc<RwLock<JobRegistry>>,
+        stats: &Arc<RwLock<WorkerStats>>,
+        mut job_metadata: JobMetadata,
+    ) -> Result<()> {
         let start_time = std::time::Instant::now();
 
         // Update stats
@@ -224,22 +273,16 @@ impl QueueWorker {
         info!("Processing job {} ({})", job_metadata.id, job_metadata.job_name);
 
         // Create job instance from metadata
-        let job_result = {
-            let registry = job_registry.read().await;
-            match registry.get(&job_metadata.job_name) {
-                Some(factory) => factory.create_job(&job_metadata.payload),
-                None => Err(anyhow::anyhow!("No factory registered for job type: {}", job_metadata.job_name)),
-            }
-        };
+        let job_result = job_registry.read().await.deserialize(&job_metadata.job_name, &job_metadata.payload);
 
         let job = match job_result {
             Ok(job) => job,
             Err(e) => {
-                error!("Failed to create job {}: {}", job_metadata.id, e);
-                job_metadata.mark_failed(&e.to_string());
+                error!("Failed to create job {}: {:#}", job_metadata.id, e);
+                job_metadata.mark_failed(&format!("{:#}", e));
                 driver.update(&job_metadata).await?;
                 stats.write().await.jobs_failed += 1;
-                return Ok(true);
+                return Ok(());
             }
         };
 
@@ -266,18 +309,18 @@ impl QueueWorker {
                 if job_metadata.can_retry() {
                     // Retry the job
                     job_metadata.mark_retrying();
-                    job_metadata.scheduled_at = Some(chrono::Utc::now() + chrono::Duration::seconds(config.retry_delay.as_secs() as i64));
-                    driver.update(&job_metadata).await?;
-                    driver.push(job_metadata.clone()).await?; // Re-queue for retry
+                    job_metadata.scheduled_at = Some(chrono::Utc::now() + Self::retry_delay(config, job.as_ref(), job_metadata.attempts));
+                    job_metadata.error_message = Some(e.to_string());
+                    driver.release(job_metadata.clone()).await?; // Re-queue for retry
                     stats.write().await.jobs_retried += 1;
-                    info!("Job {} will be retried (attempt {}/{})", job_metadata.id, job_metadata.attempts, job_metadata.max_attempts);
+                    info!("Job {} will be retried at {:?} (attempt {}/{})", job_metadata.id, job_metadata.scheduled_at, job_metadata.attempts + 1, job_metadata.max_attempts);
                 } else {
                     // Max retries exceeded
                     job_metadata.mark_failed(&e.to_string());
                     driver.update(&job_metadata).await?;
                     job.failed(&e).await;
                     stats.write().await.jobs_failed += 1;
-                    error!("Job {} failed permanently after {} attempts", job_metadata.id, job_metadata.attempts);
+                    error!("Job {} failed permanently after {} attempts", job_metadata.id, job_metadata.attempts + 1);
                 }
             }
             Err(_) => {
@@ -287,13 +330,14 @@ impl QueueWorker {
 
                 if job_metadata.can_retry() {
                     job_metadata.mark_retrying();
-                    job_metadata.scheduled_at = Some(chrono::Utc::now() + chrono::Duration::seconds(config.retry_delay.as_secs() as i64));
-                    driver.update(&job_metadata).await?;
-                    driver.push(job_metadata.clone()).await?;
+                    job_metadata.scheduled_at = Some(chrono::Utc::now() + Self::retry_delay(config, job.as_ref(), job_metadata.attempts));
+                    job_metadata.error_message = Some(timeout_error);
+                    driver.release(job_metadata.clone()).await?;
                     stats.write().await.jobs_retried += 1;
                 } else {
                     job_metadata.mark_failed(&timeout_error);
                     driver.update(&job_metadata).await?;
+                    job.failed(&anyhow::anyhow!(timeout_error)).await;
                     stats.write().await.jobs_failed += 1;
                 }
             }
@@ -302,7 +346,20 @@ impl QueueWorker {
         // Update total processing time
         stats.write().await.total_processing_time += processing_time;
 
-        Ok(true)
+        Ok(())
+    }
+
+    /// Delay before retry number `attempt`, capped at `max_retry_delay`
+    ///
+    /// The job's own backoff when it has one, else its `retry_delay` times the
+    /// attempt, so each retry waits longer than the last.
+    pub fn retry_delay(config: &WorkerConfig, job: &dyn Job, attempt: u32) -> chrono::Duration {
+        let delay = job.backoff(attempt).unwrap_or_else(|| {
+            chrono::Duration::seconds(job.retry_delay().saturating_mul(attempt as u64).min(i64::MAX as u64) as i64)
+        });
+        let ceiling = chrono::Duration::from_std(config.max_retry_delay).unwrap_or(chrono::Duration::MAX);
+
+        delay.min(ceiling)
     }
 }
 
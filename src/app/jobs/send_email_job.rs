@@ -1,7 +1,7 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Serialize, Deserialize};
-use crate::app::jobs::Job;
+use crate::app::jobs::{Job, NamedJob};
 use crate::app::mail::{mail_manager, welcome_mail::WelcomeMail, password_reset_mail::PasswordResetMail};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,10 +53,14 @@ impl SendEmailJob {
     }
 }
 
+impl NamedJob for SendEmailJob {
+    const JOB_NAME: &'static str = "SendEmailJob";
+}
+
 #[async_trait]
 impl Job for SendEmailJob {
     fn job_name(&self) -> &'static str {
-        "SendEmailJob"
+        Self::JOB_NAME
     }
 
     async fn handle(&self) -> Result<()> {
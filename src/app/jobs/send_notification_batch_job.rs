@@ -0,0 +1,246 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use serde::{Serialize, Deserialize};
+use crate::app::broadcasting::{BroadcastMessage as WebSocketBroadcastMessage, websocket::websocket_manager};
+use crate::app::jobs::{Job, JobDispatcher, NamedJob};
+use crate::app::mail::mail_manager;
+use crate::app::models::DieselUlid;
+use crate::app::notifications::channels::database_channel::DatabaseChannel;
+use crate::app::notifications::channels::mail_channel::MailChannel;
+use crate::app::notifications::notification::{
+    filter_preferred_channels, BroadcastMessage, DatabaseMessage, MailMessage, Notifiable, Notification, NotificationChannel,
+};
+use crate::config::Config;
+use crate::schema::notifications;
+
+/// Channels whose message can be built once and shared by every recipient
+const BATCHABLE_CHANNELS: [NotificationChannel; 3] = [
+    NotificationChannel::Mail,
+    NotificationChannel::Database,
+    NotificationChannel::Broadcast,
+];
+
+/// One recipient of a batched notification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchRecipient {
+    /// `Notifiable::get_key`, e.g. `User_01H...`
+    pub key: String,
+    pub notifiable_type: String,
+    /// Channels left after the recipient's preferences are applied
+    pub channels: Vec<NotificationChannel>,
+    /// Address for the mail channel
+    pub email: Option<String>,
+}
+
+/// Sends one notification to a chunk of recipients
+///
+/// Each channel's message is built once, before the notification is queued,
+/// and only the recipient varies. The notification must therefore not depend
+/// on who receives it. Only mail, database and broadcast can be batched;
+/// other channels returned by `via` are skipped with a warning.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendNotificationBatchJob {
+    pub notification_type: String,
+    pub recipients: Vec<BatchRecipient>,
+    pub mail: Option<MailMessage>,
+    pub database: Option<DatabaseMessage>,
+    pub broadcast: Option<BroadcastMessage>,
+}
+
+impl SendNotificationBatchJob {
+    /// Split the notifiables into jobs of at most `chunk_size` recipients
+    pub async fn chunks<N: Notifiable>(
+        notifiables: &[N],
+        notification: &dyn Notification,
+        chunk_size: usize,
+    ) -> Result<Vec<Self>> {
+        let notification_type = notification.notification_type();
+        let mut recipients = Vec::with_capacity(notifiables.len());
+        let mut mail = None;
+        let mut database = None;
+        let mut broadcast = None;
+
+        for notifiable in notifiables {
+            let preferences = notifiable.notification_preferences().await;
+            let mut channels = Vec::new();
+
+            for channel in filter_preferred_channels(notification, notifiable, notification.via(notifiable)) {
+                if !BATCHABLE_CHANNELS.contains(&channel) {
+                    tracing::warn!("Channel '{}' cannot be batched; skipping it for {}", channel.to_string(), notification_type);
+                    continue;
+                }
+                if channels.contains(&channel)
+                    || !notification.should_send(notifiable, &channel)
+                    || !notifiable.can_receive_notification(notification_type, &channel).await
+                    || !channel.preference_key().and_then(|key| preferences.get(key).copied()).unwrap_or(true)
+                {
+                    continue;
+                }
+
+                // Build each channel's message from the first recipient that uses it
+                match channel {
+                    NotificationChannel::Mail if mail.is_none() => mail = Some(notification.to_mail(notifiable)?),
+                    NotificationChannel::Database if database.is_none() => database = Some(notification.to_database(notifiable)?),
+                    NotificationChannel::Broadcast if broadcast.is_none() => broadcast = Some(notification.to_broadcast(notifiable)?),
+                    _ => {},
+                }
+                channels.push(channel);
+            }
+
+            if channels.is_empty() {
+                continue;
+            }
+
+            let email = if channels.contains(&NotificationChannel::Mail) {
+                notifiable.route_notification_for(&NotificationChannel::Mail).await
+            } else {
+                None
+            };
+
+            recipients.push(BatchRecipient {
+                key: notifiable.get_key(),
+                notifiable_type: DatabaseChannel::detect_notifiable_type(notifiable),
+                channels,
+                email,
+            });
+        }
+
+        Ok(recipients
+            .chunks(chunk_size.max(1))
+            .map(|chunk| Self {
+                notification_type: notification_type.to_string(),
+                recipients: chunk.to_vec(),
+                mail: mail.clone(),
+                database: database.clone(),
+                broadcast: broadcast.clone(),
+            })
+            .collect())
+    }
+
+    /// Queue the jobs, starting each one `delay_seconds` after the previous
+    pub async fn dispatch_all(dispatcher: &JobDispatcher, jobs: &[Self], delay_seconds: u64) -> Result<usize> {
+        for (i, job) in jobs.iter().enumerate() {
+            let delay = chrono::Duration::seconds((delay_seconds * i as u64) as i64);
+            dispatcher.dispatch_after(job, delay).await?;
+        }
+
+        Ok(jobs.len())
+    }
+
+    fn recipients_on<'a>(&'a self, channel: &'a NotificationChannel) -> impl Iterator<Item = &'a BatchRecipient> + 'a {
+        self.recipients.iter().filter(move |recipient| recipient.channels.contains(channel))
+    }
+
+    async fn send_mail(&self, message: &MailMessage) -> Result<()> {
+        let manager = mail_manager().await;
+        let manager = manager.read().await;
+
+        for recipient in self.recipients_on(&NotificationChannel::Mail) {
+            let Some(email) = &recipient.email else {
+                tracing::warn!("No email address found for notifiable entity: {}", recipient.key);
+                continue;
+            };
+
+            let mut message = message.clone();
+            message.to = email.clone();
+
+            // One bad address must not stop the rest of the chunk
+            if let Err(e) = manager.send_message(MailChannel::to_mail_system_message(message)).await {
+                tracing::error!("Failed to send {} email to {}: {}", self.notification_type, email, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn store_database(&self, message: &DatabaseMessage) -> Result<()> {
+        let now = chrono::Utc::now();
+        let rows: Vec<_> = self
+            .recipients_on(&NotificationChannel::Database)
+            .map(|recipient| (
+                notifications::id.eq(DieselUlid::new().to_string()),
+                notifications::type_.eq(self.notification_type.clone()),
+                notifications::notifiable_id.eq(recipient.key.clone()),
+                notifications::notifiable_type.eq(recipient.notifiable_type.clone()),
+                notifications::data.eq(message.data.clone()),
+                notifications::created_at.eq(now),
+                notifications::updated_at.eq(now),
+            ))
+            .collect();
+
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        let config = Config::load()?;
+        let pool = crate::database::create_pool(&config)?;
+        let mut conn = pool.get()?;
+
+        // A single insert for the whole chunk
+        diesel::insert_into(notifications::table)
+            .values(&rows)
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    async fn broadcast(&self, message: &BroadcastMessage) -> Result<()> {
+        let manager = websocket_manager().await;
+
+        for recipient in self.recipients_on(&NotificationChannel::Broadcast) {
+            let websocket_message = WebSocketBroadcastMessage {
+                channel: format!("user.{}", recipient.key),
+                event: self.notification_type.clone(),
+                data: message.data.clone(),
+                timestamp: chrono::Utc::now(),
+                id: None,
+            };
+
+            if let Err(e) = manager.broadcast(websocket_message).await {
+                tracing::error!("Failed to broadcast {} to {}: {}", self.notification_type, recipient.key, e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl NamedJob for SendNotificationBatchJob {
+    const JOB_NAME: &'static str = "SendNotificationBatchJob";
+}
+
+#[async_trait]
+impl Job for SendNotificationBatchJob {
+    fn job_name(&self) -> &'static str {
+        Self::JOB_NAME
+    }
+
+    async fn handle(&self) -> Result<()> {
+        tracing::info!("Sending {} to {} recipients", self.notification_type, self.recipients.len());
+
+        if let Some(message) = &self.database {
+            self.store_database(message)?;
+        }
+        if let Some(message) = &self.mail {
+            self.send_mail(message).await?;
+        }
+        if let Some(message) = &self.broadcast {
+            self.broadcast(message).await?;
+        }
+
+        Ok(())
+    }
+
+    fn queue_name(&self) -> &str {
+        "notifications"
+    }
+
+    fn max_attempts(&self) -> u32 {
+        1 // Recipients already sent to would be sent to again
+    }
+
+    fn serialize(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
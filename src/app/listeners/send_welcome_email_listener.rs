@@ -95,6 +95,10 @@ impl EventListener for SendWelcomeEmailListener {
         true
     }
 
+    fn as_should_queue(&self) -> Option<&dyn ShouldQueueListener> {
+        Some(self)
+    }
+
     fn queue_name(&self) -> Option<&str> {
         self.queue.as_deref()
     }
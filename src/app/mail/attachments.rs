@@ -0,0 +1,120 @@
+use base64::{Engine as _, engine::general_purpose};
+
+use crate::app::mail::{Attachment, AttachmentData};
+use crate::config::mail::{MailConfig, DEFAULT_ALLOWED_ATTACHMENT_TYPES, DEFAULT_MAX_ATTACHMENT_BYTES};
+
+/// Why a message's attachments were refused
+#[derive(Debug, thiserror::Error)]
+pub enum AttachmentError {
+    #[error("Attachment '{filename}' is not valid base64: {reason}")]
+    InvalidBase64 { filename: String, reason: String },
+
+    #[error("Attachment '{filename}' could not be read from '{path}': {reason}")]
+    Unreadable { filename: String, path: String, reason: String },
+
+    #[error("Attachment '{filename}' has type '{content_type}', which is not allowed")]
+    DisallowedType { filename: String, content_type: String },
+
+    #[error("Attachments total {total} bytes, over the {limit} byte limit")]
+    TooLarge { total: u64, limit: u64 },
+}
+
+/// An attachment with its content loaded
+#[derive(Debug, Clone)]
+pub struct ResolvedAttachment {
+    pub filename: String,
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Size and type rules attachments must pass before a message is sent
+#[derive(Debug, Clone)]
+pub struct AttachmentPolicy {
+    /// Largest combined size of a message's attachments, 0 for no limit
+    pub max_total_bytes: u64,
+    /// Allowed MIME types, `image/*` allowing a whole family; empty allows every type
+    pub allowed_types: Vec<String>,
+}
+
+impl Default for AttachmentPolicy {
+    fn default() -> Self {
+        Self {
+            max_total_bytes: DEFAULT_MAX_ATTACHMENT_BYTES,
+            allowed_types: DEFAULT_ALLOWED_ATTACHMENT_TYPES.split(',').map(str::to_string).collect(),
+        }
+    }
+}
+
+impl AttachmentPolicy {
+    pub fn from_config(config: &MailConfig) -> Self {
+        Self {
+            max_total_bytes: config.max_attachment_bytes,
+            allowed_types: config.allowed_attachment_types.clone(),
+        }
+    }
+
+    /// Whether `content_type` matches an allowed type, ignoring case and parameters
+    pub fn allows(&self, content_type: &str) -> bool {
+        if self.allowed_types.is_empty() {
+            return true;
+        }
+
+        let essence = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        self.allowed_types.iter().any(|allowed| {
+            let allowed = allowed.trim().to_ascii_lowercase();
+            match allowed.strip_suffix("/*") {
+                Some(family) => essence.split_once('/').is_some_and(|(kind, _)| kind == family),
+                None => allowed == essence,
+            }
+        })
+    }
+
+    /// Load every attachment, checking types before reading and the total size after
+    pub async fn resolve(&self, attachments: &[Attachment]) -> Result<Vec<ResolvedAttachment>, AttachmentError> {
+        if let Some(attachment) = attachments.iter().find(|attachment| !self.allows(&attachment.content_type)) {
+            return Err(AttachmentError::DisallowedType {
+                filename: attachment.filename.clone(),
+                content_type: attachment.content_type.clone(),
+            });
+        }
+
+        let mut resolved = Vec::with_capacity(attachments.len());
+        let mut total = 0u64;
+        for attachment in attachments {
+            let bytes = attachment.bytes().await?;
+            total += bytes.len() as u64;
+            if self.max_total_bytes > 0 && total > self.max_total_bytes {
+                return Err(AttachmentError::TooLarge { total, limit: self.max_total_bytes });
+            }
+
+            resolved.push(ResolvedAttachment {
+                filename: attachment.filename.clone(),
+                content_type: attachment.content_type.clone(),
+                bytes,
+            });
+        }
+
+        Ok(resolved)
+    }
+}
+
+impl Attachment {
+    /// The attachment's content, read through the storage module or decoded from base64
+    pub async fn bytes(&self) -> Result<Vec<u8>, AttachmentError> {
+        match &self.data {
+            AttachmentData::Bytes(bytes) => Ok(bytes.clone()),
+            AttachmentData::Base64(encoded) => general_purpose::STANDARD
+                .decode(encoded.trim())
+                .map_err(|e| AttachmentError::InvalidBase64 {
+                    filename: self.filename.clone(),
+                    reason: e.to_string(),
+                }),
+            AttachmentData::Path(path) => crate::storage::get(path).await
+                .map_err(|e| AttachmentError::Unreadable {
+                    filename: self.filename.clone(),
+                    path: path.clone(),
+                    reason: e.to_string(),
+                }),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use crate::app::mail::{MailDriver, MailMessage};
+use crate::app::mail::attachments::{AttachmentPolicy, ResolvedAttachment};
+use crate::app::utils::http_client::HttpClient;
+use crate::config::mail::MailConfig;
+
+/// Sends mail through Mailgun's HTTP API, for hosts that block outbound SMTP
+#[derive(Debug, Clone)]
+pub struct MailgunDriver {
+    pub domain: String,
+    pub api_key: String,
+    /// API base URL, `https://api.eu.mailgun.net` for EU domains
+    pub endpoint: String,
+    pub from_name: String,
+    pub from_address: String,
+    /// Size and type limits attachments are checked against before sending
+    pub attachments: AttachmentPolicy,
+}
+
+/// The form a message is posted to Mailgun as
+#[derive(Debug, Clone)]
+pub struct MailgunRequest {
+    /// Text fields in order; `to`, `cc` and `bcc` repeat once per recipient
+    pub fields: Vec<(String, String)>,
+    pub attachments: Vec<ResolvedAttachment>,
+}
+
+impl MailgunRequest {
+    /// Every value of the field `name`
+    pub fn values(&self, name: &str) -> Vec<&str> {
+        self.fields.iter()
+            .filter(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    fn into_form(self) -> Result<Form> {
+        let mut form = Form::new();
+        for (name, value) in self.fields {
+            form = form.text(name, value);
+        }
+
+        for attachment in self.attachments {
+            let part = Part::bytes(attachment.bytes)
+                .file_name(attachment.filename.clone())
+                .mime_str(&attachment.content_type)
+                .with_context(|| format!("Attachment '{}' has an invalid content type", attachment.filename))?;
+            form = form.part("attachment", part);
+        }
+
+        Ok(form)
+    }
+}
+
+impl MailgunDriver {
+    pub fn new(domain: String, api_key: String, from_name: String, from_address: String) -> Self {
+        Self {
+            domain,
+            api_key,
+            endpoint: "https://api.mailgun.net".to_string(),
+            from_name,
+            from_address,
+            attachments: AttachmentPolicy::default(),
+        }
+    }
+
+    pub fn from_config(config: &MailConfig) -> Self {
+        Self::new(
+            config.mailgun_domain.clone(),
+            config.mailgun_secret.clone(),
+            config.from_name.clone(),
+            config.from_address.clone(),
+        )
+        .with_endpoint(config.mailgun_endpoint.clone())
+        .with_attachment_policy(AttachmentPolicy::from_config(config))
+    }
+
+    pub fn with_endpoint(mut self, endpoint: String) -> Self {
+        self.endpoint = endpoint;
+        self
+    }
+
+    pub fn with_attachment_policy(mut self, attachments: AttachmentPolicy) -> Self {
+        self.attachments = attachments;
+        self
+    }
+
+    /// `{endpoint}/v3/{domain}/messages`
+    pub fn messages_url(&self) -> String {
+        format!("{}/v3/{}/messages", self.endpoint.trim_end_matches('/'), self.domain)
+    }
+
+    /// Map a message onto the fields of Mailgun's messages endpoint
+    pub async fn build_request(&self, mut mail_message: MailMessage) -> Result<MailgunRequest> {
+        // Refuse the message before anything is sent if its attachments break the limits
+        let attachments = self.attachments.resolve(&mail_message.attachments).await?;
+
+        let from_address = mail_message.from.clone().unwrap_or_else(|| {
+            format!("{} <{}>", self.from_name, self.from_address)
+        });
+
+        let mut fields = vec![("from".to_string(), from_address)];
+        fields.extend(mail_message.to.iter().map(|to| ("to".to_string(), to.clone())));
+        fields.extend(mail_message.cc.iter().map(|cc| ("cc".to_string(), cc.clone())));
+        fields.extend(mail_message.bcc.iter().map(|bcc| ("bcc".to_string(), bcc.clone())));
+        fields.push(("subject".to_string(), mail_message.subject.clone()));
+
+        // Render HTML first so template mail has its text alternative as well
+        let html_content = mail_message.content.to_html().await?;
+        fields.push(("html".to_string(), html_content));
+        fields.push(("text".to_string(), mail_message.content.to_text()));
+
+        // Mailgun takes any other header as an `h:` field
+        if let Some(reply_to) = &mail_message.reply_to {
+            fields.push(("h:Reply-To".to_string(), reply_to.clone()));
+        }
+        let mut headers: Vec<_> = mail_message.headers.iter().collect();
+        headers.sort();
+        for (name, value) in headers {
+            fields.push((format!("h:{}", name), value.clone()));
+        }
+
+        Ok(MailgunRequest { fields, attachments })
+    }
+}
+
+#[async_trait]
+impl MailDriver for MailgunDriver {
+    async fn send(&self, mail_message: MailMessage) -> Result<()> {
+        tracing::info!("Mailgun Driver: Sending email via {}", self.domain);
+
+        let form = self.build_request(mail_message).await?.into_form()?;
+
+        let client = HttpClient::shared()?;
+        let response = client.send(
+            client.post(&self.messages_url())
+                .basic_auth("api", Some(&self.api_key))
+                .multipart(form)
+        ).await?;
+
+        if response.status().is_success() {
+            tracing::info!("Email sent successfully via Mailgun");
+            Ok(())
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            tracing::error!("Failed to send email via Mailgun ({}): {}", status, error_text);
+            Err(anyhow::anyhow!("Mailgun send failed ({}): {}", status, error_text))
+        }
+    }
+
+    fn driver_name(&self) -> &'static str {
+        "mailgun"
+    }
+}
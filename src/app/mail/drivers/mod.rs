@@ -1,5 +1,7 @@
 pub mod smtp_driver;
+pub mod mailgun_driver;
 pub mod log_driver;
 
-pub use smtp_driver::SmtpDriver;
+pub use smtp_driver::{SmtpDriver, SmtpPoolConfig};
+pub use mailgun_driver::MailgunDriver;
 pub use log_driver::LogDriver;
\ No newline at end of file
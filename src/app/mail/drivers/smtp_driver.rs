@@ -1,11 +1,16 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use lettre::{Message, AsyncTransport, AsyncSmtpTransport, Tokio1Executor};
+use lettre::transport::smtp::PoolConfig;
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::transport::smtp::client::{Tls, TlsParameters};
 use lettre::message::{header::ContentType, MultiPart, SinglePart};
-use crate::app::mail::{MailDriver, MailMessage, Attachment, AttachmentData};
-use base64::{Engine as _, engine::general_purpose};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use crate::app::mail::{MailDriver, MailMessage};
+use crate::app::mail::attachments::{AttachmentPolicy, ResolvedAttachment};
+use crate::config::mail::MailConfig;
 
 #[derive(Debug, Clone)]
 pub struct SmtpDriver {
@@ -16,6 +21,44 @@ pub struct SmtpDriver {
     pub encryption: SmtpEncryption,
     pub from_name: String,
     pub from_address: String,
+    pub timeout: Option<Duration>,
+    pub pool: SmtpPoolConfig,
+    /// Size and type limits attachments are checked against before sending
+    pub attachments: AttachmentPolicy,
+    transport: Arc<Mutex<Option<PooledTransport>>>,
+}
+
+/// Connection reuse settings for `SmtpDriver`
+#[derive(Debug, Clone)]
+pub struct SmtpPoolConfig {
+    /// Most connections kept open at once
+    pub max_size: u32,
+    /// How long an idle connection stays open; it is checked with NOOP before reuse
+    pub idle_timeout: Duration,
+    /// Messages sent before the pool's connections are closed and reopened, 0 for no limit
+    pub max_messages: u32,
+}
+
+impl Default for SmtpPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 5,
+            idle_timeout: Duration::from_secs(60),
+            max_messages: 100,
+        }
+    }
+}
+
+/// Transport shared by every send, with the number of messages it has sent
+struct PooledTransport {
+    mailer: AsyncSmtpTransport<Tokio1Executor>,
+    sent: u32,
+}
+
+impl std::fmt::Debug for PooledTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PooledTransport").field("sent", &self.sent).finish()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -35,7 +78,40 @@ impl SmtpDriver {
             encryption: SmtpEncryption::StartTls,
             from_name,
             from_address,
+            timeout: None,
+            pool: SmtpPoolConfig::default(),
+            attachments: AttachmentPolicy::default(),
+            transport: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn from_config(config: &MailConfig) -> Self {
+        let encryption = match config.encryption.as_str() {
+            "ssl" => SmtpEncryption::Tls,
+            "tls" => SmtpEncryption::StartTls,
+            _ => SmtpEncryption::None,
+        };
+
+        let mut driver = Self::new(
+            config.host.clone(),
+            config.port,
+            config.from_name.clone(),
+            config.from_address.clone(),
+        )
+        .with_encryption(encryption)
+        .with_pool(SmtpPoolConfig {
+            max_size: config.pool_size,
+            idle_timeout: Duration::from_secs(config.pool_idle_timeout_seconds),
+            max_messages: config.pool_max_messages,
+        });
+        driver.timeout = Some(Duration::from_secs(config.timeout_seconds));
+        driver.attachments = AttachmentPolicy::from_config(config);
+
+        if !config.username.is_empty() {
+            driver = driver.with_credentials(config.username.clone(), config.password.clone());
         }
+
+        driver
     }
 
     pub fn with_credentials(mut self, username: String, password: String) -> Self {
@@ -49,9 +125,47 @@ impl SmtpDriver {
         self
     }
 
+    pub fn with_pool(mut self, pool: SmtpPoolConfig) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    pub fn with_attachment_policy(mut self, attachments: AttachmentPolicy) -> Self {
+        self.attachments = attachments;
+        self
+    }
+
+    /// Pooled transport, reopened once it has sent `max_messages` messages
+    async fn transport(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>> {
+        let mut transport = self.transport.lock().await;
+
+        if let Some(pooled) = transport.as_mut() {
+            if self.pool.max_messages == 0 || pooled.sent < self.pool.max_messages {
+                pooled.sent += 1;
+                return Ok(pooled.mailer.clone());
+            }
+            tracing::debug!("Reopening SMTP connections after {} messages", pooled.sent);
+        }
+
+        let mailer = self.build_transport().await?;
+        *transport = Some(PooledTransport { mailer: mailer.clone(), sent: 1 });
+        Ok(mailer)
+    }
+
+    /// Drop the pooled transport so the next send opens fresh connections
+    async fn discard_transport(&self) {
+        *self.transport.lock().await = None;
+    }
+
     async fn build_transport(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>> {
+        let pool_config = PoolConfig::new()
+            .max_size(self.pool.max_size.max(1))
+            .idle_timeout(self.pool.idle_timeout);
+
         let mut transport_builder = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.host)
-            .port(self.port);
+            .port(self.port)
+            .timeout(self.timeout)
+            .pool_config(pool_config);
 
         // Add authentication if credentials are provided
         if let (Some(username), Some(password)) = (&self.username, &self.password) {
@@ -75,10 +189,14 @@ impl SmtpDriver {
         Ok(transport_builder.build())
     }
 
-    async fn build_email(&self, mut mail_message: MailMessage) -> Result<Message> {
+    /// Build the lettre message whose envelope the transport delivers to
+    pub async fn build_email(&self, mut mail_message: MailMessage) -> Result<Message> {
         // Compile markdown if needed
         mail_message.content.compile_markdown().await?;
 
+        // Refuse the message before anything is built if its attachments break the limits
+        let attachments = self.attachments.resolve(&mail_message.attachments).await?;
+
         // Get from address
         let from_address = mail_message.from.unwrap_or_else(|| {
             format!("{} <{}>", self.from_name, self.from_address)
@@ -98,6 +216,7 @@ impl SmtpDriver {
             message_builder = message_builder.cc(cc.parse()?);
         }
 
+        // lettre adds Bcc recipients to the envelope and leaves the header out
         for bcc in &mail_message.bcc {
             message_builder = message_builder.bcc(bcc.parse()?);
         }
@@ -132,8 +251,8 @@ impl SmtpDriver {
             );
 
         // Add attachments
-        for attachment in mail_message.attachments {
-            multipart = self.add_attachment(multipart, attachment).await?;
+        for attachment in attachments {
+            multipart = Self::add_attachment(multipart, attachment);
         }
 
         let message = message_builder.multipart(multipart)?;
@@ -141,28 +260,14 @@ impl SmtpDriver {
         Ok(message)
     }
 
-    async fn add_attachment(&self, mut multipart: MultiPart, attachment: Attachment) -> Result<MultiPart> {
+    fn add_attachment(multipart: MultiPart, attachment: ResolvedAttachment) -> MultiPart {
         let content_type: ContentType = attachment.content_type.parse()
             .unwrap_or(ContentType::parse("application/octet-stream").unwrap());
 
-        let body = match attachment.data {
-            AttachmentData::Path(path) => {
-                tokio::fs::read(&path).await
-                    .map_err(|e| anyhow::anyhow!("Failed to read attachment file {}: {}", path, e))?
-            },
-            AttachmentData::Bytes(bytes) => bytes,
-            AttachmentData::Base64(base64_str) => {
-                general_purpose::STANDARD.decode(&base64_str)
-                    .map_err(|e| anyhow::anyhow!("Failed to decode base64 attachment: {}", e))?
-            },
-        };
-
         let attachment_part = lettre::message::Attachment::new(attachment.filename)
-            .body(body, content_type);
+            .body(attachment.bytes, content_type);
 
-        multipart = multipart.singlepart(attachment_part);
-
-        Ok(multipart)
+        multipart.singlepart(attachment_part)
     }
 
 }
@@ -172,20 +277,28 @@ impl MailDriver for SmtpDriver {
     async fn send(&self, mail_message: MailMessage) -> Result<()> {
         tracing::info!("SMTP Driver: Sending email via {}:{}", self.host, self.port);
 
-        // Build the transport
-        let mailer = self.build_transport().await?;
-
         // Build the email message
         let email = self.build_email(mail_message).await?;
 
-        // Send the email
-        match mailer.send(email).await {
+        // Send over a pooled connection, retrying once on fresh connections
+        // unless the server rejected the message outright
+        let mut result = self.transport().await?.send(email.clone()).await;
+        if let Err(e) = &result {
+            if !e.is_permanent() {
+                tracing::warn!("SMTP send failed on pooled connection, retrying on a fresh one: {}", e);
+                self.discard_transport().await;
+                result = self.transport().await?.send(email).await;
+            }
+        }
+
+        match result {
             Ok(_) => {
                 tracing::info!("Email sent successfully via SMTP");
                 Ok(())
             }
             Err(e) => {
                 tracing::error!("Failed to send email via SMTP: {}", e);
+                self.discard_transport().await;
                 Err(anyhow::anyhow!("SMTP send failed: {}", e))
             }
         }
@@ -1,11 +1,17 @@
 pub mod password_reset_mail;
 pub mod welcome_mail;
 pub mod drivers;
+pub mod template;
+pub mod attachments;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::config::mail::{MailConfig, MailDefaults};
 
 /// Base trait that all mail implementations must implement
 #[async_trait]
@@ -16,12 +22,22 @@ pub trait Mailable: Send + Sync {
     /// Get the recipients for this mail
     fn to(&self) -> Vec<String>;
 
-    /// Get the sender (optional, falls back to default)
+    /// Get the carbon copy recipients
+    fn cc(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Get the blind carbon copy recipients, kept out of the delivered headers
+    fn bcc(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Get the sender (optional, falls back to the configured default)
     fn from(&self) -> Option<String> {
         None
     }
 
-    /// Get the reply-to address (optional)
+    /// Get the reply-to address (optional, falls back to the configured default)
     fn reply_to(&self) -> Option<String> {
         None
     }
@@ -112,10 +128,78 @@ pub trait MailDriver: Send + Sync {
     fn driver_name(&self) -> &'static str;
 }
 
+/// Mail driver that records messages instead of sending them
+///
+/// Installed by `MailManager::fake` so tests can assert what a code path
+/// sent without a live SMTP server.
+#[derive(Debug, Clone, Default)]
+pub struct FakeMailDriver {
+    sent: Arc<RwLock<Vec<MailMessage>>>,
+}
+
+impl FakeMailDriver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every message sent since the fake was installed, in send order
+    pub async fn sent(&self) -> Vec<MailMessage> {
+        self.sent.read().await.clone()
+    }
+
+    /// Assert that a message matching `predicate` was sent
+    pub async fn assert_sent(&self, predicate: impl Fn(&MailMessage) -> bool) -> bool {
+        self.sent_count(predicate).await > 0
+    }
+
+    /// Assert that no message matching `predicate` was sent
+    pub async fn assert_not_sent(&self, predicate: impl Fn(&MailMessage) -> bool) -> bool {
+        self.sent_count(predicate).await == 0
+    }
+
+    /// Assert that a message was sent to `address`, as a to, cc or bcc recipient
+    pub async fn assert_sent_to(&self, address: &str) -> bool {
+        self.sent_count(|message| message.is_addressed_to(address)).await > 0
+    }
+
+    /// Assert that exactly `times` messages matching `predicate` were sent
+    pub async fn assert_sent_times(&self, predicate: impl Fn(&MailMessage) -> bool, times: usize) -> bool {
+        self.sent_count(predicate).await == times
+    }
+
+    /// Assert that no messages were sent
+    pub async fn assert_nothing_sent(&self) -> bool {
+        self.sent.read().await.is_empty()
+    }
+
+    async fn sent_count(&self, predicate: impl Fn(&MailMessage) -> bool) -> usize {
+        self.sent.read().await.iter().filter(|message| predicate(message)).count()
+    }
+}
+
+#[async_trait]
+impl MailDriver for FakeMailDriver {
+    async fn send(&self, message: MailMessage) -> Result<()> {
+        tracing::info!("Mail '{}' to {:?} was faked", message.subject, message.to);
+        self.sent.write().await.push(message);
+        Ok(())
+    }
+
+    fn driver_name(&self) -> &'static str {
+        "fake"
+    }
+}
+
 /// Mail manager that handles different drivers
 pub struct MailManager {
     drivers: HashMap<String, Box<dyn MailDriver>>,
     default_driver: String,
+    /// While set, every message goes here whichever driver was asked for
+    fake: Option<FakeMailDriver>,
+    /// `from` and `reply_to` for messages that set neither
+    defaults: MailDefaults,
+    /// Per-driver overrides of `defaults`
+    driver_defaults: HashMap<String, MailDefaults>,
 }
 
 impl MailManager {
@@ -123,6 +207,9 @@ impl MailManager {
         Self {
             drivers: HashMap::new(),
             default_driver,
+            fake: None,
+            defaults: MailDefaults::default(),
+            driver_defaults: HashMap::new(),
         }
     }
 
@@ -130,25 +217,124 @@ impl MailManager {
         self.drivers.insert(name, driver);
     }
 
+    /// Take the default and per-driver `from` and `reply_to` from the mail configuration
+    pub fn set_defaults(&mut self, config: &MailConfig) {
+        self.defaults = config.defaults();
+        self.driver_defaults = config.driver_defaults.clone();
+    }
+
+    /// The `from` and `reply_to` a message sent through `driver_name` falls back to
+    pub fn defaults_for(&self, driver_name: &str) -> MailDefaults {
+        match self.driver_defaults.get(driver_name) {
+            Some(overrides) => overrides.or(&self.defaults),
+            None => self.defaults.clone(),
+        }
+    }
+
     pub async fn send(&self, mailable: &dyn Mailable) -> Result<()> {
-        let message = mailable.build().await?;
-        self.send_message(message).await
+        self.send_with_driver(mailable, &self.default_driver).await
     }
 
     pub async fn send_message(&self, message: MailMessage) -> Result<()> {
-        let driver = self.drivers.get(&self.default_driver)
-            .ok_or_else(|| anyhow::anyhow!("Mail driver '{}' not found", self.default_driver))?;
-
-        driver.send(message).await
+        self.deliver(message, &self.default_driver).await
     }
 
     pub async fn send_with_driver(&self, mailable: &dyn Mailable, driver_name: &str) -> Result<()> {
-        let message = mailable.build().await?;
+        let mut message = mailable.build().await?;
+        message.from = message.from.or_else(|| mailable.from());
+        message.reply_to = message.reply_to.or_else(|| mailable.reply_to());
+        message.add_recipients(mailable.to(), mailable.cc(), mailable.bcc());
+
+        self.deliver(message, driver_name).await
+    }
+
+    /// Fill in the driver's default sender, then hand the message to the driver or the fake
+    async fn deliver(&self, mut message: MailMessage, driver_name: &str) -> Result<()> {
+        let defaults = self.defaults_for(driver_name);
+        if message.from.is_none() {
+            message.from = defaults.from.map(|from| from.mailbox());
+        }
+        if message.reply_to.is_none() {
+            message.reply_to = defaults.reply_to.map(|reply_to| reply_to.mailbox());
+        }
+
+        if let Some(fake) = &self.fake {
+            return fake.send(message).await;
+        }
+
         let driver = self.drivers.get(driver_name)
             .ok_or_else(|| anyhow::anyhow!("Mail driver '{}' not found", driver_name))?;
 
         driver.send(message).await
     }
+
+    /// Enable mail faking for testing
+    ///
+    /// Installs a fresh `FakeMailDriver` that records every message instead
+    /// of handing it to a registered driver.
+    pub fn fake(&mut self) -> FakeMailDriver {
+        let fake = FakeMailDriver::new();
+        self.fake = Some(fake.clone());
+        fake
+    }
+
+    /// Disable mail faking, sending through the registered drivers again
+    pub fn restore(&mut self) {
+        self.fake = None;
+    }
+
+    /// Check if mail is being faked
+    pub fn is_faking(&self) -> bool {
+        self.fake.is_some()
+    }
+
+    /// Get faked messages for testing assertions
+    pub async fn get_sent_messages(&self) -> Vec<MailMessage> {
+        match &self.fake {
+            Some(fake) => fake.sent().await,
+            None => Vec::new(),
+        }
+    }
+
+    /// Assert that a message matching `predicate` was sent (Laravel-style testing)
+    pub async fn assert_sent(&self, predicate: impl Fn(&MailMessage) -> bool) -> bool {
+        match &self.fake {
+            Some(fake) => fake.assert_sent(predicate).await,
+            None => false,
+        }
+    }
+
+    /// Assert that no message matching `predicate` was sent
+    pub async fn assert_not_sent(&self, predicate: impl Fn(&MailMessage) -> bool) -> bool {
+        match &self.fake {
+            Some(fake) => fake.assert_not_sent(predicate).await,
+            None => false,
+        }
+    }
+
+    /// Assert that a message was sent to `address`
+    pub async fn assert_sent_to(&self, address: &str) -> bool {
+        match &self.fake {
+            Some(fake) => fake.assert_sent_to(address).await,
+            None => false,
+        }
+    }
+
+    /// Assert that exactly `times` messages matching `predicate` were sent
+    pub async fn assert_sent_times(&self, predicate: impl Fn(&MailMessage) -> bool, times: usize) -> bool {
+        match &self.fake {
+            Some(fake) => fake.assert_sent_times(predicate, times).await,
+            None => false,
+        }
+    }
+
+    /// Assert that no messages were sent
+    pub async fn assert_nothing_sent(&self) -> bool {
+        match &self.fake {
+            Some(fake) => fake.assert_nothing_sent().await,
+            None => false,
+        }
+    }
 }
 
 impl MailMessage {
@@ -216,6 +402,25 @@ impl MailMessage {
         self.attachments.push(attachment);
         self
     }
+
+    /// Add to, cc and bcc recipients the message does not already have
+    pub fn add_recipients(&mut self, to: Vec<String>, cc: Vec<String>, bcc: Vec<String>) {
+        for (recipients, extra) in [(&mut self.to, to), (&mut self.cc, cc), (&mut self.bcc, bcc)] {
+            for address in extra {
+                if !recipients.iter().any(|recipient| recipient.eq_ignore_ascii_case(&address)) {
+                    recipients.push(address);
+                }
+            }
+        }
+    }
+
+    /// Whether `address` is a to, cc or bcc recipient, ignoring case
+    pub fn is_addressed_to(&self, address: &str) -> bool {
+        self.to.iter()
+            .chain(&self.cc)
+            .chain(&self.bcc)
+            .any(|recipient| recipient.eq_ignore_ascii_case(address))
+    }
 }
 
 impl Attachment {
@@ -279,9 +484,15 @@ impl MailContent {
                     html_escape::encode_text(text)))
             },
             MailContent::Multipart { html, .. } => Ok(html.clone()),
-            MailContent::Template { .. } => {
-                // Template rendering would be implemented here
-                Err(anyhow::anyhow!("Template rendering not implemented"))
+            MailContent::Template { name, data } => {
+                let html = template::mail_templates().await.render(name, data).await?;
+
+                // Keep the rendered mail so `to_text` has its text too
+                *self = MailContent::Multipart {
+                    text: Self::html_to_text(&html),
+                    html: html.clone(),
+                };
+                Ok(html)
             }
         }
     }
@@ -370,4 +581,64 @@ pub async fn mail(mailable: &dyn Mailable) -> Result<()> {
     let manager = mail_manager().await;
     let manager = manager.read().await;
     manager.send(mailable).await
+}
+
+/// Enable mail faking for testing
+pub async fn fake() {
+    mail_manager().await.write().await.fake();
+}
+
+/// Disable mail faking
+pub async fn restore() {
+    mail_manager().await.write().await.restore();
+}
+
+/// Check if mail is being faked
+pub async fn is_faking() -> bool {
+    mail_manager().await.read().await.is_faking()
+}
+
+/// Laravel-style Mail facade
+pub struct MailFacade;
+
+impl MailFacade {
+    /// Enable mail faking (Mail::fake())
+    pub async fn fake() {
+        fake().await;
+    }
+
+    /// Disable mail faking
+    pub async fn restore() {
+        restore().await;
+    }
+
+    /// Assert a message matching `predicate` was sent (Mail::assertSent)
+    pub async fn assert_sent(predicate: impl Fn(&MailMessage) -> bool) -> bool {
+        mail_manager().await.read().await.assert_sent(predicate).await
+    }
+
+    /// Assert no message matching `predicate` was sent (Mail::assertNotSent)
+    pub async fn assert_not_sent(predicate: impl Fn(&MailMessage) -> bool) -> bool {
+        mail_manager().await.read().await.assert_not_sent(predicate).await
+    }
+
+    /// Assert a message was sent to `address`
+    pub async fn assert_sent_to(address: &str) -> bool {
+        mail_manager().await.read().await.assert_sent_to(address).await
+    }
+
+    /// Assert messages matching `predicate` were sent a specific number of times
+    pub async fn assert_sent_times(predicate: impl Fn(&MailMessage) -> bool, times: usize) -> bool {
+        mail_manager().await.read().await.assert_sent_times(predicate, times).await
+    }
+
+    /// Assert no messages were sent (Mail::assertNothingSent)
+    pub async fn assert_nothing_sent() -> bool {
+        mail_manager().await.read().await.assert_nothing_sent().await
+    }
+
+    /// Get all sent messages
+    pub async fn get_sent_messages() -> Vec<MailMessage> {
+        mail_manager().await.read().await.get_sent_messages().await
+    }
 }
\ No newline at end of file
@@ -0,0 +1,135 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use handlebars::{Handlebars, Template};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::storage::filesystem::Filesystem;
+use crate::storage::LocalFilesystem;
+
+/// Default directory for `MailContent::Template` files
+pub const DEFAULT_TEMPLATES_PATH: &str = "resources/views/emails";
+
+/// A compiled template and the file it was compiled from
+#[derive(Debug, Clone)]
+struct CachedTemplate {
+    content_hash: u64,
+    modified: DateTime<Utc>,
+}
+
+/// Renders `MailContent::Template` mail from Handlebars files
+///
+/// `welcome` loads `welcome.hbs` from the templates directory. Compiled
+/// templates are cached by name and content hash, so a send only checks the
+/// file's modification time and recompiles when the content has changed.
+pub struct MailTemplates {
+    disk: LocalFilesystem,
+    root: PathBuf,
+    handlebars: RwLock<Handlebars<'static>>,
+    cache: RwLock<HashMap<String, CachedTemplate>>,
+}
+
+impl MailTemplates {
+    pub fn new<P: AsRef<Path>>(templates_path: P) -> Self {
+        let mut handlebars = Handlebars::new();
+        handlebars.set_strict_mode(false);
+
+        Self {
+            disk: LocalFilesystem::new(templates_path.as_ref()),
+            root: templates_path.as_ref().to_path_buf(),
+            handlebars: RwLock::new(handlebars),
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Render the template `name` with `data`
+    pub async fn render(&self, name: &str, data: &serde_json::Value) -> Result<String> {
+        self.load(name).await?;
+
+        self.handlebars.read().await
+            .render(name, data)
+            .with_context(|| format!("Failed to render mail template '{}'", name))
+    }
+
+    /// Whether `name` is compiled and cached
+    pub async fn is_cached(&self, name: &str) -> bool {
+        self.cache.read().await.contains_key(name)
+    }
+
+    /// Drop every compiled template so the next send reads the files again
+    pub async fn clear(&self) {
+        self.cache.write().await.clear();
+        self.handlebars.write().await.clear_templates();
+    }
+
+    /// Compile `name` unless the cached copy is still current
+    async fn load(&self, name: &str) -> Result<()> {
+        let path = Self::file_name(name);
+        if !self.disk.exists(&path).await.unwrap_or(false) {
+            return Err(anyhow::anyhow!(
+                "Mail template '{}' not found at {}",
+                name,
+                self.root.join(&path).display()
+            ));
+        }
+
+        let modified = self.disk.last_modified(&path).await?;
+        let cached = self.cache.read().await.get(name).cloned();
+        if cached.as_ref().is_some_and(|cached| cached.modified == modified) {
+            return Ok(());
+        }
+
+        let source = String::from_utf8(self.disk.get(&path).await?)
+            .with_context(|| format!("Mail template '{}' is not valid UTF-8", name))?;
+        let content_hash = Self::hash(&source);
+
+        // Touched but unchanged, so the compiled template still stands
+        if cached.is_none_or(|cached| cached.content_hash != content_hash) {
+            let template = Template::compile(&source)
+                .with_context(|| format!("Failed to compile mail template '{}'", name))?;
+            self.handlebars.write().await.register_template(name, template);
+            tracing::debug!("Compiled mail template '{}'", name);
+        }
+
+        self.cache.write().await.insert(name.to_string(), CachedTemplate { content_hash, modified });
+        Ok(())
+    }
+
+    fn file_name(name: &str) -> String {
+        if Path::new(name).extension().is_some() {
+            name.to_string()
+        } else {
+            format!("{}.hbs", name)
+        }
+    }
+
+    fn hash(source: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        source.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Global mail templates instance
+static MAIL_TEMPLATES: tokio::sync::OnceCell<Arc<MailTemplates>> = tokio::sync::OnceCell::const_new();
+
+/// Initialize the global mail templates from a templates directory
+pub async fn init_mail_templates(templates_path: &str) -> Arc<MailTemplates> {
+    MAIL_TEMPLATES.get_or_init(|| async {
+        Arc::new(MailTemplates::new(templates_path))
+    }).await.clone()
+}
+
+/// Get the global mail templates, reading `MAIL_TEMPLATES_PATH` if not initialized
+pub async fn mail_templates() -> Arc<MailTemplates> {
+    MAIL_TEMPLATES.get_or_init(|| async {
+        let templates_path = crate::config::mail::MailConfig::from_env()
+            .map(|config| config.templates_path)
+            .unwrap_or_else(|_| DEFAULT_TEMPLATES_PATH.to_string());
+        Arc::new(MailTemplates::new(templates_path))
+    }).await.clone()
+}
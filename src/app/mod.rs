@@ -14,4 +14,9 @@ pub mod jobs;
 pub mod validation;
 pub mod activity_log;
 pub mod traits;
-pub mod helpers;
\ No newline at end of file
+pub mod helpers;
+pub mod cache_warmers;
+pub mod features;
+pub mod providers;
+pub mod gate;
+pub mod policies;
\ No newline at end of file
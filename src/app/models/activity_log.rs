@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use utoipa::ToSchema;
 
-use crate::app::models::{DieselUlid, HasModelType};
+use crate::app::models::{DieselUlid, HasModelType, HasIdGenerator};
 
 #[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone, ToSchema)]
 #[diesel(table_name = crate::schema::activity_log)]
@@ -21,7 +21,9 @@ pub struct ActivityLog {
     pub correlation_id: Option<DieselUlid>,
     pub batch_uuid: Option<String>,
     pub event: Option<String>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 impl HasModelType for ActivityLog {
@@ -85,14 +87,73 @@ impl crate::app::query_builder::Queryable for ActivityLog {
         ]
     }
 
+    fn allowed_includes() -> Vec<&'static str> {
+        vec!["causer", "subject"]
+    }
+
+    fn morph_to_relations() -> Vec<crate::app::query_builder::MorphTo> {
+        vec![
+            crate::app::query_builder::MorphTo {
+                relation: "causer",
+                type_column: "causer_type",
+                id_column: "causer_id",
+            },
+            crate::app::query_builder::MorphTo {
+                relation: "subject",
+                type_column: "subject_type",
+                id_column: "subject_id",
+            },
+        ]
+    }
+
     fn default_sort() -> Option<(&'static str, crate::app::query_builder::SortDirection)> {
         Some(("created_at", crate::app::query_builder::SortDirection::Desc))
     }
 }
 
+impl crate::app::query_builder::Includable for ActivityLog {
+    fn load_relationships(ids: &[String], includes: &[String], conn: &mut diesel::pg::PgConnection) -> anyhow::Result<()> {
+        for include in includes {
+            Self::load_relationship(ids, include, conn)?;
+        }
+        Ok(())
+    }
+
+    /// The causer or subject of each activity, keyed by activity id
+    fn load_relationship(ids: &[String], relationship: &str, conn: &mut diesel::pg::PgConnection) -> anyhow::Result<Value> {
+        use crate::app::query_builder::{Include, Queryable, QueryExecutor};
+        use crate::schema::activity_log;
+
+        if !Self::is_include_allowed(relationship) {
+            return Ok(serde_json::json!({}));
+        }
+
+        let ids: Vec<DieselUlid> = ids.iter().filter_map(|id| DieselUlid::from_string(id).ok()).collect();
+        let mut rows = activity_log::table
+            .filter(activity_log::id.eq_any(ids))
+            .select(ActivityLog::as_select())
+            .load::<ActivityLog>(conn)?
+            .into_iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        QueryExecutor::load_morph_to::<Self>(&[Include::new(relationship.to_string())], &mut rows, conn)?;
+
+        let related = rows.into_iter()
+            .filter_map(|row| {
+                let id = row.get("id")?.as_str()?.to_string();
+                Some((id, row.get(relationship).cloned().unwrap_or(Value::Null)))
+            })
+            .collect::<serde_json::Map<_, _>>();
+        Ok(Value::Object(related))
+    }
+}
+
 // Implement the query builder service for ActivityLog
 crate::impl_query_builder_service!(ActivityLog);
 
+impl HasIdGenerator for ActivityLog {}
+
 impl ActivityLog {
     pub fn builder() -> ActivityLogBuilder {
         ActivityLogBuilder::new()
@@ -239,7 +300,7 @@ impl ActivityLogBuilder {
         let now = chrono::Utc::now();
 
         Ok(ActivityLog {
-            id: DieselUlid::new(),
+            id: ActivityLog::new_id(),
             log_name: self.log_name,
             description,
             subject_type: self.subject_type,
@@ -3,10 +3,10 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = crate::schema::algorithm_compatibility_matrix)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct AlgorithmCompatibilityMatrix {
@@ -20,12 +20,15 @@ pub struct AlgorithmCompatibilityMatrix {
     pub compatibility_level: String,
     pub negotiation_overhead_ms: Option<i32>,
     pub interop_test_passed: Option<bool>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub tested_at: Option<DateTime<Utc>>,
     pub test_version: String,
     pub notes: Option<String>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -71,6 +74,8 @@ pub struct CreateAlgorithmCompatibilityMatrix {
     pub negotiation_overhead_ms: Option<i32>,
     pub notes: Option<String>,
 }
+impl HasIdGenerator for AlgorithmCompatibilityMatrix {}
+
 impl AlgorithmCompatibilityMatrix {
     pub fn new(
         encryption_algorithm_a: String,
@@ -84,7 +89,7 @@ impl AlgorithmCompatibilityMatrix {
     ) -> Self {
         let now = Utc::now();
         AlgorithmCompatibilityMatrix {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             encryption_algorithm_a,
             encryption_algorithm_b,
             key_exchange_algorithm_a,
@@ -1,4 +1,4 @@
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
@@ -19,8 +19,11 @@ pub struct City {
     pub latitude: Option<Decimal>,
     #[schema(value_type = Option<f64>)]
     pub longitude: Option<Decimal>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
     pub created_by_id: DieselUlid,
     pub updated_by_id: DieselUlid,
@@ -54,6 +57,7 @@ pub struct UpdateCity {
 /// City response payload for API endpoints
 #[derive(Debug, Serialize, ToSchema)]
 pub struct CityResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub province_id: String,
     pub name: String,
@@ -62,10 +66,14 @@ pub struct CityResponse {
     pub latitude: Option<Decimal>,
     #[schema(value_type = Option<f64>)]
     pub longitude: Option<Decimal>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for City {}
+
 impl City {
     pub fn new(
         province_id: String,
@@ -78,7 +86,7 @@ impl City {
         let now = Utc::now();
         let creator_id = DieselUlid::from_string(created_by.trim()).expect("Invalid created_by ULID provided to City::new()");
         City {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             province_id,
             name,
             code,
@@ -189,109 +197,7 @@ impl crate::app::query_builder::Queryable for City {
 }
 
 // Implement enhanced query builder traits for City
-impl crate::app::query_builder::Filterable for City {
-    fn apply_basic_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match (column, operator) {
-            ("id", op) | ("created_by_id", op) | ("updated_by_id", op) | ("deleted_by_id", op) if op != "is_null" && op != "is_not_null" => {
-                format!("{} {} '{}'", column, op, value.as_str().unwrap_or(""))
-            }
-            ("province_id", op) if op != "is_null" && op != "is_not_null" => {
-                format!("{} {} '{}'", column, op, value.as_str().unwrap_or(""))
-            }
-            ("latitude", op) | ("longitude", op) => {
-                if let Some(num_val) = value.as_f64() {
-                    format!("{} {} {}", column, op, num_val)
-                } else {
-                    format!("{} = 0.0", column)
-                }
-            }
-            ("name", "contains") | ("code", "contains") => {
-                format!("LOWER({}) LIKE LOWER('%{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "starts_with") | ("code", "starts_with") => {
-                format!("LOWER({}) LIKE LOWER('{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "ends_with") | ("code", "ends_with") => {
-                format!("LOWER({}) LIKE LOWER('%{}')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "like") | ("code", "like") => {
-                format!("{} LIKE '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "ilike") | ("code", "ilike") => {
-                format!("{} ILIKE '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "eq") | ("code", "eq") => {
-                format!("{} = '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "ne") | ("code", "ne") => {
-                format!("{} != '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "in") | ("code", "in") | ("province_id", "in") => {
-                let values = value.as_array()
-                    .map(|arr| arr.iter()
-                        .filter_map(|v| v.as_str())
-                        .map(|s| format!("'{}'", s.replace('\'', "''")))
-                        .collect::<Vec<_>>()
-                        .join(","))
-                    .unwrap_or_default();
-                format!("{} IN ({})", column, values)
-            }
-            ("name", "not_in") | ("code", "not_in") | ("province_id", "not_in") => {
-                let values = value.as_array()
-                    .map(|arr| arr.iter()
-                        .filter_map(|v| v.as_str())
-                        .map(|s| format!("'{}'", s.replace('\'', "''")))
-                        .collect::<Vec<_>>()
-                        .join(","))
-                    .unwrap_or_default();
-                format!("{} NOT IN ({})", column, values)
-            }
-            ("latitude", "between") | ("longitude", "between") => {
-                if let Some(range) = value.as_array() {
-                    if range.len() >= 2 {
-                        format!("{} BETWEEN {} AND {}",
-                               column,
-                               range[0].as_f64().unwrap_or(0.0),
-                               range[1].as_f64().unwrap_or(0.0))
-                    } else {
-                        format!("{} IS NOT NULL", column)
-                    }
-                } else {
-                    format!("{} IS NOT NULL", column)
-                }
-            }
-            ("created_at", "gt") | ("updated_at", "gt") | ("deleted_at", "gt") => {
-                format!("{} > '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "gte") | ("updated_at", "gte") | ("deleted_at", "gte") => {
-                format!("{} >= '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "lt") | ("updated_at", "lt") | ("deleted_at", "lt") => {
-                format!("{} < '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "lte") | ("updated_at", "lte") | ("deleted_at", "lte") => {
-                format!("{} <= '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "between") | ("updated_at", "between") | ("deleted_at", "between") => {
-                if let Some(range) = value.as_array() {
-                    if range.len() >= 2 {
-                        format!("{} BETWEEN '{}' AND '{}'",
-                               column,
-                               range[0].as_str().unwrap_or(""),
-                               range[1].as_str().unwrap_or(""))
-                    } else {
-                        format!("{} IS NOT NULL", column)
-                    }
-                } else {
-                    format!("{} IS NOT NULL", column)
-                }
-            }
-            (_, "is_null") => format!("{} IS NULL", column),
-            (_, "is_not_null") => format!("{} IS NOT NULL", column),
-            _ => format!("{} = '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-        }
-    }
-}
+impl crate::app::query_builder::Filterable for City {}
 
 impl crate::app::query_builder::Sortable for City {
     fn apply_basic_sort(column: &str, direction: &str) -> String {
@@ -1,11 +1,12 @@
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use crate::schema::conversations;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use chrono::{DateTime, Utc};
 use crate::app::models::{HasModelType, activity_log::HasId};
+use crate::app::query_builder::{HasMany, SortDirection};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = conversations)]
 #[diesel(primary_key(id))]
 pub struct Conversation {
@@ -23,8 +24,11 @@ pub struct Conversation {
     pub max_participants: Option<i32>,
     pub is_public: bool,
     pub disappearing_messages_timer: Option<i32>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,6 +59,8 @@ impl From<ConversationType> for String {
     }
 }
 
+impl HasIdGenerator for Conversation {}
+
 impl Conversation {
     pub fn conversation_type_enum(&self) -> ConversationType {
         self.conversation_type.clone().into()
@@ -65,7 +71,7 @@ impl Conversation {
     pub fn new(conversation_type: ConversationType, creator_id: Option<DieselUlid>) -> Self {
         let now = chrono::Utc::now();
         Conversation {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             conversation_type: conversation_type.into(),
             is_encrypted: false,
             encryption_immutable: false,
@@ -121,4 +127,76 @@ impl HasId for Conversation {
     fn id(&self) -> String {
         self.id.to_string()
     }
-}
\ No newline at end of file
+}
+
+impl crate::app::query_builder::Queryable for Conversation {
+    fn table_name() -> &'static str {
+        "conversations"
+    }
+
+    fn allowed_filters() -> Vec<&'static str> {
+        vec![
+            "id",
+            "conversation_type",
+            "is_encrypted",
+            "creator_id",
+            "is_public",
+            "created_at",
+            "updated_at",
+            "deleted_at",
+        ]
+    }
+
+    fn allowed_sorts() -> Vec<&'static str> {
+        vec![
+            "id",
+            "conversation_type",
+            "is_public",
+            "created_at",
+            "updated_at",
+        ]
+    }
+
+    fn allowed_fields() -> Vec<&'static str> {
+        vec![
+            "id",
+            "conversation_type",
+            "is_encrypted",
+            "encryption_immutable",
+            "encrypted_name",
+            "encrypted_description",
+            "encrypted_avatar_url",
+            "preferred_algorithm",
+            "preferred_key_exchange",
+            "preferred_mac",
+            "creator_id",
+            "max_participants",
+            "is_public",
+            "disappearing_messages_timer",
+            "created_at",
+            "updated_at",
+            "deleted_at",
+        ]
+    }
+
+    fn allowed_includes() -> Vec<&'static str> {
+        vec!["messages"]
+    }
+
+    fn has_many_relations() -> Vec<HasMany> {
+        vec![HasMany {
+            relation: "messages",
+            table: "messages",
+            foreign_key: "conversation_id",
+            order_by: ("sent_at", SortDirection::Desc),
+            allowed_fields: <crate::app::models::message::Message as crate::app::query_builder::Queryable>::allowed_fields,
+        }]
+    }
+
+    fn default_sort() -> Option<(&'static str, SortDirection)> {
+        Some(("updated_at", SortDirection::Desc))
+    }
+}
+
+// Implement the query builder service for Conversation
+crate::impl_query_builder_service!(Conversation);
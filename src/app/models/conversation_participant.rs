@@ -1,11 +1,11 @@
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use crate::schema::conversation_participants;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use chrono::{DateTime, Utc};
 use crate::app::models::{HasModelType, activity_log::HasId};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = conversation_participants)]
 #[diesel(primary_key(id))]
 pub struct ConversationParticipant {
@@ -14,11 +14,16 @@ pub struct ConversationParticipant {
     pub user_id: DieselUlid,
     pub role: String,
     pub is_active: bool,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub joined_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub left_at: Option<DateTime<Utc>>,
     pub last_read_message_id: Option<DieselUlid>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub last_read_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,6 +57,8 @@ impl From<ParticipantRole> for String {
     }
 }
 
+impl HasIdGenerator for ConversationParticipant {}
+
 impl ConversationParticipant {
     pub fn role_enum(&self) -> ParticipantRole {
         self.role.clone().into()
@@ -75,7 +82,7 @@ impl ConversationParticipant {
     pub fn new(conversation_id: DieselUlid, user_id: DieselUlid, role: ParticipantRole) -> Self {
         let now = Utc::now();
         ConversationParticipant {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             conversation_id,
             user_id,
             role: role.into(),
@@ -1,4 +1,4 @@
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
@@ -24,11 +24,14 @@ pub struct Country {
     pub phone_code: Option<String>,
     /// Creation timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     /// Soft delete timestamp
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
     /// User who created this record
     pub created_by_id: DieselUlid,
@@ -57,21 +60,26 @@ pub struct UpdateCountry {
 /// Country response payload for API endpoints
 #[derive(Debug, Serialize, ToSchema)]
 pub struct CountryResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub name: String,
     pub iso_code: String,
     pub phone_code: Option<String>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for Country {}
+
 impl Country {
     pub fn new(name: String, iso_code: String, phone_code: Option<String>, created_by: &str) -> Self {
         let now = Utc::now();
         let creator_id = DieselUlid::from_string(created_by.trim())
             .expect("Invalid created_by ULID provided to Country::new()");
         Country {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             name,
             iso_code,
             phone_code,
@@ -168,85 +176,7 @@ impl crate::app::query_builder::Queryable for Country {
 }
 
 // Implement the enhanced filtering trait
-impl crate::app::query_builder::Filterable for Country {
-    fn apply_basic_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match (column, operator) {
-            ("id", op) | ("created_by_id", op) | ("updated_by_id", op) | ("deleted_by_id", op) if op != "is_null" && op != "is_not_null" => {
-                format!("{} {} '{}'", column, op, value.as_str().unwrap_or(""))
-            }
-            ("name", "contains") | ("iso_code", "contains") | ("phone_code", "contains") => {
-                format!("LOWER({}) LIKE LOWER('%{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "starts_with") | ("iso_code", "starts_with") | ("phone_code", "starts_with") => {
-                format!("LOWER({}) LIKE LOWER('{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "ends_with") | ("iso_code", "ends_with") | ("phone_code", "ends_with") => {
-                format!("LOWER({}) LIKE LOWER('%{}')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "like") | ("iso_code", "like") | ("phone_code", "like") => {
-                format!("{} LIKE '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "ilike") | ("iso_code", "ilike") | ("phone_code", "ilike") => {
-                format!("{} ILIKE '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "eq") | ("iso_code", "eq") | ("phone_code", "eq") => {
-                format!("{} = '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "ne") | ("iso_code", "ne") | ("phone_code", "ne") => {
-                format!("{} != '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "in") | ("iso_code", "in") | ("phone_code", "in") => {
-                let values = value.as_array()
-                    .map(|arr| arr.iter()
-                        .filter_map(|v| v.as_str())
-                        .map(|s| format!("'{}'", s.replace('\'', "''")))
-                        .collect::<Vec<_>>()
-                        .join(","))
-                    .unwrap_or_default();
-                format!("{} IN ({})", column, values)
-            }
-            ("name", "not_in") | ("iso_code", "not_in") | ("phone_code", "not_in") => {
-                let values = value.as_array()
-                    .map(|arr| arr.iter()
-                        .filter_map(|v| v.as_str())
-                        .map(|s| format!("'{}'", s.replace('\'', "''")))
-                        .collect::<Vec<_>>()
-                        .join(","))
-                    .unwrap_or_default();
-                format!("{} NOT IN ({})", column, values)
-            }
-            ("created_at", "gt") | ("updated_at", "gt") | ("deleted_at", "gt") => {
-                format!("{} > '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "gte") | ("updated_at", "gte") | ("deleted_at", "gte") => {
-                format!("{} >= '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "lt") | ("updated_at", "lt") | ("deleted_at", "lt") => {
-                format!("{} < '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "lte") | ("updated_at", "lte") | ("deleted_at", "lte") => {
-                format!("{} <= '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "between") | ("updated_at", "between") | ("deleted_at", "between") => {
-                if let Some(range) = value.as_array() {
-                    if range.len() >= 2 {
-                        format!("{} BETWEEN '{}' AND '{}'",
-                               column,
-                               range[0].as_str().unwrap_or(""),
-                               range[1].as_str().unwrap_or(""))
-                    } else {
-                        format!("{} IS NOT NULL", column)
-                    }
-                } else {
-                    format!("{} IS NOT NULL", column)
-                }
-            }
-            (_, "is_null") => format!("{} IS NULL", column),
-            (_, "is_not_null") => format!("{} IS NOT NULL", column),
-            _ => format!("{} = '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-        }
-    }
-}
+impl crate::app::query_builder::Filterable for Country {}
 
 // Implement the enhanced sorting trait
 impl crate::app::query_builder::Sortable for Country {
@@ -379,4 +309,15 @@ impl crate::app::models::activity_log::HasId for Country {
     fn id(&self) -> String {
         self.id.to_string()
     }
-}
\ No newline at end of file
+}
+impl crate::app::services::csv_import_service::CsvImportable for Country {
+    type Request = crate::app::http::requests::country_requests::CreateCountryRequest;
+
+    fn insert_row(conn: &mut PgConnection, row: Self::Request, created_by: &str) -> QueryResult<()> {
+        let country = Country::new(row.name, row.iso_code, row.phone_code, created_by);
+        diesel::insert_into(crate::schema::ref_geo_countries::table)
+            .values(&country)
+            .execute(conn)
+            .map(|_| ())
+    }
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use crate::schema::devices;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use chrono::{DateTime, Utc};
 use crate::app::models::{HasModelType, activity_log::HasId};
 
@@ -37,7 +37,7 @@ impl From<TrustLevel> for String {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = devices)]
 #[diesel(primary_key(id))]
 pub struct Device {
@@ -51,11 +51,15 @@ pub struct Device {
     pub signed_prekey_id: i32,
     pub supported_algorithms: Vec<Option<String>>,
     pub is_active: bool,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub last_seen_at: DateTime<Utc>,
     pub registration_id: i32,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     pub signed_prekey_rotation_needed: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub last_key_rotation_at: Option<DateTime<Utc>>,
     #[serde(skip, default = "default_interval")]
     pub prekey_rotation_interval: diesel::pg::data_types::PgInterval,
@@ -94,6 +98,8 @@ impl From<DeviceType> for String {
     }
 }
 
+impl HasIdGenerator for Device {}
+
 impl Device {
     pub fn device_type_enum(&self) -> DeviceType {
         self.device_type.clone().into()
@@ -130,7 +136,7 @@ impl Device {
 
         let now = Utc::now();
         Device {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             device_name,
             device_type: device_type.into(),
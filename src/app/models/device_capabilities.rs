@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
@@ -31,8 +31,10 @@ pub struct DeviceCapabilities {
     pub supports_disappearing_messages: bool,
     pub supports_file_encryption: bool,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     // Post-quantum cryptography support
     pub supports_ed25519_signature: bool,
@@ -61,11 +63,13 @@ pub struct CreateDeviceCapabilities {
     pub supports_disappearing_messages: bool,
     pub supports_file_encryption: bool,
 }
+impl HasIdGenerator for DeviceCapabilities {}
+
 impl DeviceCapabilities {
     pub fn new(device_id: DieselUlid) -> Self {
         let now = Utc::now();
         Self {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             device_id,
             supports_aes_256_gcm: true,
             supports_chacha20_poly1305: true,
@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
@@ -17,13 +17,17 @@ pub struct DeviceFingerprint {
     pub fingerprint_algorithm: String,
     pub is_verified: bool,
     pub verified_by_user_id: Option<DieselUlid>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub verified_at: Option<DateTime<Utc>>,
     pub verification_method: Option<String>,
     pub trust_score: i32,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub trust_last_updated: Option<DateTime<Utc>>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -114,20 +118,29 @@ pub struct VerifyDeviceFingerprint {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct DeviceFingerprintResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub device_id: DieselUlid,
     pub identity_key_fingerprint: String,
     pub fingerprint_algorithm: String,
     pub is_verified: bool,
+    #[serde(with = "crate::app::models::diesel_ulid::response::option")]
     pub verified_by_user_id: Option<DieselUlid>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub verified_at: Option<DateTime<Utc>>,
     pub verification_method: Option<String>,
     pub trust_score: i32,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub trust_last_updated: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for DeviceFingerprint {}
+
 impl DeviceFingerprint {
     pub fn new(
         device_id: DieselUlid,
@@ -136,7 +149,7 @@ impl DeviceFingerprint {
     ) -> Self {
         let now = Utc::now();
         DeviceFingerprint {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             device_id,
             identity_key_fingerprint,
             fingerprint_algorithm: fingerprint_algorithm.into(),
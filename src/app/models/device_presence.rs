@@ -3,10 +3,10 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = crate::schema::device_presence)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct DevicePresence {
@@ -14,15 +14,20 @@ pub struct DevicePresence {
     pub id: DieselUlid,
     pub device_id: DieselUlid,
     pub status: String,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub last_seen_at: DateTime<Utc>,
     pub encrypted_status_message: Option<String>,
     pub status_message_algorithm: Option<String>,
     pub auto_away_after_minutes: Option<i32>,
     pub auto_offline_after_minutes: Option<i32>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    /// Busy, do not disturb or invisible as picked by the user, shown whenever the device is connected
+    pub chosen_status: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +40,21 @@ pub enum PresenceStatus {
     Offline,
 }
 
+impl PresenceStatus {
+    /// Invisible devices appear offline to other users
+    pub fn visible(self) -> Self {
+        match self {
+            PresenceStatus::Invisible => PresenceStatus::Offline,
+            status => status,
+        }
+    }
+
+    /// Statuses the user picks, as opposed to ones derived from the connection
+    pub fn is_chosen(&self) -> bool {
+        matches!(self, PresenceStatus::Busy | PresenceStatus::DoNotDisturb | PresenceStatus::Invisible)
+    }
+}
+
 impl From<String> for PresenceStatus {
     fn from(s: String) -> Self {
         match s.as_str() {
@@ -82,18 +102,25 @@ pub struct UpdateDevicePresence {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct DevicePresenceResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub device_id: DieselUlid,
     pub status: String,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub last_seen_at: DateTime<Utc>,
     pub encrypted_status_message: Option<String>,
     pub status_message_algorithm: Option<String>,
     pub auto_away_after_minutes: Option<i32>,
     pub auto_offline_after_minutes: Option<i32>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for DevicePresence {}
+
 impl DevicePresence {
     pub fn new(
         device_id: DieselUlid,
@@ -105,7 +132,7 @@ impl DevicePresence {
     ) -> Self {
         let now = Utc::now();
         DevicePresence {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             device_id,
             status: status.into(),
             last_seen_at: now,
@@ -115,6 +142,7 @@ impl DevicePresence {
             auto_offline_after_minutes,
             created_at: now,
             updated_at: now,
+            chosen_status: None,
         }
     }
 
@@ -137,6 +165,10 @@ impl DevicePresence {
         self.status.clone().into()
     }
 
+    pub fn chosen_status_enum(&self) -> Option<PresenceStatus> {
+        self.chosen_status.clone().map(PresenceStatus::from)
+    }
+
     pub fn is_online(&self) -> bool {
         matches!(self.status_enum(), PresenceStatus::Online)
     }
@@ -157,6 +189,11 @@ impl DevicePresence {
         matches!(self.status_enum(), PresenceStatus::DoNotDisturb)
     }
 
+    /// Status as shown to other users
+    pub fn visible_status(&self) -> PresenceStatus {
+        self.status_enum().visible()
+    }
+
     pub fn should_auto_away(&self) -> bool {
         if let Some(minutes) = self.auto_away_after_minutes {
             let threshold = Utc::now() - chrono::Duration::minutes(minutes as i64);
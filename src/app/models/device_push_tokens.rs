@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
@@ -19,11 +19,15 @@ pub struct DevicePushToken {
     pub encrypted_notification_settings: Option<String>,
     pub settings_algorithm: Option<String>,
     pub is_active: bool,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub last_used_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -86,17 +90,25 @@ pub struct UpdateDevicePushToken {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct DevicePushTokenResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub device_id: DieselUlid,
     pub platform: String,
     pub endpoint: Option<String>,
     pub is_active: bool,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub last_used_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub expires_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for DevicePushToken {}
+
 impl DevicePushToken {
     pub fn new(
         device_id: DieselUlid,
@@ -111,7 +123,7 @@ impl DevicePushToken {
         let expires_at = expires_in_days.map(|days| now + chrono::Duration::days(days as i64));
 
         DevicePushToken {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             device_id,
             platform: platform.into(),
             token,
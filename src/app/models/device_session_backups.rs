@@ -3,10 +3,10 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = crate::schema::device_session_backups)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct DeviceSessionBackup {
@@ -23,14 +23,20 @@ pub struct DeviceSessionBackup {
     pub sessions_count: i32,
     pub conversations_count: i32,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub expires_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub last_accessed_at: Option<DateTime<Utc>>,
     pub backup_checksum: String,
     pub is_verified: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub verification_failed_at: Option<DateTime<Utc>>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    pub backup_key_id: Option<DieselUlid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,8 +84,11 @@ pub struct CreateDeviceSessionBackup {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct DeviceSessionBackupResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub device_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub user_id: DieselUlid,
     pub backup_name: String,
     pub backup_type: String,
@@ -87,14 +96,23 @@ pub struct DeviceSessionBackupResponse {
     pub backup_algorithm: String,
     pub sessions_count: i32,
     pub conversations_count: i32,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub expires_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub last_accessed_at: Option<DateTime<Utc>>,
     pub is_verified: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub verification_failed_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
+    #[serde(with = "crate::app::models::diesel_ulid::response::option")]
+    pub backup_key_id: Option<DieselUlid>,
 }
 
+impl HasIdGenerator for DeviceSessionBackup {}
+
 impl DeviceSessionBackup {
     pub fn new(
         device_id: DieselUlid,
@@ -114,7 +132,7 @@ impl DeviceSessionBackup {
         let backup_checksum = Self::calculate_checksum(&encrypted_sessions_data);
 
         DeviceSessionBackup {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             device_id,
             user_id,
             backup_name,
@@ -132,9 +150,16 @@ impl DeviceSessionBackup {
             is_verified: false,
             verification_failed_at: None,
             updated_at: now,
+            backup_key_id: None,
         }
     }
 
+    /// Link the backup to the encrypted backup key its sessions were sealed with
+    pub fn with_backup_key(mut self, backup_key_id: DieselUlid) -> Self {
+        self.backup_key_id = Some(backup_key_id);
+        self
+    }
+
     pub fn to_response(&self) -> DeviceSessionBackupResponse {
         DeviceSessionBackupResponse {
             id: self.id,
@@ -152,6 +177,7 @@ impl DeviceSessionBackup {
             is_verified: self.is_verified,
             verification_failed_at: self.verification_failed_at,
             updated_at: self.updated_at,
+            backup_key_id: self.backup_key_id,
         }
     }
 
@@ -279,6 +305,7 @@ impl crate::app::query_builder::Queryable for DeviceSessionBackup {
             "last_accessed_at",
             "created_at",
             "updated_at",
+            "backup_key_id",
         ]
     }
 
@@ -290,6 +317,7 @@ impl crate::app::query_builder::Queryable for DeviceSessionBackup {
         vec![
             "device",
             "user",
+            "backupKey",
         ]
     }
 }
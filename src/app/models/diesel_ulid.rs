@@ -6,12 +6,29 @@ use diesel::AsExpression;
 use diesel::FromSqlRow;
 use diesel::pg::PgValue;
 use ulid::Ulid;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::io::Write;
+use std::sync::OnceLock;
 use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, AsExpression, FromSqlRow, Serialize, Deserialize, ToSchema)]
+use crate::config::response::{IdFormat, ResponseConfig};
+
+static DEFAULT_ID_FORMAT: OnceLock<IdFormat> = OnceLock::new();
+
+tokio::task_local! {
+    /// Id format asked for by the request the current task is serving
+    static REQUESTED_ID_FORMAT: IdFormat;
+}
+
+/// ULID identifier
+///
+/// Always serialized as a canonical ULID string, so cached values, job
+/// payloads and stored JSON never change shape. Response types opt into the
+/// requested format per field with the `response` serde helpers. Any of the
+/// three forms is accepted when deserializing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, AsExpression, FromSqlRow, ToSchema)]
 #[diesel(sql_type = Text)]
+#[schema(value_type = String, example = "01ARZ3NDEKTSV4RRFFQ69G5FAV")]
 pub struct DieselUlid(pub Ulid);
 
 impl DieselUlid {
@@ -30,6 +47,110 @@ impl DieselUlid {
     pub fn inner(&self) -> Ulid {
         self.0
     }
+
+    /// Parse a canonical ULID in either case, a prefixed ULID such as
+    /// `org_01ARZ3NDEKTSV4RRFFQ69G5FAV`, or a UUID
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        if let Ok(ulid) = Ulid::from_string(&Self::strip_prefix(s).to_uppercase()) {
+            return Ok(DieselUlid(ulid));
+        }
+        uuid::Uuid::parse_str(s)
+            .map(|uuid| DieselUlid(Ulid(uuid.as_u128())))
+            .map_err(|_| format!("invalid ULID or UUID: {}", s))
+    }
+
+    /// Id with a type prefix, e.g. `org_01ARZ3NDEKTSV4RRFFQ69G5FAV`
+    pub fn prefixed(&self, prefix: &str) -> String {
+        format!("{}_{}", prefix, self.0)
+    }
+
+    /// ULID part of a prefixed id; other input is returned unchanged
+    pub fn strip_prefix(s: &str) -> &str {
+        match s.rsplit_once('_') {
+            Some((prefix, ulid))
+                if ulid.len() == 26
+                    && !prefix.is_empty()
+                    && prefix.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') => ulid,
+            _ => s,
+        }
+    }
+
+    /// Canonical form of a ULID given in any case or with a prefix; `None`
+    /// for anything else, including UUIDs
+    pub fn normalize(s: &str) -> Option<String> {
+        Ulid::from_string(&Self::strip_prefix(s.trim()).to_uppercase())
+            .ok()
+            .map(|ulid| ulid.to_string())
+    }
+
+    /// Write the id in the given representation
+    pub fn format(&self, format: IdFormat) -> String {
+        match format {
+            IdFormat::Ulid => self.0.to_string(),
+            IdFormat::Uuid => uuid::Uuid::from_u128(self.0.0).to_string(),
+            IdFormat::Lowercase => self.0.to_string().to_lowercase(),
+        }
+    }
+
+    /// Format for ids in responses: the request's, else `RESPONSE_ID_FORMAT`
+    pub fn response_format() -> IdFormat {
+        REQUESTED_ID_FORMAT.try_with(|format| *format).unwrap_or_else(|_| {
+            *DEFAULT_ID_FORMAT.get_or_init(|| {
+                ResponseConfig::from_env().map(|config| config.id_format).unwrap_or_default()
+            })
+        })
+    }
+}
+
+/// Run a future with response ids written in `format`
+pub async fn with_id_format<F: std::future::Future>(format: IdFormat, future: F) -> F::Output {
+    REQUESTED_ID_FORMAT.scope(format, future).await
+}
+
+/// Serde helpers for `DieselUlid` fields of API response types
+///
+/// Use with `#[serde(with = "crate::app::models::diesel_ulid::response")]`, or
+/// the `option` module for `Option<DieselUlid>`. Ids are written in
+/// `DieselUlid::response_format`; reading accepts every form.
+pub mod response {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(id: &DieselUlid, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&id.format(DieselUlid::response_format()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DieselUlid, D::Error> {
+        DieselUlid::deserialize(deserializer)
+    }
+
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(id: &Option<DieselUlid>, serializer: S) -> Result<S::Ok, S::Error> {
+            match id {
+                Some(id) => super::serialize(id, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<DieselUlid>, D::Error> {
+            Option::<DieselUlid>::deserialize(deserializer)
+        }
+    }
+}
+
+impl Serialize for DieselUlid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for DieselUlid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        DieselUlid::parse(&s).map_err(serde::de::Error::custom)
+    }
 }
 
 impl Default for DieselUlid {
@@ -1,4 +1,4 @@
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
@@ -24,11 +24,14 @@ pub struct District {
     pub code: Option<String>,
     /// Creation timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     /// Soft delete timestamp
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
     /// User who created this record
     pub created_by_id: DieselUlid,
@@ -57,20 +60,25 @@ pub struct UpdateDistrict {
 /// District response payload for API endpoints
 #[derive(Debug, Serialize, ToSchema)]
 pub struct DistrictResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub city_id: String,
     pub name: String,
     pub code: Option<String>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for District {}
+
 impl District {
     pub fn new(city_id: String, name: String, code: Option<String>, created_by: &str) -> Self {
         let now = Utc::now();
         let creator_id = DieselUlid::from_string(created_by.trim()).expect("Invalid created_by ULID provided to District::new()");
         District {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             city_id,
             name,
             code,
@@ -169,88 +177,7 @@ impl crate::app::query_builder::Queryable for District {
 }
 
 // Implement enhanced query builder traits for District
-impl crate::app::query_builder::Filterable for District {
-    fn apply_basic_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match (column, operator) {
-            ("id", op) | ("created_by_id", op) | ("updated_by_id", op) | ("deleted_by_id", op) if op != "is_null" && op != "is_not_null" => {
-                format!("{} {} '{}'", column, op, value.as_str().unwrap_or(""))
-            }
-            ("city_id", op) if op != "is_null" && op != "is_not_null" => {
-                format!("{} {} '{}'", column, op, value.as_str().unwrap_or(""))
-            }
-            ("name", "contains") | ("code", "contains") => {
-                format!("LOWER({}) LIKE LOWER('%{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "starts_with") | ("code", "starts_with") => {
-                format!("LOWER({}) LIKE LOWER('{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "ends_with") | ("code", "ends_with") => {
-                format!("LOWER({}) LIKE LOWER('%{}')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "like") | ("code", "like") => {
-                format!("{} LIKE '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "ilike") | ("code", "ilike") => {
-                format!("{} ILIKE '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "eq") | ("code", "eq") => {
-                format!("{} = '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "ne") | ("code", "ne") => {
-                format!("{} != '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "in") | ("code", "in") | ("city_id", "in") => {
-                let values = value.as_array()
-                    .map(|arr| arr.iter()
-                        .filter_map(|v| v.as_str())
-                        .map(|s| format!("'{}'", s.replace('\'', "''")))
-                        .collect::<Vec<_>>()
-                        .join(","))
-                    .unwrap_or_default();
-                format!("{} IN ({})", column, values)
-            }
-            ("name", "not_in") | ("code", "not_in") | ("city_id", "not_in") => {
-                let values = value.as_array()
-                    .map(|arr| arr.iter()
-                        .filter_map(|v| v.as_str())
-                        .map(|s| format!("'{}'", s.replace('\'', "''")))
-                        .collect::<Vec<_>>()
-                        .join(","))
-                    .unwrap_or_default();
-                format!("{} NOT IN ({})", column, values)
-            }
-            ("created_at", "gt") | ("updated_at", "gt") | ("deleted_at", "gt") => {
-                format!("{} > '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "gte") | ("updated_at", "gte") | ("deleted_at", "gte") => {
-                format!("{} >= '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "lt") | ("updated_at", "lt") | ("deleted_at", "lt") => {
-                format!("{} < '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "lte") | ("updated_at", "lte") | ("deleted_at", "lte") => {
-                format!("{} <= '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "between") | ("updated_at", "between") | ("deleted_at", "between") => {
-                if let Some(range) = value.as_array() {
-                    if range.len() >= 2 {
-                        format!("{} BETWEEN '{}' AND '{}'",
-                               column,
-                               range[0].as_str().unwrap_or(""),
-                               range[1].as_str().unwrap_or(""))
-                    } else {
-                        format!("{} IS NOT NULL", column)
-                    }
-                } else {
-                    format!("{} IS NOT NULL", column)
-                }
-            }
-            (_, "is_null") => format!("{} IS NULL", column),
-            (_, "is_not_null") => format!("{} IS NOT NULL", column),
-            _ => format!("{} = '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-        }
-    }
-}
+impl crate::app::query_builder::Filterable for District {}
 
 impl crate::app::query_builder::Sortable for District {
     fn apply_basic_sort(column: &str, direction: &str) -> String {
@@ -3,10 +3,10 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = crate::schema::encrypted_backup_keys)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct EncryptedBackupKey {
@@ -20,10 +20,13 @@ pub struct EncryptedBackupKey {
     pub backup_size_bytes: i64,
     pub backup_hash: String,
     pub is_verified: bool,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub expires_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -78,19 +81,27 @@ pub struct CreateEncryptedBackupKey {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct EncryptedBackupKeyResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub user_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub device_id: DieselUlid,
     pub backup_algorithm: String,
     pub backup_type: String,
     pub backup_size_bytes: i64,
     pub backup_hash: String,
     pub is_verified: bool,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub expires_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for EncryptedBackupKey {}
+
 impl EncryptedBackupKey {
     pub fn new(
         user_id: DieselUlid,
@@ -107,7 +118,7 @@ impl EncryptedBackupKey {
         let backup_size_bytes = encrypted_backup_data.len() as i64;
 
         EncryptedBackupKey {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             device_id,
             encrypted_backup_data,
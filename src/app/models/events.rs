@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::{HasModelType, activity_log::HasId};
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
@@ -17,10 +17,13 @@ pub struct Event {
     pub aggregate_id: Option<String>,
     pub aggregate_type: Option<String>,
     pub version: Option<i32>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub occurred_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -31,21 +34,28 @@ pub struct CreateEvent {
     pub aggregate_id: Option<String>,
     pub aggregate_type: Option<String>,
     pub version: Option<i32>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub occurred_at: Option<DateTime<Utc>>,
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct EventResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub event_name: String,
     pub event_data: serde_json::Value,
     pub aggregate_id: Option<String>,
     pub aggregate_type: Option<String>,
     pub version: Option<i32>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub occurred_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for Event {}
+
 impl Event {
     pub fn new(
         event_name: String,
@@ -57,7 +67,7 @@ impl Event {
     ) -> Self {
         let now = Utc::now();
         Event {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             event_name,
             event_data,
             aggregate_id,
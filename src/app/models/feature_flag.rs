@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use diesel::prelude::*;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+use super::{DieselUlid, HasIdGenerator};
+
+/// A feature that can be switched on, rolled out gradually or granted per user
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable)]
+#[diesel(table_name = crate::schema::feature_flags)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct FeatureFlag {
+    #[schema(example = "01ARZ3NDEKTSV4RRFFQ69G5FAV")]
+    pub id: DieselUlid,
+    #[schema(example = "new-dashboard")]
+    pub name: String,
+    pub description: Option<String>,
+    pub enabled: bool,
+    #[schema(example = 25)]
+    pub rollout_percentage: Option<i32>,
+    #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A per-user decision that wins over the flag's own setting
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable)]
+#[diesel(table_name = crate::schema::feature_flag_overrides)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct FeatureFlagOverride {
+    pub id: DieselUlid,
+    pub feature_flag_id: DieselUlid,
+    pub user_id: DieselUlid,
+    pub enabled: bool,
+    #[serde(with = "crate::app::utils::timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl HasIdGenerator for FeatureFlag {}
+
+impl FeatureFlag {
+    pub fn new(name: String, enabled: bool, rollout_percentage: Option<i32>) -> Self {
+        let now = Utc::now();
+        FeatureFlag {
+            id: Self::new_id(),
+            name,
+            description: None,
+            enabled,
+            rollout_percentage,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Rollout bucket of a user, 0-99
+    ///
+    /// Hashes the flag name with the user id, so a user keeps the same bucket
+    /// for a flag across requests and servers while different flags roll out
+    /// to different users.
+    pub fn rollout_bucket(&self, user_id: &str) -> u8 {
+        let digest = Sha256::digest(format!("{}:{}", self.name, user_id).as_bytes());
+        let value = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+        (value % 100) as u8
+    }
+
+    /// Whether the flag is on for a user, ignoring overrides
+    ///
+    /// A partial rollout is off for guests, who have no stable bucket.
+    pub fn is_enabled_for(&self, user_id: Option<&str>) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        match self.rollout_percentage {
+            None => true,
+            Some(percentage) if percentage >= 100 => true,
+            Some(percentage) if percentage <= 0 => false,
+            Some(percentage) => user_id.is_some_and(|user_id| i32::from(self.rollout_bucket(user_id)) < percentage),
+        }
+    }
+}
+
+impl HasIdGenerator for FeatureFlagOverride {}
+
+impl FeatureFlagOverride {
+    pub fn new(feature_flag_id: DieselUlid, user_id: DieselUlid, enabled: bool) -> Self {
+        let now = Utc::now();
+        FeatureFlagOverride {
+            id: Self::new_id(),
+            feature_flag_id,
+            user_id,
+            enabled,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
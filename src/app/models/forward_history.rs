@@ -3,10 +3,10 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable)]
 #[diesel(table_name = crate::schema::forward_history)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct ForwardHistory {
@@ -18,6 +18,7 @@ pub struct ForwardHistory {
     pub forwarded_by_device_id: DieselUlid,
     pub forward_depth: i32,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -31,15 +32,23 @@ pub struct CreateForwardHistory {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ForwardHistoryResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub message_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub original_message_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub forwarded_by_user_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub forwarded_by_device_id: DieselUlid,
     pub forward_depth: i32,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for ForwardHistory {}
+
 impl ForwardHistory {
     pub fn new(
         message_id: DieselUlid,
@@ -49,7 +58,7 @@ impl ForwardHistory {
         forward_depth: i32,
     ) -> Self {
         ForwardHistory {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             message_id,
             original_message_id,
             forwarded_by_user_id,
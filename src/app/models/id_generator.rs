@@ -0,0 +1,169 @@
+//! Id generation strategies
+//!
+//! Every id is stored as a 26-character ULID, so all strategies produce a
+//! `DieselUlid` and differ only in how its bits are chosen. Model constructors
+//! create ids with `Self::new_id()`, so a model opts into a strategy by
+//! overriding `HasIdGenerator::id_generator`; otherwise it keeps random ULIDs.
+
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use ulid::Ulid;
+
+use super::DieselUlid;
+
+pub trait IdGenerator: Send + Sync {
+    fn generate(&self) -> DieselUlid;
+
+    /// Prefix shown before the id in responses, e.g. `org` for `org_01ARZ...`
+    fn prefix(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Random ULIDs, the default
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UlidGenerator;
+
+impl IdGenerator for UlidGenerator {
+    fn generate(&self) -> DieselUlid {
+        DieselUlid::new()
+    }
+}
+
+/// ULIDs that strictly increase within the process, even inside one millisecond
+#[derive(Debug)]
+pub struct MonotonicUlidGenerator {
+    generator: Mutex<ulid::Generator>,
+}
+
+impl MonotonicUlidGenerator {
+    pub fn new() -> Self {
+        Self { generator: Mutex::new(ulid::Generator::new()) }
+    }
+}
+
+impl Default for MonotonicUlidGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdGenerator for MonotonicUlidGenerator {
+    fn generate(&self) -> DieselUlid {
+        let mut generator = self.generator.lock().unwrap_or_else(|e| e.into_inner());
+        // The random part only overflows after 2^80 ids in one millisecond
+        generator.generate().map(DieselUlid).unwrap_or_else(|_| DieselUlid::new())
+    }
+}
+
+/// Snowflake-style ids packed into a ULID
+///
+/// The 80 bits after the millisecond timestamp hold a 10-bit worker id, a
+/// 12-bit per-millisecond sequence and 58 random bits. Ids sort by time, then
+/// worker, then sequence, and the worker id tells which shard wrote a row.
+#[derive(Debug)]
+pub struct SnowflakeGenerator {
+    worker_id: u16,
+    state: Mutex<(u64, u16)>,
+}
+
+impl SnowflakeGenerator {
+    pub const MAX_WORKER_ID: u16 = (1 << 10) - 1;
+    const MAX_SEQUENCE: u16 = (1 << 12) - 1;
+
+    pub fn new(worker_id: u16) -> Self {
+        Self {
+            worker_id: worker_id & Self::MAX_WORKER_ID,
+            state: Mutex::new((0, 0)),
+        }
+    }
+
+    /// Worker that generated an id
+    pub fn worker_id_of(id: DieselUlid) -> u16 {
+        ((id.0.random() >> 70) as u16) & Self::MAX_WORKER_ID
+    }
+
+    /// Per-millisecond sequence of an id
+    pub fn sequence_of(id: DieselUlid) -> u16 {
+        ((id.0.random() >> 58) as u16) & Self::MAX_SEQUENCE
+    }
+
+    fn now_ms() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default()
+    }
+}
+
+impl IdGenerator for SnowflakeGenerator {
+    fn generate(&self) -> DieselUlid {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (last_ms, sequence) = *state;
+        let now = Self::now_ms();
+
+        // Never go backwards; borrow the next millisecond when the sequence runs out
+        let (timestamp, sequence) = if now > last_ms {
+            (now, 0)
+        } else if sequence < Self::MAX_SEQUENCE {
+            (last_ms, sequence + 1)
+        } else {
+            (last_ms + 1, 0)
+        };
+        *state = (timestamp, sequence);
+
+        let random = (u128::from(self.worker_id) << 70)
+            | (u128::from(sequence) << 58)
+            | (rand::random::<u64>() as u128 & ((1 << 58) - 1));
+        DieselUlid(Ulid::from_parts(timestamp, random))
+    }
+}
+
+/// Wraps another strategy and shows its ids with a type prefix, like Stripe's `cus_...`
+///
+/// Only the ULID is stored; `DieselUlid::parse` accepts the prefixed form.
+pub struct PrefixedIdGenerator {
+    prefix: &'static str,
+    inner: Box<dyn IdGenerator>,
+}
+
+impl PrefixedIdGenerator {
+    pub fn new(prefix: &'static str) -> Self {
+        Self::wrapping(prefix, UlidGenerator)
+    }
+
+    pub fn wrapping(prefix: &'static str, inner: impl IdGenerator + 'static) -> Self {
+        Self { prefix, inner: Box::new(inner) }
+    }
+}
+
+impl IdGenerator for PrefixedIdGenerator {
+    fn generate(&self) -> DieselUlid {
+        self.inner.generate()
+    }
+
+    fn prefix(&self) -> Option<&str> {
+        Some(self.prefix)
+    }
+}
+
+static DEFAULT_GENERATOR: UlidGenerator = UlidGenerator;
+
+/// Per-model choice of id strategy
+pub trait HasIdGenerator {
+    fn id_generator() -> &'static dyn IdGenerator {
+        &DEFAULT_GENERATOR
+    }
+
+    fn new_id() -> DieselUlid {
+        Self::id_generator().generate()
+    }
+
+    /// Id as shown to clients, with the model's prefix if it has one
+    fn display_id(id: &DieselUlid) -> String {
+        match Self::id_generator().prefix() {
+            Some(prefix) => id.prefixed(prefix),
+            None => id.to_string(),
+        }
+    }
+}
@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::{HasModelType, activity_log::HasId};
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
@@ -19,15 +19,21 @@ pub struct Job {
     pub max_attempts: i32,
     pub status: String,
     pub priority: i32,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub available_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub reserved_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub processed_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub failed_at: Option<DateTime<Utc>>,
     pub error_message: Option<String>,
     pub timeout_seconds: Option<i32>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -75,9 +81,12 @@ pub struct CreateJob {
     pub payload: serde_json::Value,
     pub max_attempts: i32,
     pub priority: i32,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub available_at: Option<DateTime<Utc>>,
     pub timeout_seconds: Option<i32>,
 }
+impl HasIdGenerator for Job {}
+
 impl Job {
     pub fn new(
         queue_name: String,
@@ -90,7 +99,7 @@ impl Job {
     ) -> Self {
         let now = Utc::now();
         Job {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             queue_name,
             job_name,
             payload,
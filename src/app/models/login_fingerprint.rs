@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use diesel::prelude::*;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use super::{DieselUlid, HasIdGenerator};
+
+/// A client a user has logged in from, identified by a hash of its request headers
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable)]
+#[diesel(table_name = crate::schema::login_fingerprints)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LoginFingerprint {
+    #[schema(example = "01ARZ3NDEKTSV4RRFFQ69G5FAV")]
+    pub id: DieselUlid,
+    pub user_id: DieselUlid,
+    pub fingerprint_hash: String,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
+    pub confirmed_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp")]
+    pub last_seen_at: DateTime<Utc>,
+    #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl HasIdGenerator for LoginFingerprint {}
+
+impl LoginFingerprint {
+    pub fn new(
+        user_id: DieselUlid,
+        fingerprint_hash: String,
+        user_agent: Option<String>,
+        ip_address: Option<String>,
+    ) -> Self {
+        let now = Utc::now();
+        LoginFingerprint {
+            id: Self::new_id(),
+            user_id,
+            fingerprint_hash,
+            user_agent,
+            ip_address,
+            confirmed_at: None,
+            last_seen_at: now,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn is_confirmed(&self) -> bool {
+        self.confirmed_at.is_some()
+    }
+}
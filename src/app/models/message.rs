@@ -1,13 +1,13 @@
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use crate::schema::messages;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use chrono::{DateTime, Utc};
 use crate::app::query_builder::SortDirection;
 use utoipa::ToSchema;
 use crate::app::models::{HasModelType, activity_log::HasId};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = messages)]
 #[diesel(primary_key(id))]
 pub struct Message {
@@ -23,10 +23,15 @@ pub struct Message {
     pub edit_of_message_id: Option<DieselUlid>,
     pub is_edited: bool,
     pub is_deleted: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub sent_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -75,6 +80,8 @@ impl From<MessageType> for String {
     }
 }
 
+impl HasIdGenerator for Message {}
+
 impl Message {
     pub fn message_type_enum(&self) -> MessageType {
         self.message_type.clone().into()
@@ -89,7 +96,7 @@ impl Message {
     ) -> Self {
         let now = Utc::now();
         Message {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             conversation_id,
             sender_user_id,
             sender_device_id,
@@ -138,22 +145,34 @@ impl Message {
 /// Message response payload
 #[derive(Debug, Serialize, ToSchema)]
 pub struct MessageResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub conversation_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub sender_user_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub sender_device_id: DieselUlid,
     pub message_type: String,
     pub encrypted_content: String,
     pub content_algorithm: String,
+    #[serde(with = "crate::app::models::diesel_ulid::response::option")]
     pub reply_to_message_id: Option<DieselUlid>,
+    #[serde(with = "crate::app::models::diesel_ulid::response::option")]
     pub forward_from_message_id: Option<DieselUlid>,
+    #[serde(with = "crate::app::models::diesel_ulid::response::option")]
     pub edit_of_message_id: Option<DieselUlid>,
     pub is_edited: bool,
     pub is_deleted: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub expires_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub sent_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub deleted_at: Option<DateTime<Utc>>,
 }
 
@@ -253,39 +272,7 @@ impl crate::app::query_builder::Queryable for Message {
     }
 }
 
-impl crate::app::query_builder::Filterable for Message {
-    fn apply_basic_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match (column, operator) {
-            ("id", op) | ("conversation_id", op) | ("sender_user_id", op) | ("sender_device_id", op) => {
-                format!("{} {} '{}'", column, op, value.as_str().unwrap_or(""))
-            }
-            ("message_type", "contains") => {
-                format!("LOWER({}) LIKE LOWER('%{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("message_type", "starts_with") => {
-                format!("LOWER({}) LIKE LOWER('{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("message_type", "ends_with") => {
-                format!("LOWER({}) LIKE LOWER('%{}')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("is_edited", op) | ("is_deleted", op) => {
-                format!("{} {} {}", column, op, value.as_bool().unwrap_or(false))
-            }
-            ("reply_to_message_id", "is_null") | ("forward_from_message_id", "is_null") |
-            ("edit_of_message_id", "is_null") | ("expires_at", "is_null") | ("deleted_at", "is_null") => {
-                format!("{} IS NULL", column)
-            }
-            ("reply_to_message_id", "is_not_null") | ("forward_from_message_id", "is_not_null") |
-            ("edit_of_message_id", "is_not_null") | ("expires_at", "is_not_null") | ("deleted_at", "is_not_null") => {
-                format!("{} IS NOT NULL", column)
-            }
-            ("expires_at", op) | ("sent_at", op) | ("created_at", op) | ("updated_at", op) | ("deleted_at", op) => {
-                format!("{} {} '{}'", column, op, value.as_str().unwrap_or(""))
-            }
-            _ => format!("{} = '{}'", column, value.as_str().unwrap_or(""))
-        }
-    }
-}
+impl crate::app::query_builder::Filterable for Message {}
 
 impl crate::app::query_builder::Sortable for Message {
     fn apply_basic_sort(column: &str, direction: &str) -> String {
@@ -315,6 +302,19 @@ impl HasModelType for Message {
     }
 }
 
+impl crate::app::traits::Touches for Message {
+    fn touches() -> Vec<&'static str> {
+        vec!["conversation"]
+    }
+
+    fn touched_parent(&self, relation: &str) -> Option<(&'static str, String)> {
+        match relation {
+            "conversation" => Some(("conversations", self.conversation_id.to_string())),
+            _ => None,
+        }
+    }
+}
+
 impl HasId for Message {
     fn id(&self) -> String {
         self.id.to_string()
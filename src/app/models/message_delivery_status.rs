@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use crate::schema::message_delivery_status;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = message_delivery_status)]
 #[diesel(primary_key(id))]
 pub struct MessageDeliveryStatus {
@@ -12,14 +13,20 @@ pub struct MessageDeliveryStatus {
     pub message_id: DieselUlid,
     pub recipient_device_id: DieselUlid,
     pub status: String,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub delivered_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub read_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub failed_at: Option<DateTime<Utc>>,
     pub failure_reason: Option<String>,
     pub retry_count: i32,
     pub max_retries: i32,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub next_retry_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +63,8 @@ impl From<DeliveryStatus> for String {
     }
 }
 
+impl HasIdGenerator for MessageDeliveryStatus {}
+
 impl MessageDeliveryStatus {
     pub fn status_enum(&self) -> DeliveryStatus {
         self.status.clone().into()
@@ -78,7 +87,7 @@ impl MessageDeliveryStatus {
     pub fn new(message_id: DieselUlid, recipient_device_id: DieselUlid) -> Self {
         let now = chrono::Utc::now();
         MessageDeliveryStatus {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             message_id,
             recipient_device_id,
             status: DeliveryStatus::Pending.into(),
@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable)]
@@ -16,9 +16,12 @@ pub struct MessageDeviceKey {
     pub recipient_device_id: DieselUlid,
     pub encrypted_message_key: String,
     pub key_algorithm: String,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub delivered_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub read_at: Option<DateTime<Utc>>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -31,15 +34,23 @@ pub struct CreateMessageDeviceKey {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct MessageDeviceKeyResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub message_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub recipient_device_id: DieselUlid,
     pub key_algorithm: String,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub delivered_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub read_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for MessageDeviceKey {}
+
 impl MessageDeviceKey {
     pub fn new(
         message_id: DieselUlid,
@@ -48,7 +59,7 @@ impl MessageDeviceKey {
         key_algorithm: String,
     ) -> Self {
         MessageDeviceKey {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             message_id,
             recipient_device_id,
             encrypted_message_key,
@@ -3,10 +3,10 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = crate::schema::message_mentions)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct MessageMention {
@@ -18,6 +18,7 @@ pub struct MessageMention {
     pub mention_start_pos: Option<i32>,
     pub mention_length: Option<i32>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -65,15 +66,21 @@ pub struct CreateMessageMention {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct MessageMentionResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub message_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub mentioned_user_id: DieselUlid,
     pub mention_type: String,
     pub mention_start_pos: Option<i32>,
     pub mention_length: Option<i32>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for MessageMention {}
+
 impl MessageMention {
     pub fn new(
         message_id: DieselUlid,
@@ -83,7 +90,7 @@ impl MessageMention {
         mention_length: Option<i32>,
     ) -> Self {
         MessageMention {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             message_id,
             mentioned_user_id,
             mention_type: mention_type.into(),
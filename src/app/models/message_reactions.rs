@@ -3,10 +3,10 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = crate::schema::message_reactions)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct MessageReaction {
@@ -18,8 +18,10 @@ pub struct MessageReaction {
     pub encrypted_reaction: String,
     pub reaction_algorithm: String,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -37,16 +39,24 @@ pub struct UpdateMessageReaction {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct MessageReactionResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub message_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub user_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub device_id: DieselUlid,
     pub encrypted_reaction: String,
     pub reaction_algorithm: String,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for MessageReaction {}
+
 impl MessageReaction {
     pub fn new(
         message_id: DieselUlid,
@@ -57,7 +67,7 @@ impl MessageReaction {
     ) -> Self {
         let now = Utc::now();
         Self {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             message_id,
             user_id,
             device_id,
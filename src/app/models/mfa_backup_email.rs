@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable)]
 #[diesel(table_name = crate::schema::mfa_backup_emails)]
@@ -13,10 +13,15 @@ pub struct MfaBackupEmail {
     pub backup_email: String,
     pub is_verified: bool,
     pub verification_token: Option<String>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub verification_sent_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub verified_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
 }
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable)]
@@ -28,11 +33,14 @@ pub struct MfaBackupEmailCode {
     pub user_id: DieselUlid,
     pub code: String,
     pub code_hash: String,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub expires_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub verified_at: Option<DateTime<Utc>>,
     pub is_used: bool,
     pub ip_address: Option<String>,
     pub user_agent: Option<String>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -61,10 +69,14 @@ pub struct BackupEmailResponse {
     pub id: String,
     pub backup_email: String,
     pub is_verified: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub verified_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for MfaBackupEmail {}
+
 impl MfaBackupEmail {
     pub fn new(
         user_id: DieselUlid,
@@ -73,7 +85,7 @@ impl MfaBackupEmail {
     ) -> Self {
         let now = Utc::now();
         MfaBackupEmail {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             backup_email,
             is_verified: false,
@@ -101,6 +113,8 @@ impl MfaBackupEmail {
     }
 }
 
+impl HasIdGenerator for MfaBackupEmailCode {}
+
 impl MfaBackupEmailCode {
     pub fn new(
         backup_email_id: DieselUlid,
@@ -113,7 +127,7 @@ impl MfaBackupEmailCode {
     ) -> Self {
         let now = Utc::now();
         MfaBackupEmailCode {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             backup_email_id,
             user_id,
             code,
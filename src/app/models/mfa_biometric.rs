@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, Insertable)]
 #[diesel(table_name = crate::schema::mfa_biometric_credentials)]
@@ -18,9 +18,13 @@ pub struct MfaBiometricCredential {
     pub device_name: Option<String>,
     pub is_platform_authenticator: bool,
     pub counter: i64,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
 }
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -56,10 +60,14 @@ pub struct BiometricCredentialResponse {
     pub biometric_type: String,
     pub platform: String,
     pub device_name: Option<String>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub last_used_at: Option<DateTime<Utc>>,
 }
 
+impl HasIdGenerator for MfaBiometricCredential {}
+
 impl MfaBiometricCredential {
     pub fn new(
         user_id: DieselUlid,
@@ -73,7 +81,7 @@ impl MfaBiometricCredential {
     ) -> Self {
         let now = Utc::now();
         MfaBiometricCredential {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             device_id,
             biometric_type,
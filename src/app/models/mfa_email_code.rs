@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, Insertable)]
 #[diesel(table_name = crate::schema::mfa_email_codes)]
@@ -12,11 +12,14 @@ pub struct MfaEmailCode {
     pub user_id: DieselUlid,
     pub code: String,
     pub code_hash: String,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub expires_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub verified_at: Option<DateTime<Utc>>,
     pub is_used: bool,
     pub ip_address: Option<String>,
     pub user_agent: Option<String>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -30,6 +33,8 @@ pub struct VerifyEmailCodeRequest {
     pub code: String,
 }
 
+impl HasIdGenerator for MfaEmailCode {}
+
 impl MfaEmailCode {
     pub fn new(
         user_id: DieselUlid,
@@ -41,7 +46,7 @@ impl MfaEmailCode {
     ) -> Self {
         let now = Utc::now();
         MfaEmailCode {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             code,
             code_hash,
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable)]
 #[diesel(table_name = crate::schema::mfa_push_devices)]
@@ -17,9 +17,13 @@ pub struct MfaPushDevice {
     pub platform_version: Option<String>,
     pub app_version: Option<String>,
     pub is_active: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
 }
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable)]
@@ -33,10 +37,13 @@ pub struct MfaPushChallenge {
     pub action_type: String,
     pub action_details: Option<serde_json::Value>,
     pub response: Option<String>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub responded_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub expires_at: DateTime<Utc>,
     pub ip_address: Option<String>,
     pub location_data: Option<serde_json::Value>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -67,7 +74,9 @@ pub struct PushDeviceResponse {
     pub device_type: String,
     pub device_name: Option<String>,
     pub is_active: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -76,10 +85,14 @@ pub struct PushChallengeResponse {
     pub id: String,
     pub action_type: String,
     pub action_details: Option<serde_json::Value>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub expires_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for MfaPushDevice {}
+
 impl MfaPushDevice {
     pub fn new(
         user_id: DieselUlid,
@@ -92,7 +105,7 @@ impl MfaPushDevice {
     ) -> Self {
         let now = Utc::now();
         MfaPushDevice {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             device_token,
             device_type,
@@ -124,6 +137,8 @@ impl MfaPushDevice {
     }
 }
 
+impl HasIdGenerator for MfaPushChallenge {}
+
 impl MfaPushChallenge {
     pub fn new(
         user_id: DieselUlid,
@@ -137,7 +152,7 @@ impl MfaPushChallenge {
     ) -> Self {
         let now = Utc::now();
         MfaPushChallenge {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             device_id,
             challenge,
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, Insertable)]
 #[diesel(table_name = crate::schema::mfa_sms_codes)]
@@ -13,12 +13,15 @@ pub struct MfaSmsCode {
     pub phone_number: String,
     pub code: String,
     pub code_hash: String,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub expires_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub verified_at: Option<DateTime<Utc>>,
     pub is_used: bool,
     pub send_attempts: i32,
     pub ip_address: Option<String>,
     pub user_agent: Option<String>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -32,6 +35,8 @@ pub struct VerifySmsCodeRequest {
     pub code: String,
 }
 
+impl HasIdGenerator for MfaSmsCode {}
+
 impl MfaSmsCode {
     pub fn new(
         user_id: DieselUlid,
@@ -44,7 +49,7 @@ impl MfaSmsCode {
     ) -> Self {
         let now = Utc::now();
         MfaSmsCode {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             phone_number,
             code,
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, Insertable)]
 #[diesel(table_name = crate::schema::mfa_trusted_devices)]
@@ -15,9 +15,13 @@ pub struct MfaTrustedDevice {
     pub ip_address: Option<String>,
     pub user_agent: Option<String>,
     pub trust_token: String,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub expires_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub revoked_at: Option<DateTime<Utc>>,
 }
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -32,11 +36,16 @@ pub struct TrustedDeviceResponse {
     pub id: String,
     pub device_name: Option<String>,
     pub ip_address: Option<String>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub expires_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for MfaTrustedDevice {}
+
 impl MfaTrustedDevice {
     pub fn new(
         user_id: DieselUlid,
@@ -49,7 +58,7 @@ impl MfaTrustedDevice {
     ) -> Self {
         let now = Utc::now();
         MfaTrustedDevice {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             device_fingerprint,
             device_name,
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, Insertable)]
 #[diesel(table_name = crate::schema::mfa_webauthn_credentials)]
@@ -19,9 +19,13 @@ pub struct MfaWebAuthnCredential {
     pub attestation_format: Option<String>,
     pub is_backup_eligible: bool,
     pub is_backup_state: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
 }
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, Insertable)]
@@ -32,8 +36,10 @@ pub struct MfaWebAuthnChallenge {
     pub user_id: DieselUlid,
     pub challenge: String,
     pub challenge_type: String,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub expires_at: DateTime<Utc>,
     pub is_used: bool,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -68,11 +74,15 @@ pub struct WebAuthnAuthenticationFinishRequest {
 pub struct WebAuthnCredentialResponse {
     pub id: String,
     pub device_name: Option<String>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub last_used_at: Option<DateTime<Utc>>,
     pub transports: Option<Vec<String>>,
 }
 
+impl HasIdGenerator for MfaWebAuthnCredential {}
+
 impl MfaWebAuthnCredential {
     pub fn new(
         user_id: DieselUlid,
@@ -87,7 +97,7 @@ impl MfaWebAuthnCredential {
     ) -> Self {
         let now = Utc::now();
         MfaWebAuthnCredential {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             credential_id,
             public_key,
@@ -122,6 +132,8 @@ impl MfaWebAuthnCredential {
     }
 }
 
+impl HasIdGenerator for MfaWebAuthnChallenge {}
+
 impl MfaWebAuthnChallenge {
     pub fn new(
         user_id: DieselUlid,
@@ -131,7 +143,7 @@ impl MfaWebAuthnChallenge {
     ) -> Self {
         let now = Utc::now();
         MfaWebAuthnChallenge {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             challenge,
             challenge_type,
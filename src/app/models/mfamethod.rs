@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::{HasModelType, DieselUlid};
+use super::{HasModelType, DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable)]
@@ -18,9 +18,13 @@ pub struct MfaMethod {
     pub is_verified: bool,
     pub backup_codes: Option<serde_json::Value>,
     pub recovery_codes_used_count: i32,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
     pub metadata: Option<serde_json::Value>,
 }
@@ -49,11 +53,14 @@ pub struct MfaSetupResponse {
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct MfaMethodResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub method_type: String,
     pub is_enabled: bool,
     pub is_verified: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -67,14 +74,18 @@ pub struct MfaAttempt {
     pub ip_address: Option<String>,
     pub user_agent: Option<String>,
     pub success: bool,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub attempted_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
 }
+impl HasIdGenerator for MfaMethod {}
+
 impl MfaMethod {
     pub fn new(user_id: DieselUlid, method_type: String) -> Self {
         let now = Utc::now();
         Self {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             method_type,
             secret: None,
@@ -114,6 +125,8 @@ impl MfaMethod {
     }
 }
 
+impl HasIdGenerator for MfaAttempt {}
+
 impl MfaAttempt {
     pub fn new(
         user_id: DieselUlid,
@@ -124,7 +137,7 @@ impl MfaAttempt {
     ) -> Self {
         let now = Utc::now();
         Self {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             method_type,
             ip_address,
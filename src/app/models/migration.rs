@@ -10,6 +10,7 @@ pub struct Migration {
     pub id: i32,
     pub migration: String,
     pub batch: i32,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub executed_at: DateTime<Utc>,
 }
 
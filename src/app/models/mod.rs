@@ -52,6 +52,7 @@ pub mod device_push_tokens;
 pub mod device_session_backups;
 
 pub use diesel_ulid::DieselUlid;
+pub use id_generator::{HasIdGenerator, IdGenerator};
 pub use decimal_wrapper::DecimalWrapper;
 
 /// Trait for models that can be used in polymorphic relationships
@@ -103,4 +104,7 @@ pub mod mfa_biometric;
 pub mod mfa_sms;
 pub mod mfa_push;
 pub mod mfa_backup_email;
-pub mod mfa_trusted_device;
\ No newline at end of file
+pub mod mfa_trusted_device;
+pub mod login_fingerprint;
+pub mod feature_flag;
+pub mod id_generator;
\ No newline at end of file
@@ -1,4 +1,4 @@
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
@@ -30,10 +30,13 @@ pub struct Notification {
     pub channels: Vec<Option<String>>,
     /// When the notification was read
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub read_at: Option<DateTime<Utc>>,
     /// When the notification was sent
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub sent_at: Option<DateTime<Utc>>,
     /// When the notification failed
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub failed_at: Option<DateTime<Utc>>,
     /// Number of retry attempts
     pub retry_count: Option<i32>,
@@ -44,12 +47,15 @@ pub struct Notification {
     /// Notification priority
     pub priority: Option<i32>,
     /// When notification should be sent
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub scheduled_at: Option<DateTime<Utc>>,
     /// Notification creation timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -66,22 +72,29 @@ pub struct CreateNotification {
 #[derive(Debug, Serialize, Deserialize, ToSchema, AsChangeset)]
 #[diesel(table_name = crate::schema::notifications)]
 pub struct UpdateNotification {
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub read_at: Option<DateTime<Utc>>,
 }
 
 /// Notification response payload
 #[derive(Debug, Serialize, ToSchema)]
 pub struct NotificationResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub notification_type: String,
     pub notifiable_id: String,
     pub notifiable_type: String,
     pub data: serde_json::Value,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub read_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for Notification {}
+
 impl Notification {
     pub fn new(
         notification_type: String,
@@ -91,7 +104,7 @@ impl Notification {
     ) -> Self {
         let now = Utc::now();
         Notification {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             notification_type,
             notifiable_type,
             notifiable_id,
@@ -184,37 +197,7 @@ impl crate::app::query_builder::Queryable for Notification {
     }
 }
 
-impl crate::app::query_builder::Filterable for Notification {
-    fn apply_basic_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match (column, operator) {
-            ("id", op) | ("notifiable_id", op) => {
-                format!("{} {} '{}'", column, op, value.as_str().unwrap_or(""))
-            }
-            ("notification_type", "contains") | ("notifiable_type", "contains") => {
-                format!("LOWER({}) LIKE LOWER('%{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("notification_type", "starts_with") | ("notifiable_type", "starts_with") => {
-                format!("LOWER({}) LIKE LOWER('{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("notification_type", "ends_with") | ("notifiable_type", "ends_with") => {
-                format!("LOWER({}) LIKE LOWER('%{}')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("read_at", "is_null") => {
-                format!("{} IS NULL", column)
-            }
-            ("read_at", "is_not_null") => {
-                format!("{} IS NOT NULL", column)
-            }
-            ("read_at", op) | ("created_at", op) | ("updated_at", op) => {
-                format!("{} {} '{}'", column, op, value.as_str().unwrap_or(""))
-            }
-            ("priority", op) | ("retry_count", op) | ("max_retries", op) => {
-                format!("{} {} {}", column, op, value.as_i64().unwrap_or(0))
-            }
-            _ => format!("{} = '{}'", column, value.as_str().unwrap_or(""))
-        }
-    }
-}
+impl crate::app::query_builder::Filterable for Notification {}
 
 impl crate::app::query_builder::Sortable for Notification {
     fn apply_basic_sort(column: &str, direction: &str) -> String {
@@ -1,4 +1,4 @@
-use crate::app::models::DieselUlid;
+use crate::app::models::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
@@ -13,8 +13,11 @@ pub struct AccessToken {
     pub name: Option<String>,
     pub scopes: Option<String>,
     pub revoked: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     pub jwk_thumbprint: Option<String>,
 }
@@ -25,6 +28,7 @@ pub struct CreateAccessToken {
     pub client_id: String,
     pub name: Option<String>,
     pub scopes: Vec<String>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
     pub jwk_thumbprint: Option<String>,
 }
@@ -36,18 +40,24 @@ pub struct UpdateAccessToken {
 
 #[derive(Debug, Serialize)]
 pub struct AccessTokenResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub user_id: Option<String>,
     pub client_id: String,
     pub name: Option<String>,
     pub scopes: Vec<String>,
     pub revoked: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub expires_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
     pub jwk_thumbprint: Option<String>,
 }
 
+impl HasIdGenerator for AccessToken {}
+
 impl AccessToken {
     pub fn to_response(&self) -> AccessTokenResponse {
         let scopes = match &self.scopes {
@@ -110,7 +120,7 @@ impl AccessToken {
     ) -> Self {
         let now = Utc::now();
         AccessToken {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             client_id,
             name,
@@ -1,4 +1,4 @@
-use crate::app::models::DieselUlid;
+use crate::app::models::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
@@ -12,11 +12,14 @@ pub struct AuthCode {
     pub client_id: String,
     pub scopes: Option<String>,
     pub revoked: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
     pub challenge: Option<String>,
     pub challenge_method: Option<String>,
     pub redirect_uri: String,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -28,21 +31,28 @@ pub struct CreateAuthCode {
     pub redirect_uri: String,
     pub challenge: Option<String>,
     pub challenge_method: Option<String>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
 }
 #[derive(Debug, Serialize)]
 pub struct AuthCodeResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub user_id: String,
     pub client_id: String,
     pub scopes: Vec<String>,
     pub revoked: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub expires_at: Option<DateTime<Utc>>,
     pub redirect_uri: String,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for AuthCode {}
+
 impl AuthCode {
 
     pub fn to_response(&self) -> AuthCodeResponse {
@@ -125,7 +135,7 @@ impl AuthCode {
     ) -> Self {
         let now = Utc::now();
         AuthCode {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id,
             client_id,
             scopes,
@@ -1,4 +1,4 @@
-use crate::app::models::DieselUlid;
+use crate::app::models::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
@@ -18,8 +18,11 @@ pub struct Client {
     pub personal_access_client: bool,
     pub password_client: bool,
     pub revoked: bool,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
     pub created_by_id: DieselUlid,
     pub updated_by_id: DieselUlid,
@@ -60,6 +63,7 @@ pub struct UpdateClient {
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ClientResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     #[schema(example = "My OAuth App")]
     pub name: String,
@@ -68,10 +72,14 @@ pub struct ClientResponse {
     pub personal_access_client: bool,
     pub password_client: bool,
     pub revoked: bool,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for Client {}
+
 impl Client {
     pub fn new(
         organization_id: Option<DieselUlid>,
@@ -85,7 +93,7 @@ impl Client {
     ) -> Self {
         let now = Utc::now();
         Self {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             organization_id,
             user_id,
             name,
@@ -1,4 +1,4 @@
-use crate::app::models::DieselUlid;
+use crate::app::models::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
@@ -17,11 +17,14 @@ pub struct DeviceCode {
     pub scopes: Option<String>,
     pub verification_uri: String,
     pub verification_uri_complete: Option<String>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub expires_at: DateTime<Utc>,
     pub interval: i32, // Polling interval in seconds
     pub user_authorized: bool,
     pub revoked: bool,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -55,6 +58,8 @@ pub struct DeviceCodeVerification {
     pub user_code: String,
 }
 
+impl HasIdGenerator for DeviceCode {}
+
 impl DeviceCode {
     pub fn new(
         device_code: String,
@@ -68,7 +73,7 @@ impl DeviceCode {
     ) -> Self {
         let now = Utc::now();
         DeviceCode {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             device_code,
             user_code,
             client_id,
@@ -1,4 +1,4 @@
-use crate::app::models::DieselUlid;
+use crate::app::models::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
@@ -9,7 +9,9 @@ use crate::app::query_builder::{SortDirection};
 pub struct PersonalAccessClient {
     pub id: DieselUlid,
     pub client_id: DieselUlid,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -19,17 +21,23 @@ pub struct CreatePersonalAccessClient {
 }
 #[derive(Debug, Serialize)]
 pub struct PersonalAccessClientResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub client_id: DieselUlid,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for PersonalAccessClient {}
+
 impl PersonalAccessClient {
     pub fn new(client_id: DieselUlid) -> Self {
         let now = Utc::now();
         PersonalAccessClient {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             client_id,
             created_at: now,
             updated_at: now,
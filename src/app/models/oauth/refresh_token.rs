@@ -1,4 +1,4 @@
-use crate::app::models::DieselUlid;
+use crate::app::models::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
@@ -10,26 +10,36 @@ pub struct RefreshToken {
     pub id: DieselUlid,
     pub access_token_id: String,
     pub revoked: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateRefreshToken {
     pub access_token_id: String,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct RefreshTokenResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub access_token_id: String,
     pub revoked: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub expires_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for RefreshToken {}
+
 impl RefreshToken {
 
     pub fn to_response(&self) -> RefreshTokenResponse {
@@ -59,7 +69,7 @@ impl RefreshToken {
     pub fn new(access_token_id: String, expires_at: Option<DateTime<Utc>>) -> Self {
         let now = Utc::now();
         RefreshToken {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             access_token_id,
             revoked: false,
             expires_at,
@@ -1,4 +1,4 @@
-use crate::app::models::DieselUlid;
+use crate::app::models::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
@@ -13,7 +13,9 @@ pub struct Scope {
     pub name: String,
     pub description: Option<String>,
     pub is_default: bool,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 #[derive(Debug, Serialize, Deserialize)]
@@ -32,19 +34,24 @@ pub struct UpdateScope {
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ScopeResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub name: String,
     pub description: Option<String>,
     pub is_default: bool,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for Scope {}
+
 impl Scope {
     pub fn new(name: String, description: Option<String>, is_default: bool) -> Self {
         let now = Utc::now();
         Scope {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             name,
             description,
             is_default,
@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
@@ -20,11 +20,14 @@ pub struct OAuthCibaAuthCode {
     pub redirect_uri: Option<String>,
     pub code_challenge: Option<String>,
     pub code_challenge_method: Option<String>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub expires_at: DateTime<Utc>,
     pub revoked: bool,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -41,20 +44,29 @@ pub struct CreateOAuthCibaAuthCode {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct OAuthCibaAuthCodeResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub ciba_request_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub client_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub user_id: DieselUlid,
     pub scopes: Option<String>,
     pub redirect_uri: Option<String>,
     pub code_challenge: Option<String>,
     pub code_challenge_method: Option<String>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub expires_at: DateTime<Utc>,
     pub revoked: bool,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for OAuthCibaAuthCode {}
+
 impl OAuthCibaAuthCode {
     pub fn new(
         ciba_request_id: DieselUlid,
@@ -72,7 +84,7 @@ impl OAuthCibaAuthCode {
         let code = Self::generate_auth_code();
 
         OAuthCibaAuthCode {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             ciba_request_id,
             code,
             client_id,
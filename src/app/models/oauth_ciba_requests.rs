@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::{HasModelType, activity_log::HasId};
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
@@ -25,13 +25,18 @@ pub struct OAuthCibaRequest {
     pub status: String,
     pub notification_endpoint: Option<String>,
     pub notification_token: Option<String>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub expires_at: DateTime<Utc>,
     pub interval_seconds: i32,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub authorized_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub denied_at: Option<DateTime<Utc>>,
 }
 
@@ -84,19 +89,27 @@ pub struct CreateOAuthCibaRequest {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct OAuthCibaRequestResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub auth_req_id: String,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub client_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response::option")]
     pub user_id: Option<DieselUlid>,
     pub scope: Option<String>,
     pub binding_message: Option<String>,
     pub user_code: Option<String>,
     pub status: String,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub expires_at: DateTime<Utc>,
     pub interval_seconds: i32,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub authorized_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub denied_at: Option<DateTime<Utc>>,
 }
 
@@ -107,6 +120,8 @@ pub struct CibaAuthenticationResponse {
     pub interval: i32,
 }
 
+impl HasIdGenerator for OAuthCibaRequest {}
+
 impl OAuthCibaRequest {
     pub fn new(
         client_id: DieselUlid,
@@ -126,7 +141,7 @@ impl OAuthCibaRequest {
         let auth_req_id = format!("urn:ietf:params:oauth:ciba:auth-req-id:{}", DieselUlid::new());
 
         OAuthCibaRequest {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             auth_req_id,
             client_id,
             user_id: None,
@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::{HasModelType, activity_log::HasId};
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
@@ -15,11 +15,14 @@ pub struct OAuthPushedRequest {
     pub request_uri: String,
     pub client_id: DieselUlid,
     pub request_data: String,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub expires_at: DateTime<Utc>,
     pub used: bool,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -31,12 +34,17 @@ pub struct CreateOAuthPushedRequest {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct OAuthPushedRequestResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub request_uri: String,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub client_id: DieselUlid,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub expires_at: DateTime<Utc>,
     pub used: bool,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -46,6 +54,8 @@ pub struct PushedAuthorizationResponse {
     pub expires_in: i64,
 }
 
+impl HasIdGenerator for OAuthPushedRequest {}
+
 impl OAuthPushedRequest {
     pub fn new(
         client_id: DieselUlid,
@@ -58,7 +68,7 @@ impl OAuthPushedRequest {
         let request_uri = format!("urn:ietf:params:oauth:request_uri:{}", DieselUlid::new());
 
         OAuthPushedRequest {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             request_uri,
             client_id,
             request_data,
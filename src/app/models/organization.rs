@@ -1,4 +1,4 @@
-use super::{DieselUlid, DecimalWrapper};
+use super::{DieselUlid, DecimalWrapper, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc, NaiveDate};
@@ -69,11 +69,14 @@ pub struct Organization {
     pub is_active: bool,
     /// Creation timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     /// Soft delete timestamp
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
     /// User who created this organization
     pub created_by_id: DieselUlid,
@@ -81,6 +84,11 @@ pub struct Organization {
     pub updated_by_id: DieselUlid,
     /// User who deleted this organization
     pub deleted_by_id: Option<DieselUlid>,
+    /// Shared by the rows soft-deleted in one cascading delete
+    pub delete_batch_id: Option<DieselUlid>,
+    /// Incremented on every update, for optimistic locking
+    #[schema(example = 1)]
+    pub lock_version: i32,
 }
 
 /// Create organization payload for service layer
@@ -114,32 +122,55 @@ pub struct UpdateOrganization {
     pub domain_id: Option<DieselUlid>,
     pub type_id: Option<DieselUlid>,
     pub name: Option<String>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub parent_id: Option<Option<DieselUlid>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub code: Option<Option<String>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub address: Option<Option<String>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub authorized_capital: Option<Option<DecimalWrapper>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub business_activities: Option<Option<String>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub contact_persons: Option<Option<JsonValue>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub email: Option<Option<String>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub establishment_date: Option<Option<NaiveDate>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub governance_structure: Option<Option<JsonValue>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub legal_status: Option<Option<String>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub paid_capital: Option<Option<DecimalWrapper>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub path: Option<Option<String>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub phone: Option<Option<String>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub registration_number: Option<Option<String>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub tax_number: Option<Option<String>>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub website: Option<Option<String>>,
     pub is_active: Option<bool>,
+    /// Only update while the row is still at this version
+    pub lock_version: Option<i32>,
 }
 
 /// Organization response payload for API endpoints
 #[derive(Debug, Serialize, ToSchema)]
 pub struct OrganizationResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub domain_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response::option")]
     pub parent_id: Option<DieselUlid>,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub type_id: DieselUlid,
     pub code: Option<String>,
     pub name: String,
@@ -159,19 +190,29 @@ pub struct OrganizationResponse {
     pub tax_number: Option<String>,
     pub website: Option<String>,
     pub is_active: bool,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub deleted_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub created_by_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub updated_by_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response::option")]
     pub deleted_by_id: Option<DieselUlid>,
+    /// Send back in `If-Match` or as `lock_version` when updating
+    pub lock_version: i32,
 }
 
+impl HasIdGenerator for Organization {}
+
 impl Organization {
     pub fn new(create_org: CreateOrganization, created_by: DieselUlid) -> Self {
         let now = Utc::now();
         Organization {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             domain_id: create_org.domain_id,
             parent_id: create_org.parent_id,
             type_id: create_org.type_id,
@@ -199,6 +240,8 @@ impl Organization {
             created_by_id: created_by.clone(),
             updated_by_id: created_by,
             deleted_by_id: None,
+            delete_batch_id: None,
+            lock_version: 1,
         }
     }
 
@@ -232,10 +275,17 @@ impl Organization {
             created_by_id: self.created_by_id,
             updated_by_id: self.updated_by_id,
             deleted_by_id: self.deleted_by_id,
+            lock_version: self.lock_version,
         }
     }
 }
 
+impl crate::app::traits::OptimisticLocking for Organization {
+    fn lock_version(&self) -> i32 {
+        self.lock_version
+    }
+}
+
 impl HasModelType for Organization {
     fn model_type() -> &'static str {
         "Organization"
@@ -381,19 +431,27 @@ impl crate::app::query_builder::Queryable for Organization {
             "deletedBy.organizations.position.level",
         ]
     }
-}
 
-// Implement the enhanced filtering trait
-impl crate::app::query_builder::Filterable for Organization {
-    fn apply_basic_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match operator {
-            "=" => format!("{} = {}", column, Self::format_filter_value(value)),
-            "!=" => format!("{} != {}", column, Self::format_filter_value(value)),
-            _ => format!("{} {} {}", column, operator, Self::format_filter_value(value))
+    fn include_fields(relation: &str) -> Option<Vec<&'static str>> {
+        use crate::app::query_builder::Queryable;
+        use super::{organization_domain::OrganizationDomain, organization_position::OrganizationPosition};
+        use super::{organization_position_level::OrganizationPositionLevel, organization_type::OrganizationType, user::User};
+
+        match relation {
+            "domain" => Some(OrganizationDomain::allowed_fields()),
+            "type" => Some(OrganizationType::allowed_fields()),
+            "parent" | "children" => Some(Self::allowed_fields()),
+            "levels" => Some(OrganizationPositionLevel::allowed_fields()),
+            "positions" => Some(OrganizationPosition::allowed_fields()),
+            "users" | "createdBy" | "updatedBy" | "deletedBy" => Some(User::allowed_fields()),
+            _ => None,
         }
     }
 }
 
+// Implement the enhanced filtering trait
+impl crate::app::query_builder::Filterable for Organization {}
+
 // Implement the enhanced sorting trait
 impl crate::app::query_builder::Sortable for Organization {
     fn apply_basic_sort(column: &str, direction: &str) -> String {
@@ -1,9 +1,9 @@
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
-use crate::app::query_builder::SortDirection;
+use crate::app::query_builder::{HasCount, SortDirection};
 
 /// OrganizationDomain model representing the domain/sector of an organization
 /// Provides high-level categorization for organizations
@@ -24,11 +24,14 @@ pub struct OrganizationDomain {
     pub description: Option<String>,
     /// Creation timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     /// Soft delete timestamp
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
     /// User who created this record
     pub created_by_id: DieselUlid,
@@ -36,6 +39,8 @@ pub struct OrganizationDomain {
     pub updated_by_id: DieselUlid,
     /// User who deleted this record
     pub deleted_by_id: Option<DieselUlid>,
+    /// Shared by the rows soft-deleted in one cascading delete
+    pub delete_batch_id: Option<DieselUlid>,
 }
 
 /// Create organization domain payload for service layer
@@ -49,27 +54,34 @@ pub struct CreateOrganizationDomain {
 /// Update organization domain payload for service layer
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UpdateOrganizationDomain {
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub code: Option<Option<String>>,
     pub name: Option<String>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub description: Option<Option<String>>,
 }
 
 /// OrganizationDomain response payload for API endpoints
 #[derive(Debug, Serialize, ToSchema)]
 pub struct OrganizationDomainResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub code: Option<String>,
     pub name: String,
     pub description: Option<String>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for OrganizationDomain {}
+
 impl OrganizationDomain {
     pub fn new(create_data: CreateOrganizationDomain, created_by: DieselUlid) -> Self {
         let now = Utc::now();
         OrganizationDomain {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             code: create_data.code,
             name: create_data.name,
             description: create_data.description,
@@ -79,6 +91,7 @@ impl OrganizationDomain {
             created_by_id: created_by.clone(),
             updated_by_id: created_by,
             deleted_by_id: None,
+            delete_batch_id: None,
         }
     }
 
@@ -164,18 +177,27 @@ impl crate::app::query_builder::Queryable for OrganizationDomain {
             "deletedBy",
         ]
     }
-}
 
-impl crate::app::query_builder::Filterable for OrganizationDomain {
-    fn apply_basic_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match operator {
-            "=" => format!("{} = {}", column, Self::format_filter_value(value)),
-            "!=" => format!("{} != {}", column, Self::format_filter_value(value)),
-            _ => format!("{} {} {}", column, operator, Self::format_filter_value(value))
-        }
+    fn count_relations() -> Vec<HasCount> {
+        vec![
+            HasCount {
+                relation: "types",
+                table: "organization_types",
+                foreign_key: "domain_id",
+                soft_deletes: true,
+            },
+            HasCount {
+                relation: "organizations",
+                table: "organizations",
+                foreign_key: "domain_id",
+                soft_deletes: true,
+            },
+        ]
     }
 }
 
+impl crate::app::query_builder::Filterable for OrganizationDomain {}
+
 impl crate::app::query_builder::Sortable for OrganizationDomain {
     fn apply_basic_sort(column: &str, direction: &str) -> String {
         format!("{} {}", column, direction)
@@ -261,3 +283,21 @@ impl crate::app::query_builder::Includable for OrganizationDomain {
 }
 
 crate::impl_query_builder_service!(OrganizationDomain);
+
+impl crate::app::traits::CascadeSoftDeletes for OrganizationDomain {
+    fn soft_delete_table() -> &'static str {
+        "organization_domains"
+    }
+
+    fn cascade_soft_deletes() -> Vec<&'static str> {
+        vec!["types", "organizations"]
+    }
+
+    fn cascaded_children(relation: &str) -> Option<(&'static str, &'static str)> {
+        match relation {
+            "types" => Some(("organization_types", "domain_id")),
+            "organizations" => Some(("organizations", "domain_id")),
+            _ => None,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::{DieselUlid, DecimalWrapper};
+use super::{DieselUlid, DecimalWrapper, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
@@ -44,11 +44,14 @@ pub struct OrganizationPosition {
     pub responsibilities: JsonValue,
     /// Creation timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     /// Soft delete timestamp
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
     /// User who created this position
     pub created_by_id: DieselUlid,
@@ -80,6 +83,7 @@ pub struct UpdateOrganizationPosition {
     pub organization_position_level_id: Option<DieselUlid>,
     pub code: Option<String>,
     pub name: Option<String>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub description: Option<Option<String>>,
     pub is_active: Option<bool>,
     pub min_salary: Option<DecimalWrapper>,
@@ -92,8 +96,11 @@ pub struct UpdateOrganizationPosition {
 /// Organization position response payload for API endpoints
 #[derive(Debug, Serialize, ToSchema)]
 pub struct OrganizationPositionResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub organization_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub organization_position_level_id: DieselUlid,
     pub code: String,
     pub name: String,
@@ -104,19 +111,27 @@ pub struct OrganizationPositionResponse {
     pub max_incumbents: i32,
     pub qualifications: JsonValue,
     pub responsibilities: JsonValue,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub deleted_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub created_by_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub updated_by_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response::option")]
     pub deleted_by_id: Option<DieselUlid>,
 }
 
+impl HasIdGenerator for OrganizationPosition {}
+
 impl OrganizationPosition {
     pub fn new(create_position: CreateOrganizationPosition, created_by: DieselUlid) -> Self {
         let now = Utc::now();
         OrganizationPosition {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             organization_id: create_position.organization_id,
             organization_position_level_id: create_position.organization_position_level_id,
             code: create_position.code,
@@ -257,46 +272,7 @@ impl crate::app::query_builder::Queryable for OrganizationPosition {
 }
 
 // Implement enhanced query builder traits for OrganizationPosition
-impl crate::app::query_builder::Filterable for OrganizationPosition {
-    fn apply_basic_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match (column, operator) {
-            ("name", "contains") => {
-                format!("LOWER({}) LIKE LOWER('%{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "starts_with") => {
-                format!("LOWER({}) LIKE LOWER('{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("name", "ends_with") => {
-                format!("LOWER({}) LIKE LOWER('%{}')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("code", "=") => {
-                format!("{} = '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("is_active", "=") => {
-                format!("{} = {}", column, value.as_bool().unwrap_or(false))
-            }
-            ("min_salary", op) | ("max_salary", op) => {
-                if let Some(num) = value.as_f64() {
-                    format!("{} {} {}", column, op, num)
-                } else {
-                    format!("{} = 0", column)
-                }
-            }
-            ("max_incumbents", op) => {
-                format!("{} {} {}", column, op, value.as_i64().unwrap_or(0))
-            }
-            _ => {
-                match value {
-                    serde_json::Value::String(s) => format!("{} {} '{}'", column, operator, s.replace('\'', "''")),
-                    serde_json::Value::Number(n) => format!("{} {} {}", column, operator, n),
-                    serde_json::Value::Bool(b) => format!("{} {} {}", column, operator, b),
-                    serde_json::Value::Null => format!("{} IS NULL", column),
-                    _ => format!("{} {} '{}'", column, operator, value.to_string().replace('\'', "''"))
-                }
-            }
-        }
-    }
-}
+impl crate::app::query_builder::Filterable for OrganizationPosition {}
 
 impl crate::app::query_builder::Sortable for OrganizationPosition {
     fn apply_basic_sort(column: &str, direction: &str) -> String {
@@ -1,4 +1,4 @@
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
@@ -33,11 +33,14 @@ pub struct OrganizationPositionLevel {
     pub is_active: bool,
     /// Creation timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     /// Soft delete timestamp
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
     /// User who created this position level
     pub created_by_id: DieselUlid,
@@ -63,6 +66,7 @@ pub struct UpdateOrganizationPositionLevel {
     pub organization_id: Option<DieselUlid>,
     pub code: Option<String>,
     pub name: Option<String>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub description: Option<Option<String>>,
     pub level: Option<i32>,
     pub is_active: Option<bool>,
@@ -71,26 +75,36 @@ pub struct UpdateOrganizationPositionLevel {
 /// Job level response payload for API endpoints
 #[derive(Debug, Serialize, ToSchema)]
 pub struct OrganizationPositionLevelResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub organization_id: DieselUlid,
     pub code: String,
     pub name: String,
     pub description: Option<String>,
     pub level: i32,
     pub is_active: bool,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub deleted_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub created_by_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub updated_by_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response::option")]
     pub deleted_by_id: Option<DieselUlid>,
 }
 
+impl HasIdGenerator for OrganizationPositionLevel {}
+
 impl OrganizationPositionLevel {
     pub fn new(create_level: CreateOrganizationPositionLevel, created_by: DieselUlid) -> Self {
         let now = Utc::now();
         OrganizationPositionLevel {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             organization_id: create_level.organization_id,
             code: create_level.code,
             name: create_level.name,
@@ -1,4 +1,4 @@
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use chrono::{DateTime, Utc};
@@ -30,11 +30,14 @@ pub struct OrganizationType {
     pub level: i32,
     /// Creation timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     /// Soft delete timestamp
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
     /// User who created this record
     pub created_by_id: DieselUlid,
@@ -42,6 +45,8 @@ pub struct OrganizationType {
     pub updated_by_id: DieselUlid,
     /// User who deleted this record
     pub deleted_by_id: Option<DieselUlid>,
+    /// Shared by the rows soft-deleted in one cascading delete
+    pub delete_batch_id: Option<DieselUlid>,
 }
 
 /// Create organization type payload for service layer
@@ -58,8 +63,10 @@ pub struct CreateOrganizationType {
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UpdateOrganizationType {
     pub domain_id: Option<DieselUlid>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub code: Option<Option<String>>,
     pub name: Option<String>,
+    #[serde(default, deserialize_with = "crate::app::http::merge_patch::nullable")]
     pub description: Option<Option<String>>,
     pub level: Option<i32>,
 }
@@ -67,21 +74,27 @@ pub struct UpdateOrganizationType {
 /// OrganizationType response payload for API endpoints
 #[derive(Debug, Serialize, ToSchema)]
 pub struct OrganizationTypeResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub domain_id: DieselUlid,
     pub code: Option<String>,
     pub name: String,
     pub description: Option<String>,
     pub level: i32,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for OrganizationType {}
+
 impl OrganizationType {
     pub fn new(create_data: CreateOrganizationType, created_by: DieselUlid) -> Self {
         let now = Utc::now();
         OrganizationType {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             domain_id: create_data.domain_id,
             code: create_data.code,
             name: create_data.name,
@@ -93,6 +106,7 @@ impl OrganizationType {
             created_by_id: created_by.clone(),
             updated_by_id: created_by,
             deleted_by_id: None,
+            delete_batch_id: None,
         }
     }
 
@@ -188,15 +202,7 @@ impl crate::app::query_builder::Queryable for OrganizationType {
     }
 }
 
-impl crate::app::query_builder::Filterable for OrganizationType {
-    fn apply_basic_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match operator {
-            "=" => format!("{} = {}", column, Self::format_filter_value(value)),
-            "!=" => format!("{} != {}", column, Self::format_filter_value(value)),
-            _ => format!("{} {} {}", column, operator, Self::format_filter_value(value))
-        }
-    }
-}
+impl crate::app::query_builder::Filterable for OrganizationType {}
 
 impl crate::app::query_builder::Sortable for OrganizationType {
     fn apply_basic_sort(column: &str, direction: &str) -> String {
@@ -283,3 +289,20 @@ impl crate::app::query_builder::Includable for OrganizationType {
 }
 
 crate::impl_query_builder_service!(OrganizationType);
+
+impl crate::app::traits::CascadeSoftDeletes for OrganizationType {
+    fn soft_delete_table() -> &'static str {
+        "organization_types"
+    }
+
+    fn cascade_soft_deletes() -> Vec<&'static str> {
+        vec!["organizations"]
+    }
+
+    fn cascaded_children(relation: &str) -> Option<(&'static str, &'static str)> {
+        match relation {
+            "organizations" => Some(("organizations", "type_id")),
+            _ => None,
+        }
+    }
+}
@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable)]
 #[diesel(table_name = crate::schema::sys_permissions)]
@@ -15,7 +15,9 @@ pub struct Permission {
     pub action: String,
     pub scope_type: Option<String>,
     pub scope_id: Option<DieselUlid>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     pub created_by_id: DieselUlid,
     pub updated_by_id: DieselUlid,
@@ -38,20 +40,25 @@ pub struct UpdatePermission {
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PermissionResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub guard_name: String,
     pub resource: Option<String>,
     pub action: String,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for Permission {}
+
 impl Permission {
     pub fn new(guard_name: Option<String>, resource: Option<String>, action: String, created_by: DieselUlid) -> Self {
         let now = Utc::now();
 
         Self {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             organization_id: None,
             guard_name: guard_name.unwrap_or_else(|| "api".to_string()),
             resource,
@@ -152,85 +159,7 @@ impl crate::app::query_builder::Queryable for Permission {
 }
 
 // Implement enhanced query builder traits for Permission
-impl crate::app::query_builder::Filterable for Permission {
-    fn apply_basic_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match (column, operator) {
-            ("id", op) | ("organization_id", op) | ("scope_id", op) | ("created_by_id", op) | ("updated_by_id", op) | ("deleted_by_id", op) if op != "is_null" && op != "is_not_null" => {
-                format!("{} {} '{}'", column, op, value.as_str().unwrap_or(""))
-            }
-            ("action", "contains") | ("resource", "contains") | ("guard_name", "contains") | ("scope_type", "contains") => {
-                format!("LOWER({}) LIKE LOWER('%{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("action", "starts_with") | ("resource", "starts_with") | ("guard_name", "starts_with") | ("scope_type", "starts_with") => {
-                format!("LOWER({}) LIKE LOWER('{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("action", "ends_with") | ("resource", "ends_with") | ("guard_name", "ends_with") | ("scope_type", "ends_with") => {
-                format!("LOWER({}) LIKE LOWER('%{}')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("action", "like") | ("resource", "like") | ("guard_name", "like") | ("scope_type", "like") => {
-                format!("{} LIKE '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("action", "ilike") | ("resource", "ilike") | ("guard_name", "ilike") | ("scope_type", "ilike") => {
-                format!("{} ILIKE '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("action", "eq") | ("resource", "eq") | ("guard_name", "eq") | ("scope_type", "eq") => {
-                format!("{} = '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("action", "ne") | ("resource", "ne") | ("guard_name", "ne") | ("scope_type", "ne") => {
-                format!("{} != '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("action", "in") | ("resource", "in") | ("guard_name", "in") | ("scope_type", "in") | ("organization_id", "in") => {
-                let values = value.as_array()
-                    .map(|arr| arr.iter()
-                        .filter_map(|v| v.as_str())
-                        .map(|s| format!("'{}'", s.replace('\'', "''")))
-                        .collect::<Vec<_>>()
-                        .join(","))
-                    .unwrap_or_default();
-                format!("{} IN ({})", column, values)
-            }
-            ("action", "not_in") | ("resource", "not_in") | ("guard_name", "not_in") | ("scope_type", "not_in") | ("organization_id", "not_in") => {
-                let values = value.as_array()
-                    .map(|arr| arr.iter()
-                        .filter_map(|v| v.as_str())
-                        .map(|s| format!("'{}'", s.replace('\'', "''")))
-                        .collect::<Vec<_>>()
-                        .join(","))
-                    .unwrap_or_default();
-                format!("{} NOT IN ({})", column, values)
-            }
-            ("created_at", "gt") | ("updated_at", "gt") => {
-                format!("{} > '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "gte") | ("updated_at", "gte") => {
-                format!("{} >= '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "lt") | ("updated_at", "lt") => {
-                format!("{} < '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "lte") | ("updated_at", "lte") => {
-                format!("{} <= '{}'", column, value.as_str().unwrap_or(""))
-            }
-            ("created_at", "between") | ("updated_at", "between") => {
-                if let Some(range) = value.as_array() {
-                    if range.len() >= 2 {
-                        format!("{} BETWEEN '{}' AND '{}'",
-                               column,
-                               range[0].as_str().unwrap_or(""),
-                               range[1].as_str().unwrap_or(""))
-                    } else {
-                        format!("{} IS NOT NULL", column)
-                    }
-                } else {
-                    format!("{} IS NOT NULL", column)
-                }
-            }
-            (_, "is_null") => format!("{} IS NULL", column),
-            (_, "is_not_null") => format!("{} IS NOT NULL", column),
-            _ => format!("{} = '{}'", column, value.as_str().unwrap_or("").replace('\'', "''"))
-        }
-    }
-}
+impl crate::app::query_builder::Filterable for Permission {}
 
 impl crate::app::query_builder::Sortable for Permission {
     fn apply_basic_sort(column: &str, direction: &str) -> String {
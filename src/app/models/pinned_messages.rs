@@ -3,10 +3,10 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = crate::schema::pinned_messages)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct PinnedMessage {
@@ -16,7 +16,9 @@ pub struct PinnedMessage {
     pub message_id: DieselUlid,
     pub pinned_by_user_id: DieselUlid,
     pub pinned_by_device_id: DieselUlid,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub pinned_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub unpinned_at: Option<DateTime<Utc>>,
     pub is_active: bool,
 }
@@ -28,16 +30,25 @@ pub struct CreatePinnedMessage {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PinnedMessageResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub conversation_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub message_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub pinned_by_user_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub pinned_by_device_id: DieselUlid,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub pinned_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub unpinned_at: Option<DateTime<Utc>>,
     pub is_active: bool,
 }
 
+impl HasIdGenerator for PinnedMessage {}
+
 impl PinnedMessage {
     pub fn new(
         conversation_id: DieselUlid,
@@ -47,7 +58,7 @@ impl PinnedMessage {
     ) -> Self {
         let now = Utc::now();
         PinnedMessage {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             conversation_id,
             message_id,
             pinned_by_user_id,
@@ -3,10 +3,10 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = crate::schema::poll_votes)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct PollVote {
@@ -18,9 +18,13 @@ pub struct PollVote {
     pub encrypted_vote_data: String,
     pub vote_algorithm: String,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    /// Zero-based indexes of the selected options
+    pub option_indexes: Vec<Option<i32>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -28,6 +32,7 @@ pub struct CreatePollVote {
     pub poll_id: DieselUlid,
     pub encrypted_vote_data: String,
     pub vote_algorithm: String,
+    pub option_indexes: Vec<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -37,16 +42,25 @@ pub struct UpdatePollVote {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PollVoteResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub poll_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub user_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub device_id: DieselUlid,
     pub encrypted_vote_data: String,
     pub vote_algorithm: String,
+    pub option_indexes: Vec<i32>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for PollVote {}
+
 impl PollVote {
     pub fn new(
         poll_id: DieselUlid,
@@ -54,10 +68,11 @@ impl PollVote {
         device_id: DieselUlid,
         encrypted_vote_data: String,
         vote_algorithm: String,
+        option_indexes: Vec<i32>,
     ) -> Self {
         let now = Utc::now();
         Self {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             poll_id,
             user_id,
             device_id,
@@ -65,9 +80,15 @@ impl PollVote {
             vote_algorithm,
             created_at: now,
             updated_at: now,
+            option_indexes: option_indexes.into_iter().map(Some).collect(),
         }
     }
 
+    /// Selected option indexes
+    pub fn selected_options(&self) -> Vec<i32> {
+        self.option_indexes.iter().flatten().copied().collect()
+    }
+
     pub fn to_response(&self) -> PollVoteResponse {
         PollVoteResponse {
             id: self.id,
@@ -76,6 +97,7 @@ impl PollVote {
             device_id: self.device_id,
             encrypted_vote_data: self.encrypted_vote_data.clone(),
             vote_algorithm: self.vote_algorithm.clone(),
+            option_indexes: self.selected_options(),
             created_at: self.created_at,
             updated_at: self.updated_at,
         }
@@ -124,6 +146,7 @@ impl crate::app::query_builder::Queryable for PollVote {
             "device_id",
             "encrypted_vote_data",
             "vote_algorithm",
+            "option_indexes",
             "created_at",
             "updated_at",
         ]
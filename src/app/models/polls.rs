@@ -3,10 +3,10 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = crate::schema::polls)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Poll {
@@ -18,12 +18,17 @@ pub struct Poll {
     pub encrypted_options: String,
     pub allows_multiple_votes: bool,
     pub is_anonymous: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
     pub is_closed: bool,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    /// Number of options in `encrypted_options`
+    pub option_count: i32,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -32,8 +37,10 @@ pub struct CreatePoll {
     pub conversation_id: DieselUlid,
     pub encrypted_question: String,
     pub encrypted_options: String,
+    pub option_count: i32,
     pub allows_multiple_votes: bool,
     pub is_anonymous: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
 }
 
@@ -43,37 +50,49 @@ pub struct UpdatePoll {
     pub encrypted_options: Option<String>,
     pub allows_multiple_votes: Option<bool>,
     pub is_anonymous: Option<bool>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
     pub is_closed: Option<bool>,
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PollResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub message_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub conversation_id: DieselUlid,
     pub encrypted_question: String,
     pub encrypted_options: String,
+    pub option_count: i32,
     pub allows_multiple_votes: bool,
     pub is_anonymous: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub expires_at: Option<DateTime<Utc>>,
     pub is_closed: bool,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for Poll {}
+
 impl Poll {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         message_id: DieselUlid,
         conversation_id: DieselUlid,
         encrypted_question: String,
         encrypted_options: String,
+        option_count: i32,
         allows_multiple_votes: bool,
         is_anonymous: bool,
         expires_at: Option<DateTime<Utc>>,
     ) -> Self {
         let now = Utc::now();
         Self {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             message_id,
             conversation_id,
             encrypted_question,
@@ -84,6 +103,7 @@ impl Poll {
             is_closed: false,
             created_at: now,
             updated_at: now,
+            option_count,
         }
     }
 
@@ -94,6 +114,7 @@ impl Poll {
             conversation_id: self.conversation_id,
             encrypted_question: self.encrypted_question.clone(),
             encrypted_options: self.encrypted_options.clone(),
+            option_count: self.option_count,
             allows_multiple_votes: self.allows_multiple_votes,
             is_anonymous: self.is_anonymous,
             expires_at: self.expires_at,
@@ -163,6 +184,7 @@ impl crate::app::query_builder::Queryable for Poll {
             "conversation_id",
             "encrypted_question",
             "encrypted_options",
+            "option_count",
             "allows_multiple_votes",
             "is_anonymous",
             "expires_at",
@@ -1,10 +1,10 @@
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use crate::schema::prekey_bundles;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use chrono::{DateTime, Utc};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = prekey_bundles)]
 #[diesel(primary_key(id))]
 pub struct PrekeyBundle {
@@ -14,12 +14,17 @@ pub struct PrekeyBundle {
     pub prekey_id: i32,
     pub prekey_public: String,
     pub is_used: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub used_at: Option<DateTime<Utc>>,
     pub used_by_user_id: Option<DieselUlid>,
     pub used_by_device_id: Option<DieselUlid>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
+impl HasIdGenerator for PrekeyBundle {}
+
 impl PrekeyBundle {
     pub fn is_available(&self) -> bool {
         !self.is_used
@@ -35,7 +40,7 @@ impl PrekeyBundle {
     ) -> Self {
         let now = chrono::Utc::now();
         PrekeyBundle {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             device_id,
             user_id,
             prekey_id,
@@ -3,10 +3,10 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = crate::schema::scheduled_messages)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct ScheduledMessage {
@@ -16,21 +16,28 @@ pub struct ScheduledMessage {
     pub conversation_id: DieselUlid,
     pub sender_user_id: DieselUlid,
     pub sender_device_id: DieselUlid,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub scheduled_for: DateTime<Utc>,
     pub timezone: String,
     pub is_sent: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub sent_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub failed_at: Option<DateTime<Utc>>,
     pub failure_reason: Option<String>,
     pub retry_count: i32,
     pub max_retries: i32,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub next_retry_at: Option<DateTime<Utc>>,
     pub is_cancelled: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub cancelled_at: Option<DateTime<Utc>>,
     pub cancelled_by_device_id: Option<DieselUlid>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -38,6 +45,7 @@ pub struct ScheduledMessage {
 pub struct CreateScheduledMessage {
     pub message_id: DieselUlid,
     pub conversation_id: DieselUlid,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub scheduled_for: DateTime<Utc>,
     pub timezone: String,
     pub max_retries: Option<i32>,
@@ -45,31 +53,45 @@ pub struct CreateScheduledMessage {
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UpdateScheduledMessage {
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub scheduled_for: Option<DateTime<Utc>>,
     pub timezone: Option<String>,
     pub max_retries: Option<i32>,
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ScheduledMessageResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub message_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub conversation_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub sender_user_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub sender_device_id: DieselUlid,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub scheduled_for: DateTime<Utc>,
     pub timezone: String,
     pub is_sent: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub sent_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub failed_at: Option<DateTime<Utc>>,
     pub failure_reason: Option<String>,
     pub retry_count: i32,
     pub max_retries: i32,
     pub is_cancelled: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub cancelled_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for ScheduledMessage {}
+
 impl ScheduledMessage {
     pub fn new(
         message_id: DieselUlid,
@@ -82,7 +104,7 @@ impl ScheduledMessage {
     ) -> Self {
         let now = Utc::now();
         ScheduledMessage {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             message_id,
             conversation_id,
             sender_user_id,
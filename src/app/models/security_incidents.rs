@@ -3,10 +3,10 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = crate::schema::security_incidents)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct SecurityIncident {
@@ -20,12 +20,16 @@ pub struct SecurityIncident {
     pub encrypted_incident_data: Option<String>,
     pub incident_algorithm: Option<String>,
     pub is_resolved: bool,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub resolved_at: Option<DateTime<Utc>>,
     pub resolution_notes: Option<String>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
+    pub metadata: serde_json::Value,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +44,9 @@ pub enum IncidentType {
     TamperingDetected,
     ReplayAttack,
     ManInTheMiddle,
+    MultipleFailedAuth,
+    TokenReuse,
+    CertificateMismatch,
 }
 
 impl From<String> for IncidentType {
@@ -55,6 +62,9 @@ impl From<String> for IncidentType {
             "tampering_detected" => IncidentType::TamperingDetected,
             "replay_attack" => IncidentType::ReplayAttack,
             "man_in_the_middle" => IncidentType::ManInTheMiddle,
+            "multiple_failed_auth" => IncidentType::MultipleFailedAuth,
+            "token_reuse" => IncidentType::TokenReuse,
+            "certificate_mismatch" => IncidentType::CertificateMismatch,
             _ => IncidentType::UnauthorizedAccess,
         }
     }
@@ -73,6 +83,9 @@ impl From<IncidentType> for String {
             IncidentType::TamperingDetected => "tampering_detected".to_string(),
             IncidentType::ReplayAttack => "replay_attack".to_string(),
             IncidentType::ManInTheMiddle => "man_in_the_middle".to_string(),
+            IncidentType::MultipleFailedAuth => "multiple_failed_auth".to_string(),
+            IncidentType::TokenReuse => "token_reuse".to_string(),
+            IncidentType::CertificateMismatch => "certificate_mismatch".to_string(),
         }
     }
 }
@@ -118,6 +131,8 @@ pub struct CreateSecurityIncident {
     pub encrypted_incident_data: Option<String>,
     pub incident_algorithm: Option<String>,
 }
+impl HasIdGenerator for SecurityIncident {}
+
 impl SecurityIncident {
     pub fn new(
         device_id: Option<DieselUlid>,
@@ -130,7 +145,7 @@ impl SecurityIncident {
     ) -> Self {
         let now = Utc::now();
         SecurityIncident {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             device_id,
             user_id,
             conversation_id,
@@ -143,9 +158,15 @@ impl SecurityIncident {
             resolution_notes: None,
             created_at: now,
             updated_at: now,
+            metadata: serde_json::json!({}),
         }
     }
 
+    pub fn with_metadata(mut self, metadata: serde_json::Value) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
     pub fn incident_type_enum(&self) -> IncidentType {
         self.incident_type.clone().into()
     }
@@ -219,6 +240,7 @@ impl crate::app::query_builder::Queryable for SecurityIncident {
             "is_resolved",
             "resolved_at",
             "resolution_notes",
+            "metadata",
             "created_at",
             "updated_at",
         ]
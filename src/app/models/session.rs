@@ -112,37 +112,7 @@ impl crate::app::query_builder::Queryable for SessionModel {
     }
 }
 
-impl crate::app::query_builder::Filterable for SessionModel {
-    fn apply_basic_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match (column, operator) {
-            ("id", op) => {
-                format!("{} {} '{}'", column, op, value.as_str().unwrap_or(""))
-            }
-            ("user_id", op) if op != "is_null" && op != "is_not_null" => {
-                format!("{} {} '{}'", column, op, value.as_str().unwrap_or(""))
-            }
-            ("ip_address", "contains") | ("user_agent", "contains") => {
-                format!("LOWER({}) LIKE LOWER('%{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("ip_address", "starts_with") | ("user_agent", "starts_with") => {
-                format!("LOWER({}) LIKE LOWER('{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("ip_address", "ends_with") | ("user_agent", "ends_with") => {
-                format!("LOWER({}) LIKE LOWER('%{}')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-            }
-            ("user_id", "is_null") => {
-                format!("{} IS NULL", column)
-            }
-            ("user_id", "is_not_null") => {
-                format!("{} IS NOT NULL", column)
-            }
-            ("last_activity", op) => {
-                format!("{} {} {}", column, op, value.as_i64().unwrap_or(0))
-            }
-            _ => format!("{} = '{}'", column, value.as_str().unwrap_or(""))
-        }
-    }
-}
+impl crate::app::query_builder::Filterable for SessionModel {}
 
 impl crate::app::query_builder::Sortable for SessionModel {
     fn apply_basic_sort(column: &str, direction: &str) -> String {
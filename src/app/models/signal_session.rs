@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use crate::schema::signal_sessions;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use chrono::{DateTime, Utc};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, AsChangeset)]
@@ -16,18 +16,25 @@ pub struct SignalSession {
     pub session_algorithm: String,
     pub session_version: i32,
     pub is_active: bool,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub established_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub last_used_at: DateTime<Utc>,
     pub encrypted_send_counter: Option<String>,
     pub encrypted_receive_counter: Option<String>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     pub backup_encrypted_state: Option<String>,
     pub recovery_key_hash: Option<String>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub backup_created_at: Option<DateTime<Utc>>,
     pub backup_device_id: Option<DieselUlid>,
     pub is_recoverable: bool,
 }
+impl HasIdGenerator for SignalSession {}
+
 impl SignalSession {
     pub fn needs_backup(&self) -> bool {
         self.backup_encrypted_state.is_none() && self.is_active
@@ -48,7 +55,7 @@ impl SignalSession {
     ) -> Self {
         let now = Utc::now();
         SignalSession {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             local_device_id,
             remote_device_id,
             conversation_id,
@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use crate::app::models::DieselUlid;
+use crate::app::models::{DieselUlid, HasIdGenerator};
 use crate::app::models::{HasModelType, activity_log::HasId};
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, QueryableByName, Insertable)]
@@ -23,10 +23,13 @@ pub struct SysModelHasPermission {
     #[schema(example = "01ARZ3NDEKTSV4RRFFQ69G5FAV")]
     pub scope_id: Option<DieselUlid>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
     #[schema(example = "01ARZ3NDEKTSV4RRFFQ69G5FAV")]
     pub created_by_id: DieselUlid,
@@ -56,13 +59,19 @@ pub struct UpdateSysModelHasPermission {
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct SysModelHasPermissionResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub model_type: String,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub model_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub permission_id: DieselUlid,
     pub scope_type: Option<String>,
+    #[serde(with = "crate::app::models::diesel_ulid::response::option")]
     pub scope_id: Option<DieselUlid>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -134,12 +143,14 @@ impl crate::app::query_builder::Queryable for SysModelHasPermission {
     }
 }
 
+impl HasIdGenerator for SysModelHasPermission {}
+
 impl SysModelHasPermission {
     pub fn new(model_type: String, model_id: DieselUlid, permission_id: DieselUlid, scope_type: Option<String>, scope_id: Option<DieselUlid>, created_by_id: DieselUlid) -> Self {
         let now = Utc::now();
 
         SysModelHasPermission {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             model_type,
             model_id,
             permission_id,
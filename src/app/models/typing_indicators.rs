@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Identifiable, AsChangeset)]
@@ -16,11 +16,15 @@ pub struct TypingIndicator {
     pub user_id: DieselUlid,
     pub device_id: DieselUlid,
     pub is_typing: bool,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub started_typing_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp")]
     pub expires_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -36,17 +40,27 @@ pub struct UpdateTypingIndicator {
 }
 #[derive(Debug, Serialize, ToSchema)]
 pub struct TypingIndicatorResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub conversation_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub user_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub device_id: DieselUlid,
     pub is_typing: bool,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub started_typing_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub expires_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for TypingIndicator {}
+
 impl TypingIndicator {
     pub fn new(
         conversation_id: DieselUlid,
@@ -58,7 +72,7 @@ impl TypingIndicator {
         let expires_at = now + chrono::Duration::seconds(30); // Typing indicators expire after 30 seconds
 
         TypingIndicator {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             conversation_id,
             user_id,
             device_id,
@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 use crate::app::query_builder::SortDirection;
-use super::{HasModelType, HasRoles, DieselUlid};
+use super::{HasModelType, HasRoles, DieselUlid, HasIdGenerator};
 use crate::app::models::activity_log::HasId;
 
 /// User model representing a registered user
@@ -23,6 +23,7 @@ pub struct User {
     pub email: String,
     /// Email verification timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub email_verified_at: Option<DateTime<Utc>>,
     /// Username (optional)
     pub username: Option<String>,
@@ -34,10 +35,12 @@ pub struct User {
     /// Password reset token
     pub password_reset_token: Option<String>,
     /// Password reset token expiration
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub password_reset_expires_at: Option<DateTime<Utc>>,
     /// JWT refresh token for authentication
     pub refresh_token: Option<String>,
     /// Refresh token expiration timestamp
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub refresh_token_expires_at: Option<DateTime<Utc>>,
     /// User avatar URL
     pub avatar: Option<String>,
@@ -50,26 +53,33 @@ pub struct User {
     pub google_id: Option<String>,
     /// Last successful login timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub last_login_at: Option<DateTime<Utc>>,
     /// Last seen timestamp
+    #[serde(with = "crate::app::utils::timestamp")]
     pub last_seen_at: DateTime<Utc>,
     /// User's locale preference
     pub locale: Option<String>,
     /// Account lock expiration timestamp
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub locked_until: Option<DateTime<Utc>>,
     /// User's phone number
     pub phone_number: Option<String>,
     /// Phone verification timestamp
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub phone_verified_at: Option<DateTime<Utc>>,
     /// User's timezone
     pub zoneinfo: Option<String>,
     /// User creation timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     /// Soft delete timestamp
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
     /// User who created this record
     pub created_by_id: DieselUlid,
@@ -80,6 +90,7 @@ pub struct User {
     /// Identity public key for encryption
     pub identity_public_key: Option<String>,
     /// Identity key creation timestamp
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub identity_key_created_at: Option<DateTime<Utc>>,
     /// MFA enabled flag
     pub mfa_enabled: bool,
@@ -172,15 +183,22 @@ pub struct RefreshTokenRequest {
 /// User response payload for API endpoints (excludes sensitive fields)
 #[derive(Debug, Serialize, ToSchema)]
 pub struct UserResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
     pub name: String,
     pub email: String,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub email_verified_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub last_login_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl HasIdGenerator for User {}
+
 impl User {
     /// Get phone number (convenience method)
     pub fn phone(&self) -> Option<&String> {
@@ -191,7 +209,7 @@ impl User {
         let creator_id = DieselUlid::from_string(created_by.trim())
             .expect("Invalid created_by ULID provided to User::new()");
         Self {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             name,
             email,
             email_verified_at: None,
@@ -294,7 +312,7 @@ impl User {
         let now = Utc::now();
         let created_by_ulid = created_by;
         User {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             name,
             email,
             email_verified_at: None,
@@ -442,15 +460,7 @@ impl crate::app::query_builder::Queryable for User {
 }
 
 // Implement the enhanced filtering trait
-impl crate::app::query_builder::Filterable for User {
-    fn apply_basic_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match operator {
-            "=" => format!("{} = {}", column, Self::format_filter_value(value)),
-            "!=" => format!("{} != {}", column, Self::format_filter_value(value)),
-            _ => format!("{} {} {}", column, operator, Self::format_filter_value(value))
-        }
-    }
-}
+impl crate::app::query_builder::Filterable for User {}
 
 // Implement the enhanced sorting trait
 impl crate::app::query_builder::Sortable for User {
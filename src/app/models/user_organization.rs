@@ -1,4 +1,4 @@
-use super::DieselUlid;
+use super::{DieselUlid, HasIdGenerator};
 use serde::{Deserialize, Serialize};
 use diesel::prelude::*;
 use crate::database::DbPool;
@@ -40,17 +40,22 @@ pub struct UserOrganization {
     pub is_active: bool,
     /// When the employment started
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub started_at: DateTime<Utc>,
     /// When the employment ended (if applicable)
     #[schema(example = "2024-01-01T00:00:00Z")]
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub ended_at: Option<DateTime<Utc>>,
     /// Creation timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
     #[schema(example = "2023-01-01T00:00:00Z")]
+    #[serde(with = "crate::app::utils::timestamp")]
     pub updated_at: DateTime<Utc>,
     /// Soft delete timestamp
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub deleted_at: Option<DateTime<Utc>>,
     /// User who created this relationship
     pub created_by_id: DieselUlid,
@@ -66,6 +71,7 @@ pub struct CreateUserOrganization {
     pub user_id: DieselUlid,
     pub organization_id: DieselUlid,
     pub organization_position_id: DieselUlid,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub started_at: Option<DateTime<Utc>>,
 }
 
@@ -75,34 +81,50 @@ pub struct UpdateUserOrganization {
     pub organization_id: Option<DieselUlid>,
     pub organization_position_id: Option<DieselUlid>,
     pub is_active: Option<bool>,
+    #[serde(default, with = "crate::app::utils::timestamp::option")]
     pub started_at: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "crate::app::utils::timestamp::nullable")]
     pub ended_at: Option<Option<DateTime<Utc>>>,
 }
 
 /// User organization response payload for API endpoints
 #[derive(Debug, Serialize, ToSchema)]
 pub struct UserOrganizationResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub user_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub organization_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub organization_position_id: DieselUlid,
     pub is_active: bool,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub started_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub ended_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::app::utils::timestamp::response")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::app::utils::timestamp::response::option")]
     pub deleted_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub created_by_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
     pub updated_by_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response::option")]
     pub deleted_by_id: Option<DieselUlid>,
 }
 
 
+impl HasIdGenerator for UserOrganization {}
+
 impl UserOrganization {
     pub fn new(user_id: String, organization_id: String, organization_position_id: String, started_at: Option<DateTime<Utc>>, created_by_id: String, updated_by_id: String) -> Self {
         let now = Utc::now();
         UserOrganization {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             user_id: DieselUlid(Ulid::from_string(&user_id).unwrap()),
             organization_id: DieselUlid(Ulid::from_string(&organization_id).unwrap()),
             organization_position_id: DieselUlid(Ulid::from_string(&organization_position_id).unwrap()),
@@ -211,7 +233,7 @@ impl UserOrganization {
         let now = Utc::now();
 
         let new_role = SysModelHasRole {
-            id: DieselUlid::new(),
+            id: Self::new_id(),
             model_type: "UserOrganization".to_string(),
             model_id: DieselUlid::from_string(&user_organization_id).unwrap(),
             role_id: DieselUlid::from_string(&role_id).unwrap(),
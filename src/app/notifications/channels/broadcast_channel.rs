@@ -28,6 +28,7 @@ impl Channel for BroadcastChannel {
             event: notification.notification_type().to_string(),
             data: broadcast_message.data,
             timestamp: chrono::Utc::now(),
+            id: None,
         };
 
         // Get the global WebSocket manager and broadcast the message
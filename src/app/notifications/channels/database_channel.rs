@@ -23,7 +23,7 @@ impl DatabaseChannel {
     }
 
     /// Detect the notifiable type using multiple strategies for robust type detection
-    fn detect_notifiable_type(notifiable: &dyn Notifiable) -> String {
+    pub fn detect_notifiable_type(notifiable: &dyn Notifiable) -> String {
         // Strategy 1: Use std::any::type_name if available through as_any
         if let Some(any_ref) = notifiable.as_any() {
             let type_name = std::any::type_name_of_val(any_ref);
@@ -11,23 +11,9 @@ impl MailChannel {
     pub fn new() -> Self {
         Self
     }
-}
-
-#[async_trait]
-impl Channel for MailChannel {
-    async fn send(&self, notification: &dyn Notification, notifiable: &dyn Notifiable) -> Result<()> {
-        // Get email address for the notifiable entity
-        let email_address = match notifiable.route_notification_for(&NotificationChannel::Mail).await {
-            Some(email) => email,
-            None => {
-                tracing::warn!("No email address found for notifiable entity: {}", notifiable.get_key());
-                return Ok(());
-            }
-        };
-
-        // Get mail message from notification
-        let old_mail_message = notification.to_mail(notifiable)?;
 
+    /// Convert a notification's mail message to the mail system format
+    pub fn to_mail_system_message(old_mail_message: crate::app::notifications::notification::MailMessage) -> MailMessage {
         // Convert to new mail system format
         let new_content = match old_mail_message.content {
             crate::app::notifications::notification::MailContent::Text(text) => {
@@ -64,6 +50,25 @@ impl Channel for MailChannel {
             );
         }
 
+        new_message
+    }
+}
+
+#[async_trait]
+impl Channel for MailChannel {
+    async fn send(&self, notification: &dyn Notification, notifiable: &dyn Notifiable) -> Result<()> {
+        // Get email address for the notifiable entity
+        let email_address = match notifiable.route_notification_for(&NotificationChannel::Mail).await {
+            Some(email) => email,
+            None => {
+                tracing::warn!("No email address found for notifiable entity: {}", notifiable.get_key());
+                return Ok(());
+            }
+        };
+
+        // Get mail message from notification
+        let new_message = Self::to_mail_system_message(notification.to_mail(notifiable)?);
+
         // Send using the mail manager
         let manager = mail_manager().await;
         let manager = manager.read().await;
@@ -19,6 +19,12 @@ pub trait Channel: Send + Sync {
     fn channel_type(&self) -> NotificationChannel;
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum ChannelError {
+    #[error("Notification '{notification}' requires the {channel} channel, which is not configured")]
+    NotConfigured { notification: String, channel: String },
+}
+
 /// Channel manager for routing notifications to appropriate channels
 #[derive(Debug)]
 pub struct ChannelManager {
@@ -59,6 +65,15 @@ impl ChannelManager {
             }
         };
 
+        Self::with_optional_channels(web_push_channel, sms_channel, slack_channel)
+    }
+
+    /// Build a manager with the given optional channels, `None` meaning unconfigured
+    pub fn with_optional_channels(
+        web_push_channel: Option<web_push_channel::WebPushChannel>,
+        sms_channel: Option<sms_channel::SmsChannel>,
+        slack_channel: Option<slack_channel::SlackChannel>,
+    ) -> Self {
         Self {
             mail_channel: mail_channel::MailChannel::new(),
             database_channel: database_channel::DatabaseChannel::new(),
@@ -69,12 +84,35 @@ impl ChannelManager {
         }
     }
 
+    /// Whether `channel` can deliver, rather than being skipped with a warning
+    pub fn is_configured(&self, channel: &NotificationChannel) -> bool {
+        match channel {
+            NotificationChannel::Mail | NotificationChannel::Database | NotificationChannel::Broadcast => true,
+            NotificationChannel::WebPush => self.web_push_channel.is_some(),
+            NotificationChannel::Sms | NotificationChannel::Vonage => self.sms_channel.is_some(),
+            NotificationChannel::Slack => self.slack_channel.as_ref().is_some_and(|slack| slack.is_configured()),
+            NotificationChannel::Custom(_) => false,
+        }
+    }
+
+    /// Send over `channels`, skipping unconfigured optional channels
+    ///
+    /// Fails before delivering anything when one of the notification's
+    /// `required_channels` is among `channels` but not configured.
     pub async fn send(
         &self,
         notification: &dyn Notification,
         notifiable: &dyn Notifiable,
         channels: Vec<NotificationChannel>,
     ) -> Result<()> {
+        let required = notification.required_channels();
+        if let Some(missing) = channels.iter().find(|channel| required.contains(channel) && !self.is_configured(channel)) {
+            return Err(ChannelError::NotConfigured {
+                notification: notification.notification_type().to_string(),
+                channel: missing.to_string(),
+            }.into());
+        }
+
         for channel in channels {
             match channel {
                 NotificationChannel::Mail => {
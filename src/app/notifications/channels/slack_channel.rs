@@ -4,6 +4,7 @@ use serde::{Serialize, Deserialize};
 use crate::app::notifications::channels::Channel;
 use crate::app::notifications::notification::{Notification, Notifiable, NotificationChannel, SlackMessage, SlackAttachment, SlackField};
 use crate::config::Config;
+use crate::app::utils::HttpClient;
 
 #[derive(Debug, Clone)]
 pub struct SlackChannel {
@@ -51,13 +52,58 @@ impl SlackChannel {
         }
     }
 
+    /// Whether a webhook is set; without one messages are only logged
+    pub fn is_configured(&self) -> bool {
+        self.webhook_url.is_some()
+    }
+
     pub fn with_default_channel(mut self, channel: String) -> Self {
         self.default_channel = channel;
         self
     }
 
-    async fn send_slack_message(&self, message: &SlackMessage) -> Result<()> {
-        let webhook_url = match &self.webhook_url {
+    /// The JSON body posted to an incoming webhook, leaving out unset fields
+    pub fn payload(message: &SlackMessage) -> serde_json::Value {
+        let mut payload = serde_json::json!({ "text": message.text });
+
+        let optional = [
+            ("username", &message.username),
+            ("channel", &message.channel),
+            ("icon_emoji", &message.icon_emoji),
+            ("icon_url", &message.icon_url),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                payload[key] = serde_json::json!(value);
+            }
+        }
+
+        if !message.attachments.is_empty() {
+            let attachments: Vec<serde_json::Value> = message.attachments.iter().map(|attachment| {
+                let mut json = serde_json::json!({});
+                for (key, value) in [("color", &attachment.color), ("title", &attachment.title), ("text", &attachment.text)] {
+                    if let Some(value) = value {
+                        json[key] = serde_json::json!(value);
+                    }
+                }
+                if !attachment.fields.is_empty() {
+                    json["fields"] = serde_json::json!(attachment.fields);
+                }
+                json
+            }).collect();
+            payload["attachments"] = serde_json::json!(attachments);
+        }
+
+        // Slack rejects an empty blocks array
+        if !message.blocks.is_empty() {
+            payload["blocks"] = serde_json::json!(message.blocks);
+        }
+
+        payload
+    }
+
+    async fn send_slack_message(&self, message: &SlackMessage, webhook_url: Option<&str>) -> Result<()> {
+        let webhook_url = match webhook_url.or(self.webhook_url.as_deref()) {
             Some(url) => url,
             None => {
                 tracing::warn!("No Slack webhook URL configured, logging message instead");
@@ -66,20 +112,19 @@ impl SlackChannel {
             }
         };
 
-        let client = reqwest::Client::new();
+        let client = HttpClient::shared()?;
 
         let response = client
-            .post(webhook_url)
-            .json(&message)
-            .send()
+            .send(client.post(webhook_url).json(&Self::payload(message)))
             .await?;
 
         if response.status().is_success() {
             tracing::info!("Slack message sent successfully");
             Ok(())
         } else {
-            let error_text = response.text().await?;
-            Err(anyhow::anyhow!("Slack webhook failed: {}", error_text))
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            Err(anyhow::anyhow!("Slack webhook failed ({}): {}", status, error_text))
         }
     }
 
@@ -127,7 +172,6 @@ impl SlackChannel {
             .field(SlackField::new("Time".to_string(), chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string(), true));
 
         SlackMessage::new(format!("*{}*", title))
-            .channel(self.default_channel.clone())
             .username("Notification Bot".to_string())
             .icon_emoji(":bell:".to_string())
             .attachment(attachment)
@@ -147,9 +191,20 @@ impl Default for SlackChannel {
 #[async_trait]
 impl Channel for SlackChannel {
     async fn send(&self, notification: &dyn Notification, notifiable: &dyn Notifiable) -> Result<()> {
-        // Get Slack channel for the notifiable entity (optional)
-        let target_channel = notifiable.route_notification_for(&NotificationChannel::Slack).await
-            .unwrap_or_else(|| self.default_channel.clone());
+        // The route is either a channel posted to through the configured
+        // webhook or the notifiable's own webhook URL
+        let route = match notifiable.route_notification_for(&NotificationChannel::Slack).await {
+            Some(route) => route,
+            None => {
+                tracing::warn!("No Slack route found for notifiable entity: {}", notifiable.get_key());
+                return Ok(());
+            }
+        };
+        let (webhook_url, target_channel) = if route.starts_with("https://") || route.starts_with("http://") {
+            (Some(route.as_str()), None)
+        } else {
+            (None, Some(route.clone()))
+        };
 
         // Try to get the slack message from the notification first
         let mut slack_message = match notification.to_slack(notifiable) {
@@ -163,11 +218,12 @@ impl Channel for SlackChannel {
 
         // Override channel if not set
         if slack_message.channel.is_none() {
-            slack_message.channel = Some(target_channel.clone());
+            slack_message.channel = target_channel;
         }
+        let target_channel = slack_message.channel.clone().unwrap_or_else(|| "webhook default".to_string());
 
         // Send the message
-        match self.send_slack_message(&slack_message).await {
+        match self.send_slack_message(&slack_message, webhook_url).await {
             Ok(()) => {
                 tracing::info!(
                     "Slack notification sent successfully to channel: {} (type: {})",
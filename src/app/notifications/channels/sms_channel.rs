@@ -4,6 +4,7 @@ use serde::{Serialize, Deserialize};
 use crate::app::notifications::channels::Channel;
 use crate::app::notifications::notification::{Notification, Notifiable, NotificationChannel};
 use crate::config::Config;
+use crate::app::utils::HttpClient;
 
 #[derive(Debug, Clone)]
 pub struct SmsChannel {
@@ -84,7 +85,7 @@ impl SmsChannel {
     async fn send_twilio_sms(&self, message: SmsMessage, account_sid: &str, auth_token: &str) -> Result<()> {
         tracing::info!("Sending SMS via Twilio to {}: {}", message.to, message.message);
 
-        let client = reqwest::Client::new();
+        let client = HttpClient::shared()?;
         let url = format!("https://api.twilio.com/2010-04-01/Accounts/{}/Messages.json", account_sid);
 
         let params = [
@@ -94,10 +95,7 @@ impl SmsChannel {
         ];
 
         let response = client
-            .post(&url)
-            .basic_auth(account_sid, Some(auth_token))
-            .form(&params)
-            .send()
+            .send(client.post(&url).basic_auth(account_sid, Some(auth_token)).form(&params))
             .await?;
 
         if response.status().is_success() {
@@ -112,7 +110,7 @@ impl SmsChannel {
     async fn send_nexmo_sms(&self, message: SmsMessage, api_key: &str, api_secret: &str) -> Result<()> {
         tracing::info!("Sending SMS via Nexmo to {}: {}", message.to, message.message);
 
-        let client = reqwest::Client::new();
+        let client = HttpClient::shared()?;
         let url = "https://rest.nexmo.com/sms/json";
 
         let payload = serde_json::json!({
@@ -124,9 +122,7 @@ impl SmsChannel {
         });
 
         let response = client
-            .post(url)
-            .json(&payload)
-            .send()
+            .send(client.post(url).json(&payload))
             .await?;
 
         if response.status().is_success() {
@@ -6,14 +6,26 @@ use web_push::{WebPushMessageBuilder, SubscriptionInfo};
 use crate::app::notifications::channels::Channel;
 use crate::app::notifications::notification::{Notification, Notifiable, NotificationChannel};
 use crate::config::Config;
-use crate::app::utils::vapid::VapidTokenGenerator;
+use crate::app::utils::vapid::{VapidKeyRing, VapidTokenGenerator};
 use crate::app::utils::web_push_metrics;
+use crate::app::utils::HttpClient;
 use diesel::prelude::*;
-use crate::schema::push_subscriptions;
+use crate::schema::{device_push_tokens, push_subscriptions};
 
 #[derive(Debug)]
 pub struct WebPushChannel {
-    vapid_token_generator: VapidTokenGenerator,
+    vapid_keys: VapidKeyRing,
+}
+
+/// Push service responses that callers need to tell apart from transient failures
+#[derive(Debug, thiserror::Error)]
+pub enum WebPushSendError {
+    /// The push service answered 404/410; the subscription has been pruned
+    #[error("Push subscription expired and removed")]
+    SubscriptionGone,
+    /// The push service rejected the VAPID signature
+    #[error("Push authentication failed")]
+    AuthenticationFailed,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,17 +73,36 @@ impl WebPushChannel {
         let vapid_public_key = config.webpush.vapid_public_key
             .ok_or_else(|| anyhow::anyhow!("VAPID public key not configured"))?;
 
-        let vapid_token_generator = VapidTokenGenerator::new(
+        let mut vapid_keys = VapidKeyRing::new(VapidTokenGenerator::new(
             vapid_private_key,
             vapid_public_key,
-            config.webpush.vapid_subject,
-        );
+            config.webpush.vapid_subject.clone(),
+        ));
+
+        // Keep signing with the previous key pair while a rotation is in progress
+        if config.webpush.is_rotation_in_grace_period() {
+            if let (Some(private_key), Some(public_key), Some(grace_until)) = (
+                config.webpush.vapid_previous_private_key,
+                config.webpush.vapid_previous_public_key,
+                config.webpush.vapid_rotation_grace_until,
+            ) {
+                vapid_keys = vapid_keys.with_previous(
+                    VapidTokenGenerator::new(private_key, public_key, config.webpush.vapid_subject),
+                    grace_until,
+                );
+            }
+        }
 
+        Self::with_key_ring(vapid_keys)
+    }
+
+    /// Create a channel from an explicit VAPID key ring
+    pub fn with_key_ring(vapid_keys: VapidKeyRing) -> Result<Self> {
         // Validate the VAPID configuration
-        vapid_token_generator.validate_keys()?;
+        vapid_keys.validate_keys()?;
 
         Ok(Self {
-            vapid_token_generator,
+            vapid_keys,
         })
     }
 
@@ -181,6 +212,39 @@ impl WebPushChannel {
         Ok(())
     }
 
+    /// Delete a subscription the push service reported as gone (404/410).
+    ///
+    /// The endpoint is also removed from `device_push_tokens` so device-level
+    /// registrations for the same browser subscription do not linger.
+    pub async fn prune_subscription(subscription: &PushSubscription) -> Result<usize> {
+        let pool = Self::get_database_pool()?;
+        let mut conn = pool.get()?;
+
+        let removed = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let subscriptions = diesel::delete(
+                push_subscriptions::table
+                    .filter(push_subscriptions::id.eq(&subscription.id))
+            )
+            .execute(conn)?;
+
+            let device_tokens = diesel::delete(
+                device_push_tokens::table
+                    .filter(device_push_tokens::platform.eq("webpush"))
+                    .filter(device_push_tokens::endpoint.eq(&subscription.endpoint))
+            )
+            .execute(conn)?;
+
+            Ok(subscriptions + device_tokens)
+        })?;
+
+        if removed > 0 {
+            web_push_metrics::record_subscription_pruned().await;
+            tracing::info!("Pruned gone push subscription: {}", subscription.endpoint);
+        }
+
+        Ok(removed)
+    }
+
     /// Clean up old and inactive subscriptions
     pub async fn cleanup_inactive_subscriptions(days_inactive: i32) -> Result<u64> {
         let pool = Self::get_database_pool()?;
@@ -196,6 +260,24 @@ impl WebPushChannel {
         Ok(deleted_count as u64)
     }
 
+    /// Remove web push device tokens that were deactivated or have expired
+    pub async fn prune_inactive_device_tokens() -> Result<u64> {
+        let pool = Self::get_database_pool()?;
+        let mut conn = pool.get()?;
+
+        let deleted_count = diesel::delete(
+            device_push_tokens::table
+                .filter(device_push_tokens::platform.eq("webpush"))
+                .filter(
+                    device_push_tokens::is_active.eq(false)
+                        .or(device_push_tokens::expires_at.lt(chrono::Utc::now()))
+                )
+        ).execute(&mut conn)?;
+
+        tracing::info!("Pruned {} inactive web push device tokens", deleted_count);
+        Ok(deleted_count as u64)
+    }
+
     /// Mark subscription as failed for future cleanup
     pub async fn mark_subscription_failed(subscription_id: &str) -> Result<()> {
         use crate::database::connection::get_connection;
@@ -249,9 +331,9 @@ impl WebPushChannel {
         builder.set_payload(web_push::ContentEncoding::Aes128Gcm, test_payload_str.as_bytes());
 
         let web_push_message = builder.build()?;
-        let client = reqwest::Client::new();
+        let client = HttpClient::shared()?;
 
-        let response = client
+        let request = client
             .post(&subscription.endpoint)
             .header("TTL", web_push_message.ttl.to_string())
             .header("Content-Type", "application/octet-stream")
@@ -260,9 +342,8 @@ impl WebPushChannel {
                 payload.content
             } else {
                 Vec::new()
-            })
-            .send()
-            .await?;
+            });
+        let response = client.send(request).await?;
 
         Ok(response.status().is_success())
     }
@@ -297,6 +378,11 @@ impl WebPushChannel {
                     return Ok(());
                 }
                 Err(e) => {
+                    // A pruned subscription will never succeed, so don't retry it
+                    if matches!(e.downcast_ref::<WebPushSendError>(), Some(WebPushSendError::SubscriptionGone)) {
+                        return Err(e);
+                    }
+
                     last_error = Some(e);
 
                     if attempt < max_retries {
@@ -335,11 +421,33 @@ impl WebPushChannel {
         Err(final_error)
     }
 
-    /// Single attempt to send notification
+    /// Single attempt to send notification, trying every accepted VAPID key
     async fn attempt_send_notification(
         &self,
         subscription: &PushSubscription,
         message: &WebPushMessage,
+    ) -> Result<()> {
+        let mut result = Err(anyhow::anyhow!("No VAPID keys configured"));
+
+        for vapid_token_generator in self.vapid_keys.active_generators() {
+            result = self.send_signed_notification(subscription, message, vapid_token_generator).await;
+
+            // Subscriptions created before a key rotation only accept the old key
+            match &result {
+                Err(e) if matches!(e.downcast_ref::<WebPushSendError>(), Some(WebPushSendError::AuthenticationFailed)) => continue,
+                _ => break,
+            }
+        }
+
+        result
+    }
+
+    /// Send a notification signed with a single VAPID key pair
+    async fn send_signed_notification(
+        &self,
+        subscription: &PushSubscription,
+        message: &WebPushMessage,
+        vapid_token_generator: &VapidTokenGenerator,
     ) -> Result<()> {
         let subscription_info = SubscriptionInfo::new(
             &subscription.endpoint,
@@ -356,8 +464,8 @@ impl WebPushChannel {
         // but still need to use the web-push library's builder for message construction
         let web_push_message = builder.build()?;
 
-        // Create HTTP client and send the notification
-        let client = reqwest::Client::new();
+        // Send through the shared client so pooling and correlation IDs apply
+        let client = HttpClient::shared()?;
 
         // Extract headers and body from the web push message
         let mut request_builder = client.post(&subscription.endpoint);
@@ -382,7 +490,7 @@ impl WebPushChannel {
         request_builder = request_builder.header("Content-Type", "application/octet-stream");
 
         // Add Authorization header with proper VAPID JWT
-        let auth_header = self.vapid_token_generator.generate_auth_header(&subscription.endpoint)?;
+        let auth_header = vapid_token_generator.generate_auth_header(&subscription.endpoint)?;
         request_builder = request_builder.header("Authorization", auth_header);
 
         // Send the request with encrypted payload
@@ -393,9 +501,8 @@ impl WebPushChannel {
         };
 
         let request_start = std::time::Instant::now();
-        let response = request_builder
-            .body(body_data)
-            .send()
+        let response = client
+            .send(request_builder.body(body_data))
             .await?;
 
         let request_duration = request_start.elapsed();
@@ -432,20 +539,20 @@ impl WebPushChannel {
                     subscription.endpoint,
                     response_status
                 );
-                Err(anyhow::anyhow!("Push authentication failed"))
+                Err(WebPushSendError::AuthenticationFailed.into())
             }
-            410 => {
-                web_push_metrics::record_failure("subscription_expired", Some(410)).await;
+            404 | 410 => {
+                web_push_metrics::record_failure("subscription_expired", Some(response_status.as_u16())).await;
 
                 tracing::warn!(
                     "Push subscription expired: {}",
                     subscription.endpoint
                 );
                 // Remove expired subscription from database
-                if let Err(e) = Self::remove_expired_subscription(&subscription.id).await {
-                    tracing::error!("Failed to remove expired subscription {}: {}", subscription.id, e);
+                if let Err(e) = Self::prune_subscription(subscription).await {
+                    tracing::error!("Failed to prune expired subscription {}: {}", subscription.id, e);
                 }
-                Err(anyhow::anyhow!("Push subscription expired and removed"))
+                Err(WebPushSendError::SubscriptionGone.into())
             }
             413 => {
                 web_push_metrics::record_failure("payload_too_large", Some(413)).await;
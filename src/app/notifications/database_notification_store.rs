@@ -0,0 +1,121 @@
+use anyhow::Result;
+use chrono::Utc;
+use diesel::prelude::*;
+use crate::app::models::notification::Notification as NotificationModel;
+use crate::app::notifications::notification::DatabaseNotification;
+use crate::database::DbPool;
+use crate::schema::notifications;
+
+/// Read state of the notifications stored by the database channel
+///
+/// Notifications are looked up by `notifiable_id`, the `Notifiable::get_key`
+/// of the recipient. Reading a notification sets its `read_at`; rows are
+/// never deleted here.
+#[derive(Clone)]
+pub struct DatabaseNotificationStore {
+    pool: DbPool,
+}
+
+impl DatabaseNotificationStore {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    pub fn find(&self, id: &str) -> Result<Option<DatabaseNotification>> {
+        let mut conn = self.pool.get()?;
+
+        let notification = notifications::table
+            .filter(notifications::id.eq(id))
+            .select(NotificationModel::as_select())
+            .first::<NotificationModel>(&mut conn)
+            .optional()?;
+
+        Ok(notification.map(DatabaseNotification::from))
+    }
+
+    /// Unread notifications for `notifiable_id`, newest first
+    pub fn unread(&self, notifiable_id: &str) -> Result<Vec<DatabaseNotification>> {
+        let mut conn = self.pool.get()?;
+
+        let unread = notifications::table
+            .filter(notifications::notifiable_id.eq(notifiable_id))
+            .filter(notifications::read_at.is_null())
+            .order(notifications::created_at.desc())
+            .select(NotificationModel::as_select())
+            .load::<NotificationModel>(&mut conn)?;
+
+        Ok(unread.into_iter().map(DatabaseNotification::from).collect())
+    }
+
+    pub fn unread_count(&self, notifiable_id: &str) -> Result<i64> {
+        let mut conn = self.pool.get()?;
+
+        let count = notifications::table
+            .filter(notifications::notifiable_id.eq(notifiable_id))
+            .filter(notifications::read_at.is_null())
+            .count()
+            .get_result(&mut conn)?;
+
+        Ok(count)
+    }
+
+    /// Mark one notification as read, returning whether it was unread
+    pub fn mark_as_read(&self, id: &str) -> Result<bool> {
+        let mut conn = self.pool.get()?;
+
+        let updated = diesel::update(
+            notifications::table
+                .filter(notifications::id.eq(id))
+                .filter(notifications::read_at.is_null())
+        )
+        .set(notifications::read_at.eq(Some(Utc::now())))
+        .execute(&mut conn)?;
+
+        Ok(updated > 0)
+    }
+
+    /// Clear `read_at` so the notification shows as unread again, returning whether it was read
+    pub fn mark_as_unread(&self, id: &str) -> Result<bool> {
+        let mut conn = self.pool.get()?;
+
+        let updated = diesel::update(
+            notifications::table
+                .filter(notifications::id.eq(id))
+                .filter(notifications::read_at.is_not_null())
+        )
+        .set(notifications::read_at.eq(None::<chrono::DateTime<Utc>>))
+        .execute(&mut conn)?;
+
+        Ok(updated > 0)
+    }
+
+    /// Mark every unread notification for `notifiable_id` as read, returning how many were marked
+    pub fn mark_all_as_read(&self, notifiable_id: &str) -> Result<usize> {
+        let mut conn = self.pool.get()?;
+
+        let updated = diesel::update(
+            notifications::table
+                .filter(notifications::notifiable_id.eq(notifiable_id))
+                .filter(notifications::read_at.is_null())
+        )
+        .set(notifications::read_at.eq(Some(Utc::now())))
+        .execute(&mut conn)?;
+
+        Ok(updated)
+    }
+}
+
+impl From<NotificationModel> for DatabaseNotification {
+    fn from(notification: NotificationModel) -> Self {
+        Self {
+            id: notification.id.to_string(),
+            notifiable_type: notification.notifiable_type,
+            notifiable_id: notification.notifiable_id,
+            notification_type: notification.notification_type,
+            data: notification.data,
+            read_at: notification.read_at,
+            created_at: notification.created_at,
+            updated_at: notification.updated_at,
+        }
+    }
+}
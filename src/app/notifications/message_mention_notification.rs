@@ -0,0 +1,54 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use crate::app::notifications::{
+    Notification, Notifiable, NotificationChannel, DatabaseMessage,
+};
+
+/// Sent to a conversation participant who was mentioned in a message.
+///
+/// Message content is end-to-end encrypted, so only identifiers are included.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageMentionNotification {
+    pub message_id: String,
+    pub conversation_id: String,
+    pub sender_user_id: String,
+}
+
+impl MessageMentionNotification {
+    pub fn new(message_id: String, conversation_id: String, sender_user_id: String) -> Self {
+        Self {
+            message_id,
+            conversation_id,
+            sender_user_id,
+        }
+    }
+}
+
+#[async_trait]
+impl Notification for MessageMentionNotification {
+    fn via(&self, _notifiable: &dyn Notifiable) -> Vec<NotificationChannel> {
+        vec![
+            NotificationChannel::Database,
+            NotificationChannel::WebPush,
+        ]
+    }
+
+    fn to_database(&self, _notifiable: &dyn Notifiable) -> Result<DatabaseMessage> {
+        let data = json!({
+            "title": "New mention",
+            "message": "You were mentioned in a conversation",
+            "message_id": self.message_id,
+            "conversation_id": self.conversation_id,
+            "sender_user_id": self.sender_user_id,
+            "type": self.notification_type()
+        });
+
+        Ok(DatabaseMessage::new(data))
+    }
+
+    fn notification_type(&self) -> &'static str {
+        "MessageMentionNotification"
+    }
+}
@@ -1,18 +1,25 @@
 pub mod notification;
 pub mod channels;
 pub mod notifiable;
+pub mod database_notification_store;
+pub mod message_mention_notification;
+pub mod security_incident_notification;
+pub mod send_email_verification;
 
 // Re-export main traits and types for easier imports
 pub use notification::{
-    Notification, Notifiable, NotificationChannel,
+    Notification, Notifiable, NotificationChannel, DatabaseNotification,
     MailMessage, MailContent, DatabaseMessage, BroadcastMessage,
     SmsMessage, SlackMessage, SlackAttachment, SlackField,
     ShouldQueue, ShouldQueueAfterCommit, Queueable, HasLocalePreference,
-    NotificationFacade, notify, notify_via
+    NotificationFacade, NotificationFake, SentNotification, filter_preferred_channels, notify, notify_via, notify_many
 };
 
+pub use database_notification_store::DatabaseNotificationStore;
+
 // Re-export notification channels
 pub use channels::{
+    ChannelManager, ChannelError,
     mail_channel::MailChannel,
     database_channel::DatabaseChannel,
     broadcast_channel::BroadcastChannel,
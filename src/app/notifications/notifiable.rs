@@ -261,26 +261,9 @@ impl User {
     pub async fn prefers_channel(&self, channel: &NotificationChannel) -> bool {
         let preferences = self.notification_preferences().await;
 
-        match channel {
-            NotificationChannel::Mail => {
-                preferences.get("email_notifications").unwrap_or(&true).clone()
-            }
-            NotificationChannel::Database => {
-                preferences.get("database_notifications").unwrap_or(&true).clone()
-            }
-            NotificationChannel::Broadcast => {
-                preferences.get("broadcast_notifications").unwrap_or(&true).clone()
-            }
-            NotificationChannel::WebPush => {
-                preferences.get("web_push_notifications").unwrap_or(&true).clone()
-            }
-            NotificationChannel::Sms | NotificationChannel::Vonage => {
-                preferences.get("sms_notifications").unwrap_or(&true).clone()
-            }
-            NotificationChannel::Slack => {
-                preferences.get("slack_notifications").unwrap_or(&true).clone()
-            }
-            _ => true, // Default to allowing other channels
+        match channel.preference_key() {
+            Some(key) => preferences.get(key).copied().unwrap_or(true),
+            None => true, // Default to allowing other channels
         }
     }
 }
@@ -3,6 +3,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use chrono::{DateTime, Utc};
+use std::sync::Arc;
 
 /// Database notification structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -178,6 +179,14 @@ pub trait Notification: Send + Sync {
         vec![]
     }
 
+    /// Channels that must be configured for the notification to be delivered
+    ///
+    /// Sending over an unconfigured required channel fails instead of being
+    /// skipped with a warning, as optional channels are.
+    fn required_channels(&self) -> Vec<NotificationChannel> {
+        vec![]
+    }
+
     /// Determine if the notification should be sent
     fn should_send(&self, _notifiable: &dyn Notifiable, _channel: &NotificationChannel) -> bool {
         true
@@ -207,6 +216,13 @@ pub trait Notifiable: Send + Sync {
         HashMap::new()
     }
 
+    /// Channels this entity wants `notification` on, `None` to accept every channel it declares
+    ///
+    /// Custom channels are not affected; only the built-in channels are filtered.
+    fn preferred_channels(&self, _notification: &dyn Notification) -> Option<Vec<NotificationChannel>> {
+        None
+    }
+
     /// Get the preferred locale for notifications
     fn preferred_locale(&self) -> Option<String> {
         None
@@ -299,6 +315,19 @@ impl NotificationChannel {
         }
     }
 
+    /// Key in `Notifiable::notification_preferences` that turns this channel on or off
+    pub fn preference_key(&self) -> Option<&'static str> {
+        match self {
+            NotificationChannel::Mail => Some("email_notifications"),
+            NotificationChannel::Database => Some("database_notifications"),
+            NotificationChannel::Broadcast => Some("broadcast_notifications"),
+            NotificationChannel::WebPush => Some("web_push_notifications"),
+            NotificationChannel::Sms | NotificationChannel::Vonage => Some("sms_notifications"),
+            NotificationChannel::Slack => Some("slack_notifications"),
+            NotificationChannel::Custom(_) => None,
+        }
+    }
+
     /// Create a channel from a string
     pub fn from_string(name: &str) -> Self {
         match name.to_lowercase().as_str() {
@@ -580,98 +609,206 @@ impl NotificationFacade {
         notification_service.send(&notification, &recipient).await
     }
 
-    /// Enable notification faking for testing
-    pub async fn fake() {
-        // In a production implementation, this would enable a fake driver
-        // that captures notifications instead of sending them
-        tracing::info!("Notification faking enabled - notifications will be captured for testing");
+    /// Enable notification faking for testing (Notification::fake())
+    ///
+    /// Installs a fresh `NotificationFake`; notifications sent afterwards are
+    /// recorded per notifiable and channel instead of being delivered.
+    pub async fn fake() -> NotificationFake {
+        NotificationFake::install()
+    }
+
+    /// Disable notification faking
+    pub async fn restore() {
+        NotificationFake::uninstall();
+    }
+
+    /// Check if notifications are being faked
+    pub async fn is_faking() -> bool {
+        NotificationFake::current().is_some()
     }
 
-    /// Assert that a notification was sent
-    pub async fn assert_sent_to<N: Notifiable>(
+    /// Assert that a notification was sent to `notifiable` (Notification::assertSentTo)
+    pub async fn assert_sent_to<N: Notifiable>(notifiable: &N, notification_type: &str) -> bool {
+        match NotificationFake::current() {
+            Some(fake) => fake.assert_sent_to(notifiable, notification_type).await,
+            None => false,
+        }
+    }
+
+    /// Assert that a notification was sent to `notifiable` on a specific channel
+    pub async fn assert_sent_to_via<N: Notifiable>(
         notifiable: &N,
         notification_type: &str,
+        channel: NotificationChannel,
     ) -> bool {
-        use crate::database::connection::get_connection;
-        use crate::schema::notifications;
-        use diesel::prelude::*;
-        use chrono::{Utc, Duration};
-
-        let pool = match get_connection().await {
-            Ok(pool) => pool,
-            Err(e) => {
-                tracing::error!("Database connection failed in notification assertion: {}", e);
-                return false;
-            }
-        };
-        let mut conn = match pool.get() {
-            Ok(conn) => conn,
-            Err(e) => {
-                tracing::error!("Failed to get connection from pool: {}", e);
-                return false;
-            }
-        };
-
-        // Extract notifiable type and ID from the key
-        let key = notifiable.get_key();
-        let parts: Vec<&str> = key.split('_').collect();
-        if parts.len() >= 2 {
-            let notifiable_type = parts[0];
-            let notifiable_id = parts[1];
-
-            // Check for notifications sent in the last 5 minutes (test window)
-            let test_window_start = Utc::now() - Duration::minutes(5);
-
-            let notification_exists = notifications::table
-                .filter(notifications::notifiable_type.eq(notifiable_type))
-                .filter(notifications::notifiable_id.eq(notifiable_id))
-                .filter(notifications::type_.eq(notification_type))
-                .filter(notifications::created_at.ge(test_window_start.naive_utc()))
-                .select(notifications::id)
-                .limit(1)
-                .load::<String>(&mut conn)
-                .map(|results| !results.is_empty())
-                .unwrap_or(false);
-
-            return notification_exists;
+        match NotificationFake::current() {
+            Some(fake) => fake.assert_sent_to_via(notifiable, notification_type, &channel).await,
+            None => false,
         }
-        false
     }
 
-    /// Assert that no notifications were sent
+    /// Assert that a notification was not sent to `notifiable` (Notification::assertNotSentTo)
+    pub async fn assert_not_sent_to<N: Notifiable>(notifiable: &N, notification_type: &str) -> bool {
+        match NotificationFake::current() {
+            Some(fake) => fake.assert_not_sent_to(notifiable, notification_type).await,
+            None => false,
+        }
+    }
+
+    /// Assert that a notification was sent a specific number of times, over any channel
+    pub async fn assert_sent_times(notification_type: &str, times: usize) -> bool {
+        match NotificationFake::current() {
+            Some(fake) => fake.assert_sent_times(notification_type, times).await,
+            None => false,
+        }
+    }
+
+    /// Assert that no notifications were sent (Notification::assertNothingSent)
     pub async fn assert_nothing_sent() -> bool {
-        use crate::database::connection::get_connection;
-        use crate::schema::notifications;
-        use diesel::prelude::*;
-        use chrono::{Utc, Duration};
-
-        let pool = match get_connection().await {
-            Ok(pool) => pool,
-            Err(e) => {
-                tracing::error!("Database connection failed in notification assertion: {}", e);
-                return false;
-            }
-        };
-        let mut conn = match pool.get() {
-            Ok(conn) => conn,
-            Err(e) => {
-                tracing::error!("Failed to get connection from pool: {}", e);
-                return false;
-            }
-        };
-
-        // Check for notifications sent in the last 5 minutes (test window)
-        let test_window_start = Utc::now() - Duration::minutes(5);
-
-        let notification_count: i64 = notifications::table
-            .filter(notifications::created_at.ge(test_window_start.naive_utc()))
-            .count()
-            .get_result(&mut conn)
-            .unwrap_or(1); // Default to 1 if query fails, indicating notifications were found
-
-        let no_notifications_sent = notification_count == 0;
-        tracing::info!("Checking that no notifications were sent during test: {} notifications found", notification_count);
-        no_notifications_sent
+        match NotificationFake::current() {
+            Some(fake) => fake.assert_nothing_sent().await,
+            None => false,
+        }
+    }
+
+    /// Get all sent notifications
+    pub async fn get_sent_notifications() -> Vec<SentNotification> {
+        match NotificationFake::current() {
+            Some(fake) => fake.sent().await,
+            None => Vec::new(),
+        }
+    }
+}
+
+/// A notification recorded by `NotificationFake`, one per channel it was sent on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentNotification {
+    /// `Notifiable::get_key` of the recipient
+    pub notifiable_key: String,
+    pub notification_type: String,
+    pub channel: NotificationChannel,
+    /// `Notification::to_array`, when the notification has a database representation
+    pub data: Option<serde_json::Value>,
+}
+
+static NOTIFICATION_FAKE: std::sync::RwLock<Option<NotificationFake>> = std::sync::RwLock::new(None);
+
+/// Records notifications instead of delivering them
+///
+/// Installed by `NotificationFacade::fake`; while installed,
+/// `NotificationService` hands every notification here rather than to its
+/// channels, after working out the channels as usual.
+#[derive(Debug, Clone, Default)]
+pub struct NotificationFake {
+    sent: Arc<tokio::sync::RwLock<Vec<SentNotification>>>,
+}
+
+impl NotificationFake {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Install a fresh fake, replacing any earlier one
+    pub fn install() -> Self {
+        let fake = Self::new();
+        *NOTIFICATION_FAKE.write().unwrap_or_else(|e| e.into_inner()) = Some(fake.clone());
+        fake
+    }
+
+    /// Remove the installed fake, delivering notifications for real again
+    pub fn uninstall() {
+        *NOTIFICATION_FAKE.write().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// The installed fake, if notifications are being faked
+    pub fn current() -> Option<Self> {
+        NOTIFICATION_FAKE.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Record `notification` as sent to `notifiable` on each of `channels`
+    pub async fn record(
+        &self,
+        notification: &dyn Notification,
+        notifiable: &dyn Notifiable,
+        channels: &[NotificationChannel],
+    ) {
+        tracing::info!(
+            "Notification {} to {} was faked",
+            notification.notification_type(),
+            notifiable.get_key()
+        );
+
+        let data = notification.to_array(notifiable).ok();
+        let mut sent = self.sent.write().await;
+        for channel in channels {
+            sent.push(SentNotification {
+                notifiable_key: notifiable.get_key(),
+                notification_type: notification.notification_type().to_string(),
+                channel: channel.clone(),
+                data: data.clone(),
+            });
+        }
+    }
+
+    /// Every notification recorded since the fake was installed, in send order
+    pub async fn sent(&self) -> Vec<SentNotification> {
+        self.sent.read().await.clone()
+    }
+
+    /// Assert that a notification was sent to `notifiable` on any channel
+    pub async fn assert_sent_to(&self, notifiable: &dyn Notifiable, notification_type: &str) -> bool {
+        let key = notifiable.get_key();
+        self.sent_count(|sent| sent.notifiable_key == key && sent.notification_type == notification_type).await > 0
+    }
+
+    /// Assert that a notification was sent to `notifiable` on `channel`
+    pub async fn assert_sent_to_via(
+        &self,
+        notifiable: &dyn Notifiable,
+        notification_type: &str,
+        channel: &NotificationChannel,
+    ) -> bool {
+        let key = notifiable.get_key();
+        self.sent_count(|sent| {
+            sent.notifiable_key == key && sent.notification_type == notification_type && &sent.channel == channel
+        }).await > 0
+    }
+
+    /// Assert that a notification was not sent to `notifiable`
+    pub async fn assert_not_sent_to(&self, notifiable: &dyn Notifiable, notification_type: &str) -> bool {
+        !self.assert_sent_to(notifiable, notification_type).await
+    }
+
+    /// Assert that a notification was sent exactly `times` times, counting each channel
+    pub async fn assert_sent_times(&self, notification_type: &str, times: usize) -> bool {
+        self.sent_count(|sent| sent.notification_type == notification_type).await == times
+    }
+
+    /// Assert that no notifications were sent
+    pub async fn assert_nothing_sent(&self) -> bool {
+        self.sent.read().await.is_empty()
+    }
+
+    async fn sent_count(&self, matches: impl Fn(&SentNotification) -> bool) -> usize {
+        self.sent.read().await.iter().filter(|sent| matches(sent)).count()
+    }
+}
+
+/// Keep the channels of `channels` that `notifiable` prefers for `notification`
+///
+/// `Custom` channels always survive, so a preference list that only names
+/// built-in channels does not switch off an application's own channels.
+pub fn filter_preferred_channels(
+    notification: &dyn Notification,
+    notifiable: &dyn Notifiable,
+    channels: Vec<NotificationChannel>,
+) -> Vec<NotificationChannel> {
+    match notifiable.preferred_channels(notification) {
+        Some(preferred) => channels
+            .into_iter()
+            .filter(|channel| matches!(channel, NotificationChannel::Custom(_)) || preferred.contains(channel))
+            .collect(),
+        None => channels,
     }
 }
 
@@ -709,4 +846,23 @@ pub async fn notify_via<N: Notifiable>(
     // Use the notification service with specific channels
     let notification_service = crate::app::services::notification_service::NotificationService::new().await;
     notification_service.send_via_channels(&notification, notifiable, channels).await
+}
+
+/// Send a notification to many notifiables through queued batch jobs
+///
+/// Recipients are split into chunks of `NOTIFICATIONS_BATCH_SIZE`, and chunk
+/// jobs are staggered by `NOTIFICATIONS_BATCH_DELAY_SECONDS` to throttle
+/// delivery. Returns the number of jobs queued.
+pub async fn notify_many<N: Notifiable>(
+    notifiables: &[N],
+    notification: impl Notification + Send + Sync,
+) -> Result<usize> {
+    use crate::app::jobs::send_notification_batch_job::SendNotificationBatchJob;
+
+    let config = crate::config::Config::load()?.notifications;
+    let jobs = SendNotificationBatchJob::chunks(notifiables, &notification, config.batch_size).await?;
+
+    let dispatcher = crate::app::jobs::job_dispatcher().await;
+    let dispatcher = dispatcher.read().await;
+    SendNotificationBatchJob::dispatch_all(&dispatcher, &jobs, config.batch_delay_seconds).await
 }
\ No newline at end of file
@@ -0,0 +1,56 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use crate::app::models::security_incidents::SecurityIncident;
+use crate::app::notifications::{
+    Notification, Notifiable, NotificationChannel, DatabaseMessage,
+};
+
+/// Sent to administrators when a high or critical severity security incident is recorded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityIncidentNotification {
+    pub incident_id: String,
+    pub incident_type: String,
+    pub severity: String,
+    pub user_id: Option<String>,
+}
+
+impl SecurityIncidentNotification {
+    pub fn new(incident: &SecurityIncident) -> Self {
+        Self {
+            incident_id: incident.id.to_string(),
+            incident_type: incident.incident_type.clone(),
+            severity: incident.severity.clone(),
+            user_id: incident.user_id.map(|id| id.to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl Notification for SecurityIncidentNotification {
+    fn via(&self, _notifiable: &dyn Notifiable) -> Vec<NotificationChannel> {
+        vec![
+            NotificationChannel::Database,
+            NotificationChannel::WebPush,
+        ]
+    }
+
+    fn to_database(&self, _notifiable: &dyn Notifiable) -> Result<DatabaseMessage> {
+        let data = json!({
+            "title": "Security incident",
+            "message": format!("A {} severity {} incident was recorded", self.severity, self.incident_type.replace('_', " ")),
+            "incident_id": self.incident_id,
+            "incident_type": self.incident_type,
+            "severity": self.severity,
+            "user_id": self.user_id,
+            "type": self.notification_type()
+        });
+
+        Ok(DatabaseMessage::new(data))
+    }
+
+    fn notification_type(&self) -> &'static str {
+        "SecurityIncidentNotification"
+    }
+}
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::app::models::user::User;
+use crate::app::notifications::{
+    Notification, Notifiable, NotificationChannel, MailMessage, MailContent,
+};
+use crate::app::utils::UrlSigner;
+
+/// Sent after registration with a signed link that confirms the user's email address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendEmailVerification {
+    pub email: String,
+    pub name: String,
+    pub verification_url: String,
+    pub expire_minutes: i64,
+}
+
+impl SendEmailVerification {
+    pub fn new(user: &User, signer: &UrlSigner, app_url: &str, expire_minutes: i64) -> Self {
+        Self {
+            email: user.email.clone(),
+            name: user.name.clone(),
+            verification_url: Self::verification_url(user, signer, app_url, expire_minutes),
+            expire_minutes,
+        }
+    }
+
+    /// Absolute, temporary signed URL for `GET /email/verify/{id}`
+    ///
+    /// A hash of the email is part of the signed query, so a link stops
+    /// working if the user changes their address before following it.
+    pub fn verification_url(user: &User, signer: &UrlSigner, app_url: &str, expire_minutes: i64) -> String {
+        let path = format!("/email/verify/{}", user.id);
+        let hash = Self::email_hash(&user.email);
+        let signed = signer.sign_temporary(
+            &path,
+            &[("hash", &hash)],
+            Utc::now() + Duration::minutes(expire_minutes),
+        );
+        format!("{}{}", app_url.trim_end_matches('/'), signed)
+    }
+
+    /// Hash of an email address as carried in the verification link
+    pub fn email_hash(email: &str) -> String {
+        hex::encode(Sha256::digest(email.trim().to_lowercase().as_bytes()))
+    }
+}
+
+#[async_trait]
+impl Notification for SendEmailVerification {
+    fn via(&self, _notifiable: &dyn Notifiable) -> Vec<NotificationChannel> {
+        vec![NotificationChannel::Mail]
+    }
+
+    fn to_mail(&self, _notifiable: &dyn Notifiable) -> Result<MailMessage> {
+        let content = format!(
+            r#"# Verify Your Email Address
+
+Hello **{}**,
+
+Please confirm your email address by following the link below:
+
+[Verify Email Address]({})
+
+This link will expire in {} minutes.
+
+If you did not create an account, no further action is required.
+"#,
+            self.name, self.verification_url, self.expire_minutes
+        );
+
+        Ok(MailMessage::new(
+            self.email.clone(),
+            "Verify Email Address".to_string(),
+            MailContent::Markdown(content),
+        ))
+    }
+
+    fn notification_type(&self) -> &'static str {
+        "SendEmailVerification"
+    }
+}
@@ -0,0 +1,2 @@
+//! Model policies, generated with `make:policy --model=...` and registered
+//! with the gate in `providers::auth_service_provider`
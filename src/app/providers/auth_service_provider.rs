@@ -0,0 +1,13 @@
+use crate::app::gate::Gate;
+
+/// Line `make:policy --model=...` inserts new registrations above
+pub const REGISTRATION_MARKER: &str = "// make:policy registrations go above this line";
+
+/// Register each model's policy with the gate, like Laravel's `AuthServiceProvider`
+///
+/// Called once from `create_app` with the global gate. A model without a
+/// registered policy is denied every ability.
+#[allow(unused_variables)]
+pub fn register_policies(gate: &Gate) {
+    // make:policy registrations go above this line
+}
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+use crate::app::events::{event_dispatcher, EventDispatcher};
+
+static GLOBAL_LISTENERS: OnceCell<()> = OnceCell::const_new();
+
+/// Line `make:listener --event=...` inserts new registrations above
+pub const REGISTRATION_MARKER: &str = "// make:listener registrations go above this line";
+
+/// Wire every app listener to its event, like Laravel's `EventServiceProvider`
+///
+/// Keep all `listen` calls here so a listener cannot be written and then
+/// forgotten.
+pub async fn register_listeners(dispatcher: &EventDispatcher) {
+    dispatcher.listen::<crate::app::events::user_registered_event::UserRegisteredEvent>(Arc::new(crate::app::listeners::send_welcome_email_listener::SendWelcomeEmailListener::new())).await;
+    // make:listener registrations go above this line
+}
+
+/// Register the app listeners on the global dispatcher, once per process
+///
+/// `create_app` can run more than once, e.g. in tests; registering again
+/// would run every listener once per call.
+pub async fn register_global_listeners() {
+    GLOBAL_LISTENERS
+        .get_or_init(|| async { register_listeners(&event_dispatcher().await).await })
+        .await;
+}
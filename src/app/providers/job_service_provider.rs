@@ -0,0 +1,20 @@
+use crate::app::jobs::job_registry::JobRegistry;
+
+/// Register every app job type a queue worker may pop
+///
+/// Jobs are pushed as their `job_name()` and a JSON payload; a job missing
+/// here is failed by the worker instead of being run.
+pub fn register_jobs(registry: &mut JobRegistry) {
+    registry
+        .register::<crate::app::jobs::send_email_job::SendEmailJob>()
+        .register::<crate::app::jobs::process_payment_job::ProcessPaymentJob>()
+        .register::<crate::app::jobs::send_notification_batch_job::SendNotificationBatchJob>()
+        .register::<crate::app::jobs::call_queued_listener_job::CallQueuedListenerJob>();
+}
+
+/// A registry holding every app job type
+pub fn job_registry() -> JobRegistry {
+    let mut registry = JobRegistry::new();
+    register_jobs(&mut registry);
+    registry
+}
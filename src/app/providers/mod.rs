@@ -0,0 +1,3 @@
+pub mod event_service_provider;
+pub mod auth_service_provider;
+pub mod job_service_provider;
\ No newline at end of file
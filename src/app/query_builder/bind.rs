@@ -0,0 +1,205 @@
+use crate::app::query_builder::{Filter, FilterOperator, FilterValue};
+use anyhow::Result;
+use diesel::pg::{Pg, PgConnection};
+use diesel::prelude::*;
+use diesel::query_builder::{BoxedSqlQuery, SqlQuery};
+use diesel::sql_types::{Nullable, Text};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Column types by table, read from the catalog the first time a table is queried
+static COLUMN_TYPES: OnceLock<RwLock<HashMap<String, Arc<HashMap<String, String>>>>> = OnceLock::new();
+
+/// Values bound to `$1`, `$2`, ... placeholders instead of being written into the SQL
+///
+/// Every value is sent as text. A placeholder compared with a column other
+/// than `text` or `varchar` is cast to that column's type, so Postgres reads
+/// the value the way it would read a quoted literal.
+#[derive(Debug, Clone, Default)]
+pub struct Bindings {
+    values: Vec<Option<String>>,
+    column_types: Arc<HashMap<String, String>>,
+}
+
+impl Bindings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bindings cast to the column types of `table`
+    pub fn for_table(table: &str, conn: &mut PgConnection) -> Result<Self> {
+        Ok(Self::with_column_types(column_types(table, conn)?))
+    }
+
+    /// Bindings cast to the given types, keyed by column name
+    pub fn with_column_types(column_types: Arc<HashMap<String, String>>) -> Self {
+        Self {
+            values: Vec::new(),
+            column_types,
+        }
+    }
+
+    /// Bind `value` and return the placeholder to compare `column` with
+    pub fn push(&mut self, column: &str, value: &Value) -> String {
+        let placeholder = self.push_text(value);
+        match self.column_types.get(column) {
+            Some(column_type) => format!("CAST({} AS {})", placeholder, column_type),
+            None => placeholder,
+        }
+    }
+
+    /// Bind `value` as text, for LIKE patterns
+    pub fn push_text(&mut self, value: &Value) -> String {
+        self.values.push(match value {
+            Value::Null => None,
+            Value::String(text) => Some(text.clone()),
+            value => Some(value.to_string()),
+        });
+        format!("${}", self.values.len())
+    }
+
+    /// Condition for `filter` with its values bound, or an empty string when it adds none
+    ///
+    /// Every filter path builds its SQL here, so no filter value is ever
+    /// written into a query.
+    pub fn filter(&mut self, filter: &Filter) -> String {
+        let column = filter.field.as_str();
+        let value = match &filter.value {
+            FilterValue::Single(value) => value.clone(),
+            FilterValue::Multiple(values) | FilterValue::Array(values) => Value::Array(values.clone()),
+            FilterValue::Range(start, end) => Value::Array(vec![start.clone(), end.clone()]),
+        };
+
+        match filter.operator {
+            FilterOperator::Eq | FilterOperator::Ne | FilterOperator::Gt | FilterOperator::Gte
+            | FilterOperator::Lt | FilterOperator::Lte => {
+                format!("{} {} {}", column, filter.operator.to_sql(), self.push(column, &value))
+            }
+            FilterOperator::Between => match value.as_array().map(Vec::as_slice) {
+                Some([start, end]) => format!("{} BETWEEN {} AND {}", column, self.push(column, start), self.push(column, end)),
+                _ => format!("{} = {}", column, self.push(column, &value)),
+            },
+            FilterOperator::In | FilterOperator::NotIn => match value.as_array() {
+                Some(values) => self.in_list(column, filter.operator == FilterOperator::NotIn, values),
+                None => String::new(),
+            },
+            FilterOperator::Like | FilterOperator::Ilike => self.like(column, &filter.operator, &value),
+            FilterOperator::Contains | FilterOperator::StartsWith | FilterOperator::EndsWith => {
+                let Some(text) = value.as_str() else {
+                    return String::new();
+                };
+                let text = escape_like(text);
+                let pattern = match filter.operator {
+                    FilterOperator::Contains => format!("%{}%", text),
+                    FilterOperator::StartsWith => format!("{}%", text),
+                    _ => format!("%{}", text),
+                };
+                self.like(column, &filter.operator, &Value::String(pattern))
+            }
+            FilterOperator::IsNull => format!("{} IS NULL", column),
+            FilterOperator::IsNotNull => format!("{} IS NOT NULL", column),
+            _ => String::new(),
+        }
+    }
+
+    /// `column IN (...)`, or `NOT IN`, with a placeholder per value
+    pub fn in_list(&mut self, column: &str, is_not_in: bool, values: &[Value]) -> String {
+        if values.is_empty() {
+            return if is_not_in { "1=1".to_string() } else { "1=0".to_string() };
+        }
+        let placeholders: Vec<String> = values.iter()
+            .map(|value| self.push(column, value))
+            .collect();
+        let operator = if is_not_in { "NOT IN" } else { "IN" };
+        format!("{} {} ({})", column, operator, placeholders.join(", "))
+    }
+
+    fn like(&mut self, column: &str, operator: &FilterOperator, value: &Value) -> String {
+        if value.is_string() {
+            format!("{} {} {}", column, operator.to_sql(), self.push_text(value))
+        } else {
+            String::new()
+        }
+    }
+
+    /// The bound values, in placeholder order
+    pub fn values(&self) -> &[Option<String>] {
+        &self.values
+    }
+
+    /// `sql` with every bound value attached
+    pub fn query<'f>(&self, sql: impl Into<String>) -> BoxedSqlQuery<'f, Pg, SqlQuery> {
+        self.values
+            .iter()
+            .fold(diesel::sql_query(sql).into_boxed::<Pg>(), |query, value| {
+                query.bind::<Nullable<Text>, _>(value.clone())
+            })
+    }
+}
+
+/// Escape LIKE wildcards so the text is matched literally
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// Types of the columns of `table` that placeholders must be cast to
+///
+/// `text` and `varchar` columns are left out, since a text value compares
+/// with them as it is. `char(n)` keeps its length so ULID keys still use
+/// their index.
+fn column_types(table: &str, conn: &mut PgConnection) -> Result<Arc<HashMap<String, String>>> {
+    let cache = COLUMN_TYPES.get_or_init(Default::default);
+    if let Some(types) = cache.read().ok().and_then(|cache| cache.get(table).cloned()) {
+        return Ok(types);
+    }
+
+    let columns: Vec<ColumnType> = diesel::sql_query(
+        "SELECT a.attname::text AS column_name, \
+         format_type(a.atttypid, CASE WHEN t.typname = 'bpchar' THEN a.atttypmod END) AS column_type \
+         FROM pg_attribute a JOIN pg_type t ON t.oid = a.atttypid \
+         WHERE a.attrelid = to_regclass($1) AND a.attnum > 0 AND NOT a.attisdropped \
+         AND t.typname NOT IN ('text', 'varchar')",
+    )
+    .bind::<Text, _>(table)
+    .load(conn)?;
+
+    let types: Arc<HashMap<String, String>> = Arc::new(
+        columns
+            .into_iter()
+            .map(|column| (column.column_name, column.column_type))
+            .collect(),
+    );
+    if let Ok(mut cache) = cache.write() {
+        cache.insert(table.to_string(), types.clone());
+    }
+    Ok(types)
+}
+
+#[derive(QueryableByName)]
+struct ColumnType {
+    #[diesel(sql_type = Text)]
+    column_name: String,
+    #[diesel(sql_type = Text)]
+    column_type: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_placeholders_number_in_order() {
+        let types = HashMap::from([("age".to_string(), "integer".to_string())]);
+        let mut bindings = Bindings::with_column_types(Arc::new(types));
+
+        assert_eq!(bindings.push("name", &json!("O'Brien")), "$1");
+        assert_eq!(bindings.push("age", &json!(18)), "CAST($2 AS integer)");
+        assert_eq!(bindings.push_text(&json!(null)), "$3");
+        assert_eq!(
+            bindings.values(),
+            &[Some("O'Brien".to_string()), Some("18".to_string()), None]
+        );
+    }
+}
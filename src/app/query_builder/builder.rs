@@ -1,6 +1,7 @@
 use crate::app::query_builder::{
-    Filter, Sort, Include, Pagination, QueryParams, Queryable,
+    Filter, FilterGroup, Sort, Include, Pagination, QueryParams, Queryable,
 };
+use crate::config::query_builder::QueryBuilderConfig;
 use anyhow::{Result};
 use std::collections::HashMap;
 
@@ -13,6 +14,8 @@ where
 {
     /// Applied filters
     filters: Vec<Filter>,
+    /// Applied AND/OR filter groups
+    filter_groups: Vec<FilterGroup>,
     /// Applied sorts
     sorts: Vec<Sort>,
     /// Applied includes
@@ -41,6 +44,7 @@ where
     pub fn new() -> Self {
         Self {
             filters: Vec::new(),
+            filter_groups: Vec::new(),
             sorts: Vec::new(),
             includes: Vec::new(),
             fields: None,
@@ -55,16 +59,34 @@ where
 
     /// Create a query builder from query parameters
     pub fn from_params(params: QueryParams) -> Result<Self> {
+        let config = QueryBuilderConfig::global();
+        params.validate_per_page::<T>(config.strict_per_page)?;
+        params.validate_includes(config.max_include_depth, config.max_includes)?;
         let mut builder = Self::new();
 
         // Apply filters
-        let filters = Filter::from_params(&params.filter);
+        let filters = if config.strict_filters {
+            params.validate_filters(&T::allowed_filters())?
+        } else {
+            params.validate_filter_operators(&T::allowed_filters())?;
+            Filter::from_params(&params.filter)
+        };
         for filter in filters {
             if T::is_filter_allowed(&filter.field) {
                 builder = builder.filter(filter);
             }
         }
 
+        // Apply filter groups
+        let filter_groups = if config.strict_filters {
+            params.validate_filter_groups(&T::allowed_filters())?
+        } else {
+            params.get_filter_groups(&T::allowed_filters())
+        };
+        for group in filter_groups {
+            builder = builder.filter_group(group);
+        }
+
         // Apply sorts
         let sorts = params.get_sorts();
         for sort in sorts {
@@ -73,27 +95,31 @@ where
             }
         }
 
-        // Apply includes
+        // Apply includes, with any field selection checked against the related model
         let includes = params.get_includes();
         for include in includes {
             if T::is_include_allowed(&include) {
-                builder = builder.include(Include::new(include));
+                let pagination = params.get_include_pagination(&include);
+                let mut include_spec = Include::new(include.clone()).with_pagination(pagination);
+                if let Some(fields) = params.get_fields(&include) {
+                    if let Some(allowed) = T::include_fields(&include) {
+                        Include::validate_fields(&include, &fields, &allowed)?;
+                    }
+                    include_spec = include_spec.with_fields(fields);
+                }
+                builder = builder.include(include_spec);
             }
         }
 
         // Apply field selection
-        if let Some(fields) = params.get_fields(T::table_name()) {
-            let allowed_fields: Vec<String> = fields
-                .into_iter()
-                .filter(|f| T::is_field_allowed(f))
-                .collect();
-            if !allowed_fields.is_empty() {
-                builder = builder.fields(allowed_fields);
+        if let Some(fields) = params.validate_fields::<T>()? {
+            if !fields.is_empty() {
+                builder = builder.fields(fields);
             }
         }
 
         // Apply pagination
-        builder = builder.paginate(params.get_pagination());
+        builder = builder.paginate(params.get_pagination_for::<T>());
 
         // Apply appends
         builder.appends = params.append;
@@ -109,6 +135,16 @@ where
         self
     }
 
+    /// Add a group of filters combined with AND or OR
+    ///
+    /// Groups filtering on a field that is not allowed are ignored whole.
+    pub fn filter_group(mut self, group: FilterGroup) -> Self {
+        if group.filters().iter().all(|filter| T::is_filter_allowed(&filter.field)) {
+            self.filter_groups.push(group);
+        }
+        self
+    }
+
     /// Add multiple filters
     pub fn filters(mut self, filters: Vec<Filter>) -> Self {
         for filter in filters {
@@ -395,6 +431,11 @@ where
         &self.filters
     }
 
+    /// Get the current filter groups
+    pub fn get_filter_groups(&self) -> &[FilterGroup] {
+        &self.filter_groups
+    }
+
     /// Get the current sorts
     pub fn get_sorts(&self) -> &[Sort] {
         &self.sorts
@@ -542,6 +583,7 @@ where
     /// Reset all filters
     pub fn reset_filters(mut self) -> Self {
         self.filters.clear();
+        self.filter_groups.clear();
         self
     }
 
@@ -685,6 +727,7 @@ where
     /// Clear all filters
     pub fn clear_filters(mut self) -> Self {
         self.filters.clear();
+        self.filter_groups.clear();
         self
     }
 
@@ -731,6 +774,7 @@ where
     pub fn clone_builder(&self) -> Self {
         QueryBuilder {
             filters: self.filters.clone(),
+            filter_groups: self.filter_groups.clone(),
             sorts: self.sorts.clone(),
             includes: self.includes.clone(),
             fields: self.fields.clone(),
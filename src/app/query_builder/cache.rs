@@ -0,0 +1,114 @@
+//! Opt-in caching of query builder results
+//!
+//! Index responses are cached under a key derived from the tenant and the
+//! normalized query parameters, and tagged with the model type. Writes that go through the
+//! model-event layer (`ServiceActivityLogger::log_created` and friends) flush
+//! the model's tag, so a cached page never outlives a change to its model.
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+use crate::app::models::HasModelType;
+use crate::app::query_builder::{CacheStatus, PaginationResult, QueryBuilderExt, QueryExecutor, QueryParams, Queryable};
+use crate::cache::manager::{shared_cache, CacheDriver};
+use crate::cache::TaggableCache;
+use crate::config::Config;
+use crate::database::DbPool;
+
+pub struct QueryCache {
+    cache: CacheDriver,
+    ttl: Duration,
+    tenant: Option<String>,
+}
+
+impl QueryCache {
+    pub async fn new() -> Result<Self> {
+        let config = Config::load()?;
+        Ok(Self::with_cache(shared_cache().await?, Duration::from_secs(config.cache.query_ttl_secs)))
+    }
+
+    pub fn with_cache(cache: CacheDriver, ttl: Duration) -> Self {
+        Self { cache, ttl, tenant: None }
+    }
+
+    /// Keep results for `tenant` apart from other tenants' and the central database's
+    pub fn for_tenant(mut self, tenant: Option<String>) -> Self {
+        self.tenant = tenant;
+        self
+    }
+
+    /// Paginated results for the query, served from cache when a fresh copy exists
+    pub async fn index<T>(&self, params: QueryParams, pool: &DbPool) -> Result<(PaginationResult<Value>, CacheStatus)>
+    where
+        T: Queryable + HasModelType + Clone,
+    {
+        let tagged = self.cache.tags(&[T::model_type()]);
+        let key = Self::key::<T>(self.tenant.as_deref(), &params);
+
+        if let Some(result) = tagged.get::<PaginationResult<Value>>(&key).await? {
+            return Ok((result, CacheStatus::Hit));
+        }
+
+        let result = {
+            let mut conn = pool.get()?;
+            QueryExecutor::execute_paginated(T::from_params(params)?, &mut conn)?
+        };
+        tagged.put(&key, &result, Some(self.ttl)).await?;
+
+        Ok((result, CacheStatus::Miss))
+    }
+
+    /// Drop every cached result for a model type
+    pub async fn invalidate(&self, model_type: &str) -> Result<()> {
+        self.cache.tags(&[model_type]).flush().await
+    }
+
+    /// Cache key for a query against `tenant`, `None` for the central database;
+    /// parameter order and defaults don't change it
+    pub fn key<T: Queryable>(tenant: Option<&str>, params: &QueryParams) -> String {
+        let normalized = json!({
+            "tenant": tenant,
+            "filter": Self::canonical(&json!(params.filter)),
+            "sort": params.sort,
+            "include": params.include,
+            "fields": Self::canonical(&json!(params.fields)),
+            "page": params.page.unwrap_or(1),
+            "per_page": params.per_page.unwrap_or(15),
+            "pagination_type": params.pagination_type.unwrap_or_default(),
+            "cursor": params.cursor,
+            "append": Self::canonical(&json!(params.append)),
+            "include_page": Self::canonical(&json!(params.include_page)),
+            "include_per_page": Self::canonical(&json!(params.include_per_page)),
+        });
+
+        let digest = Sha256::digest(normalized.to_string().as_bytes());
+        format!("query:{}:{}", T::table_name(), hex::encode(digest))
+    }
+
+    /// Objects rewritten with sorted keys so equal parameters serialize equally
+    fn canonical(value: &Value) -> Value {
+        match value {
+            Value::Object(map) => {
+                let mut entries = map.iter().collect::<Vec<_>>();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                Value::Array(entries.into_iter().map(|(k, v)| json!([k, Self::canonical(v)])).collect())
+            },
+            Value::Array(items) => Value::Array(items.iter().map(Self::canonical).collect()),
+            other => other.clone(),
+        }
+    }
+}
+
+/// Flush a model's cached query results after a write
+pub async fn invalidate_model(model_type: &str) {
+    let result = match QueryCache::new().await {
+        Ok(cache) => cache.invalidate(model_type).await,
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+        tracing::warn!("Failed to invalidate query cache for {}: {}", model_type, e);
+    }
+}
@@ -1,9 +1,11 @@
-use crate::app::query_builder::{Filter, Sort, QueryBuilder, Queryable, Filterable, Pagination, PaginationResult, SortDirection};
+use crate::app::query_builder::{Filter, FilterCondition, FilterGroup, Sort, QueryBuilder, Queryable, Pagination, PaginationResult, SortDirection};
+use crate::app::query_builder::{Bindings, CursorAnchor, HasMany, Include, MorphTarget, DEFAULT_INCLUDE_PER_PAGE};
 use crate::database::DbConnection;
+use diesel::pg::PgConnection;
 use diesel::prelude::*;
-use diesel::sql_query;
 use diesel::sql_types::{BigInt, Text};
 use anyhow::Result;
+use std::collections::HashMap;
 
 /// Query executor that builds and executes SQL queries using Diesel
 pub struct QueryExecutor;
@@ -20,7 +22,7 @@ impl QueryExecutor {
         let pagination = builder.get_pagination().cloned().unwrap_or_default();
 
         // Build the base query
-        let mut query_parts = QueryParts::new(T::table_name());
+        let mut query_parts = QueryParts::for_table(T::table_name(), conn)?;
 
         // Apply field selection
         if let Some(fields) = builder.get_fields() {
@@ -28,14 +30,18 @@ impl QueryExecutor {
         } else {
             query_parts.select_fields(&T::default_fields().iter().map(|s| s.to_string()).collect::<Vec<_>>());
         }
+        Self::select_counts::<T>(builder.get_includes(), &mut query_parts);
 
         // Apply filters
         for filter in builder.get_filters() {
             query_parts.add_filter(filter);
         }
+        for group in builder.get_filter_groups() {
+            query_parts.add_filter_group(group);
+        }
 
         // Apply sorts using enhanced multi-column sorting
-        let sorts = if builder.get_sorts().is_empty() {
+        let mut sorts = if builder.get_sorts().is_empty() {
             if let Some((field, direction)) = T::default_sort() {
                 vec![Sort::new(field.to_string(), direction)]
             } else {
@@ -45,34 +51,42 @@ impl QueryExecutor {
             builder.get_sorts().to_vec()
         };
 
+        // Cursor pages continue after the last row's sort values, which needs `id` to break ties
+        if pagination.is_cursor() && T::allowed_sorts().contains(&"id") && !sorts.iter().any(|sort| sort.field == "id") {
+            sorts.push(Sort::new("id".to_string(), SortDirection::Asc));
+        }
+
         // Use the new Sortable trait methods for multi-column sorting
-        if !sorts.is_empty() {
-            let sort_tuples = Sort::vec_to_tuples(&sorts);
+        let sort_tuples = Sort::vec_to_tuples(&sorts);
+        if !sort_tuples.is_empty() {
             query_parts.add_multi_sort(&sort_tuples);
         }
 
         // Execute count query for pagination
         let count_sql = query_parts.build_count_query();
-        let total: i64 = sql_query(&count_sql)
-            .bind::<Text, _>(&count_sql)
+        let total: i64 = query_parts.bindings.query(count_sql)
             .get_result::<CountResult>(conn)?
             .count;
 
         // Apply pagination
-        query_parts.paginate(&pagination);
+        query_parts.paginate(&pagination, &sort_tuples);
 
         // Execute main query
-        let query_sql = query_parts.build_query();
-        let results: Vec<QueryResult> = sql_query(&query_sql)
-            .bind::<Text, _>(&query_sql)
+        let results: Vec<QueryResult> = query_parts.bindings.query(query_parts.build_json_query())
             .load(conn)?;
 
         // Convert results to JSON
-        let data: Vec<serde_json::Value> = results
+        let mut data: Vec<serde_json::Value> = results
             .into_iter()
             .map(|r| r.to_json())
             .collect();
 
+        Self::load_has_many::<T>(builder.get_includes(), &mut data, conn)?;
+        Self::load_morph_to::<T>(builder.get_includes(), &mut data, conn)?;
+
+        if pagination.is_cursor() {
+            return Ok(pagination.paginate_keyset(data, &sort_tuples));
+        }
         Ok(pagination.paginate(total as u64, data))
     }
 
@@ -84,7 +98,7 @@ impl QueryExecutor {
     where
         T: Queryable + Clone,
     {
-        let mut query_parts = QueryParts::new(T::table_name());
+        let mut query_parts = QueryParts::for_table(T::table_name(), conn)?;
 
         // Apply field selection
         if let Some(fields) = builder.get_fields() {
@@ -92,11 +106,15 @@ impl QueryExecutor {
         } else {
             query_parts.select_fields(&T::default_fields().iter().map(|s| s.to_string()).collect::<Vec<_>>());
         }
+        Self::select_counts::<T>(builder.get_includes(), &mut query_parts);
 
         // Apply filters
         for filter in builder.get_filters() {
             query_parts.add_filter(filter);
         }
+        for group in builder.get_filter_groups() {
+            query_parts.add_filter_group(group);
+        }
 
         // Apply sorts
         let sorts = if builder.get_sorts().is_empty() {
@@ -114,14 +132,139 @@ impl QueryExecutor {
         }
 
         // Execute query
-        let query_sql = query_parts.build_query();
-        let results: Vec<QueryResult> = sql_query(&query_sql)
-            .bind::<Text, _>(&query_sql)
+        let results: Vec<QueryResult> = query_parts.bindings.query(query_parts.build_json_query())
+            .load(conn)?;
+
+        let mut data: Vec<serde_json::Value> = results.into_iter().map(|r| r.to_json()).collect();
+        Self::load_has_many::<T>(builder.get_includes(), &mut data, conn)?;
+        Self::load_morph_to::<T>(builder.get_includes(), &mut data, conn)?;
+
+        Ok(data)
+    }
+
+    /// Execute a query builder for one chunk of rows, ignoring its pagination
+    ///
+    /// Rows are ordered by the builder's sorts with `id` as a tie-breaker, so
+    /// consecutive chunks read inside one transaction neither skip nor repeat rows.
+    pub fn execute_chunk<T>(
+        builder: &QueryBuilder<T>,
+        offset: u32,
+        limit: u32,
+        conn: &mut PgConnection,
+    ) -> Result<Vec<serde_json::Value>>
+    where
+        T: Queryable + Clone,
+    {
+        let mut query_parts = QueryParts::for_table(T::table_name(), conn)?;
+
+        if let Some(fields) = builder.get_fields() {
+            query_parts.select_fields(fields);
+        } else {
+            query_parts.select_fields(&T::default_fields().iter().map(|s| s.to_string()).collect::<Vec<_>>());
+        }
+        Self::select_counts::<T>(builder.get_includes(), &mut query_parts);
+
+        for filter in builder.get_filters() {
+            query_parts.add_filter(filter);
+        }
+        for group in builder.get_filter_groups() {
+            query_parts.add_filter_group(group);
+        }
+
+        let mut sorts = if builder.get_sorts().is_empty() {
+            T::default_sort()
+                .map(|(field, direction)| vec![Sort::new(field.to_string(), direction)])
+                .unwrap_or_default()
+        } else {
+            builder.get_sorts().to_vec()
+        };
+        if T::allowed_sorts().contains(&"id") && !sorts.iter().any(|sort| sort.field == "id") {
+            sorts.push(Sort::new("id".to_string(), SortDirection::Asc));
+        }
+        for sort in &sorts {
+            query_parts.add_sort(sort);
+        }
+
+        query_parts.limit = Some(limit);
+        query_parts.offset = Some(offset);
+
+        let results: Vec<QueryResult> = query_parts.bindings.query(query_parts.build_json_query())
             .load(conn)?;
 
         Ok(results.into_iter().map(|r| r.to_json()).collect())
     }
 
+    /// Execute a query builder for the chunk of rows after `after`, ignoring its pagination
+    ///
+    /// Rows are ordered by the builder's sorts with `id` as a tie-breaker, and
+    /// the anchor for the following chunk is returned with them. Each chunk
+    /// stands alone, so chunks can be read on separate connections.
+    pub fn execute_keyset_chunk<T>(
+        builder: &QueryBuilder<T>,
+        after: Option<&CursorAnchor>,
+        limit: u32,
+        conn: &mut PgConnection,
+    ) -> Result<(Vec<serde_json::Value>, Option<CursorAnchor>)>
+    where
+        T: Queryable + Clone,
+    {
+        let mut query_parts = QueryParts::for_table(T::table_name(), conn)?;
+
+        let mut sorts = if builder.get_sorts().is_empty() {
+            T::default_sort()
+                .map(|(field, direction)| vec![Sort::new(field.to_string(), direction)])
+                .unwrap_or_default()
+        } else {
+            builder.get_sorts().to_vec()
+        };
+        if !sorts.iter().any(|sort| sort.field == "id") {
+            sorts.push(Sort::new("id".to_string(), SortDirection::Asc));
+        }
+        let sort_tuples = Sort::vec_to_tuples(&sorts);
+
+        // The anchor is read from the rows, so sort columns are selected even when not asked for
+        let mut fields = match builder.get_fields() {
+            Some(fields) => fields.to_vec(),
+            None => T::default_fields().iter().map(|s| s.to_string()).collect(),
+        };
+        let anchor_only: Vec<String> = sort_tuples.iter()
+            .map(|(field, _)| field.clone())
+            .filter(|field| !fields.contains(field))
+            .collect();
+        fields.extend(anchor_only.iter().cloned());
+        query_parts.select_fields(&fields);
+        Self::select_counts::<T>(builder.get_includes(), &mut query_parts);
+
+        for filter in builder.get_filters() {
+            query_parts.add_filter(filter);
+        }
+        for group in builder.get_filter_groups() {
+            query_parts.add_filter_group(group);
+        }
+
+        if let Some(anchor) = after.filter(|anchor| anchor.matches(&sort_tuples)) {
+            let clause = query_parts.keyset_clause(&sort_tuples, &anchor.values);
+            query_parts.where_clauses.push(clause);
+        }
+        query_parts.add_multi_sort(&sort_tuples);
+        query_parts.limit = Some(limit);
+
+        let results: Vec<QueryResult> = query_parts.bindings.query(query_parts.build_json_query())
+            .load(conn)?;
+        let mut data: Vec<serde_json::Value> = results.into_iter().map(|r| r.to_json()).collect();
+
+        let next = data.last().and_then(|row| CursorAnchor::from_row(row, &sort_tuples));
+        if !anchor_only.is_empty() {
+            for row in data.iter_mut().filter_map(|row| row.as_object_mut()) {
+                for field in &anchor_only {
+                    row.remove(field);
+                }
+            }
+        }
+
+        Ok((data, next))
+    }
+
     /// Execute a query builder and return the first result
     pub fn execute_first<T>(
         builder: QueryBuilder<T>,
@@ -143,23 +286,211 @@ impl QueryExecutor {
     where
         T: Queryable + Clone,
     {
-        let mut query_parts = QueryParts::new(T::table_name());
+        let mut query_parts = QueryParts::for_table(T::table_name(), conn)?;
 
         // Apply filters only (no sorting/pagination for count)
         for filter in builder.get_filters() {
             query_parts.add_filter(filter);
         }
+        for group in builder.get_filter_groups() {
+            query_parts.add_filter_group(group);
+        }
 
         let count_sql = query_parts.build_count_query();
-        let result: CountResult = sql_query(&count_sql)
-            .bind::<Text, _>(&count_sql)
+        let result: CountResult = query_parts.bindings.query(count_sql)
             .get_result(conn)?;
 
         Ok(result.count)
     }
+
+    /// Attach a page of children to each parent row for included has-many relationships
+    ///
+    /// Each relationship costs two queries regardless of the number of parents:
+    /// one for the windowed page of children and one for the per-parent totals.
+    /// Rows selected without their `id` can't be matched and are left untouched.
+    fn load_has_many<T: Queryable>(
+        includes: &[Include],
+        rows: &mut [serde_json::Value],
+        conn: &mut DbConnection,
+    ) -> Result<()> {
+        let relations = T::has_many_relations();
+
+        for include in includes {
+            let Some(relation) = relations.iter().find(|r| r.relation == include.relation) else {
+                continue;
+            };
+
+            let parent_ids: Vec<serde_json::Value> = rows
+                .iter()
+                .filter_map(|row| row.get("id").filter(|id| !id.is_null()).cloned())
+                .collect();
+            if parent_ids.is_empty() {
+                continue;
+            }
+
+            let pagination = include
+                .pagination
+                .clone()
+                .unwrap_or_else(|| Pagination::page_based(1, DEFAULT_INCLUDE_PER_PAGE));
+
+            let mut query_parts = QueryParts::for_table(relation.table, conn)?;
+            let in_clause = query_parts.bindings.in_list(relation.foreign_key, false, &parent_ids);
+            query_parts.where_clauses.push(in_clause);
+
+            // Select only the requested fields the child model allows, plus the key children are matched on
+            let allowed_fields = (relation.allowed_fields)();
+            let selected_fields: Option<Vec<String>> = include.fields.as_ref()
+                .map(|fields| fields.iter().filter(|field| allowed_fields.contains(&field.as_str())).cloned().collect::<Vec<_>>())
+                .filter(|fields| !fields.is_empty());
+            let strip_foreign_key = selected_fields
+                .as_ref()
+                .is_some_and(|fields| !fields.iter().any(|field| field == relation.foreign_key));
+            if let Some(mut fields) = selected_fields {
+                if strip_foreign_key {
+                    fields.push(relation.foreign_key.to_string());
+                }
+                query_parts.select_fields(&fields);
+            }
+
+            let totals: HashMap<String, i64> = query_parts.bindings.query(query_parts.build_grouped_count_query(relation.foreign_key))
+                .load::<GroupedCountResult>(conn)?
+                .into_iter()
+                .map(|r| (r.parent_id, r.count))
+                .collect();
+
+            let mut children: HashMap<String, Vec<serde_json::Value>> = HashMap::new();
+            let results: Vec<QueryResult> = query_parts.bindings.query(query_parts.build_partitioned_json_query(relation, &pagination))
+                .load(conn)?;
+            for result in results {
+                let mut child = result.to_json();
+                if let Some(object) = child.as_object_mut() {
+                    object.remove("include_row");
+                }
+                let parent_id = child.get(relation.foreign_key).and_then(|v| v.as_str()).map(str::to_string);
+                if let Some(parent_id) = parent_id {
+                    if strip_foreign_key {
+                        if let Some(object) = child.as_object_mut() {
+                            object.remove(relation.foreign_key);
+                        }
+                    }
+                    children.entry(parent_id).or_default().push(child);
+                }
+            }
+
+            for row in rows.iter_mut() {
+                let Some(parent_id) = row.get("id").and_then(|v| v.as_str()).map(str::to_string) else {
+                    continue;
+                };
+                let total = totals.get(&parent_id).copied().unwrap_or(0);
+                let page = pagination.paginate(total as u64, children.remove(&parent_id).unwrap_or_default());
+                if let Some(object) = row.as_object_mut() {
+                    object.insert(relation.relation.to_string(), serde_json::to_value(page)?);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Select `{relation}_count` for each included count relationship
+    fn select_counts<T: Queryable>(includes: &[Include], query_parts: &mut QueryParts) {
+        let relations = T::count_relations();
+
+        for include in includes {
+            if let Some(relation) = relations.iter().find(|r| r.count_key() == include.relation) {
+                let count = relation.select_sql(&query_parts.table);
+                query_parts.select_fields.push(count);
+            }
+        }
+    }
+
+    /// Attach the related record to each row for included morph-to relationships
+    ///
+    /// Rows are grouped by their type column and each related table is queried
+    /// once. Only fields the related model allows are selected, so an include
+    /// never exposes more than listing that model would. Rows whose type is
+    /// unknown, whose record is gone, or that were selected without the type
+    /// and id columns get null.
+    pub fn load_morph_to<T: Queryable>(
+        includes: &[Include],
+        rows: &mut [serde_json::Value],
+        conn: &mut DbConnection,
+    ) -> Result<()> {
+        let relations = T::morph_to_relations();
+
+        for include in includes {
+            let Some(relation) = relations.iter().find(|r| r.relation == include.relation) else {
+                continue;
+            };
+
+            let mut ids_by_type: HashMap<String, Vec<serde_json::Value>> = HashMap::new();
+            for row in rows.iter() {
+                if let Some((model_type, id)) = Self::morph_key(row, relation.type_column, relation.id_column) {
+                    ids_by_type.entry(model_type).or_default().push(serde_json::Value::String(id));
+                }
+            }
+
+            let mut related: HashMap<(String, String), serde_json::Value> = HashMap::new();
+            for (model_type, ids) in ids_by_type {
+                let Some(target) = MorphTarget::find(&model_type) else {
+                    continue;
+                };
+
+                let allowed_fields = (target.allowed_fields)();
+                let mut fields: Vec<String> = include.fields.as_ref()
+                    .map(|fields| fields.iter().filter(|field| allowed_fields.contains(&field.as_str())).cloned().collect::<Vec<_>>())
+                    .filter(|fields| !fields.is_empty())
+                    .unwrap_or_else(|| allowed_fields.iter().map(|field| field.to_string()).collect());
+                let strip_id = !fields.iter().any(|field| field == "id");
+                if strip_id {
+                    fields.push("id".to_string());
+                }
+
+                let mut query_parts = QueryParts::for_table(target.table, conn)?;
+                query_parts.select_fields(&fields);
+                let in_clause = query_parts.bindings.in_list("id", false, &ids);
+                query_parts.where_clauses.push(in_clause);
+
+                let results: Vec<QueryResult> = query_parts.bindings.query(query_parts.build_json_query())
+                    .load(conn)?;
+                for result in results {
+                    let mut record = result.to_json();
+                    let Some(id) = record.get("id").and_then(|v| v.as_str()).map(str::to_string) else {
+                        continue;
+                    };
+                    if strip_id {
+                        if let Some(object) = record.as_object_mut() {
+                            object.remove("id");
+                        }
+                    }
+                    related.insert((model_type.clone(), id), record);
+                }
+            }
+
+            for row in rows.iter_mut() {
+                let record = Self::morph_key(row, relation.type_column, relation.id_column)
+                    .and_then(|key| related.get(&key).cloned())
+                    .unwrap_or(serde_json::Value::Null);
+                if let Some(object) = row.as_object_mut() {
+                    object.insert(relation.relation.to_string(), record);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn morph_key(row: &serde_json::Value, type_column: &str, id_column: &str) -> Option<(String, String)> {
+        let model_type = row.get(type_column)?.as_str()?;
+        let id = row.get(id_column)?.as_str()?;
+        Some((model_type.to_string(), id.to_string()))
+    }
 }
 
 /// Helper struct for building SQL query parts
+///
+/// Values compared against columns go into `bindings` as placeholders
+/// rather than being written into the clauses.
 #[derive(Debug)]
 struct QueryParts {
     table: String,
@@ -168,6 +499,7 @@ struct QueryParts {
     order_clauses: Vec<String>,
     limit: Option<u32>,
     offset: Option<u32>,
+    bindings: Bindings,
 }
 
 impl QueryParts {
@@ -179,9 +511,17 @@ impl QueryParts {
             order_clauses: Vec::new(),
             limit: None,
             offset: None,
+            bindings: Bindings::new(),
         }
     }
 
+    /// Query parts whose placeholders are cast to the column types of `table`
+    fn for_table(table: &str, conn: &mut PgConnection) -> Result<Self> {
+        let mut parts = Self::new(table);
+        parts.bindings = Bindings::for_table(table, conn)?;
+        Ok(parts)
+    }
+
     fn select_fields(&mut self, fields: &[String]) {
         if !fields.is_empty() {
             self.select_fields = fields.to_vec();
@@ -195,6 +535,13 @@ impl QueryParts {
         }
     }
 
+    fn add_filter_group(&mut self, group: &FilterGroup) {
+        let where_clause = self.build_filter_group_clause(group);
+        if !where_clause.is_empty() {
+            self.where_clauses.push(where_clause);
+        }
+    }
+
     fn add_sort(&mut self, sort: &Sort) {
         let order_clause = format!("{} {}", sort.field, sort.direction.to_sql());
         self.order_clauses.push(order_clause);
@@ -207,116 +554,85 @@ impl QueryParts {
         }
     }
 
-    fn paginate(&mut self, pagination: &Pagination) {
-        self.limit = Some(pagination.limit());
-        self.offset = Some(pagination.offset());
-    }
-
-    fn build_filter_clause(&self, filter: &Filter) -> String {
-        use crate::app::query_builder::FilterOperator;
-
-        // Convert filter to JSON for trait method compatibility
-        let value_json = match &filter.value {
-            crate::app::query_builder::FilterValue::Single(v) => v.clone(),
-            crate::app::query_builder::FilterValue::Multiple(v) | crate::app::query_builder::FilterValue::Array(v) => serde_json::Value::Array(v.clone()),
-            crate::app::query_builder::FilterValue::Range(start, end) => {
-                serde_json::Value::Array(vec![start.clone(), end.clone()])
-            }
-        };
-
-        // Use the enhanced Filterable trait methods for comprehensive filtering
-        match filter.operator {
-            FilterOperator::Eq => format!("{} = {}", filter.field, self.escape_value(filter.value.as_single().unwrap_or(&serde_json::Value::Null))),
-            FilterOperator::Ne => format!("{} != {}", filter.field, self.escape_value(filter.value.as_single().unwrap_or(&serde_json::Value::Null))),
-            FilterOperator::Gt | FilterOperator::Gte | FilterOperator::Lt | FilterOperator::Lte | FilterOperator::Between => {
-                // Use range filtering from trait
-                self.apply_range_filter(&filter.field, filter.operator.to_sql(), &value_json)
-            }
-            FilterOperator::In | FilterOperator::NotIn => {
-                // Use IN filtering from trait
-                if let Some(values) = filter.value.as_multiple() {
-                    self.apply_in_filter(&filter.field, filter.operator == FilterOperator::NotIn, values)
-                } else {
-                    String::new()
+    fn paginate(&mut self, pagination: &Pagination, sorts: &[(String, SortDirection)]) {
+        if pagination.is_cursor() {
+            // One row past the page tells `paginate_cursor` that another page follows
+            self.limit = Some(pagination.limit() + 1);
+
+            // Start after the anchor's sort values; the anchor row itself is not
+            // looked up, so the page is right even if it has since been deleted
+            match pagination.cursor_anchor().filter(|anchor| anchor.matches(sorts)) {
+                Some(anchor) => {
+                    let clause = self.keyset_clause(sorts, &anchor.values);
+                    self.where_clauses.push(clause);
+                    self.offset = None;
                 }
+                None => self.offset = Some(pagination.cursor_position()),
             }
-            FilterOperator::Like | FilterOperator::Ilike => {
-                // Use LIKE filtering from trait
-                self.apply_like_filter(&filter.field, filter.operator.to_sql(), &value_json)
-            }
-            FilterOperator::IsNull => format!("{} IS NULL", filter.field),
-            FilterOperator::IsNotNull => format!("{} IS NOT NULL", filter.field),
-            _ => {
-                // Fallback to original implementation for any missing operators
-                self.build_legacy_filter_clause(filter)
-            }
+        } else {
+            self.limit = Some(pagination.limit());
+            self.offset = Some(pagination.offset());
         }
     }
 
-    fn apply_range_filter(&self, column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match operator {
-            "BETWEEN" => {
-                if let Some(values) = value.as_array() {
-                    if values.len() == 2 {
-                        format!("{} BETWEEN {} AND {}", column,
-                               self.escape_value(&values[0]),
-                               self.escape_value(&values[1]))
-                    } else {
-                        format!("{} = {}", column, self.escape_value(value))
-                    }
-                } else {
-                    format!("{} = {}", column, self.escape_value(value))
-                }
-            },
-            ">" => format!("{} > {}", column, self.escape_value(value)),
-            ">=" => format!("{} >= {}", column, self.escape_value(value)),
-            "<" => format!("{} < {}", column, self.escape_value(value)),
-            "<=" => format!("{} <= {}", column, self.escape_value(value)),
-            _ => String::new()
+    /// Rows that sort after `values` under `sorts`, with NULLs last ascending and first descending
+    fn keyset_clause(&mut self, sorts: &[(String, SortDirection)], values: &[serde_json::Value]) -> String {
+        let mut alternatives = Vec::new();
+        for (i, ((field, direction), value)) in sorts.iter().zip(values).enumerate() {
+            let Some(after) = self.keyset_after(field, *direction, value) else {
+                // Nothing sorts after a NULL in ascending order
+                continue;
+            };
+
+            let mut conditions: Vec<String> = sorts[..i].iter()
+                .zip(values)
+                .map(|((field, _), value)| self.keyset_equal(field, value))
+                .collect();
+            conditions.push(after);
+            alternatives.push(format!("({})", conditions.join(" AND ")));
         }
-    }
 
-    fn apply_in_filter(&self, column: &str, is_not_in: bool, values: &[serde_json::Value]) -> String {
-        if values.is_empty() {
-            return if is_not_in { "1=1".to_string() } else { "1=0".to_string() };
+        if alternatives.is_empty() {
+            return "1=0".to_string();
         }
-        let formatted_values: Vec<String> = values.iter()
-            .map(|v| self.escape_value(v))
-            .collect();
-        let operator = if is_not_in { "NOT IN" } else { "IN" };
-        format!("{} {} ({})", column, operator, formatted_values.join(", "))
+        format!("({})", alternatives.join(" OR "))
     }
 
-    fn apply_like_filter(&self, column: &str, operator: &str, value: &serde_json::Value) -> String {
-        if let Some(text) = value.as_str() {
-            let escaped_text = format!("'{}'", text.replace("'", "''"));
-            format!("{} {} {}", column, operator, escaped_text)
+    fn keyset_equal(&mut self, field: &str, value: &serde_json::Value) -> String {
+        if value.is_null() {
+            format!("{} IS NULL", field)
         } else {
-            String::new()
+            format!("{} = {}", field, self.bindings.push(field, value))
         }
     }
 
-    fn build_legacy_filter_clause(&self, filter: &Filter) -> String {
-        // Fallback for any operators not covered by the new trait methods
-        match filter.operator {
-            crate::app::query_builder::FilterOperator::Eq => {
-                if let Some(value) = filter.value.as_single() {
-                    format!("{} = {}", filter.field, self.escape_value(value))
-                } else {
-                    String::new()
-                }
-            }
-            _ => String::new()
+    fn keyset_after(&mut self, field: &str, direction: SortDirection, value: &serde_json::Value) -> Option<String> {
+        match (direction, value.is_null()) {
+            (SortDirection::Asc, false) => Some(format!("({} > {} OR {} IS NULL)", field, self.bindings.push(field, value), field)),
+            (SortDirection::Asc, true) => None,
+            (SortDirection::Desc, false) => Some(format!("{} < {}", field, self.bindings.push(field, value))),
+            (SortDirection::Desc, true) => Some(format!("{} IS NOT NULL", field)),
         }
     }
 
-    fn escape_value(&self, value: &serde_json::Value) -> String {
-        match value {
-            serde_json::Value::String(s) => format!("'{}'", s.replace("'", "''")),
-            serde_json::Value::Number(n) => n.to_string(),
-            serde_json::Value::Bool(b) => b.to_string(),
-            serde_json::Value::Null => "NULL".to_string(),
-            _ => format!("'{}'", value.to_string().replace("'", "''")),
+    fn build_filter_clause(&mut self, filter: &Filter) -> String {
+        self.bindings.filter(filter)
+    }
+
+    /// `(a OR b)` for a group, skipping conditions that render to nothing
+    fn build_filter_group_clause(&mut self, group: &FilterGroup) -> String {
+        let clauses: Vec<String> = group.conditions.iter()
+            .map(|condition| match condition {
+                FilterCondition::Filter(filter) => self.build_filter_clause(filter),
+                FilterCondition::Group(group) => self.build_filter_group_clause(group),
+            })
+            .filter(|clause| !clause.is_empty())
+            .collect();
+
+        if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("({})", clauses.join(&format!(" {} ", group.combinator.to_sql())))
         }
     }
 
@@ -346,6 +662,48 @@ impl QueryParts {
         query
     }
 
+    /// Wrap the query so each row comes back as a single JSON text column
+    fn build_json_query(&self) -> String {
+        format!("SELECT row_to_json(q)::text AS data FROM ({}) q", self.build_query())
+    }
+
+    /// Rows numbered within each `partition_by` group, keeping one page per group
+    fn build_partitioned_json_query(&self, relation: &HasMany, pagination: &Pagination) -> String {
+        let (order_field, order_direction) = &relation.order_by;
+        let mut ranked = format!(
+            "SELECT {}, ROW_NUMBER() OVER (PARTITION BY {} ORDER BY {} {}) AS include_row FROM {}",
+            self.select_fields.join(", "),
+            relation.foreign_key,
+            order_field,
+            order_direction.to_sql(),
+            self.table
+        );
+
+        if !self.where_clauses.is_empty() {
+            ranked.push_str(&format!(" WHERE {}", self.where_clauses.join(" AND ")));
+        }
+
+        let offset = pagination.offset();
+        format!(
+            "SELECT row_to_json(q)::text AS data FROM (SELECT * FROM ({}) ranked WHERE include_row > {} AND include_row <= {} ORDER BY {}, include_row) q",
+            ranked,
+            offset,
+            offset + pagination.limit(),
+            relation.foreign_key
+        )
+    }
+
+    fn build_grouped_count_query(&self, group_by: &str) -> String {
+        let mut query = format!("SELECT {}::text AS parent_id, COUNT(*) as count FROM {}", group_by, self.table);
+
+        if !self.where_clauses.is_empty() {
+            query.push_str(&format!(" WHERE {}", self.where_clauses.join(" AND ")));
+        }
+
+        query.push_str(&format!(" GROUP BY {}", group_by));
+        query
+    }
+
     fn build_count_query(&self) -> String {
         let mut query = format!("SELECT COUNT(*) as count FROM {}", self.table);
 
@@ -377,6 +735,15 @@ struct CountResult {
     count: i64,
 }
 
+/// Result struct for per-parent count queries
+#[derive(QueryableByName)]
+struct GroupedCountResult {
+    #[diesel(sql_type = Text)]
+    parent_id: String,
+    #[diesel(sql_type = BigInt)]
+    count: i64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -398,6 +765,78 @@ mod tests {
         assert!(query.contains("OFFSET 5"));
     }
 
+    #[test]
+    fn test_query_parts_build_json_query() {
+        let mut parts = QueryParts::new("users");
+        parts.select_fields(&vec!["id".to_string(), "name".to_string()]);
+        parts.limit = Some(10);
+
+        let query = parts.build_json_query();
+        assert_eq!(query, "SELECT row_to_json(q)::text AS data FROM (SELECT id, name FROM users LIMIT 10) q");
+    }
+
+    #[test]
+    fn test_keyset_clause_continues_after_anchor_values() {
+        let mut parts = QueryParts::new("messages");
+        let sorts = vec![("created_at".to_string(), SortDirection::Desc), ("id".to_string(), SortDirection::Asc)];
+
+        let clause = parts.keyset_clause(&sorts, &[serde_json::json!("2025-01-02T00:00:00Z"), serde_json::json!("01ABC")]);
+        assert_eq!(clause, "((created_at < $1) OR (created_at = $2 AND (id > $3 OR id IS NULL)))");
+        assert_eq!(
+            parts.bindings.values(),
+            &[
+                Some("2025-01-02T00:00:00Z".to_string()),
+                Some("2025-01-02T00:00:00Z".to_string()),
+                Some("01ABC".to_string()),
+            ]
+        );
+
+        let mut parts = QueryParts::new("messages");
+        let clause = parts.keyset_clause(&sorts, &[serde_json::Value::Null, serde_json::json!("01ABC")]);
+        assert_eq!(clause, "((created_at IS NOT NULL) OR (created_at IS NULL AND (id > $1 OR id IS NULL)))");
+    }
+
+    #[test]
+    fn test_filter_group_clause() {
+        let mut parts = QueryParts::new("users");
+        let group = FilterGroup::or(vec![
+            FilterCondition::Filter(Filter::contains("name", "jo")),
+            FilterCondition::Group(FilterGroup::and(vec![
+                FilterCondition::Filter(Filter::ends_with("email", "@example.com")),
+                FilterCondition::Filter(Filter::gte("failed_login_attempts", 3)),
+            ])),
+        ]);
+
+        parts.add_filter_group(&group);
+        assert_eq!(
+            parts.where_clauses,
+            vec!["(name ILIKE $1 OR (email ILIKE $2 AND failed_login_attempts >= $3))"]
+        );
+        assert_eq!(
+            parts.bindings.values(),
+            &[Some("%jo%".to_string()), Some("%@example.com".to_string()), Some("3".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_filter_values_are_bound_not_interpolated() {
+        let types = HashMap::from([("failed_login_attempts".to_string(), "integer".to_string())]);
+        let mut parts = QueryParts::new("users");
+        parts.bindings = Bindings::with_column_types(std::sync::Arc::new(types));
+
+        parts.add_filter(&Filter::eq("name", "O'Brien"));
+        parts.add_filter(&Filter::in_values("email", vec!["a@example.com", "'; DROP TABLE users; --"]));
+        parts.add_filter(&Filter::between("failed_login_attempts", 1, 3));
+
+        assert_eq!(
+            parts.build_count_query(),
+            "SELECT COUNT(*) as count FROM users WHERE name = $1 AND email IN ($2, $3) \
+             AND failed_login_attempts BETWEEN CAST($4 AS integer) AND CAST($5 AS integer)"
+        );
+        assert_eq!(parts.bindings.values()[0].as_deref(), Some("O'Brien"));
+        assert_eq!(parts.bindings.values()[2].as_deref(), Some("'; DROP TABLE users; --"));
+    }
+
     #[test]
     fn test_query_parts_build_count_query() {
         let mut parts = QueryParts::new("users");
@@ -406,4 +845,22 @@ mod tests {
         let query = parts.build_count_query();
         assert_eq!(query, "SELECT COUNT(*) as count FROM users WHERE active = true");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_query_parts_build_partitioned_json_query() {
+        let relation = HasMany {
+            relation: "messages",
+            table: "messages",
+            foreign_key: "conversation_id",
+            order_by: ("sent_at", SortDirection::Desc),
+            allowed_fields: || vec!["id", "conversation_id", "sent_at"],
+        };
+        let mut parts = QueryParts::new("messages");
+        parts.where_clauses.push("conversation_id IN ('a', 'b')".to_string());
+
+        let query = parts.build_partitioned_json_query(&relation, &Pagination::page_based(2, 10));
+        assert!(query.contains("ROW_NUMBER() OVER (PARTITION BY conversation_id ORDER BY sent_at DESC)"));
+        assert!(query.contains("WHERE conversation_id IN ('a', 'b')"));
+        assert!(query.contains("include_row > 10 AND include_row <= 20"));
+    }
+}
@@ -0,0 +1,263 @@
+//! CSV and NDJSON export of query builder results
+//!
+//! An export runs the same filters, sorts and field selection as an index
+//! request but ignores pagination. Rows are read in chunks inside a single
+//! read-only transaction on a blocking thread and handed to the response body
+//! through a bounded channel, so a slow client pauses the reader instead of
+//! the whole result being buffered in memory. Its statements run under
+//! `DB_EXPORT_STATEMENT_TIMEOUT_MS` rather than the pool's shorter limit.
+
+use anyhow::Result;
+use axum::body::{Body, Bytes};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::Response;
+use diesel::pg::PgConnection;
+use diesel::Connection;
+use serde::Deserialize;
+use serde_json::Value;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use utoipa::ToSchema;
+
+use crate::app::query_builder::{QueryBuilder, QueryBuilderExt, QueryExecutor, QueryParams, Queryable};
+use crate::database::statement_timeout::{self, with_statement_timeout};
+use crate::database::DbPool;
+
+/// Most rows a single export returns
+pub const DEFAULT_EXPORT_MAX_ROWS: u32 = 100_000;
+
+/// Rows read from the database per chunk
+pub const EXPORT_CHUNK_SIZE: u32 = 1_000;
+
+/// Encoded chunks buffered ahead of the client
+const EXPORT_BUFFERED_CHUNKS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Ndjson,
+}
+
+impl ExportFormat {
+    /// Export format requested by `?format=`, or else by the `Accept` header
+    ///
+    /// Returns `None` for ordinary JSON requests.
+    pub fn negotiate(params: &QueryParams, headers: &HeaderMap) -> Option<Self> {
+        if params.format.is_some() {
+            return params.format;
+        }
+
+        let accept = headers.get(header::ACCEPT)?.to_str().ok()?;
+        accept.split(',').find_map(|media_type| {
+            match media_type.split(';').next().unwrap_or_default().trim() {
+                "text/csv" => Some(Self::Csv),
+                "application/x-ndjson" | "application/ndjson" => Some(Self::Ndjson),
+                _ => None,
+            }
+        })
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Ndjson => "ndjson",
+        }
+    }
+
+    /// Bytes written before the first row
+    pub fn encode_header(&self, columns: &[String]) -> Result<Vec<u8>> {
+        match self {
+            Self::Csv => {
+                let mut writer = csv::Writer::from_writer(Vec::new());
+                writer.write_record(columns)?;
+                writer.into_inner().map_err(|e| anyhow::anyhow!("Failed to write CSV header: {}", e.error()))
+            },
+            Self::Ndjson => Ok(Vec::new()),
+        }
+    }
+
+    /// Encode rows, keeping only the given columns in their given order
+    pub fn encode_rows(&self, columns: &[String], rows: &[Value]) -> Result<Vec<u8>> {
+        match self {
+            Self::Csv => {
+                let mut writer = csv::Writer::from_writer(Vec::new());
+                for row in rows {
+                    writer.write_record(columns.iter().map(|column| Self::csv_cell(&row[column.as_str()])))?;
+                }
+                writer.into_inner().map_err(|e| anyhow::anyhow!("Failed to write CSV rows: {}", e.error()))
+            },
+            Self::Ndjson => {
+                let mut buffer = Vec::new();
+                for row in rows {
+                    let record: serde_json::Map<String, Value> = columns
+                        .iter()
+                        .map(|column| (column.clone(), row[column.as_str()].clone()))
+                        .collect();
+                    serde_json::to_writer(&mut buffer, &record)?;
+                    buffer.push(b'\n');
+                }
+                Ok(buffer)
+            },
+        }
+    }
+
+    fn csv_cell(value: &Value) -> String {
+        match value {
+            Value::Null => String::new(),
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        }
+    }
+}
+
+/// Streams every row matching a query as CSV or NDJSON
+pub struct QueryExport {
+    max_rows: u32,
+    chunk_size: u32,
+    statement_timeout: Option<Duration>,
+}
+
+impl QueryExport {
+    pub fn new() -> Self {
+        Self {
+            max_rows: DEFAULT_EXPORT_MAX_ROWS,
+            chunk_size: EXPORT_CHUNK_SIZE,
+            statement_timeout: statement_timeout::export_timeout(),
+        }
+    }
+
+    pub fn with_max_rows(mut self, max_rows: u32) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    pub fn with_chunk_size(mut self, chunk_size: u32) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Limit for each export statement, `None` meaning no limit
+    pub fn with_statement_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.statement_timeout = timeout;
+        self
+    }
+
+    /// Streaming response for the query
+    ///
+    /// The total is counted up front so a bad query fails before any bytes are
+    /// sent; it is reported in `x-export-total`, and `x-export-truncated` is set
+    /// when it exceeds the row cap.
+    pub fn response<T>(&self, params: QueryParams, format: ExportFormat, pool: &DbPool) -> Result<Response>
+    where
+        T: Queryable + Clone + Send + 'static,
+    {
+        let builder = T::from_params(params)?;
+        let columns: Vec<String> = match builder.get_fields() {
+            Some(fields) => fields.to_vec(),
+            None => T::default_fields().iter().map(|field| field.to_string()).collect(),
+        };
+
+        let total = {
+            let mut conn = pool.get()?;
+            with_statement_timeout(&mut conn, self.statement_timeout, |conn| {
+                QueryExecutor::execute_count(builder.clone(), conn)
+            })?
+        };
+        let preamble = format.encode_header(&columns)?;
+
+        let (tx, mut rx) = mpsc::channel::<Result<Bytes, std::io::Error>>(EXPORT_BUFFERED_CHUNKS);
+        let pool = pool.clone();
+        let max_rows = self.max_rows;
+        let chunk_size = self.chunk_size;
+        let timeout = self.statement_timeout;
+
+        tokio::task::spawn_blocking(move || {
+            let result = pool
+                .get()
+                .map_err(anyhow::Error::from)
+                .and_then(|mut conn| Self::produce(&builder, &columns, format, max_rows, chunk_size, timeout, &mut conn, &tx));
+
+            if let Err(e) = result {
+                tracing::error!("Export of {} failed: {}", T::table_name(), e);
+                let _ = tx.blocking_send(Err(std::io::Error::other(e.to_string())));
+            }
+        });
+
+        let stream = async_stream::stream! {
+            yield Ok::<Bytes, std::io::Error>(Bytes::from(preamble));
+            while let Some(chunk) = rx.recv().await {
+                yield chunk;
+            }
+        };
+
+        let mut response = Response::new(Body::from_stream(stream));
+        *response.status_mut() = StatusCode::OK;
+        let headers = response.headers_mut();
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(format.content_type()));
+        headers.insert(
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_str(&format!("attachment; filename=\"{}.{}\"", T::table_name(), format.extension()))?,
+        );
+        headers.insert("x-export-total", HeaderValue::from(total));
+        if total > i64::from(max_rows) {
+            headers.insert("x-export-truncated", HeaderValue::from_static("true"));
+        }
+
+        Ok(response)
+    }
+
+    /// Read chunks in one read-only snapshot until the rows or the cap run out
+    #[allow(clippy::too_many_arguments)]
+    fn produce<T>(
+        builder: &QueryBuilder<T>,
+        columns: &[String],
+        format: ExportFormat,
+        max_rows: u32,
+        chunk_size: u32,
+        timeout: Option<Duration>,
+        conn: &mut PgConnection,
+        tx: &mpsc::Sender<Result<Bytes, std::io::Error>>,
+    ) -> Result<()>
+    where
+        T: Queryable + Clone,
+    {
+        conn.build_transaction().read_only().repeatable_read().run::<_, anyhow::Error, _>(|conn| {
+            statement_timeout::set_local(conn, timeout)?;
+            let mut offset = 0;
+
+            while offset < max_rows {
+                let limit = chunk_size.min(max_rows - offset);
+                let rows = QueryExecutor::execute_chunk(builder, offset, limit, conn)?;
+                if rows.is_empty() {
+                    break;
+                }
+
+                // A closed channel means the client went away
+                if tx.blocking_send(Ok(Bytes::from(format.encode_rows(columns, &rows)?))).is_err() {
+                    break;
+                }
+
+                offset += rows.len() as u32;
+                if (rows.len() as u32) < limit {
+                    break;
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl Default for QueryExport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use crate::app::query_builder::FilterGroup;
 
 /// Filter operators for query conditions
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -162,7 +163,7 @@ impl FilterOperator {
 }
 
 /// Filter value types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum FilterValue {
     /// Single value
@@ -441,14 +442,44 @@ impl Filter {
         let mut filters = Vec::new();
 
         for (key, value) in params {
+            if FilterGroup::is_group_key(key) {
+                continue;
+            }
             if let Some(filter) = Self::parse_filter_param(key, value) {
-                filters.push(filter);
+                filters.push(filter.normalize_ids());
             }
         }
 
         filters
     }
 
+    /// Rewrite ULIDs on `id` and `*_id` fields to their stored form
+    ///
+    /// Responses may show ids in lowercase or with a model prefix such as
+    /// `org_`; ids are stored as canonical ULIDs, so a filter copied from a
+    /// response would otherwise match nothing. Other values are left alone.
+    pub fn normalize_ids(mut self) -> Self {
+        if self.field != "id" && !self.field.ends_with("_id") {
+            return self;
+        }
+
+        let normalize = |value: &mut Value| {
+            if let Some(id) = value.as_str().and_then(crate::app::models::DieselUlid::normalize) {
+                *value = Value::String(id);
+            }
+        };
+
+        match &mut self.value {
+            FilterValue::Single(value) => normalize(value),
+            FilterValue::Multiple(values) | FilterValue::Array(values) => values.iter_mut().for_each(normalize),
+            FilterValue::Range(start, end) => {
+                normalize(start);
+                normalize(end);
+            },
+        }
+        self
+    }
+
     /// Parse a single filter parameter
     fn parse_filter_param(key: &str, value: &Value) -> Option<Filter> {
         // Handle simple format: filter[field]=value (defaults to eq)
@@ -501,6 +532,134 @@ impl Filter {
         // Fallback: treat as simple equals
         Some(Filter::eq(key, value.clone()))
     }
+
+    /// Parse filter parameters, rejecting what `from_params` would guess at
+    ///
+    /// Unknown operators, `between`/`in` given the wrong number of values and
+    /// fields outside `allowed_fields` are all reported instead of being read
+    /// as `eq` or dropped.
+    pub fn from_params_strict(params: &HashMap<String, Value>, allowed_fields: &[&str]) -> Result<Vec<Filter>, Vec<FilterError>> {
+        let mut keys: Vec<&String> = params.keys().filter(|key| !FilterGroup::is_group_key(key)).collect();
+        keys.sort();
+
+        let mut filters = Vec::new();
+        let mut errors = Vec::new();
+        for key in keys {
+            for (field, operator, value) in Self::filter_terms(key, &params[key]) {
+                match Self::parse_term(field, operator, value, allowed_fields) {
+                    Ok(filter) => filters.push(filter.normalize_ids()),
+                    Err(error) => errors.push(error),
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(filters)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// `(field, operator, value)` for `field[op]=value`, `field={op: value}` or `field=value`
+    fn filter_terms<'a>(key: &'a str, value: &'a Value) -> Vec<(&'a str, Option<&'a str>, &'a Value)> {
+        if let Some((field, operator)) = key.strip_suffix(']').and_then(|key| key.split_once('[')) {
+            return vec![(field, Some(operator), value)];
+        }
+
+        match value {
+            Value::Object(operators) => operators
+                .iter()
+                .map(|(operator, value)| (key, Some(operator.as_str()), value))
+                .collect(),
+            _ => vec![(key, None, value)],
+        }
+    }
+
+    pub(crate) fn parse_term(field: &str, operator: Option<&str>, value: &Value, allowed_fields: &[&str]) -> Result<Filter, FilterError> {
+        let filter = match operator {
+            Some(operator) => format!("filter[{}][{}]", field, operator),
+            None => format!("filter[{}]", field),
+        };
+
+        if !allowed_fields.contains(&field) {
+            return Err(FilterError::FieldNotAllowed { filter, field: field.to_string() });
+        }
+
+        let Some(operator_name) = operator else {
+            return Ok(Filter::eq(field, value.clone()));
+        };
+        let Some(operator) = FilterOperator::from_string(operator_name) else {
+            return Err(FilterError::UnknownOperator { filter, operator: operator_name.to_string() });
+        };
+
+        let values = Self::list_values(value);
+        let wrong_count = |expected: &'static str| FilterError::WrongArgumentCount {
+            filter: filter.clone(),
+            operator: operator_name.to_string(),
+            expected,
+            given: values.len(),
+        };
+
+        let filter_value = match operator {
+            FilterOperator::Between | FilterOperator::NotBetween => match values.as_slice() {
+                [start, end] => FilterValue::Range(start.clone(), end.clone()),
+                _ => return Err(wrong_count("2")),
+            },
+            FilterOperator::In | FilterOperator::NotIn => {
+                if values.is_empty() {
+                    return Err(wrong_count("at least 1"));
+                }
+                FilterValue::Multiple(values)
+            }
+            FilterOperator::IsNull | FilterOperator::IsNotNull => FilterValue::single(Value::Null),
+            _ => FilterValue::single(value.clone()),
+        };
+
+        Ok(Filter::new(field, operator, filter_value))
+    }
+
+    /// Values of a list argument: an array, or a comma-separated string
+    fn list_values(value: &Value) -> Vec<Value> {
+        match value {
+            Value::Array(values) => values.clone(),
+            Value::String(list) => list
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| Value::String(item.to_string()))
+                .collect(),
+            Value::Null => Vec::new(),
+            value => vec![value.clone()],
+        }
+    }
+}
+
+/// A filter parameter rejected by strict parsing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, thiserror::Error)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum FilterError {
+    #[error("{filter}: unknown operator '{operator}'")]
+    UnknownOperator { filter: String, operator: String },
+    #[error("{filter}: '{operator}' takes {expected} values, got {given}")]
+    WrongArgumentCount { filter: String, operator: String, expected: &'static str, given: usize },
+    #[error("{filter}: filtering on '{field}' is not allowed")]
+    FieldNotAllowed { filter: String, field: String },
+    #[error("{filter}: {reason}")]
+    MalformedGroup { filter: String, reason: String },
+}
+
+impl FilterError {
+    /// The same error reported against another parameter name
+    pub(crate) fn for_filter(self, name: String) -> Self {
+        match self {
+            FilterError::UnknownOperator { operator, .. } => FilterError::UnknownOperator { filter: name, operator },
+            FilterError::WrongArgumentCount { operator, expected, given, .. } => {
+                FilterError::WrongArgumentCount { filter: name, operator, expected, given }
+            }
+            FilterError::FieldNotAllowed { field, .. } => FilterError::FieldNotAllowed { filter: name, field },
+            FilterError::MalformedGroup { reason, .. } => FilterError::MalformedGroup { filter: name, reason },
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,260 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use crate::app::query_builder::{Filter, FilterError};
+
+/// How the conditions of a `FilterGroup` are combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterCombinator {
+    And,
+    Or,
+}
+
+impl FilterCombinator {
+    /// Get SQL keyword
+    pub fn to_sql(&self) -> &'static str {
+        match self {
+            FilterCombinator::And => "AND",
+            FilterCombinator::Or => "OR",
+        }
+    }
+
+    /// Parse combinator from string
+    pub fn from_string(combinator: &str) -> Option<Self> {
+        match combinator {
+            "and" => Some(FilterCombinator::And),
+            "or" => Some(FilterCombinator::Or),
+            _ => None,
+        }
+    }
+}
+
+/// One condition of a `FilterGroup`: a single filter or a nested group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FilterCondition {
+    Filter(Filter),
+    Group(FilterGroup),
+}
+
+/// Filters combined with AND or OR into one parenthesized condition
+///
+/// Parsed from `filter[or][0][name][contains]=jo&filter[or][1][email][contains]=jo`.
+/// The number is the condition's position in the group; terms sharing a
+/// position are ANDed, and a position may hold a group of its own, such as
+/// `filter[or][1][and][0][age][gte]=18`, one level deep.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterGroup {
+    pub combinator: FilterCombinator,
+    pub conditions: Vec<FilterCondition>,
+}
+
+/// `(combinator, position)` of each group a term sits in, outermost first
+type GroupPath = Vec<(FilterCombinator, usize)>;
+
+/// How deep `filter[or][0][and][0]...` keys may nest
+const MAX_GROUP_DEPTH: usize = 2;
+
+impl FilterGroup {
+    /// Create a new filter group
+    pub fn new(combinator: FilterCombinator, conditions: Vec<FilterCondition>) -> Self {
+        Self { combinator, conditions }
+    }
+
+    /// Create a group matching when every condition matches
+    pub fn and(conditions: Vec<FilterCondition>) -> Self {
+        Self::new(FilterCombinator::And, conditions)
+    }
+
+    /// Create a group matching when any condition matches
+    pub fn or(conditions: Vec<FilterCondition>) -> Self {
+        Self::new(FilterCombinator::Or, conditions)
+    }
+
+    /// Every filter in the group, nested groups included
+    pub fn filters(&self) -> Vec<&Filter> {
+        self.conditions
+            .iter()
+            .flat_map(|condition| match condition {
+                FilterCondition::Filter(filter) => vec![filter],
+                FilterCondition::Group(group) => group.filters(),
+            })
+            .collect()
+    }
+
+    /// Whether a filter parameter key such as `or[0][name]` belongs to a group
+    pub fn is_group_key(key: &str) -> bool {
+        key.split_once('[')
+            .and_then(|(combinator, _)| FilterCombinator::from_string(combinator))
+            .is_some()
+    }
+
+    /// Parse `or[...]` and `and[...]` filter parameters into groups
+    ///
+    /// Terms on fields outside `allowed_fields`, and keys that cannot be
+    /// read, are dropped.
+    pub fn from_params(params: &HashMap<String, Value>, allowed_fields: &[&str]) -> Vec<FilterGroup> {
+        Self::parse(params, allowed_fields).0
+    }
+
+    /// Parse filter groups, reporting every term `from_params` would drop
+    pub fn from_params_strict(params: &HashMap<String, Value>, allowed_fields: &[&str]) -> Result<Vec<FilterGroup>, Vec<FilterError>> {
+        let (groups, errors) = Self::parse(params, allowed_fields);
+        if errors.is_empty() {
+            Ok(groups)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn parse(params: &HashMap<String, Value>, allowed_fields: &[&str]) -> (Vec<FilterGroup>, Vec<FilterError>) {
+        let mut keys: Vec<&String> = params.keys().filter(|key| Self::is_group_key(key)).collect();
+        keys.sort();
+
+        let mut terms: BTreeMap<FilterCombinator, Vec<(GroupPath, Filter)>> = BTreeMap::new();
+        let mut errors = Vec::new();
+        for key in keys {
+            let name = format!("filter[{}]", key.replacen('[', "][", 1));
+            let (path, field, operator) = match Self::parse_key(key) {
+                Ok(term) => term,
+                Err(reason) => {
+                    errors.push(FilterError::MalformedGroup { filter: name, reason });
+                    continue;
+                }
+            };
+
+            match Filter::parse_term(field, operator, &params[key], allowed_fields) {
+                Ok(filter) => terms.entry(path[0].0).or_default().push((path, filter.normalize_ids())),
+                Err(error) => errors.push(error.for_filter(name)),
+            }
+        }
+
+        let groups = terms
+            .into_iter()
+            .map(|(combinator, terms)| Self::group(combinator, terms))
+            .collect();
+        (groups, errors)
+    }
+
+    /// Split `or[0][and][1][age][gte]` into its group path, field and operator
+    fn parse_key(key: &str) -> Result<(GroupPath, &str, Option<&str>), String> {
+        let (head, rest) = key.split_once('[').ok_or("expected a group position")?;
+        let rest = rest.strip_suffix(']').ok_or("unclosed bracket")?;
+        let segments: Vec<&str> = std::iter::once(head).chain(rest.split("][")).collect();
+
+        let mut path = GroupPath::new();
+        let mut remaining = segments.as_slice();
+        while let Some(combinator) = remaining.first().and_then(|segment| FilterCombinator::from_string(segment)) {
+            let position = remaining
+                .get(1)
+                .and_then(|position| position.parse::<usize>().ok())
+                .ok_or_else(|| format!("expected a position after '{}'", combinator.to_sql().to_lowercase()))?;
+            path.push((combinator, position));
+            remaining = &remaining[2..];
+        }
+
+        if path.len() > MAX_GROUP_DEPTH {
+            return Err("filter groups nest one level deep".to_string());
+        }
+
+        match remaining {
+            [field] if !field.is_empty() => Ok((path, *field, None)),
+            [field, operator] if !field.is_empty() => Ok((path, *field, Some(*operator))),
+            _ => Err("expected a field and an optional operator".to_string()),
+        }
+    }
+
+    /// Build a group from terms whose paths start at this group's position
+    fn group(combinator: FilterCombinator, terms: Vec<(GroupPath, Filter)>) -> FilterGroup {
+        let mut positions: BTreeMap<usize, Vec<(GroupPath, Filter)>> = BTreeMap::new();
+        for (mut path, filter) in terms {
+            let (_, position) = path.remove(0);
+            positions.entry(position).or_default().push((path, filter));
+        }
+
+        FilterGroup::new(combinator, positions.into_values().map(Self::condition).collect())
+    }
+
+    /// The condition at one position: its filters ANDed with any nested groups
+    fn condition(terms: Vec<(GroupPath, Filter)>) -> FilterCondition {
+        let mut conditions = Vec::new();
+        let mut nested: BTreeMap<FilterCombinator, Vec<(GroupPath, Filter)>> = BTreeMap::new();
+        for (path, filter) in terms {
+            match path.first() {
+                Some(&(combinator, _)) => nested.entry(combinator).or_default().push((path, filter)),
+                None => conditions.push(FilterCondition::Filter(filter)),
+            }
+        }
+        conditions.extend(
+            nested
+                .into_iter()
+                .map(|(combinator, terms)| FilterCondition::Group(Self::group(combinator, terms))),
+        );
+
+        if conditions.len() == 1 {
+            conditions.remove(0)
+        } else {
+            FilterCondition::Group(FilterGroup::and(conditions))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::query_builder::FilterOperator;
+    use serde_json::json;
+
+    const ALLOWED: &[&str] = &["name", "email", "age"];
+
+    fn params(pairs: &[(&str, Value)]) -> HashMap<String, Value> {
+        pairs.iter().map(|(key, value)| (key.to_string(), value.clone())).collect()
+    }
+
+    #[test]
+    fn test_or_group_from_params() {
+        let groups = FilterGroup::from_params(&params(&[
+            ("or[0][name][contains]", json!("jo")),
+            ("or[1][email][contains]", json!("jo")),
+            ("name", json!("ignored")),
+        ]), ALLOWED);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].combinator, FilterCombinator::Or);
+        let fields: Vec<&str> = groups[0].filters().iter().map(|filter| filter.field.as_str()).collect();
+        assert_eq!(fields, vec!["name", "email"]);
+        assert!(groups[0].filters().iter().all(|filter| filter.operator == FilterOperator::Contains));
+    }
+
+    #[test]
+    fn test_nested_group_from_params() {
+        let groups = FilterGroup::from_params(&params(&[
+            ("or[0][name]", json!("Ada")),
+            ("or[1][and][0][age][gte]", json!("18")),
+            ("or[1][and][1][email][ends_with]", json!("@example.com")),
+        ]), ALLOWED);
+
+        let FilterCondition::Group(nested) = &groups[0].conditions[1] else {
+            panic!("expected a nested group");
+        };
+        assert_eq!(nested.combinator, FilterCombinator::And);
+        assert_eq!(nested.conditions.len(), 2);
+    }
+
+    #[test]
+    fn test_strict_group_errors() {
+        let errors = FilterGroup::from_params_strict(&params(&[
+            ("or[0][password]", json!("secret")),
+            ("or[x][name]", json!("Ada")),
+            ("or[0][and][0][or][0][name]", json!("Ada")),
+        ]), ALLOWED).unwrap_err();
+
+        assert_eq!(errors.len(), 3);
+        assert!(errors.contains(&FilterError::FieldNotAllowed {
+            filter: "filter[or][0][password]".to_string(),
+            field: "password".to_string(),
+        }));
+        assert!(errors.iter().all(|error| error.to_string().starts_with("filter[or]")));
+    }
+}
@@ -1,6 +1,137 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::app::models::HasModelType;
+use crate::app::query_builder::{Pagination, QueryParamsError, Queryable, SortDirection};
+
+/// Children loaded per parent for a has-many include when no page size is given
+pub const DEFAULT_INCLUDE_PER_PAGE: u32 = 20;
+
+/// Upper bound on children loaded per parent, whatever page size is requested
+pub const MAX_INCLUDE_PER_PAGE: u32 = 100;
+
+/// A has-many relationship that is eager loaded a page at a time
+///
+/// Children are matched to parents through `foreign_key` on the child table
+/// and paged per parent in `order_by` order. `allowed_fields` is the child
+/// model's `Queryable::allowed_fields`, which `fields[relation]` is checked against.
+#[derive(Debug, Clone)]
+pub struct HasMany {
+    pub relation: &'static str,
+    pub table: &'static str,
+    pub foreign_key: &'static str,
+    pub order_by: (&'static str, SortDirection),
+    pub allowed_fields: fn() -> Vec<&'static str>,
+}
+
+impl HasMany {
+    /// Reject a field selection naming a field the child model does not allow
+    pub fn validate_fields(&self, fields: &[String]) -> Result<(), QueryParamsError> {
+        Include::validate_fields(self.relation, fields, &(self.allowed_fields)())
+    }
+}
+
+/// A has-many relationship that can be counted with `include={relation}_count`
+///
+/// Each row gets `{relation}_count` from a correlated `COUNT(*)` subquery
+/// instead of the children themselves. With `soft_deletes`, children whose
+/// `deleted_at` is set are not counted.
+#[derive(Debug, Clone)]
+pub struct HasCount {
+    pub relation: &'static str,
+    pub table: &'static str,
+    pub foreign_key: &'static str,
+    pub soft_deletes: bool,
+}
+
+impl HasCount {
+    /// The include name and response key, `{relation}_count`
+    pub fn count_key(&self) -> String {
+        format!("{}_count", self.relation)
+    }
+
+    /// `(SELECT COUNT(*) ...) AS {relation}_count` for rows of `parent_table`
+    pub fn select_sql(&self, parent_table: &str) -> String {
+        let alias = self.count_key();
+        let mut conditions = vec![format!("{}.{} = {}.id", alias, self.foreign_key, parent_table)];
+        if self.soft_deletes {
+            conditions.push(format!("{}.deleted_at IS NULL", alias));
+        }
+
+        format!(
+            "(SELECT COUNT(*) FROM {} AS {} WHERE {}) AS {}",
+            self.table,
+            alias,
+            conditions.join(" AND "),
+            alias
+        )
+    }
+}
+
+/// A polymorphic belongs-to relationship, such as an activity's causer
+///
+/// `type_column` holds the related model's `HasModelType::model_type`, which
+/// `MorphTarget::find` resolves to a table. Each row gets the related record
+/// under `relation`, or null when the type is unknown or the record is gone.
+#[derive(Debug, Clone)]
+pub struct MorphTo {
+    pub relation: &'static str,
+    pub type_column: &'static str,
+    pub id_column: &'static str,
+}
+
+/// A model a `MorphTo` relationship can point at
+#[derive(Debug, Clone)]
+pub struct MorphTarget {
+    pub model_type: &'static str,
+    pub table: &'static str,
+    pub allowed_fields: fn() -> Vec<&'static str>,
+}
+
+impl MorphTarget {
+    pub fn of<T: Queryable + HasModelType>() -> Self {
+        Self {
+            model_type: T::model_type(),
+            table: T::table_name(),
+            allowed_fields: T::allowed_fields,
+        }
+    }
+
+    /// Models that are recorded as the causer or subject of activity
+    pub fn all() -> Vec<Self> {
+        use crate::app::models::{
+            city::City, conversation::Conversation, country::Country, district::District,
+            message::Message, organization::Organization, organization_domain::OrganizationDomain,
+            organization_position::OrganizationPosition, organization_position_level::OrganizationPositionLevel,
+            organization_type::OrganizationType, permission::Permission, province::Province, role::Role,
+            user::User, user_organization::UserOrganization, village::Village,
+        };
+
+        vec![
+            Self::of::<User>(),
+            Self::of::<Organization>(),
+            Self::of::<OrganizationType>(),
+            Self::of::<OrganizationDomain>(),
+            Self::of::<OrganizationPosition>(),
+            Self::of::<OrganizationPositionLevel>(),
+            Self::of::<UserOrganization>(),
+            Self::of::<Role>(),
+            Self::of::<Permission>(),
+            Self::of::<Country>(),
+            Self::of::<Province>(),
+            Self::of::<City>(),
+            Self::of::<District>(),
+            Self::of::<Village>(),
+            Self::of::<Conversation>(),
+            Self::of::<Message>(),
+        ]
+    }
+
+    pub fn find(model_type: &str) -> Option<Self> {
+        Self::all().into_iter().find(|target| target.model_type == model_type)
+    }
+}
+
 /// Include specification for eager loading relationships
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Include {
@@ -14,6 +145,8 @@ pub struct Include {
     pub filters: Option<HashMap<String, serde_json::Value>>,
     /// Sort to apply to the included relationship
     pub sort: Option<String>,
+    /// Page of children to load per parent for has-many relationships
+    pub pagination: Option<Pagination>,
 }
 
 impl Include {
@@ -25,6 +158,7 @@ impl Include {
             fields: None,
             filters: None,
             sort: None,
+            pagination: None,
         }
     }
 
@@ -52,6 +186,43 @@ impl Include {
         self
     }
 
+    /// Set the page of children to load per parent
+    pub fn with_pagination(mut self, pagination: Pagination) -> Self {
+        self.pagination = Some(pagination);
+        self
+    }
+
+    /// Reject a `fields[relation]` selection naming a field outside `allowed`
+    pub fn validate_fields(relation: &str, fields: &[String], allowed: &[&str]) -> Result<(), QueryParamsError> {
+        match fields.iter().find(|field| !allowed.contains(&field.as_str())) {
+            Some(field) => Err(QueryParamsError::IncludeFieldNotAllowed {
+                relation: relation.to_string(),
+                field: field.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Keep only the selected fields of loaded relationship rows, if fields were selected
+    ///
+    /// `data` is a list of rows, or an object keyed by parent id holding a row or a list of rows.
+    pub fn restrict_fields(&self, data: &mut serde_json::Value) {
+        let Some(fields) = &self.fields else {
+            return;
+        };
+
+        fn restrict(value: &mut serde_json::Value, fields: &[String], keyed: bool) {
+            match value {
+                serde_json::Value::Array(rows) => rows.iter_mut().for_each(|row| restrict(row, fields, false)),
+                serde_json::Value::Object(object) if keyed => object.values_mut().for_each(|row| restrict(row, fields, false)),
+                serde_json::Value::Object(object) => object.retain(|key, _| fields.contains(key)),
+                _ => {}
+            }
+        }
+
+        restrict(data, fields, true);
+    }
+
     /// Parse includes from string format
     /// Supports nested includes: "user,organization.positions,organization.positions.level"
     pub fn from_string(include_string: &str) -> Vec<Include> {
@@ -164,7 +335,8 @@ impl Include {
         let mut relationship_data = HashMap::new();
 
         // Load the main relationship
-        let main_data = T::load_relationship(ids, &self.relation, conn)?;
+        let mut main_data = T::load_relationship(ids, &self.relation, conn)?;
+        self.restrict_fields(&mut main_data);
         relationship_data.insert(self.relation.clone(), main_data);
 
         // Load nested relationships recursively
@@ -275,6 +447,24 @@ mod tests {
         assert_eq!(org_include.nested[0].nested[0].relation, "level");
     }
 
+    #[test]
+    fn test_has_count_select_sql() {
+        let relation = HasCount {
+            relation: "organizations",
+            table: "organizations",
+            foreign_key: "domain_id",
+            soft_deletes: true,
+        };
+
+        assert_eq!(relation.count_key(), "organizations_count");
+        assert_eq!(
+            relation.select_sql("organization_domains"),
+            "(SELECT COUNT(*) FROM organizations AS organizations_count \
+             WHERE organizations_count.domain_id = organization_domains.id \
+             AND organizations_count.deleted_at IS NULL) AS organizations_count"
+        );
+    }
+
     #[test]
     fn test_include_to_string() {
         let include = Include::new("organization")
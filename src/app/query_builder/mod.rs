@@ -1,5 +1,7 @@
+pub mod bind;
 pub mod builder;
 pub mod filter;
+pub mod filter_group;
 pub mod sort;
 pub mod include;
 pub mod pagination;
@@ -9,23 +11,34 @@ pub mod service;
 pub mod response;
 pub mod audit_loader;
 pub mod role_permission_loader;
+pub mod cache;
+pub mod export;
 
 // Re-exports for convenient access
+pub use bind::Bindings;
 pub use builder::{QueryBuilder, QueryBuilderExt};
-pub use filter::{Filter, FilterOperator, FilterValue};
+pub use filter::{Filter, FilterError, FilterOperator, FilterValue};
+pub use filter_group::{FilterCombinator, FilterCondition, FilterGroup};
 pub use sort::{Sort, SortDirection};
-pub use include::Include;
-pub use pagination::{Pagination, PaginationResult, PaginationType};
+pub use include::{Include, HasCount, HasMany, MorphTarget, MorphTo, DEFAULT_INCLUDE_PER_PAGE, MAX_INCLUDE_PER_PAGE};
+pub use pagination::{CursorAnchor, Pagination, PaginationResult, PaginationType, MAX_PER_PAGE};
 pub use traits::{Queryable, Filterable, Sortable, Includable};
 pub use executor::QueryExecutor;
 pub use service::{QueryBuilderService, QueryService};
 pub use response::{QueryResponse, QueryMeta, DataResponse, QueryErrorResponse, ResponseLinks, Link, CacheStatus};
 pub use audit_loader::AuditRelationshipLoader;
 pub use role_permission_loader::RolePermissionLoader;
+pub use cache::QueryCache;
+pub use export::{ExportFormat, QueryExport};
 
 use serde::Deserialize;
 use std::collections::HashMap;
-use axum::extract::Query;
+use axum::extract::{FromRequestParts, Query};
+use axum::http::request::Parts;
+use axum::http::{StatusCode, Uri};
+use axum::response::{IntoResponse, Json, Response};
+use crate::config::query_builder::QueryBuilderConfig;
+use crate::database::StatementTimedOut;
 
 /// Query parameters that can be passed to the query builder
 #[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
@@ -61,6 +74,17 @@ pub struct QueryParams {
     /// Custom append parameters
     #[serde(default)]
     pub append: HashMap<String, String>,
+
+    /// Export format (e.g., ?format=csv or ?format=ndjson); exports ignore pagination
+    pub format: Option<ExportFormat>,
+
+    /// Page per included relationship (e.g., ?page[messages]=2)
+    #[serde(skip)]
+    pub include_page: HashMap<String, u32>,
+
+    /// Items per page per included relationship (e.g., ?per_page[messages]=20)
+    #[serde(skip)]
+    pub include_per_page: HashMap<String, u32>,
 }
 
 impl Default for QueryParams {
@@ -75,16 +99,104 @@ impl Default for QueryParams {
             pagination_type: Some(PaginationType::default()),
             cursor: None,
             append: HashMap::new(),
+            format: None,
+            include_page: HashMap::new(),
+            include_per_page: HashMap::new(),
         }
     }
 }
 
+/// Extract QueryParams with `QueryParams::from_query_string`
+///
+/// Unlike `Query<QueryParams>`, this keeps the bracketed `filter[...]`,
+/// `fields[...]`, `page[...]` and `per_page[...]` keys. A malformed query
+/// string is answered with 400.
+impl<S> FromRequestParts<S> for QueryParams
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Self::from_query_string(parts.uri.query().unwrap_or_default()).map_err(|e| {
+            (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e.to_string() }))).into_response()
+        })
+    }
+}
+
 impl QueryParams {
     /// Create a new QueryParams instance from HTTP query parameters
     pub fn from_query(query: Query<QueryParams>) -> Self {
         query.0
     }
 
+    /// Parse QueryParams from a raw query string, including relationship pagination
+    pub fn from_query_string(query: &str) -> anyhow::Result<Self> {
+        let uri: Uri = format!("/?{}", query).parse()?;
+        let Query(params) = Query::<QueryParams>::try_from_uri(&uri)?;
+        Ok(params.with_filters(Some(query)).with_include_pagination(Some(query)))
+    }
+
+    /// Read `filter[field]` and `filter[field][operator]` keys from the raw query string
+    ///
+    /// Like `fields[resource]`, these keys are left untouched by the `Query`
+    /// extractor. They are stored the way `Filter::from_params` reads them:
+    /// `field` or `field[operator]`, with groups kept as `or[0][field][operator]`.
+    pub fn with_filters(mut self, query: Option<&str>) -> Self {
+        let Some(query) = query else {
+            return self;
+        };
+
+        for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+            let Some(term) = Self::bracketed(&key, "filter") else {
+                continue;
+            };
+            let key = match term.split_once("][") {
+                Some((field, operator)) => format!("{}[{}]", field, operator),
+                None => term.to_string(),
+            };
+            self.filter.insert(key, serde_json::Value::String(value.into_owned()));
+        }
+
+        self
+    }
+
+    /// Read `page[relation]`, `per_page[relation]` and `fields[resource]` keys from the raw query string
+    ///
+    /// The `Query` extractor leaves bracketed keys untouched, so the
+    /// `QueryParams` extractor reads them through here.
+    pub fn with_include_pagination(mut self, query: Option<&str>) -> Self {
+        let Some(query) = query else {
+            return self;
+        };
+
+        for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+            if let Some(resource) = Self::bracketed(&key, "fields") {
+                self.fields.insert(resource.to_string(), value.into_owned());
+                continue;
+            }
+
+            let Ok(value) = value.parse::<u32>() else {
+                continue;
+            };
+
+            if let Some(relation) = Self::bracketed(&key, "page") {
+                self.include_page.insert(relation.to_string(), value);
+            } else if let Some(relation) = Self::bracketed(&key, "per_page") {
+                self.include_per_page.insert(relation.to_string(), value);
+            }
+        }
+
+        self
+    }
+
+    fn bracketed<'a>(key: &'a str, name: &str) -> Option<&'a str> {
+        key.strip_prefix(name)?
+            .strip_prefix('[')?
+            .strip_suffix(']')
+            .filter(|relation| !relation.is_empty())
+    }
+
     /// Get filter value for a specific field
     pub fn get_filter(&self, field: &str) -> Option<&serde_json::Value> {
         self.filter.get(field)
@@ -95,6 +207,10 @@ impl QueryParams {
         let mut filters = Vec::new();
 
         for (field_key, value) in &self.filter {
+            if FilterGroup::is_group_key(field_key) {
+                continue;
+            }
+
             // Handle Laravel-style nested filter syntax: filter[field][operator]=value
             if let Some(parsed_filter) = self.parse_nested_filter(field_key, value) {
                 filters.push(parsed_filter);
@@ -178,7 +294,12 @@ impl QueryParams {
     }
 
     /// Parse include relationships with validation and nested support
-    pub fn parse_includes(&self, allowed_includes: &[&str]) -> Vec<Include> {
+    ///
+    /// Fails when the includes exceed the configured depth or count limits.
+    pub fn parse_includes(&self, allowed_includes: &[&str]) -> Result<Vec<Include>, QueryParamsError> {
+        let config = QueryBuilderConfig::global();
+        self.validate_includes(config.max_include_depth, config.max_includes)?;
+
         let include_strings = self.get_includes();
         let mut includes = Vec::new();
 
@@ -190,7 +311,31 @@ impl QueryParams {
             }
         }
 
-        includes
+        Ok(includes)
+    }
+
+    /// Reject includes nested deeper than `max_depth` or more than `max_includes` of them
+    pub fn validate_includes(&self, max_depth: usize, max_includes: usize) -> Result<(), QueryParamsError> {
+        let includes = self.get_includes();
+        if includes.len() > max_includes {
+            return Err(QueryParamsError::TooManyIncludes {
+                count: includes.len(),
+                max: max_includes,
+            });
+        }
+
+        for include in includes {
+            let depth = include.split('.').count();
+            if depth > max_depth {
+                return Err(QueryParamsError::IncludeTooDeep {
+                    include,
+                    depth,
+                    max: max_depth,
+                });
+            }
+        }
+
+        Ok(())
     }
 
     /// Validate if an include relationship is allowed
@@ -211,6 +356,54 @@ impl QueryParams {
         false
     }
 
+    /// Filters parsed strictly against `allowed_filters`
+    ///
+    /// Fails with every malformed filter at once, grouped ones included, so a
+    /// client can fix them in one round trip.
+    pub fn validate_filters(&self, allowed_filters: &[&str]) -> Result<Vec<Filter>, QueryParamsError> {
+        match (
+            Filter::from_params_strict(&self.filter, allowed_filters),
+            FilterGroup::from_params_strict(&self.filter, allowed_filters),
+        ) {
+            (Ok(filters), Ok(_)) => Ok(filters),
+            (filters, groups) => {
+                let mut errors = filters.err().unwrap_or_default();
+                errors.extend(groups.err().unwrap_or_default());
+                Err(QueryParamsError::MalformedFilters(errors))
+            }
+        }
+    }
+
+    /// Reject filters whose operator is not a `FilterOperator`, strict or not
+    ///
+    /// Lenient parsing would otherwise read `filter[name][nonsense]` as a
+    /// filter on a field named `name[nonsense]` and quietly drop it.
+    pub fn validate_filter_operators(&self, allowed_filters: &[&str]) -> Result<(), QueryParamsError> {
+        let Err(QueryParamsError::MalformedFilters(errors)) = self.validate_filters(allowed_filters) else {
+            return Ok(());
+        };
+
+        let unknown: Vec<FilterError> = errors
+            .into_iter()
+            .filter(|error| matches!(error, FilterError::UnknownOperator { .. }))
+            .collect();
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(QueryParamsError::MalformedFilters(unknown))
+        }
+    }
+
+    /// `filter[or][...]` and `filter[and][...]` groups parsed strictly against `allowed_filters`
+    pub fn validate_filter_groups(&self, allowed_filters: &[&str]) -> Result<Vec<FilterGroup>, QueryParamsError> {
+        FilterGroup::from_params_strict(&self.filter, allowed_filters).map_err(QueryParamsError::MalformedFilters)
+    }
+
+    /// `filter[or][...]` and `filter[and][...]` groups, dropping terms outside `allowed_filters`
+    pub fn get_filter_groups(&self, allowed_filters: &[&str]) -> Vec<FilterGroup> {
+        FilterGroup::from_params(&self.filter, allowed_filters)
+    }
+
     /// Get advanced filtering options with operator support
     pub fn get_advanced_filters(&self, allowed_filters: &[&str]) -> Vec<Filter> {
         self.parse_filters()
@@ -230,18 +423,125 @@ impl QueryParams {
         })
     }
 
-    /// Get pagination info
+    /// Fields selected with `fields[table]`, rejecting any the model does not allow
+    pub fn validate_fields<T: Queryable>(&self) -> Result<Option<Vec<String>>, QueryParamsError> {
+        let Some(fields) = self.get_fields(T::table_name()) else {
+            return Ok(None);
+        };
+        match fields.iter().find(|field| !T::is_field_allowed(field)) {
+            Some(field) => Err(QueryParamsError::FieldNotAllowed {
+                resource: T::table_name().to_string(),
+                field: field.clone(),
+            }),
+            None => Ok(Some(fields)),
+        }
+    }
+
+    /// Get pagination for an included relationship, capped at `MAX_INCLUDE_PER_PAGE`
+    pub fn get_include_pagination(&self, relation: &str) -> Pagination {
+        Pagination::page_based(
+            self.include_page.get(relation).copied().unwrap_or(1),
+            self.include_per_page
+                .get(relation)
+                .copied()
+                .unwrap_or(DEFAULT_INCLUDE_PER_PAGE)
+                .min(MAX_INCLUDE_PER_PAGE),
+        )
+    }
+
+    /// Get pagination info with the crate-wide page sizes
     pub fn get_pagination(&self) -> Pagination {
-        let pagination_type = self.pagination_type.unwrap_or_default();
-        match pagination_type {
-            PaginationType::Offset => Pagination::page_based(
-                self.page.unwrap_or(1),
-                self.per_page.unwrap_or(15),
-            ),
-            PaginationType::Cursor => Pagination::cursor(
-                self.per_page.unwrap_or(15),
-                self.cursor.clone(),
-            ),
+        let config = QueryBuilderConfig::global();
+        self.get_pagination_with(config.default_per_page, config.max_per_page)
+    }
+
+    /// Get pagination info with the page sizes of a model
+    pub fn get_pagination_for<T: Queryable>(&self) -> Pagination {
+        self.get_pagination_with(T::default_per_page(), T::max_per_page())
+    }
+
+    /// Get pagination info, `per_page` defaulting to `default_per_page` and clamped to `max_per_page`
+    pub fn get_pagination_with(&self, default_per_page: u32, max_per_page: u32) -> Pagination {
+        let per_page = self.per_page.unwrap_or(default_per_page);
+        let pagination = match self.pagination_type.unwrap_or_default() {
+            PaginationType::Offset => Pagination::page_based(self.page.unwrap_or(1), per_page),
+            PaginationType::Cursor => Pagination::cursor(per_page, self.cursor.clone()),
+        };
+        pagination.with_per_page(per_page, max_per_page)
+    }
+
+    /// Reject a `per_page` above the model's maximum when strict mode is on
+    pub fn validate_per_page<T: Queryable>(&self, strict: bool) -> Result<(), QueryParamsError> {
+        match self.per_page {
+            Some(requested) if strict && requested > T::max_per_page() => Err(QueryParamsError::PerPageTooLarge {
+                requested,
+                max: T::max_per_page(),
+            }),
+            _ => Ok(()),
         }
     }
+}
+
+/// Query parameters a client got wrong
+#[derive(Debug, thiserror::Error)]
+pub enum QueryParamsError {
+    #[error("per_page may not be greater than {max}, got {requested}")]
+    PerPageTooLarge { requested: u32, max: u32 },
+    #[error("include '{include}' is nested {depth} levels deep, the maximum is {max}")]
+    IncludeTooDeep { include: String, depth: usize, max: usize },
+    #[error("{count} includes were requested, the maximum is {max}")]
+    TooManyIncludes { count: usize, max: usize },
+    #[error("field '{field}' is not allowed on '{resource}'")]
+    FieldNotAllowed { resource: String, field: String },
+    #[error("field '{field}' is not allowed on include '{relation}'")]
+    IncludeFieldNotAllowed { relation: String, field: String },
+    #[error("malformed filters: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    MalformedFilters(Vec<FilterError>),
+}
+
+impl QueryParamsError {
+    /// 400 for bad query parameters, 504 for a query cancelled by the
+    /// statement timeout, 500 for any other query failure
+    pub fn status_code(error: &anyhow::Error) -> axum::http::StatusCode {
+        if error.downcast_ref::<QueryParamsError>().is_some() {
+            axum::http::StatusCode::BAD_REQUEST
+        } else if StatementTimedOut::from_error(error).is_some() {
+            axum::http::StatusCode::GATEWAY_TIMEOUT
+        } else {
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+
+    /// 400 response for a query parameter error, listing each malformed filter
+    /// and keying a rejected field selection by its `fields[...]` parameter
+    ///
+    /// A statement timeout gets a 504, and any other error a 500 with its message.
+    pub fn response(error: &anyhow::Error) -> axum::response::Response {
+        use axum::response::IntoResponse;
+
+        let message = match StatementTimedOut::from_error(error) {
+            Some(timed_out) => timed_out.to_string(),
+            None => error.to_string(),
+        };
+        let mut body = serde_json::json!({ "error": message });
+        match error.downcast_ref::<QueryParamsError>() {
+            Some(QueryParamsError::MalformedFilters(filters)) => {
+                body["filters"] = filters
+                    .iter()
+                    .map(|filter| {
+                        let mut entry = serde_json::json!(filter);
+                        entry["message"] = serde_json::Value::String(filter.to_string());
+                        entry
+                    })
+                    .collect();
+            }
+            Some(rejected @ QueryParamsError::FieldNotAllowed { resource, .. })
+            | Some(rejected @ QueryParamsError::IncludeFieldNotAllowed { relation: resource, .. }) => {
+                body["errors"] = serde_json::json!({ format!("fields[{}]", resource): [rejected.to_string()] });
+            }
+            _ => {}
+        }
+
+        (Self::status_code(error), axum::Json(body)).into_response()
+    }
 }
\ No newline at end of file
@@ -2,6 +2,10 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use jsonwebtoken::{encode, decode, Header, EncodingKey, DecodingKey, Validation, Algorithm};
 use std::time::{SystemTime, UNIX_EPOCH};
+use crate::app::query_builder::SortDirection;
+
+/// Ceiling of the `Pagination` constructors; models raise or lower it with `Queryable::max_per_page`
+pub const MAX_PER_PAGE: u32 = 100;
 
 /// JWT claims for secure cursor data
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,6 +55,44 @@ pub struct CursorData {
     pub position: u32,
     /// Page size for consistency checks
     pub per_page: u32,
+    /// Sort values of the last row returned, when the cursor was issued by `paginate_keyset`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<CursorAnchor>,
+}
+
+/// The last row of a cursor page, recorded by the values it was sorted on
+///
+/// The next page starts after these values rather than at an offset or at
+/// the anchor row itself, so it stays correct when rows before the cursor,
+/// the anchor included, are deleted between requests.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CursorAnchor {
+    /// `ORDER BY` columns, tie-breaker last
+    pub columns: Vec<String>,
+    /// The row's value for each of `columns`
+    #[schema(value_type = Vec<Object>)]
+    pub values: Vec<serde_json::Value>,
+}
+
+impl CursorAnchor {
+    /// Anchor at `row`, `None` when the row lacks one of the sort columns
+    pub fn from_row(row: &serde_json::Value, sorts: &[(String, SortDirection)]) -> Option<Self> {
+        let values = sorts.iter()
+            .map(|(column, _)| row.get(column).cloned())
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            columns: sorts.iter().map(|(column, _)| column.clone()).collect(),
+            values,
+        })
+    }
+
+    /// Whether the anchor was taken under the same sort columns
+    pub fn matches(&self, sorts: &[(String, SortDirection)]) -> bool {
+        !sorts.is_empty()
+            && self.columns.len() == sorts.len()
+            && self.columns.iter().zip(sorts).all(|(column, (sort, _))| column == sort)
+    }
 }
 
 impl CursorData {
@@ -60,6 +102,7 @@ impl CursorData {
             timestamp,
             position,
             per_page,
+            anchor: None,
         }
     }
 
@@ -158,7 +201,7 @@ impl Pagination {
     pub fn new(page: u32, per_page: u32) -> Self {
         Self {
             page: page.max(1),
-            per_page: per_page.clamp(1, 100),
+            per_page: per_page.clamp(1, MAX_PER_PAGE),
             pagination_type: PaginationType::default(),
             cursor: None,
         }
@@ -168,7 +211,7 @@ impl Pagination {
     pub fn new_with_type(page: u32, per_page: u32, pagination_type: PaginationType) -> Self {
         Self {
             page: page.max(1),
-            per_page: per_page.clamp(1, 100),
+            per_page: per_page.clamp(1, MAX_PER_PAGE),
             pagination_type,
             cursor: None,
         }
@@ -178,7 +221,7 @@ impl Pagination {
     pub fn cursor(per_page: u32, cursor: Option<String>) -> Self {
         Self {
             page: 1, // Not used for cursor pagination
-            per_page: per_page.clamp(1, 100),
+            per_page: per_page.clamp(1, MAX_PER_PAGE),
             pagination_type: PaginationType::Cursor,
             cursor,
         }
@@ -188,12 +231,18 @@ impl Pagination {
     pub fn page_based(page: u32, per_page: u32) -> Self {
         Self {
             page: page.max(1),
-            per_page: per_page.clamp(1, 100),
+            per_page: per_page.clamp(1, MAX_PER_PAGE),
             pagination_type: PaginationType::Offset,
             cursor: None,
         }
     }
 
+    /// Same pagination with `per_page` clamped to `max` rather than `MAX_PER_PAGE`
+    pub fn with_per_page(mut self, per_page: u32, max: u32) -> Self {
+        self.per_page = per_page.clamp(1, max.max(1));
+        self
+    }
+
     /// Check if this is cursor-based pagination
     pub fn is_cursor(&self) -> bool {
         self.pagination_type == PaginationType::Cursor
@@ -214,6 +263,48 @@ impl Pagination {
         self.per_page
     }
 
+    /// Rows already returned before this cursor page, 0 without a valid cursor
+    pub fn cursor_position(&self) -> u32 {
+        self.cursor
+            .as_deref()
+            .and_then(|cursor| self.decode_cursor(cursor))
+            .map_or(0, |cursor| cursor.position)
+    }
+
+    /// Anchor of the cursor, when it was issued by `paginate_keyset`
+    pub fn cursor_anchor(&self) -> Option<CursorAnchor> {
+        self.cursor
+            .as_deref()
+            .and_then(|cursor| self.decode_cursor(cursor))
+            .and_then(|cursor| cursor.anchor)
+    }
+
+    /// Cursor pagination whose next cursor anchors on the last row's sort values
+    ///
+    /// `sorts` are the query's `ORDER BY` columns, tie-breaker included. When
+    /// the last row lacks one of them the next cursor falls back to a position.
+    pub fn paginate_keyset(
+        &self,
+        data: Vec<serde_json::Value>,
+        sorts: &[(String, SortDirection)],
+    ) -> PaginationResult<serde_json::Value> {
+        let mut result = self.paginate_cursor(data);
+        if result.pagination.next_cursor.is_none() {
+            return result;
+        }
+
+        if let Some(anchor) = result.data.last().and_then(|row| CursorAnchor::from_row(row, sorts)) {
+            let cursor_data = CursorData {
+                timestamp: chrono::Utc::now().timestamp_millis(),
+                position: self.cursor_position() + result.data.len() as u32,
+                per_page: self.per_page,
+                anchor: Some(anchor),
+            };
+            result.pagination.next_cursor = self.encode_cursor(&cursor_data);
+        }
+        result
+    }
+
     /// Calculate pagination info from total count
     pub fn paginate<T>(&self, total: u64, data: Vec<T>) -> PaginationResult<T> {
         match self.pagination_type {
@@ -319,13 +410,14 @@ impl Pagination {
 
         // Use timestamp-based cursor for better consistency
         let timestamp = chrono::Utc::now().timestamp_millis();
-        let position = data.len();
+        let position = self.cursor_position() as usize + data.len();
 
         // Create a base64-encoded cursor containing timestamp and position
         let cursor_data = CursorData {
             timestamp,
             position: position as u32,
             per_page: self.per_page,
+            anchor: None,
         };
 
         self.encode_cursor(&cursor_data)
@@ -338,6 +430,7 @@ impl Pagination {
             timestamp,
             position,
             per_page: self.per_page,
+            anchor: None,
         };
         self.encode_cursor(&cursor_data)
     }
@@ -429,6 +522,7 @@ impl Pagination {
             timestamp: timestamp - (self.per_page as i64 * 1000), // Go back in time
             position: 0,
             per_page: self.per_page,
+            anchor: None,
         };
 
         self.encode_cursor(&cursor_data)
@@ -769,6 +863,24 @@ mod tests {
         assert!(pagination.is_offset());
     }
 
+    #[test]
+    fn test_keyset_cursor_anchors_on_last_row() {
+        let sorts = vec![("created_at".to_string(), SortDirection::Desc), ("id".to_string(), SortDirection::Asc)];
+        let data: Vec<serde_json::Value> = (1..=3)
+            .map(|i| serde_json::json!({ "id": format!("row-{}", i), "created_at": format!("2025-01-0{}", i) }))
+            .collect();
+
+        let result = Pagination::cursor(2, None).paginate_keyset(data, &sorts);
+        assert_eq!(result.data.len(), 2);
+
+        let next = Pagination::cursor(2, result.pagination.next_cursor);
+        let anchor = next.cursor_anchor().expect("next cursor is anchored");
+        assert_eq!(anchor.values, vec![serde_json::json!("2025-01-02"), serde_json::json!("row-2")]);
+        assert!(anchor.matches(&sorts));
+        assert!(!anchor.matches(&sorts[..1]));
+        assert_eq!(next.cursor_position(), 2);
+    }
+
     #[test]
     fn test_jwt_cursor_encode_decode() {
         let pagination = Pagination::cursor(20, None);
@@ -79,7 +79,7 @@ pub struct QueryMeta {
 }
 
 /// Cache status for query responses
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum CacheStatus {
     /// Cache hit - data served from cache
@@ -92,6 +92,17 @@ pub enum CacheStatus {
     Disabled,
 }
 
+impl CacheStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheStatus::Hit => "hit",
+            CacheStatus::Miss => "miss",
+            CacheStatus::Stale => "stale",
+            CacheStatus::Disabled => "disabled",
+        }
+    }
+}
+
 /// HATEOAS links for API navigation
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ResponseLinks {
@@ -1,7 +1,14 @@
 use crate::app::query_builder::{QueryBuilder, QueryBuilderExt, QueryExecutor, QueryParams, Queryable, Filterable, Sortable, Includable, PaginationResult};
+use crate::app::query_builder::{CursorAnchor, ExportFormat, QueryExport};
+use crate::config::query_builder::QueryBuilderConfig;
 use crate::database::{DbPool};
 use anyhow::Result;
 use axum::extract::Query;
+use axum::response::Response;
+use futures::stream::BoxStream;
+
+/// Rows read from the database per chunk of a stream
+pub const STREAM_CHUNK_SIZE: u32 = 1_000;
 
 /// Service trait for models that can be queried using the query builder
 /// This provides a high-level interface for controllers to use
@@ -49,6 +56,29 @@ where
         QueryExecutor::execute_count(builder, &mut conn)
     }
 
+    /// Stream every matching row as JSON, ignoring pagination
+    fn stream(
+        query_params: Query<QueryParams>,
+        pool: &DbPool,
+    ) -> Result<BoxStream<'static, Result<serde_json::Value>>>
+    where
+        T: Send + 'static,
+    {
+        QueryService::stream::<T>(query_params, pool)
+    }
+
+    /// Stream every matching row as CSV or NDJSON, ignoring pagination
+    fn export(
+        query_params: Query<QueryParams>,
+        format: ExportFormat,
+        pool: &DbPool,
+    ) -> Result<Response>
+    where
+        T: Send + 'static,
+    {
+        QueryExport::new().response::<T>(query_params.0, format, pool)
+    }
+
     /// Create a custom query builder
     fn query() -> QueryBuilder<T> {
         T::query()
@@ -116,6 +146,61 @@ impl QueryService {
         QueryExecutor::execute_paginated(builder, &mut conn)
     }
 
+    /// Stream every row matching the query parameters, ignoring pagination
+    ///
+    /// The parameters are validated before anything is read, exactly as for
+    /// `query_with_params`, so a rejected filter or sort fails here rather
+    /// than partway through the stream.
+    pub fn stream<T>(
+        query_params: Query<QueryParams>,
+        pool: &DbPool,
+    ) -> Result<BoxStream<'static, Result<serde_json::Value>>>
+    where
+        T: Queryable + Clone + Send + 'static,
+    {
+        let builder = T::from_params(query_params.0)?;
+        Ok(Self::stream_builder(builder, pool, STREAM_CHUNK_SIZE))
+    }
+
+    /// Stream every row matching a query builder, `chunk_size` rows at a time
+    ///
+    /// Each chunk is read on its own pooled connection and continues after the
+    /// last row of the one before, so no connection is held between chunks and
+    /// the next chunk is only read once the consumer has taken the current one.
+    pub fn stream_builder<T>(
+        builder: QueryBuilder<T>,
+        pool: &DbPool,
+        chunk_size: u32,
+    ) -> BoxStream<'static, Result<serde_json::Value>>
+    where
+        T: Queryable + Clone + Send + 'static,
+    {
+        let pool = pool.clone();
+        let chunk_size = chunk_size.max(1);
+
+        Box::pin(async_stream::try_stream! {
+            let mut after: Option<CursorAnchor> = None;
+
+            loop {
+                let (builder, pool, anchor) = (builder.clone(), pool.clone(), after.take());
+                let (rows, next) = tokio::task::spawn_blocking(move || -> Result<_> {
+                    let mut conn = pool.get()?;
+                    QueryExecutor::execute_keyset_chunk(&builder, anchor.as_ref(), chunk_size, &mut conn)
+                }).await??;
+
+                let exhausted = (rows.len() as u32) < chunk_size;
+                for row in rows {
+                    yield row;
+                }
+
+                match next {
+                    Some(next) if !exhausted => after = Some(next),
+                    _ => break,
+                }
+            }
+        })
+    }
+
     /// Build a query for a specific model type
     pub fn for_model<T>() -> QueryBuilder<T>
     where
@@ -146,12 +231,19 @@ impl QueryService {
     {
         let mut conn = pool.get()?;
         let params = query_params.0;
+        let config = QueryBuilderConfig::global();
+        params.validate_per_page::<T>(config.strict_per_page)?;
 
         // Build advanced query using enhanced traits
         let mut builder = T::query();
 
         // Apply advanced filters using the new Filterable trait
-        let advanced_filters = params.get_advanced_filters(&T::allowed_filters());
+        let advanced_filters = if config.strict_filters {
+            params.validate_filters(&T::allowed_filters())?
+        } else {
+            params.validate_filter_operators(&T::allowed_filters())?;
+            params.get_advanced_filters(&T::allowed_filters())
+        };
         for filter in advanced_filters {
             builder = builder.filter(filter);
         }
@@ -162,18 +254,18 @@ impl QueryService {
         }
 
         // Apply relationship includes using the new Includable trait
-        let validated_includes = params.parse_includes(&T::allowed_includes());
+        let validated_includes = params.parse_includes(&T::allowed_includes())?;
         for include in validated_includes {
             builder = builder.include(include);
         }
 
         // Apply field selection if specified
-        if let Some(fields) = params.get_fields(&T::table_name()) {
+        if let Some(fields) = params.validate_fields::<T>()? {
             builder = builder.select(fields);
         }
 
         // Apply pagination
-        let pagination = params.get_pagination();
+        let pagination = params.get_pagination_for::<T>();
         builder = builder.paginate(pagination);
 
         QueryExecutor::execute_paginated(builder, &mut conn)
@@ -265,30 +357,7 @@ mod tests {
     impl_query_builder_service!(TestModel);
 
     // Enhanced test model implementing all new traits
-    impl crate::app::query_builder::Filterable for TestModel {
-        fn apply_basic_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-            match (column, operator) {
-                ("name", "contains") => {
-                    format!("LOWER({}) LIKE LOWER('%{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-                }
-                ("name", "starts_with") => {
-                    format!("LOWER({}) LIKE LOWER('{}%')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-                }
-                ("name", "ends_with") => {
-                    format!("LOWER({}) LIKE LOWER('%{}')", column, value.as_str().unwrap_or("").replace('\'', "''"))
-                }
-                _ => {
-                    match value {
-                        serde_json::Value::String(s) => format!("{} {} '{}'", column, operator, s.replace('\'', "''")),
-                        serde_json::Value::Number(n) => format!("{} {} {}", column, operator, n),
-                        serde_json::Value::Bool(b) => format!("{} {} {}", column, operator, b),
-                        serde_json::Value::Null => format!("{} IS NULL", column),
-                        _ => format!("{} {} '{}'", column, operator, value.to_string().replace('\'', "''"))
-                    }
-                }
-            }
-        }
-    }
+    impl crate::app::query_builder::Filterable for TestModel {}
 
     impl crate::app::query_builder::Sortable for TestModel {
         fn apply_basic_sort(column: &str, direction: &str) -> String {
@@ -342,29 +411,24 @@ mod tests {
 
     #[test]
     fn test_enhanced_filterable_trait() {
-        use crate::app::query_builder::Filterable;
-
-        // Test contains filter
-        let sql = TestModel::apply_basic_filter("name", "contains", &serde_json::json!("john"));
-        assert_eq!(sql, "LOWER(name) LIKE LOWER('%john%')");
+        use crate::app::query_builder::{Bindings, Filter, Filterable};
 
-        // Test starts_with filter
-        let sql = TestModel::apply_basic_filter("name", "starts_with", &serde_json::json!("test"));
-        assert_eq!(sql, "LOWER(name) LIKE LOWER('test%')");
+        let mut bindings = Bindings::new();
 
-        // Test ends_with filter
-        let sql = TestModel::apply_basic_filter("name", "ends_with", &serde_json::json!("ing"));
-        assert_eq!(sql, "LOWER(name) LIKE LOWER('%ing')");
+        // Pattern operators bind the pattern with its wildcards
+        let sql = TestModel::apply_filter(&Filter::contains("name", "jo%hn"), &mut bindings);
+        assert_eq!(sql, "name ILIKE $1");
 
-        // Test basic equality with different value types
-        let sql = TestModel::apply_basic_filter("id", "=", &serde_json::json!(123));
-        assert_eq!(sql, "id = 123");
+        let sql = TestModel::apply_filter(&Filter::eq("id", 123), &mut bindings);
+        assert_eq!(sql, "id = $2");
 
-        let sql = TestModel::apply_basic_filter("active", "=", &serde_json::json!(true));
-        assert_eq!(sql, "active = true");
-
-        let sql = TestModel::apply_basic_filter("deleted_at", "=", &serde_json::json!(null));
+        let sql = TestModel::apply_filter(&Filter::is_null("deleted_at"), &mut bindings);
         assert_eq!(sql, "deleted_at IS NULL");
+
+        assert_eq!(
+            bindings.values(),
+            &[Some("%jo\\%hn%".to_string()), Some("123".to_string())]
+        );
     }
 
     #[test]
@@ -1,4 +1,4 @@
-use crate::app::query_builder::SortDirection;
+use crate::app::query_builder::{Bindings, Filter, HasCount, HasMany, MorphTo, SortDirection};
 use diesel::pg::PgConnection;
 use anyhow::Result;
 
@@ -21,11 +21,48 @@ pub trait Queryable: Sized {
         vec![]
     }
 
+    /// Has-many relationships loaded a page at a time when included
+    fn has_many_relations() -> Vec<HasMany> {
+        vec![]
+    }
+
+    /// Has-many relationships counted when `{relation}_count` is included
+    fn count_relations() -> Vec<HasCount> {
+        vec![]
+    }
+
+    /// Polymorphic belongs-to relationships loaded when included
+    fn morph_to_relations() -> Vec<MorphTo> {
+        vec![]
+    }
+
+    /// Fields of the related model `fields[relation]` may select for an included relationship
+    ///
+    /// Defaults to the child model's fields for has-many relationships. Models
+    /// whose other includes are loaded through `Includable` list them here so
+    /// their field selections are checked too.
+    fn include_fields(relation: &str) -> Option<Vec<&'static str>> {
+        Self::has_many_relations()
+            .into_iter()
+            .find(|has_many| has_many.relation == relation)
+            .map(|has_many| (has_many.allowed_fields)())
+    }
+
     /// Default sort field and direction
     fn default_sort() -> Option<(&'static str, SortDirection)> {
         None
     }
 
+    /// Page size when a request does not pass `per_page`
+    fn default_per_page() -> u32 {
+        crate::config::query_builder::QueryBuilderConfig::global().default_per_page
+    }
+
+    /// Largest page a request may ask for; larger `per_page` values are clamped
+    fn max_per_page() -> u32 {
+        crate::config::query_builder::QueryBuilderConfig::global().max_per_page
+    }
+
     /// Default fields to select when no fields are specified
     fn default_fields() -> Vec<&'static str> {
         Self::allowed_fields()
@@ -46,117 +83,23 @@ pub trait Queryable: Sized {
         Self::allowed_fields().contains(&field)
     }
 
-    /// Check if an include relationship is allowed
+    /// Check if an include relationship, or a `{relation}_count` include, is allowed
     fn is_include_allowed(include: &str) -> bool {
         Self::allowed_includes().contains(&include)
+            || Self::count_relations().iter().any(|relation| relation.count_key() == include)
     }
 }
 
-/// Trait for models that support filtering with complex operations
+/// Trait for models that support filtering
+///
+/// Conditions are built by `Bindings::filter`, the same path the query
+/// executor uses, so filter values are bound as placeholders and never
+/// written into the SQL.
 pub trait Filterable {
-    /// Apply basic filtering - implementation depends on the specific model
-    fn apply_basic_filter(column: &str, operator: &str, value: &serde_json::Value) -> String;
-
-    /// Apply range filtering (e.g. BETWEEN, >, <, >=, <=)
-    fn apply_range_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match operator {
-            "between" => {
-                if let Some(values) = value.as_array() {
-                    if values.len() == 2 {
-                        format!("{} BETWEEN {} AND {}", column,
-                               Self::format_filter_value(&values[0]),
-                               Self::format_filter_value(&values[1]))
-                    } else {
-                        format!("{} = {}", column, Self::format_filter_value(value))
-                    }
-                } else {
-                    format!("{} = {}", column, Self::format_filter_value(value))
-                }
-            },
-            "gt" | ">" => format!("{} > {}", column, Self::format_filter_value(value)),
-            "gte" | ">=" => format!("{} >= {}", column, Self::format_filter_value(value)),
-            "lt" | "<" => format!("{} < {}", column, Self::format_filter_value(value)),
-            "lte" | "<=" => format!("{} <= {}", column, Self::format_filter_value(value)),
-            _ => Self::apply_basic_filter(column, operator, value)
-        }
-    }
-
-    /// Apply IN filtering for multiple values
-    fn apply_in_filter(column: &str, values: &[serde_json::Value]) -> String {
-        if values.is_empty() {
-            return "1=0".to_string(); // No matches
-        }
-        let formatted_values: Vec<String> = values.iter()
-            .map(|v| Self::format_filter_value(v))
-            .collect();
-        format!("{} IN ({})", column, formatted_values.join(", "))
-    }
-
-    /// Apply LIKE filtering for text search
-    fn apply_like_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        let search_value = if let Some(text) = value.as_str() {
-            match operator {
-                "like" => format!("'{}'", text.replace("'", "''")),
-                "ilike" => format!("'{}'", text.replace("'", "''")),
-                "starts_with" => format!("'{}%'", text.replace("'", "''")),
-                "ends_with" => format!("'%{}'", text.replace("'", "''")),
-                "contains" => format!("'%{}%'", text.replace("'", "''")),
-                _ => format!("'{}'", text.replace("'", "''"))
-            }
-        } else {
-            "'%'".to_string()
-        };
-
-        match operator {
-            "ilike" | "contains" | "starts_with" | "ends_with" => {
-                format!("{} ILIKE {}", column, search_value)
-            },
-            _ => format!("{} LIKE {}", column, search_value)
-        }
-    }
-
-    /// Apply NULL/NOT NULL filtering
-    fn apply_null_filter(column: &str, is_null: bool) -> String {
-        if is_null {
-            format!("{} IS NULL", column)
-        } else {
-            format!("{} IS NOT NULL", column)
-        }
-    }
-
-    /// Format a single filter value for SQL
-    fn format_filter_value(value: &serde_json::Value) -> String {
-        match value {
-            serde_json::Value::String(s) => format!("'{}'", s.replace("'", "''")),
-            serde_json::Value::Number(n) => n.to_string(),
-            serde_json::Value::Bool(b) => b.to_string(),
-            serde_json::Value::Null => "NULL".to_string(),
-            _ => format!("'{}'", value.to_string().replace("'", "''"))
-        }
-    }
-
-    /// Apply complex filtering based on operator type
-    fn apply_filter(column: &str, operator: &str, value: &serde_json::Value) -> String {
-        match operator {
-            "eq" | "=" => Self::apply_basic_filter(column, "=", value),
-            "ne" | "!=" => Self::apply_basic_filter(column, "!=", value),
-            "gt" | ">" | "gte" | ">=" | "lt" | "<" | "lte" | "<=" | "between" => {
-                Self::apply_range_filter(column, operator, value)
-            },
-            "in" => {
-                if let Some(values) = value.as_array() {
-                    Self::apply_in_filter(column, values)
-                } else {
-                    Self::apply_basic_filter(column, "=", value)
-                }
-            },
-            "like" | "ilike" | "contains" | "starts_with" | "ends_with" => {
-                Self::apply_like_filter(column, operator, value)
-            },
-            "is_null" => Self::apply_null_filter(column, true),
-            "is_not_null" => Self::apply_null_filter(column, false),
-            _ => Self::apply_basic_filter(column, operator, value)
-        }
+    /// Condition for `filter` with its values added to `bindings`, or an empty
+    /// string when it adds none
+    fn apply_filter(filter: &Filter, bindings: &mut Bindings) -> String {
+        bindings.filter(filter)
     }
 }
 
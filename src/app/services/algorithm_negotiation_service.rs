@@ -0,0 +1,106 @@
+use anyhow::Result;
+use diesel::prelude::*;
+use serde::Serialize;
+use utoipa::ToSchema;
+use crate::database::DbPool;
+use crate::schema::algorithm_compatibility_matrix;
+use crate::app::models::algorithm_compatibility_matrix::{AlgorithmCompatibilityMatrix, CompatibilityLevel};
+use crate::app::models::device::Device;
+use crate::app::services::conversation_service::ConversationError;
+
+/// Session ciphers the server will negotiate, strongest first
+pub const ENCRYPTION_PREFERENCE: [&str; 3] = ["aes-256-gcm", "chacha20-poly1305", "aes-128-gcm"];
+
+/// Key agreement algorithms the server will negotiate, strongest first
+pub const KEY_EXCHANGE_PREFERENCE: [&str; 2] = ["curve25519", "p256-ecdh"];
+
+/// Errors raised while negotiating a session suite
+#[derive(Debug, thiserror::Error)]
+pub enum AlgorithmNegotiationError {
+    #[error("Devices share no compatible encryption suite")]
+    NoCompatibleSuite,
+}
+
+impl AlgorithmNegotiationError {
+    /// HTTP status for an error returned while negotiating a suite
+    pub fn status_code(error: &anyhow::Error) -> axum::http::StatusCode {
+        use axum::http::StatusCode;
+
+        match error.downcast_ref::<AlgorithmNegotiationError>() {
+            Some(AlgorithmNegotiationError::NoCompatibleSuite) => StatusCode::CONFLICT,
+            None => ConversationError::status_code(error),
+        }
+    }
+}
+
+/// Cipher and key agreement both devices will use for a new session
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct NegotiatedSuite {
+    pub encryption_algorithm: String,
+    pub key_exchange_algorithm: String,
+    /// From the compatibility matrix, or full when the pair has no entry
+    pub compatibility_level: String,
+}
+
+pub struct AlgorithmNegotiationService;
+
+impl AlgorithmNegotiationService {
+    /// Pick the strongest suite both algorithm sets support that the matrix does not rule out
+    pub fn negotiate(
+        local: &[String],
+        remote: &[String],
+        matrix: &[AlgorithmCompatibilityMatrix],
+    ) -> Result<NegotiatedSuite> {
+        let supported = |algorithm: &str| {
+            local.iter().any(|a| a.eq_ignore_ascii_case(algorithm)) && remote.iter().any(|a| a.eq_ignore_ascii_case(algorithm))
+        };
+
+        for encryption in ENCRYPTION_PREFERENCE.iter().copied().filter(|a| supported(a)) {
+            for key_exchange in KEY_EXCHANGE_PREFERENCE.iter().copied().filter(|a| supported(a)) {
+                let entry = matrix.iter().find(|entry| {
+                    entry.encryption_algorithm_a == encryption
+                        && entry.encryption_algorithm_b == encryption
+                        && entry.key_exchange_algorithm_a == key_exchange
+                        && entry.key_exchange_algorithm_b == key_exchange
+                });
+
+                let compatibility_level = match entry {
+                    Some(entry) if !entry.is_compatible || matches!(entry.compatibility_level_enum(), CompatibilityLevel::None) => continue,
+                    Some(entry) => entry.compatibility_level.clone(),
+                    None => CompatibilityLevel::Full.into(),
+                };
+
+                return Ok(NegotiatedSuite {
+                    encryption_algorithm: encryption.to_string(),
+                    key_exchange_algorithm: key_exchange.to_string(),
+                    compatibility_level,
+                });
+            }
+        }
+
+        Err(AlgorithmNegotiationError::NoCompatibleSuite.into())
+    }
+
+    /// Negotiate the suite for a new session between two devices
+    pub fn negotiate_for_devices(pool: &DbPool, local: &Device, remote: &Device) -> Result<NegotiatedSuite> {
+        let mut conn = pool.get()?;
+
+        let matrix = algorithm_compatibility_matrix::table
+            .filter(algorithm_compatibility_matrix::encryption_algorithm_a.eq_any(ENCRYPTION_PREFERENCE))
+            .filter(algorithm_compatibility_matrix::key_exchange_algorithm_a.eq_any(KEY_EXCHANGE_PREFERENCE))
+            .select(AlgorithmCompatibilityMatrix::as_select())
+            .load::<AlgorithmCompatibilityMatrix>(&mut conn)?;
+
+        let suite = Self::negotiate(&Self::algorithms(local), &Self::algorithms(remote), &matrix)?;
+        tracing::debug!(
+            "Negotiated {}/{} between devices {} and {}",
+            suite.encryption_algorithm, suite.key_exchange_algorithm, local.id, remote.id
+        );
+
+        Ok(suite)
+    }
+
+    fn algorithms(device: &Device) -> Vec<String> {
+        device.supported_algorithms.iter().flatten().cloned().collect()
+    }
+}
@@ -1,5 +1,4 @@
 use anyhow::{Result, bail};
-use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier, password_hash::{rand_core::OsRng, SaltString}};
 use jsonwebtoken::{encode, decode, Header, EncodingKey, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -7,13 +6,20 @@ use chrono::{Duration, Utc, DateTime};
 use ulid::Ulid;
 use crate::database::DbPool;
 
-use crate::app::models::user::{CreateUser, LoginRequest, ForgotPasswordRequest, ResetPasswordRequest, ChangePasswordRequest, RefreshTokenRequest, UserResponse};
+use crate::app::models::user::{User, CreateUser, LoginRequest, ForgotPasswordRequest, ResetPasswordRequest, ChangePasswordRequest, RefreshTokenRequest, UserResponse};
 // use crate::app::utils::password_validator::PasswordValidator;
 use crate::app::utils::token_utils::TokenUtils;
 use crate::app::services::user_service::UserService;
 use crate::app::services::email_service::EmailService;
 use crate::app::services::mfa_service::MfaService;
+use crate::app::services::mfa_email_service::MfaEmailService;
+use crate::app::services::login_fingerprint_service::{ClientFingerprint, FingerprintCheck, LoginFingerprintService};
+use crate::app::services::security_incident_service::{IncidentSubject, SecurityIncidentService};
+use crate::app::models::security_incidents::{IncidentSeverity, IncidentType};
 use crate::app::traits::ServiceActivityLogger;
+use crate::app::notifications::notify;
+use crate::app::notifications::send_email_verification::SendEmailVerification;
+use crate::app::utils::{Hash, UrlSigner};
 use crate::config::Config;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,6 +28,9 @@ pub struct Claims {
     pub exp: usize,
     pub iat: usize,
     pub jti: String,
+    /// Set while an administrator is impersonating `sub`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonator_id: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -48,6 +57,20 @@ pub enum LoginResponse {
     Success(AuthResponse),
     #[serde(rename = "mfa_required")]
     MfaRequired(MfaRequiredResponse),
+    #[serde(rename = "device_confirmation_required")]
+    DeviceConfirmationRequired(DeviceConfirmationResponse),
+}
+
+/// Returned when a login comes from an unrecognized client and must be confirmed
+#[derive(Debug, Serialize)]
+pub struct DeviceConfirmationResponse {
+    pub message: String,
+    pub requires_device_confirmation: bool,
+    pub user_id: String,
+    /// Pass back with the confirmation code to complete the login
+    pub challenge_id: String,
+    /// `email` when a code was emailed, otherwise the user's MFA methods
+    pub methods: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -64,23 +87,49 @@ pub struct AuthService;
 impl ServiceActivityLogger for AuthService {}
 
 impl AuthService {
+    /// Hasher configured by `HASH_DRIVER` and its cost settings
+    pub fn hasher() -> Result<Hash> {
+        Ok(Hash::new(Config::load()?.hashing))
+    }
+
     pub fn hash_password(password: &str) -> Result<String> {
-        let salt = SaltString::generate(&mut OsRng);
-        let argon2 = Argon2::default();
-        let password_hash = argon2.hash_password(password.as_bytes(), &salt)
-            .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))?;
-        Ok(password_hash.to_string())
+        Self::hasher()?.make(password)
     }
 
     pub fn verify_password(password: &str, hash: &str) -> Result<bool> {
-        let parsed_hash = PasswordHash::new(hash)
-            .map_err(|e| anyhow::anyhow!("Failed to parse password hash: {}", e))?;
-        let argon2 = Argon2::default();
-        let is_valid = argon2.verify_password(password.as_bytes(), &parsed_hash).is_ok();
-        Ok(is_valid)
+        Self::hasher()?.check(password, hash)
+    }
+
+    /// Replace a stored hash that uses a weaker algorithm or outdated parameters
+    ///
+    /// Called after a successful login, while the plain password is at hand.
+    /// Failures are logged and do not affect the login.
+    fn rehash_password_if_needed(pool: &DbPool, user: &User, password: &str) {
+        let rehashed = Self::hasher().and_then(|hasher| {
+            if !hasher.needs_rehash(&user.password) {
+                return Ok(false);
+            }
+            UserService::update_password(pool, user.id, hasher.make(password)?, None)?;
+            Ok(true)
+        });
+
+        match rehashed {
+            Ok(true) => tracing::info!("Rehashed password for user {}", user.id),
+            Ok(false) => {},
+            Err(e) => tracing::warn!("Failed to rehash password for user {}: {}", user.id, e),
+        }
     }
 
     pub fn generate_access_token(user_id: &str, expires_in_seconds: u64) -> Result<String> {
+        Self::encode_access_token(user_id, None, expires_in_seconds)
+    }
+
+    /// Access token for `user_id` that records which administrator is impersonating them
+    pub fn generate_impersonation_token(user_id: &str, impersonator_id: &str, expires_in_seconds: u64) -> Result<String> {
+        Self::encode_access_token(user_id, Some(impersonator_id.to_string()), expires_in_seconds)
+    }
+
+    fn encode_access_token(user_id: &str, impersonator_id: Option<String>, expires_in_seconds: u64) -> Result<String> {
         let config = Config::load()?;
         let now = Utc::now();
         let expiration = now + Duration::seconds(expires_in_seconds as i64);
@@ -91,6 +140,7 @@ impl AuthService {
             exp: expiration.timestamp() as usize,
             iat: now.timestamp() as usize,
             jti,
+            impersonator_id,
         };
 
         let token = encode(
@@ -102,6 +152,58 @@ impl AuthService {
         Ok(token)
     }
 
+    /// Blacklist an access token by its `jti` until `exp`, so it stops
+    /// authenticating before it would expire on its own
+    ///
+    /// Revocations are stored in the database so every app instance sees them.
+    pub fn revoke_access_token(pool: &DbPool, jti: &str, exp: usize) -> Result<()> {
+        use diesel::prelude::*;
+        use crate::schema::revoked_access_tokens;
+
+        let expires_at = DateTime::from_timestamp(exp as i64, 0).unwrap_or_else(Utc::now);
+        let mut conn = pool.get()?;
+
+        diesel::insert_into(revoked_access_tokens::table)
+            .values((
+                revoked_access_tokens::jti.eq(jti),
+                revoked_access_tokens::expires_at.eq(expires_at),
+            ))
+            .on_conflict_do_nothing()
+            .execute(&mut conn)?;
+
+        // Expired tokens fail validation anyway, so their rows are no longer needed
+        diesel::delete(revoked_access_tokens::table.filter(revoked_access_tokens::expires_at.lt(Utc::now())))
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    /// Whether the access token with `claims` was revoked
+    ///
+    /// When the lookup fails an impersonation token is treated as revoked, so
+    /// an outage cannot bring a stopped impersonation back; other tokens are
+    /// logged and let through.
+    pub async fn is_access_token_revoked(claims: &Claims) -> bool {
+        Self::find_revocation(&claims.jti).await.unwrap_or_else(|e| {
+            tracing::error!("Failed to check revoked access token {}: {}", claims.jti, e);
+            claims.impersonator_id.is_some()
+        })
+    }
+
+    async fn find_revocation(jti: &str) -> Result<bool> {
+        use diesel::prelude::*;
+        use crate::schema::revoked_access_tokens;
+
+        let pool = crate::database::connection::get_connection().await?;
+        let mut conn = pool.get()?;
+
+        let revoked = diesel::select(diesel::dsl::exists(
+            revoked_access_tokens::table.filter(revoked_access_tokens::jti.eq(jti)),
+        ))
+        .get_result(&mut conn)?;
+        Ok(revoked)
+    }
+
     pub fn generate_refresh_token() -> String {
         Ulid::new().to_string()
     }
@@ -172,6 +274,10 @@ impl AuthService {
             tracing::warn!("Failed to send welcome email: {}", e);
         }
 
+        if let Err(e) = Self::send_email_verification(&created_user).await {
+            tracing::warn!("Failed to send email verification: {}", e);
+        }
+
         Ok(AuthResponse {
             access_token,
             refresh_token,
@@ -181,7 +287,7 @@ impl AuthService {
         })
     }
 
-    pub async fn login(pool: &DbPool, data: LoginRequest) -> Result<LoginResponse> {
+    pub async fn login(pool: &DbPool, data: LoginRequest, fingerprint: &ClientFingerprint) -> Result<LoginResponse> {
         // Find user by email
         let mut user = UserService::find_by_email(pool, &data.email)?
             .ok_or_else(|| anyhow::anyhow!("Invalid credentials"))?;
@@ -203,6 +309,10 @@ impl AuthService {
 
             UserService::update_failed_attempts(pool, user.id, user.failed_login_attempts, user.locked_until)?;
 
+            if user.failed_login_attempts >= MAX_FAILED_ATTEMPTS {
+                Self::report_lockout(pool, &user, "login").await;
+            }
+
             // Log failed login attempt
             let service = AuthService;
             let properties = json!({
@@ -230,6 +340,15 @@ impl AuthService {
             UserService::reset_failed_attempts(pool, user.id.clone())?;
         }
 
+        Self::rehash_password_if_needed(pool, &user, &data.password);
+
+        // Step up when the login comes from a client the user has not confirmed
+        if let FingerprintCheck::Unrecognized(pending) = LoginFingerprintService::new()?.check(pool, user.id, fingerprint)? {
+            return Ok(LoginResponse::DeviceConfirmationRequired(
+                Self::challenge_new_device(pool, &user.id.to_string(), &pending.id.to_string(), fingerprint).await?
+            ));
+        }
+
         // Check if MFA is enabled for this user
         if MfaService::is_mfa_enabled(pool, user.id.to_string())? {
             let mfa_methods = MfaService::get_mfa_methods(pool, user.id.to_string())?;
@@ -335,6 +454,154 @@ impl AuthService {
         })
     }
 
+    /// Send the step-up challenge for a login from an unrecognized client
+    async fn challenge_new_device(
+        pool: &DbPool,
+        user_id: &str,
+        challenge_id: &str,
+        fingerprint: &ClientFingerprint,
+    ) -> Result<DeviceConfirmationResponse> {
+        let methods = if MfaService::is_mfa_enabled(pool, user_id.to_string())? {
+            MfaService::get_mfa_methods(pool, user_id.to_string())?
+                .into_iter()
+                .map(|m| m.method_type)
+                .collect()
+        } else {
+            if let Err(e) = MfaEmailService::send_code(
+                pool,
+                user_id.to_string(),
+                fingerprint.ip_address.clone(),
+                fingerprint.user_agent.clone(),
+            ).await {
+                tracing::warn!("Failed to send device confirmation code to user {}: {}", user_id, e);
+            }
+            vec!["email".to_string()]
+        };
+
+        Ok(DeviceConfirmationResponse {
+            message: "Confirm this device to finish signing in".to_string(),
+            requires_device_confirmation: true,
+            user_id: user_id.to_string(),
+            challenge_id: challenge_id.to_string(),
+            methods,
+        })
+    }
+
+    /// Complete a login from an unrecognized client with an emailed or MFA code
+    pub async fn confirm_new_device(pool: &DbPool, user_id: String, challenge_id: &str, code: &str) -> Result<AuthResponse> {
+        let fingerprints = LoginFingerprintService::new()?;
+        if !fingerprints.is_pending(pool, &user_id, challenge_id)? {
+            bail!("Device confirmation not found or already completed");
+        }
+
+        let verified = if MfaService::is_mfa_enabled(pool, user_id.clone())? {
+            MfaService::verify_mfa_code(pool, user_id.clone(), code).await?
+        } else {
+            MfaEmailService::verify_code(pool, user_id.clone(), code).await?
+        };
+        if !verified {
+            bail!("Invalid confirmation code");
+        }
+
+        fingerprints.confirm(pool, &user_id, challenge_id)?;
+
+        let mut user = UserService::find_by_id(pool, user_id.clone())?
+            .ok_or_else(|| anyhow::anyhow!("User not found"))?;
+
+        let access_token = Self::generate_access_token(&user.id.to_string(), 86400)?; // 24 hours
+        let refresh_token = Self::generate_refresh_token();
+        let expires_at = Utc::now() + Duration::seconds(86400);
+        let refresh_expires_at = Utc::now() + Duration::seconds(604800); // 7 days
+
+        UserService::update_refresh_token(pool, user.id.clone(), Some(refresh_token.clone()), Some(refresh_expires_at))?;
+
+        UserService::update_last_login(pool, user.id.clone())?;
+        user.last_login_at = Some(Utc::now());
+
+        let service = AuthService;
+        let properties = json!({
+            "user_id": user.id.to_string(),
+            "email": user.email.clone(),
+            "last_login": user.last_login_at,
+            "login_method": "device_confirmation",
+            "challenge_id": challenge_id
+        });
+
+        if let Err(e) = service.log_authentication(
+            "device_confirmation_login",
+            Some(&user.id.to_string()),
+            true,
+            Some(properties)
+        ).await {
+            tracing::error!("Failed to log device confirmation login activity: {}", e);
+        }
+
+        Ok(AuthResponse {
+            access_token,
+            refresh_token,
+            user: user.to_response(),
+            expires_at,
+            refresh_expires_at,
+        })
+    }
+
+    /// Email a signed link that confirms the user's address
+    pub async fn send_email_verification(user: &User) -> Result<()> {
+        let config = Config::load()?;
+        let signer = UrlSigner::from_config(&config.app)?;
+        let notification = SendEmailVerification::new(
+            user,
+            &signer,
+            &config.app.url,
+            config.auth.email_verification_expire_minutes,
+        );
+
+        notify(user, notification).await
+    }
+
+    /// Resend the verification link to a user who has not verified yet
+    pub async fn resend_email_verification(pool: &DbPool, user_id: &str) -> Result<MessageResponse> {
+        let user = UserService::find_by_id(pool, user_id.to_string())?
+            .ok_or_else(|| anyhow::anyhow!("User not found"))?;
+
+        if user.email_verified_at.is_some() {
+            return Ok(MessageResponse {
+                message: "Email address is already verified.".to_string(),
+            });
+        }
+
+        Self::send_email_verification(&user).await?;
+
+        Ok(MessageResponse {
+            message: "A new verification link has been sent to your email address.".to_string(),
+        })
+    }
+
+    /// Mark a user's email as verified from a signed verification link
+    ///
+    /// The signature is checked by the `validate_signature` middleware; this
+    /// only checks that the link was issued for the user's current address.
+    pub fn verify_email(pool: &DbPool, user_id: &str, hash: &str) -> Result<MessageResponse> {
+        let user = UserService::find_by_id(pool, user_id.to_string())?
+            .ok_or_else(|| anyhow::anyhow!("User not found"))?;
+
+        if SendEmailVerification::email_hash(&user.email) != hash {
+            bail!("Invalid verification link");
+        }
+
+        if user.email_verified_at.is_some() {
+            return Ok(MessageResponse {
+                message: "Email address is already verified.".to_string(),
+            });
+        }
+
+        UserService::mark_email_verified(pool, user.id)?;
+
+        Ok(MessageResponse {
+            message: "Email address verified successfully.".to_string(),
+        })
+    }
+
     pub async fn forgot_password(pool: &DbPool, data: ForgotPasswordRequest) -> Result<MessageResponse> {
         // Find user by email
         let user = UserService::find_by_email(pool, &data.email)?;
@@ -464,6 +731,11 @@ impl AuthService {
             }
 
             UserService::update_failed_attempts(pool, user.id, user.failed_login_attempts, user.locked_until)?;
+
+            if user.failed_login_attempts >= MAX_FAILED_ATTEMPTS {
+                Self::report_lockout(pool, &user, "oauth_password_grant").await;
+            }
+
             bail!("Invalid credentials");
         }
 
@@ -472,9 +744,26 @@ impl AuthService {
             UserService::reset_failed_attempts(pool, user.id.clone())?;
         }
 
+        Self::rehash_password_if_needed(pool, &user, password);
+
         // Update last login
         UserService::update_last_login(pool, user.id.clone())?;
 
         Ok(user.id.to_string())
     }
-}
\ No newline at end of file
+
+    /// Record a security incident when repeated failed logins lock an account
+    async fn report_lockout(pool: &DbPool, user: &User, source: &str) {
+        SecurityIncidentService::report(
+            pool,
+            IncidentType::MultipleFailedAuth,
+            IncidentSeverity::Medium,
+            IncidentSubject::user(user.id),
+            json!({
+                "source": source,
+                "failed_attempts": user.failed_login_attempts,
+                "locked_until": user.locked_until,
+            }),
+        ).await;
+    }
+}
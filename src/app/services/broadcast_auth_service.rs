@@ -0,0 +1,163 @@
+use anyhow::Result;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use utoipa::ToSchema;
+use crate::app::broadcasting::channels::{can_access_channel, channel_user};
+use crate::config::Config;
+use crate::database::DbPool;
+use crate::config::broadcasting::BroadcastingConfig;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Errors raised while authorizing a broadcast channel subscription
+#[derive(Debug, thiserror::Error)]
+pub enum BroadcastAuthError {
+    #[error("Not authorized to listen on channel {0}")]
+    Forbidden(String),
+
+    #[error("Invalid channel authorization request: {0}")]
+    Invalid(String),
+
+    #[error("Broadcast channel authorization is not configured; set BROADCAST_APP_SECRET")]
+    NotConfigured,
+}
+
+impl BroadcastAuthError {
+    /// HTTP status for an error returned while authorizing a channel
+    pub fn status_code(error: &anyhow::Error) -> axum::http::StatusCode {
+        use axum::http::StatusCode;
+
+        match error.downcast_ref::<BroadcastAuthError>() {
+            Some(BroadcastAuthError::Forbidden(_)) => StatusCode::FORBIDDEN,
+            Some(BroadcastAuthError::Invalid(_)) => StatusCode::UNPROCESSABLE_ENTITY,
+            Some(BroadcastAuthError::NotConfigured) | None => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Channel authorization request sent by Echo-style clients before subscribing
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BroadcastAuthRequest {
+    /// Socket the subscription is for; required by the Pusher protocol
+    pub socket_id: Option<String>,
+    /// Channel to subscribe to, optionally prefixed with `private-` or `presence-`
+    pub channel_name: String,
+}
+
+/// Signed authorization the client includes in its subscribe frame
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BroadcastAuthResponse {
+    /// `key:signature` for the Pusher protocol, or an allow token for the native protocol
+    pub auth: String,
+    /// Member data for presence channels, as signed in `auth`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_data: Option<String>,
+}
+
+pub struct BroadcastAuthService {
+    config: BroadcastingConfig,
+}
+
+impl BroadcastAuthService {
+    pub fn new() -> Result<Self> {
+        Ok(Self::with_config(Config::load()?.broadcasting))
+    }
+
+    pub fn with_config(config: BroadcastingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run the channel authorization callbacks for a user and sign the subscription
+    pub fn authorize(&self, pool: &DbPool, user_id: &str, request: BroadcastAuthRequest) -> Result<BroadcastAuthResponse> {
+        let secret = self.secret()?;
+        let channel = Self::channel(&request.channel_name);
+        if channel.is_empty() {
+            return Err(BroadcastAuthError::Invalid("channel_name is required".to_string()).into());
+        }
+
+        let user = channel_user(pool, user_id)?;
+        if !can_access_channel(pool, &user, channel) {
+            return Err(BroadcastAuthError::Forbidden(request.channel_name.clone()).into());
+        }
+
+        let channel_data = if request.channel_name.starts_with("presence-") {
+            Some(serde_json::json!({ "user_id": user.user_id }).to_string())
+        } else {
+            None
+        };
+
+        let auth = match self.config.auth_protocol.as_str() {
+            "pusher" => {
+                let socket_id = request.socket_id.as_deref()
+                    .filter(|socket_id| Self::is_valid_socket_id(socket_id))
+                    .ok_or_else(|| BroadcastAuthError::Invalid("a valid socket_id is required".to_string()))?;
+
+                let mut payload = format!("{}:{}", socket_id, request.channel_name);
+                if let Some(channel_data) = &channel_data {
+                    payload.push(':');
+                    payload.push_str(channel_data);
+                }
+
+                format!("{}:{}", self.config.app_key, Self::sign(secret, &payload))
+            },
+            _ => {
+                let expires_at = Utc::now().timestamp() + self.config.auth_token_ttl_secs;
+                let signature = Self::sign(secret, &Self::native_payload(&user.user_id, channel, expires_at));
+                format!("{}.{}.{}", user.user_id, expires_at, signature)
+            },
+        };
+
+        tracing::debug!("Authorized user {} for channel {}", user.user_id, request.channel_name);
+
+        Ok(BroadcastAuthResponse { auth, channel_data })
+    }
+
+    /// Check a native allow token for a channel, returning the user it was issued to
+    pub fn verify_native_token(&self, channel: &str, token: &str) -> Option<String> {
+        let secret = self.secret().ok()?;
+        let mut parts = token.splitn(3, '.');
+        let (user_id, expires_at, signature) = (parts.next()?, parts.next()?, parts.next()?);
+
+        let expires_at = expires_at.parse::<i64>().ok()?;
+        if expires_at < Utc::now().timestamp() {
+            return None;
+        }
+
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).ok()?;
+        mac.update(Self::native_payload(user_id, Self::channel(channel), expires_at).as_bytes());
+        mac.verify_slice(&hex::decode(signature).ok()?).ok()?;
+
+        Some(user_id.to_string())
+    }
+
+    fn secret(&self) -> Result<&str> {
+        self.config.app_secret.as_deref()
+            .ok_or_else(|| BroadcastAuthError::NotConfigured.into())
+    }
+
+    /// Channel name without the Echo `private-` or `presence-` prefix
+    fn channel(channel_name: &str) -> &str {
+        channel_name.strip_prefix("private-")
+            .or_else(|| channel_name.strip_prefix("presence-"))
+            .unwrap_or(channel_name)
+    }
+
+    fn native_payload(user_id: &str, channel: &str, expires_at: i64) -> String {
+        format!("{}:{}:{}", user_id, channel, expires_at)
+    }
+
+    /// Pusher socket IDs look like `1234.5678`
+    fn is_valid_socket_id(socket_id: &str) -> bool {
+        socket_id.split_once('.').is_some_and(|(a, b)| {
+            !a.is_empty() && !b.is_empty() && a.chars().chain(b.chars()).all(|c| c.is_ascii_digit())
+        })
+    }
+
+    fn sign(secret: &str, payload: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+        mac.update(payload.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
@@ -0,0 +1,177 @@
+use anyhow::Result;
+use diesel::prelude::*;
+use crate::database::DbPool;
+use crate::schema::{conversation_participants, conversations, devices, messages};
+use crate::app::models::conversation::Conversation;
+use crate::app::models::conversation_participant::ConversationParticipant;
+use crate::app::models::device::Device;
+use crate::app::models::message::Message;
+use crate::app::broadcasting::helpers::{broadcast_to_conversation, broadcast_to_user};
+
+/// Errors raised when a user acts on a conversation they are not allowed to access
+#[derive(Debug, thiserror::Error)]
+pub enum ConversationError {
+    #[error("Conversation not found")]
+    NotFound,
+
+    #[error("Message not found")]
+    MessageNotFound,
+
+    #[error("You are not a participant of this conversation")]
+    NotParticipant,
+
+    #[error("Device does not belong to the authenticated user")]
+    InvalidDevice,
+}
+
+impl ConversationError {
+    /// HTTP status for an error returned by the conversation services
+    pub fn status_code(error: &anyhow::Error) -> axum::http::StatusCode {
+        use axum::http::StatusCode;
+
+        match error.downcast_ref::<ConversationError>() {
+            Some(ConversationError::NotFound) | Some(ConversationError::MessageNotFound) => StatusCode::NOT_FOUND,
+            Some(ConversationError::NotParticipant) => StatusCode::FORBIDDEN,
+            Some(ConversationError::InvalidDevice) => StatusCode::UNPROCESSABLE_ENTITY,
+            None => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+pub struct ConversationService;
+
+impl ConversationService {
+    /// Find a conversation that has not been deleted
+    pub fn find_conversation(pool: &DbPool, conversation_id: &str) -> Result<Conversation> {
+        let mut conn = pool.get()?;
+
+        let conversation = conversations::table
+            .filter(conversations::id.eq(conversation_id))
+            .filter(conversations::deleted_at.is_null())
+            .select(Conversation::as_select())
+            .first::<Conversation>(&mut conn)
+            .optional()?
+            .ok_or(ConversationError::NotFound)?;
+
+        Ok(conversation)
+    }
+
+    /// Find the active participant record for a user in a conversation
+    pub fn find_participant(pool: &DbPool, conversation_id: &str, user_id: &str) -> Result<Option<ConversationParticipant>> {
+        let mut conn = pool.get()?;
+
+        let participant = conversation_participants::table
+            .filter(conversation_participants::conversation_id.eq(conversation_id))
+            .filter(conversation_participants::user_id.eq(user_id))
+            .filter(conversation_participants::is_active.eq(true))
+            .select(ConversationParticipant::as_select())
+            .first::<ConversationParticipant>(&mut conn)
+            .optional()?;
+
+        Ok(participant)
+    }
+
+    /// Ensure the user is an active participant, returning their participant record
+    pub fn ensure_participant(pool: &DbPool, conversation_id: &str, user_id: &str) -> Result<ConversationParticipant> {
+        Self::find_participant(pool, conversation_id, user_id)?
+            .ok_or_else(|| ConversationError::NotParticipant.into())
+    }
+
+    /// Load a message and ensure the user participates in its conversation
+    pub fn find_message_for_participant(pool: &DbPool, message_id: &str, user_id: &str) -> Result<(Message, ConversationParticipant)> {
+        let mut conn = pool.get()?;
+
+        let message = messages::table
+            .filter(messages::id.eq(message_id))
+            .filter(messages::is_deleted.eq(false))
+            .select(Message::as_select())
+            .first::<Message>(&mut conn)
+            .optional()?
+            .ok_or(ConversationError::MessageNotFound)?;
+
+        let participant = Self::ensure_participant(pool, &message.conversation_id.to_string(), user_id)?;
+
+        Ok((message, participant))
+    }
+
+    /// Ensure the device is active and owned by the user
+    pub fn ensure_user_device(pool: &DbPool, device_id: &str, user_id: &str) -> Result<Device> {
+        let mut conn = pool.get()?;
+
+        let device = devices::table
+            .filter(devices::id.eq(device_id))
+            .filter(devices::user_id.eq(user_id))
+            .filter(devices::is_active.eq(true))
+            .select(Device::as_select())
+            .first::<Device>(&mut conn)
+            .optional()?
+            .ok_or(ConversationError::InvalidDevice)?;
+
+        Ok(device)
+    }
+
+    /// User IDs of all active participants in a conversation
+    pub fn participant_user_ids(pool: &DbPool, conversation_id: &str) -> Result<Vec<String>> {
+        let mut conn = pool.get()?;
+
+        let user_ids = conversation_participants::table
+            .filter(conversation_participants::conversation_id.eq(conversation_id))
+            .filter(conversation_participants::is_active.eq(true))
+            .select(conversation_participants::user_id)
+            .load::<String>(&mut conn)?;
+
+        Ok(user_ids)
+    }
+
+    /// IDs of the conversations a user actively participates in
+    pub fn conversation_ids_for_user(pool: &DbPool, user_id: &str) -> Result<Vec<String>> {
+        let mut conn = pool.get()?;
+
+        let conversation_ids = conversation_participants::table
+            .filter(conversation_participants::user_id.eq(user_id))
+            .filter(conversation_participants::is_active.eq(true))
+            .select(conversation_participants::conversation_id)
+            .load::<String>(&mut conn)?;
+
+        Ok(conversation_ids)
+    }
+
+    /// Whether two users are active participants of at least one common conversation
+    pub fn shares_conversation(pool: &DbPool, user_id: &str, other_user_id: &str) -> Result<bool> {
+        let conversation_ids = Self::conversation_ids_for_user(pool, user_id)?;
+        let mut conn = pool.get()?;
+
+        let shared: i64 = conversation_participants::table
+            .filter(conversation_participants::conversation_id.eq_any(&conversation_ids))
+            .filter(conversation_participants::user_id.eq(other_user_id))
+            .filter(conversation_participants::is_active.eq(true))
+            .count()
+            .get_result(&mut conn)?;
+
+        Ok(shared > 0)
+    }
+
+    /// Broadcast an event on the conversation's private channel
+    pub async fn broadcast(conversation_id: &str, event: &str, data: serde_json::Value) -> Result<()> {
+        broadcast_to_conversation(conversation_id, event, data).await
+    }
+
+    /// Broadcast an event to every active participant except one user, on their private channels
+    pub async fn broadcast_to_others(
+        pool: &DbPool,
+        conversation_id: &str,
+        except_user_id: &str,
+        event: &str,
+        data: serde_json::Value,
+    ) -> Result<()> {
+        for user_id in Self::participant_user_ids(pool, conversation_id)? {
+            if user_id == except_user_id {
+                continue;
+            }
+
+            broadcast_to_user(&user_id, event, data.clone()).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+use anyhow::Result;
+use diesel::prelude::*;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+use crate::app::http::form_request::FormRequest;
+use crate::app::validation::{make_validator, ValidationErrors};
+use crate::database::DbPool;
+
+/// Default number of rows inserted per transaction
+pub const DEFAULT_IMPORT_BATCH_SIZE: usize = 500;
+
+/// A model that can be imported from CSV with `import:csv`
+///
+/// Each row is read into the model's create request, prepared and validated
+/// with the request's rules, and only then handed to `insert_row`.
+pub trait CsvImportable {
+    /// Request whose rules validate each row
+    type Request: FormRequest;
+
+    /// Insert one validated row
+    fn insert_row(conn: &mut PgConnection, row: Self::Request, created_by: &str) -> QueryResult<()>;
+}
+
+/// Options for a CSV import
+#[derive(Debug, Clone)]
+pub struct CsvImportOptions {
+    /// User recorded as the creator of imported rows
+    pub created_by: String,
+    /// Rows committed per transaction
+    pub batch_size: usize,
+    /// Validate every row without inserting anything
+    pub dry_run: bool,
+    /// CSV header to model field, for headers that differ from field names
+    pub column_map: HashMap<String, String>,
+}
+
+impl CsvImportOptions {
+    pub fn new(created_by: impl Into<String>) -> Self {
+        Self {
+            created_by: created_by.into(),
+            batch_size: DEFAULT_IMPORT_BATCH_SIZE,
+            dry_run: false,
+            column_map: HashMap::new(),
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn with_column(mut self, header: impl Into<String>, field: impl Into<String>) -> Self {
+        self.column_map.insert(header.into(), field.into());
+        self
+    }
+}
+
+/// A row that could not be imported
+#[derive(Debug, Clone, Serialize)]
+pub struct CsvRowError {
+    /// Line in the file, counting the header as line 1
+    pub line: usize,
+    /// Messages keyed by field, or by `row` for errors not tied to one field
+    pub errors: HashMap<String, Vec<String>>,
+}
+
+/// Outcome of a CSV import
+#[derive(Debug, Default, Serialize)]
+pub struct CsvImportReport {
+    /// Data rows read from the file
+    pub processed: usize,
+    /// Rows that passed validation
+    pub valid: usize,
+    /// Rows written to the database; always zero for a dry run
+    pub inserted: usize,
+    pub failed: Vec<CsvRowError>,
+}
+
+impl CsvImportReport {
+    pub fn has_failures(&self) -> bool {
+        !self.failed.is_empty()
+    }
+}
+
+pub struct CsvImportService;
+
+impl CsvImportService {
+    pub async fn import_file<T: CsvImportable>(pool: &DbPool, path: &Path, options: &CsvImportOptions) -> Result<CsvImportReport> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| anyhow::anyhow!("Failed to open {}: {}", path.display(), e))?;
+        Self::import::<T, _>(pool, file, options).await
+    }
+
+    /// Validate every row, then insert the valid ones in batched transactions
+    ///
+    /// A row rejected by the database is rolled back to its own savepoint and
+    /// reported, without failing the rest of its batch.
+    pub async fn import<T, R>(pool: &DbPool, reader: R, options: &CsvImportOptions) -> Result<CsvImportReport>
+    where
+        T: CsvImportable,
+        R: Read,
+    {
+        let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(reader);
+        let fields: Vec<String> = reader
+            .headers()?
+            .iter()
+            .map(|header| options.column_map.get(header).cloned().unwrap_or_else(|| header.to_string()))
+            .collect();
+
+        let mut report = CsvImportReport::default();
+        let mut valid_rows = Vec::new();
+
+        for (index, record) in reader.records().enumerate() {
+            let line = index + 2;
+            report.processed += 1;
+
+            let record = match record {
+                Ok(record) => record,
+                Err(e) => {
+                    report.failed.push(Self::row_error(line, e.to_string()));
+                    continue;
+                }
+            };
+
+            match Self::validate_row::<T>(&fields, &record).await {
+                Ok(row) => valid_rows.push((line, row)),
+                Err(errors) => report.failed.push(CsvRowError { line, errors }),
+            }
+        }
+
+        report.valid = valid_rows.len();
+        if options.dry_run || valid_rows.is_empty() {
+            report.failed.sort_by_key(|error| error.line);
+            return Ok(report);
+        }
+
+        let mut conn = pool.get()?;
+        let mut rows = valid_rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let batch: Vec<_> = rows.by_ref().take(options.batch_size).collect();
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                for (line, row) in batch {
+                    match conn.transaction(|conn| T::insert_row(conn, row, &options.created_by)) {
+                        Ok(()) => report.inserted += 1,
+                        Err(e) => report.failed.push(Self::row_error(line, e.to_string())),
+                    }
+                }
+                Ok(())
+            })?;
+        }
+
+        report.failed.sort_by_key(|error| error.line);
+        Ok(report)
+    }
+
+    /// Map a record onto the request's fields, then prepare and validate it
+    async fn validate_row<T: CsvImportable>(
+        fields: &[String],
+        record: &csv::StringRecord,
+    ) -> std::result::Result<T::Request, HashMap<String, Vec<String>>> {
+        // Blank cells are treated as missing so `required` and optional fields
+        // behave as they do for JSON requests
+        let data: Map<String, Value> = fields
+            .iter()
+            .zip(record.iter())
+            .filter(|(_, cell)| !cell.is_empty())
+            .map(|(field, cell)| (field.clone(), Value::String(cell.to_string())))
+            .collect();
+        let data = Value::Object(data);
+
+        let mut row: T::Request = match serde_json::from_value(data.clone()) {
+            Ok(row) => row,
+            Err(e) => {
+                // Report the rules the raw row breaks when there are any, as
+                // they say more than the deserialization error
+                return Err(match make_validator(data, T::Request::rules()).validate().await {
+                    Err(errors) => Self::field_errors(errors),
+                    Ok(()) => Self::row_message(e.to_string()),
+                });
+            }
+        };
+
+        row.prepare_for_validation();
+        let prepared = serde_json::to_value(&row).map_err(|e| Self::row_message(e.to_string()))?;
+        row.validate_data(prepared).await.map_err(Self::field_errors)?;
+
+        Ok(row)
+    }
+
+    fn field_errors(errors: ValidationErrors) -> HashMap<String, Vec<String>> {
+        errors
+            .errors
+            .into_iter()
+            .map(|(field, messages)| {
+                let mut messages: Vec<String> = messages.into_values().collect();
+                messages.sort();
+                (field, messages)
+            })
+            .collect()
+    }
+
+    fn row_error(line: usize, message: String) -> CsvRowError {
+        CsvRowError { line, errors: Self::row_message(message) }
+    }
+
+    fn row_message(message: String) -> HashMap<String, Vec<String>> {
+        HashMap::from([("row".to_string(), vec![message])])
+    }
+}
@@ -0,0 +1,296 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+use serde_json::json;
+use std::time::Duration;
+use utoipa::ToSchema;
+use crate::config::Config;
+use crate::config::messaging::MessagingConfig;
+use crate::database::DbPool;
+use crate::schema::{device_presence, devices};
+use crate::app::broadcasting::helpers::broadcast_to_user;
+use crate::app::models::DieselUlid;
+use crate::app::models::device_presence::{DevicePresence, DevicePresenceResponse, PresenceStatus};
+use crate::app::services::conversation_service::{ConversationError, ConversationService};
+
+/// Presence of a user across their devices
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserPresence {
+    pub user_id: String,
+    /// Most available status of any device
+    pub status: String,
+    pub last_seen_at: Option<DateTime<Utc>>,
+    pub devices: Vec<DevicePresenceResponse>,
+}
+
+pub struct DevicePresenceService {
+    config: MessagingConfig,
+}
+
+impl DevicePresenceService {
+    pub fn new() -> Result<Self> {
+        Ok(Self::with_config(Config::load()?.messaging))
+    }
+
+    pub fn with_config(config: MessagingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Mark a device online when its WebSocket connects
+    pub async fn connect(&self, pool: &DbPool, device_id: &str) -> Result<DevicePresence> {
+        self.touch(pool, device_id, PresenceStatus::Online).await
+    }
+
+    /// Record a heartbeat, switching between online and away as reported by the client
+    pub async fn heartbeat(&self, pool: &DbPool, device_id: &str, status: PresenceStatus) -> Result<DevicePresence> {
+        let status = match status {
+            PresenceStatus::Away => PresenceStatus::Away,
+            _ => PresenceStatus::Online,
+        };
+        self.touch(pool, device_id, status).await
+    }
+
+    /// Choose busy, do not disturb or invisible for a device, or clear the choice with online or away
+    ///
+    /// A chosen status is kept while the device is offline and shown again when it reconnects.
+    pub async fn set_status(&self, pool: &DbPool, device_id: &str, status: PresenceStatus) -> Result<DevicePresence> {
+        let chosen_status = status.is_chosen().then(|| String::from(status.clone()));
+        let (previous, presence) = Self::upsert(pool, device_id, status, Some(chosen_status))?;
+        Self::broadcast_if_changed(pool, previous, &presence).await;
+        Ok(presence)
+    }
+
+    /// Mark a device offline when its last WebSocket disconnects, keeping its chosen status
+    pub async fn disconnect(&self, pool: &DbPool, device_id: &str) -> Result<DevicePresence> {
+        let (previous, presence) = Self::upsert(pool, device_id, PresenceStatus::Offline, None)?;
+        Self::broadcast_if_changed(pool, previous, &presence).await;
+        Ok(presence)
+    }
+
+    /// Mark devices offline whose last heartbeat is older than the presence timeout, keeping their chosen status
+    pub async fn mark_stale_offline(&self, pool: &DbPool) -> Result<Vec<DevicePresence>> {
+        let now = Utc::now();
+        let cutoff = now - chrono::Duration::seconds(self.config.presence_timeout_secs);
+        let offline: String = PresenceStatus::Offline.into();
+        let mut conn = pool.get()?;
+
+        let stale = diesel::update(
+            device_presence::table
+                .filter(device_presence::status.ne(&offline))
+                .filter(device_presence::last_seen_at.lt(cutoff))
+        )
+        .set((
+            device_presence::status.eq(&offline),
+            device_presence::updated_at.eq(now),
+        ))
+        .returning(DevicePresence::as_returning())
+        .get_results::<DevicePresence>(&mut conn)?;
+
+        for presence in &stale {
+            Self::broadcast(pool, presence).await;
+        }
+
+        Ok(stale)
+    }
+
+    /// Sweep for stale devices until the task is dropped
+    pub async fn run(self, pool: DbPool) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(self.config.presence_sweep_interval_secs.max(1)));
+
+        loop {
+            ticker.tick().await;
+
+            match self.mark_stale_offline(&pool).await {
+                Ok(stale) if !stale.is_empty() => {
+                    tracing::info!("Marked {} stale devices offline", stale.len());
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Device presence sweep failed: {}", e),
+            }
+        }
+    }
+
+    /// Spawn the stale device sweep on the Tokio runtime
+    pub fn spawn(self, pool: DbPool) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(self.run(pool))
+    }
+
+    /// Presence of a user's devices, visible to the user and anyone sharing a conversation with them
+    pub fn for_user(&self, pool: &DbPool, viewer_id: &str, user_id: &str) -> Result<UserPresence> {
+        let is_self = viewer_id == user_id;
+        if !is_self && !ConversationService::shares_conversation(pool, viewer_id, user_id)? {
+            return Err(ConversationError::NotParticipant.into());
+        }
+
+        let mut conn = pool.get()?;
+        let device_ids = devices::table
+            .filter(devices::user_id.eq(user_id))
+            .filter(devices::is_active.eq(true))
+            .select(devices::id)
+            .load::<String>(&mut conn)?;
+
+        let presences = device_presence::table
+            .filter(device_presence::device_id.eq_any(&device_ids))
+            .order(device_presence::last_seen_at.desc())
+            .select(DevicePresence::as_select())
+            .load::<DevicePresence>(&mut conn)?;
+
+        let status = presences
+            .iter()
+            .map(|presence| if is_self { presence.status_enum() } else { presence.visible_status() })
+            .min_by_key(Self::availability_rank)
+            .unwrap_or(PresenceStatus::Offline);
+
+        let devices = presences
+            .iter()
+            .map(|presence| {
+                let mut response = presence.to_response();
+                if !is_self {
+                    response.status = presence.visible_status().into();
+                }
+                response
+            })
+            .collect();
+
+        Ok(UserPresence {
+            user_id: user_id.to_string(),
+            status: status.into(),
+            last_seen_at: presences.first().map(|presence| presence.last_seen_at),
+            devices,
+        })
+    }
+
+    /// Refresh `last_seen_at`, showing the status the user chose if there is one
+    async fn touch(&self, pool: &DbPool, device_id: &str, status: PresenceStatus) -> Result<DevicePresence> {
+        let status = Self::find(pool, device_id)?
+            .and_then(|presence| presence.chosen_status_enum())
+            .unwrap_or(status);
+
+        let (previous, presence) = Self::upsert(pool, device_id, status, None)?;
+        Self::broadcast_if_changed(pool, previous, &presence).await;
+        Ok(presence)
+    }
+
+    fn find(pool: &DbPool, device_id: &str) -> Result<Option<DevicePresence>> {
+        let mut conn = pool.get()?;
+
+        let presence = device_presence::table
+            .filter(device_presence::device_id.eq(device_id))
+            .select(DevicePresence::as_select())
+            .first::<DevicePresence>(&mut conn)
+            .optional()?;
+
+        Ok(presence)
+    }
+
+    /// Write the device's status, replacing its chosen status only when `chosen_status` is given
+    fn upsert(
+        pool: &DbPool,
+        device_id: &str,
+        status: PresenceStatus,
+        chosen_status: Option<Option<String>>,
+    ) -> Result<(Option<String>, DevicePresence)> {
+        let device_ulid = DieselUlid::from_string(device_id)?;
+        let mut conn = pool.get()?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            let previous = device_presence::table
+                .filter(device_presence::device_id.eq(device_id))
+                .select(device_presence::status)
+                .for_update()
+                .first::<String>(conn)
+                .optional()?;
+
+            let presence = DevicePresence::new(device_ulid, status, None, None, None, None);
+            let presence = diesel::insert_into(device_presence::table)
+                .values(&presence)
+                .on_conflict(device_presence::device_id)
+                .do_update()
+                .set((
+                    device_presence::status.eq(&presence.status),
+                    device_presence::last_seen_at.eq(presence.last_seen_at),
+                    device_presence::updated_at.eq(presence.updated_at),
+                ))
+                .returning(DevicePresence::as_returning())
+                .get_result::<DevicePresence>(conn)?;
+
+            let presence = match chosen_status {
+                Some(chosen_status) => diesel::update(device_presence::table.filter(device_presence::device_id.eq(device_id)))
+                    .set(device_presence::chosen_status.eq(chosen_status))
+                    .returning(DevicePresence::as_returning())
+                    .get_result::<DevicePresence>(conn)?,
+                None => presence,
+            };
+
+            Ok((previous, presence))
+        })
+    }
+
+    async fn broadcast_if_changed(pool: &DbPool, previous: Option<String>, presence: &DevicePresence) {
+        let previous = previous.map(|status| String::from(PresenceStatus::from(status).visible()));
+        let current: String = presence.visible_status().into();
+
+        if previous.as_deref() != Some(current.as_str()) {
+            Self::broadcast(pool, presence).await;
+        }
+    }
+
+    /// Broadcast a presence change to the device owner and every conversation they are in
+    async fn broadcast(pool: &DbPool, presence: &DevicePresence) {
+        let user_id = {
+            let Ok(mut conn) = pool.get() else {
+                return;
+            };
+            match devices::table
+                .find(presence.device_id.to_string())
+                .select(devices::user_id)
+                .first::<String>(&mut conn)
+            {
+                Ok(user_id) => user_id,
+                Err(e) => {
+                    tracing::warn!("Failed to load owner of device {}: {}", presence.device_id, e);
+                    return;
+                }
+            }
+        };
+
+        let status: String = presence.visible_status().into();
+        let event = json!({
+            "user_id": user_id,
+            "device_id": presence.device_id.to_string(),
+            "status": status,
+            "last_seen_at": presence.last_seen_at,
+        });
+
+        if let Err(e) = broadcast_to_user(&user_id, "presence.updated", event.clone()).await {
+            tracing::warn!("Failed to broadcast presence of device {} to its owner: {}", presence.device_id, e);
+        }
+
+        let conversation_ids = match ConversationService::conversation_ids_for_user(pool, &user_id) {
+            Ok(conversation_ids) => conversation_ids,
+            Err(e) => {
+                tracing::warn!("Failed to load conversations for presence of user {}: {}", user_id, e);
+                return;
+            }
+        };
+
+        for conversation_id in conversation_ids {
+            if let Err(e) = ConversationService::broadcast(&conversation_id, "presence.updated", event.clone()).await {
+                tracing::warn!("Failed to broadcast presence of device {} to conversation {}: {}", presence.device_id, conversation_id, e);
+            }
+        }
+    }
+
+    /// Lower is more available
+    fn availability_rank(status: &PresenceStatus) -> u8 {
+        match status {
+            PresenceStatus::Online => 0,
+            PresenceStatus::Busy => 1,
+            PresenceStatus::DoNotDisturb => 2,
+            PresenceStatus::Away => 3,
+            PresenceStatus::Invisible => 4,
+            PresenceStatus::Offline => 5,
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use anyhow::Result;
+use chrono::Utc;
+use diesel::prelude::*;
+use std::time::Duration;
+use crate::app::models::DieselUlid;
+use crate::app::models::feature_flag::{FeatureFlag, FeatureFlagOverride};
+use crate::cache::{shared_cache, Cache};
+use crate::config::Config;
+use crate::database::DbPool;
+use crate::schema::{feature_flag_overrides, feature_flags};
+
+#[derive(Debug, thiserror::Error)]
+pub enum FeatureFlagError {
+    #[error("Feature flag '{0}' not found")]
+    NotFound(String),
+
+    #[error("Rollout percentage must be between 0 and 100")]
+    InvalidRollout,
+}
+
+pub struct FeatureFlagService;
+
+impl FeatureFlagService {
+    pub fn list(pool: &DbPool) -> Result<Vec<FeatureFlag>> {
+        let mut conn = pool.get()?;
+
+        let result = feature_flags::table
+            .order(feature_flags::name.asc())
+            .select(FeatureFlag::as_select())
+            .load::<FeatureFlag>(&mut conn)?;
+        Ok(result)
+    }
+
+    pub fn find_by_name(pool: &DbPool, name: &str) -> Result<Option<FeatureFlag>> {
+        let mut conn = pool.get()?;
+
+        let result = feature_flags::table
+            .filter(feature_flags::name.eq(name))
+            .select(FeatureFlag::as_select())
+            .first::<FeatureFlag>(&mut conn)
+            .optional()?;
+        Ok(result)
+    }
+
+    /// A user's override of a flag, if any
+    pub fn find_override(pool: &DbPool, feature_flag_id: DieselUlid, user_id: &str) -> Result<Option<bool>> {
+        let mut conn = pool.get()?;
+
+        let result = feature_flag_overrides::table
+            .filter(feature_flag_overrides::feature_flag_id.eq(feature_flag_id.to_string()))
+            .filter(feature_flag_overrides::user_id.eq(user_id))
+            .select(feature_flag_overrides::enabled)
+            .first::<bool>(&mut conn)
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Create a flag or change the given settings of an existing one
+    ///
+    /// A new flag is off unless `enabled` says otherwise.
+    pub async fn set(
+        pool: &DbPool,
+        name: &str,
+        enabled: Option<bool>,
+        rollout_percentage: Option<i32>,
+        description: Option<String>,
+    ) -> Result<FeatureFlag> {
+        if rollout_percentage.is_some_and(|percentage| !(0..=100).contains(&percentage)) {
+            return Err(FeatureFlagError::InvalidRollout.into());
+        }
+
+        let flag = {
+            let mut conn = pool.get()?;
+            let mut flag = FeatureFlag::new(name.to_string(), enabled.unwrap_or(false), rollout_percentage);
+            flag.description = description.clone();
+
+            let existing = feature_flags::table
+                .filter(feature_flags::name.eq(name))
+                .select(FeatureFlag::as_select())
+                .first::<FeatureFlag>(&mut conn)
+                .optional()?;
+
+            match existing {
+                Some(existing) => diesel::update(feature_flags::table.filter(feature_flags::id.eq(existing.id.to_string())))
+                    .set((
+                        feature_flags::enabled.eq(enabled.unwrap_or(existing.enabled)),
+                        feature_flags::rollout_percentage.eq(rollout_percentage.or(existing.rollout_percentage)),
+                        feature_flags::description.eq(description.or(existing.description)),
+                        feature_flags::updated_at.eq(Utc::now()),
+                    ))
+                    .returning(FeatureFlag::as_returning())
+                    .get_result::<FeatureFlag>(&mut conn)?,
+                None => diesel::insert_into(feature_flags::table)
+                    .values(&flag)
+                    .returning(FeatureFlag::as_returning())
+                    .get_result::<FeatureFlag>(&mut conn)?,
+            }
+        };
+
+        Self::forget(&Self::cache_key(name)).await;
+        Ok(flag)
+    }
+
+    /// Force a flag on or off for one user; `None` removes the override
+    pub async fn set_override(pool: &DbPool, name: &str, user_id: DieselUlid, enabled: Option<bool>) -> Result<()> {
+        let flag = Self::find_by_name(pool, name)?
+            .ok_or_else(|| FeatureFlagError::NotFound(name.to_string()))?;
+
+        {
+            let mut conn = pool.get()?;
+            match enabled {
+                Some(enabled) => {
+                    diesel::insert_into(feature_flag_overrides::table)
+                        .values(&FeatureFlagOverride::new(flag.id, user_id, enabled))
+                        .on_conflict((feature_flag_overrides::feature_flag_id, feature_flag_overrides::user_id))
+                        .do_update()
+                        .set((
+                            feature_flag_overrides::enabled.eq(enabled),
+                            feature_flag_overrides::updated_at.eq(Utc::now()),
+                        ))
+                        .execute(&mut conn)?;
+                },
+                None => {
+                    diesel::delete(
+                        feature_flag_overrides::table
+                            .filter(feature_flag_overrides::feature_flag_id.eq(flag.id.to_string()))
+                            .filter(feature_flag_overrides::user_id.eq(user_id.to_string())),
+                    )
+                    .execute(&mut conn)?;
+                },
+            }
+        }
+
+        Self::forget(&Self::override_cache_key(name, &user_id.to_string())).await;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &DbPool, name: &str) -> Result<bool> {
+        let deleted = {
+            let mut conn = pool.get()?;
+            diesel::delete(feature_flags::table.filter(feature_flags::name.eq(name))).execute(&mut conn)?
+        };
+
+        Self::forget(&Self::cache_key(name)).await;
+        Ok(deleted > 0)
+    }
+
+    /// Flag by name, cached for `FEATURE_FLAG_CACHE_TTL_SECS`
+    pub async fn cached(pool: &DbPool, name: &str) -> Result<Option<FeatureFlag>> {
+        let pool = pool.clone();
+        let owned_name = name.to_string();
+        shared_cache().await?
+            .remember(&Self::cache_key(name), Some(Self::ttl()?), || async move {
+                Self::find_by_name(&pool, &owned_name)
+            })
+            .await
+    }
+
+    /// A user's override of a flag, cached like the flag itself
+    pub async fn cached_override(pool: &DbPool, flag: &FeatureFlag, user_id: &str) -> Result<Option<bool>> {
+        let pool = pool.clone();
+        let flag_id = flag.id;
+        let owned_user_id = user_id.to_string();
+        shared_cache().await?
+            .remember(&Self::override_cache_key(&flag.name, user_id), Some(Self::ttl()?), || async move {
+                Self::find_override(&pool, flag_id, &owned_user_id)
+            })
+            .await
+    }
+
+    fn ttl() -> Result<Duration> {
+        Ok(Duration::from_secs(Config::load()?.cache.feature_ttl_secs))
+    }
+
+    fn cache_key(name: &str) -> String {
+        format!("feature_flags:{}", name)
+    }
+
+    fn override_cache_key(name: &str, user_id: &str) -> String {
+        format!("feature_flags:{}:users:{}", name, user_id)
+    }
+
+    /// Drop a cached lookup so the change is seen right away
+    ///
+    /// With a shared (redis) store that covers every process. With the
+    /// `memory` store only this process forgets it; others keep their copy
+    /// for up to `FEATURE_FLAG_CACHE_TTL_SECS`.
+    async fn forget(key: &str) {
+        match shared_cache().await {
+            Ok(cache) => {
+                if let Err(e) = cache.forget(key).await {
+                    tracing::warn!("Failed to forget cached feature flag {}: {}", key, e);
+                }
+            },
+            Err(e) => tracing::warn!("Failed to forget cached feature flag {}: {}", key, e),
+        }
+    }
+}
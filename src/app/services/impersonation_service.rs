@@ -0,0 +1,213 @@
+use anyhow::Result;
+use axum::http::StatusCode;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use crate::database::DbPool;
+
+use crate::app::http::middleware::auth_guard::AuthUser;
+use crate::app::http::middleware::auth_middleware::validate_jwt_claims;
+use crate::app::models::DieselUlid;
+use crate::app::models::activity_log::ActivityLog;
+use crate::app::models::user::UserResponse;
+use crate::app::services::activity_log_service::ActivityLogService;
+use crate::app::services::auth_service::AuthService;
+use crate::app::services::security_incident_service::SecurityIncidentService;
+use crate::app::services::user_service::UserService;
+use crate::config::Config;
+
+/// Activity log name for everything recorded during impersonation
+pub const IMPERSONATION_LOG: &str = "impersonation";
+
+/// Errors raised while starting or stopping impersonation
+#[derive(Debug, thiserror::Error)]
+pub enum ImpersonationError {
+    #[error("Only administrators can impersonate users")]
+    NotAdmin,
+
+    #[error("User not found")]
+    UserNotFound,
+
+    #[error("You cannot impersonate yourself")]
+    CannotImpersonateSelf,
+
+    #[error("Administrators cannot be impersonated")]
+    CannotImpersonateAdmin,
+
+    #[error("Stop the current impersonation before starting another")]
+    AlreadyImpersonating,
+
+    #[error("You are not impersonating anyone")]
+    NotImpersonating,
+}
+
+impl ImpersonationError {
+    /// HTTP status for an error returned by the impersonation service
+    pub fn status_code(error: &anyhow::Error) -> StatusCode {
+        match error.downcast_ref::<ImpersonationError>() {
+            Some(ImpersonationError::NotAdmin) | Some(ImpersonationError::CannotImpersonateAdmin) => StatusCode::FORBIDDEN,
+            Some(ImpersonationError::UserNotFound) => StatusCode::NOT_FOUND,
+            Some(ImpersonationError::CannotImpersonateSelf)
+            | Some(ImpersonationError::AlreadyImpersonating)
+            | Some(ImpersonationError::NotImpersonating) => StatusCode::CONFLICT,
+            None => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Token issued when impersonation starts or stops
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImpersonationResponse {
+    pub access_token: String,
+    pub expires_at: DateTime<Utc>,
+    /// User the token authenticates as
+    pub user: UserResponse,
+    /// Administrator acting as `user`; the UI shows a banner while this is set
+    pub impersonator_id: Option<String>,
+}
+
+/// "Login as" for support staff, similar to Laravel packages such as
+/// `lab404/laravel-impersonate`
+///
+/// The impersonation token carries an `impersonator_id` claim, and every
+/// request made with it is written to the activity log with both ids. It is
+/// revoked when impersonation stops.
+pub struct ImpersonationService;
+
+impl ImpersonationService {
+    /// Issue a token that authenticates the administrator as `user_id`
+    pub async fn start(pool: &DbPool, auth_user: &AuthUser, user_id: &str) -> Result<ImpersonationResponse> {
+        if auth_user.is_impersonated() {
+            return Err(ImpersonationError::AlreadyImpersonating.into());
+        }
+        if !SecurityIncidentService::is_admin(pool, &auth_user.user_id)? {
+            return Err(ImpersonationError::NotAdmin.into());
+        }
+        if auth_user.user_id == user_id {
+            return Err(ImpersonationError::CannotImpersonateSelf.into());
+        }
+
+        let user = UserService::find_by_id(pool, user_id.to_string())?
+            .ok_or(ImpersonationError::UserNotFound)?;
+        if SecurityIncidentService::is_admin(pool, user_id)? {
+            return Err(ImpersonationError::CannotImpersonateAdmin.into());
+        }
+
+        let config = Config::load()?;
+        let expires_in = Duration::minutes(config.auth.impersonation_expire_minutes);
+        let access_token = AuthService::generate_impersonation_token(
+            user_id,
+            &auth_user.user_id,
+            expires_in.num_seconds().max(0) as u64,
+        )?;
+
+        Self::log(
+            ActivityLogService::with_pool(pool.clone()),
+            user_id,
+            &auth_user.user_id,
+            "impersonation.started",
+            format!("Impersonation of user {} started by {}", user.id, auth_user.user_id),
+            json!({}),
+        ).await;
+
+        Ok(ImpersonationResponse {
+            access_token,
+            expires_at: Utc::now() + expires_in,
+            user: user.to_response(),
+            impersonator_id: Some(auth_user.user_id.clone()),
+        })
+    }
+
+    /// End impersonation and issue a regular token for the administrator again
+    ///
+    /// `token` is the impersonation token the request was made with; it is
+    /// revoked so it cannot be used again after impersonation stops.
+    pub async fn stop(pool: &DbPool, auth_user: &AuthUser, token: &str) -> Result<ImpersonationResponse> {
+        let impersonator_id = auth_user
+            .impersonator_id
+            .as_deref()
+            .ok_or(ImpersonationError::NotImpersonating)?;
+        let claims = validate_jwt_claims(token)
+            .filter(|claims| claims.impersonator_id.as_deref() == Some(impersonator_id))
+            .ok_or(ImpersonationError::NotImpersonating)?;
+
+        let impersonator = UserService::find_by_id(pool, impersonator_id.to_string())?
+            .ok_or(ImpersonationError::UserNotFound)?;
+
+        AuthService::revoke_access_token(pool, &claims.jti, claims.exp)?;
+
+        let config = Config::load()?;
+        let access_token = AuthService::generate_access_token(impersonator_id, config.auth.jwt_expires_in)?;
+
+        Self::log(
+            ActivityLogService::with_pool(pool.clone()),
+            &auth_user.user_id,
+            impersonator_id,
+            "impersonation.stopped",
+            format!("Impersonation of user {} stopped by {}", auth_user.user_id, impersonator_id),
+            json!({}),
+        ).await;
+
+        Ok(ImpersonationResponse {
+            access_token,
+            expires_at: Utc::now() + Duration::seconds(config.auth.jwt_expires_in as i64),
+            user: impersonator.to_response(),
+            impersonator_id: None,
+        })
+    }
+
+    /// Record a request made with an impersonation token
+    pub async fn log_request(auth_user: &AuthUser, method: &str, path: &str, status: StatusCode) {
+        let Some(impersonator_id) = auth_user.impersonator_id.as_deref() else {
+            return;
+        };
+
+        Self::log(
+            ActivityLogService::new(),
+            &auth_user.user_id,
+            impersonator_id,
+            "impersonation.request",
+            format!("{} {} as user {} by {}", method, path, auth_user.user_id, impersonator_id),
+            json!({
+                "method": method,
+                "path": path,
+                "status_code": status.as_u16(),
+            }),
+        ).await;
+    }
+
+    /// Activity caused by the impersonator, with the impersonated user as subject
+    async fn log(
+        service: ActivityLogService,
+        user_id: &str,
+        impersonator_id: &str,
+        event: &str,
+        description: String,
+        mut properties: Value,
+    ) {
+        properties["user_id"] = json!(user_id);
+        properties["impersonator_id"] = json!(impersonator_id);
+
+        let now = Utc::now();
+        let activity = ActivityLog {
+            id: DieselUlid::new(),
+            log_name: Some(IMPERSONATION_LOG.to_string()),
+            description,
+            subject_type: Some("User".to_string()),
+            subject_id: Some(user_id.to_string()),
+            causer_type: Some("User".to_string()),
+            causer_id: Some(impersonator_id.to_string()),
+            properties: Some(properties),
+            correlation_id: None,
+            batch_uuid: None,
+            event: Some(event.to_string()),
+            created_at: now,
+            updated_at: now,
+        };
+
+        if let Err(e) = service.create(activity).await {
+            tracing::error!("Failed to log {}: {}", event, e);
+        }
+    }
+}
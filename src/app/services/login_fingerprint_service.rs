@@ -0,0 +1,180 @@
+use anyhow::Result;
+use axum::http::HeaderMap;
+use chrono::Utc;
+use diesel::prelude::*;
+use sha2::{Digest, Sha256};
+use std::net::IpAddr;
+use crate::config::Config;
+use crate::config::auth::NewDeviceChallenge;
+use crate::database::DbPool;
+use crate::schema::login_fingerprints;
+use crate::app::models::DieselUlid;
+use crate::app::models::login_fingerprint::LoginFingerprint;
+
+/// Headers hashed into a login fingerprint, in order
+const FINGERPRINT_HEADERS: [&str; 7] = [
+    "user-agent",
+    "accept",
+    "accept-language",
+    "accept-encoding",
+    "sec-ch-ua",
+    "sec-ch-ua-platform",
+    "sec-ch-ua-mobile",
+];
+
+/// Fingerprint of the client making a login request
+#[derive(Debug, Clone)]
+pub struct ClientFingerprint {
+    pub hash: String,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+impl ClientFingerprint {
+    /// Hash the User-Agent, Accept headers and any client hints of a request
+    ///
+    /// `client_ip` is the address resolved by `trusted_proxy_middleware`;
+    /// forwarding headers are not read here since any client can send them.
+    pub fn from_headers(headers: &HeaderMap, client_ip: Option<IpAddr>) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok()).map(str::trim);
+
+        let mut hasher = Sha256::new();
+        for name in FINGERPRINT_HEADERS {
+            hasher.update(name.as_bytes());
+            hasher.update(b"=");
+            hasher.update(header(name).unwrap_or_default().as_bytes());
+            hasher.update(b"\n");
+        }
+
+        Self {
+            hash: format!("{:x}", hasher.finalize()),
+            user_agent: header("user-agent").map(str::to_string),
+            ip_address: client_ip.map(|ip| ip.to_string()),
+        }
+    }
+}
+
+/// Outcome of checking a login against the user's known clients
+#[derive(Debug)]
+pub enum FingerprintCheck {
+    Recognized(LoginFingerprint),
+    /// Recorded, but the login must be confirmed before tokens are issued
+    Unrecognized(LoginFingerprint),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LoginFingerprintError {
+    #[error("Device confirmation not found or already completed")]
+    ChallengeNotFound,
+}
+
+pub struct LoginFingerprintService {
+    mode: NewDeviceChallenge,
+}
+
+impl LoginFingerprintService {
+    pub fn new() -> Result<Self> {
+        Ok(Self::with_mode(Config::load()?.auth.new_device_challenge))
+    }
+
+    pub fn with_mode(mode: NewDeviceChallenge) -> Self {
+        Self { mode }
+    }
+
+    /// Compare a login's fingerprint with the user's confirmed ones, recording it if new
+    pub fn check(&self, pool: &DbPool, user_id: DieselUlid, fingerprint: &ClientFingerprint) -> Result<FingerprintCheck> {
+        let mut conn = pool.get()?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            let now = Utc::now();
+            let existing = login_fingerprints::table
+                .filter(login_fingerprints::user_id.eq(user_id.to_string()))
+                .filter(login_fingerprints::fingerprint_hash.eq(&fingerprint.hash))
+                .select(LoginFingerprint::as_select())
+                .for_update()
+                .first::<LoginFingerprint>(conn)
+                .optional()?;
+
+            let trusted = match existing.as_ref() {
+                Some(known) if known.is_confirmed() => true,
+                _ => match self.mode {
+                    NewDeviceChallenge::Off => true,
+                    NewDeviceChallenge::Strict => false,
+                    NewDeviceChallenge::Lenient => !Self::has_confirmed(conn, user_id)?,
+                },
+            };
+            let confirmed_at = existing.as_ref().and_then(|known| known.confirmed_at)
+                .or_else(|| trusted.then_some(now));
+
+            let mut record = LoginFingerprint::new(user_id, fingerprint.hash.clone(), fingerprint.user_agent.clone(), fingerprint.ip_address.clone());
+            record.confirmed_at = confirmed_at;
+
+            let record = diesel::insert_into(login_fingerprints::table)
+                .values(&record)
+                .on_conflict((login_fingerprints::user_id, login_fingerprints::fingerprint_hash))
+                .do_update()
+                .set((
+                    login_fingerprints::ip_address.eq(&fingerprint.ip_address),
+                    login_fingerprints::confirmed_at.eq(confirmed_at),
+                    login_fingerprints::last_seen_at.eq(now),
+                    login_fingerprints::updated_at.eq(now),
+                ))
+                .returning(LoginFingerprint::as_returning())
+                .get_result::<LoginFingerprint>(conn)?;
+
+            Ok(if trusted {
+                FingerprintCheck::Recognized(record)
+            } else {
+                FingerprintCheck::Unrecognized(record)
+            })
+        })
+    }
+
+    /// Mark a pending fingerprint as recognized once the login was confirmed
+    pub fn confirm(&self, pool: &DbPool, user_id: &str, challenge_id: &str) -> Result<LoginFingerprint> {
+        let mut conn = pool.get()?;
+        let now = Utc::now();
+
+        let fingerprint = diesel::update(
+            login_fingerprints::table
+                .filter(login_fingerprints::id.eq(challenge_id))
+                .filter(login_fingerprints::user_id.eq(user_id))
+                .filter(login_fingerprints::confirmed_at.is_null())
+        )
+        .set((
+            login_fingerprints::confirmed_at.eq(Some(now)),
+            login_fingerprints::last_seen_at.eq(now),
+            login_fingerprints::updated_at.eq(now),
+        ))
+        .returning(LoginFingerprint::as_returning())
+        .get_result::<LoginFingerprint>(&mut conn)
+        .optional()?
+        .ok_or(LoginFingerprintError::ChallengeNotFound)?;
+
+        Ok(fingerprint)
+    }
+
+    /// Whether a pending fingerprint belongs to the user, checked before consuming a confirmation code
+    pub fn is_pending(&self, pool: &DbPool, user_id: &str, challenge_id: &str) -> Result<bool> {
+        let mut conn = pool.get()?;
+
+        let count: i64 = login_fingerprints::table
+            .filter(login_fingerprints::id.eq(challenge_id))
+            .filter(login_fingerprints::user_id.eq(user_id))
+            .filter(login_fingerprints::confirmed_at.is_null())
+            .count()
+            .get_result(&mut conn)?;
+
+        Ok(count > 0)
+    }
+
+    fn has_confirmed(conn: &mut PgConnection, user_id: DieselUlid) -> QueryResult<bool> {
+        let count: i64 = login_fingerprints::table
+            .filter(login_fingerprints::user_id.eq(user_id.to_string()))
+            .filter(login_fingerprints::confirmed_at.is_not_null())
+            .count()
+            .get_result(conn)?;
+
+        Ok(count > 0)
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use diesel::prelude::*;
+use regex::Regex;
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::OnceLock;
+use crate::database::DbPool;
+use crate::schema::{message_mentions, sys_users};
+use crate::app::broadcasting::helpers::broadcast_to_user;
+use crate::app::models::message::Message;
+use crate::app::models::message_mentions::{MentionType, MessageMention};
+use crate::app::models::user::User;
+use crate::app::notifications::message_mention_notification::MessageMentionNotification;
+use crate::app::notifications::notify;
+use crate::app::services::conversation_service::ConversationService;
+
+/// An `@token` found in message text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedMention {
+    /// Username or user ULID, without the leading `@`
+    pub token: String,
+    /// Character offset of the `@`
+    pub start: i32,
+    /// Length in characters, including the `@`
+    pub length: i32,
+}
+
+fn mention_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"(?:^|\s)@([A-Za-z0-9_][A-Za-z0-9_.\-]*)").unwrap())
+}
+
+pub struct MentionService;
+
+impl MentionService {
+    /// Extract `@username` and `@ulid` mentions from plaintext
+    pub fn extract(text: &str) -> Vec<ParsedMention> {
+        mention_pattern()
+            .captures_iter(text)
+            .filter_map(|captures| {
+                let token = captures.get(1)?;
+                // Trailing punctuation ends a sentence, not a username
+                let name = token.as_str().trim_end_matches(['.', '-']);
+                if name.is_empty() {
+                    return None;
+                }
+
+                let at = token.start() - 1;
+                Some(ParsedMention {
+                    token: name.to_string(),
+                    start: text[..at].chars().count() as i32,
+                    length: name.chars().count() as i32 + 1,
+                })
+            })
+            .collect()
+    }
+
+    /// Parse client-supplied mention tokens, which carry no position data
+    pub fn from_tokens(tokens: &[String]) -> Vec<ParsedMention> {
+        tokens
+            .iter()
+            .map(|token| token.trim().trim_start_matches('@'))
+            .filter(|token| !token.is_empty())
+            .map(|token| ParsedMention {
+                token: token.to_string(),
+                start: -1,
+                length: -1,
+            })
+            .collect()
+    }
+
+    /// Store mentions of active participants and notify each mentioned user.
+    ///
+    /// Tokens that do not resolve to another participant of the message's
+    /// conversation are dropped, so non-participants are never notified.
+    pub async fn record(pool: &DbPool, message: &Message, mentions: Vec<ParsedMention>) -> Result<Vec<MessageMention>> {
+        if mentions.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conversation_id = message.conversation_id.to_string();
+        let sender_id = message.sender_user_id.to_string();
+
+        let participant_ids = ConversationService::participant_user_ids(pool, &conversation_id)?
+            .into_iter()
+            .filter(|user_id| *user_id != sender_id)
+            .collect::<Vec<_>>();
+
+        let mut conn = pool.get()?;
+        let participants = sys_users::table
+            .filter(sys_users::id.eq_any(&participant_ids))
+            .filter(sys_users::deleted_at.is_null())
+            .select(User::as_select())
+            .load::<User>(&mut conn)?;
+
+        let rows = mentions
+            .iter()
+            .filter_map(|mention| {
+                let user = Self::resolve(&participants, &mention.token)?;
+                let (start, length) = if mention.start >= 0 {
+                    (Some(mention.start), Some(mention.length))
+                } else {
+                    (None, None)
+                };
+                Some(MessageMention::new(message.id, user.id, MentionType::User, start, length))
+            })
+            .collect::<Vec<_>>();
+
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let stored = diesel::insert_into(message_mentions::table)
+            .values(&rows)
+            .returning(MessageMention::as_returning())
+            .get_results::<MessageMention>(&mut conn)?;
+
+        let mut notified = HashSet::new();
+        for mention in &stored {
+            if !notified.insert(mention.mentioned_user_id) {
+                continue;
+            }
+            if let Some(user) = participants.iter().find(|user| user.id == mention.mentioned_user_id) {
+                Self::notify_mentioned(user, message).await;
+            }
+        }
+
+        Ok(stored)
+    }
+
+    fn resolve<'a>(participants: &'a [User], token: &str) -> Option<&'a User> {
+        participants.iter().find(|user| {
+            user.id.to_string().eq_ignore_ascii_case(token)
+                || user.username.as_deref().is_some_and(|username| username.eq_ignore_ascii_case(token))
+        })
+    }
+
+    async fn notify_mentioned(user: &User, message: &Message) {
+        let notification = MessageMentionNotification::new(
+            message.id.to_string(),
+            message.conversation_id.to_string(),
+            message.sender_user_id.to_string(),
+        );
+        if let Err(e) = notify(user, notification).await {
+            tracing::warn!("Failed to notify user {} of mention in message {}: {}", user.id, message.id, e);
+        }
+
+        let event = json!({
+            "message_id": message.id.to_string(),
+            "conversation_id": message.conversation_id.to_string(),
+            "sender_user_id": message.sender_user_id.to_string(),
+        });
+        if let Err(e) = broadcast_to_user(&user.id.to_string(), "message.mentioned", event).await {
+            tracing::warn!("Failed to broadcast mention in message {} to user {}: {}", message.id, user.id, e);
+        }
+    }
+}
@@ -0,0 +1,475 @@
+use anyhow::Result;
+use chrono::Utc;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utoipa::ToSchema;
+use crate::database::DbPool;
+use crate::schema::{conversation_participants, devices, forward_history, message_delivery_status, message_reactions, messages};
+use crate::app::models::DieselUlid;
+use crate::app::models::forward_history::{ForwardHistory, ForwardHistoryResponse};
+use crate::app::models::message::{Message, MessageResponse, MessageType};
+use crate::app::models::message_delivery_status::{DeliveryStatus, MessageDeliveryStatus};
+use crate::app::models::message_mentions::MessageMentionResponse;
+use crate::app::models::message_reactions::MessageReaction;
+use crate::app::services::conversation_service::{ConversationError, ConversationService};
+use crate::app::services::mention_service::MentionService;
+use crate::app::traits::Touches;
+
+/// Errors raised by message operations
+#[derive(Debug, thiserror::Error)]
+pub enum MessageError {
+    #[error("Encrypted messages must be re-encrypted for the destination conversation")]
+    ContentRequired,
+}
+
+impl MessageError {
+    /// HTTP status for an error returned by the message service
+    pub fn status_code(error: &anyhow::Error) -> axum::http::StatusCode {
+        use axum::http::StatusCode;
+
+        match error.downcast_ref::<MessageError>() {
+            Some(MessageError::ContentRequired) => StatusCode::UNPROCESSABLE_ENTITY,
+            None => ConversationError::status_code(error),
+        }
+    }
+}
+
+/// Request payload identifying the device acknowledging a message
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MessageReceiptRequest {
+    pub device_id: DieselUlid,
+}
+
+/// Request payload for reacting to a message
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AddReactionRequest {
+    pub device_id: DieselUlid,
+    pub encrypted_reaction: String,
+    pub reaction_algorithm: String,
+}
+
+/// Request payload for sending a message to a conversation
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SendMessageRequest {
+    pub device_id: DieselUlid,
+    pub message_type: Option<String>,
+    pub encrypted_content: String,
+    pub content_algorithm: String,
+    pub reply_to_message_id: Option<DieselUlid>,
+    /// Usernames or user IDs mentioned in an encrypted message, which the server cannot parse
+    pub mentions: Option<Vec<String>>,
+}
+
+/// A sent message with the mentions recorded for it
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SentMessage {
+    pub message: MessageResponse,
+    pub mentions: Vec<MessageMentionResponse>,
+}
+
+/// Request payload for forwarding a message to another conversation
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ForwardMessageRequest {
+    pub conversation_id: DieselUlid,
+    pub device_id: DieselUlid,
+    /// Content re-encrypted for the destination; required when either conversation is encrypted
+    pub encrypted_content: Option<String>,
+    pub content_algorithm: Option<String>,
+}
+
+/// A forwarded message with its place in the forward chain
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ForwardedMessage {
+    pub message: MessageResponse,
+    pub forward: ForwardHistoryResponse,
+    /// Sender of the message that started the forward chain
+    pub forwarded_from_user_id: DieselUlid,
+}
+
+pub struct MessageService;
+
+impl MessageService {
+    /// Send a message, recording and notifying any mentioned participants
+    pub async fn send(pool: &DbPool, conversation_id: &str, user_id: &str, data: SendMessageRequest) -> Result<SentMessage> {
+        let conversation = ConversationService::find_conversation(pool, conversation_id)?;
+        ConversationService::ensure_participant(pool, conversation_id, user_id)?;
+        let device = ConversationService::ensure_user_device(pool, &data.device_id.to_string(), user_id)?;
+
+        let mut conn = pool.get()?;
+
+        if let Some(reply_to_message_id) = data.reply_to_message_id {
+            messages::table
+                .filter(messages::id.eq(reply_to_message_id.to_string()))
+                .filter(messages::conversation_id.eq(conversation_id))
+                .select(messages::id)
+                .first::<String>(&mut conn)
+                .optional()?
+                .ok_or(ConversationError::MessageNotFound)?;
+        }
+
+        // Plaintext bodies are parsed; encrypted bodies rely on the client's mention list
+        let mentions = if conversation.is_encrypted {
+            MentionService::from_tokens(&data.mentions.unwrap_or_default())
+        } else {
+            MentionService::extract(&data.encrypted_content)
+        };
+
+        let mut message = Message::new(
+            conversation.id,
+            device.user_id,
+            device.id,
+            data.encrypted_content,
+            data.content_algorithm,
+        );
+        if let Some(message_type) = data.message_type {
+            message = message.with_type(MessageType::from(message_type));
+        }
+        if let Some(reply_to_message_id) = data.reply_to_message_id {
+            message = message.reply_to(reply_to_message_id);
+        }
+
+        let message = conn.transaction::<_, anyhow::Error, _>(|conn| {
+            let message = diesel::insert_into(messages::table)
+                .values(&message)
+                .returning(Message::as_returning())
+                .get_result::<Message>(conn)?;
+
+            Self::create_receipts(conn, &message)?;
+            message.touch_parents(conn)?;
+
+            Ok(message)
+        })?;
+
+        // The message is committed; a failed mention must not make the client resend it
+        let mentions = match MentionService::record(pool, &message, mentions).await {
+            Ok(mentions) => mentions,
+            Err(e) => {
+                tracing::warn!("Failed to record mentions for message {}: {}", message.id, e);
+                Vec::new()
+            }
+        };
+
+        let sent = SentMessage {
+            message: message.to_response(),
+            mentions: mentions.iter().map(|mention| mention.to_response()).collect(),
+        };
+
+        let event = json!({
+            "message": sent.message,
+            "mentions": sent.mentions,
+        });
+        if let Err(e) = ConversationService::broadcast(conversation_id, "message.sent", event).await {
+            tracing::warn!("Failed to broadcast message {}: {}", message.id, e);
+        }
+
+        Ok(sent)
+    }
+
+    /// Forward a message into another conversation the user participates in
+    pub async fn forward(pool: &DbPool, message_id: &str, user_id: &str, data: ForwardMessageRequest) -> Result<ForwardedMessage> {
+        let (source, _) = ConversationService::find_message_for_participant(pool, message_id, user_id)?;
+        let destination_id = data.conversation_id.to_string();
+        let source_conversation = ConversationService::find_conversation(pool, &source.conversation_id.to_string())?;
+        let destination = ConversationService::find_conversation(pool, &destination_id)?;
+        ConversationService::ensure_participant(pool, &destination_id, user_id)?;
+        let device = ConversationService::ensure_user_device(pool, &data.device_id.to_string(), user_id)?;
+
+        // Ciphertext is bound to its conversation, so only plaintext can be copied as is
+        let (encrypted_content, content_algorithm) = match (data.encrypted_content, data.content_algorithm) {
+            (Some(content), Some(algorithm)) => (content, algorithm),
+            _ if source_conversation.is_encrypted || destination.is_encrypted => {
+                return Err(MessageError::ContentRequired.into());
+            }
+            _ => (source.encrypted_content.clone(), source.content_algorithm.clone()),
+        };
+
+        let mut conn = pool.get()?;
+        let (message, forward, original_sender_id) = conn.transaction::<_, anyhow::Error, _>(|conn| {
+            // Forwarding a forward extends the chain back to the first message
+            let parent = forward_history::table
+                .filter(forward_history::message_id.eq(source.id.to_string()))
+                .select(ForwardHistory::as_select())
+                .first::<ForwardHistory>(conn)
+                .optional()?;
+
+            let (original_message_id, forward_depth) = match &parent {
+                Some(parent) => (parent.original_message_id, parent.forward_depth + 1),
+                None => (source.id, 1),
+            };
+
+            let original_sender_id = messages::table
+                .find(original_message_id.to_string())
+                .select(messages::sender_user_id)
+                .first::<String>(conn)?;
+
+            let message = Message::new(
+                destination.id,
+                device.user_id,
+                device.id,
+                encrypted_content,
+                content_algorithm,
+            )
+            .with_type(MessageType::from(source.message_type.clone()))
+            .forward_from(source.id);
+
+            let message = diesel::insert_into(messages::table)
+                .values(&message)
+                .returning(Message::as_returning())
+                .get_result::<Message>(conn)?;
+
+            Self::create_receipts(conn, &message)?;
+            message.touch_parents(conn)?;
+
+            let forward = ForwardHistory::new(message.id, original_message_id, device.user_id, device.id, forward_depth);
+            let forward = diesel::insert_into(forward_history::table)
+                .values(&forward)
+                .returning(ForwardHistory::as_returning())
+                .get_result::<ForwardHistory>(conn)?;
+
+            Ok((message, forward, DieselUlid::from_string(&original_sender_id)?))
+        })?;
+
+        let forwarded = ForwardedMessage {
+            message: message.to_response(),
+            forward: forward.to_response(),
+            forwarded_from_user_id: original_sender_id,
+        };
+
+        let event = json!({
+            "message": forwarded.message,
+            "forward": forwarded.forward,
+            "forwarded_from_user_id": forwarded.forwarded_from_user_id,
+        });
+        if let Err(e) = ConversationService::broadcast(&destination_id, "message.sent", event).await {
+            tracing::warn!("Failed to broadcast forwarded message {}: {}", message.id, e);
+        }
+
+        Ok(forwarded)
+    }
+
+    /// Create `sent` receipts for the active devices of every participant except the sending device
+    pub(crate) fn create_receipts(conn: &mut PgConnection, message: &Message) -> QueryResult<usize> {
+        let recipient_user_ids = conversation_participants::table
+            .filter(conversation_participants::conversation_id.eq(message.conversation_id.to_string()))
+            .filter(conversation_participants::is_active.eq(true))
+            .select(conversation_participants::user_id)
+            .load::<String>(conn)?;
+
+        let recipient_device_ids = devices::table
+            .filter(devices::user_id.eq_any(&recipient_user_ids))
+            .filter(devices::is_active.eq(true))
+            .filter(devices::id.ne(message.sender_device_id.to_string()))
+            .select(devices::id)
+            .load::<String>(conn)?;
+
+        let receipts = recipient_device_ids
+            .iter()
+            .filter_map(|device_id| DieselUlid::from_string(device_id).ok())
+            .map(|device_id| MessageDeliveryStatus::new(message.id, device_id).with_status(DeliveryStatus::Sent))
+            .collect::<Vec<_>>();
+
+        diesel::insert_into(message_delivery_status::table)
+            .values(&receipts)
+            .on_conflict((message_delivery_status::message_id, message_delivery_status::recipient_device_id))
+            .do_nothing()
+            .execute(conn)
+    }
+
+    /// Mark a message as delivered to one of the user's devices
+    pub async fn mark_delivered(pool: &DbPool, message_id: &str, user_id: &str, device_id: &str) -> Result<MessageDeliveryStatus> {
+        Self::record_receipt(pool, message_id, user_id, device_id, DeliveryStatus::Delivered).await
+    }
+
+    /// Mark a message as read on one of the user's devices
+    pub async fn mark_read(pool: &DbPool, message_id: &str, user_id: &str, device_id: &str) -> Result<MessageDeliveryStatus> {
+        Self::record_receipt(pool, message_id, user_id, device_id, DeliveryStatus::Read).await
+    }
+
+    async fn record_receipt(
+        pool: &DbPool,
+        message_id: &str,
+        user_id: &str,
+        device_id: &str,
+        status: DeliveryStatus,
+    ) -> Result<MessageDeliveryStatus> {
+        let (message, participant) = ConversationService::find_message_for_participant(pool, message_id, user_id)?;
+        let device = ConversationService::ensure_user_device(pool, device_id, user_id)?;
+        let is_read = matches!(status, DeliveryStatus::Read);
+
+        let mut conn = pool.get()?;
+        let receipt = conn.transaction::<_, anyhow::Error, _>(|conn| {
+            let now = Utc::now();
+
+            let existing = message_delivery_status::table
+                .filter(message_delivery_status::message_id.eq(message.id.to_string()))
+                .filter(message_delivery_status::recipient_device_id.eq(device.id.to_string()))
+                .select(MessageDeliveryStatus::as_select())
+                .for_update()
+                .first::<MessageDeliveryStatus>(conn)
+                .optional()?;
+
+            let is_new = existing.is_none();
+            let mut receipt = existing.unwrap_or_else(|| MessageDeliveryStatus::new(message.id, device.id));
+
+            // Reading implies delivery, and a read receipt is never downgraded
+            if receipt.delivered_at.is_none() {
+                receipt.delivered_at = Some(now);
+            }
+            if is_read && receipt.read_at.is_none() {
+                receipt.read_at = Some(now);
+            }
+            receipt.status = if receipt.read_at.is_some() {
+                DeliveryStatus::Read.into()
+            } else {
+                DeliveryStatus::Delivered.into()
+            };
+            receipt.updated_at = now;
+
+            let receipt = if is_new {
+                diesel::insert_into(message_delivery_status::table)
+                    .values(&receipt)
+                    .get_result::<MessageDeliveryStatus>(conn)?
+            } else {
+                diesel::update(message_delivery_status::table.find(receipt.id.to_string()))
+                    .set(&receipt)
+                    .get_result::<MessageDeliveryStatus>(conn)?
+            };
+
+            if is_read {
+                // Receipts can arrive out of order, so the read marker only moves forward
+                let older_messages = messages::table
+                    .filter(messages::conversation_id.eq(message.conversation_id.to_string()))
+                    .filter(
+                        messages::created_at.lt(message.created_at).or(
+                            messages::created_at.eq(message.created_at)
+                                .and(messages::id.lt(message.id.to_string())),
+                        ),
+                    )
+                    .select(messages::id.nullable());
+
+                diesel::update(conversation_participants::table.find(participant.id.to_string()))
+                    .filter(
+                        conversation_participants::last_read_message_id.is_null()
+                            .or(conversation_participants::last_read_message_id.eq_any(older_messages)),
+                    )
+                    .set((
+                        conversation_participants::last_read_message_id.eq(Some(message.id.to_string())),
+                        conversation_participants::last_read_at.eq(Some(now)),
+                        conversation_participants::updated_at.eq(now),
+                    ))
+                    .execute(conn)?;
+            }
+
+            Ok(receipt)
+        })?;
+
+        let event = if is_read { "message.read" } else { "message.delivered" };
+        let data = json!({
+            "message_id": message.id.to_string(),
+            "user_id": user_id,
+            "device_id": device.id.to_string(),
+            "status": receipt.status,
+            "delivered_at": receipt.delivered_at,
+            "read_at": receipt.read_at,
+        });
+
+        if let Err(e) = ConversationService::broadcast(&message.conversation_id.to_string(), event, data).await {
+            tracing::warn!("Failed to broadcast {} for message {}: {}", event, message.id, e);
+        }
+
+        Ok(receipt)
+    }
+
+    /// Delivery status of a message across all recipient devices
+    pub fn delivery_status(pool: &DbPool, message_id: &str, user_id: &str) -> Result<Vec<MessageDeliveryStatus>> {
+        let (message, _) = ConversationService::find_message_for_participant(pool, message_id, user_id)?;
+        let mut conn = pool.get()?;
+
+        let statuses = message_delivery_status::table
+            .filter(message_delivery_status::message_id.eq(message.id.to_string()))
+            .order(message_delivery_status::created_at.asc())
+            .select(MessageDeliveryStatus::as_select())
+            .load::<MessageDeliveryStatus>(&mut conn)?;
+
+        Ok(statuses)
+    }
+
+    /// Reactions on a message
+    pub fn reactions(pool: &DbPool, message_id: &str, user_id: &str) -> Result<Vec<MessageReaction>> {
+        let (message, _) = ConversationService::find_message_for_participant(pool, message_id, user_id)?;
+        let mut conn = pool.get()?;
+
+        let reactions = message_reactions::table
+            .filter(message_reactions::message_id.eq(message.id.to_string()))
+            .order(message_reactions::created_at.asc())
+            .select(MessageReaction::as_select())
+            .load::<MessageReaction>(&mut conn)?;
+
+        Ok(reactions)
+    }
+
+    /// Add a reaction to a message
+    pub async fn add_reaction(pool: &DbPool, message_id: &str, user_id: &str, data: AddReactionRequest) -> Result<MessageReaction> {
+        let (message, participant) = ConversationService::find_message_for_participant(pool, message_id, user_id)?;
+        let device = ConversationService::ensure_user_device(pool, &data.device_id.to_string(), user_id)?;
+        let mut conn = pool.get()?;
+
+        let reaction = MessageReaction::new(
+            message.id,
+            participant.user_id,
+            device.id,
+            data.encrypted_reaction,
+            data.reaction_algorithm,
+        );
+
+        // The same reaction twice is a no-op rather than a conflict
+        let reaction = diesel::insert_into(message_reactions::table)
+            .values(&reaction)
+            .on_conflict((message_reactions::message_id, message_reactions::user_id, message_reactions::encrypted_reaction))
+            .do_update()
+            .set(message_reactions::updated_at.eq(Utc::now()))
+            .get_result::<MessageReaction>(&mut conn)?;
+
+        let data = json!({
+            "message_id": message.id.to_string(),
+            "reaction": reaction.to_response(),
+        });
+
+        if let Err(e) = ConversationService::broadcast(&message.conversation_id.to_string(), "message.reaction_added", data).await {
+            tracing::warn!("Failed to broadcast reaction for message {}: {}", message.id, e);
+        }
+
+        Ok(reaction)
+    }
+
+    /// Remove one of the user's reactions from a message
+    pub async fn remove_reaction(pool: &DbPool, message_id: &str, reaction_id: &str, user_id: &str) -> Result<bool> {
+        let (message, _) = ConversationService::find_message_for_participant(pool, message_id, user_id)?;
+        let mut conn = pool.get()?;
+
+        let deleted = diesel::delete(
+            message_reactions::table
+                .filter(message_reactions::id.eq(reaction_id))
+                .filter(message_reactions::message_id.eq(message.id.to_string()))
+                .filter(message_reactions::user_id.eq(user_id))
+        )
+        .execute(&mut conn)?;
+
+        if deleted == 0 {
+            return Ok(false);
+        }
+
+        let data = json!({
+            "message_id": message.id.to_string(),
+            "reaction_id": reaction_id,
+            "user_id": user_id,
+        });
+
+        if let Err(e) = ConversationService::broadcast(&message.conversation_id.to_string(), "message.reaction_removed", data).await {
+            tracing::warn!("Failed to broadcast reaction removal for message {}: {}", message.id, e);
+        }
+
+        Ok(true)
+    }
+}
@@ -29,4 +29,21 @@ pub mod mfa_email_service;
 pub mod mfa_webauthn_service;
 pub mod mfa_biometric_service;
 pub mod mfa_sms_service;
-pub mod mfa_manager_service;
\ No newline at end of file
+pub mod mfa_manager_service;
+pub mod conversation_service;
+pub mod message_service;
+pub mod typing_indicator_service;
+pub mod scheduled_message_service;
+pub mod poll_service;
+pub mod pinned_message_service;
+pub mod mention_service;
+pub mod device_presence_service;
+pub mod prekey_service;
+pub mod security_incident_service;
+pub mod login_fingerprint_service;
+pub mod session_backup_service;
+pub mod algorithm_negotiation_service;
+pub mod broadcast_auth_service;
+pub mod csv_import_service;
+pub mod impersonation_service;
+pub mod feature_flag_service;
\ No newline at end of file
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use serde_json::json;
-use crate::app::notifications::notification::{Notification, Notifiable};
+use crate::app::notifications::notification::{filter_preferred_channels, Notification, Notifiable, NotificationChannel, NotificationFake};
 use crate::app::notifications::channels::{ChannelManager};
 use crate::app::notifications::channels::database_channel::DatabaseChannel;
 use crate::app::models::notification::Notification as NotificationModel;
@@ -28,19 +28,12 @@ impl NotificationService {
     ) -> Result<()> {
         // Get the channels this notification should be sent on
         let channels = notification.via(notifiable);
+        let filtered_channels = self.preferred_channels(notification, notifiable, channels).await;
 
-        // Filter channels based on notifiable preferences (if implemented)
-        let filtered_channels = if let Some(user) = self.try_as_user(notifiable) {
-            let mut filtered = Vec::new();
-            for channel in channels {
-                if user.prefers_channel(&channel).await {
-                    filtered.push(channel);
-                }
-            }
-            filtered
-        } else {
-            channels
-        };
+        if let Some(fake) = NotificationFake::current() {
+            fake.record(notification, notifiable, &filtered_channels).await;
+            return Ok(());
+        }
 
         // Send the notification via the appropriate channels
         self.channel_manager
@@ -95,6 +88,11 @@ impl NotificationService {
         notifiable: &dyn Notifiable,
     ) -> Result<()> {
         let channels = notification.via(notifiable);
+        if let Some(fake) = NotificationFake::current() {
+            fake.record(notification, notifiable, &channels).await;
+            return Ok(());
+        }
+
         self.channel_manager
             .send(notification, notifiable, channels)
             .await
@@ -105,20 +103,14 @@ impl NotificationService {
         &self,
         notification: &dyn Notification,
         notifiable: &dyn Notifiable,
-        channels: Vec<crate::app::notifications::notification::NotificationChannel>,
+        channels: Vec<NotificationChannel>,
     ) -> Result<()> {
-        // Filter channels based on notifiable preferences (if implemented)
-        let filtered_channels = if let Some(user) = self.try_as_user(notifiable) {
-            let mut filtered = Vec::new();
-            for channel in channels {
-                if user.prefers_channel(&channel).await {
-                    filtered.push(channel);
-                }
-            }
-            filtered
-        } else {
-            channels
-        };
+        let filtered_channels = self.preferred_channels(notification, notifiable, channels).await;
+
+        if let Some(fake) = NotificationFake::current() {
+            fake.record(notification, notifiable, &filtered_channels).await;
+            return Ok(());
+        }
 
         // Send the notification via the specified channels
         self.channel_manager
@@ -199,6 +191,28 @@ impl NotificationService {
 
         None
     }
+
+    /// Narrow `channels` to the notifiable's preferred channels, then to a user's stored preferences
+    async fn preferred_channels(
+        &self,
+        notification: &dyn Notification,
+        notifiable: &dyn Notifiable,
+        channels: Vec<NotificationChannel>,
+    ) -> Vec<NotificationChannel> {
+        let channels = filter_preferred_channels(notification, notifiable, channels);
+
+        let Some(user) = self.try_as_user(notifiable) else {
+            return channels;
+        };
+
+        let mut filtered = Vec::new();
+        for channel in channels {
+            if user.prefers_channel(&channel).await {
+                filtered.push(channel);
+            }
+        }
+        filtered
+    }
 }
 
 
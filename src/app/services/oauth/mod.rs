@@ -10,6 +10,7 @@ pub mod ciba_service;
 pub mod client_auth_service;
 pub mod scope_validation_service;
 pub mod identity_resolution_service;
+pub mod token_throttle_service;
 
 pub use client_service::*;
 pub use token_service::*;
@@ -22,4 +23,5 @@ pub use par_service::*;
 pub use ciba_service::*;
 pub use client_auth_service::*;
 pub use scope_validation_service::*;
-pub use identity_resolution_service::*;
\ No newline at end of file
+pub use identity_resolution_service::*;
+pub use token_throttle_service::*;
\ No newline at end of file
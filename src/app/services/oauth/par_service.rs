@@ -14,6 +14,22 @@ use rand::{distributions::Alphanumeric, Rng};
 /// receiving a request URI that represents the authorization request data.
 pub struct PARService;
 
+/// Reasons a pushed authorization request cannot be used
+#[derive(Debug, thiserror::Error)]
+pub enum PARError {
+    #[error("Invalid request_uri")]
+    NotFound,
+
+    #[error("request_uri has already been used")]
+    AlreadyUsed,
+
+    #[error("request_uri has expired")]
+    Expired,
+
+    #[error("Client requires pushed authorization requests; send a request_uri from the PAR endpoint")]
+    Required,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PushedAuthRequest {
     // Standard OAuth parameters
@@ -107,6 +123,21 @@ impl PARService {
         })
     }
 
+    /// Look up an unused, unexpired pushed authorization request without consuming it
+    /// RFC 9126: The authorization endpoint hydrates the request from request_uri
+    pub async fn find_pushed_request(
+        pool: &DbPool,
+        request_uri: &str,
+        client_id: &str,
+    ) -> Result<PushedAuthRequest> {
+        let mut conn = pool.get()?;
+
+        let stored_request = Self::find_stored_request(&mut conn, request_uri, client_id)?;
+        Self::ensure_usable(&stored_request)?;
+
+        Ok(serde_json::from_str(&stored_request.request_data)?)
+    }
+
     /// Retrieve and consume a pushed authorization request
     /// RFC 9126: Exchange request_uri for original authorization parameters
     pub async fn consume_pushed_request(
@@ -115,37 +146,59 @@ impl PARService {
         client_id: &str,
     ) -> Result<PushedAuthRequest> {
         let mut conn = pool.get()?;
+        let now = Utc::now();
 
-        // Find the stored request
-        let stored_request: StoredPushedRequest = oauth_pushed_requests::table
+        // Mark as used (single-use) in the same statement that checks it, so concurrent
+        // authorizations cannot both consume the request
+        let request_data = diesel::update(oauth_pushed_requests::table)
             .filter(oauth_pushed_requests::request_uri.eq(request_uri))
             .filter(oauth_pushed_requests::client_id.eq(client_id))
             .filter(oauth_pushed_requests::used.eq(false))
-            .first(&mut conn)
-            .map_err(|_| anyhow::anyhow!("Invalid or expired request_uri"))?;
-
-        // Check if expired
-        if Utc::now() > stored_request.expires_at {
-            return Err(anyhow::anyhow!("Request URI has expired"));
-        }
-
-        // Mark as used (single-use)
-        diesel::update(oauth_pushed_requests::table)
-            .filter(oauth_pushed_requests::id.eq(stored_request.id))
+            .filter(oauth_pushed_requests::expires_at.gt(now))
             .set((
                 oauth_pushed_requests::used.eq(true),
-                oauth_pushed_requests::updated_at.eq(Utc::now()),
+                oauth_pushed_requests::updated_at.eq(now),
             ))
-            .execute(&mut conn)?;
+            .returning(oauth_pushed_requests::request_data)
+            .get_result::<String>(&mut conn)
+            .optional()?;
+
+        let Some(request_data) = request_data else {
+            // Report why the request could not be consumed
+            let stored_request = Self::find_stored_request(&mut conn, request_uri, client_id)?;
+            Self::ensure_usable(&stored_request)?;
+            return Err(PARError::AlreadyUsed.into());
+        };
 
         // Deserialize request data
-        let request: PushedAuthRequest = serde_json::from_str(&stored_request.request_data)?;
+        let request: PushedAuthRequest = serde_json::from_str(&request_data)?;
 
         tracing::info!("Consumed pushed authorization request {} for client {}", request_uri, client_id);
 
         Ok(request)
     }
 
+    fn find_stored_request(conn: &mut PgConnection, request_uri: &str, client_id: &str) -> Result<StoredPushedRequest> {
+        let stored_request = oauth_pushed_requests::table
+            .filter(oauth_pushed_requests::request_uri.eq(request_uri))
+            .filter(oauth_pushed_requests::client_id.eq(client_id))
+            .first::<StoredPushedRequest>(conn)
+            .optional()?
+            .ok_or(PARError::NotFound)?;
+
+        Ok(stored_request)
+    }
+
+    fn ensure_usable(stored_request: &StoredPushedRequest) -> Result<()> {
+        if stored_request.used {
+            return Err(PARError::AlreadyUsed.into());
+        }
+        if Utc::now() >= stored_request.expires_at {
+            return Err(PARError::Expired.into());
+        }
+        Ok(())
+    }
+
     /// Validate client is authorized to use PAR
     async fn validate_client(pool: &DbPool, client_id: &str) -> Result<()> {
         use crate::app::services::oauth::ClientService;
@@ -393,35 +446,28 @@ impl PARService {
         Ok(deleted as u64)
     }
 
-    /// Validate that authorization request uses PAR when required
-    /// Check if PAR is required for client (uses metadata or naming convention)
+    /// Check if PAR is required for client
+    /// RFC 9126: Clients registered with require_pushed_authorization_requests may only
+    /// send authorization parameters through the PAR endpoint
     pub async fn require_par_for_client(pool: &DbPool, client_id: &str) -> bool {
-        // First, check the client's metadata for explicit PAR requirement
-        if let Ok(Some(client)) = ClientService::find_by_id(pool, client_id.to_string()) {
-            // Check if explicitly configured in client metadata
-            if let Some(require_par) = client.metadata.as_ref().and_then(|m| m.get("require_par")).and_then(|v| v.as_bool()) {
-                return require_par;
-            }
+        let Ok(Some(client)) = ClientService::find_by_id(pool, client_id.to_string()) else {
+            return false;
+        };
 
-            // Check if client has require_pushed_authorization_requests flag set
-            if client.require_pushed_authorization_requests {
-                return true;
-            }
+        // Explicit metadata setting overrides the registered flag
+        if let Some(require_par) = client.metadata.as_ref().and_then(|m| m.get("require_par")).and_then(|v| v.as_bool()) {
+            return require_par;
         }
 
-        // Fallback to naming convention: high-security clients require PAR
-        let high_security_patterns = [
-            "fapi",     // Financial API clients
-            "bank",     // Banking applications
-            "payment",  // Payment processors
-            "finance",  // Financial services
-            "trading",  // Trading platforms
-            "secure",   // Explicitly secure clients
-            "prod",     // Production environments
-        ];
-
-        let client_lower = client_id.to_lowercase();
-        high_security_patterns.iter().any(|&pattern| client_lower.contains(pattern))
+        client.require_pushed_authorization_requests
+    }
+
+    /// Reject authorization requests carrying plain parameters for clients that require PAR
+    pub async fn ensure_par_not_required(pool: &DbPool, client_id: &str) -> Result<()> {
+        if Self::require_par_for_client(pool, client_id).await {
+            return Err(PARError::Required.into());
+        }
+        Ok(())
     }
 
     /// Create authorization URL with request_uri
@@ -1,19 +1,70 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use chrono::{DateTime, Utc, Duration};
 use crate::database::DbPool;
-use crate::app::models::oauth::{CreateAccessToken, AccessToken};
+use crate::app::models::oauth::{CreateAccessToken, AccessToken, Client};
 use crate::app::services::oauth::{TokenService, ClientService};
 use crate::app::services::oauth::token_service::RFC9068Claims;
 use ulid::Ulid;
 
+/// Grant type identifying a token exchange request
+pub const TOKEN_EXCHANGE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+
+/// Token type URIs accepted as `subject_token_type`
+pub const SUPPORTED_SUBJECT_TOKEN_TYPES: [&str; 4] = [
+    "urn:ietf:params:oauth:token-type:access_token",
+    "urn:ietf:params:oauth:token-type:refresh_token",
+    "urn:ietf:params:oauth:token-type:id_token",
+    "urn:ietf:params:oauth:token-type:jwt",
+];
+
+/// Token type URIs accepted as `actor_token_type`
+pub const SUPPORTED_ACTOR_TOKEN_TYPES: [&str; 2] = [
+    "urn:ietf:params:oauth:token-type:access_token",
+    "urn:ietf:params:oauth:token-type:jwt",
+];
+
+/// Token type URIs that can be requested with `requested_token_type`
+pub const SUPPORTED_REQUESTED_TOKEN_TYPES: [&str; 1] = [
+    "urn:ietf:params:oauth:token-type:access_token",
+];
+
 /// RFC 8693: OAuth 2.0 Token Exchange
 ///
 /// This service implements secure token exchange for delegation and impersonation scenarios.
 /// It allows clients to exchange one token for another, enabling complex authorization flows
 /// such as service-to-service communication with user context preservation.
+///
+/// Clients must opt in through their `token_exchange_scenarios` metadata, and may only
+/// target the audiences and resources listed in `token_exchange_audiences`.
 pub struct TokenExchangeService;
 
+/// Reasons a token exchange request is refused before any token is issued
+#[derive(Debug, thiserror::Error)]
+pub enum TokenExchangeError {
+    #[error("{0}")]
+    InvalidRequest(String),
+
+    #[error("{0}")]
+    UnauthorizedClient(String),
+
+    #[error("{0}")]
+    InvalidTarget(String),
+}
+
+impl TokenExchangeError {
+    /// OAuth error code for an error returned by a token exchange
+    pub fn error_code(error: &anyhow::Error) -> &'static str {
+        match error.downcast_ref::<TokenExchangeError>() {
+            Some(TokenExchangeError::InvalidRequest(_)) => "invalid_request",
+            Some(TokenExchangeError::UnauthorizedClient(_)) => "unauthorized_client",
+            Some(TokenExchangeError::InvalidTarget(_)) => "invalid_target",
+            None => "invalid_grant",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenExchangeRequest {
     pub grant_type: String, // Must be "urn:ietf:params:oauth:grant-type:token-exchange"
@@ -45,6 +96,10 @@ pub struct TokenContext {
     pub scopes: Vec<String>,
     pub expires_at: Option<DateTime<Utc>>,
     pub token_type: TokenType,
+    /// `act` claim of a previously exchanged token, naming its actors
+    pub act: Option<Value>,
+    /// `may_act` claim limiting who may act for the token's subject
+    pub may_act: Option<Value>,
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +118,16 @@ pub enum ExchangeScenario {
     ServiceToService, // Service acting as itself with user context
 }
 
+impl ExchangeScenario {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ExchangeScenario::Impersonation => "impersonation",
+            ExchangeScenario::Delegation => "delegation",
+            ExchangeScenario::ServiceToService => "service_to_service",
+        }
+    }
+}
+
 impl TokenExchangeService {
     /// Exchange tokens according to RFC 8693
     pub async fn exchange_token(
@@ -71,10 +136,14 @@ impl TokenExchangeService {
         request: TokenExchangeRequest,
     ) -> Result<TokenExchangeResponse> {
         // Validate grant type
-        if request.grant_type != "urn:ietf:params:oauth:grant-type:token-exchange" {
-            return Err(anyhow::anyhow!("Invalid grant type for token exchange"));
+        if request.grant_type != TOKEN_EXCHANGE_GRANT_TYPE {
+            return Err(TokenExchangeError::InvalidRequest("Invalid grant type for token exchange".to_string()).into());
         }
 
+        // Only clients configured for token exchange may use the grant
+        let client = Self::find_exchange_client(pool, client_id)?;
+        Self::validate_request_types(&request)?;
+
         // Validate and parse subject token
         let subject_context = Self::validate_and_parse_token(
             pool,
@@ -84,23 +153,25 @@ impl TokenExchangeService {
         ).await?;
 
         // Validate actor token if present
-        let actor_context = if let Some(actor_token) = &request.actor_token {
-            Some(Self::validate_and_parse_token(
+        let actor_context = match (&request.actor_token, &request.actor_token_type) {
+            (Some(actor_token), Some(actor_token_type)) => Some(Self::validate_and_parse_token(
                 pool,
                 actor_token,
-                request.actor_token_type.as_ref()
-                    .ok_or_else(|| anyhow::anyhow!("Actor token type is required when actor token is provided"))?,
+                actor_token_type,
                 client_id,
-            ).await?)
-        } else {
-            None
+            ).await?),
+            _ => None,
         };
 
         // Determine exchange scenario
         let scenario = Self::determine_exchange_scenario(&subject_context, &actor_context)?;
 
         // Validate client permissions for token exchange
-        Self::validate_client_exchange_permissions(pool, client_id, &scenario, &request).await?;
+        Self::validate_client_exchange_permissions(&client, &scenario, &request)?;
+
+        if let (ExchangeScenario::Delegation, Some(actor)) = (&scenario, &actor_context) {
+            Self::ensure_actor_may_act(&subject_context, actor)?;
+        }
 
         // Validate requested scopes
         let granted_scopes = Self::validate_and_limit_scopes(
@@ -131,6 +202,44 @@ impl TokenExchangeService {
         })
     }
 
+    /// Check the token types of a request against the supported token type URIs
+    fn validate_request_types(request: &TokenExchangeRequest) -> Result<()> {
+        if !SUPPORTED_SUBJECT_TOKEN_TYPES.contains(&request.subject_token_type.as_str()) {
+            return Err(TokenExchangeError::InvalidRequest(
+                format!("Unsupported subject_token_type: {}", request.subject_token_type)
+            ).into());
+        }
+
+        match (&request.actor_token, &request.actor_token_type) {
+            (Some(_), None) => {
+                return Err(TokenExchangeError::InvalidRequest(
+                    "actor_token_type is required when actor_token is provided".to_string()
+                ).into());
+            },
+            (None, Some(_)) => {
+                return Err(TokenExchangeError::InvalidRequest(
+                    "actor_token is required when actor_token_type is provided".to_string()
+                ).into());
+            },
+            (Some(_), Some(actor_token_type)) if !SUPPORTED_ACTOR_TOKEN_TYPES.contains(&actor_token_type.as_str()) => {
+                return Err(TokenExchangeError::InvalidRequest(
+                    format!("Unsupported actor_token_type: {}", actor_token_type)
+                ).into());
+            },
+            _ => {},
+        }
+
+        if let Some(requested_token_type) = &request.requested_token_type {
+            if !SUPPORTED_REQUESTED_TOKEN_TYPES.contains(&requested_token_type.as_str()) {
+                return Err(TokenExchangeError::InvalidRequest(
+                    format!("Unsupported requested_token_type: {}", requested_token_type)
+                ).into());
+            }
+        }
+
+        Ok(())
+    }
+
     /// Validate and parse input tokens
     async fn validate_and_parse_token(
         pool: &DbPool,
@@ -151,16 +260,17 @@ impl TokenExchangeService {
             "urn:ietf:params:oauth:token-type:jwt" => {
                 Self::parse_jwt_token(token).await
             },
-            _ => Err(anyhow::anyhow!("Unsupported token type: {}", token_type)),
+            _ => Err(TokenExchangeError::InvalidRequest(format!("Unsupported token type: {}", token_type)).into()),
         }
     }
 
     /// Parse access token and extract context
     async fn parse_access_token(pool: &DbPool, token: &str) -> Result<TokenContext> {
-        let claims = TokenService::decode_jwt_token(token)?;
-        let token_id = Ulid::from_string(&claims.jti.to_string())?;
+        let claims = Self::decode_token_claims(token)?;
+        let token_id = Self::claim(&claims, "jti")
+            .ok_or_else(|| anyhow::anyhow!("Access token missing jti claim"))?;
 
-        let access_token = TokenService::find_access_token_by_id(pool, token_id.to_string())?
+        let access_token = TokenService::find_access_token_by_id(pool, token_id.clone())?
             .ok_or_else(|| anyhow::anyhow!("Access token not found"))?;
 
         if !access_token.is_valid() {
@@ -168,12 +278,14 @@ impl TokenExchangeService {
         }
 
         Ok(TokenContext {
-            token_id: token_id.to_string(),
+            token_id,
             user_id: access_token.user_id.clone(),
             client_id: access_token.client_id.clone(),
             scopes: access_token.get_scopes(),
             expires_at: access_token.expires_at,
             token_type: TokenType::AccessToken,
+            act: claims.get("act").cloned(),
+            may_act: claims.get("may_act").cloned(),
         })
     }
 
@@ -197,6 +309,8 @@ impl TokenExchangeService {
             scopes: access_token.get_scopes(),
             expires_at: refresh_token.expires_at,
             token_type: TokenType::RefreshToken,
+            act: None,
+            may_act: None,
         })
     }
 
@@ -248,6 +362,8 @@ impl TokenExchangeService {
             expires_at: chrono::DateTime::from_timestamp(claims.exp as i64, 0)
                 .map(|dt| dt.with_timezone(&Utc)),
             token_type: TokenType::IdToken,
+            act: None,
+            may_act: None,
         })
     }
 
@@ -298,19 +414,56 @@ impl TokenExchangeService {
 
     /// Parse generic JWT token and extract context
     async fn parse_jwt_token(token: &str) -> Result<TokenContext> {
-        let claims = TokenService::decode_jwt_token(token)?;
+        let claims = Self::decode_token_claims(token)?;
+
+        let scopes = match claims.get("scope").and_then(Value::as_str) {
+            Some(scope) => scope.split_whitespace().map(String::from).collect(),
+            None => claims.get("scopes")
+                .and_then(Value::as_array)
+                .map(|scopes| scopes.iter().filter_map(Value::as_str).map(String::from).collect())
+                .unwrap_or_default(),
+        };
 
         Ok(TokenContext {
-            token_id: claims.jti.to_string(),
-            user_id: Some(claims.sub.to_string()),
-            client_id: claims.aud.to_string(),
-            scopes: claims.scopes,
-            expires_at: claims.exp.map(|exp| chrono::DateTime::from_timestamp(exp as i64, 0)
-                .map(|dt| dt.with_timezone(&Utc))).flatten(),
+            token_id: Self::claim(&claims, "jti")
+                .ok_or_else(|| anyhow::anyhow!("JWT missing jti claim"))?,
+            user_id: Self::claim(&claims, "sub"),
+            client_id: Self::claim(&claims, "client_id")
+                .ok_or_else(|| anyhow::anyhow!("JWT missing client_id claim"))?,
+            scopes,
+            expires_at: claims.get("exp")
+                .and_then(Value::as_i64)
+                .and_then(|exp| DateTime::from_timestamp(exp, 0)),
             token_type: TokenType::Jwt,
+            act: claims.get("act").cloned(),
+            may_act: claims.get("may_act").cloned(),
         })
     }
 
+    /// Verify a JWT issued by this server and return all of its claims
+    fn decode_token_claims(token: &str) -> Result<Map<String, Value>> {
+        use jsonwebtoken::{decode, decode_header, DecodingKey, Validation};
+
+        let header = decode_header(token)
+            .map_err(|e| anyhow::anyhow!("Invalid JWT header: {}", e))?;
+
+        let jwt_secret = std::env::var("OAUTH_JWT_SECRET")
+            .unwrap_or_else(|_| "oauth-jwt-secret".to_string());
+
+        // Exchanged tokens name their target audience, so audience is checked per use
+        let mut validation = Validation::new(header.alg);
+        validation.validate_aud = false;
+
+        let token_data = decode::<Map<String, Value>>(token, &DecodingKey::from_secret(jwt_secret.as_ref()), &validation)
+            .map_err(|e| anyhow::anyhow!("JWT validation failed: {}", e))?;
+
+        Ok(token_data.claims)
+    }
+
+    fn claim(claims: &Map<String, Value>, name: &str) -> Option<String> {
+        claims.get(name).and_then(Value::as_str).map(String::from)
+    }
+
     /// Determine the exchange scenario based on tokens
     fn determine_exchange_scenario(
         subject_context: &TokenContext,
@@ -328,87 +481,122 @@ impl TokenExchangeService {
         }
     }
 
-    /// Validate client permissions for token exchange
-    async fn validate_client_exchange_permissions(
-        pool: &DbPool,
-        client_id: &str,
-        scenario: &ExchangeScenario,
-        request: &TokenExchangeRequest,
-    ) -> Result<()> {
+    /// Find the requesting client, refusing clients not configured for token exchange
+    fn find_exchange_client(pool: &DbPool, client_id: &str) -> Result<Client> {
         let client = ClientService::find_by_id(pool, client_id.to_string())?
-            .ok_or_else(|| anyhow::anyhow!("Client not found"))?;
+            .filter(|client| !client.revoked)
+            .ok_or_else(|| TokenExchangeError::UnauthorizedClient("Client not found".to_string()))?;
+
+        if Self::allowed_scenarios(&client).is_empty() {
+            return Err(TokenExchangeError::UnauthorizedClient(
+                "Client is not allowed to perform token exchange".to_string()
+            ).into());
+        }
 
-        // Check client configuration for allowed exchange scenarios
-        let allowed_scenarios = client.metadata.as_ref()
+        Ok(client)
+    }
+
+    /// Exchange scenarios listed in the client's `token_exchange_scenarios` metadata
+    fn allowed_scenarios(client: &Client) -> Vec<&str> {
+        client.metadata.as_ref()
             .and_then(|m| m.get("token_exchange_scenarios"))
             .and_then(|v| v.as_array())
-            .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>())
-            .unwrap_or_else(|| vec!["delegation"]); // Default to delegation only
+            .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default()
+    }
 
-        let scenario_name = match scenario {
-            ExchangeScenario::Impersonation => "impersonation",
-            ExchangeScenario::Delegation => "delegation",
-            ExchangeScenario::ServiceToService => "service_to_service",
-        };
+    /// Validate client permissions for token exchange
+    fn validate_client_exchange_permissions(
+        client: &Client,
+        scenario: &ExchangeScenario,
+        request: &TokenExchangeRequest,
+    ) -> Result<()> {
+        if !Self::allowed_scenarios(client).contains(&scenario.as_str()) {
+            return Err(TokenExchangeError::UnauthorizedClient(
+                format!("Client not configured for {} token exchange", scenario.as_str())
+            ).into());
+        }
 
-        if !allowed_scenarios.contains(&scenario_name) {
-            return Err(anyhow::anyhow!("Client not configured for {} token exchange", scenario_name));
+        if let ExchangeScenario::ServiceToService = scenario {
+            // Service-to-service exchanges are only allowed for confidential clients
+            if client.secret.is_none() {
+                return Err(TokenExchangeError::UnauthorizedClient(
+                    "Public clients not allowed for service-to-service exchange".to_string()
+                ).into());
+            }
         }
-        
-        match scenario {
-            ExchangeScenario::Impersonation => {
-                // Only trusted clients should be allowed to impersonate users
-                if client.name.contains("untrusted") {
-                    return Err(anyhow::anyhow!("Client not authorized for impersonation"));
-                }
-            },
-            ExchangeScenario::Delegation => {
-                // Validate delegation permissions
-                if let Some(audience) = &request.audience {
-                    // Ensure client is authorized to delegate to the target audience
-                    if !Self::is_authorized_for_audience(&client, audience) {
-                        return Err(anyhow::anyhow!("Client not authorized for target audience"));
-                    }
-                }
-            },
-            ExchangeScenario::ServiceToService => {
-                // Service-to-service exchanges are generally allowed for confidential clients
-                if client.secret.is_none() {
-                    return Err(anyhow::anyhow!("Public clients not allowed for service-to-service exchange"));
-                }
-            },
+
+        // Every target of the new token must be permitted for the client
+        for target in request.audience.iter().chain(request.resource.iter()) {
+            if !Self::is_authorized_for_audience(client, target) {
+                return Err(TokenExchangeError::InvalidTarget(
+                    format!("Client not authorized for target audience {}", target)
+                ).into());
+            }
         }
 
         Ok(())
     }
 
     /// Check if client is authorized for specific audience
-    fn is_authorized_for_audience(
-        client: &crate::app::models::oauth::Client,
-        audience: &str,
-    ) -> bool {
-        // For production: implement proper audience validation
-        // This would check a dedicated table like oauth_client_audiences
-        // or use a metadata field if added to the oauth_clients table
-
-        // Placeholder implementation - allow common OAuth audiences
-        let allowed_audiences = vec![
-            "https://api.example.com",
-            "https://graph.microsoft.com",
-            "https://www.googleapis.com/auth/userinfo.email",
-        ];
-
-        // Check if the target audience is in the allowed list
-        allowed_audiences.contains(&audience)
-            || allowed_audiences.iter().any(|allowed| {
-                // Support wildcard matching for domain-based audiences
-                if allowed.starts_with("*.") {
-                    let domain = &allowed[2..];
-                    audience.ends_with(domain)
-                } else {
-                    false
+    ///
+    /// Entries in the client's `token_exchange_audiences` metadata match exactly, or
+    /// as `*.example.com` to match any subdomain of example.com.
+    fn is_authorized_for_audience(client: &Client, audience: &str) -> bool {
+        let host = url::Url::parse(audience).ok()
+            .and_then(|url| url.host_str().map(String::from))
+            .unwrap_or_else(|| audience.to_string());
+
+        client.metadata.as_ref()
+            .and_then(|m| m.get("token_exchange_audiences"))
+            .and_then(|v| v.as_array())
+            .map(|allowed| allowed.iter().filter_map(|v| v.as_str()).any(|allowed| {
+                match allowed.strip_prefix("*.") {
+                    Some(domain) => host.ends_with(&format!(".{}", domain)),
+                    None => allowed == audience,
                 }
-            })
+            }))
+            .unwrap_or(false)
+    }
+
+    /// Honour a `may_act` claim on the subject token, which names who may act for its subject
+    fn ensure_actor_may_act(subject_context: &TokenContext, actor_context: &TokenContext) -> Result<()> {
+        let Some(may_act) = &subject_context.may_act else {
+            return Ok(());
+        };
+
+        let actor_subject = Self::actor_subject(actor_context);
+        let sub_matches = may_act.get("sub").and_then(Value::as_str)
+            .map_or(true, |sub| sub == actor_subject);
+        let client_matches = may_act.get("client_id").and_then(Value::as_str)
+            .map_or(true, |client_id| client_id == actor_context.client_id);
+
+        if !sub_matches || !client_matches {
+            return Err(anyhow::anyhow!("Actor is not permitted to act for the subject"));
+        }
+
+        Ok(())
+    }
+
+    /// The user behind an actor token, or its client when it has no user
+    fn actor_subject(actor_context: &TokenContext) -> &str {
+        actor_context.user_id.as_deref().unwrap_or(&actor_context.client_id)
+    }
+
+    /// Build the `act` claim for a delegated token
+    ///
+    /// The current actor is outermost; actors of an already exchanged subject token
+    /// are nested beneath it so the full delegation chain is preserved.
+    fn actor_claim(subject_context: &TokenContext, actor_context: &TokenContext) -> Value {
+        let mut act = Map::new();
+        act.insert("sub".to_string(), Value::String(Self::actor_subject(actor_context).to_string()));
+        act.insert("client_id".to_string(), Value::String(actor_context.client_id.clone()));
+
+        if let Some(prior) = &subject_context.act {
+            act.insert("act".to_string(), prior.clone());
+        }
+
+        Value::Object(act)
     }
 
     /// Validate and limit requested scopes
@@ -454,7 +642,7 @@ impl TokenExchangeService {
         pool: &DbPool,
         client_id: &str,
         subject_context: &TokenContext,
-        _actor_context: &Option<TokenContext>,
+        actor_context: &Option<TokenContext>,
         scopes: &[String],
         request: &TokenExchangeRequest,
         scenario: ExchangeScenario,
@@ -471,8 +659,13 @@ impl TokenExchangeService {
 
         let access_token = TokenService::create_access_token(pool, create_token, Some(3600), None).await?;
 
+        let act = match (&scenario, actor_context) {
+            (ExchangeScenario::Delegation, Some(actor)) => Some(Self::actor_claim(subject_context, actor)),
+            _ => None,
+        };
+
         // Create enhanced JWT with exchange context
-        let jwt_token = Self::generate_exchange_jwt(pool, &access_token, client_id, request, scenario)?;
+        let jwt_token = Self::generate_exchange_jwt(pool, &access_token, client_id, request, scenario, act)?;
 
         Ok(ExchangedToken {
             access_token,
@@ -487,54 +680,46 @@ impl TokenExchangeService {
         client_id: &str,
         request: &TokenExchangeRequest,
         scenario: ExchangeScenario,
+        act: Option<Value>,
     ) -> Result<String> {
-        use crate::app::services::oauth::TokenService;
-        use jsonwebtoken::{encode, Header, EncodingKey};
-        use serde_json::Value;
+        use jsonwebtoken::{encode, decode_header, EncodingKey};
 
         // Get base JWT token
         let jwt_token = TokenService::generate_jwt_token(pool, access_token, client_id)?;
 
         // Decode the existing token to get claims
-        let token_data = TokenService::decode_jwt_token(&jwt_token)?;
-        let mut claims = serde_json::to_value(&token_data)?;
+        let header = decode_header(&jwt_token)?;
+        let mut claims = Self::decode_token_claims(&jwt_token)?;
 
         // Add token exchange specific claims per RFC 8693
-        if let Some(claims_obj) = claims.as_object_mut() {
-            // Add exchange scenario context
-            claims_obj.insert("exchange_scenario".to_string(), Value::String(format!("{:?}", scenario)));
+        claims.insert("exchange_scenario".to_string(), Value::String(format!("{:?}", scenario)));
 
-            // Add audience if specified
-            if let Some(ref audience) = request.audience {
-                claims_obj.insert("aud".to_string(), Value::String(audience.clone()));
-            }
+        // Add audience if specified
+        if let Some(ref audience) = request.audience {
+            claims.insert("aud".to_string(), Value::String(audience.clone()));
+        }
 
-            // Add resource if specified
-            if let Some(ref resource) = request.resource {
-                claims_obj.insert("resource".to_string(), Value::String(resource.clone()));
-            }
+        // Add resource if specified
+        if let Some(ref resource) = request.resource {
+            claims.insert("resource".to_string(), Value::String(resource.clone()));
+        }
 
-            // Add requested token type
-            claims_obj.insert("requested_token_type".to_string(),
-                Value::String(request.requested_token_type.clone().unwrap_or_else(|| "urn:ietf:params:oauth:token-type:access_token".to_string())));
+        // Add requested token type
+        claims.insert("requested_token_type".to_string(),
+            Value::String(request.requested_token_type.clone().unwrap_or_else(|| "urn:ietf:params:oauth:token-type:access_token".to_string())));
 
-            // Add subject token type for audit trail
-            claims_obj.insert("subject_token_type".to_string(), Value::String(request.subject_token_type.clone()));
+        // Add subject token type for audit trail
+        claims.insert("subject_token_type".to_string(), Value::String(request.subject_token_type.clone()));
 
-            // Add may_act claim for delegation scenarios
-            if matches!(scenario, ExchangeScenario::Delegation) {
-                if let Some(ref actor_token_type) = request.actor_token_type {
-                    claims_obj.insert("may_act".to_string(), serde_json::json!({
-                        "actor_token_type": actor_token_type
-                    }));
-                }
-            }
+        // Name the acting party for delegation scenarios
+        if let Some(act) = act {
+            claims.insert("act".to_string(), act);
         }
 
-        // Re-encode the token with exchange-specific claims
-        let header = Header::default();
-        let encoding_key = EncodingKey::from_secret(std::env::var("JWT_SECRET").unwrap_or_else(|_| "default_secret".to_string()).as_ref());
-        let new_token = encode(&header, &claims, &encoding_key)?;
+        // Re-encode with the same key so the token can itself be exchanged again
+        let jwt_secret = std::env::var("OAUTH_JWT_SECRET")
+            .unwrap_or_else(|_| "oauth-jwt-secret".to_string());
+        let new_token = encode(&header, &claims, &EncodingKey::from_secret(jwt_secret.as_ref()))?;
 
         Ok(new_token)
     }
@@ -657,6 +842,8 @@ mod tests {
             scopes: vec!["read".to_string()],
             expires_at: None,
             token_type: TokenType::AccessToken,
+            act: None,
+            may_act: None,
         };
 
         let actor = Some(TokenContext {
@@ -666,6 +853,8 @@ mod tests {
             scopes: vec!["admin".to_string()],
             expires_at: None,
             token_type: TokenType::AccessToken,
+            act: None,
+            may_act: None,
         });
 
         let scenario = TokenExchangeService::determine_exchange_scenario(&subject, &actor);
@@ -682,6 +871,8 @@ mod tests {
             scopes: vec!["read".to_string(), "write".to_string()],
             expires_at: None,
             token_type: TokenType::AccessToken,
+            act: None,
+            may_act: None,
         };
 
         let scopes = TokenExchangeService::validate_and_limit_scopes(
@@ -11,6 +11,8 @@ use crate::app::models::DieselUlid;
 
 use crate::app::models::oauth::{AccessToken, CreateAccessToken, RefreshToken, AuthCode, CreateAuthCode};
 use crate::app::services::oauth::client_service::ClientService;
+use crate::app::services::security_incident_service::{IncidentSubject, SecurityIncidentService};
+use crate::app::models::security_incidents::{IncidentSeverity, IncidentType};
 use crate::app::traits::ServiceActivityLogger;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -464,6 +466,12 @@ impl TokenService {
         let refresh_token = Self::find_refresh_token_by_id(pool, refresh_id.to_string())?
             .ok_or_else(|| anyhow::anyhow!("Invalid refresh token"))?;
 
+        // A revoked refresh token has already been rotated, so presenting it again means it leaked
+        if refresh_token.revoked {
+            Self::handle_refresh_token_reuse(pool, &refresh_token, &client_id).await?;
+            return Err(anyhow::anyhow!("Refresh token is expired or revoked"));
+        }
+
         if !refresh_token.is_valid() {
             return Err(anyhow::anyhow!("Refresh token is expired or revoked"));
         }
@@ -536,6 +544,37 @@ impl TokenService {
         })
     }
 
+    /// Revoke every token of the user a reused refresh token belongs to and record the incident
+    async fn handle_refresh_token_reuse(pool: &DbPool, refresh_token: &RefreshToken, client_id: &str) -> Result<()> {
+        let access_token = Self::find_access_token_by_id(pool, refresh_token.access_token_id.clone())?;
+        let user_id = access_token.as_ref().and_then(|token| token.user_id.clone());
+
+        if let Some(user_id) = &user_id {
+            Self::revoke_all_user_tokens(pool, user_id.clone())?;
+        }
+
+        let subject = match &user_id {
+            Some(user_id) => IncidentSubject::user(DieselUlid::from_string(user_id)?),
+            None => IncidentSubject::default(),
+        };
+
+        SecurityIncidentService::report(
+            pool,
+            IncidentType::TokenReuse,
+            IncidentSeverity::High,
+            subject,
+            json!({
+                "token_type": "oauth_refresh_token",
+                "refresh_token_id": refresh_token.id.to_string(),
+                "access_token_id": refresh_token.access_token_id,
+                "client_id": client_id,
+                "tokens_revoked": user_id.is_some(),
+            }),
+        ).await;
+
+        Ok(())
+    }
+
     /// RFC 9068: JWT Profile for OAuth 2.0 Access Tokens
     /// Generate JWT access token compliant with RFC 9068
     pub fn generate_jwt_token(pool: &DbPool, access_token: &AccessToken, client_id: &str) -> Result<String> {
@@ -0,0 +1,182 @@
+use anyhow::Result;
+use chrono::Utc;
+use serde_json::json;
+use std::time::Duration;
+
+use crate::app::models::security_incidents::{IncidentSeverity, IncidentType};
+use crate::app::services::security_incident_service::{IncidentSubject, SecurityIncidentService};
+use crate::app::utils::CacheRateLimiter;
+use crate::cache::{manager::CacheDriver, Cache};
+use crate::config::oauth::OAuthConfig;
+use crate::database::DbPool;
+
+/// Window the per-client and per-IP token request limits apply to
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Why a token request was refused before its grant ran
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenThrottleReason {
+    ClientRateLimit,
+    IpRateLimit,
+    ClientAuthLockout,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Too many token requests. Try again in {retry_after} seconds")]
+pub struct TokenThrottled {
+    pub reason: TokenThrottleReason,
+    pub retry_after: u64,
+}
+
+/// Brute-force protection for `/oauth/token`
+///
+/// Limits requests per client and per client IP, and locks a client out for
+/// an IP after repeated failed client authentication. The lockout doubles
+/// with each further failure up to `client_auth_lockout_max_secs`. Counters
+/// live in the cache store, so instances sharing Redis share the limits.
+pub struct TokenThrottleService {
+    cache: CacheDriver,
+    limiter: CacheRateLimiter,
+    config: OAuthConfig,
+}
+
+impl TokenThrottleService {
+    pub fn new(cache: CacheDriver, config: OAuthConfig) -> Self {
+        Self {
+            limiter: CacheRateLimiter::new(cache.clone()),
+            cache,
+            config,
+        }
+    }
+
+    /// Throttle on the process-wide cache store with the configured limits
+    pub async fn shared() -> Result<Self> {
+        Ok(Self::new(crate::cache::shared_cache().await?, OAuthConfig::from_env()?))
+    }
+
+    /// Count a token request, refusing it while the client or IP is over its limit or locked out
+    ///
+    /// Fails open: when the cache store is unreachable the request is allowed.
+    pub async fn attempt(&self, client_id: &str, ip: &str) -> std::result::Result<(), TokenThrottled> {
+        match self.check(client_id, ip).await {
+            Ok(Some(throttled)) => Err(throttled),
+            Ok(None) => Ok(()),
+            Err(e) => {
+                tracing::warn!("Token throttling skipped, cache unavailable: {}", e);
+                Ok(())
+            }
+        }
+    }
+
+    /// Count a failed client authentication, returning the lockout it started, if any
+    ///
+    /// Every lockout is recorded as a security incident.
+    pub async fn record_failure(&self, pool: &DbPool, client_id: &str, ip: &str) -> Option<Duration> {
+        let (failures, lockout) = match self.count_failure(client_id, ip).await {
+            Ok(counted) => counted,
+            Err(e) => {
+                tracing::warn!("Failed client authentication not counted: {}", e);
+                return None;
+            }
+        };
+
+        let lockout = lockout?;
+        let severity = if lockout.as_secs() >= self.config.client_auth_lockout_max_secs {
+            IncidentSeverity::High
+        } else {
+            IncidentSeverity::Medium
+        };
+
+        SecurityIncidentService::report(
+            pool,
+            IncidentType::MultipleFailedAuth,
+            severity,
+            IncidentSubject::default(),
+            json!({
+                "source": "oauth_token",
+                "client_id": client_id,
+                "ip": ip,
+                "failed_attempts": failures,
+                "lockout_seconds": lockout.as_secs(),
+            }),
+        ).await;
+
+        Some(lockout)
+    }
+
+    /// Forget the failures of a client at an IP after it authenticates
+    pub async fn record_success(&self, client_id: &str, ip: &str) {
+        if let Err(e) = self.limiter.clear(&Self::failures_key(client_id, ip)).await {
+            tracing::warn!("Failed to reset client authentication failures: {}", e);
+        }
+    }
+
+    /// Lockout after `failures` failed authentications
+    ///
+    /// None below `client_auth_max_failures`, then `client_auth_lockout_secs`
+    /// doubling per further failure, capped at `client_auth_lockout_max_secs`.
+    pub fn lockout_for(&self, failures: i64) -> Option<Duration> {
+        let over = failures - self.config.client_auth_max_failures;
+        if over < 0 {
+            return None;
+        }
+
+        let seconds = self.config.client_auth_lockout_secs
+            .saturating_mul(1u64 << over.min(32))
+            .min(self.config.client_auth_lockout_max_secs);
+        Some(Duration::from_secs(seconds))
+    }
+
+    async fn check(&self, client_id: &str, ip: &str) -> Result<Option<TokenThrottled>> {
+        if let Some(retry_after) = self.lockout_remaining(client_id, ip).await? {
+            return Ok(Some(TokenThrottled { reason: TokenThrottleReason::ClientAuthLockout, retry_after }));
+        }
+
+        let limits = [
+            (TokenThrottleReason::ClientRateLimit, format!("oauth_token:client:{}", client_id), self.config.token_rate_limit_per_client),
+            (TokenThrottleReason::IpRateLimit, format!("oauth_token:ip:{}", ip), self.config.token_rate_limit_per_ip),
+        ];
+
+        for (reason, key, max_attempts) in &limits {
+            if self.limiter.too_many_attempts(key, *max_attempts).await? {
+                let retry_after = self.limiter.available_in(key).await?.max(1);
+                return Ok(Some(TokenThrottled { reason: *reason, retry_after }));
+            }
+        }
+
+        for (_, key, _) in &limits {
+            self.limiter.hit(key, RATE_LIMIT_WINDOW).await?;
+        }
+
+        Ok(None)
+    }
+
+    async fn count_failure(&self, client_id: &str, ip: &str) -> Result<(i64, Option<Duration>)> {
+        let memory = Duration::from_secs(self.config.client_auth_lockout_max_secs);
+        let failures = self.limiter.hit(&Self::failures_key(client_id, ip), memory).await?;
+
+        let lockout = self.lockout_for(failures);
+        if let Some(lockout) = lockout {
+            let until = Utc::now().timestamp() + lockout.as_secs() as i64;
+            self.cache.put(&Self::lockout_key(client_id, ip), &until, Some(lockout)).await?;
+        }
+
+        Ok((failures, lockout))
+    }
+
+    async fn lockout_remaining(&self, client_id: &str, ip: &str) -> Result<Option<u64>> {
+        let until = self.cache.get::<i64>(&Self::lockout_key(client_id, ip)).await?;
+        Ok(until
+            .map(|until| until - Utc::now().timestamp())
+            .filter(|remaining| *remaining > 0)
+            .map(|remaining| remaining as u64))
+    }
+
+    fn failures_key(client_id: &str, ip: &str) -> String {
+        format!("oauth_token:failures:{}:{}", client_id, ip)
+    }
+
+    fn lockout_key(client_id: &str, ip: &str) -> String {
+        format!("oauth_token:lockout:{}:{}", client_id, ip)
+    }
+}
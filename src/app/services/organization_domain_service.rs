@@ -8,7 +8,7 @@ use crate::app::models::{
     organization_domain::{OrganizationDomain, CreateOrganizationDomain, UpdateOrganizationDomain}
 };
 use crate::schema::organization_domains;
-use crate::app::traits::ServiceActivityLogger;
+use crate::app::traits::{CascadeSoftDeletes, ServiceActivityLogger};
 
 pub struct OrganizationDomainService;
 
@@ -154,7 +154,7 @@ impl OrganizationDomainService {
         Ok(result)
     }
 
-    /// Soft delete organization domain
+    /// Soft delete organization domain and its children
     pub async fn delete(pool: &DbPool, id: String, deleted_by: &str) -> Result<()> {
         let mut conn = pool.get()?;
 
@@ -166,14 +166,10 @@ impl OrganizationDomainService {
             .first::<OrganizationDomain>(&mut conn)
             .optional()?;
 
-        let deleted_by_ulid = DieselUlid::from_string(deleted_by)?;
+        DieselUlid::from_string(deleted_by)?;
 
-        diesel::update(organization_domains::table.filter(organization_domains::id.eq(&id)))
-            .set((
-                organization_domains::deleted_at.eq(Utc::now()),
-                organization_domains::deleted_by_id.eq(deleted_by_ulid),
-            ))
-            .execute(&mut conn)?;
+        // Soft-delete its types and organizations with it, so none are left orphaned
+        conn.transaction(|conn| OrganizationDomain::cascade_soft_delete(&id, deleted_by, conn))?;
 
         // Log the deletion activity
         if let Some(domain) = domain {
@@ -190,6 +186,21 @@ impl OrganizationDomainService {
         Ok(())
     }
 
+    /// Restore a soft-deleted organization domain
+    ///
+    /// With `cascade`, the children deleted along with it are restored too;
+    /// children deleted separately beforehand stay deleted.
+    pub fn restore(pool: &DbPool, id: String, restored_by: &str, cascade: bool) -> Result<()> {
+        let mut conn = pool.get()?;
+
+        DieselUlid::from_string(restored_by)?;
+
+        conn.transaction(|conn| OrganizationDomain::cascade_restore(&id, restored_by, cascade, conn))?
+            .ok_or_else(|| anyhow::anyhow!("Deleted organization domain not found"))?;
+
+        Ok(())
+    }
+
     /// Count organization domains
     pub fn count(pool: &DbPool) -> Result<i64> {
         let mut conn = pool.get()?;
@@ -8,7 +8,7 @@ use std::collections::HashMap;
 
 use crate::app::models::organization::{Organization, CreateOrganization, UpdateOrganization};
 use crate::app::models::DieselUlid;
-use crate::app::traits::ServiceActivityLogger;
+use crate::app::traits::{ServiceActivityLogger, StaleModelError};
 
 pub struct OrganizationService;
 
@@ -93,19 +93,47 @@ impl OrganizationService {
 
         let updated_by_ulid = DieselUlid::from_string(updated_by)?;
 
-        let result = diesel::update(organizations::table.filter(organizations::id.eq(&id)))
-            .set((
-                data.name.as_ref().map(|n| organizations::name.eq(n)),
-                data.domain_id.as_ref().map(|d| organizations::domain_id.eq(d)),
-                data.type_id.as_ref().map(|t| organizations::type_id.eq(t)),
-                data.parent_id.as_ref().map(|p| organizations::parent_id.eq(p)),
-                data.code.as_ref().map(|c| organizations::code.eq(c)),
-                data.description.as_ref().map(|d| organizations::description.eq(d)),
-                data.is_active.as_ref().map(|a| organizations::is_active.eq(a)),
-                organizations::updated_at.eq(Utc::now()),
-                organizations::updated_by_id.eq(updated_by_ulid),
-            ))
-            .get_result::<Organization>(&mut conn)?;
+        let changes = (
+            data.name.as_ref().map(|n| organizations::name.eq(n)),
+            data.domain_id.as_ref().map(|d| organizations::domain_id.eq(d)),
+            data.type_id.as_ref().map(|t| organizations::type_id.eq(t)),
+            data.parent_id.as_ref().map(|p| organizations::parent_id.eq(p)),
+            data.code.as_ref().map(|c| organizations::code.eq(c)),
+            data.description.as_ref().map(|d| organizations::description.eq(d)),
+            data.is_active.as_ref().map(|a| organizations::is_active.eq(a)),
+            organizations::updated_at.eq(Utc::now()),
+            organizations::updated_by_id.eq(updated_by_ulid),
+            organizations::lock_version.eq(organizations::lock_version + 1),
+        );
+
+        // With a lock version, a row that moved on since the client read it is left alone
+        let target = organizations::table.filter(organizations::id.eq(&id));
+        let result = match data.lock_version {
+            Some(lock_version) => diesel::update(target.filter(organizations::lock_version.eq(lock_version)))
+                .set(changes)
+                .get_result::<Organization>(&mut conn)
+                .optional()?,
+            None => diesel::update(target)
+                .set(changes)
+                .get_result::<Organization>(&mut conn)
+                .optional()?,
+        };
+
+        let result = match result {
+            Some(result) => result,
+            None => {
+                let current_version = organizations::table
+                    .filter(organizations::id.eq(&id))
+                    .select(organizations::lock_version)
+                    .first::<i32>(&mut conn)
+                    .optional()?;
+
+                return match current_version {
+                    Some(current_version) => Err(StaleModelError::new("Organization", current_version).into()),
+                    None => Err(anyhow::anyhow!("Organization not found")),
+                };
+            },
+        };
 
         // Log the update activity
         let service = OrganizationService;
@@ -8,7 +8,7 @@ use crate::app::models::{
     organization_type::{OrganizationType, CreateOrganizationType, UpdateOrganizationType}
 };
 use crate::schema::organization_types;
-use crate::app::traits::ServiceActivityLogger;
+use crate::app::traits::{CascadeSoftDeletes, ServiceActivityLogger};
 
 pub struct OrganizationTypeService;
 
@@ -195,7 +195,7 @@ impl OrganizationTypeService {
         Ok(result)
     }
 
-    /// Soft delete organization type
+    /// Soft delete organization type and its children
     pub async fn delete(pool: &DbPool, id: String, deleted_by: &str) -> Result<()> {
         let mut conn = pool.get()?;
 
@@ -207,14 +207,10 @@ impl OrganizationTypeService {
             .first::<OrganizationType>(&mut conn)
             .optional()?;
 
-        let deleted_by_ulid = DieselUlid::from_string(deleted_by)?;
+        DieselUlid::from_string(deleted_by)?;
 
-        diesel::update(organization_types::table.filter(organization_types::id.eq(&id)))
-            .set((
-                organization_types::deleted_at.eq(Utc::now()),
-                organization_types::deleted_by_id.eq(deleted_by_ulid),
-            ))
-            .execute(&mut conn)?;
+        // Soft-delete its organizations with it, so none are left orphaned
+        conn.transaction(|conn| OrganizationType::cascade_soft_delete(&id, deleted_by, conn))?;
 
         // Log the deletion activity
         if let Some(org_type) = org_type {
@@ -231,6 +227,21 @@ impl OrganizationTypeService {
         Ok(())
     }
 
+    /// Restore a soft-deleted organization type
+    ///
+    /// With `cascade`, the children deleted along with it are restored too;
+    /// children deleted separately beforehand stay deleted.
+    pub fn restore(pool: &DbPool, id: String, restored_by: &str, cascade: bool) -> Result<()> {
+        let mut conn = pool.get()?;
+
+        DieselUlid::from_string(restored_by)?;
+
+        conn.transaction(|conn| OrganizationType::cascade_restore(&id, restored_by, cascade, conn))?
+            .ok_or_else(|| anyhow::anyhow!("Deleted organization type not found"))?;
+
+        Ok(())
+    }
+
     /// Count organization types
     pub fn count(pool: &DbPool) -> Result<i64> {
         let mut conn = pool.get()?;
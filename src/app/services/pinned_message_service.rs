@@ -0,0 +1,194 @@
+use anyhow::Result;
+use chrono::Utc;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utoipa::ToSchema;
+use crate::config::messaging::MessagingConfig;
+use crate::database::DbPool;
+use crate::schema::{conversations, messages, pinned_messages};
+use crate::app::models::DieselUlid;
+use crate::app::models::conversation::ConversationType;
+use crate::app::models::message::Message;
+use crate::app::models::pinned_messages::PinnedMessage;
+use crate::app::services::conversation_service::{ConversationError, ConversationService};
+
+/// Errors raised by pin operations
+#[derive(Debug, thiserror::Error)]
+pub enum PinError {
+    #[error("Message is not pinned")]
+    NotPinned,
+
+    #[error("Conversation already has the maximum of {0} pinned messages")]
+    LimitReached(i64),
+
+    #[error("Only conversation owners, admins, and moderators can pin messages")]
+    InsufficientRole,
+}
+
+impl PinError {
+    /// HTTP status for an error returned by the pin service
+    pub fn status_code(error: &anyhow::Error) -> axum::http::StatusCode {
+        use axum::http::StatusCode;
+
+        match error.downcast_ref::<PinError>() {
+            Some(PinError::NotPinned) => StatusCode::NOT_FOUND,
+            Some(PinError::LimitReached(_)) => StatusCode::CONFLICT,
+            Some(PinError::InsufficientRole) => StatusCode::FORBIDDEN,
+            None => ConversationError::status_code(error),
+        }
+    }
+}
+
+/// Request payload for pinning a message
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PinMessageRequest {
+    pub message_id: DieselUlid,
+    pub device_id: DieselUlid,
+}
+
+pub struct PinnedMessageService {
+    config: MessagingConfig,
+}
+
+impl Default for PinnedMessageService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PinnedMessageService {
+    pub fn new() -> Self {
+        Self::with_config(MessagingConfig::global().clone())
+    }
+
+    pub fn with_config(config: MessagingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Active pins in a conversation, most recent first
+    pub fn list(&self, pool: &DbPool, conversation_id: &str, user_id: &str) -> Result<Vec<PinnedMessage>> {
+        ConversationService::ensure_participant(pool, conversation_id, user_id)?;
+        let mut conn = pool.get()?;
+
+        let pins = pinned_messages::table
+            .filter(pinned_messages::conversation_id.eq(conversation_id))
+            .filter(pinned_messages::is_active.eq(true))
+            .order(pinned_messages::pinned_at.desc())
+            .select(PinnedMessage::as_select())
+            .load::<PinnedMessage>(&mut conn)?;
+
+        Ok(pins)
+    }
+
+    /// Pin a message, returning the existing pin if it is already pinned
+    ///
+    /// The flag is true when a new pin was created.
+    pub async fn pin(&self, pool: &DbPool, conversation_id: &str, user_id: &str, data: PinMessageRequest) -> Result<(PinnedMessage, bool)> {
+        self.ensure_can_pin(pool, conversation_id, user_id)?;
+        let device = ConversationService::ensure_user_device(pool, &data.device_id.to_string(), user_id)?;
+        let max_pins = self.config.max_pinned_messages;
+
+        let mut conn = pool.get()?;
+        let message = messages::table
+            .filter(messages::id.eq(data.message_id.to_string()))
+            .filter(messages::conversation_id.eq(conversation_id))
+            .filter(messages::is_deleted.eq(false))
+            .select(Message::as_select())
+            .first::<Message>(&mut conn)
+            .optional()?
+            .ok_or(ConversationError::MessageNotFound)?;
+
+        let (pin, created) = conn.transaction::<_, anyhow::Error, _>(|conn| {
+            // Lock the conversation so concurrent pins cannot exceed the limit
+            conversations::table
+                .find(conversation_id)
+                .select(conversations::id)
+                .for_update()
+                .first::<String>(conn)?;
+
+            let existing = pinned_messages::table
+                .filter(pinned_messages::conversation_id.eq(conversation_id))
+                .filter(pinned_messages::message_id.eq(message.id.to_string()))
+                .filter(pinned_messages::is_active.eq(true))
+                .select(PinnedMessage::as_select())
+                .first::<PinnedMessage>(conn)
+                .optional()?;
+
+            if let Some(existing) = existing {
+                return Ok((existing, false));
+            }
+
+            let active_pins: i64 = pinned_messages::table
+                .filter(pinned_messages::conversation_id.eq(conversation_id))
+                .filter(pinned_messages::is_active.eq(true))
+                .count()
+                .get_result(conn)?;
+
+            if active_pins >= max_pins {
+                return Err(PinError::LimitReached(max_pins).into());
+            }
+
+            let pin = PinnedMessage::new(message.conversation_id, message.id, device.user_id, device.id);
+            let pin = diesel::insert_into(pinned_messages::table)
+                .values(&pin)
+                .returning(PinnedMessage::as_returning())
+                .get_result::<PinnedMessage>(conn)?;
+
+            Ok((pin, true))
+        })?;
+
+        if created {
+            let event = json!({ "pin": pin.to_response() });
+            if let Err(e) = ConversationService::broadcast(conversation_id, "message.pinned", event).await {
+                tracing::warn!("Failed to broadcast pin for message {}: {}", pin.message_id, e);
+            }
+        }
+
+        Ok((pin, created))
+    }
+
+    /// Unpin a message
+    pub async fn unpin(&self, pool: &DbPool, conversation_id: &str, message_id: &str, user_id: &str) -> Result<PinnedMessage> {
+        self.ensure_can_pin(pool, conversation_id, user_id)?;
+        let mut conn = pool.get()?;
+
+        let pin = diesel::update(
+            pinned_messages::table
+                .filter(pinned_messages::conversation_id.eq(conversation_id))
+                .filter(pinned_messages::message_id.eq(message_id))
+                .filter(pinned_messages::is_active.eq(true))
+        )
+        .set((
+            pinned_messages::is_active.eq(false),
+            pinned_messages::unpinned_at.eq(Some(Utc::now())),
+        ))
+        .returning(PinnedMessage::as_returning())
+        .get_result::<PinnedMessage>(&mut conn)
+        .optional()?
+        .ok_or(PinError::NotPinned)?;
+
+        let event = json!({
+            "message_id": message_id,
+            "unpinned_by_user_id": user_id,
+        });
+        if let Err(e) = ConversationService::broadcast(conversation_id, "message.unpinned", event).await {
+            tracing::warn!("Failed to broadcast unpin for message {}: {}", message_id, e);
+        }
+
+        Ok(pin)
+    }
+
+    fn ensure_can_pin(&self, pool: &DbPool, conversation_id: &str, user_id: &str) -> Result<()> {
+        let conversation = ConversationService::find_conversation(pool, conversation_id)?;
+        let participant = ConversationService::ensure_participant(pool, conversation_id, user_id)?;
+
+        // Both sides of a direct conversation may always pin
+        let is_direct = matches!(conversation.conversation_type_enum(), ConversationType::Direct);
+        if self.config.pin_requires_moderator && !is_direct && !participant.can_moderate() {
+            return Err(PinError::InsufficientRole.into());
+        }
+
+        Ok(())
+    }
+}
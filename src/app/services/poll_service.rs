@@ -0,0 +1,304 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use utoipa::ToSchema;
+use crate::database::DbPool;
+use crate::schema::{messages, poll_votes, polls};
+use crate::app::models::DieselUlid;
+use crate::app::models::message::Message;
+use crate::app::models::polls::{Poll, PollResponse};
+use crate::app::models::poll_votes::PollVote;
+use crate::app::services::conversation_service::{ConversationError, ConversationService};
+
+/// Errors raised by poll operations
+#[derive(Debug, thiserror::Error)]
+pub enum PollError {
+    #[error("Poll not found")]
+    NotFound,
+
+    #[error("Poll is closed")]
+    Closed,
+
+    #[error("Invalid poll: {0}")]
+    Invalid(String),
+
+    #[error("Invalid vote: {0}")]
+    InvalidVote(String),
+
+    #[error("Only the poll creator or a conversation admin can close this poll")]
+    CannotClose,
+}
+
+impl PollError {
+    /// HTTP status for an error returned by the poll service
+    pub fn status_code(error: &anyhow::Error) -> axum::http::StatusCode {
+        use axum::http::StatusCode;
+
+        match error.downcast_ref::<PollError>() {
+            Some(PollError::NotFound) => StatusCode::NOT_FOUND,
+            Some(PollError::Closed) => StatusCode::CONFLICT,
+            Some(PollError::Invalid(_)) | Some(PollError::InvalidVote(_)) => StatusCode::UNPROCESSABLE_ENTITY,
+            Some(PollError::CannotClose) => StatusCode::FORBIDDEN,
+            None => ConversationError::status_code(error),
+        }
+    }
+}
+
+/// Request payload for creating a poll attached to a message
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreatePollRequest {
+    pub message_id: DieselUlid,
+    pub encrypted_question: String,
+    pub encrypted_options: String,
+    pub option_count: i32,
+    #[serde(default)]
+    pub allows_multiple_votes: bool,
+    #[serde(default)]
+    pub is_anonymous: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Request payload for casting or changing a vote
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CastVoteRequest {
+    pub device_id: DieselUlid,
+    pub option_indexes: Vec<i32>,
+    pub encrypted_vote_data: String,
+    pub vote_algorithm: String,
+}
+
+/// Aggregated poll results
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PollResults {
+    pub poll: PollResponse,
+    /// Number of users who voted
+    pub total_voters: usize,
+    /// Vote count per option, indexed like the encrypted option list
+    pub option_counts: Vec<i64>,
+    /// Options selected by the requesting user
+    pub my_selection: Vec<i32>,
+}
+
+pub struct PollService;
+
+impl PollService {
+    /// Create a poll on a message the user sent in the conversation
+    pub async fn create(pool: &DbPool, conversation_id: &str, user_id: &str, data: CreatePollRequest) -> Result<Poll> {
+        ConversationService::ensure_participant(pool, conversation_id, user_id)?;
+
+        if data.option_count < 2 {
+            return Err(PollError::Invalid("a poll needs at least two options".to_string()).into());
+        }
+        if data.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+            return Err(PollError::Invalid("expires_at must be in the future".to_string()).into());
+        }
+
+        let mut conn = pool.get()?;
+
+        messages::table
+            .filter(messages::id.eq(data.message_id.to_string()))
+            .filter(messages::conversation_id.eq(conversation_id))
+            .filter(messages::sender_user_id.eq(user_id))
+            .filter(messages::is_deleted.eq(false))
+            .select(Message::as_select())
+            .first::<Message>(&mut conn)
+            .optional()?
+            .ok_or(ConversationError::MessageNotFound)?;
+
+        let conversation_ulid = DieselUlid::from_string(conversation_id)
+            .map_err(|_| ConversationError::NotFound)?;
+
+        let poll = Poll::new(
+            data.message_id,
+            conversation_ulid,
+            data.encrypted_question,
+            data.encrypted_options,
+            data.option_count,
+            data.allows_multiple_votes,
+            data.is_anonymous,
+            data.expires_at,
+        );
+
+        let poll = diesel::insert_into(polls::table)
+            .values(&poll)
+            .returning(Poll::as_returning())
+            .get_result::<Poll>(&mut conn)?;
+
+        let event = json!({ "poll": poll.to_response() });
+        if let Err(e) = ConversationService::broadcast(conversation_id, "poll.created", event).await {
+            tracing::warn!("Failed to broadcast poll {}: {}", poll.id, e);
+        }
+
+        Ok(poll)
+    }
+
+    /// Cast a vote, replacing any earlier vote by the same user
+    pub async fn vote(pool: &DbPool, poll_id: &str, user_id: &str, data: CastVoteRequest) -> Result<PollResults> {
+        let poll = Self::find_for_participant(pool, poll_id, user_id)?;
+        let device = ConversationService::ensure_user_device(pool, &data.device_id.to_string(), user_id)?;
+
+        let mut selection = data.option_indexes.clone();
+        selection.sort_unstable();
+        selection.dedup();
+
+        if selection.is_empty() {
+            return Err(PollError::InvalidVote("select at least one option".to_string()).into());
+        }
+        if !poll.allows_multiple_votes && selection.len() > 1 {
+            return Err(PollError::InvalidVote("this poll allows a single choice".to_string()).into());
+        }
+        if selection.iter().any(|index| *index < 0 || *index >= poll.option_count) {
+            return Err(PollError::InvalidVote("option index out of range".to_string()).into());
+        }
+
+        let mut conn = pool.get()?;
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            // Share-lock the poll so a concurrent close waits until this vote is committed
+            let poll = polls::table
+                .find(poll_id)
+                .select(Poll::as_select())
+                .for_share()
+                .first::<Poll>(conn)?;
+
+            if !poll.is_active() {
+                return Err(PollError::Closed.into());
+            }
+
+            // One vote per user, whichever device it was cast from
+            diesel::delete(
+                poll_votes::table
+                    .filter(poll_votes::poll_id.eq(poll_id))
+                    .filter(poll_votes::user_id.eq(user_id))
+                    .filter(poll_votes::device_id.ne(device.id.to_string()))
+            )
+            .execute(conn)?;
+
+            let vote = PollVote::new(
+                poll.id,
+                device.user_id,
+                device.id,
+                data.encrypted_vote_data,
+                data.vote_algorithm,
+                selection,
+            );
+
+            diesel::insert_into(poll_votes::table)
+                .values(&vote)
+                .on_conflict((poll_votes::poll_id, poll_votes::user_id, poll_votes::device_id))
+                .do_update()
+                .set((
+                    poll_votes::option_indexes.eq(&vote.option_indexes),
+                    poll_votes::encrypted_vote_data.eq(&vote.encrypted_vote_data),
+                    poll_votes::vote_algorithm.eq(&vote.vote_algorithm),
+                    poll_votes::updated_at.eq(vote.updated_at),
+                ))
+                .execute(conn)?;
+
+            Ok(())
+        })?;
+
+        let results = Self::aggregate(pool, poll, user_id)?;
+
+        let event = json!({
+            "poll_id": poll_id,
+            "total_voters": results.total_voters,
+            "option_counts": results.option_counts,
+        });
+        if let Err(e) = ConversationService::broadcast(&results.poll.conversation_id.to_string(), "poll.vote_updated", event).await {
+            tracing::warn!("Failed to broadcast vote for poll {}: {}", poll_id, e);
+        }
+
+        Ok(results)
+    }
+
+    /// Close a poll so no further votes are accepted
+    pub async fn close(pool: &DbPool, poll_id: &str, user_id: &str) -> Result<PollResults> {
+        let poll = Self::find_for_participant(pool, poll_id, user_id)?;
+        let participant = ConversationService::ensure_participant(pool, &poll.conversation_id.to_string(), user_id)?;
+
+        let mut conn = pool.get()?;
+        let creator_id = messages::table
+            .find(poll.message_id.to_string())
+            .select(messages::sender_user_id)
+            .first::<String>(&mut conn)?;
+
+        if creator_id != user_id && !participant.is_admin() {
+            return Err(PollError::CannotClose.into());
+        }
+
+        let poll = diesel::update(polls::table.find(poll_id))
+            .set((
+                polls::is_closed.eq(true),
+                polls::updated_at.eq(Utc::now()),
+            ))
+            .returning(Poll::as_returning())
+            .get_result::<Poll>(&mut conn)?;
+
+        let results = Self::aggregate(pool, poll, user_id)?;
+
+        let event = json!({
+            "poll_id": poll_id,
+            "total_voters": results.total_voters,
+            "option_counts": results.option_counts,
+        });
+        if let Err(e) = ConversationService::broadcast(&results.poll.conversation_id.to_string(), "poll.closed", event).await {
+            tracing::warn!("Failed to broadcast close for poll {}: {}", poll_id, e);
+        }
+
+        Ok(results)
+    }
+
+    /// Aggregated results with the requesting user's selection
+    pub fn results(pool: &DbPool, poll_id: &str, user_id: &str) -> Result<PollResults> {
+        let poll = Self::find_for_participant(pool, poll_id, user_id)?;
+        Self::aggregate(pool, poll, user_id)
+    }
+
+    fn find_for_participant(pool: &DbPool, poll_id: &str, user_id: &str) -> Result<Poll> {
+        let mut conn = pool.get()?;
+
+        let poll = polls::table
+            .find(poll_id)
+            .select(Poll::as_select())
+            .first::<Poll>(&mut conn)
+            .optional()?
+            .ok_or(PollError::NotFound)?;
+
+        ConversationService::ensure_participant(pool, &poll.conversation_id.to_string(), user_id)?;
+
+        Ok(poll)
+    }
+
+    fn aggregate(pool: &DbPool, poll: Poll, user_id: &str) -> Result<PollResults> {
+        let mut conn = pool.get()?;
+
+        let votes = poll_votes::table
+            .filter(poll_votes::poll_id.eq(poll.id.to_string()))
+            .select(PollVote::as_select())
+            .load::<PollVote>(&mut conn)?;
+
+        let mut option_counts = vec![0i64; poll.option_count.max(0) as usize];
+        let mut my_selection = Vec::new();
+
+        for vote in &votes {
+            let selected = vote.selected_options();
+            for index in &selected {
+                if let Some(count) = option_counts.get_mut(*index as usize) {
+                    *count += 1;
+                }
+            }
+            if vote.user_id.to_string() == user_id {
+                my_selection = selected;
+            }
+        }
+
+        Ok(PollResults {
+            poll: poll.to_response(),
+            total_voters: votes.len(),
+            option_counts,
+            my_selection,
+        })
+    }
+}
@@ -0,0 +1,312 @@
+use anyhow::Result;
+use chrono::Utc;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashSet;
+use utoipa::ToSchema;
+use crate::config::Config;
+use crate::config::messaging::MessagingConfig;
+use crate::database::DbPool;
+use crate::schema::{devices, prekey_bundles};
+use crate::app::broadcasting::helpers::broadcast_to_user;
+use crate::app::models::DieselUlid;
+use crate::app::models::device::Device;
+use crate::app::models::prekey_bundle::PrekeyBundle;
+use crate::app::services::algorithm_negotiation_service::{AlgorithmNegotiationError, AlgorithmNegotiationService, NegotiatedSuite};
+use crate::app::services::conversation_service::ConversationService;
+
+/// Most one-time prekeys accepted in a single upload
+pub const MAX_PREKEYS_PER_UPLOAD: usize = 100;
+
+/// Errors raised by prekey operations
+#[derive(Debug, thiserror::Error)]
+pub enum PrekeyError {
+    #[error("Device not found")]
+    DeviceNotFound,
+
+    #[error("Prekey {0} has already been uploaded for this device")]
+    DuplicatePrekey(i32),
+
+    #[error("Identity key does not match the registered device; register a new device instead")]
+    IdentityKeyMismatch,
+
+    #[error("Invalid keys: {0}")]
+    Invalid(String),
+}
+
+impl PrekeyError {
+    /// HTTP status for an error returned by the prekey service
+    pub fn status_code(error: &anyhow::Error) -> axum::http::StatusCode {
+        use axum::http::StatusCode;
+
+        match error.downcast_ref::<PrekeyError>() {
+            Some(PrekeyError::DeviceNotFound) => StatusCode::NOT_FOUND,
+            Some(PrekeyError::DuplicatePrekey(_)) | Some(PrekeyError::IdentityKeyMismatch) => StatusCode::CONFLICT,
+            Some(PrekeyError::Invalid(_)) => StatusCode::UNPROCESSABLE_ENTITY,
+            None => AlgorithmNegotiationError::status_code(error),
+        }
+    }
+}
+
+/// A signed prekey with its identity key signature
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SignedPrekey {
+    pub key_id: i32,
+    pub public_key: String,
+    pub signature: String,
+}
+
+/// A one-time prekey
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OneTimePrekey {
+    pub key_id: i32,
+    pub public_key: String,
+}
+
+/// Request payload for publishing a device's keys
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UploadKeysRequest {
+    /// Must match the identity key the device registered with
+    pub identity_public_key: Option<String>,
+    /// Replaces the current signed prekey when present
+    pub signed_prekey: Option<SignedPrekey>,
+    #[serde(default)]
+    pub one_time_prekeys: Vec<OneTimePrekey>,
+}
+
+/// Request payload for fetching another device's bundle
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FetchBundleRequest {
+    /// The requesting user's device that will establish the session
+    pub device_id: DieselUlid,
+}
+
+/// One-time prekey supply of a device
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PrekeyStatus {
+    pub device_id: DieselUlid,
+    pub signed_prekey_id: i32,
+    pub available_one_time_prekeys: i64,
+    /// The device should upload more one-time prekeys
+    pub replenish: bool,
+}
+
+/// Keys needed to start an X3DH session with a device
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PrekeyBundleResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
+    pub user_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
+    pub device_id: DieselUlid,
+    pub registration_id: i32,
+    pub identity_public_key: String,
+    pub signed_prekey: SignedPrekey,
+    /// Absent once the device has run out of one-time prekeys
+    pub one_time_prekey: Option<OneTimePrekey>,
+    /// Suite the new session must use
+    pub suite: NegotiatedSuite,
+}
+
+pub struct PrekeyService {
+    config: MessagingConfig,
+}
+
+impl PrekeyService {
+    pub fn new() -> Result<Self> {
+        Ok(Self::with_config(Config::load()?.messaging))
+    }
+
+    pub fn with_config(config: MessagingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Publish a device's signed prekey and one-time prekeys
+    pub fn upload(&self, pool: &DbPool, user_id: &str, device_id: &str, data: UploadKeysRequest) -> Result<PrekeyStatus> {
+        let device = ConversationService::ensure_user_device(pool, device_id, user_id)?;
+
+        if data.identity_public_key.as_ref().is_some_and(|key| *key != device.identity_public_key) {
+            return Err(PrekeyError::IdentityKeyMismatch.into());
+        }
+        Self::validate(&data)?;
+
+        let mut conn = pool.get()?;
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            let now = Utc::now();
+
+            if let Some(signed_prekey) = &data.signed_prekey {
+                diesel::update(devices::table.find(device_id))
+                    .set((
+                        devices::signed_prekey_id.eq(signed_prekey.key_id),
+                        devices::signed_prekey_public.eq(&signed_prekey.public_key),
+                        devices::signed_prekey_signature.eq(&signed_prekey.signature),
+                        devices::signed_prekey_rotation_needed.eq(false),
+                        devices::last_key_rotation_at.eq(Some(now)),
+                        devices::updated_at.eq(now),
+                    ))
+                    .execute(conn)?;
+            }
+
+            if data.one_time_prekeys.is_empty() {
+                return Ok(());
+            }
+
+            // Key IDs are never reused, even after the earlier key was consumed
+            let key_ids = data.one_time_prekeys.iter().map(|prekey| prekey.key_id).collect::<Vec<_>>();
+            let existing = prekey_bundles::table
+                .filter(prekey_bundles::device_id.eq(device_id))
+                .filter(prekey_bundles::prekey_id.eq_any(&key_ids))
+                .select(prekey_bundles::prekey_id)
+                .first::<i32>(conn)
+                .optional()?;
+
+            if let Some(key_id) = existing {
+                return Err(PrekeyError::DuplicatePrekey(key_id).into());
+            }
+
+            let prekeys = data.one_time_prekeys
+                .iter()
+                .map(|prekey| PrekeyBundle::new(device.id, device.user_id, prekey.key_id, prekey.public_key.clone()))
+                .collect::<Vec<_>>();
+
+            diesel::insert_into(prekey_bundles::table)
+                .values(&prekeys)
+                .execute(conn)?;
+
+            Ok(())
+        })?;
+
+        self.status(pool, user_id, device_id)
+    }
+
+    /// One-time prekey supply of one of the user's devices
+    pub fn status(&self, pool: &DbPool, user_id: &str, device_id: &str) -> Result<PrekeyStatus> {
+        let device = ConversationService::ensure_user_device(pool, device_id, user_id)?;
+        let available = Self::available_count(pool, device_id)?;
+
+        Ok(PrekeyStatus {
+            device_id: device.id,
+            signed_prekey_id: device.signed_prekey_id,
+            available_one_time_prekeys: available,
+            replenish: available < self.config.prekey_low_threshold,
+        })
+    }
+
+    /// Fetch a device's bundle, consuming one of its one-time prekeys
+    pub async fn fetch_bundle(
+        &self,
+        pool: &DbPool,
+        user_id: &str,
+        target_device_id: &str,
+        data: FetchBundleRequest,
+    ) -> Result<PrekeyBundleResponse> {
+        let requester = ConversationService::ensure_user_device(pool, &data.device_id.to_string(), user_id)?;
+        if requester.id.to_string() == target_device_id {
+            return Err(PrekeyError::Invalid("a device cannot fetch its own bundle".to_string()).into());
+        }
+
+        let mut conn = pool.get()?;
+        let device = devices::table
+            .filter(devices::id.eq(target_device_id))
+            .filter(devices::is_active.eq(true))
+            .select(Device::as_select())
+            .first::<Device>(&mut conn)
+            .optional()?
+            .ok_or(PrekeyError::DeviceNotFound)?;
+
+        // Negotiate before consuming a prekey so incompatible devices do not drain the supply
+        let suite = AlgorithmNegotiationService::negotiate_for_devices(pool, &requester, &device)?;
+
+        let prekey = conn.transaction::<_, anyhow::Error, _>(|conn| {
+            // Skip keys another fetch is consuming so concurrent requests never share a key
+            let prekey = prekey_bundles::table
+                .filter(prekey_bundles::device_id.eq(target_device_id))
+                .filter(prekey_bundles::is_used.eq(false))
+                .order(prekey_bundles::prekey_id.asc())
+                .select(PrekeyBundle::as_select())
+                .for_update()
+                .skip_locked()
+                .first::<PrekeyBundle>(conn)
+                .optional()?;
+
+            let Some(prekey) = prekey else {
+                return Ok(None);
+            };
+
+            let now = Utc::now();
+            let prekey = diesel::update(prekey_bundles::table.find(prekey.id.to_string()))
+                .set((
+                    prekey_bundles::is_used.eq(true),
+                    prekey_bundles::used_at.eq(Some(now)),
+                    prekey_bundles::used_by_user_id.eq(Some(requester.user_id.to_string())),
+                    prekey_bundles::used_by_device_id.eq(Some(requester.id.to_string())),
+                    prekey_bundles::updated_at.eq(now),
+                ))
+                .returning(PrekeyBundle::as_returning())
+                .get_result::<PrekeyBundle>(conn)?;
+
+            Ok(Some(prekey))
+        })?;
+
+        let available = Self::available_count(pool, target_device_id)?;
+        if available < self.config.prekey_low_threshold {
+            let event = json!({
+                "device_id": target_device_id,
+                "available_one_time_prekeys": available,
+            });
+            if let Err(e) = broadcast_to_user(&device.user_id.to_string(), "prekeys.low", event).await {
+                tracing::warn!("Failed to warn device {} about low prekeys: {}", target_device_id, e);
+            }
+        }
+
+        Ok(PrekeyBundleResponse {
+            user_id: device.user_id,
+            device_id: device.id,
+            registration_id: device.registration_id,
+            identity_public_key: device.identity_public_key,
+            signed_prekey: SignedPrekey {
+                key_id: device.signed_prekey_id,
+                public_key: device.signed_prekey_public,
+                signature: device.signed_prekey_signature,
+            },
+            one_time_prekey: prekey.map(|prekey| OneTimePrekey {
+                key_id: prekey.prekey_id,
+                public_key: prekey.prekey_public,
+            }),
+            suite,
+        })
+    }
+
+    fn validate(data: &UploadKeysRequest) -> Result<()> {
+        if data.one_time_prekeys.len() > MAX_PREKEYS_PER_UPLOAD {
+            return Err(PrekeyError::Invalid(format!("at most {} one-time prekeys per upload", MAX_PREKEYS_PER_UPLOAD)).into());
+        }
+        if data.signed_prekey.as_ref().is_some_and(|key| key.public_key.is_empty() || key.signature.is_empty()) {
+            return Err(PrekeyError::Invalid("signed prekey needs a public key and signature".to_string()).into());
+        }
+
+        let mut key_ids = HashSet::new();
+        for prekey in &data.one_time_prekeys {
+            if prekey.key_id < 0 || prekey.public_key.is_empty() {
+                return Err(PrekeyError::Invalid(format!("one-time prekey {} is malformed", prekey.key_id)).into());
+            }
+            if !key_ids.insert(prekey.key_id) {
+                return Err(PrekeyError::DuplicatePrekey(prekey.key_id).into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn available_count(pool: &DbPool, device_id: &str) -> Result<i64> {
+        let mut conn = pool.get()?;
+
+        let count = prekey_bundles::table
+            .filter(prekey_bundles::device_id.eq(device_id))
+            .filter(prekey_bundles::is_used.eq(false))
+            .count()
+            .get_result(&mut conn)?;
+
+        Ok(count)
+    }
+}
@@ -0,0 +1,191 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde_json::json;
+use std::time::Duration;
+use crate::database::DbPool;
+use crate::schema::{messages, scheduled_messages};
+use crate::app::models::message::Message;
+use crate::app::models::scheduled_messages::ScheduledMessage;
+use crate::app::services::conversation_service::ConversationService;
+use crate::app::services::message_service::MessageService;
+use crate::app::traits::Touches;
+
+/// Base delay before retrying a failed scheduled message, doubled on every attempt
+const RETRY_BASE_DELAY_SECS: i64 = 60;
+
+/// Outcome of a dispatch run
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DispatchSummary {
+    pub sent: usize,
+    pub failed: usize,
+}
+
+/// A scheduled message delivered within a committed transaction
+struct DeliveredMessage {
+    scheduled: ScheduledMessage,
+    message: Message,
+    recipients: usize,
+}
+
+pub struct ScheduledMessageService;
+
+impl ScheduledMessageService {
+    /// Deliver every scheduled message that is due, up to `limit` messages
+    pub async fn dispatch_due(pool: &DbPool, limit: i64) -> Result<DispatchSummary> {
+        let mut summary = DispatchSummary::default();
+
+        for id in Self::due_ids(pool, Utc::now(), limit)? {
+            match Self::deliver(pool, &id) {
+                Ok(Some(delivered)) => {
+                    summary.sent += 1;
+                    Self::broadcast_sent(&delivered).await;
+                }
+                // Already sent, cancelled, or claimed by another worker
+                Ok(None) => {}
+                Err(e) => {
+                    summary.failed += 1;
+                    tracing::error!("Failed to deliver scheduled message {}: {}", id, e);
+                    if let Err(e) = Self::record_failure(pool, &id, &e.to_string()) {
+                        tracing::error!("Failed to record failure for scheduled message {}: {}", id, e);
+                    }
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Poll for due messages until the task is dropped
+    ///
+    /// Polls at most once a second, since a zero interval would spin.
+    pub async fn run(pool: DbPool, interval: Duration, limit: i64) {
+        let mut ticker = tokio::time::interval(interval.max(Duration::from_secs(1)));
+
+        loop {
+            ticker.tick().await;
+
+            match Self::dispatch_due(&pool, limit).await {
+                Ok(summary) if summary.sent > 0 || summary.failed > 0 => {
+                    tracing::info!("Dispatched scheduled messages: {} sent, {} failed", summary.sent, summary.failed);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Scheduled message dispatch failed: {}", e),
+            }
+        }
+    }
+
+    /// Spawn the polling task on the Tokio runtime
+    pub fn spawn(pool: DbPool, interval: Duration, limit: i64) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(Self::run(pool, interval, limit))
+    }
+
+    fn due_ids(pool: &DbPool, now: DateTime<Utc>, limit: i64) -> Result<Vec<String>> {
+        let mut conn = pool.get()?;
+
+        let ids = scheduled_messages::table
+            .filter(scheduled_messages::is_sent.eq(false))
+            .filter(scheduled_messages::is_cancelled.eq(false))
+            .filter(scheduled_messages::scheduled_for.le(now))
+            .filter(scheduled_messages::retry_count.lt(scheduled_messages::max_retries))
+            .filter(
+                scheduled_messages::next_retry_at.is_null()
+                    .or(scheduled_messages::next_retry_at.le(now))
+            )
+            .order(scheduled_messages::scheduled_for.asc())
+            .limit(limit)
+            .select(scheduled_messages::id)
+            .load::<String>(&mut conn)?;
+
+        Ok(ids)
+    }
+
+    /// Deliver one scheduled message.
+    ///
+    /// The row is locked with `SKIP LOCKED` and every write happens in the same
+    /// transaction that marks it sent, so a crash mid-send rolls back and a
+    /// concurrent worker never delivers it twice.
+    fn deliver(pool: &DbPool, id: &str) -> Result<Option<DeliveredMessage>> {
+        let mut conn = pool.get()?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            let scheduled = scheduled_messages::table
+                .filter(scheduled_messages::id.eq(id))
+                .filter(scheduled_messages::is_sent.eq(false))
+                .filter(scheduled_messages::is_cancelled.eq(false))
+                .select(ScheduledMessage::as_select())
+                .for_update()
+                .skip_locked()
+                .first::<ScheduledMessage>(conn)
+                .optional()?;
+
+            let Some(scheduled) = scheduled else {
+                return Ok(None);
+            };
+
+            let now = Utc::now();
+
+            // The encrypted payload is stored with the schedule; sending stamps it with the delivery time
+            let message = diesel::update(messages::table.find(scheduled.message_id.to_string()))
+                .set((
+                    messages::sent_at.eq(now),
+                    messages::updated_at.eq(now),
+                ))
+                .get_result::<Message>(conn)?;
+
+            let recipients = MessageService::create_receipts(conn, &message)?;
+            message.touch_parents(conn)?;
+
+            let scheduled = diesel::update(scheduled_messages::table.find(id))
+                .set((
+                    scheduled_messages::is_sent.eq(true),
+                    scheduled_messages::sent_at.eq(Some(now)),
+                    scheduled_messages::failure_reason.eq(None::<String>),
+                    scheduled_messages::next_retry_at.eq(None::<DateTime<Utc>>),
+                    scheduled_messages::updated_at.eq(now),
+                ))
+                .get_result::<ScheduledMessage>(conn)?;
+
+            Ok(Some(DeliveredMessage { scheduled, message, recipients }))
+        })
+    }
+
+    fn record_failure(pool: &DbPool, id: &str, reason: &str) -> Result<()> {
+        let mut conn = pool.get()?;
+
+        let scheduled = scheduled_messages::table
+            .find(id)
+            .select(ScheduledMessage::as_select())
+            .first::<ScheduledMessage>(&mut conn)?;
+
+        let now = Utc::now();
+        let retry_count = scheduled.retry_count + 1;
+        let next_retry_at = (retry_count < scheduled.max_retries)
+            .then(|| now + chrono::Duration::seconds(RETRY_BASE_DELAY_SECS * 2_i64.pow(scheduled.retry_count as u32)));
+
+        diesel::update(scheduled_messages::table.find(id))
+            .set((
+                scheduled_messages::retry_count.eq(retry_count),
+                scheduled_messages::failed_at.eq(Some(now)),
+                scheduled_messages::failure_reason.eq(Some(reason)),
+                scheduled_messages::next_retry_at.eq(next_retry_at),
+                scheduled_messages::updated_at.eq(now),
+            ))
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    async fn broadcast_sent(delivered: &DeliveredMessage) {
+        let conversation_id = delivered.scheduled.conversation_id.to_string();
+        let data = json!({
+            "message": delivered.message.to_response(),
+            "scheduled_message_id": delivered.scheduled.id.to_string(),
+            "recipients": delivered.recipients,
+        });
+
+        if let Err(e) = ConversationService::broadcast(&conversation_id, "message.sent", data).await {
+            tracing::warn!("Failed to broadcast scheduled message {}: {}", delivered.message.id, e);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use diesel::prelude::*;
+use serde_json::json;
+use crate::database::DbPool;
+use crate::schema::{security_incidents, sys_model_has_roles, sys_roles, sys_users};
+use crate::app::broadcasting::helpers::broadcast_to_channel;
+use crate::app::models::DieselUlid;
+use crate::app::models::security_incidents::{IncidentSeverity, IncidentType, SecurityIncident};
+use crate::app::models::user::User;
+use crate::app::notifications::notify;
+use crate::app::notifications::security_incident_notification::SecurityIncidentNotification;
+
+/// Channel administrators subscribe to for high severity incidents
+pub const ADMIN_SECURITY_CHANNEL: &str = "admin.security";
+
+/// Roles whose members are alerted about incidents and may list them
+const ADMIN_ROLES: [&str; 2] = ["admin", "super_admin"];
+
+/// Errors raised by security incident operations
+#[derive(Debug, thiserror::Error)]
+pub enum SecurityIncidentError {
+    #[error("Only administrators can view security incidents")]
+    NotAdmin,
+}
+
+impl SecurityIncidentError {
+    /// HTTP status for an error returned by the security incident service
+    pub fn status_code(error: &anyhow::Error) -> axum::http::StatusCode {
+        use axum::http::StatusCode;
+
+        match error.downcast_ref::<SecurityIncidentError>() {
+            Some(SecurityIncidentError::NotAdmin) => StatusCode::FORBIDDEN,
+            None => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// What an incident is about
+#[derive(Debug, Clone, Default)]
+pub struct IncidentSubject {
+    pub user_id: Option<DieselUlid>,
+    pub device_id: Option<DieselUlid>,
+    pub conversation_id: Option<DieselUlid>,
+}
+
+impl IncidentSubject {
+    pub fn user(user_id: DieselUlid) -> Self {
+        Self {
+            user_id: Some(user_id),
+            ..Self::default()
+        }
+    }
+}
+
+pub struct SecurityIncidentService;
+
+impl SecurityIncidentService {
+    /// Record a security incident, alerting administrators when it is high or critical severity
+    pub async fn record_incident(
+        pool: &DbPool,
+        kind: IncidentType,
+        severity: IncidentSeverity,
+        subject: IncidentSubject,
+        metadata: serde_json::Value,
+    ) -> Result<SecurityIncident> {
+        let incident = SecurityIncident::new(
+            subject.device_id,
+            subject.user_id,
+            subject.conversation_id,
+            kind,
+            severity,
+            None,
+            None,
+        )
+        .with_metadata(metadata);
+
+        let mut conn = pool.get()?;
+        let incident = diesel::insert_into(security_incidents::table)
+            .values(&incident)
+            .returning(SecurityIncident::as_returning())
+            .get_result::<SecurityIncident>(&mut conn)?;
+
+        tracing::warn!(
+            "Security incident {} recorded: {} ({})",
+            incident.id, incident.incident_type, incident.severity
+        );
+
+        if incident.is_high_priority() {
+            Self::alert_admins(pool, &incident).await;
+        }
+
+        Ok(incident)
+    }
+
+    /// Record an incident from a code path that must not fail because recording did
+    pub async fn report(
+        pool: &DbPool,
+        kind: IncidentType,
+        severity: IncidentSeverity,
+        subject: IncidentSubject,
+        metadata: serde_json::Value,
+    ) {
+        if let Err(e) = Self::record_incident(pool, kind, severity, subject, metadata).await {
+            tracing::error!("Failed to record security incident: {}", e);
+        }
+    }
+
+    /// Active users holding an administrator role
+    pub fn admin_users(pool: &DbPool) -> Result<Vec<User>> {
+        let mut conn = pool.get()?;
+
+        let admins = sys_users::table
+            .inner_join(
+                sys_model_has_roles::table.on(
+                    sys_model_has_roles::model_id.eq(sys_users::id)
+                    .and(sys_model_has_roles::model_type.eq("User"))
+                )
+            )
+            .inner_join(sys_roles::table.on(sys_roles::id.eq(sys_model_has_roles::role_id)))
+            .filter(sys_roles::name.eq_any(ADMIN_ROLES))
+            .filter(sys_roles::deleted_at.is_null())
+            .filter(sys_model_has_roles::deleted_at.is_null())
+            .filter(sys_users::deleted_at.is_null())
+            .select(User::as_select())
+            .distinct()
+            .load::<User>(&mut conn)?;
+
+        Ok(admins)
+    }
+
+    pub fn is_admin(pool: &DbPool, user_id: &str) -> Result<bool> {
+        Ok(Self::admin_users(pool)?.iter().any(|user| user.id.to_string() == user_id))
+    }
+
+    /// Fail unless the user holds an administrator role
+    pub fn ensure_admin(pool: &DbPool, user_id: &str) -> Result<()> {
+        if !Self::is_admin(pool, user_id)? {
+            return Err(SecurityIncidentError::NotAdmin.into());
+        }
+        Ok(())
+    }
+
+    async fn alert_admins(pool: &DbPool, incident: &SecurityIncident) {
+        match Self::admin_users(pool) {
+            Ok(admins) => {
+                for admin in &admins {
+                    if let Err(e) = notify(admin, SecurityIncidentNotification::new(incident)).await {
+                        tracing::warn!("Failed to notify admin {} of security incident {}: {}", admin.id, incident.id, e);
+                    }
+                }
+            }
+            Err(e) => tracing::warn!("Failed to load admins for security incident {}: {}", incident.id, e),
+        }
+
+        let event = json!({
+            "id": incident.id.to_string(),
+            "incident_type": incident.incident_type,
+            "severity": incident.severity,
+            "user_id": incident.user_id.map(|id| id.to_string()),
+            "device_id": incident.device_id.map(|id| id.to_string()),
+            "conversation_id": incident.conversation_id.map(|id| id.to_string()),
+            "metadata": incident.metadata,
+            "created_at": incident.created_at,
+        });
+
+        if let Err(e) = broadcast_to_channel(ADMIN_SECURITY_CHANNEL, "security.incident", event).await {
+            tracing::warn!("Failed to broadcast security incident {}: {}", incident.id, e);
+        }
+    }
+}
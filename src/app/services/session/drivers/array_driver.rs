@@ -1,35 +1,81 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::app::services::session::SessionHandler;
 
+/// Session lifetime when none is configured, matching `SESSION_LIFETIME=120`
+const DEFAULT_LIFETIME: u64 = 7200;
+
 #[derive(Debug, Clone)]
 struct SessionData {
     data: String,
-    expires_at: u64,
+    /// When the session was last written, in seconds since the epoch
+    last_activity: u64,
 }
 
+/// In-memory session store for tests
+///
+/// Behaves like the persistent drivers: a session expires `lifetime` seconds
+/// after its last write, and `gc` drops sessions idle for longer than the
+/// lifetime it is given. `travel` moves the handler's clock forward so tests
+/// can exercise expiry without sleeping.
 #[derive(Debug, Clone)]
 pub struct ArraySessionHandler {
     sessions: Arc<RwLock<HashMap<String, SessionData>>>,
+    lifetime: u64,
+    offset: Arc<AtomicU64>,
 }
 
 impl ArraySessionHandler {
     pub fn new() -> Self {
+        Self::with_lifetime(DEFAULT_LIFETIME)
+    }
+
+    /// Handler whose sessions expire `lifetime` seconds after their last write
+    pub fn with_lifetime(lifetime: u64) -> Self {
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
+            lifetime,
+            offset: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Move the handler's clock forward by `duration`
+    pub fn travel(&self, duration: Duration) {
+        self.offset.fetch_add(duration.as_secs(), Ordering::SeqCst);
+    }
+
+    /// Number of sessions held, expired or not
+    pub async fn len(&self) -> usize {
+        self.sessions.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.sessions.read().await.is_empty()
+    }
+
     fn current_timestamp(&self) -> u64 {
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs()
+            + self.offset.load(Ordering::SeqCst)
+    }
+
+    /// Whether a session last written at `last_activity` is older than `lifetime`
+    fn is_expired(&self, last_activity: u64, lifetime: u64) -> bool {
+        last_activity + lifetime < self.current_timestamp()
+    }
+}
+
+impl Default for ArraySessionHandler {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -38,24 +84,20 @@ impl SessionHandler for ArraySessionHandler {
     async fn read(&self, session_id: &str) -> Result<Option<String>> {
         let sessions = self.sessions.read().await;
 
-        if let Some(session_data) = sessions.get(session_id) {
-            if self.current_timestamp() <= session_data.expires_at {
-                return Ok(Some(session_data.data.clone()));
-            }
-        }
-
-        Ok(None)
+        Ok(sessions
+            .get(session_id)
+            .filter(|session_data| !self.is_expired(session_data.last_activity, self.lifetime))
+            .map(|session_data| session_data.data.clone()))
     }
 
     async fn write(&self, session_id: &str, data: &str) -> Result<()> {
         let mut sessions = self.sessions.write().await;
-        let expires_at = self.current_timestamp() + 7200; // 2 hours from now
 
         sessions.insert(
             session_id.to_string(),
             SessionData {
                 data: data.to_string(),
-                expires_at,
+                last_activity: self.current_timestamp(),
             },
         );
 
@@ -68,12 +110,11 @@ impl SessionHandler for ArraySessionHandler {
         Ok(())
     }
 
-    async fn gc(&self, _lifetime: u64) -> Result<()> {
+    async fn gc(&self, lifetime: u64) -> Result<()> {
         let mut sessions = self.sessions.write().await;
-        let current_time = self.current_timestamp();
 
-        sessions.retain(|_, session_data| current_time <= session_data.expires_at);
+        sessions.retain(|_, session_data| !self.is_expired(session_data.last_activity, lifetime));
 
         Ok(())
     }
-}
\ No newline at end of file
+}
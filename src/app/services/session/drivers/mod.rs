@@ -29,7 +29,7 @@ pub async fn create_session_handler(
             let redis_url = env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
             Ok(Box::new(RedisSessionHandler::new(&redis_url)?))
         }
-        "array" => Ok(Box::new(ArraySessionHandler::new())),
+        "array" => Ok(Box::new(ArraySessionHandler::with_lifetime(config.lifetime_in_seconds()))),
         _ => Err(anyhow::anyhow!("Unsupported session driver: {}", driver)),
     }
 }
\ No newline at end of file
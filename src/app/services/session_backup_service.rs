@@ -0,0 +1,354 @@
+use anyhow::Result;
+use chrono::Utc;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+use crate::config::Config;
+use crate::config::messaging::MessagingConfig;
+use crate::database::DbPool;
+use crate::schema::{device_session_backups, encrypted_backup_keys, sys_users};
+use crate::app::models::DieselUlid;
+use crate::app::models::device_session_backups::{DeviceSessionBackup, DeviceSessionBackupResponse, SessionBackupType};
+use crate::app::models::encrypted_backup_keys::{BackupType, EncryptedBackupKey};
+use crate::app::models::security_incidents::{IncidentSeverity, IncidentType};
+use crate::app::services::conversation_service::{ConversationError, ConversationService};
+use crate::app::services::security_incident_service::{IncidentSubject, SecurityIncidentService};
+
+/// Largest wrapped backup key accepted, in bytes
+pub const MAX_BACKUP_KEY_BYTES: usize = 8192;
+
+/// Longest a backup may be kept before it expires, in days
+pub const MAX_BACKUP_EXPIRY_DAYS: i32 = 365;
+
+/// Errors raised by session backup operations
+#[derive(Debug, thiserror::Error)]
+pub enum SessionBackupError {
+    #[error("Session backup not found")]
+    NotFound,
+
+    #[error("Session backup exceeds the {0} byte limit")]
+    TooLarge(usize),
+
+    #[error("Session backup quota of {0} reached; delete an older backup first")]
+    QuotaExceeded(i64),
+
+    #[error("Backup key proof does not match")]
+    InvalidKeyProof,
+
+    #[error("Session backup failed its integrity check")]
+    Corrupted,
+
+    #[error("Invalid session backup: {0}")]
+    Invalid(String),
+}
+
+impl SessionBackupError {
+    /// HTTP status for an error returned by the session backup service
+    pub fn status_code(error: &anyhow::Error) -> axum::http::StatusCode {
+        use axum::http::StatusCode;
+
+        match error.downcast_ref::<SessionBackupError>() {
+            Some(SessionBackupError::NotFound) => StatusCode::NOT_FOUND,
+            Some(SessionBackupError::TooLarge(_)) => StatusCode::PAYLOAD_TOO_LARGE,
+            Some(SessionBackupError::QuotaExceeded(_)) => StatusCode::CONFLICT,
+            Some(SessionBackupError::InvalidKeyProof) => StatusCode::FORBIDDEN,
+            Some(SessionBackupError::Corrupted) => StatusCode::UNPROCESSABLE_ENTITY,
+            Some(SessionBackupError::Invalid(_)) => StatusCode::UNPROCESSABLE_ENTITY,
+            None => ConversationError::status_code(error),
+        }
+    }
+}
+
+/// Request payload for uploading a session backup
+///
+/// The client seals its sessions with a random backup key and wraps that key
+/// with a recovery secret only the user knows. The server stores both blobs
+/// and the SHA-256 of the unwrapped backup key, never the key itself.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UploadSessionBackupRequest {
+    pub backup_name: String,
+    /// One of full, partial, metadata, emergency
+    pub backup_type: Option<String>,
+    pub encrypted_sessions_data: String,
+    pub backup_algorithm: String,
+    /// Backup key wrapped with the user's recovery secret
+    pub encrypted_backup_key: String,
+    pub key_algorithm: String,
+    /// Hex SHA-256 of the unwrapped backup key, presented again to restore
+    pub backup_key_hash: String,
+    #[serde(default)]
+    pub sessions_count: i32,
+    #[serde(default)]
+    pub conversations_count: i32,
+    pub expires_in_days: Option<i32>,
+}
+
+/// Request payload for fetching a backup's wrapped key
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BackupKeyRequest {
+    /// The user's device that will restore the backup
+    pub device_id: DieselUlid,
+}
+
+/// Request payload for restoring a session backup
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RestoreSessionBackupRequest {
+    /// The user's device that will restore the backup
+    pub device_id: DieselUlid,
+    /// Hex SHA-256 of the unwrapped backup key
+    pub backup_key_hash: String,
+}
+
+/// Wrapped key of a session backup, unwrapped on the device with the recovery secret
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BackupKeyResponse {
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
+    pub backup_id: DieselUlid,
+    #[serde(with = "crate::app::models::diesel_ulid::response")]
+    pub backup_key_id: DieselUlid,
+    pub encrypted_backup_key: String,
+    pub key_algorithm: String,
+}
+
+/// Encrypted sessions handed to a device that proved it holds the backup key
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RestoredSessionBackup {
+    pub backup: DeviceSessionBackupResponse,
+    pub encrypted_sessions_data: String,
+    pub backup_checksum: String,
+}
+
+pub struct SessionBackupService {
+    config: MessagingConfig,
+}
+
+impl SessionBackupService {
+    pub fn new() -> Result<Self> {
+        Ok(Self::with_config(Config::load()?.messaging))
+    }
+
+    pub fn with_config(config: MessagingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Store a device's encrypted session backup together with its wrapped backup key
+    pub fn upload(&self, pool: &DbPool, user_id: &str, device_id: &str, data: UploadSessionBackupRequest) -> Result<DeviceSessionBackupResponse> {
+        let device = ConversationService::ensure_user_device(pool, device_id, user_id)?;
+        self.validate(&data)?;
+
+        let backup_type = SessionBackupType::from(data.backup_type.clone().unwrap_or_else(|| "full".to_string()));
+        let key_hash = data.backup_key_hash.to_lowercase();
+        let wrapped_key_hash = format!("{:x}", Sha256::digest(data.encrypted_backup_key.as_bytes()));
+
+        let mut conn = pool.get()?;
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            // Serialize uploads per user so concurrent requests cannot overshoot the quota
+            sys_users::table
+                .find(user_id)
+                .select(sys_users::id)
+                .for_update()
+                .first::<String>(conn)?;
+
+            let active: i64 = device_session_backups::table
+                .filter(device_session_backups::user_id.eq(user_id))
+                .filter(device_session_backups::expires_at.gt(Utc::now()))
+                .count()
+                .get_result(conn)?;
+
+            if active >= self.config.session_backup_quota {
+                return Err(SessionBackupError::QuotaExceeded(self.config.session_backup_quota).into());
+            }
+
+            let mut backup_key = EncryptedBackupKey::new(
+                device.user_id,
+                device.id,
+                data.encrypted_backup_key,
+                data.key_algorithm,
+                BackupType::SessionKeys,
+                wrapped_key_hash,
+                data.expires_in_days,
+            );
+            let backup = DeviceSessionBackup::new(
+                device.id,
+                device.user_id,
+                data.backup_name,
+                backup_type,
+                data.encrypted_sessions_data,
+                data.backup_algorithm,
+                key_hash,
+                data.sessions_count,
+                data.conversations_count,
+                data.expires_in_days,
+            )
+            .with_backup_key(backup_key.id);
+            // The key is useless without the backup, so it expires with it
+            backup_key.expires_at = backup.expires_at;
+
+            diesel::insert_into(encrypted_backup_keys::table)
+                .values(&backup_key)
+                .execute(conn)?;
+
+            let backup = diesel::insert_into(device_session_backups::table)
+                .values(&backup)
+                .returning(DeviceSessionBackup::as_returning())
+                .get_result::<DeviceSessionBackup>(conn)?;
+
+            Ok(backup.to_response())
+        })
+    }
+
+    /// The user's unexpired session backups, newest first
+    pub fn list(&self, pool: &DbPool, user_id: &str) -> Result<Vec<DeviceSessionBackupResponse>> {
+        let mut conn = pool.get()?;
+
+        let backups = device_session_backups::table
+            .filter(device_session_backups::user_id.eq(user_id))
+            .filter(device_session_backups::expires_at.gt(Utc::now()))
+            .order(device_session_backups::created_at.desc())
+            .select(DeviceSessionBackup::as_select())
+            .load::<DeviceSessionBackup>(&mut conn)?;
+
+        Ok(backups.iter().map(DeviceSessionBackup::to_response).collect())
+    }
+
+    /// Hand the wrapped backup key to one of the user's active devices
+    pub fn backup_key(&self, pool: &DbPool, user_id: &str, backup_id: &str, data: BackupKeyRequest) -> Result<BackupKeyResponse> {
+        ConversationService::ensure_user_device(pool, &data.device_id.to_string(), user_id)?;
+        let backup = Self::find_for_user(pool, user_id, backup_id)?;
+        let backup_key = Self::find_backup_key(pool, &backup)?;
+
+        Ok(BackupKeyResponse {
+            backup_id: backup.id,
+            backup_key_id: backup_key.id,
+            encrypted_backup_key: backup_key.encrypted_backup_data,
+            key_algorithm: backup_key.backup_algorithm,
+        })
+    }
+
+    /// Release the encrypted sessions to a device of the user that proves it unwrapped the backup key
+    pub async fn restore(&self, pool: &DbPool, user_id: &str, backup_id: &str, data: RestoreSessionBackupRequest) -> Result<RestoredSessionBackup> {
+        let device = ConversationService::ensure_user_device(pool, &data.device_id.to_string(), user_id)?;
+        let mut backup = Self::find_for_user(pool, user_id, backup_id)?;
+
+        if !constant_time_eq(data.backup_key_hash.to_lowercase().as_bytes(), backup.backup_key_hash.as_bytes()) {
+            tracing::warn!("Device {} presented a wrong key proof for session backup {}", device.id, backup.id);
+            SecurityIncidentService::report(
+                pool,
+                IncidentType::UnauthorizedAccess,
+                IncidentSeverity::Medium,
+                IncidentSubject {
+                    user_id: Some(device.user_id),
+                    device_id: Some(device.id),
+                    conversation_id: None,
+                },
+                json!({ "session_backup_id": backup.id.to_string(), "reason": "backup_key_proof_mismatch" }),
+            ).await;
+            return Err(SessionBackupError::InvalidKeyProof.into());
+        }
+
+        let intact = backup.verify();
+        if intact {
+            backup.access();
+        }
+
+        let mut conn = pool.get()?;
+        let backup = diesel::update(device_session_backups::table.find(backup.id.to_string()))
+            .set((
+                device_session_backups::is_verified.eq(backup.is_verified),
+                device_session_backups::verification_failed_at.eq(backup.verification_failed_at),
+                device_session_backups::last_accessed_at.eq(backup.last_accessed_at),
+                device_session_backups::updated_at.eq(backup.updated_at),
+            ))
+            .returning(DeviceSessionBackup::as_returning())
+            .get_result::<DeviceSessionBackup>(&mut conn)?;
+
+        if !intact {
+            return Err(SessionBackupError::Corrupted.into());
+        }
+
+        Ok(RestoredSessionBackup {
+            backup: backup.to_response(),
+            encrypted_sessions_data: backup.encrypted_sessions_data,
+            backup_checksum: backup.backup_checksum,
+        })
+    }
+
+    /// Delete one of the user's backups along with its wrapped key, freeing quota
+    pub fn delete(&self, pool: &DbPool, user_id: &str, backup_id: &str) -> Result<()> {
+        let backup = Self::find_for_user(pool, user_id, backup_id)?;
+
+        let mut conn = pool.get()?;
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            diesel::delete(device_session_backups::table.find(backup.id.to_string())).execute(conn)?;
+            if let Some(backup_key_id) = backup.backup_key_id {
+                diesel::delete(encrypted_backup_keys::table.find(backup_key_id.to_string())).execute(conn)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn validate(&self, data: &UploadSessionBackupRequest) -> Result<()> {
+        if data.encrypted_sessions_data.len() > self.config.session_backup_max_bytes {
+            return Err(SessionBackupError::TooLarge(self.config.session_backup_max_bytes).into());
+        }
+        if data.encrypted_backup_key.len() > MAX_BACKUP_KEY_BYTES {
+            return Err(SessionBackupError::TooLarge(MAX_BACKUP_KEY_BYTES).into());
+        }
+        if data.backup_name.trim().is_empty() || data.encrypted_sessions_data.is_empty() || data.encrypted_backup_key.is_empty() {
+            return Err(SessionBackupError::Invalid("backup name, sessions and backup key are required".to_string()).into());
+        }
+        if data.backup_algorithm.is_empty() || data.key_algorithm.is_empty() {
+            return Err(SessionBackupError::Invalid("backup and key algorithms are required".to_string()).into());
+        }
+        if data.backup_key_hash.len() != 64 || !data.backup_key_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(SessionBackupError::Invalid("backup key hash must be a hex SHA-256 digest".to_string()).into());
+        }
+        if data.sessions_count < 0 || data.conversations_count < 0 {
+            return Err(SessionBackupError::Invalid("counts cannot be negative".to_string()).into());
+        }
+        if data.expires_in_days.is_some_and(|days| !(1..=MAX_BACKUP_EXPIRY_DAYS).contains(&days)) {
+            return Err(SessionBackupError::Invalid(
+                format!("expiry must be between 1 and {} days", MAX_BACKUP_EXPIRY_DAYS)
+            ).into());
+        }
+
+        Ok(())
+    }
+
+    fn find_for_user(pool: &DbPool, user_id: &str, backup_id: &str) -> Result<DeviceSessionBackup> {
+        let mut conn = pool.get()?;
+
+        let backup = device_session_backups::table
+            .filter(device_session_backups::id.eq(backup_id))
+            .filter(device_session_backups::user_id.eq(user_id))
+            .filter(device_session_backups::expires_at.gt(Utc::now()))
+            .select(DeviceSessionBackup::as_select())
+            .first::<DeviceSessionBackup>(&mut conn)
+            .optional()?
+            .ok_or(SessionBackupError::NotFound)?;
+
+        Ok(backup)
+    }
+
+    fn find_backup_key(pool: &DbPool, backup: &DeviceSessionBackup) -> Result<EncryptedBackupKey> {
+        let backup_key_id = backup.backup_key_id.ok_or(SessionBackupError::NotFound)?;
+        let mut conn = pool.get()?;
+
+        let backup_key = encrypted_backup_keys::table
+            .filter(encrypted_backup_keys::id.eq(backup_key_id.to_string()))
+            .filter(encrypted_backup_keys::user_id.eq(backup.user_id.to_string()))
+            .select(EncryptedBackupKey::as_select())
+            .first::<EncryptedBackupKey>(&mut conn)
+            .optional()?
+            .ok_or(SessionBackupError::NotFound)?;
+
+        Ok(backup_key)
+    }
+}
+
+/// Compare two byte strings without leaking where they first differ
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,89 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+use utoipa::ToSchema;
+use crate::cache::manager::CacheFacade;
+use crate::database::DbPool;
+use crate::app::models::DieselUlid;
+use crate::app::services::conversation_service::ConversationService;
+
+/// Seconds a typing indicator stays active unless the client refreshes it
+pub const TYPING_INDICATOR_TTL_SECS: u64 = 5;
+
+/// Request payload for a typing notification
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TypingRequest {
+    pub device_id: DieselUlid,
+    /// Set to false when the user stops typing before the indicator expires
+    #[serde(default = "default_is_typing")]
+    pub is_typing: bool,
+}
+
+fn default_is_typing() -> bool {
+    true
+}
+
+/// Typing state stored in the cache until it expires
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TypingState {
+    pub conversation_id: String,
+    pub user_id: String,
+    pub device_id: String,
+    pub is_typing: bool,
+    pub expires_at: DateTime<Utc>,
+}
+
+pub struct TypingIndicatorService;
+
+impl TypingIndicatorService {
+    fn cache_key(conversation_id: &str, user_id: &str) -> String {
+        format!("typing:{}:{}", conversation_id, user_id)
+    }
+
+    /// Record that a user is (or stopped) typing and notify the other participants
+    pub async fn typing(pool: &DbPool, conversation_id: &str, user_id: &str, data: TypingRequest) -> Result<TypingState> {
+        ConversationService::ensure_participant(pool, conversation_id, user_id)?;
+        let device = ConversationService::ensure_user_device(pool, &data.device_id.to_string(), user_id)?;
+
+        let ttl = Duration::from_secs(TYPING_INDICATOR_TTL_SECS);
+        let state = TypingState {
+            conversation_id: conversation_id.to_string(),
+            user_id: user_id.to_string(),
+            device_id: device.id.to_string(),
+            is_typing: data.is_typing,
+            expires_at: Utc::now() + chrono::Duration::seconds(TYPING_INDICATOR_TTL_SECS as i64),
+        };
+
+        // Indicators are short-lived, so they live in the cache rather than the typing_indicators table
+        let key = Self::cache_key(conversation_id, user_id);
+        let cached = if data.is_typing {
+            CacheFacade::put(&key, &state, Some(ttl)).await
+        } else {
+            CacheFacade::forget(&key).await.map(|_| ())
+        };
+        if let Err(e) = cached {
+            tracing::warn!("Failed to cache typing indicator for conversation {}: {}", conversation_id, e);
+        }
+
+        let event = json!({
+            "conversation_id": state.conversation_id,
+            "user_id": state.user_id,
+            "device_id": state.device_id,
+            "is_typing": state.is_typing,
+            "expires_at": state.expires_at,
+        });
+
+        if let Err(e) = ConversationService::broadcast_to_others(pool, conversation_id, user_id, "typing", event).await {
+            tracing::warn!("Failed to broadcast typing for conversation {}: {}", conversation_id, e);
+        }
+
+        Ok(state)
+    }
+
+    /// Whether a user currently has an unexpired typing indicator in a conversation
+    pub async fn is_typing(conversation_id: &str, user_id: &str) -> Result<bool> {
+        CacheFacade::has(&Self::cache_key(conversation_id, user_id)).await
+    }
+}
@@ -187,6 +187,21 @@ impl UserService {
         Ok(())
     }
 
+    pub fn mark_email_verified(pool: &DbPool, id: DieselUlid) -> Result<()> {
+        let mut conn = pool.get()?;
+
+        diesel::update(sys_users::table
+            .filter(sys_users::id.eq(id.to_string()))
+            .filter(sys_users::deleted_at.is_null()))
+            .set((
+                sys_users::email_verified_at.eq(Some(Utc::now())),
+                sys_users::updated_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+
     pub fn update_failed_attempts(pool: &DbPool, id: DieselUlid, attempts: i32, locked_until: Option<DateTime<Utc>>) -> Result<()> {
         let mut conn = pool.get()?;
 
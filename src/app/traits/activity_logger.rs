@@ -2,6 +2,7 @@ use anyhow::Result;
 use serde_json::json;
 use crate::app::models::HasModelType;
 use crate::app::models::activity_log::HasId;
+use crate::app::query_builder::cache::invalidate_model;
 use crate::app::http::middleware::activity_logging_middleware::ActivityLogger as MiddlewareActivityLogger;
 
 /// Trait for service-level activity logging using the existing middleware ActivityLogger
@@ -22,6 +23,8 @@ pub trait ServiceActivityLogger {
     }
 
     /// Log a create operation using the middleware ActivityLogger
+    ///
+    /// Create, update and delete events also flush the model's cached query results.
     fn log_created<T: HasModelType + HasId>(
         &self,
         entity: &T,
@@ -42,6 +45,7 @@ pub trait ServiceActivityLogger {
         let model_type = T::model_type();
 
         async move {
+            invalidate_model(model_type).await;
             logger.log_create(model_type, &entity_id, Some(props)).await
                 .map_err(anyhow::Error::from)
         }
@@ -70,6 +74,7 @@ pub trait ServiceActivityLogger {
         let model_type = T::model_type();
 
         async move {
+            invalidate_model(model_type).await;
             logger.log_update(model_type, &entity_id, Some(props)).await
                 .map_err(anyhow::Error::from)
         }
@@ -96,6 +101,7 @@ pub trait ServiceActivityLogger {
         let model_type = T::model_type();
 
         async move {
+            invalidate_model(model_type).await;
             logger.log_delete(model_type, &entity_id, Some(props)).await
                 .map_err(anyhow::Error::from)
         }
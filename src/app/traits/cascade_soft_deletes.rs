@@ -0,0 +1,115 @@
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::sql_query;
+use diesel::sql_types::{Bpchar, Nullable, Text};
+
+use crate::app::models::DieselUlid;
+
+#[derive(QueryableByName)]
+struct DeleteBatch {
+    #[diesel(sql_type = Nullable<Bpchar>)]
+    delete_batch_id: Option<String>,
+}
+
+/// Models whose children are soft-deleted along with them
+///
+/// Every row soft-deleted by one `cascade_soft_delete` shares a
+/// `delete_batch_id`, so `cascade_restore` brings back only the children that
+/// went with the parent and leaves ones deleted on their own before it alone.
+/// Services call both inside a transaction.
+pub trait CascadeSoftDeletes {
+    /// Table the model is stored in
+    fn soft_delete_table() -> &'static str;
+
+    /// Child relations soft-deleted with the model
+    fn cascade_soft_deletes() -> Vec<&'static str>;
+
+    /// Table and foreign key of the children behind a cascaded relation
+    fn cascaded_children(relation: &str) -> Option<(&'static str, &'static str)>;
+
+    /// Soft-delete the row and its declared children under a new batch id
+    ///
+    /// Returns `None` when the row does not exist or is already deleted,
+    /// otherwise the number of children deleted with it.
+    fn cascade_soft_delete(id: &str, deleted_by: &str, conn: &mut PgConnection) -> QueryResult<Option<usize>> {
+        let batch_id = DieselUlid::new().to_string();
+
+        let deleted = sql_query(format!(
+            "UPDATE {} SET deleted_at = NOW(), deleted_by_id = $1, delete_batch_id = $2 \
+             WHERE id = $3 AND deleted_at IS NULL",
+            Self::soft_delete_table()
+        ))
+            .bind::<Text, _>(deleted_by)
+            .bind::<Text, _>(&batch_id)
+            .bind::<Text, _>(id)
+            .execute(conn)?;
+
+        if deleted == 0 {
+            return Ok(None);
+        }
+
+        let mut children = 0;
+        for relation in Self::cascade_soft_deletes() {
+            if let Some((table, foreign_key)) = Self::cascaded_children(relation) {
+                children += sql_query(format!(
+                    "UPDATE {} SET deleted_at = NOW(), deleted_by_id = $1, delete_batch_id = $2 \
+                     WHERE {} = $3 AND deleted_at IS NULL",
+                    table, foreign_key
+                ))
+                    .bind::<Text, _>(deleted_by)
+                    .bind::<Text, _>(&batch_id)
+                    .bind::<Text, _>(id)
+                    .execute(conn)?;
+            }
+        }
+
+        Ok(Some(children))
+    }
+
+    /// Restore a soft-deleted row, and with `cascade` the children deleted in
+    /// the same batch
+    ///
+    /// Returns `None` when the row is not deleted, otherwise the number of
+    /// children restored.
+    fn cascade_restore(id: &str, restored_by: &str, cascade: bool, conn: &mut PgConnection) -> QueryResult<Option<usize>> {
+        let table = Self::soft_delete_table();
+
+        let batch = sql_query(format!(
+            "SELECT delete_batch_id FROM {} WHERE id = $1 AND deleted_at IS NOT NULL",
+            table
+        ))
+            .bind::<Text, _>(id)
+            .get_result::<DeleteBatch>(conn)
+            .optional()?;
+
+        let Some(batch) = batch else {
+            return Ok(None);
+        };
+
+        let restore = "SET deleted_at = NULL, deleted_by_id = NULL, delete_batch_id = NULL, \
+                       updated_at = NOW(), updated_by_id = $1";
+
+        sql_query(format!("UPDATE {} {} WHERE id = $2", table, restore))
+            .bind::<Text, _>(restored_by)
+            .bind::<Text, _>(id)
+            .execute(conn)?;
+
+        let mut children = 0;
+        if let (true, Some(batch_id)) = (cascade, batch.delete_batch_id) {
+            for relation in Self::cascade_soft_deletes() {
+                if let Some((child_table, foreign_key)) = Self::cascaded_children(relation) {
+                    children += sql_query(format!(
+                        "UPDATE {} {} WHERE {} = $2 AND delete_batch_id = $3",
+                        child_table, restore, foreign_key
+                    ))
+                        .bind::<Text, _>(restored_by)
+                        .bind::<Text, _>(id)
+                        .bind::<Text, _>(&batch_id)
+                        .execute(conn)?;
+                }
+            }
+        }
+
+        Ok(Some(children))
+    }
+}
@@ -1,3 +1,9 @@
 pub mod activity_logger;
+pub mod cascade_soft_deletes;
+pub mod touches;
+pub mod optimistic_locking;
 
-pub use activity_logger::*;
\ No newline at end of file
+pub use activity_logger::*;
+pub use cascade_soft_deletes::CascadeSoftDeletes;
+pub use touches::Touches;
+pub use optimistic_locking::{OptimisticLocking, StaleModelError};
\ No newline at end of file
@@ -0,0 +1,47 @@
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+
+/// Models with a `lock_version` column, like Rails' optimistic locking
+///
+/// Services bump the version on every update and only write the row while
+/// its version still matches the one the client last read. A stale write
+/// fails with `StaleModelError` instead of overwriting the newer edit.
+pub trait OptimisticLocking {
+    /// Version of the row as last read
+    fn lock_version(&self) -> i32;
+
+    /// Strong `ETag` carrying the version, for `If-Match` on the next update
+    fn etag(&self) -> HeaderValue {
+        HeaderValue::from_str(&format!("\"{}\"", self.lock_version()))
+            .expect("a quoted integer is a valid header value")
+    }
+}
+
+/// An update was based on an older version of the row
+#[derive(Debug, thiserror::Error)]
+#[error("{model} was modified by someone else; reload it and try again (current version {current_version})")]
+pub struct StaleModelError {
+    pub model: &'static str,
+    pub current_version: i32,
+}
+
+impl StaleModelError {
+    pub fn new(model: &'static str, current_version: i32) -> Self {
+        Self { model, current_version }
+    }
+
+    /// 409 for a stale write, `None` for any other error
+    pub fn status_code(e: &anyhow::Error) -> Option<StatusCode> {
+        e.downcast_ref::<StaleModelError>().map(|_| StatusCode::CONFLICT)
+    }
+}
+
+/// Version a client sent in `If-Match`, e.g. `"3"` or `W/"3"`
+pub fn if_match_version(headers: &HeaderMap) -> Option<i32> {
+    let value = headers.get(header::IF_MATCH)?.to_str().ok()?.trim();
+    value
+        .strip_prefix("W/")
+        .unwrap_or(value)
+        .trim_matches('"')
+        .parse()
+        .ok()
+}
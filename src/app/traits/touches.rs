@@ -0,0 +1,31 @@
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::sql_query;
+use diesel::sql_types::Text;
+
+/// Models that bump their parents' `updated_at` when written, like Laravel's `$touches`
+///
+/// Services call `touch_parents` inside the transaction that creates, updates
+/// or deletes the model, so the parent's timestamp commits with the change.
+pub trait Touches {
+    /// Parent relations to touch
+    fn touches() -> Vec<&'static str>;
+
+    /// Table and primary key of the parent behind a touched relation
+    fn touched_parent(&self, relation: &str) -> Option<(&'static str, String)>;
+
+    /// Set `updated_at` to the current time on every touched parent
+    fn touch_parents(&self, conn: &mut PgConnection) -> QueryResult<usize> {
+        let mut touched = 0;
+
+        for relation in Self::touches() {
+            if let Some((table, id)) = self.touched_parent(relation) {
+                touched += sql_query(format!("UPDATE {} SET updated_at = NOW() WHERE id = $1", table))
+                    .bind::<Text, _>(id)
+                    .execute(conn)?;
+            }
+        }
+
+        Ok(touched)
+    }
+}
@@ -0,0 +1,75 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::time::Duration;
+
+use crate::cache::{manager::CacheDriver, Cache};
+
+/// Fixed-window rate limiter kept in the cache store
+///
+/// Unlike `RateLimiter`, which counts in process memory, the counters live in
+/// the cache, so every instance sharing a Redis store enforces one limit.
+/// Follows Laravel's `RateLimiter`: `hit` counts an attempt in a window that
+/// starts with the first attempt, and `available_in` is the time until it ends.
+#[derive(Clone)]
+pub struct CacheRateLimiter {
+    cache: CacheDriver,
+}
+
+impl CacheRateLimiter {
+    pub fn new(cache: CacheDriver) -> Self {
+        Self { cache }
+    }
+
+    /// Limiter on the process-wide cache store
+    pub async fn shared() -> Result<Self> {
+        Ok(Self::new(crate::cache::shared_cache().await?))
+    }
+
+    /// Count an attempt, returning the attempts made in the current window
+    pub async fn hit(&self, key: &str, decay: Duration) -> Result<i64> {
+        let available_at = Utc::now().timestamp() + decay.as_secs() as i64;
+        self.cache.add(&Self::timer_key(key), &available_at, Some(decay)).await?;
+
+        let added = self.cache.add(key, &0i64, Some(decay)).await?;
+        let hits = self.cache.increment(key, 1).await?;
+
+        // The counter outlived its timer; start it over with a fresh expiry
+        if !added && hits == 1 {
+            self.cache.put(key, &1i64, Some(decay)).await?;
+        }
+
+        Ok(hits)
+    }
+
+    pub async fn attempts(&self, key: &str) -> Result<i64> {
+        Ok(self.cache.get::<i64>(key).await?.unwrap_or(0))
+    }
+
+    /// Whether `key` has used up `max_attempts` in the current window
+    pub async fn too_many_attempts(&self, key: &str, max_attempts: i64) -> Result<bool> {
+        if self.attempts(key).await? >= max_attempts {
+            if self.cache.has(&Self::timer_key(key)).await? {
+                return Ok(true);
+            }
+            self.clear(key).await?;
+        }
+
+        Ok(false)
+    }
+
+    /// Seconds until the window of `key` ends
+    pub async fn available_in(&self, key: &str) -> Result<u64> {
+        let available_at = self.cache.get::<i64>(&Self::timer_key(key)).await?.unwrap_or(0);
+        Ok((available_at - Utc::now().timestamp()).max(0) as u64)
+    }
+
+    pub async fn clear(&self, key: &str) -> Result<()> {
+        self.cache.forget(key).await?;
+        self.cache.forget(&Self::timer_key(key)).await?;
+        Ok(())
+    }
+
+    fn timer_key(key: &str) -> String {
+        format!("{}:timer", key)
+    }
+}
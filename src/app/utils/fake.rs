@@ -0,0 +1,104 @@
+//! Fake data for seeders and factories
+//!
+//! Thin helpers over the `fake` crate so seeders and test factories don't
+//! each pick their own generators. Emails are unique for the lifetime of the
+//! process, so a single seeding run never trips the unique email constraint.
+
+use ::fake::faker::address::en::{BuildingNumber, CityName, CountryName, StateName, StreetName, ZipCode};
+use ::fake::faker::company::en::CompanyName;
+use ::fake::faker::internet::en::{SafeEmail, Username};
+use ::fake::faker::lorem::en::{Paragraph, Sentence};
+use ::fake::faker::name::en::{FirstName, LastName, Name};
+use ::fake::faker::phone_number::en::CellNumber;
+use ::fake::Fake;
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+
+use crate::app::models::DieselUlid;
+
+static EMAILS: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+/// A postal address with its parts kept apart
+#[derive(Debug, Clone)]
+pub struct FakeAddress {
+    pub street: String,
+    pub city: String,
+    pub state: String,
+    pub postal_code: String,
+    pub country: String,
+}
+
+impl std::fmt::Display for FakeAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, {}, {} {}, {}", self.street, self.city, self.state, self.postal_code, self.country)
+    }
+}
+
+pub fn name() -> String {
+    Name().fake()
+}
+
+pub fn first_name() -> String {
+    FirstName().fake()
+}
+
+pub fn last_name() -> String {
+    LastName().fake()
+}
+
+pub fn username() -> String {
+    Username().fake::<String>().to_lowercase()
+}
+
+/// An email address not handed out before in this process
+pub fn email() -> String {
+    let mut seen = EMAILS.get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    let candidate = SafeEmail().fake::<String>().to_lowercase();
+    if seen.insert(candidate.clone()) {
+        return candidate;
+    }
+
+    // The generator repeats quickly, so number the local part on collision
+    let (local, domain) = candidate.split_once('@').unwrap_or((candidate.as_str(), "example.com"));
+    (1..)
+        .map(|n| format!("{}{}@{}", local, n, domain))
+        .find(|email| seen.insert(email.clone()))
+        .expect("an unused suffix always exists")
+}
+
+pub fn phone_number() -> String {
+    CellNumber().fake()
+}
+
+pub fn company() -> String {
+    CompanyName().fake()
+}
+
+pub fn address() -> FakeAddress {
+    FakeAddress {
+        street: format!("{} {}", BuildingNumber().fake::<String>(), StreetName().fake::<String>()),
+        city: CityName().fake(),
+        state: StateName().fake(),
+        postal_code: ZipCode().fake(),
+        country: CountryName().fake(),
+    }
+}
+
+pub fn country() -> String {
+    CountryName().fake()
+}
+
+pub fn ulid() -> DieselUlid {
+    DieselUlid::new()
+}
+
+pub fn sentence() -> String {
+    Sentence(4..10).fake()
+}
+
+pub fn paragraph() -> String {
+    Paragraph(2..5).fake()
+}
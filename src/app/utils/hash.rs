@@ -0,0 +1,104 @@
+use anyhow::Result;
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Algorithm, Argon2, Params, Version,
+};
+
+use crate::config::hashing::{HashDriver, HashingConfig};
+
+/// Password hashing, like Laravel's `Hash` facade
+///
+/// New hashes use the configured driver. `check` accepts Argon2 and bcrypt
+/// hashes whatever the driver, so stored passwords keep working after the
+/// driver changes, and `needs_rehash` reports hashes that should be
+/// replaced the next time the plain password is available.
+#[derive(Debug, Clone)]
+pub struct Hash {
+    config: HashingConfig,
+}
+
+impl Hash {
+    pub fn new(config: HashingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Hash a value with the configured driver
+    pub fn make(&self, value: &str) -> Result<String> {
+        match self.config.driver {
+            HashDriver::Argon2id => {
+                let salt = SaltString::generate(&mut OsRng);
+                let hash = self.argon2()?
+                    .hash_password(value.as_bytes(), &salt)
+                    .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))?;
+                Ok(hash.to_string())
+            },
+            HashDriver::Bcrypt => Ok(bcrypt::hash(value, self.config.bcrypt_cost)?),
+        }
+    }
+
+    /// Check a value against an Argon2 or bcrypt hash
+    pub fn check(&self, value: &str, hashed: &str) -> Result<bool> {
+        match Self::driver_of(hashed) {
+            Some(HashDriver::Argon2id) => {
+                let parsed = PasswordHash::new(hashed)
+                    .map_err(|e| anyhow::anyhow!("Failed to parse password hash: {}", e))?;
+                // Parameters come from the hash itself, not the configuration
+                Ok(Argon2::default().verify_password(value.as_bytes(), &parsed).is_ok())
+            },
+            Some(HashDriver::Bcrypt) => Ok(bcrypt::verify(value, hashed)?),
+            None => anyhow::bail!("Unsupported password hash format"),
+        }
+    }
+
+    /// Whether a hash was made with another driver or other parameters than configured
+    pub fn needs_rehash(&self, hashed: &str) -> bool {
+        if Self::driver_of(hashed) != Some(self.config.driver) {
+            return true;
+        }
+
+        match self.config.driver {
+            HashDriver::Argon2id => {
+                let Ok(parsed) = PasswordHash::new(hashed) else {
+                    return true;
+                };
+                let Ok(params) = Params::try_from(&parsed) else {
+                    return true;
+                };
+
+                parsed.algorithm != Algorithm::Argon2id.ident()
+                    || parsed.version != Some(Version::V0x13.into())
+                    || params.m_cost() != self.config.argon_memory
+                    || params.t_cost() != self.config.argon_time
+                    || params.p_cost() != self.config.argon_threads
+            },
+            HashDriver::Bcrypt => Self::bcrypt_cost(hashed) != Some(self.config.bcrypt_cost),
+        }
+    }
+
+    /// Algorithm family of a stored hash
+    pub fn driver_of(hashed: &str) -> Option<HashDriver> {
+        if hashed.starts_with("$argon2") {
+            Some(HashDriver::Argon2id)
+        } else if ["$2a$", "$2b$", "$2x$", "$2y$"].iter().any(|prefix| hashed.starts_with(prefix)) {
+            Some(HashDriver::Bcrypt)
+        } else {
+            None
+        }
+    }
+
+    /// Cost factor of a `$2b$<cost>$...` hash
+    fn bcrypt_cost(hashed: &str) -> Option<u32> {
+        hashed.split('$').nth(2)?.parse().ok()
+    }
+
+    fn argon2(&self) -> Result<Argon2<'static>> {
+        let params = Params::new(
+            self.config.argon_memory,
+            self.config.argon_time,
+            self.config.argon_threads,
+            None,
+        ).map_err(|e| anyhow::anyhow!("Invalid Argon2 parameters: {}", e))?;
+
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+}
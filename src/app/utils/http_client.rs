@@ -0,0 +1,137 @@
+//! Shared outbound HTTP client
+//!
+//! Drivers that call third-party APIs (SMS, Slack, web push, webhooks) go
+//! through this client instead of building their own `reqwest::Client`, so
+//! they share one connection pool, the configured timeouts, retry with
+//! backoff for idempotent requests, and the `X-Correlation-ID` of the
+//! request that triggered the call.
+
+use anyhow::Result;
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::{Method, RequestBuilder, Response};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::app::http::middleware::correlation_middleware::{current_correlation_id, CORRELATION_ID_HEADER_LOWERCASE};
+use crate::config::http_client::HttpClientConfig;
+use crate::config::Config;
+
+static SHARED: OnceLock<HttpClient> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    config: HttpClientConfig,
+}
+
+impl HttpClient {
+    pub fn new() -> Result<Self> {
+        Self::with_config(Config::load()?.http_client)
+    }
+
+    pub fn with_config(config: HttpClientConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .pool_idle_timeout(Duration::from_secs(config.pool_idle_timeout_secs))
+            .user_agent(config.user_agent.clone())
+            .build()?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Process-wide client built from the application config
+    pub fn shared() -> Result<&'static HttpClient> {
+        if let Some(client) = SHARED.get() {
+            return Ok(client);
+        }
+
+        let client = Self::new()?;
+        Ok(SHARED.get_or_init(|| client))
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    pub fn put(&self, url: &str) -> RequestBuilder {
+        self.client.put(url)
+    }
+
+    pub fn delete(&self, url: &str) -> RequestBuilder {
+        self.client.delete(url)
+    }
+
+    pub fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        self.client.request(method, url)
+    }
+
+    /// Send a request built from this client
+    ///
+    /// Idempotent requests are retried with exponential backoff when they fail
+    /// with a 5xx or a connection/timeout error. Once retries are exhausted the
+    /// last response is returned as-is, so callers still check the status.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let mut request = request.build()?;
+
+        let header = HeaderName::from_static(CORRELATION_ID_HEADER_LOWERCASE);
+        if !request.headers().contains_key(&header) {
+            if let Some(correlation_id) = current_correlation_id() {
+                if let Ok(value) = HeaderValue::from_str(&correlation_id.to_string()) {
+                    request.headers_mut().insert(header, value);
+                }
+            }
+        }
+
+        let retryable = Self::is_idempotent(request.method());
+        let mut attempt = 0;
+
+        loop {
+            // Streaming bodies can't be cloned, so those requests get a single attempt
+            let retry = if retryable && attempt < self.config.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+            let method = request.method().clone();
+            let url = request.url().clone();
+
+            let result = self.client.execute(request).await;
+            let Some(retry) = retry else {
+                return Ok(result?);
+            };
+
+            match result {
+                Ok(response) if !response.status().is_server_error() => return Ok(response),
+                Ok(response) => {
+                    tracing::warn!("{} {} returned {}; retrying (attempt {})", method, url, response.status(), attempt + 1);
+                },
+                Err(e) if e.is_connect() || e.is_timeout() => {
+                    tracing::warn!("{} {} failed: {}; retrying (attempt {})", method, url, e, attempt + 1);
+                },
+                Err(e) => return Err(e.into()),
+            }
+
+            tokio::time::sleep(self.backoff(attempt)).await;
+            attempt += 1;
+            request = retry;
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self.config.retry_delay_ms.saturating_mul(2u64.saturating_pow(attempt));
+        Duration::from_millis(delay.min(self.config.retry_max_delay_ms))
+    }
+
+    fn is_idempotent(method: &Method) -> bool {
+        matches!(
+            *method,
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS | Method::TRACE
+        )
+    }
+}
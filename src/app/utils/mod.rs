@@ -1,8 +1,20 @@
 pub mod token_utils;
 pub mod vapid;
 pub mod rate_limiter;
+pub mod cache_rate_limiter;
 pub mod web_push_metrics;
+pub mod performance_metrics;
+pub mod http_client;
+pub mod fake;
+pub mod url_signer;
+pub mod hash;
+pub mod timestamp;
 
-pub use vapid::{VapidTokenGenerator, VapidClaims, generate_vapid_keys};
+pub use vapid::{VapidTokenGenerator, VapidKeyRing, VapidClaims, generate_vapid_keys};
 pub use rate_limiter::{RateLimiter, RateLimitError};
+pub use cache_rate_limiter::CacheRateLimiter;
+pub use http_client::HttpClient;
+pub use url_signer::{UrlSigner, SignatureError};
+pub use hash::Hash;
+pub use performance_metrics::{PerformanceMetrics, PerformanceSnapshot};
 pub use web_push_metrics::{WebPushMetrics, WebPushStatsSnapshot, get_metrics, init_metrics};
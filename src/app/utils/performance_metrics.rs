@@ -0,0 +1,51 @@
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+/// Process-wide request and query counters for the debug `/metrics` endpoint
+#[derive(Debug, Default)]
+pub struct PerformanceMetrics {
+    requests: AtomicU64,
+    slow_requests: AtomicU64,
+    queries: AtomicU64,
+    slow_queries: AtomicU64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PerformanceSnapshot {
+    pub requests: u64,
+    pub slow_requests: u64,
+    pub queries: u64,
+    pub slow_queries: u64,
+}
+
+static PERFORMANCE_METRICS: OnceLock<PerformanceMetrics> = OnceLock::new();
+
+impl PerformanceMetrics {
+    pub fn global() -> &'static PerformanceMetrics {
+        PERFORMANCE_METRICS.get_or_init(PerformanceMetrics::default)
+    }
+
+    pub fn record_request(&self, slow: bool) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        if slow {
+            self.slow_requests.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn record_query(&self, slow: bool) {
+        self.queries.fetch_add(1, Ordering::Relaxed);
+        if slow {
+            self.slow_queries.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn snapshot(&self) -> PerformanceSnapshot {
+        PerformanceSnapshot {
+            requests: self.requests.load(Ordering::Relaxed),
+            slow_requests: self.slow_requests.load(Ordering::Relaxed),
+            queries: self.queries.load(Ordering::Relaxed),
+            slow_queries: self.slow_queries.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Serde helpers for `DateTime<Utc>` fields in API payloads
+//!
+//! Use with `#[serde(with = "crate::app::utils::timestamp")]`, or the
+//! `option` module for `Option<DateTime<Utc>>`. Timestamps are always written
+//! as RFC 3339, so models keep one shape in caches, job payloads and stored
+//! JSON. Response types use the `response` module instead, which writes the
+//! format the request asked for. Reading accepts an RFC 3339 string or an
+//! epoch number in seconds or milliseconds.
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::de::{self, Visitor};
+use serde::{Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::config::response::{ResponseConfig, TimestampFormat};
+
+/// Epoch numbers at or above this magnitude are read as milliseconds
+///
+/// As seconds it would be the year 5138; as milliseconds it is March 1973.
+pub const EPOCH_MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+static DEFAULT_TIMESTAMP_FORMAT: OnceLock<TimestampFormat> = OnceLock::new();
+
+tokio::task_local! {
+    /// Timestamp format asked for by the request the current task is serving
+    static REQUESTED_TIMESTAMP_FORMAT: TimestampFormat;
+}
+
+/// Format for timestamps in responses: the request's, else `RESPONSE_TIMESTAMP_FORMAT`
+pub fn response_format() -> TimestampFormat {
+    REQUESTED_TIMESTAMP_FORMAT.try_with(|format| *format).unwrap_or_else(|_| {
+        *DEFAULT_TIMESTAMP_FORMAT.get_or_init(|| {
+            ResponseConfig::from_env().map(|config| config.timestamp_format).unwrap_or_default()
+        })
+    })
+}
+
+/// Run a future with response timestamps written in `format`
+pub async fn with_timestamp_format<F: std::future::Future>(format: TimestampFormat, future: F) -> F::Output {
+    REQUESTED_TIMESTAMP_FORMAT.scope(format, future).await
+}
+
+/// Timestamp from epoch seconds or milliseconds, told apart by magnitude
+pub fn from_epoch(value: i64) -> Option<DateTime<Utc>> {
+    if value.abs() >= EPOCH_MILLIS_THRESHOLD {
+        Utc.timestamp_millis_opt(value).single()
+    } else {
+        Utc.timestamp_opt(value, 0).single()
+    }
+}
+
+/// Timestamp from an RFC 3339 string or a string of epoch digits
+pub fn parse(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .ok()
+        .or_else(|| value.parse::<i64>().ok().and_then(from_epoch))
+}
+
+pub fn serialize<S: Serializer>(timestamp: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+    timestamp.serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+    deserializer.deserialize_any(TimestampVisitor)
+}
+
+/// `Option<DateTime<Utc>>` fields; pair with `default` so omitted fields stay `None`
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(timestamp: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error> {
+        match timestamp {
+            Some(timestamp) => super::serialize(timestamp, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error> {
+        deserializer.deserialize_option(OptionVisitor)
+    }
+
+    struct OptionVisitor;
+
+    impl<'de> Visitor<'de> for OptionVisitor {
+        type Value = Option<DateTime<Utc>>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("null, an RFC 3339 timestamp or epoch seconds or milliseconds")
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+            super::deserialize(deserializer).map(Some)
+        }
+    }
+}
+
+/// `DateTime<Utc>` fields of API response types, written in `response_format`
+pub mod response {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(timestamp: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+        match response_format() {
+            TimestampFormat::Rfc3339 => timestamp.serialize(serializer),
+            TimestampFormat::EpochSeconds => serializer.serialize_i64(timestamp.timestamp()),
+            TimestampFormat::EpochMillis => serializer.serialize_i64(timestamp.timestamp_millis()),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+        super::deserialize(deserializer)
+    }
+
+    /// `Option<DateTime<Utc>>` fields; pair with `default` so omitted fields stay `None`
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(timestamp: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error> {
+            match timestamp {
+                Some(timestamp) => super::serialize(timestamp, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error> {
+            crate::app::utils::timestamp::option::deserialize(deserializer)
+        }
+    }
+}
+
+/// Deserialize a nullable timestamp so `null` becomes `Some(None)`, for merge patches
+pub fn nullable<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Option<DateTime<Utc>>>, D::Error> {
+    option::deserialize(deserializer).map(Some)
+}
+
+struct TimestampVisitor;
+
+impl<'de> Visitor<'de> for TimestampVisitor {
+    type Value = DateTime<Utc>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an RFC 3339 timestamp or epoch seconds or milliseconds")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        parse(value).ok_or_else(|| E::custom(format!("invalid timestamp: {}", value)))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        from_epoch(value).ok_or_else(|| E::custom(format!("timestamp out of range: {}", value)))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        i64::try_from(value)
+            .map_err(|_| E::custom(format!("timestamp out of range: {}", value)))
+            .and_then(|value| self.visit_i64(value))
+    }
+}
+
@@ -2,6 +2,7 @@ use anyhow::Result;
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use jsonwebtoken::{Algorithm, Header, EncodingKey};
 use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// VAPID JWT claims structure
@@ -117,6 +118,70 @@ impl VapidTokenGenerator {
     }
 }
 
+/// VAPID key ring used during key rotation.
+///
+/// Subscriptions created before a rotation are bound to the old public key, so
+/// while the grace period lasts requests may be signed with either key pair.
+#[derive(Debug)]
+pub struct VapidKeyRing {
+    current: VapidTokenGenerator,
+    previous: Option<VapidTokenGenerator>,
+    grace_until: Option<DateTime<Utc>>,
+}
+
+impl VapidKeyRing {
+    /// Create a key ring holding only the current key pair
+    pub fn new(current: VapidTokenGenerator) -> Self {
+        Self {
+            current,
+            previous: None,
+            grace_until: None,
+        }
+    }
+
+    /// Accept a previous key pair until the given instant
+    pub fn with_previous(mut self, previous: VapidTokenGenerator, grace_until: DateTime<Utc>) -> Self {
+        self.previous = Some(previous);
+        self.grace_until = Some(grace_until);
+        self
+    }
+
+    /// The key pair new subscriptions should use
+    pub fn current(&self) -> &VapidTokenGenerator {
+        &self.current
+    }
+
+    /// The previous key pair, if the rotation grace period has not elapsed
+    pub fn previous(&self) -> Option<&VapidTokenGenerator> {
+        self.previous_at(Utc::now())
+    }
+
+    fn previous_at(&self, now: DateTime<Utc>) -> Option<&VapidTokenGenerator> {
+        match (&self.previous, self.grace_until) {
+            (Some(previous), Some(until)) if now < until => Some(previous),
+            _ => None,
+        }
+    }
+
+    /// All key pairs currently accepted, current key first
+    pub fn active_generators(&self) -> Vec<&VapidTokenGenerator> {
+        let mut generators = vec![&self.current];
+        if let Some(previous) = self.previous() {
+            generators.push(previous);
+        }
+        generators
+    }
+
+    /// Validate every key pair in the ring
+    pub fn validate_keys(&self) -> Result<()> {
+        self.current.validate_keys()?;
+        if let Some(previous) = &self.previous {
+            previous.validate_keys()?;
+        }
+        Ok(())
+    }
+}
+
 /// Generate new VAPID key pair for development/testing
 pub fn generate_vapid_keys() -> Result<(String, String)> {
     use p256::{SecretKey, ecdsa::SigningKey};
@@ -200,4 +265,27 @@ mod tests {
         // Keys should be different
         assert_ne!(private_key, public_key);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_key_ring_grace_period() {
+        let generator = |name: &str| VapidTokenGenerator::new(
+            format!("{}_private", name),
+            format!("{}_public", name),
+            "mailto:test@example.com".to_string(),
+        );
+
+        let now = Utc::now();
+        let ring = VapidKeyRing::new(generator("new"))
+            .with_previous(generator("old"), now + chrono::Duration::hours(1));
+
+        assert_eq!(ring.current().get_public_key(), "new_public");
+        assert_eq!(ring.active_generators().len(), 2);
+        assert_eq!(ring.previous_at(now).unwrap().get_public_key(), "old_public");
+
+        // Previous key is dropped once the grace period ends
+        assert!(ring.previous_at(now + chrono::Duration::hours(2)).is_none());
+
+        let ring = VapidKeyRing::new(generator("new"));
+        assert_eq!(ring.active_generators().len(), 1);
+    }
+}
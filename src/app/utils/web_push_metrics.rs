@@ -16,6 +16,7 @@ struct MetricsData {
     total_notifications_failed: u64,
     total_subscriptions_created: u64,
     total_subscriptions_deleted: u64,
+    total_subscriptions_pruned: u64,
     response_times: Vec<Duration>,
     error_counts: HashMap<String, u64>,
     status_code_counts: HashMap<u16, u64>,
@@ -30,6 +31,7 @@ pub struct DailyStats {
     pub notifications_failed: u64,
     pub subscriptions_created: u64,
     pub subscriptions_deleted: u64,
+    pub subscriptions_pruned: u64,
     pub average_response_time_ms: f64,
     pub success_rate: f64,
 }
@@ -48,6 +50,7 @@ pub struct WebPushStatsSnapshot {
     pub total_notifications_failed: u64,
     pub total_subscriptions_created: u64,
     pub total_subscriptions_deleted: u64,
+    pub total_subscriptions_pruned: u64,
     pub success_rate: f64,
     pub average_response_time_ms: f64,
     pub last_24h_notifications: u64,
@@ -119,6 +122,17 @@ impl WebPushMetrics {
         daily_stats.subscriptions_deleted += 1;
     }
 
+    /// Record a subscription removed because the push service reported it gone
+    pub async fn record_subscription_pruned(&self) {
+        let mut data = self.data.write().await;
+        data.total_subscriptions_pruned += 1;
+
+        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        let daily_stats = data.daily_stats.entry(today.clone()).or_default();
+        daily_stats.date = today;
+        daily_stats.subscriptions_pruned += 1;
+    }
+
     /// Get current metrics snapshot
     pub async fn get_snapshot(&self) -> WebPushStatsSnapshot {
         let data = self.data.read().await;
@@ -166,6 +180,7 @@ impl WebPushMetrics {
             total_notifications_failed: data.total_notifications_failed,
             total_subscriptions_created: data.total_subscriptions_created,
             total_subscriptions_deleted: data.total_subscriptions_deleted,
+            total_subscriptions_pruned: data.total_subscriptions_pruned,
             success_rate,
             average_response_time_ms,
             last_24h_notifications,
@@ -304,6 +319,10 @@ pub async fn record_subscription_deleted() {
     get_metrics().record_subscription_deleted().await;
 }
 
+pub async fn record_subscription_pruned() {
+    get_metrics().record_subscription_pruned().await;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -344,6 +363,19 @@ mod tests {
         assert_eq!(snapshot.total_subscriptions_deleted, 1);
     }
 
+    #[tokio::test]
+    async fn test_subscription_pruning() {
+        let metrics = WebPushMetrics::new();
+
+        metrics.record_subscription_pruned().await;
+
+        let snapshot = metrics.get_snapshot().await;
+        assert_eq!(snapshot.total_subscriptions_pruned, 1);
+
+        let daily_stats = metrics.get_daily_stats(1).await;
+        assert_eq!(daily_stats[0].subscriptions_pruned, 1);
+    }
+
     #[tokio::test]
     async fn test_daily_stats() {
         let metrics = WebPushMetrics::new();
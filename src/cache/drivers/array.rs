@@ -0,0 +1,265 @@
+use crate::cache::{Cache, CacheError};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Clone)]
+enum ArrayValue {
+    Value(String),
+    Set(HashSet<String>),
+}
+
+#[derive(Clone)]
+struct ArrayEntry {
+    value: ArrayValue,
+    expires_at: Option<Instant>,
+}
+
+impl ArrayEntry {
+    fn new(value: ArrayValue, ttl: Option<Duration>) -> Self {
+        Self { value, expires_at: ttl.map(|duration| Instant::now() + duration) }
+    }
+
+    fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| Instant::now() > expires_at)
+    }
+}
+
+/// Cache store that keeps entries in a plain map, like Laravel's `array` store
+///
+/// Meant as a test double: nothing outlives the value, clones share the same
+/// entries, and `keys` and `expires_in` let tests look at what was stored and
+/// for how long.
+#[derive(Clone, Default)]
+pub struct ArrayCache {
+    entries: Arc<Mutex<HashMap<String, ArrayEntry>>>,
+}
+
+impl ArrayCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every key holding a live entry, sorted
+    pub fn keys(&self) -> Vec<String> {
+        let entries = self.entries.lock().unwrap();
+        let mut keys: Vec<String> = entries
+            .iter()
+            .filter(|(_, entry)| !entry.is_expired())
+            .map(|(key, _)| key.clone())
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// Time left before `key` expires, or `None` when it is missing or kept forever
+    pub fn expires_in(&self, key: &str) -> Option<Duration> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(key)
+            .filter(|entry| !entry.is_expired())
+            .and_then(|entry| entry.expires_at)
+            .map(|expires_at| expires_at.saturating_duration_since(Instant::now()))
+    }
+
+    fn live<'a>(entries: &'a HashMap<String, ArrayEntry>, key: &str) -> Option<&'a ArrayEntry> {
+        entries.get(key).filter(|entry| !entry.is_expired())
+    }
+
+    fn read<T: for<'de> Deserialize<'de>>(entry: &ArrayEntry, key: &str) -> Result<T> {
+        match &entry.value {
+            ArrayValue::Value(value) => serde_json::from_str(value).map_err(|e| CacheError::Deserialization {
+                message: e.to_string(),
+            }.into()),
+            ArrayValue::Set(_) => Err(CacheError::Operation {
+                message: format!("Key '{}' holds a set", key),
+            }.into()),
+        }
+    }
+
+    fn write<T: Serialize>(value: &T) -> Result<ArrayValue> {
+        Ok(ArrayValue::Value(serde_json::to_string(value)?))
+    }
+}
+
+#[async_trait]
+impl Cache for ArrayCache {
+    async fn get<T>(&self, key: &str) -> Result<Option<T>>
+    where
+        T: for<'de> Deserialize<'de> + Send,
+    {
+        let entries = self.entries.lock().unwrap();
+        Self::live(&entries, key).map(|entry| Self::read(entry, key)).transpose()
+    }
+
+    async fn put<T>(&self, key: &str, value: &T, ttl: Option<Duration>) -> Result<()>
+    where
+        T: Serialize + Send + Sync,
+    {
+        let entry = ArrayEntry::new(Self::write(value)?, ttl);
+        self.entries.lock().unwrap().insert(key.to_string(), entry);
+        Ok(())
+    }
+
+    async fn forever<T>(&self, key: &str, value: &T) -> Result<()>
+    where
+        T: Serialize + Send + Sync,
+    {
+        self.put(key, value, None).await
+    }
+
+    async fn remember<T, F, Fut>(&self, key: &str, ttl: Option<Duration>, callback: F) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de> + Serialize + Send + Sync,
+        F: FnOnce() -> Fut + Send,
+        Fut: std::future::Future<Output = Result<T>> + Send,
+    {
+        if let Some(value) = self.get(key).await? {
+            return Ok(value);
+        }
+
+        let value = callback().await?;
+        self.put(key, &value, ttl).await?;
+        Ok(value)
+    }
+
+    async fn remember_forever<T, F, Fut>(&self, key: &str, callback: F) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de> + Serialize + Send + Sync,
+        F: FnOnce() -> Fut + Send,
+        Fut: std::future::Future<Output = Result<T>> + Send,
+    {
+        self.remember(key, None, callback).await
+    }
+
+    async fn has(&self, key: &str) -> Result<bool> {
+        Ok(Self::live(&self.entries.lock().unwrap(), key).is_some())
+    }
+
+    async fn forget(&self, key: &str) -> Result<bool> {
+        let removed = self.entries.lock().unwrap().remove(key);
+        Ok(removed.is_some_and(|entry| !entry.is_expired()))
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.entries.lock().unwrap().clear();
+        Ok(())
+    }
+
+    async fn many<T>(&self, keys: &[&str]) -> Result<Vec<(String, Option<T>)>>
+    where
+        T: for<'de> Deserialize<'de> + Send,
+    {
+        let entries = self.entries.lock().unwrap();
+        keys.iter()
+            .map(|key| {
+                let value = Self::live(&entries, key).map(|entry| Self::read(entry, key)).transpose()?;
+                Ok((key.to_string(), value))
+            })
+            .collect()
+    }
+
+    async fn put_many<T>(&self, values: &[(&str, &T)], ttl: Option<Duration>) -> Result<()>
+    where
+        T: Serialize + Send + Sync,
+    {
+        let mut entries = self.entries.lock().unwrap();
+        for (key, value) in values {
+            entries.insert(key.to_string(), ArrayEntry::new(Self::write(value)?, ttl));
+        }
+        Ok(())
+    }
+
+    async fn increment(&self, key: &str, value: i64) -> Result<i64> {
+        let mut entries = self.entries.lock().unwrap();
+
+        let (current, expires_at) = match Self::live(&entries, key) {
+            Some(entry) => (Self::read::<i64>(entry, key)?, entry.expires_at),
+            None => (0, None),
+        };
+        let new_value = current.checked_add(value).ok_or_else(|| CacheError::Operation {
+            message: format!("Incrementing key '{}' would overflow", key),
+        })?;
+
+        entries.insert(key.to_string(), ArrayEntry { value: Self::write(&new_value)?, expires_at });
+        Ok(new_value)
+    }
+
+    async fn decrement(&self, key: &str, value: i64) -> Result<i64> {
+        let value = value.checked_neg().ok_or_else(|| CacheError::Operation {
+            message: format!("Cannot decrement key '{}' by {}", key, value),
+        })?;
+        self.increment(key, value).await
+    }
+
+    async fn add<T>(&self, key: &str, value: &T, ttl: Option<Duration>) -> Result<bool>
+    where
+        T: Serialize + Send + Sync,
+    {
+        let mut entries = self.entries.lock().unwrap();
+        if Self::live(&entries, key).is_some() {
+            return Ok(false);
+        }
+
+        entries.insert(key.to_string(), ArrayEntry::new(Self::write(value)?, ttl));
+        Ok(true)
+    }
+
+    async fn set_add(&self, key: &str, members: &[&str], ttl: Option<Duration>) -> Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        let members = members.iter().map(|member| member.to_string());
+
+        match entries.get_mut(key).filter(|entry| !entry.is_expired()) {
+            Some(ArrayEntry { value: ArrayValue::Set(set), expires_at }) => {
+                set.extend(members);
+                *expires_at = match (*expires_at, ttl) {
+                    (Some(current), Some(duration)) => Some(current.max(Instant::now() + duration)),
+                    _ => None,
+                };
+            },
+            Some(_) => {
+                return Err(CacheError::Operation {
+                    message: format!("Key '{}' does not hold a set", key),
+                }.into());
+            },
+            None => {
+                entries.insert(key.to_string(), ArrayEntry::new(ArrayValue::Set(members.collect()), ttl));
+            },
+        }
+        Ok(())
+    }
+
+    async fn set_remove(&self, key: &str, members: &[&str]) -> Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+
+        if let Some(ArrayEntry { value: ArrayValue::Set(set), .. }) = entries.get_mut(key) {
+            for member in members {
+                set.remove(*member);
+            }
+            if set.is_empty() {
+                entries.remove(key);
+            }
+        }
+        Ok(())
+    }
+
+    async fn set_members(&self, key: &str) -> Result<Vec<String>> {
+        let entries = self.entries.lock().unwrap();
+
+        match Self::live(&entries, key) {
+            Some(ArrayEntry { value: ArrayValue::Set(set), .. }) => Ok(set.iter().cloned().collect()),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "array"
+    }
+
+    fn prefix(&self) -> Option<&str> {
+        None
+    }
+}
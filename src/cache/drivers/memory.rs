@@ -2,7 +2,7 @@ use crate::cache::{Cache, CacheError};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
@@ -26,9 +26,32 @@ impl CacheEntry {
     }
 }
 
+#[derive(Clone, Default)]
+struct SetEntry {
+    members: HashSet<String>,
+    expires_at: Option<Instant>,
+}
+
+impl SetEntry {
+    fn is_expired(&self) -> bool {
+        self.expires_at
+            .map(|expires_at| Instant::now() > expires_at)
+            .unwrap_or(false)
+    }
+
+    /// Keep the set until the later of its expiry and `ttl` from now
+    fn extend(&mut self, ttl: Option<Duration>) {
+        self.expires_at = match (self.expires_at, ttl) {
+            (Some(expires_at), Some(duration)) => Some(expires_at.max(Instant::now() + duration)),
+            _ => None,
+        };
+    }
+}
+
 #[derive(Clone)]
 pub struct MemoryCache {
     store: Arc<RwLock<HashMap<String, CacheEntry>>>,
+    sets: Arc<RwLock<HashMap<String, SetEntry>>>,
     prefix: Option<String>,
     name: String,
 }
@@ -37,6 +60,7 @@ impl MemoryCache {
     pub fn new(prefix: Option<String>) -> Self {
         Self {
             store: Arc::new(RwLock::new(HashMap::new())),
+            sets: Arc::new(RwLock::new(HashMap::new())),
             prefix,
             name: "memory".to_string(),
         }
@@ -69,6 +93,10 @@ impl MemoryCache {
     async fn cleanup_expired(&self) {
         let mut store = self.store.write().await;
         store.retain(|_, entry| !entry.is_expired());
+        drop(store);
+
+        let mut sets = self.sets.write().await;
+        sets.retain(|_, entry| !entry.is_expired());
     }
 }
 
@@ -137,31 +165,34 @@ impl Cache for MemoryCache {
 
     async fn has(&self, key: &str) -> Result<bool> {
         let cache_key = self.build_key(key);
-        let store = self.store.read().await;
-
-        match store.get(&cache_key) {
-            Some(entry) => Ok(!entry.is_expired()),
-            None => Ok(false),
+        if let Some(entry) = self.store.read().await.get(&cache_key) {
+            return Ok(!entry.is_expired());
         }
+
+        let sets = self.sets.read().await;
+        Ok(sets.get(&cache_key).is_some_and(|entry| !entry.is_expired()))
     }
 
     async fn forget(&self, key: &str) -> Result<bool> {
         let cache_key = self.build_key(key);
-        let mut store = self.store.write().await;
+        let removed = self.store.write().await.remove(&cache_key).is_some();
 
-        Ok(store.remove(&cache_key).is_some())
+        Ok(self.sets.write().await.remove(&cache_key).is_some() || removed)
     }
 
     async fn flush(&self) -> Result<()> {
         let mut store = self.store.write().await;
+        let mut sets = self.sets.write().await;
 
         if let Some(prefix) = &self.prefix {
             // Remove only keys with the prefix
             let prefix_pattern = format!("{}:", prefix);
             store.retain(|key, _| !key.starts_with(&prefix_pattern));
+            sets.retain(|key, _| !key.starts_with(&prefix_pattern));
         } else {
             // Clear everything
             store.clear();
+            sets.clear();
         }
 
         Ok(())
@@ -198,29 +229,36 @@ impl Cache for MemoryCache {
         Ok(())
     }
 
+    /// Increment under the store's write lock, so concurrent calls never lose an update
     async fn increment(&self, key: &str, value: i64) -> Result<i64> {
         let cache_key = self.build_key(key);
         let mut store = self.store.write().await;
 
-        let new_value = match store.get(&cache_key) {
+        let (new_value, expires_at) = match store.get(&cache_key) {
             Some(entry) if !entry.is_expired() => {
                 // Try to parse the existing value as i64
                 let current: i64 = entry.value.parse().map_err(|_| CacheError::Operation {
                     message: format!("Cannot increment non-numeric value for key '{}'", cache_key),
                 })?;
-                current + value
+                let new_value = current.checked_add(value).ok_or_else(|| CacheError::Operation {
+                    message: format!("Incrementing key '{}' would overflow", cache_key),
+                })?;
+                // Keep the existing expiry, like Redis INCR, so counters still expire
+                (new_value, entry.expires_at)
             }
-            _ => value, // Key doesn't exist or is expired, start with the increment value
+            _ => (value, None), // Key doesn't exist or is expired, start with the increment value
         };
 
-        let entry = CacheEntry::new(new_value.to_string(), None);
-        store.insert(cache_key, entry);
+        store.insert(cache_key, CacheEntry { value: new_value.to_string(), expires_at });
 
         Ok(new_value)
     }
 
     async fn decrement(&self, key: &str, value: i64) -> Result<i64> {
-        self.increment(key, -value).await
+        let value = value.checked_neg().ok_or_else(|| CacheError::Operation {
+            message: format!("Cannot decrement key '{}' by {}", key, value),
+        })?;
+        self.increment(key, value).await
     }
 
     async fn add<T>(&self, key: &str, value: &T, ttl: Option<Duration>) -> Result<bool>
@@ -243,6 +281,51 @@ impl Cache for MemoryCache {
         }
     }
 
+    async fn set_add(&self, key: &str, members: &[&str], ttl: Option<Duration>) -> Result<()> {
+        let cache_key = self.build_key(key);
+        let mut sets = self.sets.write().await;
+
+        let entry = sets.entry(cache_key).or_default();
+        if entry.is_expired() || entry.members.is_empty() {
+            *entry = SetEntry {
+                members: HashSet::new(),
+                expires_at: ttl.map(|duration| Instant::now() + duration),
+            };
+        } else {
+            entry.extend(ttl);
+        }
+        entry.members.extend(members.iter().map(|member| member.to_string()));
+
+        Ok(())
+    }
+
+    async fn set_remove(&self, key: &str, members: &[&str]) -> Result<()> {
+        let cache_key = self.build_key(key);
+        let mut sets = self.sets.write().await;
+
+        if let Some(entry) = sets.get_mut(&cache_key) {
+            for member in members {
+                entry.members.remove(*member);
+            }
+            // Like Redis, a set with no members left is gone
+            if entry.members.is_empty() {
+                sets.remove(&cache_key);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn set_members(&self, key: &str) -> Result<Vec<String>> {
+        let cache_key = self.build_key(key);
+        let sets = self.sets.read().await;
+
+        match sets.get(&cache_key) {
+            Some(entry) if !entry.is_expired() => Ok(entry.members.iter().cloned().collect()),
+            _ => Ok(Vec::new()),
+        }
+    }
+
     fn name(&self) -> &str {
         &self.name
     }
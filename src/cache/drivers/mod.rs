@@ -1,5 +1,7 @@
+pub mod array;
 pub mod memory;
 pub mod redis;
 
+pub use array::ArrayCache;
 pub use memory::MemoryCache;
 pub use redis::RedisCache;
\ No newline at end of file
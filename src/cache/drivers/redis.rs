@@ -6,6 +6,26 @@ use redis::aio::ConnectionManager;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+/// Adds members to a set and keeps it for the longest TTL it has been given
+///
+/// KEYS: set. ARGV: ttl milliseconds (-1 for none), members.
+const SET_ADD_SCRIPT: &str = r#"
+local current = redis.call('PTTL', KEYS[1])
+redis.call('SADD', KEYS[1], unpack(ARGV, 2))
+local ttl = tonumber(ARGV[1])
+if ttl < 0 then
+    redis.call('PERSIST', KEYS[1])
+elseif current ~= -1 and ttl > current then
+    redis.call('PEXPIRE', KEYS[1], ttl)
+end
+return 1
+"#;
+
+/// Cache store backed by Redis
+///
+/// Every call shares one multiplexed `ConnectionManager` connection, which
+/// reconnects on its own, so no connection is opened per call. Batch reads and
+/// writes go out in a single round trip.
 pub struct RedisCache {
     connection: ConnectionManager,
     prefix: Option<String>,
@@ -44,6 +64,11 @@ impl RedisCache {
         }
     }
 
+    /// A TTL in milliseconds, at least one, since Redis rejects an expiry of zero
+    fn ttl_millis(duration: Duration) -> u64 {
+        (duration.as_millis() as u64).max(1)
+    }
+
     async fn serialize_value<T: Serialize>(&self, value: &T) -> Result<String> {
         serde_json::to_string(value).map_err(|e| CacheError::Serialization {
             message: e.to_string(),
@@ -89,7 +114,7 @@ impl Cache for RedisCache {
 
         match ttl {
             Some(duration) => {
-                conn.set_ex::<_, _, ()>(&cache_key, &serialized, duration.as_secs()).await
+                conn.pset_ex::<_, _, ()>(&cache_key, &serialized, Self::ttl_millis(duration)).await
             }
             None => conn.set::<_, _, ()>(&cache_key, &serialized).await,
         }
@@ -178,43 +203,69 @@ impl Cache for RedisCache {
         Ok(())
     }
 
+    /// One `MGET` for every key
     async fn many<T>(&self, keys: &[&str]) -> Result<Vec<(String, Option<T>)>>
     where
         T: for<'de> Deserialize<'de> + Send,
     {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
         let cache_keys: Vec<String> = keys.iter().map(|k| self.build_key(k)).collect();
         let mut conn = self.connection.clone();
 
-        let values: Vec<Option<String>> = conn.mget(&cache_keys).await.map_err(|e| CacheError::Operation {
-            message: format!("Failed to get multiple keys: {}", e),
-        })?;
+        // Always MGET, which replies with a list even for a single key
+        let values: Vec<Option<String>> = redis::cmd("MGET")
+            .arg(&cache_keys)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| CacheError::Operation {
+                message: format!("Failed to get multiple keys: {}", e),
+            })?;
 
         let mut results = Vec::new();
-        for (i, value) in values.into_iter().enumerate() {
-            let key = keys[i].to_string();
+        for (key, value) in keys.iter().zip(values) {
             match value {
                 Some(json_str) => {
                     let deserialized = self.deserialize_value(&json_str).await?;
-                    results.push((key, Some(deserialized)));
+                    results.push((key.to_string(), Some(deserialized)));
                 }
-                None => results.push((key, None)),
+                None => results.push((key.to_string(), None)),
             }
         }
 
         Ok(results)
     }
 
+    /// One pipeline of `SET`s or `PSETEX`s, since `MSET` cannot give the keys a TTL
     async fn put_many<T>(&self, values: &[(&str, &T)], ttl: Option<Duration>) -> Result<()>
     where
         T: Serialize + Send + Sync,
     {
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let mut pipe = redis::pipe();
         for (key, value) in values {
-            self.put(key, value, ttl).await?;
+            let cache_key = self.build_key(key);
+            let serialized = self.serialize_value(value).await?;
+            match ttl {
+                Some(duration) => pipe.pset_ex(cache_key, serialized, Self::ttl_millis(duration)).ignore(),
+                None => pipe.set(cache_key, serialized).ignore(),
+            };
         }
 
+        let mut conn = self.connection.clone();
+        let (): () = pipe.query_async(&mut conn).await.map_err(|e| CacheError::Operation {
+            message: format!("Failed to set multiple keys: {}", e),
+        })?;
+
         Ok(())
     }
 
+    /// Atomic `INCRBY`, which starts a missing key at zero
     async fn increment(&self, key: &str, value: i64) -> Result<i64> {
         let cache_key = self.build_key(key);
         let mut conn = self.connection.clone();
@@ -226,8 +277,16 @@ impl Cache for RedisCache {
         Ok(result)
     }
 
+    /// Atomic `DECRBY`, which starts a missing key at zero
     async fn decrement(&self, key: &str, value: i64) -> Result<i64> {
-        self.increment(key, -value).await
+        let cache_key = self.build_key(key);
+        let mut conn = self.connection.clone();
+
+        let result: i64 = conn.decr(&cache_key, value).await.map_err(|e| CacheError::Operation {
+            message: format!("Failed to decrement key '{}': {}", cache_key, e),
+        })?;
+
+        Ok(result)
     }
 
     async fn add<T>(&self, key: &str, value: &T, ttl: Option<Duration>) -> Result<bool>
@@ -240,12 +299,12 @@ impl Cache for RedisCache {
 
         let result: bool = match ttl {
             Some(duration) => {
-                // Use SET with NX (only if not exists) and EX (expiration)
+                // Use SET with NX (only if not exists) and PX (expiration)
                 let result: Option<String> = conn
                     .set_options(&cache_key, &serialized, redis::SetOptions::default()
                         .conditional_set(redis::ExistenceCheck::NX)
                         .get(true)
-                        .with_expiration(redis::SetExpiry::EX(duration.as_secs())))
+                        .with_expiration(redis::SetExpiry::PX(Self::ttl_millis(duration))))
                     .await
                     .map_err(|e| CacheError::Operation {
                         message: format!("Failed to add key '{}': {}", cache_key, e),
@@ -269,6 +328,55 @@ impl Cache for RedisCache {
         Ok(result)
     }
 
+    /// `SADD` and the TTL update run as one script
+    async fn set_add(&self, key: &str, members: &[&str], ttl: Option<Duration>) -> Result<()> {
+        if members.is_empty() {
+            return Ok(());
+        }
+
+        let cache_key = self.build_key(key);
+        let ttl = ttl.map(|duration| Self::ttl_millis(duration) as i64).unwrap_or(-1);
+        let mut conn = self.connection.clone();
+
+        let _: i64 = redis::Script::new(SET_ADD_SCRIPT)
+            .key(&cache_key)
+            .arg(ttl)
+            .arg(members)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| CacheError::Operation {
+                message: format!("Failed to add to set '{}': {}", cache_key, e),
+            })?;
+
+        Ok(())
+    }
+
+    async fn set_remove(&self, key: &str, members: &[&str]) -> Result<()> {
+        if members.is_empty() {
+            return Ok(());
+        }
+
+        let cache_key = self.build_key(key);
+        let mut conn = self.connection.clone();
+
+        conn.srem::<_, _, ()>(&cache_key, members).await.map_err(|e| CacheError::Operation {
+            message: format!("Failed to remove from set '{}': {}", cache_key, e),
+        })?;
+
+        Ok(())
+    }
+
+    async fn set_members(&self, key: &str) -> Result<Vec<String>> {
+        let cache_key = self.build_key(key);
+        let mut conn = self.connection.clone();
+
+        let members: Vec<String> = conn.smembers(&cache_key).await.map_err(|e| CacheError::Operation {
+            message: format!("Failed to read set '{}': {}", cache_key, e),
+        })?;
+
+        Ok(members)
+    }
+
     fn name(&self) -> &str {
         &self.name
     }
@@ -0,0 +1,39 @@
+//! In-process locks keyed by cache key
+//!
+//! `CacheDriver::remember` takes the lock for its key before running the
+//! callback on a miss, so concurrent misses wait for the first caller's value
+//! instead of all computing it. A lock only lives while some task holds or
+//! waits on it.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+#[derive(Default)]
+pub struct KeyedLocks {
+    locks: Mutex<HashMap<String, Weak<AsyncMutex<()>>>>,
+}
+
+impl KeyedLocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait for the lock on `key`, held until the guard is dropped
+    pub async fn lock(&self, key: &str) -> OwnedMutexGuard<()> {
+        self.lock_for(key).lock_owned().await
+    }
+
+    fn lock_for(&self, key: &str) -> Arc<AsyncMutex<()>> {
+        let mut locks = self.locks.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(lock) = locks.get(key).and_then(Weak::upgrade) {
+            return lock;
+        }
+
+        // Drop locks nobody holds any more before adding a new one
+        locks.retain(|_, lock| lock.strong_count() > 0);
+        let lock = Arc::new(AsyncMutex::new(()));
+        locks.insert(key.to_string(), Arc::downgrade(&lock));
+        lock
+    }
+}
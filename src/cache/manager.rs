@@ -1,13 +1,20 @@
 use crate::cache::drivers::{MemoryCache, RedisCache};
+use crate::cache::keyed_lock::KeyedLocks;
 use crate::cache::{Cache, CacheError};
 use crate::config::{cache::CacheConfig, Config};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
+/// Locks taken by `CacheDriver::remember` on a miss
+fn remember_locks() -> &'static KeyedLocks {
+    static LOCKS: OnceLock<KeyedLocks> = OnceLock::new();
+    LOCKS.get_or_init(KeyedLocks::new)
+}
+
 #[derive(Clone)]
 pub enum CacheDriver {
     Memory(Arc<MemoryCache>),
@@ -46,16 +53,33 @@ impl Cache for CacheDriver {
         }
     }
 
+    /// Get a value, or compute and store it once however many tasks miss together
+    ///
+    /// On a miss the caller takes an in-process lock for the key and checks the
+    /// cache again before running the callback, so tasks that miss while the
+    /// value is being computed wait and then read it instead of computing it
+    /// too. If the callback fails, the next waiter runs its own. The lock is
+    /// per process: instances sharing a Redis store may each compute the value
+    /// once.
     async fn remember<T, F, Fut>(&self, key: &str, ttl: Option<Duration>, callback: F) -> Result<T>
     where
         T: for<'de> Deserialize<'de> + Serialize + Send + Sync,
         F: FnOnce() -> Fut + Send,
         Fut: std::future::Future<Output = Result<T>> + Send,
     {
-        match self {
-            CacheDriver::Memory(cache) => cache.remember(key, ttl, callback).await,
-            CacheDriver::Redis(cache) => cache.remember(key, ttl, callback).await,
+        if let Some(value) = self.get(key).await? {
+            return Ok(value);
         }
+
+        let lock_key = format!("{}:{}:{}", self.name(), self.prefix().unwrap_or_default(), key);
+        let _guard = remember_locks().lock(&lock_key).await;
+        if let Some(value) = self.get(key).await? {
+            return Ok(value);
+        }
+
+        let value = callback().await?;
+        self.put(key, &value, ttl).await?;
+        Ok(value)
     }
 
     async fn remember_forever<T, F, Fut>(&self, key: &str, callback: F) -> Result<T>
@@ -64,10 +88,7 @@ impl Cache for CacheDriver {
         F: FnOnce() -> Fut + Send,
         Fut: std::future::Future<Output = Result<T>> + Send,
     {
-        match self {
-            CacheDriver::Memory(cache) => cache.remember_forever(key, callback).await,
-            CacheDriver::Redis(cache) => cache.remember_forever(key, callback).await,
-        }
+        self.remember(key, None, callback).await
     }
 
     async fn has(&self, key: &str) -> Result<bool> {
@@ -135,6 +156,27 @@ impl Cache for CacheDriver {
         }
     }
 
+    async fn set_add(&self, key: &str, members: &[&str], ttl: Option<Duration>) -> Result<()> {
+        match self {
+            CacheDriver::Memory(cache) => cache.set_add(key, members, ttl).await,
+            CacheDriver::Redis(cache) => cache.set_add(key, members, ttl).await,
+        }
+    }
+
+    async fn set_remove(&self, key: &str, members: &[&str]) -> Result<()> {
+        match self {
+            CacheDriver::Memory(cache) => cache.set_remove(key, members).await,
+            CacheDriver::Redis(cache) => cache.set_remove(key, members).await,
+        }
+    }
+
+    async fn set_members(&self, key: &str) -> Result<Vec<String>> {
+        match self {
+            CacheDriver::Memory(cache) => cache.set_members(key).await,
+            CacheDriver::Redis(cache) => cache.set_members(key).await,
+        }
+    }
+
     fn name(&self) -> &str {
         match self {
             CacheDriver::Memory(cache) => cache.name(),
@@ -265,6 +307,14 @@ pub async fn default_cache() -> Result<CacheDriver> {
     cache("default").await
 }
 
+/// Default store shared by the whole process, so the memory driver sees the
+/// same entries on every call
+static SHARED_STORE: tokio::sync::OnceCell<CacheDriver> = tokio::sync::OnceCell::const_new();
+
+pub async fn shared_cache() -> Result<CacheDriver> {
+    SHARED_STORE.get_or_try_init(default_cache).await.cloned()
+}
+
 // Facade-like interface for easy access
 pub struct CacheFacade;
 
@@ -4,9 +4,14 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 pub mod drivers;
+pub mod keyed_lock;
 pub mod manager;
+pub mod tagged;
+pub mod warmer;
 
-pub use manager::{CacheManager, cache, default_cache};
+pub use manager::{CacheManager, cache, default_cache, shared_cache};
+pub use tagged::{TaggableCache, TaggedCache};
+pub use warmer::{CacheWarmer, CacheWarmerRegistry, WarmResult, WarmStatus};
 
 #[async_trait]
 pub trait Cache: Send + Sync {
@@ -69,6 +74,18 @@ pub trait Cache: Send + Sync {
     where
         T: Serialize + Send + Sync;
 
+    /// Add members to the set stored at `key`
+    ///
+    /// The set is kept for at least `ttl`: a longer TTL than it already has
+    /// extends it, a shorter one leaves it alone, and `None` keeps it forever.
+    async fn set_add(&self, key: &str, members: &[&str], ttl: Option<Duration>) -> Result<()>;
+
+    /// Remove members from the set stored at `key`
+    async fn set_remove(&self, key: &str, members: &[&str]) -> Result<()>;
+
+    /// Every member of the set stored at `key`
+    async fn set_members(&self, key: &str) -> Result<Vec<String>>;
+
     /// Get the name of the cache driver
     fn name(&self) -> &str;
 
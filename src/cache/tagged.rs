@@ -0,0 +1,134 @@
+use crate::cache::manager::CacheDriver;
+use crate::cache::Cache;
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+/// Tagged views over any cache store
+#[async_trait]
+pub trait TaggableCache: Cache + Clone + Sized + 'static {
+    /// Scope cache operations to tags that can be flushed together
+    fn tags(&self, tags: &[&str]) -> TaggedCache<Self> {
+        TaggedCache::new(self.clone(), tags)
+    }
+
+    /// Evict every entry stored under any of `tags`
+    async fn flush_tags(&self, tags: &[&str]) -> Result<()> {
+        self.tags(tags).flush().await
+    }
+}
+
+impl<C> TaggableCache for C where C: Cache + Clone + 'static {}
+
+/// A view of a cache store where entries belong to one or more tags
+///
+/// Like Laravel's `Cache::tags()`, flushing a tag drops every entry stored
+/// under it. Each tag keeps a set of the keys stored under it, which a flush
+/// evicts, and a version counter that is part of the entry keys, which a
+/// flush increments. The set lives as long as the longest-lived entry in it,
+/// and the counter makes sure an entry written while a flush runs is never
+/// read again either.
+#[derive(Clone)]
+pub struct TaggedCache<C: Cache = CacheDriver> {
+    cache: C,
+    tags: Vec<String>,
+}
+
+impl<C: Cache> TaggedCache<C> {
+    pub fn new(cache: C, tags: &[&str]) -> Self {
+        Self {
+            cache,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        }
+    }
+
+    pub async fn get<T>(&self, key: &str) -> Result<Option<T>>
+    where
+        T: for<'de> Deserialize<'de> + Send,
+    {
+        let key = self.tagged_key(key).await?;
+        self.cache.get(&key).await
+    }
+
+    /// Store a value and add its key to each tag's set
+    ///
+    /// A tag's set is kept for the longest TTL of the entries added to it.
+    pub async fn put<T>(&self, key: &str, value: &T, ttl: Option<Duration>) -> Result<()>
+    where
+        T: Serialize + Send + Sync,
+    {
+        let key = self.tagged_key(key).await?;
+        self.cache.put(&key, value, ttl).await?;
+
+        for tag in &self.tags {
+            self.cache.set_add(&Self::index_key(tag), &[&key], ttl).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn has(&self, key: &str) -> Result<bool> {
+        let key = self.tagged_key(key).await?;
+        self.cache.has(&key).await
+    }
+
+    /// Remove a value and its key from each tag's set
+    pub async fn forget(&self, key: &str) -> Result<bool> {
+        let key = self.tagged_key(key).await?;
+        let forgotten = self.cache.forget(&key).await?;
+
+        for tag in &self.tags {
+            self.cache.set_remove(&Self::index_key(tag), &[&key]).await?;
+        }
+        Ok(forgotten)
+    }
+
+    /// Get a value or store the callback's result under these tags
+    pub async fn remember<T, F, Fut>(&self, key: &str, ttl: Option<Duration>, callback: F) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de> + Serialize + Send + Sync,
+        F: FnOnce() -> Fut + Send,
+        Fut: std::future::Future<Output = Result<T>> + Send,
+    {
+        if let Some(value) = self.get(key).await? {
+            return Ok(value);
+        }
+
+        let value = callback().await?;
+        self.put(key, &value, ttl).await?;
+        Ok(value)
+    }
+
+    /// Evict every entry stored under any of these tags
+    pub async fn flush(&self) -> Result<()> {
+        for tag in &self.tags {
+            self.cache.increment(&Self::version_key(tag), 1).await?;
+
+            let index_key = Self::index_key(tag);
+            for key in self.cache.set_members(&index_key).await? {
+                self.cache.forget(&key).await?;
+            }
+            self.cache.forget(&index_key).await?;
+        }
+        Ok(())
+    }
+
+    async fn tagged_key(&self, key: &str) -> Result<String> {
+        let mut namespace = Sha256::new();
+        for tag in &self.tags {
+            let version = self.cache.get::<i64>(&Self::version_key(tag)).await?.unwrap_or(0);
+            namespace.update(format!("{}:{}|", tag, version).as_bytes());
+        }
+
+        Ok(format!("tagged:{}:{}", hex::encode(namespace.finalize()), key))
+    }
+
+    fn version_key(tag: &str) -> String {
+        format!("tag:{}:version", tag)
+    }
+
+    fn index_key(tag: &str) -> String {
+        format!("tag:{}:keys", tag)
+    }
+}
\ No newline at end of file
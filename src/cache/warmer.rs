@@ -0,0 +1,156 @@
+//! Cache warming at boot and on a schedule
+//!
+//! Warmers prime keys that are expensive to compute on the first request,
+//! such as reference data read through `remember`. The registry runs every
+//! warmer concurrently with a per-warmer timeout, so one slow warmer never
+//! holds up the others or application startup.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+use crate::cache::manager::CacheDriver;
+use crate::logging::Log;
+
+#[async_trait]
+pub trait CacheWarmer: Send + Sync {
+    /// Name shown in logs and by `cache:warm`
+    fn name(&self) -> &str;
+
+    /// Compute the values and write them to the cache
+    async fn warm(&self, cache: &CacheDriver) -> Result<()>;
+}
+
+/// Warmer backed by an async closure
+struct FnWarmer<F> {
+    name: String,
+    callback: F,
+}
+
+#[async_trait]
+impl<F, Fut> CacheWarmer for FnWarmer<F>
+where
+    F: Fn(CacheDriver) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn warm(&self, cache: &CacheDriver) -> Result<()> {
+        (self.callback)(cache.clone()).await
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WarmStatus {
+    Warmed,
+    Failed(String),
+    TimedOut,
+}
+
+/// Outcome of one warmer run
+#[derive(Debug, Clone)]
+pub struct WarmResult {
+    pub name: String,
+    pub status: WarmStatus,
+    pub elapsed: Duration,
+}
+
+impl WarmResult {
+    pub fn is_warmed(&self) -> bool {
+        self.status == WarmStatus::Warmed
+    }
+
+    fn log(&self) {
+        let mut context = HashMap::new();
+        context.insert("warmer".to_string(), serde_json::json!(self.name));
+        context.insert("elapsed_ms".to_string(), serde_json::json!(self.elapsed.as_millis() as u64));
+
+        match &self.status {
+            WarmStatus::Warmed => Log::info_with_context("Cache warmer finished", context),
+            WarmStatus::Failed(error) => {
+                context.insert("error".to_string(), serde_json::json!(error));
+                Log::warning_with_context("Cache warmer failed", context);
+            },
+            WarmStatus::TimedOut => Log::warning_with_context("Cache warmer timed out", context),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CacheWarmerRegistry {
+    warmers: Vec<Arc<dyn CacheWarmer>>,
+    timeout: Duration,
+}
+
+impl CacheWarmerRegistry {
+    pub fn new(timeout: Duration) -> Self {
+        Self { warmers: Vec::new(), timeout }
+    }
+
+    pub fn register(&mut self, warmer: impl CacheWarmer + 'static) -> &mut Self {
+        self.warmers.push(Arc::new(warmer));
+        self
+    }
+
+    /// Register an async closure as a warmer
+    pub fn register_fn<F, Fut>(&mut self, name: &str, callback: F) -> &mut Self
+    where
+        F: Fn(CacheDriver) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.register(FnWarmer { name: name.to_string(), callback })
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.warmers.iter().map(|warmer| warmer.name().to_string()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.warmers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.warmers.is_empty()
+    }
+
+    /// Run every warmer once, concurrently, each bounded by the timeout
+    pub async fn warm(&self, cache: &CacheDriver) -> Vec<WarmResult> {
+        futures::future::join_all(self.warmers.iter().map(|warmer| async move {
+            let start = Instant::now();
+            let status = match tokio::time::timeout(self.timeout, warmer.warm(cache)).await {
+                Ok(Ok(())) => WarmStatus::Warmed,
+                Ok(Err(e)) => WarmStatus::Failed(e.to_string()),
+                Err(_) => WarmStatus::TimedOut,
+            };
+
+            let result = WarmResult {
+                name: warmer.name().to_string(),
+                status,
+                elapsed: start.elapsed(),
+            };
+            result.log();
+            result
+        }))
+        .await
+    }
+
+    /// Warm in the background, then again every `interval` when one is given
+    pub fn spawn(self, cache: CacheDriver, interval: Option<Duration>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                self.warm(&cache).await;
+
+                match interval {
+                    Some(interval) => tokio::time::sleep(interval).await,
+                    None => break,
+                }
+            }
+        })
+    }
+}
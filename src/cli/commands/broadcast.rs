@@ -1,6 +1,10 @@
 use anyhow::Result;
-use tokio::time::{sleep, Duration};
-use crate::app::broadcasting;
+use futures::StreamExt;
+use std::time::Instant;
+use tokio::time::{interval as interval_timer, sleep, Duration};
+use crate::app::broadcasting::{self, BroadcastMessage};
+use crate::app::broadcasting::monitor::{ChannelActivity, PingSummary};
+use crate::config::Config;
 
 /// Handle broadcast test command
 pub async fn handle_broadcast_test_command(channel: Option<String>, message: Option<String>) -> Result<()> {
@@ -59,15 +63,21 @@ pub async fn handle_broadcast_stats_command() -> Result<()> {
         }
     }
 
-    // Get broadcast manager info
-    let broadcast_manager = broadcasting::broadcast_manager().await;
-    let _manager = broadcast_manager.read().await;
+    let config = Config::load()?.broadcasting;
 
     println!();
     println!("🔧 Broadcast Configuration:");
-    println!("  • Default Driver: Available");
+    println!("  • Default Driver: {}", config.default_driver);
     println!("  • Total Active Channels: {}", active_channels.len());
 
+    if config.default_driver == "redis" || config.redis_enabled {
+        let driver = broadcasting::RedisDriver::from_config(&config);
+        match driver.health_check().await {
+            Ok(()) => println!("  • Redis ({}:{}): 🟢 Healthy", driver.host, driver.port),
+            Err(e) => println!("  • Redis ({}:{}): 🔴 Unreachable - {}", driver.host, driver.port, e),
+        }
+    }
+
     if active_channels.is_empty() {
         println!();
         println!("💡 Tip: Start the WebSocket server with 'cargo run --bin artisan -- broadcast:websocket --port 8080'");
@@ -84,23 +94,41 @@ pub async fn handle_broadcast_ping_command(channel: Option<String>, interval: Op
     println!("🏓 Starting broadcast ping to channel '{}' every {} seconds", channel, interval_secs);
     println!("Press Ctrl+C to stop");
 
-    let mut counter = 1;
+    let started = Instant::now();
+    let mut summary = PingSummary::default();
+    let mut ticker = interval_timer(Duration::from_secs(interval_secs.max(1)));
+    let shutdown = tokio::signal::ctrl_c();
+    tokio::pin!(shutdown);
 
     loop {
-        let data = serde_json::json!({
-            "ping": counter,
-            "timestamp": chrono::Utc::now(),
-            "message": format!("Ping #{}", counter)
-        });
-
-        match broadcasting::helpers::broadcast_to_channel(&channel, "ping", data).await {
-            Ok(_) => println!("📡 Ping #{} sent to channel '{}'", counter, channel),
-            Err(e) => eprintln!("❌ Failed to send ping #{}: {}", counter, e),
+        tokio::select! {
+            _ = &mut shutdown => break,
+            _ = ticker.tick() => {
+                let counter = summary.sent + summary.failed + 1;
+                let data = serde_json::json!({
+                    "ping": counter,
+                    "timestamp": chrono::Utc::now(),
+                    "message": format!("Ping #{}", counter)
+                });
+
+                match broadcasting::helpers::broadcast_to_channel(&channel, "ping", data).await {
+                    Ok(_) => {
+                        summary.record_sent();
+                        println!("📡 Ping #{} sent to channel '{}'", counter, channel);
+                    }
+                    Err(e) => {
+                        summary.record_failed();
+                        eprintln!("❌ Failed to send ping #{}: {}", counter, e);
+                    }
+                }
+            }
         }
-
-        counter += 1;
-        sleep(Duration::from_secs(interval_secs)).await;
     }
+
+    println!();
+    println!("🛑 Ping stopped");
+    println!("{}", summary.render(started.elapsed()));
+    Ok(())
 }
 
 /// Handle broadcast channels command
@@ -158,36 +186,61 @@ pub async fn handle_system_alert_command(level: String, message: String, action_
 }
 
 /// Handle broadcast monitor command
+///
+/// Listens on the Redis broadcast backplane, so it sees messages published by
+/// every instance, and prints per-channel counts when the window ends or on Ctrl+C.
 pub async fn handle_broadcast_monitor_command(duration: Option<u64>) -> Result<()> {
     let duration_secs = duration.unwrap_or(30);
+    let config = Config::load()?.broadcasting;
+
+    let client = redis::Client::open(config.redis_url())?;
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.psubscribe(config.channels_pattern()).await?;
 
     println!("👀 Monitoring broadcast activity for {} seconds", duration_secs);
-    println!("Watching for new connections and broadcasts...");
     println!("Press Ctrl+C to stop early");
 
-    let ws_manager = broadcasting::websocket::websocket_manager().await;
-    let start_time = std::time::Instant::now();
-
-    while start_time.elapsed().as_secs() < duration_secs {
-        let active_channels = ws_manager.active_channels().await;
-        let total_connections: usize = {
-            let mut total = 0;
-            for channel in &active_channels {
-                total += ws_manager.connection_count(channel).await;
-            }
-            total
-        };
-
-        print!("\r📊 Channels: {} | Connections: {} | Time: {}s",
-               active_channels.len(),
-               total_connections,
-               start_time.elapsed().as_secs());
-        std::io::Write::flush(&mut std::io::stdout()).unwrap();
+    let started = Instant::now();
+    let mut activity = ChannelActivity::new();
+    let mut messages = pubsub.on_message();
+    let deadline = sleep(Duration::from_secs(duration_secs));
+    let shutdown = tokio::signal::ctrl_c();
+    tokio::pin!(deadline, shutdown);
 
-        sleep(Duration::from_secs(1)).await;
+    loop {
+        tokio::select! {
+            _ = &mut deadline => break,
+            _ = &mut shutdown => {
+                println!();
+                println!("🛑 Monitoring stopped early");
+                break;
+            }
+            msg = messages.next() => {
+                let Some(msg) = msg else {
+                    println!();
+                    eprintln!("❌ Redis broadcast subscription closed");
+                    break;
+                };
+
+                let message = msg.get_payload::<String>()
+                    .ok()
+                    .and_then(|payload| serde_json::from_str::<BroadcastMessage>(&payload).ok());
+                if let Some(message) = message {
+                    activity.record(&message);
+                }
+
+                print!("\r📊 Messages: {} | Channels: {} | Time: {}s",
+                       activity.total(),
+                       activity.channels().len(),
+                       started.elapsed().as_secs());
+                std::io::Write::flush(&mut std::io::stdout()).unwrap();
+            }
+        }
     }
 
     println!();
-    println!("✅ Monitoring completed");
+    println!("✅ Monitoring completed after {}s", started.elapsed().as_secs());
+    println!();
+    println!("{}", activity.render_table());
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,36 @@
+use anyhow::Result;
+use crate::{config, database};
+use crate::app::cache_warmers;
+use crate::cache::{shared_cache, WarmStatus};
+
+/// Handle cache:warm command
+pub async fn handle_cache_warm_command() -> Result<()> {
+    let config = config::Config::load()?;
+    let pool = database::create_pool(&config)?;
+    let registry = cache_warmers::registry(&pool, &config.cache);
+
+    if config.cache.default == "memory" {
+        println!("⚠️  The memory cache driver is local to this process; a running server will not see these entries");
+    }
+
+    println!("🔥 Running {} cache warmer(s)", registry.len());
+
+    let results = registry.warm(&shared_cache().await?).await;
+    for result in &results {
+        let elapsed = result.elapsed.as_millis();
+        match &result.status {
+            WarmStatus::Warmed => println!("  • {}: warmed in {}ms", result.name, elapsed),
+            WarmStatus::Failed(error) => println!("  • {}: failed after {}ms: {}", result.name, elapsed, error),
+            WarmStatus::TimedOut => println!("  • {}: timed out after {}ms", result.name, elapsed),
+        }
+    }
+
+    let failed = results.iter().filter(|result| !result.is_warmed()).count();
+    if failed > 0 {
+        eprintln!("❌ {} of {} cache warmer(s) did not finish", failed, results.len());
+        anyhow::bail!("{} cache warmer(s) did not finish", failed);
+    }
+
+    println!("✅ Cache warming completed");
+    Ok(())
+}
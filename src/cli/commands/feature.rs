@@ -0,0 +1,99 @@
+use anyhow::Result;
+use crate::{config, database};
+use crate::app::models::DieselUlid;
+use crate::app::services::feature_flag_service::FeatureFlagService;
+
+/// Handle feature:set command
+///
+/// The cached lookup is forgotten in the configured cache store. With the
+/// in-process `memory` store that only reaches this command's own cache, so
+/// running servers see the change once their cached copy expires.
+pub async fn handle_feature_set_command(
+    name: String,
+    on: bool,
+    off: bool,
+    rollout: Option<i32>,
+    description: Option<String>,
+    user: Option<String>,
+    clear: bool,
+) -> Result<()> {
+    let config = config::Config::load()?;
+    let pool = database::create_pool(&config)?;
+    let enabled = if on { Some(true) } else if off { Some(false) } else { None };
+
+    if let Some(user) = user {
+        if enabled.is_none() && !clear {
+            anyhow::bail!("Pass --on, --off or --clear together with --user");
+        }
+
+        let user_id = DieselUlid::from_string(&user)?;
+        if let Err(e) = FeatureFlagService::set_override(&pool, &name, user_id, enabled).await {
+            eprintln!("❌ Failed to update override: {}", e);
+            return Err(e);
+        }
+
+        match enabled {
+            Some(enabled) => println!("✅ Feature '{}' forced {} for user {}", name, if enabled { "on" } else { "off" }, user),
+            None => println!("✅ Removed override of feature '{}' for user {}", name, user),
+        }
+        warn_if_cache_is_local(&config);
+        return Ok(());
+    }
+
+    let flag = match FeatureFlagService::set(&pool, &name, enabled, rollout, description).await {
+        Ok(flag) => flag,
+        Err(e) => {
+            eprintln!("❌ Failed to set feature flag: {}", e);
+            return Err(e);
+        }
+    };
+
+    println!("✅ Feature '{}' saved", flag.name);
+    println!("  • Enabled: {}", flag.enabled);
+    match flag.rollout_percentage {
+        Some(percentage) => println!("  • Rollout: {}% of users", percentage),
+        None => println!("  • Rollout: everyone"),
+    }
+    warn_if_cache_is_local(&config);
+    Ok(())
+}
+
+/// Running servers cannot be told about the change when the cache store is
+/// not shared between processes
+fn warn_if_cache_is_local(config: &config::Config) {
+    let driver = config.cache.default_store().map(|store| store.driver.as_str()).unwrap_or("memory");
+    if driver != "redis" {
+        println!(
+            "⚠️  Cache store '{}' is local to each process; running servers see this change within {}s",
+            driver,
+            config.cache.feature_ttl_secs,
+        );
+    }
+}
+
+/// Handle feature:list command
+pub fn handle_feature_list_command() -> Result<()> {
+    let config = config::Config::load()?;
+    let pool = database::create_pool(&config)?;
+    let flags = FeatureFlagService::list(&pool)?;
+
+    if flags.is_empty() {
+        println!("No feature flags defined");
+        return Ok(());
+    }
+
+    println!("🚩 Feature flags:");
+    for flag in flags {
+        let rollout = flag.rollout_percentage
+            .map(|percentage| format!("{}%", percentage))
+            .unwrap_or_else(|| "everyone".to_string());
+        println!(
+            "  • {} [{}] rollout: {}{}",
+            flag.name,
+            if flag.enabled { "on" } else { "off" },
+            rollout,
+            flag.description.map(|description| format!(" - {}", description)).unwrap_or_default(),
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anyhow::Result;
+use std::path::Path;
+use crate::{config, database};
+use crate::app::models::country::Country;
+use crate::app::services::csv_import_service::{CsvImportOptions, CsvImportReport, CsvImportService};
+
+/// Handle import:csv command
+pub async fn handle_import_csv_command(
+    model: String,
+    file: String,
+    user: String,
+    map: Vec<String>,
+    batch_size: usize,
+    dry_run: bool,
+) -> Result<()> {
+    let config = config::Config::load()?;
+    let pool = database::create_pool(&config)?;
+
+    if ulid::Ulid::from_string(user.trim()).is_err() {
+        anyhow::bail!("Invalid user ID: {}", user);
+    }
+
+    let mut options = CsvImportOptions::new(user.trim())
+        .with_batch_size(batch_size)
+        .with_dry_run(dry_run);
+    for mapping in map {
+        let (header, field) = mapping
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid column mapping '{}', expected HEADER=field", mapping))?;
+        options = options.with_column(header.trim(), field.trim());
+    }
+
+    if dry_run {
+        println!("🔎 Validating {} rows from {} (dry run)", model, file);
+    } else {
+        println!("📥 Importing {} rows from {}", model, file);
+    }
+
+    let path = Path::new(&file);
+    let report = match model.as_str() {
+        "Country" => CsvImportService::import_file::<Country>(&pool, path, &options).await,
+        other => Err(anyhow::anyhow!("Model '{}' does not support CSV import", other)),
+    };
+
+    let report = match report {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("❌ Import failed: {}", e);
+            return Err(e);
+        }
+    };
+
+    print_report(&report, dry_run);
+    Ok(())
+}
+
+fn print_report(report: &CsvImportReport, dry_run: bool) {
+    println!("  • Rows processed: {}", report.processed);
+    println!("  • Rows valid: {}", report.valid);
+    if !dry_run {
+        println!("  • Rows inserted: {}", report.inserted);
+    }
+    println!("  • Rows failed: {}", report.failed.len());
+
+    for failure in &report.failed {
+        let mut fields: Vec<_> = failure.errors.iter().collect();
+        fields.sort_by_key(|(field, _)| field.as_str());
+        for (field, messages) in fields {
+            println!("    line {}: {}: {}", failure.line, field, messages.join("; "));
+        }
+    }
+
+    if report.has_failures() {
+        println!("⚠️  Import finished with {} failed rows", report.failed.len());
+    } else if dry_run {
+        println!("✅ All rows are valid");
+    } else {
+        println!("✅ CSV import completed");
+    }
+}
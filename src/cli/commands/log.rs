@@ -0,0 +1,48 @@
+use anyhow::Result;
+use serde_json::Value;
+use crate::config::Config;
+
+/// Handle log:level command
+///
+/// The filter lives in the server process, so this talks to the running
+/// instance through its admin endpoint rather than the local subscriber.
+pub async fn handle_log_level_command(directive: Option<String>, reset: bool, url: Option<String>, token: String) -> Result<()> {
+    let base_url = match url {
+        Some(url) => url,
+        None => Config::load()?.app.url,
+    };
+    let endpoint = format!("{}/api/admin/log-level", base_url.trim_end_matches('/'));
+    let client = reqwest::Client::new();
+
+    let request = if reset {
+        client.delete(&endpoint)
+    } else if let Some(directive) = &directive {
+        client.put(&endpoint).json(&serde_json::json!({ "directive": directive }))
+    } else {
+        client.get(&endpoint)
+    };
+
+    let response = request.bearer_auth(&token).send().await?;
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+
+    if !status.is_success() {
+        let error = body["error"].as_str().unwrap_or("unexpected response");
+        eprintln!("❌ Failed to update log level ({}): {}", status, error);
+        anyhow::bail!("log:level request failed with status {}", status);
+    }
+
+    if reset {
+        println!("✅ Log filter reset to startup directives");
+    } else if let Some(directive) = &directive {
+        println!("✅ Applied log directive: {}", directive);
+    }
+
+    println!("  • Startup: {}", body["startup"].as_str().unwrap_or(""));
+    let overrides = body["overrides"].as_array().map(|overrides| {
+        overrides.iter().filter_map(Value::as_str).collect::<Vec<_>>().join(", ")
+    }).unwrap_or_default();
+    println!("  • Overrides: {}", if overrides.is_empty() { "none" } else { &overrides });
+    println!("  • Current: {}", body["current"].as_str().unwrap_or(""));
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anyhow::Result;
+use std::time::Duration;
+use crate::{config, database};
+use crate::app::services::scheduled_message_service::ScheduledMessageService;
+
+/// Handle messages:dispatch-scheduled command
+pub async fn handle_dispatch_scheduled_command(limit: i64, watch: bool, interval: u64) -> Result<()> {
+    let config = config::Config::load()?;
+    let pool = database::create_pool(&config)?;
+
+    if watch {
+        println!("⏰ Dispatching scheduled messages every {} seconds (Ctrl+C to stop)", interval);
+        ScheduledMessageService::run(pool, Duration::from_secs(interval), limit).await;
+        return Ok(());
+    }
+
+    println!("⏰ Dispatching due scheduled messages...");
+
+    let summary = match ScheduledMessageService::dispatch_due(&pool, limit).await {
+        Ok(summary) => summary,
+        Err(e) => {
+            eprintln!("❌ Failed to dispatch scheduled messages: {}", e);
+            return Err(e);
+        }
+    };
+
+    println!("  • Messages sent: {}", summary.sent);
+    println!("  • Messages failed: {}", summary.failed);
+    println!("✅ Scheduled message dispatch completed");
+    Ok(())
+}
@@ -4,4 +4,11 @@ pub mod serve;
 pub mod passport;
 pub mod seed;
 pub mod route;
-pub mod broadcast;
\ No newline at end of file
+pub mod broadcast;
+pub mod webpush;
+pub mod messages;
+pub mod log;
+pub mod import;
+pub mod cache;
+pub mod feature;
+pub mod queue;
\ No newline at end of file
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use std::sync::Arc;
+use crate::{config, database};
+use crate::app::jobs::database_queue_driver::DatabaseQueueDriver;
+use crate::app::jobs::queue_worker::{QueueWorker, WorkerConfig};
+use crate::app::providers::job_service_provider::job_registry;
+
+/// Handle queue:work command
+pub async fn handle_queue_work_command(queue: String, concurrency: usize, once: bool) -> Result<()> {
+    let config = config::Config::load()?;
+    if config.queue.connection != "database" {
+        anyhow::bail!(
+            "queue:work needs QUEUE_CONNECTION=database; the '{}' queue only lives inside the server process",
+            config.queue.connection
+        );
+    }
+
+    let pool = database::create_pool(&config)?;
+    database::connection::initialize_pool(pool.clone());
+
+    // Queued listeners are resolved by name, and may send mail or queue further listeners
+    crate::boot_queue(&config, &pool).await;
+    crate::boot_mail(&config).await;
+
+    let worker_config = WorkerConfig {
+        queue_name: queue.clone(),
+        concurrency,
+        ..WorkerConfig::default()
+    };
+    let driver = DatabaseQueueDriver::new(pool).retry_after(std::time::Duration::from_secs(config.queue.retry_after));
+    let mut worker = QueueWorker::with_registry(worker_config, Arc::new(driver), job_registry());
+
+    if once {
+        if worker.work_once().await? {
+            println!("✅ Processed one job from '{}'", queue);
+        } else {
+            println!("📭 No jobs waiting on '{}'", queue);
+        }
+        return Ok(());
+    }
+
+    println!("⚙️  Processing jobs from '{}' with {} worker(s) (Ctrl+C to stop)", queue, concurrency);
+    worker.start().await
+}
@@ -1,16 +1,18 @@
 use anyhow::Result;
 use crate::create_app;
+use crate::logging::{self, LogLevelFilter};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 pub async fn handle_serve_command(host: String, port: u16) -> Result<()> {
     // Initialize tracing
+    let default_directives = "rustaxum=debug,tower_http=debug";
+    let directives = std::env::var("RUST_LOG").unwrap_or_else(|_| default_directives.to_string());
+    let (filter, level_filter) = LogLevelFilter::with_fallback(&directives, default_directives)?;
     tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "rustaxum=debug,tower_http=debug".into()),
-        )
+        .with(filter)
         .with(tracing_subscriber::fmt::layer())
         .init();
+    logging::level::install(level_filter);
 
     let server_addr = format!("{}:{}", host, port);
     println!("🚀 Starting development server...");
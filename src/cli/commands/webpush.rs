@@ -0,0 +1,28 @@
+use anyhow::Result;
+use crate::app::notifications::channels::web_push_channel::WebPushChannel;
+
+/// Handle webpush:prune command
+pub async fn handle_webpush_prune_command(days: i32) -> Result<()> {
+    println!("🧹 Pruning web push subscriptions inactive for more than {} days", days);
+
+    let subscriptions = match WebPushChannel::cleanup_inactive_subscriptions(days).await {
+        Ok(count) => count,
+        Err(e) => {
+            eprintln!("❌ Failed to prune push subscriptions: {}", e);
+            return Err(e);
+        }
+    };
+
+    let device_tokens = match WebPushChannel::prune_inactive_device_tokens().await {
+        Ok(count) => count,
+        Err(e) => {
+            eprintln!("❌ Failed to prune web push device tokens: {}", e);
+            return Err(e);
+        }
+    };
+
+    println!("  • Push subscriptions removed: {}", subscriptions);
+    println!("  • Device push tokens removed: {}", device_tokens);
+    println!("✅ Web push pruning completed");
+    Ok(())
+}
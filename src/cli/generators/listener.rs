@@ -24,6 +24,10 @@ pub async fn generate_listener(name: &str, event: Option<String>, queued: bool)
 
     update_listeners_mod(&listener_name)?;
 
+    if let Some(event) = &event {
+        register_listener(&listener_name, event)?;
+    }
+
     println!("Listener created successfully: {}", file_path);
     Ok(())
 }
@@ -151,6 +155,10 @@ impl EventListener for {} {{
         true
     }}
 
+    fn as_should_queue(&self) -> Option<&dyn ShouldQueueListener> {{
+        Some(self)
+    }}
+
     fn queue_name(&self) -> Option<&str> {{
         Some("listeners")
     }}
@@ -251,6 +259,51 @@ fn update_listeners_mod(listener_name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Add the listener to `EventServiceProvider::register_listeners` for its event
+fn register_listener(listener_name: &str, event: &str) -> Result<()> {
+    let provider_path = "src/app/providers/event_service_provider.rs";
+    let event_type = if event.ends_with("Event") {
+        event.to_string()
+    } else {
+        format!("{}Event", event)
+    };
+    let event_module = to_snake_case(&event_type);
+
+    if !Path::new(&format!("src/app/events/{}.rs", event_module)).exists() {
+        println!("Event {} not found in src/app/events; register {} in {} yourself", event_type, listener_name, provider_path);
+        return Ok(());
+    }
+
+    let provider = fs::read_to_string(provider_path)?;
+    let registration = registration_line(listener_name, &event_type);
+    if provider.contains(registration.trim()) {
+        return Ok(());
+    }
+
+    let marker = format!("    {}", crate::app::providers::event_service_provider::REGISTRATION_MARKER);
+    let Some(position) = provider.find(&marker) else {
+        println!("Registration marker not found; register {} in {} yourself", listener_name, provider_path);
+        return Ok(());
+    };
+
+    let mut updated = provider;
+    updated.insert_str(position, &registration);
+    fs::write(provider_path, updated)?;
+
+    println!("Listener registered for {} in {}", event_type, provider_path);
+    Ok(())
+}
+
+fn registration_line(listener_name: &str, event_type: &str) -> String {
+    format!(
+        "    dispatcher.listen::<crate::app::events::{}::{}>(Arc::new(crate::app::listeners::{}::{}::new())).await;\n",
+        to_snake_case(event_type),
+        event_type,
+        to_snake_case(listener_name),
+        listener_name,
+    )
+}
+
 fn to_snake_case(s: &str) -> String {
     let mut result = String::new();
     let mut prev_is_upper = false;
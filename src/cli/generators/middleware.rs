@@ -5,7 +5,7 @@ use std::path::Path;
 pub async fn generate_middleware(name: &str) -> Result<()> {
     let middleware_name = format_middleware_name(name);
     let file_name = to_snake_case(&middleware_name);
-    let file_path = format!("src/app/middleware/{}.rs", file_name);
+    let file_path = format!("src/app/http/middleware/{}.rs", file_name);
 
     if Path::new(&file_path).exists() {
         return Err(anyhow!("Middleware {} already exists", middleware_name));
@@ -19,9 +19,68 @@ pub async fn generate_middleware(name: &str) -> Result<()> {
     // Update the middleware mod.rs file
     update_middleware_mod(&file_name)?;
 
+    register_alias(&middleware_name)?;
+
+    Ok(())
+}
+
+/// Alias a middleware is registered under, e.g. `EnsureTokenIsValid` -> `ensure_token_is_valid`
+pub fn middleware_alias(name: &str) -> String {
+    to_snake_case(format_middleware_name(name).trim_end_matches("Middleware"))
+}
+
+/// Add the middleware to `register_aliases` and print how to use it
+fn register_alias(middleware_name: &str) -> Result<()> {
+    let aliases_path = "src/app/http/middleware/aliases.rs";
+    let alias = middleware_alias(middleware_name);
+
+    let source = fs::read_to_string(aliases_path)?;
+    match insert_alias_registration(&source, middleware_name) {
+        Some(updated) => {
+            fs::write(aliases_path, updated)?;
+            println!("Middleware registered as \"{}\" in {}", alias, aliases_path);
+        }
+        None if source.contains(&format!("aliases.alias(\"{}\"", alias)) => {}
+        None => {
+            println!("Registration marker not found; register {} in {} yourself", middleware_name, aliases_path);
+            return Ok(());
+        }
+    }
+
+    println!();
+    println!("Apply it to routes by alias:");
+    println!("    let router = MiddlewareAliases::global().apply(router, &[\"{}\"])?;", alias);
     Ok(())
 }
 
+/// `source` with the alias registration for `middleware_name` inserted above
+/// the registration marker, or `None` when it is already registered or the
+/// marker is missing
+pub fn insert_alias_registration(source: &str, middleware_name: &str) -> Option<String> {
+    let middleware_name = format_middleware_name(middleware_name);
+    let registration = alias_registration_line(&middleware_name);
+    if source.contains(registration.trim()) {
+        return None;
+    }
+
+    let marker = format!("    {}", crate::app::http::middleware::aliases::REGISTRATION_MARKER);
+    let position = source.find(&marker)?;
+
+    let mut updated = source.to_string();
+    updated.insert_str(position, &registration);
+    Some(updated)
+}
+
+fn alias_registration_line(middleware_name: &str) -> String {
+    let alias = middleware_alias(middleware_name);
+    format!(
+        "    aliases.alias(\"{}\", |request, next| Box::pin(crate::app::http::middleware::{}::{}(request, next)));\n",
+        alias,
+        to_snake_case(middleware_name),
+        alias,
+    )
+}
+
 fn format_middleware_name(name: &str) -> String {
     if name.ends_with("Middleware") {
         name.to_string()
@@ -50,28 +109,32 @@ fn to_snake_case(name: &str) -> String {
     result
 }
 
-fn generate_middleware_content(middleware_name: &str) -> String {
-    let function_name = to_snake_case(&middleware_name.replace("Middleware", ""));
+/// Source of a middleware with the `(Request, Next) -> Response` signature `from_fn` expects
+pub fn generate_middleware_content(middleware_name: &str) -> String {
+    let function_name = middleware_alias(middleware_name);
 
     format!(r#"use axum::{{
-    extract::{{Request, State}},
-    http::{{StatusCode, HeaderValue}},
+    extract::Request,
+    http::HeaderValue,
     middleware::Next,
-    response::{{IntoResponse, Response}},
+    response::Response,
 }};
-use anyhow::Result;
-use tracing::{{info, warn, error}};
+use tracing::info;
 
 /// {} middleware for request processing
+///
+/// Registered as the `{}` alias; apply it with
+/// `MiddlewareAliases::global().apply(router, &["{}"])` or
+/// `route_layer(axum::middleware::from_fn({}))`.
 pub async fn {}(
     request: Request,
     next: Next,
-) -> Result<Response, impl IntoResponse> {{
+) -> Response {{
     let start_time = std::time::Instant::now();
     let method = request.method().clone();
     let uri = request.uri().clone();
 
-    info!("Processing {} request to: {{}}", method, uri);
+    info!("Processing {} request to: {{}} {{}}", method, uri);
 
     // Pre-processing: Add your middleware logic here
     // Examples:
@@ -80,6 +143,8 @@ pub async fn {}(
     // - Request validation
     // - Headers manipulation
     // - Logging and metrics
+    //
+    // Return early with `(StatusCode::FORBIDDEN, "...").into_response()` to reject
 
     // Process the request
     let mut response = next.run(request).await;
@@ -98,24 +163,15 @@ pub async fn {}(
         response.headers_mut().insert("X-Processing-Time-Ms", duration_header);
     }}
 
-    info!("Completed {} request to {{}} in {{:.2?}}", method, uri, duration);
+    info!("Completed {} request to {{}} {{}} in {{:.2?}}", method, uri, duration);
 
-    Ok(response)
-}}
-
-/// Error handler for {} middleware
-pub fn handle_middleware_error(error: &str) -> impl IntoResponse {{
-    error!("Middleware error: {{}}", error);
-    (
-        StatusCode::INTERNAL_SERVER_ERROR,
-        format!("Middleware error: {{}}", error)
-    )
+    response
 }}
-"#, middleware_name, function_name, middleware_name, middleware_name, middleware_name)
+"#, middleware_name, function_name, function_name, function_name, function_name, middleware_name, middleware_name)
 }
 
 fn update_middleware_mod(file_name: &str) -> Result<()> {
-    let mod_path = "src/app/middleware/mod.rs";
+    let mod_path = "src/app/http/middleware/mod.rs";
     let module_declaration = format!("pub mod {};", file_name);
 
     if let Ok(current_content) = fs::read_to_string(mod_path) {
@@ -2,6 +2,8 @@ use anyhow::Result;
 use std::fs;
 use std::path::Path;
 
+use crate::app::gate::ABILITIES;
+
 pub async fn generate_policy(name: &str, model: Option<String>) -> Result<()> {
     let policy_name = if name.ends_with("Policy") {
         name.to_string()
@@ -21,84 +23,132 @@ pub async fn generate_policy(name: &str, model: Option<String>) -> Result<()> {
     update_policies_mod(&policy_name)?;
 
     println!("Policy created successfully: {}", file_path);
+
+    match &model {
+        Some(model) => {
+            register_policy(&policy_name, model)?;
+            println!("{}", guard_snippet(model));
+        }
+        None => println!("No --model given; implement Policy<YourModel> and register {} in {} yourself", policy_name, PROVIDER_PATH),
+    }
+
     Ok(())
 }
 
+const PROVIDER_PATH: &str = "src/app/providers/auth_service_provider.rs";
+
 fn generate_policy_template(policy_name: &str, model: &Option<String>) -> String {
     let model_name = model.as_deref().unwrap_or("Model");
+    let model_import = match model {
+        Some(model) => format!("use crate::app::models::{}::{};", to_snake_case(model), model),
+        None => "// use crate::app::models::model::Model;".to_string(),
+    };
 
-    format!(r#"use anyhow::Result;
-use serde::{{Deserialize, Serialize}};
+    // One method per ability the gate dispatches, so the trait impl covers all of them
+    let methods = ABILITIES.iter()
+        .map(|ability| ability_method(ability, model_name))
+        .collect::<Vec<_>>()
+        .join("\n\n");
 
-// Import the model this policy applies to
-// use crate::app::models::{};
+    format!(r#"use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::app::gate::Policy;
+use crate::app::http::middleware::auth_guard::AuthUser;
+{model_import}
+
+/// Authorization rules for `{model_name}`, checked through the gate
+///
+/// Guard a controller action with:
+///
+/// ```ignore
+/// gate().authorize(&auth_user, "update", &{model_var}).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct {policy_name};
+
+impl {policy_name} {{
+    pub fn new() -> Self {{
+        Self
+    }}
+}}
 
-#[derive(Debug, Clone)]
-pub struct {} {{
-    // Add policy-specific configuration here
+#[async_trait]
+impl Policy<{model_name}> for {policy_name} {{
+{methods}
 }}
+"#, model_var = to_snake_case(model_name))
+}
 
-impl {} {{
-    pub fn new() -> Self {{
-        Self {{}}
-    }}
+/// A denying `Policy` method for `ability`, to be filled in
+fn ability_method(ability: &str, model_name: &str) -> String {
+    let (description, takes_model) = match ability {
+        "view_any" => ("list models", false),
+        "view" => ("view the model", true),
+        "create" => ("create models", false),
+        "update" => ("update the model", true),
+        "delete" => ("delete the model", true),
+        "restore" => ("restore the soft-deleted model", true),
+        _ => ("permanently delete the model", true),
+    };
+    let model_param = if takes_model {
+        format!(", _model: &{}", model_name)
+    } else {
+        String::new()
+    };
 
-    // Determine whether the user can view any models
-    pub async fn view_any(&self, user: &User) -> Result<bool> {{
-        // Implement authorization logic
-        // Example: Check if user has admin role
-        Ok(user.is_admin() || user.has_permission("view_any"))
-    }}
+    format!(
+        "    /// Determine whether the user can {}\n    async fn {}(&self, _user: &AuthUser{}) -> Result<bool> {{\n        Ok(false)\n    }}",
+        description, ability, model_param,
+    )
+}
 
-    // Determine whether the user can view the model
-    pub async fn view(&self, user: &User, model: &{}) -> Result<bool> {{
-        // Implement authorization logic
-        // Example: User can view if they own it or have permission
-        Ok(user.id() == model.user_id ||
-           user.has_permission("view") ||
-           self.view_any(user).await?)
-    }}
+/// Controller guard to print once the policy is registered
+fn guard_snippet(model: &str) -> String {
+    format!(
+        "Guard controller actions with:\n\n    use crate::app::gate::gate;\n\n    gate().authorize(&auth_user, \"update\", &{}).await?;\n    gate().authorize_for::<{}>(&auth_user, \"create\").await?;",
+        to_snake_case(model),
+        model,
+    )
+}
 
-    // Determine whether the user can create models
-    pub async fn create(&self, user: &User) -> Result<bool> {{
-        // Implement authorization logic
-        // Example: Check if user has create permission
-        Ok(user.has_permission("create") ||
-           user.is_admin())
-    }}
+/// Add the policy to `AuthServiceProvider::register_policies` for its model
+fn register_policy(policy_name: &str, model: &str) -> Result<()> {
+    let model_module = to_snake_case(model);
 
-    // Determine whether the user can update the model
-    pub async fn update(&self, user: &User, model: &{}) -> Result<bool> {{
-        // Implement authorization logic
-        // Example: User can update if they own it or have permission
-        Ok(user.id() == model.user_id ||
-           user.has_permission("update") ||
-           user.is_admin())
-    }}
+    if !Path::new(&format!("src/app/models/{}.rs", model_module)).exists() {
+        println!("Model {} not found in src/app/models; register {} in {} yourself", model, policy_name, PROVIDER_PATH);
+        return Ok(());
+    }
 
-    // Determine whether the user can delete the model
-    pub async fn delete(&self, user: &User, model: &{}) -> Result<bool> {{
-        // Implement authorization logic
-        // Example: User can delete if they own it or have permission
-        Ok(user.id() == model.user_id ||
-           user.has_permission("delete") ||
-           user.is_admin())
-    }}
-}}
+    let provider = fs::read_to_string(PROVIDER_PATH)?;
+    let registration = registration_line(policy_name, model);
+    if provider.contains(registration.trim()) {
+        return Ok(());
+    }
 
-impl Default for {} {{
-    fn default() -> Self {{
-        Self::new()
-    }}
-}}
+    let marker = format!("    {}", crate::app::providers::auth_service_provider::REGISTRATION_MARKER);
+    let Some(position) = provider.find(&marker) else {
+        println!("Registration marker not found; register {} in {} yourself", policy_name, PROVIDER_PATH);
+        return Ok(());
+    };
 
-// User trait for authorization (you should implement this based on your User model)
-pub trait User {{
-    fn id(&self) -> &str;
-    fn is_admin(&self) -> bool;
-    fn has_permission(&self, permission: &str) -> bool;
-}}
-"#, model_name, policy_name, policy_name, model_name, model_name, model_name, policy_name)
+    let mut updated = provider;
+    updated.insert_str(position, &registration);
+    fs::write(PROVIDER_PATH, updated)?;
+
+    println!("Policy registered for {} in {}", model, PROVIDER_PATH);
+    Ok(())
+}
+
+fn registration_line(policy_name: &str, model: &str) -> String {
+    format!(
+        "    gate.policy::<crate::app::models::{}::{}, _>(crate::app::policies::{}::{}::new());\n",
+        to_snake_case(model),
+        model,
+        to_snake_case(policy_name),
+        policy_name,
+    )
 }
 
 fn update_policies_mod(policy_name: &str) -> Result<()> {
@@ -75,6 +75,106 @@ pub enum Commands {
     /// Broadcasting and WebSocket commands
     #[command(subcommand)]
     Broadcast(BroadcastCommands),
+    /// Remove stale and expired web push subscriptions
+    #[command(name = "webpush:prune")]
+    WebPushPrune {
+        /// Remove subscriptions not updated within this many days
+        #[arg(long, default_value = "30")]
+        days: i32,
+    },
+    /// Deliver scheduled messages that are due
+    #[command(name = "messages:dispatch-scheduled")]
+    MessagesDispatchScheduled {
+        /// Maximum number of messages to deliver per run
+        #[arg(long, default_value = "100")]
+        limit: i64,
+        /// Keep polling for due messages instead of exiting after one run
+        #[arg(long)]
+        watch: bool,
+        /// Seconds between polls when watching
+        #[arg(long, default_value = "10", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+    },
+    /// Run queued jobs and listeners from the database queue
+    #[command(name = "queue:work")]
+    QueueWork {
+        /// Queue to take jobs from
+        #[arg(long, default_value = "default")]
+        queue: String,
+        /// Jobs processed at the same time
+        #[arg(long, default_value = "1", value_parser = clap::value_parser!(u64).range(1..))]
+        concurrency: u64,
+        /// Process a single job and exit
+        #[arg(long)]
+        once: bool,
+    },
+    /// Show or change the log filter of a running server
+    #[command(name = "log:level")]
+    LogLevel {
+        /// Directive to apply, e.g. `rustaxum::app::services=debug`
+        directive: Option<String>,
+        /// Drop runtime overrides and restore the startup filter
+        #[arg(long)]
+        reset: bool,
+        /// Base URL of the running server (defaults to APP_URL)
+        #[arg(long)]
+        url: Option<String>,
+        /// Administrator access token
+        #[arg(long)]
+        token: String,
+    },
+    /// Import model records from a CSV file
+    #[command(name = "import:csv")]
+    ImportCsv {
+        /// Model to import (e.g., Country)
+        #[arg(long)]
+        model: String,
+        /// Path to the CSV file; the header row names the model fields
+        #[arg(long)]
+        file: String,
+        /// ID of the user recorded as creator of the imported records
+        #[arg(long)]
+        user: String,
+        /// Map a CSV header to a model field, e.g. `--map "Country Name=name"`
+        #[arg(long = "map")]
+        map: Vec<String>,
+        /// Rows inserted per transaction
+        #[arg(long, default_value = "500")]
+        batch_size: usize,
+        /// Validate every row without inserting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Run the registered cache warmers once
+    #[command(name = "cache:warm")]
+    CacheWarm,
+    /// Create or change a feature flag, or a user's override of it
+    #[command(name = "feature:set")]
+    FeatureSet {
+        /// Flag name, e.g. `new-dashboard`
+        name: String,
+        /// Turn the flag (or the user's override) on
+        #[arg(long, conflicts_with = "off")]
+        on: bool,
+        /// Turn the flag (or the user's override) off
+        #[arg(long)]
+        off: bool,
+        /// Percentage of users the flag is on for (0-100)
+        #[arg(long)]
+        rollout: Option<i32>,
+        /// Description shown by feature:list
+        #[arg(long)]
+        description: Option<String>,
+        /// Set an override for this user instead of the flag itself
+        #[arg(long)]
+        user: Option<String>,
+        /// Remove the user's override
+        #[arg(long, requires = "user", conflicts_with_all = ["on", "off"])]
+        clear: bool,
+    },
+    /// List feature flags
+    #[command(name = "feature:list")]
+    FeatureList,
 }
 
 #[derive(Subcommand)]
@@ -376,5 +476,13 @@ pub async fn run_cli(cli: Cli) -> Result<()> {
             BroadcastCommands::SystemAlert { level, message, action_required } => commands::broadcast::handle_system_alert_command(level, message, action_required).await,
             BroadcastCommands::Monitor { duration } => commands::broadcast::handle_broadcast_monitor_command(Some(duration)).await,
         },
+        Commands::WebPushPrune { days } => commands::webpush::handle_webpush_prune_command(days).await,
+        Commands::MessagesDispatchScheduled { limit, watch, interval } => commands::messages::handle_dispatch_scheduled_command(limit, watch, interval).await,
+        Commands::QueueWork { queue, concurrency, once } => commands::queue::handle_queue_work_command(queue, concurrency as usize, once).await,
+        Commands::LogLevel { directive, reset, url, token } => commands::log::handle_log_level_command(directive, reset, url, token).await,
+        Commands::ImportCsv { model, file, user, map, batch_size, dry_run } => commands::import::handle_import_csv_command(model, file, user, map, batch_size, dry_run).await,
+        Commands::CacheWarm => commands::cache::handle_cache_warm_command().await,
+        Commands::FeatureSet { name, on, off, rollout, description, user, clear } => commands::feature::handle_feature_set_command(name, on, off, rollout, description, user, clear).await,
+        Commands::FeatureList => commands::feature::handle_feature_list_command(),
     }
 }
\ No newline at end of file
@@ -0,0 +1,60 @@
+use anyhow::Result;
+use std::env;
+
+#[derive(Debug, Clone)]
+pub struct AccessLogConfig {
+    pub enabled: bool,
+    /// Log request and response bodies on every route
+    pub capture_bodies: bool,
+    /// Path prefixes whose bodies are logged even when `capture_bodies` is off
+    pub capture_paths: Vec<String>,
+    /// Bodies longer than this many bytes are truncated in the log
+    pub max_body_bytes: usize,
+    /// Bodies larger than this many bytes, or streamed without a known size, are not buffered
+    pub max_capture_bytes: usize,
+    /// Header names whose values are masked, compared case-insensitively
+    pub redact_headers: Vec<String>,
+    /// JSON, form and query field names whose values are masked, compared case-insensitively
+    pub redact_fields: Vec<String>,
+}
+
+impl AccessLogConfig {
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            enabled: env::var("ACCESS_LOG_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            capture_bodies: env::var("ACCESS_LOG_CAPTURE_BODIES")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            capture_paths: Self::list("ACCESS_LOG_CAPTURE_PATHS", ""),
+            max_body_bytes: env::var("ACCESS_LOG_MAX_BODY_BYTES")
+                .unwrap_or_else(|_| "4096".to_string())
+                .parse()
+                .unwrap_or(4096),
+            max_capture_bytes: env::var("ACCESS_LOG_MAX_CAPTURE_BYTES")
+                .unwrap_or_else(|_| "1048576".to_string())
+                .parse()
+                .unwrap_or(1_048_576),
+            redact_headers: Self::list(
+                "ACCESS_LOG_REDACT_HEADERS",
+                "authorization,proxy-authorization,cookie,set-cookie,x-api-key,x-csrf-token",
+            ),
+            redact_fields: Self::list(
+                "ACCESS_LOG_REDACT_FIELDS",
+                "password,password_confirmation,current_password,new_password,token,access_token,refresh_token,client_secret,secret",
+            ),
+        })
+    }
+
+    fn list(key: &str, default: &str) -> Vec<String> {
+        env::var(key)
+            .unwrap_or_else(|_| default.to_string())
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+}
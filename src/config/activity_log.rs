@@ -42,6 +42,9 @@ pub struct ActivityLogConfig {
 
     /// Maximum size of properties JSON (in bytes)
     pub max_properties_size: usize,
+
+    /// Activity log queries each user may run per minute (0 = unlimited)
+    pub query_rate_limit: i64,
 }
 
 impl ActivityLogConfig {
@@ -112,6 +115,11 @@ impl ActivityLogConfig {
             .parse::<usize>()
             .unwrap_or(65536);
 
+        let query_rate_limit = env::var("ACTIVITY_LOG_QUERY_RATE_LIMIT")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse::<i64>()
+            .unwrap_or(60);
+
         Ok(ActivityLogConfig {
             enabled,
             default_log_name,
@@ -126,6 +134,7 @@ impl ActivityLogConfig {
             excluded_models,
             log_properties,
             max_properties_size,
+            query_rate_limit,
         })
     }
 
@@ -191,6 +200,7 @@ impl Default for ActivityLogConfig {
             excluded_models: Vec::new(),
             log_properties: true,
             max_properties_size: 65536,
+            query_rate_limit: 60,
         }
     }
 }
@@ -10,6 +10,8 @@ pub struct AppConfig {
     pub port: u16,
     pub key: String,
     pub templates_path: String,
+    /// Fill OpenAPI schema examples from model factories
+    pub docs_factory_examples: bool,
 }
 
 impl AppConfig {
@@ -28,6 +30,10 @@ impl AppConfig {
                 .unwrap_or(3000),
             key: env::var("APP_KEY").unwrap_or_else(|_| "".to_string()),
             templates_path: env::var("TEMPLATES_PATH").unwrap_or_else(|_| "resources/views".to_string()),
+            docs_factory_examples: env::var("DOCS_FACTORY_EXAMPLES")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
         })
     }
 
@@ -11,6 +11,77 @@ pub struct AuthConfig {
     pub lockout_duration_minutes: u64,
     pub password_min_length: usize,
     pub require_email_verification: bool,
+    /// Minutes an email verification link stays valid
+    pub email_verification_expire_minutes: i64,
+    /// Minutes an impersonation token stays valid
+    pub impersonation_expire_minutes: i64,
+    pub new_device_challenge: NewDeviceChallenge,
+    /// Guards the `AuthUser` extractor tries, in order
+    pub guards: Vec<AuthGuard>,
+}
+
+/// When a login from an unrecognized client must be confirmed before tokens are issued
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NewDeviceChallenge {
+    /// Record new clients without challenging them
+    Off,
+    /// Challenge new clients once the user has at least one recognized client
+    Lenient,
+    /// Challenge every new client, including on a user's first login
+    Strict,
+}
+
+impl From<&str> for NewDeviceChallenge {
+    fn from(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "off" | "false" => NewDeviceChallenge::Off,
+            "strict" => NewDeviceChallenge::Strict,
+            _ => NewDeviceChallenge::Lenient,
+        }
+    }
+}
+
+/// A way a request can prove who its user is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthGuard {
+    /// Bearer token: an OAuth access token checked by `oauth_middleware`, or a first-party JWT
+    Token,
+    /// Authenticated web session
+    Session,
+}
+
+impl AuthGuard {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "token" | "api" | "jwt" | "oauth" => Some(AuthGuard::Token),
+            "session" | "web" => Some(AuthGuard::Session),
+            _ => None,
+        }
+    }
+
+    /// Parse a comma separated list, skipping unknown names and falling back
+    /// to token then session when nothing is left
+    pub fn parse_list(s: &str) -> Vec<Self> {
+        let mut guards = Vec::new();
+        for guard in s.split(',').filter_map(Self::parse) {
+            if !guards.contains(&guard) {
+                guards.push(guard);
+            }
+        }
+
+        if guards.is_empty() {
+            vec![AuthGuard::Token, AuthGuard::Session]
+        } else {
+            guards
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthGuard::Token => "token",
+            AuthGuard::Session => "session",
+        }
+    }
 }
 
 impl AuthConfig {
@@ -46,6 +117,21 @@ impl AuthConfig {
                 .unwrap_or_else(|_| "false".to_string())
                 .parse()
                 .unwrap_or(false),
+            email_verification_expire_minutes: env::var("EMAIL_VERIFICATION_EXPIRE_MINUTES")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .unwrap_or(60),
+            impersonation_expire_minutes: env::var("IMPERSONATION_EXPIRE_MINUTES")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .unwrap_or(60),
+            new_device_challenge: env::var("NEW_DEVICE_CHALLENGE")
+                .unwrap_or_else(|_| "lenient".to_string())
+                .as_str()
+                .into(),
+            guards: AuthGuard::parse_list(
+                &env::var("AUTH_GUARDS").unwrap_or_else(|_| "token,session".to_string())
+            ),
         })
     }
 
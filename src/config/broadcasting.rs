@@ -1,6 +1,12 @@
 use anyhow::Result;
 use std::env;
 
+/// Redis channel prefix used when `BROADCAST_CHANNELS_PREFIX` is unset or blank
+///
+/// Subscribers listen on `{prefix}*`, so an empty prefix would pick up every
+/// channel on the Redis server.
+pub const DEFAULT_CHANNELS_PREFIX: &str = "broadcast:";
+
 #[derive(Debug, Clone)]
 pub struct BroadcastingConfig {
     pub default_driver: String,
@@ -11,7 +17,22 @@ pub struct BroadcastingConfig {
     pub redis_port: u16,
     pub redis_password: Option<String>,
     pub redis_database: u8,
+    /// Prefix of the Redis channels broadcasts are published on, never empty
     pub channels_prefix: String,
+    /// Format of `/broadcasting/auth` responses: "native" or "pusher"
+    pub auth_protocol: String,
+    /// Application key sent in Pusher-compatible auth signatures
+    pub app_key: String,
+    /// Secret used to sign channel authorizations
+    pub app_secret: Option<String>,
+    /// Lifetime of native channel authorization tokens
+    pub auth_token_ttl_secs: i64,
+    /// Seconds between keep-alive comments on idle SSE streams, at least 1
+    pub sse_heartbeat_secs: u64,
+    /// Messages kept per channel for clients resuming with a last event id; 0 disables replay
+    pub replay_buffer_size: usize,
+    /// Seconds a buffered message stays replayable; 0 keeps it until pushed out
+    pub replay_ttl_secs: u64,
 }
 
 impl BroadcastingConfig {
@@ -42,11 +63,41 @@ impl BroadcastingConfig {
                 .unwrap_or_else(|_| "0".to_string())
                 .parse()
                 .unwrap_or(0),
-            channels_prefix: env::var("BROADCAST_CHANNELS_PREFIX")
+            channels_prefix: channels_prefix_or_default(
+                env::var("BROADCAST_CHANNELS_PREFIX").unwrap_or_default(),
+            ),
+            auth_protocol: env::var("BROADCAST_AUTH_PROTOCOL")
+                .unwrap_or_else(|_| "native".to_string()),
+            app_key: env::var("BROADCAST_APP_KEY")
                 .unwrap_or_else(|_| "".to_string()),
+            app_secret: env::var("BROADCAST_APP_SECRET").ok()
+                .filter(|secret| !secret.is_empty()),
+            auth_token_ttl_secs: env::var("BROADCAST_AUTH_TOKEN_TTL_SECS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .unwrap_or(300),
+            sse_heartbeat_secs: env::var("BROADCAST_SSE_HEARTBEAT_SECS")
+                .unwrap_or_else(|_| "15".to_string())
+                .parse()
+                .unwrap_or(15)
+                // A zero interval would make the keep-alive timer spin
+                .max(1),
+            replay_buffer_size: env::var("BROADCAST_REPLAY_BUFFER_SIZE")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse()
+                .unwrap_or(100),
+            replay_ttl_secs: env::var("BROADCAST_REPLAY_TTL_SECS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .unwrap_or(300),
         })
     }
 
+    /// Pattern matching every channel broadcasts are published on
+    pub fn channels_pattern(&self) -> String {
+        format!("{}*", channels_prefix_or_default(self.channels_prefix.clone()))
+    }
+
     pub fn redis_url(&self) -> String {
         if let Some(ref password) = self.redis_password {
             format!("redis://:{}@{}:{}/{}", password, self.redis_host, self.redis_port, self.redis_database)
@@ -54,4 +105,13 @@ impl BroadcastingConfig {
             format!("redis://{}:{}/{}", self.redis_host, self.redis_port, self.redis_database)
         }
     }
+}
+
+/// `prefix`, or the default prefix when it is blank
+pub fn channels_prefix_or_default(prefix: String) -> String {
+    if prefix.trim().is_empty() {
+        DEFAULT_CHANNELS_PREFIX.to_string()
+    } else {
+        prefix
+    }
 }
\ No newline at end of file
@@ -7,6 +7,16 @@ use std::env;
 pub struct CacheConfig {
     pub default: String,
     pub stores: HashMap<String, CacheStoreConfig>,
+    /// How long opt-in query builder results stay cached
+    pub query_ttl_secs: u64,
+    /// How long feature flag lookups stay cached
+    pub feature_ttl_secs: u64,
+    /// Run the registered cache warmers when the server starts
+    pub warm_on_boot: bool,
+    /// How long one warmer may run before it is abandoned
+    pub warm_timeout_secs: u64,
+    /// Re-run the warmers this often after boot; 0 warms only once
+    pub warm_interval_secs: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,7 +89,44 @@ impl CacheConfig {
             },
         );
 
-        Ok(CacheConfig { default, stores })
+        let query_ttl_secs = env::var("QUERY_CACHE_TTL_SECS")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse()
+            .unwrap_or(60);
+
+        let feature_ttl_secs = env::var("FEATURE_FLAG_CACHE_TTL_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .unwrap_or(30);
+
+        let warm_on_boot = env::var("CACHE_WARM_ON_BOOT")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse()
+            .unwrap_or(true);
+
+        let warm_timeout_secs = env::var("CACHE_WARM_TIMEOUT_SECS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .unwrap_or(10);
+
+        let warm_interval_secs = env::var("CACHE_WARM_INTERVAL_SECS")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse()
+            .unwrap_or(0);
+
+        Ok(CacheConfig {
+            default,
+            stores,
+            query_ttl_secs,
+            feature_ttl_secs,
+            warm_on_boot,
+            warm_timeout_secs,
+            warm_interval_secs,
+        })
+    }
+
+    pub fn warm_interval(&self) -> Option<std::time::Duration> {
+        (self.warm_interval_secs > 0).then(|| std::time::Duration::from_secs(self.warm_interval_secs))
     }
 
     pub fn get_store(&self, name: &str) -> Option<&CacheStoreConfig> {
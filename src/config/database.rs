@@ -1,5 +1,6 @@
 use anyhow::Result;
 use std::env;
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub struct DatabaseConfig {
@@ -14,6 +15,13 @@ pub struct DatabaseConfig {
     pub pool_acquire_timeout_seconds: u64,
     pub pool_idle_timeout_seconds: u64,
     pub pool_max_lifetime_seconds: u64,
+    pub connect_retries: u32,
+    pub connect_backoff_ms: u64,
+    pub connect_backoff_max_ms: u64,
+    /// Server-side limit for each statement on pooled connections, 0 turns it off
+    pub statement_timeout_ms: u64,
+    /// Longer limit that exports opt into, 0 turns it off
+    pub export_statement_timeout_ms: u64,
 }
 
 impl DatabaseConfig {
@@ -56,6 +64,34 @@ impl DatabaseConfig {
                 .unwrap_or_else(|_| "1800".to_string())
                 .parse()
                 .unwrap_or(1800),
+            connect_retries: env::var("DB_CONNECT_RETRIES")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+            connect_backoff_ms: env::var("DB_CONNECT_BACKOFF")
+                .unwrap_or_else(|_| "500".to_string())
+                .parse()
+                .unwrap_or(500),
+            connect_backoff_max_ms: env::var("DB_CONNECT_BACKOFF_MAX")
+                .unwrap_or_else(|_| "30000".to_string())
+                .parse()
+                .unwrap_or(30000),
+            statement_timeout_ms: env::var("DB_STATEMENT_TIMEOUT_MS")
+                .unwrap_or_else(|_| "15000".to_string())
+                .parse()
+                .unwrap_or(15000),
+            export_statement_timeout_ms: env::var("DB_EXPORT_STATEMENT_TIMEOUT_MS")
+                .unwrap_or_else(|_| "300000".to_string())
+                .parse()
+                .unwrap_or(300000),
         })
     }
+
+    pub fn statement_timeout(&self) -> Option<Duration> {
+        (self.statement_timeout_ms > 0).then(|| Duration::from_millis(self.statement_timeout_ms))
+    }
+
+    pub fn export_statement_timeout(&self) -> Option<Duration> {
+        (self.export_statement_timeout_ms > 0).then(|| Duration::from_millis(self.export_statement_timeout_ms))
+    }
 }
\ No newline at end of file
@@ -0,0 +1,59 @@
+use anyhow::Result;
+use std::env;
+
+#[derive(Debug, Clone)]
+pub struct HashingConfig {
+    /// Algorithm used for new password hashes
+    pub driver: HashDriver,
+    /// bcrypt cost factor (log2 of the number of rounds)
+    pub bcrypt_cost: u32,
+    /// Argon2 memory cost in KiB
+    pub argon_memory: u32,
+    /// Argon2 number of iterations
+    pub argon_time: u32,
+    /// Argon2 degree of parallelism
+    pub argon_threads: u32,
+}
+
+/// Password hashing algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashDriver {
+    Argon2id,
+    Bcrypt,
+}
+
+impl From<&str> for HashDriver {
+    fn from(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "bcrypt" => HashDriver::Bcrypt,
+            _ => HashDriver::Argon2id,
+        }
+    }
+}
+
+impl HashingConfig {
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            driver: env::var("HASH_DRIVER")
+                .unwrap_or_else(|_| "argon2id".to_string())
+                .as_str()
+                .into(),
+            bcrypt_cost: env::var("BCRYPT_COST")
+                .unwrap_or_else(|_| "12".to_string())
+                .parse()
+                .unwrap_or(12),
+            argon_memory: env::var("ARGON_MEMORY")
+                .unwrap_or_else(|_| "19456".to_string())
+                .parse()
+                .unwrap_or(19456),
+            argon_time: env::var("ARGON_TIME")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse()
+                .unwrap_or(2),
+            argon_threads: env::var("ARGON_THREADS")
+                .unwrap_or_else(|_| "1".to_string())
+                .parse()
+                .unwrap_or(1),
+        })
+    }
+}
@@ -0,0 +1,23 @@
+use anyhow::Result;
+use std::env;
+
+#[derive(Debug, Clone)]
+pub struct HealthConfig {
+    /// Answer `/ready` with 503 while migrations are pending
+    pub ready_check_migrations: bool,
+    /// Directory of `.up.sql`/`.down.sql` files compared against the `migrations` table
+    pub migrations_path: String,
+}
+
+impl HealthConfig {
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            ready_check_migrations: env::var("READY_CHECK_MIGRATIONS")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            migrations_path: env::var("MIGRATIONS_PATH")
+                .unwrap_or_else(|_| "./src/database/migrations".to_string()),
+        })
+    }
+}
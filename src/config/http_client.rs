@@ -0,0 +1,54 @@
+use anyhow::Result;
+use std::env;
+
+#[derive(Debug, Clone)]
+pub struct HttpClientConfig {
+    /// Total time allowed for a single request attempt
+    pub timeout_secs: u64,
+    pub connect_timeout_secs: u64,
+    /// Extra attempts for idempotent requests that fail with a 5xx or a connection error
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled on each further attempt
+    pub retry_delay_ms: u64,
+    pub retry_max_delay_ms: u64,
+    pub pool_max_idle_per_host: usize,
+    pub pool_idle_timeout_secs: u64,
+    pub user_agent: String,
+}
+
+impl HttpClientConfig {
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            timeout_secs: env::var("HTTP_CLIENT_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            connect_timeout_secs: env::var("HTTP_CLIENT_CONNECT_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+            max_retries: env::var("HTTP_CLIENT_MAX_RETRIES")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse()
+                .unwrap_or(2),
+            retry_delay_ms: env::var("HTTP_CLIENT_RETRY_DELAY_MS")
+                .unwrap_or_else(|_| "200".to_string())
+                .parse()
+                .unwrap_or(200),
+            retry_max_delay_ms: env::var("HTTP_CLIENT_RETRY_MAX_DELAY_MS")
+                .unwrap_or_else(|_| "5000".to_string())
+                .parse()
+                .unwrap_or(5000),
+            pool_max_idle_per_host: env::var("HTTP_CLIENT_POOL_MAX_IDLE_PER_HOST")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+            pool_idle_timeout_secs: env::var("HTTP_CLIENT_POOL_IDLE_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "90".to_string())
+                .parse()
+                .unwrap_or(90),
+            user_agent: env::var("HTTP_CLIENT_USER_AGENT")
+                .unwrap_or_else(|_| format!("rustaxum/{}", env!("CARGO_PKG_VERSION"))),
+        })
+    }
+}
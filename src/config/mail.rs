@@ -1,6 +1,80 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::env;
 
+/// Drivers that can override the default sender with `MAIL_{DRIVER}_FROM_ADDRESS` and friends
+pub const MAIL_DRIVERS: [&str; 3] = ["smtp", "mailgun", "log"];
+
+/// 25 MiB, the common limit of receiving mail servers
+pub const DEFAULT_MAX_ATTACHMENT_BYTES: u64 = 25 * 1024 * 1024;
+
+/// Attachment types allowed unless `MAIL_ALLOWED_ATTACHMENT_TYPES` says otherwise
+pub const DEFAULT_ALLOWED_ATTACHMENT_TYPES: &str = "application/pdf,application/zip,application/json,text/*,image/*,application/msword,application/vnd.ms-excel,application/vnd.openxmlformats-officedocument.wordprocessingml.document,application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+/// An email address with an optional display name, checked when it is built
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MailAddress {
+    pub address: String,
+    pub name: Option<String>,
+}
+
+impl MailAddress {
+    pub fn new(address: &str, name: Option<&str>) -> Result<Self> {
+        let address = address.trim();
+        address.parse::<lettre::Address>()
+            .with_context(|| format!("'{}' is not a valid email address", address))?;
+
+        Ok(Self {
+            address: address.to_string(),
+            name: name.map(str::trim).filter(|name| !name.is_empty()).map(str::to_string),
+        })
+    }
+
+    /// `Name <address>`, or the bare address without a name
+    pub fn mailbox(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{} <{}>", name, self.address),
+            None => self.address.clone(),
+        }
+    }
+}
+
+/// `from` and `reply_to` applied to messages that set neither
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MailDefaults {
+    pub from: Option<MailAddress>,
+    pub reply_to: Option<MailAddress>,
+}
+
+impl MailDefaults {
+    /// Read `{prefix}FROM_ADDRESS`, `{prefix}FROM_NAME`, `{prefix}REPLY_TO_ADDRESS` and `{prefix}REPLY_TO_NAME`
+    fn from_env(prefix: &str) -> Result<Self> {
+        Ok(Self {
+            from: address_from_env(&format!("{}FROM", prefix))?,
+            reply_to: address_from_env(&format!("{}REPLY_TO", prefix))?,
+        })
+    }
+
+    /// These defaults, falling back to `fallback` for whatever they leave unset
+    pub fn or(&self, fallback: &MailDefaults) -> MailDefaults {
+        MailDefaults {
+            from: self.from.clone().or_else(|| fallback.from.clone()),
+            reply_to: self.reply_to.clone().or_else(|| fallback.reply_to.clone()),
+        }
+    }
+}
+
+fn address_from_env(prefix: &str) -> Result<Option<MailAddress>> {
+    let Some(address) = env::var(format!("{}_ADDRESS", prefix)).ok().filter(|address| !address.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let name = env::var(format!("{}_NAME", prefix)).ok();
+
+    MailAddress::new(&address, name.as_deref())
+        .map(Some)
+        .with_context(|| format!("Invalid {}_ADDRESS", prefix))
+}
+
 #[derive(Debug, Clone)]
 pub struct MailConfig {
     pub mailer: String,
@@ -12,11 +86,37 @@ pub struct MailConfig {
     pub from_address: String,
     pub from_name: String,
     pub timeout_seconds: u64,
+    pub pool_size: u32,
+    pub pool_idle_timeout_seconds: u64,
+    pub pool_max_messages: u32,
+    /// Reply-to applied to messages that set none
+    pub reply_to: Option<MailAddress>,
+    /// Per-driver `from` and `reply_to`, taking precedence over the defaults above
+    pub driver_defaults: HashMap<String, MailDefaults>,
+    /// Directory `MailContent::Template` names are loaded from, as `{name}.hbs`
+    pub templates_path: String,
+    /// Largest combined size of a message's attachments, 0 for no limit
+    pub max_attachment_bytes: u64,
+    /// MIME types attachments may have, `image/*` allowing a whole family; empty allows every type
+    pub allowed_attachment_types: Vec<String>,
+    /// Sending domain for the Mailgun API driver; the driver is registered when it and the secret are set
+    pub mailgun_domain: String,
+    pub mailgun_secret: String,
+    /// `https://api.eu.mailgun.net` for domains in Mailgun's EU region
+    pub mailgun_endpoint: String,
 }
 
 impl MailConfig {
     pub fn from_env() -> Result<Self> {
-        Ok(MailConfig {
+        let mut driver_defaults = HashMap::new();
+        for driver in MAIL_DRIVERS {
+            let defaults = MailDefaults::from_env(&format!("MAIL_{}_", driver.to_uppercase()))?;
+            if defaults != MailDefaults::default() {
+                driver_defaults.insert(driver.to_string(), defaults);
+            }
+        }
+
+        let config = MailConfig {
             mailer: env::var("MAIL_MAILER").unwrap_or_else(|_| "smtp".to_string()),
             host: env::var("MAIL_HOST")
                 .unwrap_or_else(|_| "localhost".to_string()),
@@ -38,7 +138,55 @@ impl MailConfig {
                 .unwrap_or_else(|_| "30".to_string())
                 .parse()
                 .unwrap_or(30),
-        })
+            pool_size: env::var("MAIL_POOL_SIZE")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
+            pool_idle_timeout_seconds: env::var("MAIL_POOL_IDLE_TIMEOUT_SECONDS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .unwrap_or(60),
+            pool_max_messages: env::var("MAIL_POOL_MAX_MESSAGES")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse()
+                .unwrap_or(100),
+            reply_to: address_from_env("MAIL_REPLY_TO")?,
+            driver_defaults,
+            templates_path: env::var("MAIL_TEMPLATES_PATH")
+                .unwrap_or_else(|_| "resources/views/emails".to_string()),
+            max_attachment_bytes: env::var("MAIL_MAX_ATTACHMENT_BYTES")
+                .unwrap_or_else(|_| DEFAULT_MAX_ATTACHMENT_BYTES.to_string())
+                .parse()
+                .unwrap_or(DEFAULT_MAX_ATTACHMENT_BYTES),
+            allowed_attachment_types: env::var("MAIL_ALLOWED_ATTACHMENT_TYPES")
+                .unwrap_or_else(|_| DEFAULT_ALLOWED_ATTACHMENT_TYPES.to_string())
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            mailgun_domain: env::var("MAILGUN_DOMAIN").unwrap_or_default(),
+            mailgun_secret: env::var("MAILGUN_SECRET").unwrap_or_default(),
+            mailgun_endpoint: env::var("MAILGUN_ENDPOINT")
+                .unwrap_or_else(|_| "https://api.mailgun.net".to_string()),
+        };
+
+        // Fail at startup rather than on the first send
+        MailAddress::new(&config.from_address, Some(&config.from_name))
+            .context("Invalid MAIL_FROM_ADDRESS")?;
+
+        Ok(config)
+    }
+
+    /// The `from` and `reply_to` for drivers without their own
+    pub fn defaults(&self) -> MailDefaults {
+        MailDefaults {
+            from: MailAddress::new(&self.from_address, Some(&self.from_name)).ok(),
+            reply_to: self.reply_to.clone(),
+        }
+    }
+
+    pub fn has_mailgun(&self) -> bool {
+        !self.mailgun_domain.is_empty() && !self.mailgun_secret.is_empty()
     }
 
     pub fn use_tls(&self) -> bool {
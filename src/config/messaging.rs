@@ -0,0 +1,92 @@
+use anyhow::Result;
+use std::env;
+use std::sync::OnceLock;
+
+static MESSAGING_CONFIG: OnceLock<MessagingConfig> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+pub struct MessagingConfig {
+    pub max_pinned_messages: i64,
+    pub pin_requires_moderator: bool,
+    pub presence_timeout_secs: i64,
+    pub presence_sweep_interval_secs: u64,
+    pub prekey_low_threshold: i64,
+    pub session_backup_max_bytes: usize,
+    pub session_backup_quota: i64,
+    /// Seconds between scheduled message dispatch runs in the server; 0 leaves it to the CLI
+    pub scheduled_dispatch_interval_secs: u64,
+    pub scheduled_dispatch_limit: i64,
+}
+
+impl MessagingConfig {
+    pub fn from_env() -> Result<Self> {
+        let max_pinned_messages = env::var("MESSAGING_MAX_PINNED_MESSAGES")
+            .unwrap_or_else(|_| "50".to_string())
+            .parse::<i64>()
+            .unwrap_or(50);
+
+        // In group conversations and channels only owners, admins, and moderators may pin
+        let pin_requires_moderator = env::var("MESSAGING_PIN_REQUIRES_MODERATOR")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse::<bool>()
+            .unwrap_or(true);
+
+        // Devices without a WebSocket heartbeat for this long are marked offline
+        let presence_timeout_secs = env::var("MESSAGING_PRESENCE_TIMEOUT_SECS")
+            .unwrap_or_else(|_| "90".to_string())
+            .parse::<i64>()
+            .unwrap_or(90);
+
+        let presence_sweep_interval_secs = env::var("MESSAGING_PRESENCE_SWEEP_INTERVAL_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse::<u64>()
+            .unwrap_or(30);
+
+        // Devices are asked to upload more one-time prekeys below this many
+        let prekey_low_threshold = env::var("MESSAGING_PREKEY_LOW_THRESHOLD")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse::<i64>()
+            .unwrap_or(10);
+
+        // Largest encrypted session backup a device may upload; keep below the request body limit
+        let session_backup_max_bytes = env::var("MESSAGING_SESSION_BACKUP_MAX_BYTES")
+            .unwrap_or_else(|_| "1048576".to_string())
+            .parse::<usize>()
+            .unwrap_or(1048576);
+
+        // Unexpired session backups a user may keep at once
+        let session_backup_quota = env::var("MESSAGING_SESSION_BACKUP_QUOTA")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse::<i64>()
+            .unwrap_or(5);
+
+        // The server delivers due scheduled messages on this interval; 0 disables it so
+        // `messages:dispatch-scheduled --watch` can run as a separate process instead
+        let scheduled_dispatch_interval_secs = env::var("MESSAGING_SCHEDULED_DISPATCH_INTERVAL_SECS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse::<u64>()
+            .unwrap_or(10);
+
+        let scheduled_dispatch_limit = env::var("MESSAGING_SCHEDULED_DISPATCH_LIMIT")
+            .unwrap_or_else(|_| "100".to_string())
+            .parse::<i64>()
+            .unwrap_or(100);
+
+        Ok(MessagingConfig {
+            max_pinned_messages,
+            pin_requires_moderator,
+            presence_timeout_secs,
+            presence_sweep_interval_secs,
+            prekey_low_threshold,
+            session_backup_max_bytes,
+            session_backup_quota,
+            scheduled_dispatch_interval_secs,
+            scheduled_dispatch_limit,
+        })
+    }
+
+    /// Settings read once for the process
+    pub fn global() -> &'static MessagingConfig {
+        MESSAGING_CONFIG.get_or_init(|| Self::from_env().expect("messaging config is read from the environment"))
+    }
+}
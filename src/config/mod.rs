@@ -18,6 +18,18 @@ pub mod notifications;
 pub mod activity_log;
 pub mod session;
 pub mod csrf;
+pub mod messaging;
+pub mod http_client;
+pub mod tenancy;
+pub mod response;
+pub mod hashing;
+pub mod access_log;
+pub mod trusted_proxies;
+pub mod performance;
+pub mod request_timeout;
+pub mod query_builder;
+pub mod health;
+pub mod queue;
 
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -36,6 +48,18 @@ pub struct Config {
     pub activity_log: activity_log::ActivityLogConfig,
     pub session: session::SessionConfig,
     pub csrf: csrf::CSRFConfig,
+    pub messaging: messaging::MessagingConfig,
+    pub http_client: http_client::HttpClientConfig,
+    pub tenancy: tenancy::TenancyConfig,
+    pub response: response::ResponseConfig,
+    pub hashing: hashing::HashingConfig,
+    pub access_log: access_log::AccessLogConfig,
+    pub trusted_proxies: trusted_proxies::TrustedProxiesConfig,
+    pub performance: performance::PerformanceConfig,
+    pub request_timeout: request_timeout::RequestTimeoutConfig,
+    pub query_builder: query_builder::QueryBuilderConfig,
+    pub health: health::HealthConfig,
+    pub queue: queue::QueueConfig,
 }
 
 impl Config {
@@ -58,6 +82,18 @@ impl Config {
             activity_log: activity_log::ActivityLogConfig::from_env()?,
             session: session::SessionConfig::from_env()?,
             csrf: csrf::CSRFConfig::from_env()?,
+            messaging: messaging::MessagingConfig::from_env()?,
+            http_client: http_client::HttpClientConfig::from_env()?,
+            tenancy: tenancy::TenancyConfig::from_env()?,
+            response: response::ResponseConfig::from_env()?,
+            hashing: hashing::HashingConfig::from_env()?,
+            access_log: access_log::AccessLogConfig::from_env()?,
+            trusted_proxies: trusted_proxies::TrustedProxiesConfig::from_env()?,
+            performance: performance::PerformanceConfig::from_env()?,
+            request_timeout: request_timeout::RequestTimeoutConfig::from_env()?,
+            query_builder: query_builder::QueryBuilderConfig::from_env()?,
+            health: health::HealthConfig::from_env()?,
+            queue: queue::QueueConfig::from_env()?,
         })
     }
 
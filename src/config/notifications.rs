@@ -10,6 +10,10 @@ pub struct NotificationsConfig {
     pub sms_provider: String,
     pub sms_api_key: Option<String>,
     pub notification_preferences_enabled: bool,
+    /// Recipients per queued job when notifying many notifiables
+    pub batch_size: usize,
+    /// Seconds between consecutive batch jobs, to stay under provider rate limits
+    pub batch_delay_seconds: u64,
 }
 
 impl NotificationsConfig {
@@ -37,6 +41,14 @@ impl NotificationsConfig {
                 .unwrap_or_else(|_| "true".to_string())
                 .parse()
                 .unwrap_or(true),
+            batch_size: env::var("NOTIFICATIONS_BATCH_SIZE")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse()
+                .unwrap_or(100),
+            batch_delay_seconds: env::var("NOTIFICATIONS_BATCH_DELAY_SECONDS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .unwrap_or(0),
         })
     }
 
@@ -7,6 +7,16 @@ pub struct OAuthConfig {
     pub access_token_ttl: u64,
     pub refresh_token_ttl: u64,
     pub auth_code_ttl: u64,
+    /// Token requests allowed per client each minute
+    pub token_rate_limit_per_client: i64,
+    /// Token requests allowed per client IP each minute
+    pub token_rate_limit_per_ip: i64,
+    /// Failed client authentications from one IP before the client is locked out there
+    pub client_auth_max_failures: i64,
+    /// First lockout after `client_auth_max_failures`; doubles with each further failure
+    pub client_auth_lockout_secs: u64,
+    /// Longest lockout, and how long failures are remembered
+    pub client_auth_lockout_max_secs: u64,
 }
 
 impl OAuthConfig {
@@ -26,6 +36,26 @@ impl OAuthConfig {
                 .unwrap_or_else(|_| "600".to_string())
                 .parse()
                 .unwrap_or(600),
+            token_rate_limit_per_client: env::var("OAUTH_TOKEN_RATE_LIMIT_PER_CLIENT")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .unwrap_or(60),
+            token_rate_limit_per_ip: env::var("OAUTH_TOKEN_RATE_LIMIT_PER_IP")
+                .unwrap_or_else(|_| "120".to_string())
+                .parse()
+                .unwrap_or(120),
+            client_auth_max_failures: env::var("OAUTH_CLIENT_AUTH_MAX_FAILURES")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
+            client_auth_lockout_secs: env::var("OAUTH_CLIENT_AUTH_LOCKOUT_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            client_auth_lockout_max_secs: env::var("OAUTH_CLIENT_AUTH_LOCKOUT_MAX_SECS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse()
+                .unwrap_or(3600),
         })
     }
 }
\ No newline at end of file
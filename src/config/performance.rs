@@ -0,0 +1,34 @@
+use anyhow::Result;
+use std::env;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct PerformanceConfig {
+    /// Requests slower than this many milliseconds are logged as warnings, 0 turns it off
+    pub slow_request_ms: u64,
+    /// Queries slower than this many milliseconds are logged as warnings, 0 turns it off
+    pub slow_query_ms: u64,
+}
+
+impl PerformanceConfig {
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            slow_request_ms: env::var("SLOW_REQUEST_MS")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .unwrap_or(1000),
+            slow_query_ms: env::var("SLOW_QUERY_MS")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse()
+                .unwrap_or(100),
+        })
+    }
+
+    pub fn slow_request_threshold(&self) -> Option<Duration> {
+        (self.slow_request_ms > 0).then(|| Duration::from_millis(self.slow_request_ms))
+    }
+
+    pub fn slow_query_threshold(&self) -> Option<Duration> {
+        (self.slow_query_ms > 0).then(|| Duration::from_millis(self.slow_query_ms))
+    }
+}
@@ -0,0 +1,58 @@
+use anyhow::Result;
+use std::env;
+use std::sync::OnceLock;
+
+static QUERY_BUILDER_CONFIG: OnceLock<QueryBuilderConfig> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+pub struct QueryBuilderConfig {
+    /// Page size for models that do not set `Queryable::default_per_page`
+    pub default_per_page: u32,
+    /// Largest page for models that do not set `Queryable::max_per_page`
+    pub max_per_page: u32,
+    /// Answer 400 when `per_page` exceeds the maximum instead of clamping it
+    pub strict_per_page: bool,
+    /// Most relationships an include may chain, e.g. 3 for `createdBy.organizations.position`
+    pub max_include_depth: usize,
+    /// Most relationships one request may include
+    pub max_includes: usize,
+    /// Answer 400 for unknown operators, wrong value counts and disallowed
+    /// fields instead of reading the filter as `eq` or dropping it
+    pub strict_filters: bool,
+}
+
+impl QueryBuilderConfig {
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            default_per_page: env::var("QUERY_DEFAULT_PER_PAGE")
+                .unwrap_or_else(|_| "15".to_string())
+                .parse()
+                .unwrap_or(15),
+            max_per_page: env::var("QUERY_MAX_PER_PAGE")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse()
+                .unwrap_or(100),
+            strict_per_page: env::var("QUERY_STRICT_PER_PAGE")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            max_include_depth: env::var("QUERY_MAX_INCLUDE_DEPTH")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .unwrap_or(3),
+            max_includes: env::var("QUERY_MAX_INCLUDES")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+            strict_filters: env::var("QUERY_STRICT_FILTERS")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+        })
+    }
+
+    /// Settings read once for the process
+    pub fn global() -> &'static QueryBuilderConfig {
+        QUERY_BUILDER_CONFIG.get_or_init(|| Self::from_env().expect("query builder config is read from the environment"))
+    }
+}
@@ -0,0 +1,22 @@
+use anyhow::Result;
+use std::env;
+
+#[derive(Debug, Clone)]
+pub struct QueueConfig {
+    /// Driver behind the default queue connection: `database` keeps jobs across restarts, `memory` does not
+    pub connection: String,
+    /// Seconds a reserved job may stay `processing` before another worker reclaims it
+    pub retry_after: u64,
+}
+
+impl QueueConfig {
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            connection: env::var("QUEUE_CONNECTION").unwrap_or_else(|_| "database".to_string()),
+            retry_after: env::var("QUEUE_RETRY_AFTER")
+                .unwrap_or_else(|_| "360".to_string())
+                .parse()
+                .unwrap_or(360),
+        })
+    }
+}
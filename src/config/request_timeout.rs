@@ -0,0 +1,37 @@
+use anyhow::Result;
+use std::env;
+
+#[derive(Debug, Clone)]
+pub struct RequestTimeoutConfig {
+    /// Seconds a handler may run before the request is answered with 504, 0 turns it off
+    pub timeout_secs: u64,
+    /// Path prefixes with their own limit, e.g. `/api/exports=120`; 0 turns it off for the prefix
+    pub overrides: Vec<(String, u64)>,
+    /// Path prefixes that are never timed out, such as streaming routes
+    pub exclude: Vec<String>,
+}
+
+impl RequestTimeoutConfig {
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            timeout_secs: env::var("REQUEST_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            overrides: env::var("REQUEST_TIMEOUT_OVERRIDES")
+                .unwrap_or_default()
+                .split(',')
+                .filter_map(|entry| {
+                    let (prefix, secs) = entry.split_once('=')?;
+                    Some((prefix.trim().to_string(), secs.trim().parse().ok()?))
+                })
+                .collect(),
+            exclude: env::var("REQUEST_TIMEOUT_EXCLUDE")
+                .unwrap_or_else(|_| "/sse,/ws".to_string())
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,89 @@
+use anyhow::Result;
+use std::env;
+
+#[derive(Debug, Clone)]
+pub struct ResponseConfig {
+    /// Wrap response bodies in a `data` key unless a request asks otherwise;
+    /// `None` keeps lists wrapped and single resources bare
+    pub wrap_data: Option<bool>,
+    /// How ULID ids are written in JSON unless a request asks otherwise
+    pub id_format: IdFormat,
+    /// How `DateTime<Utc>` fields are written in JSON unless a request asks otherwise
+    pub timestamp_format: TimestampFormat,
+}
+
+/// JSON representation of a ULID id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdFormat {
+    /// Canonical 26-character Crockford base32, e.g. `01ARZ3NDEKTSV4RRFFQ69G5FAV`
+    #[default]
+    Ulid,
+    /// Hyphenated UUID with the same 128 bits
+    Uuid,
+    /// Canonical ULID in lowercase
+    Lowercase,
+}
+
+impl IdFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "ulid" => Some(Self::Ulid),
+            "uuid" => Some(Self::Uuid),
+            "lowercase" | "ulid-lowercase" => Some(Self::Lowercase),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for IdFormat {
+    fn from(value: &str) -> Self {
+        Self::parse(value).unwrap_or_default()
+    }
+}
+
+/// JSON representation of a UTC timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampFormat {
+    /// RFC 3339 string, e.g. `2024-01-15T09:30:00Z`
+    #[default]
+    Rfc3339,
+    /// Whole seconds since the Unix epoch
+    EpochSeconds,
+    /// Milliseconds since the Unix epoch
+    EpochMillis,
+}
+
+impl TimestampFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().replace('-', "_").as_str() {
+            "rfc3339" | "iso8601" => Some(Self::Rfc3339),
+            "epoch" | "epoch_seconds" | "unix" => Some(Self::EpochSeconds),
+            "epoch_millis" | "epoch_ms" | "unix_ms" => Some(Self::EpochMillis),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for TimestampFormat {
+    fn from(value: &str) -> Self {
+        Self::parse(value).unwrap_or_default()
+    }
+}
+
+impl ResponseConfig {
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            wrap_data: env::var("RESPONSE_WRAP_DATA")
+                .ok()
+                .and_then(|value| value.parse().ok()),
+            id_format: env::var("RESPONSE_ID_FORMAT")
+                .unwrap_or_else(|_| "ulid".to_string())
+                .as_str()
+                .into(),
+            timestamp_format: env::var("RESPONSE_TIMESTAMP_FORMAT")
+                .unwrap_or_else(|_| "rfc3339".to_string())
+                .as_str()
+                .into(),
+        })
+    }
+}
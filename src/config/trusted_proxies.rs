@@ -0,0 +1,22 @@
+use anyhow::Result;
+use std::env;
+
+#[derive(Debug, Clone)]
+pub struct TrustedProxiesConfig {
+    /// Proxy addresses or CIDR ranges whose forwarding headers are believed;
+    /// `*` trusts every peer
+    pub proxies: Vec<String>,
+}
+
+impl TrustedProxiesConfig {
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            proxies: env::var("TRUSTED_PROXIES")
+                .unwrap_or_default()
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+        })
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use std::env;
 
 #[derive(Debug, Clone)]
@@ -6,6 +7,11 @@ pub struct WebPushConfig {
     pub vapid_private_key: Option<String>,
     pub vapid_public_key: Option<String>,
     pub vapid_subject: String,
+    /// Previous VAPID key pair, still accepted while a key rotation is in progress
+    pub vapid_previous_private_key: Option<String>,
+    pub vapid_previous_public_key: Option<String>,
+    /// End of the rotation grace period; after this the previous key pair is ignored
+    pub vapid_rotation_grace_until: Option<DateTime<Utc>>,
 }
 
 impl WebPushConfig {
@@ -15,6 +21,12 @@ impl WebPushConfig {
             vapid_public_key: env::var("VAPID_PUBLIC_KEY").ok(),
             vapid_subject: env::var("VAPID_SUBJECT")
                 .unwrap_or_else(|_| "mailto:admin@rustaxum.com".to_string()),
+            vapid_previous_private_key: env::var("VAPID_PREVIOUS_PRIVATE_KEY").ok(),
+            vapid_previous_public_key: env::var("VAPID_PREVIOUS_PUBLIC_KEY").ok(),
+            vapid_rotation_grace_until: env::var("VAPID_ROTATION_GRACE_UNTIL")
+                .ok()
+                .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+                .map(|value| value.with_timezone(&Utc)),
         })
     }
 
@@ -25,4 +37,13 @@ impl WebPushConfig {
     pub fn get_public_key(&self) -> Option<&str> {
         self.vapid_public_key.as_deref()
     }
-}
\ No newline at end of file
+
+    /// Whether the previous key pair should still be used to sign requests
+    pub fn is_rotation_in_grace_period(&self) -> bool {
+        self.vapid_previous_private_key.is_some()
+            && self.vapid_previous_public_key.is_some()
+            && self.vapid_rotation_grace_until
+                .map(|until| Utc::now() < until)
+                .unwrap_or(false)
+    }
+}
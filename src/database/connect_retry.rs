@@ -0,0 +1,79 @@
+use anyhow::Result;
+use diesel::pg::PgConnection;
+use diesel::{Connection, RunQueryDsl};
+use std::future::Future;
+use std::time::Duration;
+
+use crate::config::database::DatabaseConfig;
+
+/// Bounded exponential backoff for reaching the database at startup
+///
+/// Containers often start before Postgres accepts connections, so the first
+/// attempt is retried up to `retries` more times, waiting `backoff` and then
+/// twice as long each time, never more than `max_backoff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectRetry {
+    pub retries: u32,
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl ConnectRetry {
+    pub fn from_config(config: &DatabaseConfig) -> Self {
+        Self {
+            retries: config.connect_retries,
+            backoff: Duration::from_millis(config.connect_backoff_ms),
+            max_backoff: Duration::from_millis(config.connect_backoff_max_ms),
+        }
+    }
+
+    /// Wait before retry number `retry`, counting from 1
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let factor = 1u32.checked_shl(retry.saturating_sub(1)).unwrap_or(u32::MAX);
+        self.backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+
+    /// Run `check` until it succeeds or the retries are used up, returning its last error
+    pub async fn run<T, F, Fut>(&self, mut check: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let attempts = self.retries.saturating_add(1);
+        let mut attempt = 1;
+
+        loop {
+            tracing::info!("Connecting to database (attempt {}/{})", attempt, attempts);
+            match check().await {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt < attempts => {
+                    let delay = self.delay_for(attempt);
+                    tracing::warn!("Database not reachable: {}. Retrying in {:?}", e, delay);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    tracing::error!("Database not reachable after {} attempts: {}", attempts, e);
+                    return Err(e.context(format!("database not reachable after {} attempts", attempts)));
+                }
+            }
+        }
+    }
+}
+
+/// Wait until the database answers `SELECT 1`, retrying per `DB_CONNECT_RETRIES`/`DB_CONNECT_BACKOFF`
+pub async fn wait_for_database(config: &DatabaseConfig) -> Result<()> {
+    ConnectRetry::from_config(config)
+        .run(|| {
+            let url = config.url.clone();
+            async move {
+                tokio::task::spawn_blocking(move || -> Result<()> {
+                    let mut conn = PgConnection::establish(&url)?;
+                    diesel::sql_query("SELECT 1").execute(&mut conn)?;
+                    Ok(())
+                })
+                .await?
+            }
+        })
+        .await
+}
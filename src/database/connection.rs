@@ -4,8 +4,9 @@ use std::sync::OnceLock;
 
 static DB_POOL: OnceLock<DbPool> = OnceLock::new();
 
+/// Set the global pool; when it is already set the first pool is kept
 pub fn initialize_pool(pool: DbPool) {
-    DB_POOL.set(pool).expect("Database pool already initialized");
+    let _ = DB_POOL.set(pool);
 }
 
 pub async fn get_connection() -> Result<&'static DbPool> {
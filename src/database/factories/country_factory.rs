@@ -0,0 +1,58 @@
+use rand::Rng;
+
+use super::Factory;
+use crate::app::http::requests::country_requests::{CreateCountryRequest, UpdateCountryRequest};
+use crate::app::models::country::{Country, CountryResponse, CreateCountry};
+use crate::app::utils::fake;
+
+fn iso_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..2).map(|_| rng.gen_range(b'A'..=b'Z') as char).collect()
+}
+
+fn phone_code() -> String {
+    format!("+{}", rand::thread_rng().gen_range(1..=999))
+}
+
+impl Factory for Country {
+    fn definition() -> Self {
+        Country::new(fake::country(), iso_code(), Some(phone_code()), &fake::ulid().to_string())
+    }
+}
+
+impl Factory for CountryResponse {
+    fn definition() -> Self {
+        Country::definition().to_response()
+    }
+}
+
+impl Factory for CreateCountry {
+    fn definition() -> Self {
+        CreateCountry {
+            name: fake::country(),
+            iso_code: iso_code(),
+            phone_code: Some(phone_code()),
+        }
+    }
+}
+
+impl Factory for CreateCountryRequest {
+    fn definition() -> Self {
+        CreateCountryRequest {
+            name: fake::country(),
+            iso_code: iso_code(),
+            phone_code: Some(phone_code()),
+        }
+    }
+}
+
+impl Factory for UpdateCountryRequest {
+    fn definition() -> Self {
+        UpdateCountryRequest {
+            name: Some(fake::country()),
+            iso_code: None,
+            phone_code: None,
+        }
+    }
+}
+
@@ -0,0 +1,29 @@
+//! Model factories
+//!
+//! A factory describes a realistic instance of a model, like a Laravel
+//! factory's `definition()`. The API docs use them to generate request and
+//! response examples, so payloads in Swagger UI follow the models without
+//! hand-written `#[schema(example = ...)]` attributes.
+
+pub mod country_factory;
+pub mod user_factory;
+
+use serde::Serialize;
+
+pub trait Factory: Serialize + Sized {
+    /// A new instance with fake attributes
+    fn definition() -> Self;
+
+    fn make() -> Self {
+        Self::definition()
+    }
+
+    fn make_many(count: usize) -> Vec<Self> {
+        (0..count).map(|_| Self::definition()).collect()
+    }
+
+    /// JSON of a fresh instance, as used for OpenAPI examples
+    fn example() -> serde_json::Value {
+        serde_json::to_value(Self::definition()).unwrap_or(serde_json::Value::Null)
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::Utc;
+
+use super::Factory;
+use crate::app::http::requests::auth_requests::RegisterRequest;
+use crate::app::models::user::UserResponse;
+use crate::app::utils::fake;
+
+impl Factory for UserResponse {
+    fn definition() -> Self {
+        let now = Utc::now();
+        UserResponse {
+            id: fake::ulid(),
+            name: fake::name(),
+            email: fake::email(),
+            email_verified_at: Some(now),
+            last_login_at: Some(now),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+impl Factory for RegisterRequest {
+    fn definition() -> Self {
+        let password = "SecurePass123!".to_string();
+        RegisterRequest {
+            name: fake::name(),
+            email: fake::email(),
+            password_confirmation: password.clone(),
+            password,
+        }
+    }
+}
@@ -24,6 +24,20 @@ pub struct MigrationRunner {
     migrations_path: String,
 }
 
+/// Applied and pending migrations, as `migrate:status` reports them
+#[derive(Debug)]
+pub struct MigrationStatus {
+    pub executed: Vec<Migration>,
+    pub pending: Vec<MigrationFile>,
+}
+
+impl MigrationStatus {
+    /// Whether every migration file has been applied
+    pub fn is_up_to_date(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
 #[derive(Debug)]
 pub struct MigrationFile {
     pub filename: String,
@@ -247,11 +261,18 @@ impl MigrationRunner {
         Ok(())
     }
 
-    pub fn show_status(&self) -> Result<()> {
+    /// Applied migrations and the migration files not yet applied
+    pub fn status(&self) -> Result<MigrationStatus> {
         self.ensure_migrations_table()?;
 
-        let executed = self.get_executed_migrations()?;
-        let pending = self.get_pending_migrations()?;
+        Ok(MigrationStatus {
+            executed: self.get_executed_migrations()?,
+            pending: self.get_pending_migrations()?,
+        })
+    }
+
+    pub fn show_status(&self) -> Result<()> {
+        let MigrationStatus { executed, pending } = self.status()?;
 
         println!("\n📊 Migration Status");
         println!("==================");
@@ -0,0 +1,7 @@
+-- Revert poll option tracking
+
+ALTER TABLE poll_votes
+DROP COLUMN IF EXISTS option_indexes;
+
+ALTER TABLE polls
+DROP COLUMN IF EXISTS option_count;
@@ -0,0 +1,11 @@
+-- Track poll options and selected option indexes so results can be aggregated server-side
+-- while the question, option labels, and vote payloads stay encrypted
+
+ALTER TABLE polls
+ADD COLUMN IF NOT EXISTS option_count INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE poll_votes
+ADD COLUMN IF NOT EXISTS option_indexes INTEGER[] NOT NULL DEFAULT '{}';
+
+COMMENT ON COLUMN polls.option_count IS 'Number of options in the encrypted option list - bounds valid vote option indexes';
+COMMENT ON COLUMN poll_votes.option_indexes IS 'Zero-based indexes of the selected options - single element unless the poll allows multiple votes';
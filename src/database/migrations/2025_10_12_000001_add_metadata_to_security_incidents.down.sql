@@ -0,0 +1,23 @@
+-- Revert security incident metadata
+
+DROP INDEX IF EXISTS idx_security_incidents_severity_created_at;
+
+ALTER TABLE security_incidents
+DROP COLUMN IF EXISTS metadata;
+
+DELETE FROM security_incidents
+WHERE incident_type NOT IN (
+    'key_compromise', 'session_reset', 'device_unauthorized',
+    'decryption_failure', 'verification_failure', 'replay_attack_detected',
+    'algorithm_downgrade_attempt', 'multiple_failed_auth'
+);
+
+ALTER TABLE security_incidents
+DROP CONSTRAINT IF EXISTS security_incidents_incident_type_check;
+
+ALTER TABLE security_incidents
+ADD CONSTRAINT security_incidents_incident_type_check CHECK (incident_type IN (
+    'key_compromise', 'session_reset', 'device_unauthorized',
+    'decryption_failure', 'verification_failure', 'replay_attack_detected',
+    'algorithm_downgrade_attempt', 'multiple_failed_auth'
+));
@@ -0,0 +1,22 @@
+-- Record server-detected incidents (failed logins, refresh token reuse, certificate mismatches)
+-- alongside the client-reported ones, with plaintext metadata for investigation
+
+ALTER TABLE security_incidents
+DROP CONSTRAINT IF EXISTS security_incidents_incident_type_check;
+
+ALTER TABLE security_incidents
+ADD CONSTRAINT security_incidents_incident_type_check CHECK (incident_type IN (
+    'key_compromise', 'session_reset', 'device_unauthorized',
+    'decryption_failure', 'verification_failure', 'replay_attack_detected',
+    'algorithm_downgrade_attempt', 'multiple_failed_auth',
+    'unauthorized_access', 'session_hijack', 'message_intercept', 'device_compromise',
+    'protocol_violation', 'cryptographic_failure', 'tampering_detected', 'replay_attack',
+    'man_in_the_middle', 'token_reuse', 'certificate_mismatch'
+));
+
+ALTER TABLE security_incidents
+ADD COLUMN IF NOT EXISTS metadata JSONB NOT NULL DEFAULT '{}';
+
+CREATE INDEX IF NOT EXISTS idx_security_incidents_severity_created_at ON security_incidents (severity, created_at DESC);
+
+COMMENT ON COLUMN security_incidents.metadata IS 'Server-side context of the incident (never message content or secrets)';
@@ -0,0 +1,20 @@
+-- Browser/client fingerprints users have logged in from, so logins from
+-- unrecognized clients can require step-up confirmation
+CREATE TABLE login_fingerprints (
+    id CHAR(26) PRIMARY KEY,
+    user_id CHAR(26) NOT NULL REFERENCES sys_users(id) ON DELETE CASCADE,
+    fingerprint_hash VARCHAR(64) NOT NULL,
+    user_agent TEXT,
+    ip_address TEXT,
+    confirmed_at TIMESTAMPTZ,
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE(user_id, fingerprint_hash)
+);
+
+CREATE INDEX idx_login_fingerprints_user_confirmed ON login_fingerprints(user_id, confirmed_at);
+
+COMMENT ON COLUMN login_fingerprints.fingerprint_hash IS 'SHA-256 of the User-Agent, Accept headers and client hints';
+COMMENT ON COLUMN login_fingerprints.confirmed_at IS 'NULL while the login from this fingerprint awaits step-up confirmation';
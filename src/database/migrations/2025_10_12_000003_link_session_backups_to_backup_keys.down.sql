@@ -0,0 +1,29 @@
+-- Revert session backup key links
+
+DROP INDEX IF EXISTS idx_device_session_backups_backup_key;
+DROP INDEX IF EXISTS idx_device_session_backups_user_expires;
+
+ALTER TABLE device_session_backups
+DROP COLUMN IF EXISTS backup_key_id;
+
+DELETE FROM device_session_backups
+WHERE backup_type NOT IN ('full_sync', 'incremental', 'emergency', 'scheduled');
+
+ALTER TABLE device_session_backups
+DROP CONSTRAINT IF EXISTS device_session_backups_backup_type_check;
+
+ALTER TABLE device_session_backups
+ADD CONSTRAINT device_session_backups_backup_type_check CHECK (backup_type IN (
+    'full_sync', 'incremental', 'emergency', 'scheduled'
+));
+
+DELETE FROM encrypted_backup_keys
+WHERE backup_type NOT IN ('full', 'incremental', 'keys_only');
+
+ALTER TABLE encrypted_backup_keys
+DROP CONSTRAINT IF EXISTS encrypted_backup_keys_backup_type_check;
+
+ALTER TABLE encrypted_backup_keys
+ADD CONSTRAINT encrypted_backup_keys_backup_type_check CHECK (backup_type IN (
+    'full', 'incremental', 'keys_only'
+));
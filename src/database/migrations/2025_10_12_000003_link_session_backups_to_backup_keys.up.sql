@@ -0,0 +1,29 @@
+-- Link device session backups to the encrypted backup key they were sealed with,
+-- so a new device can fetch the wrapped key and restore the backup
+
+ALTER TABLE device_session_backups
+DROP CONSTRAINT IF EXISTS device_session_backups_backup_type_check;
+
+ALTER TABLE device_session_backups
+ADD CONSTRAINT device_session_backups_backup_type_check CHECK (backup_type IN (
+    'full_sync', 'incremental', 'emergency', 'scheduled',
+    'full', 'partial', 'metadata'
+));
+
+ALTER TABLE encrypted_backup_keys
+DROP CONSTRAINT IF EXISTS encrypted_backup_keys_backup_type_check;
+
+ALTER TABLE encrypted_backup_keys
+ADD CONSTRAINT encrypted_backup_keys_backup_type_check CHECK (backup_type IN (
+    'full', 'incremental', 'keys_only',
+    'full_keys', 'session_keys', 'identity_keys', 'prekey_bundle',
+    'signed_prekeys', 'group_keys', 'device_state'
+));
+
+ALTER TABLE device_session_backups
+ADD COLUMN IF NOT EXISTS backup_key_id CHAR(26) REFERENCES encrypted_backup_keys(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_device_session_backups_user_expires ON device_session_backups (user_id, expires_at);
+CREATE INDEX IF NOT EXISTS idx_device_session_backups_backup_key ON device_session_backups (backup_key_id);
+
+COMMENT ON COLUMN device_session_backups.backup_key_id IS 'Encrypted backup key the sessions were sealed with - the server only stores it wrapped by the client';
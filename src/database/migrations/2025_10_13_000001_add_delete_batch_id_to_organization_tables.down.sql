@@ -0,0 +1,13 @@
+-- Remove soft-delete batch tracking from organization tables
+
+DROP INDEX IF EXISTS idx_organizations_delete_batch;
+DROP INDEX IF EXISTS idx_organization_types_delete_batch;
+
+ALTER TABLE organizations
+DROP COLUMN IF EXISTS delete_batch_id;
+
+ALTER TABLE organization_types
+DROP COLUMN IF EXISTS delete_batch_id;
+
+ALTER TABLE organization_domains
+DROP COLUMN IF EXISTS delete_batch_id;
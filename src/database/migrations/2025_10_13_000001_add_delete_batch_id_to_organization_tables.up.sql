@@ -0,0 +1,18 @@
+-- Track which soft-deletes happened together, so restoring an organization
+-- domain or type brings back only the children deleted along with it
+
+ALTER TABLE organization_domains
+ADD COLUMN IF NOT EXISTS delete_batch_id CHAR(26);
+
+ALTER TABLE organization_types
+ADD COLUMN IF NOT EXISTS delete_batch_id CHAR(26);
+
+ALTER TABLE organizations
+ADD COLUMN IF NOT EXISTS delete_batch_id CHAR(26);
+
+CREATE INDEX IF NOT EXISTS idx_organization_types_delete_batch ON organization_types (delete_batch_id) WHERE delete_batch_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_organizations_delete_batch ON organizations (delete_batch_id) WHERE delete_batch_id IS NOT NULL;
+
+COMMENT ON COLUMN organization_domains.delete_batch_id IS 'Shared by every row soft-deleted in the same cascading delete';
+COMMENT ON COLUMN organization_types.delete_batch_id IS 'Shared by every row soft-deleted in the same cascading delete';
+COMMENT ON COLUMN organizations.delete_batch_id IS 'Shared by every row soft-deleted in the same cascading delete';
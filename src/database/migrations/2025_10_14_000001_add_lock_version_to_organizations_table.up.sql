@@ -0,0 +1,7 @@
+-- Optimistic locking: every update bumps lock_version, and an update sent
+-- with an older version is rejected instead of overwriting a newer edit
+
+ALTER TABLE organizations
+ADD COLUMN IF NOT EXISTS lock_version INTEGER NOT NULL DEFAULT 1;
+
+COMMENT ON COLUMN organizations.lock_version IS 'Incremented on every update; updates must send the version they were based on';
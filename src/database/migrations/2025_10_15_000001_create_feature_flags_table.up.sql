@@ -0,0 +1,28 @@
+-- Feature flags toggled at runtime without a redeploy. Each environment has
+-- its own database, so flags are naturally set per environment.
+CREATE TABLE IF NOT EXISTS feature_flags (
+    id CHAR(26) PRIMARY KEY,
+    name VARCHAR(100) NOT NULL UNIQUE,
+    description TEXT,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    rollout_percentage INTEGER CHECK (rollout_percentage BETWEEN 0 AND 100),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Per-user decisions that win over the flag's own setting
+CREATE TABLE IF NOT EXISTS feature_flag_overrides (
+    id CHAR(26) PRIMARY KEY,
+    feature_flag_id CHAR(26) NOT NULL REFERENCES feature_flags(id) ON DELETE CASCADE,
+    user_id CHAR(26) NOT NULL REFERENCES sys_users(id) ON DELETE CASCADE,
+    enabled BOOLEAN NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE(feature_flag_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_feature_flag_overrides_user ON feature_flag_overrides(user_id);
+
+COMMENT ON COLUMN feature_flags.enabled IS 'Master switch; when false the flag is off for everyone without an override';
+COMMENT ON COLUMN feature_flags.rollout_percentage IS 'Share of users (by stable hash of flag name and user id) the flag is on for; NULL means everyone';
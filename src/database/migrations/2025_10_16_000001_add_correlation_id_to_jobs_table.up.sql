@@ -0,0 +1,7 @@
+-- Correlation ID of the request that dispatched a job, so logs written
+-- while the job runs can be traced back to that request
+
+ALTER TABLE jobs
+ADD COLUMN IF NOT EXISTS correlation_id CHAR(26);
+
+COMMENT ON COLUMN jobs.correlation_id IS 'Correlation ID captured when the job was dispatched';
@@ -0,0 +1,2 @@
+ALTER TABLE device_presence
+DROP COLUMN IF EXISTS chosen_status;
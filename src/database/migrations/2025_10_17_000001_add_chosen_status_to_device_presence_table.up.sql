@@ -0,0 +1,8 @@
+-- Status the user picked for a device (busy, do not disturb, invisible),
+-- kept apart from `status` so it survives disconnects and is restored
+-- when the device reconnects
+
+ALTER TABLE device_presence
+ADD COLUMN IF NOT EXISTS chosen_status VARCHAR;
+
+COMMENT ON COLUMN device_presence.chosen_status IS 'Status chosen by the user, restored whenever the device is connected';
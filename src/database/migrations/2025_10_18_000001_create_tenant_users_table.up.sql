@@ -0,0 +1,12 @@
+-- Tenants each user may act in. Lives in the central database, next to
+-- sys_users, and is checked before a request is routed to a tenant's database.
+CREATE TABLE IF NOT EXISTS tenant_users (
+    id CHAR(26) PRIMARY KEY,
+    tenant VARCHAR(63) NOT NULL,
+    user_id CHAR(26) NOT NULL REFERENCES sys_users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE(tenant, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_tenant_users_user ON tenant_users(user_id);
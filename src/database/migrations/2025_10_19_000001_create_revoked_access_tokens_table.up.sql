@@ -0,0 +1,10 @@
+-- Access tokens revoked before they expire, keyed by the JWT's jti. Kept in
+-- the database so every app instance sees a revocation; rows can be pruned
+-- once expires_at has passed.
+CREATE TABLE IF NOT EXISTS revoked_access_tokens (
+    jti CHAR(26) PRIMARY KEY,
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_revoked_access_tokens_expires_at ON revoked_access_tokens(expires_at);
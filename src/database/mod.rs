@@ -1,7 +1,12 @@
+pub mod connect_retry;
 pub mod connection;
+pub mod factories;
 pub mod migration_runner;
+pub mod query_timing;
 pub mod seeder;
 pub mod seeders;
+pub mod statement_timeout;
+pub mod tenancy;
 
 use diesel::pg::PgConnection;
 use diesel::r2d2::{ConnectionManager, Pool, PooledConnection};
@@ -12,6 +17,9 @@ use crate::config::Config;
 pub type DbPool = Pool<ConnectionManager<PgConnection>>;
 pub type DbConnection = PooledConnection<ConnectionManager<PgConnection>>;
 
+pub use connect_retry::{wait_for_database, ConnectRetry};
+pub use statement_timeout::{with_statement_timeout, StatementTimedOut, StatementTimeout};
+
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("src/database/migrations");
 
 pub fn create_pool(config: &Config) -> Result<DbPool> {
@@ -19,6 +27,7 @@ pub fn create_pool(config: &Config) -> Result<DbPool> {
     let pool = Pool::builder()
         .max_size(config.database.pool_max_connections)
         .min_idle(Some(config.database.pool_min_connections))
+        .connection_customizer(Box::new(StatementTimeout::from_config(&config.database)))
         .build(manager)?;
 
     Ok(pool)
@@ -26,8 +35,11 @@ pub fn create_pool(config: &Config) -> Result<DbPool> {
 
 pub fn run_migrations(pool: &DbPool) -> Result<()> {
     let mut conn = pool.get()?;
-    conn.run_pending_migrations(MIGRATIONS)
-        .map_err(|e| anyhow::anyhow!("Migration failed: {}", e))?;
-    Ok(())
+    // Migrations may rebuild large tables, so they run without the pool's statement timeout
+    statement_timeout::without_statement_timeout(&mut conn, |conn| {
+        conn.run_pending_migrations(MIGRATIONS)
+            .map_err(|e| anyhow::anyhow!("Migration failed: {}", e))?;
+        Ok(())
+    })
 }
 
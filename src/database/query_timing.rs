@@ -0,0 +1,123 @@
+use anyhow::Result;
+use diesel::connection::{Instrumentation, InstrumentationEvent};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::app::utils::PerformanceMetrics;
+use crate::config::performance::PerformanceConfig;
+
+static SLOW_QUERY: OnceLock<Option<Duration>> = OnceLock::new();
+
+/// Diesel instrumentation counting every query and warning about slow ones
+///
+/// Slow queries are logged by their shape: literals are replaced with `?`
+/// and bound values are left out, so no user data reaches the log.
+#[derive(Debug, Default)]
+pub struct QueryTimer {
+    slow_query: Option<Duration>,
+    started: Option<Instant>,
+}
+
+impl QueryTimer {
+    pub fn new(slow_query: Option<Duration>) -> Self {
+        Self { slow_query, started: None }
+    }
+
+    /// Time queries on every connection opened from now on, pools included
+    pub fn install(config: &PerformanceConfig) -> Result<()> {
+        SLOW_QUERY.get_or_init(|| config.slow_query_threshold());
+        diesel::connection::set_default_instrumentation(|| {
+            Some(Box::new(QueryTimer::new(SLOW_QUERY.get().copied().flatten())))
+        })?;
+        Ok(())
+    }
+
+    /// Record a finished query, warning when it ran past the threshold
+    pub fn finish(&self, elapsed: Duration, query: impl FnOnce() -> String) -> bool {
+        let slow = self.slow_query.is_some_and(|threshold| elapsed >= threshold);
+        PerformanceMetrics::global().record_query(slow);
+
+        if slow {
+            let (sql, binds) = sql_shape(&query());
+            let duration_ms = elapsed.as_secs_f64() * 1000.0;
+            tracing::warn!(
+                sql = %sql,
+                binds,
+                duration_ms,
+                "Slow query took {:.2}ms with {} binds: {}",
+                duration_ms,
+                binds,
+                sql,
+            );
+        }
+
+        slow
+    }
+}
+
+impl Instrumentation for QueryTimer {
+    fn on_connection_event(&mut self, event: InstrumentationEvent<'_>) {
+        match event {
+            InstrumentationEvent::StartQuery { .. } => {
+                self.started = Some(Instant::now());
+            }
+            InstrumentationEvent::FinishQuery { query, .. } => {
+                if let Some(started) = self.started.take() {
+                    self.finish(started.elapsed(), || query.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// SQL of a debug-printed query with its values removed, and its number of binds
+///
+/// Diesel prints queries as `<sql> -- binds: [..]`; the bind list is dropped,
+/// and quoted strings and numbers inlined into the SQL become `?`. The bind
+/// count is the number of distinct `$n` placeholders.
+pub fn sql_shape(query: &str) -> (String, usize) {
+    let sql = query.split(" -- binds: ").next().unwrap_or_default().trim();
+    let mut shape = String::with_capacity(sql.len());
+    let mut placeholders = std::collections::BTreeSet::new();
+    let mut chars = sql.chars().peekable();
+    let mut previous: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                // Skip to the closing quote; '' is an escaped quote inside the literal
+                while let Some(next) = chars.next() {
+                    if next == '\'' {
+                        if chars.peek() == Some(&'\'') {
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                shape.push('?');
+            }
+            '$' if chars.peek().is_some_and(|next| next.is_ascii_digit()) => {
+                let mut number = String::new();
+                while let Some(digit) = chars.peek().copied().filter(|next| next.is_ascii_digit()) {
+                    number.push(digit);
+                    chars.next();
+                }
+                placeholders.insert(number.clone());
+                shape.push('$');
+                shape.push_str(&number);
+            }
+            c if c.is_ascii_digit() && !previous.is_some_and(|p| p.is_alphanumeric() || p == '_' || p == '"') => {
+                while chars.peek().is_some_and(|next| next.is_ascii_digit() || *next == '.') {
+                    chars.next();
+                }
+                shape.push('?');
+            }
+            c => shape.push(c),
+        }
+        previous = shape.chars().last();
+    }
+
+    (shape, placeholders.len())
+}
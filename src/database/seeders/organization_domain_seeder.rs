@@ -61,6 +61,7 @@ impl Seeder for OrganizationDomainSeeder {
                 created_by_id: DieselUlid::from_string(&system_user_id).unwrap(),
                 updated_by_id: DieselUlid::from_string(&system_user_id).unwrap(),
                 deleted_by_id: None,
+                delete_batch_id: None,
             };
 
             diesel::insert_into(organization_domains::table)
@@ -0,0 +1,127 @@
+use anyhow::Result;
+use diesel::connection::SimpleConnection;
+use diesel::pg::PgConnection;
+use diesel::r2d2::CustomizeConnection;
+use diesel::result::Error as DieselError;
+use diesel::sql_types::Text;
+use diesel::{Connection, RunQueryDsl};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::config::database::DatabaseConfig;
+
+static EXPORT_STATEMENT_TIMEOUT: OnceLock<Option<Duration>> = OnceLock::new();
+
+/// Postgres `statement_timeout` value, `0` meaning no limit
+fn setting(timeout: Option<Duration>) -> String {
+    match timeout {
+        Some(timeout) => format!("{}ms", timeout.as_millis().max(1)),
+        None => "0".to_string(),
+    }
+}
+
+/// Sets `statement_timeout` on every connection the pool opens
+///
+/// A runaway query is then cancelled by Postgres itself instead of holding a
+/// connection for minutes. Requests that need longer, such as exports, raise
+/// the limit for one transaction with `with_statement_timeout`, so the pooled
+/// connection always goes back with the default.
+#[derive(Debug, Clone, Copy)]
+pub struct StatementTimeout {
+    timeout: Option<Duration>,
+}
+
+impl StatementTimeout {
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self { timeout }
+    }
+
+    pub fn from_config(config: &DatabaseConfig) -> Self {
+        Self::new(config.statement_timeout())
+    }
+
+    /// Set the limit for the rest of the session
+    pub fn apply(&self, conn: &mut PgConnection) -> Result<(), DieselError> {
+        conn.batch_execute(&format!("SET statement_timeout = '{}'", setting(self.timeout)))
+    }
+}
+
+impl CustomizeConnection<PgConnection, diesel::r2d2::Error> for StatementTimeout {
+    fn on_acquire(&self, conn: &mut PgConnection) -> Result<(), diesel::r2d2::Error> {
+        self.apply(conn).map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+
+/// Longer limit exports run under, from `DB_EXPORT_STATEMENT_TIMEOUT_MS`
+pub fn export_timeout() -> Option<Duration> {
+    *EXPORT_STATEMENT_TIMEOUT.get_or_init(|| {
+        DatabaseConfig::from_env()
+            .map(|config| config.export_statement_timeout())
+            .unwrap_or(Some(Duration::from_secs(300)))
+    })
+}
+
+/// Set the limit until the current transaction ends
+pub fn set_local(conn: &mut PgConnection, timeout: Option<Duration>) -> Result<(), DieselError> {
+    conn.batch_execute(&format!("SET LOCAL statement_timeout = '{}'", setting(timeout)))
+}
+
+/// Run `f` in a transaction whose statements get their own limit, `None` meaning no limit
+///
+/// The limit is `SET LOCAL`, so it ends with the transaction and the
+/// connection returns to the pool with its default.
+pub fn with_statement_timeout<T, E>(
+    conn: &mut PgConnection,
+    timeout: Option<Duration>,
+    f: impl FnOnce(&mut PgConnection) -> Result<T, E>,
+) -> Result<T, E>
+where
+    E: From<DieselError>,
+{
+    conn.transaction(|conn| {
+        set_local(conn, timeout)?;
+        f(conn)
+    })
+}
+
+/// Run `f` with no limit on this session, then put the previous limit back
+///
+/// For work that cannot run in one transaction, such as migrations.
+pub fn without_statement_timeout<T>(
+    conn: &mut PgConnection,
+    f: impl FnOnce(&mut PgConnection) -> Result<T>,
+) -> Result<T> {
+    let previous = diesel::select(diesel::dsl::sql::<Text>("current_setting('statement_timeout')"))
+        .get_result::<String>(conn)?;
+    conn.batch_execute("SET statement_timeout = 0")?;
+
+    let result = f(conn);
+    conn.batch_execute(&format!("SET statement_timeout = '{}'", previous.replace('\'', "")))?;
+    result
+}
+
+/// A statement Postgres cancelled for running past `statement_timeout`
+///
+/// SQLSTATE 57014 is also used for cancellations the client asked for, so the
+/// message tells the two apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("The query took too long and was cancelled")]
+pub struct StatementTimedOut;
+
+impl StatementTimedOut {
+    /// Find a statement timeout anywhere in an error's chain
+    pub fn from_error(error: &anyhow::Error) -> Option<Self> {
+        error.chain()
+            .find_map(|cause| {
+                cause.downcast_ref::<DieselError>().and_then(Self::from_diesel)
+                    .or_else(|| cause.downcast_ref::<Self>().copied())
+            })
+    }
+
+    pub fn from_diesel(error: &DieselError) -> Option<Self> {
+        match error {
+            DieselError::DatabaseError(_, info) if info.message().contains("statement timeout") => Some(Self),
+            _ => None,
+        }
+    }
+}
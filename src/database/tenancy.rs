@@ -0,0 +1,234 @@
+use anyhow::Result;
+use axum::http::{header, HeaderMap};
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::app::models::DieselUlid;
+use crate::config::tenancy::TenancyConfig;
+use crate::database::DbPool;
+use crate::schema::tenant_users;
+
+/// Errors raised while resolving a request's tenant
+#[derive(Debug, thiserror::Error)]
+pub enum TenantError {
+    #[error("You do not belong to tenant '{0}'")]
+    NotAMember(String),
+}
+
+impl TenantError {
+    /// HTTP status for an error returned while resolving a tenant
+    pub fn status_code(error: &anyhow::Error) -> axum::http::StatusCode {
+        use axum::http::StatusCode;
+
+        match error.downcast_ref::<TenantError>() {
+            Some(TenantError::NotAMember(_)) => StatusCode::FORBIDDEN,
+            None => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Works out which tenant a request belongs to
+#[derive(Debug, Clone)]
+pub struct TenantResolver {
+    header: String,
+    base_domain: Option<String>,
+}
+
+impl TenantResolver {
+    pub fn new(header: impl Into<String>, base_domain: Option<String>) -> Self {
+        Self {
+            header: header.into(),
+            base_domain: base_domain.map(|domain| domain.trim_start_matches('.').to_lowercase()),
+        }
+    }
+
+    pub fn from_config(config: &TenancyConfig) -> Self {
+        Self::new(config.header.clone(), config.base_domain.clone())
+    }
+
+    /// Tenant the request runs against, once the user is known to belong to it
+    ///
+    /// Requests without a user get no tenant and stay on the central pool;
+    /// tenant routes sit behind the auth guards, so they never run that way.
+    /// A user naming a tenant they are not a member of is refused with
+    /// `TenantError::NotAMember`.
+    pub fn resolve(&self, central: &DbPool, headers: &HeaderMap, user_id: Option<&str>) -> Result<Option<String>> {
+        let (Some(tenant), Some(user_id)) = (self.identify(headers), user_id) else {
+            return Ok(None);
+        };
+
+        if !TenantMembers::contains(central, &tenant, user_id)? {
+            return Err(TenantError::NotAMember(tenant).into());
+        }
+
+        Ok(Some(tenant))
+    }
+
+    /// Tenant named by the tenant header, or else by the subdomain of the base domain
+    ///
+    /// Identifiers end up in database URLs, so anything other than lowercase
+    /// letters, digits, `-` and `_` is rejected.
+    pub fn identify(&self, headers: &HeaderMap) -> Option<String> {
+        let from_header = headers
+            .get(self.header.as_str())
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_lowercase());
+
+        from_header
+            .or_else(|| self.from_host(headers))
+            .filter(|tenant| Self::is_valid(tenant))
+    }
+
+    fn from_host(&self, headers: &HeaderMap) -> Option<String> {
+        let base_domain = self.base_domain.as_deref()?;
+        let host = headers.get(header::HOST)?.to_str().ok()?.to_lowercase();
+        let host = host.split(':').next().unwrap_or_default();
+
+        host.strip_suffix(base_domain)?
+            .strip_suffix('.')
+            .filter(|subdomain| !subdomain.contains('.'))
+            .map(str::to_string)
+    }
+
+    fn is_valid(tenant: &str) -> bool {
+        !tenant.is_empty()
+            && tenant.len() <= 63
+            && tenant.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    }
+}
+
+/// Users allowed to act in each tenant, kept in the central database
+pub struct TenantMembers;
+
+impl TenantMembers {
+    pub fn add(central: &DbPool, tenant: &str, user_id: &str) -> Result<()> {
+        let mut conn = central.get()?;
+
+        diesel::insert_into(tenant_users::table)
+            .values((
+                tenant_users::id.eq(DieselUlid::new().to_string()),
+                tenant_users::tenant.eq(tenant),
+                tenant_users::user_id.eq(user_id),
+            ))
+            .on_conflict((tenant_users::tenant, tenant_users::user_id))
+            .do_nothing()
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    pub fn remove(central: &DbPool, tenant: &str, user_id: &str) -> Result<()> {
+        let mut conn = central.get()?;
+
+        diesel::delete(tenant_users::table
+            .filter(tenant_users::tenant.eq(tenant))
+            .filter(tenant_users::user_id.eq(user_id)))
+            .execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    pub fn contains(central: &DbPool, tenant: &str, user_id: &str) -> Result<bool> {
+        let mut conn = central.get()?;
+
+        let count: i64 = tenant_users::table
+            .filter(tenant_users::tenant.eq(tenant))
+            .filter(tenant_users::user_id.eq(user_id))
+            .count()
+            .get_result(&mut conn)?;
+
+        Ok(count > 0)
+    }
+}
+
+/// Keeps a connection pool per tenant database next to the central pool
+///
+/// Tenant pools are created on first use and the least recently used one is
+/// closed once `max_pools` is reached. Shared tables stay on the central pool.
+pub struct TenantConnectionManager {
+    central: DbPool,
+    url_template: String,
+    max_pools: usize,
+    pool_max_connections: u32,
+    pools: Mutex<TenantPools>,
+}
+
+#[derive(Default)]
+struct TenantPools {
+    pools: HashMap<String, (DbPool, u64)>,
+    clock: u64,
+}
+
+impl TenantConnectionManager {
+    pub fn new(central: DbPool, url_template: impl Into<String>, max_pools: usize, pool_max_connections: u32) -> Self {
+        Self {
+            central,
+            url_template: url_template.into(),
+            max_pools: max_pools.max(1),
+            pool_max_connections: pool_max_connections.max(1),
+            pools: Mutex::new(TenantPools::default()),
+        }
+    }
+
+    pub fn from_config(central: DbPool, config: &TenancyConfig) -> Self {
+        Self::new(central, config.database_url_template.clone(), config.max_pools, config.pool_max_connections)
+    }
+
+    /// Pool for tables shared by every tenant
+    pub fn central(&self) -> &DbPool {
+        &self.central
+    }
+
+    /// Pool for a tenant's database, creating it on first use
+    pub fn pool_for(&self, tenant: &str) -> DbPool {
+        let mut state = self.pools.lock().unwrap_or_else(|e| e.into_inner());
+        state.clock += 1;
+        let now = state.clock;
+
+        if let Some((pool, last_used)) = state.pools.get_mut(tenant) {
+            *last_used = now;
+            return pool.clone();
+        }
+
+        if state.pools.len() >= self.max_pools {
+            let oldest = state.pools
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(tenant, _)| tenant.clone());
+            if let Some(oldest) = oldest {
+                state.pools.remove(&oldest);
+                tracing::debug!("Closed connection pool for tenant {}", oldest);
+            }
+        }
+
+        // Built unchecked so a tenant pool opens its connections on first checkout
+        let manager = ConnectionManager::<PgConnection>::new(self.database_url(tenant));
+        let pool = Pool::builder()
+            .max_size(self.pool_max_connections)
+            .min_idle(Some(0))
+            .build_unchecked(manager);
+
+        state.pools.insert(tenant.to_string(), (pool.clone(), now));
+        tracing::debug!("Opened connection pool for tenant {}", tenant);
+
+        pool
+    }
+
+    /// Connection URL of a tenant's database
+    pub fn database_url(&self, tenant: &str) -> String {
+        self.url_template.replace("{tenant}", tenant)
+    }
+
+    /// Whether a tenant currently has an open pool
+    pub fn has_pool(&self, tenant: &str) -> bool {
+        self.pools.lock().unwrap_or_else(|e| e.into_inner()).pools.contains_key(tenant)
+    }
+
+    /// Number of tenant pools currently open
+    pub fn pool_count(&self) -> usize {
+        self.pools.lock().unwrap_or_else(|e| e.into_inner()).pools.len()
+    }
+}
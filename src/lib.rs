@@ -18,6 +18,50 @@ use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use app::http::middleware::session_middleware::session_middleware;
 // use app::http::middleware::csrf_middleware::csrf_middleware;
 
+/// Set up the job queue and wire app listeners to their events
+///
+/// Listeners that should queue are pushed as jobs only on the `database`
+/// connection, where `queue:work` can pick them up from another process; on
+/// the in-process `memory` connection nothing would run them, so they are
+/// handled inline.
+pub async fn boot_queue(config: &config::Config, pool: &database::DbPool) {
+    // Queue jobs on the configured connection; the database driver keeps them across restarts
+    let queue_driver: Box<dyn app::jobs::QueueDriver> = match config.queue.connection.as_str() {
+        "database" => Box::new(
+            app::jobs::database_queue_driver::DatabaseQueueDriver::new(pool.clone())
+                .retry_after(std::time::Duration::from_secs(config.queue.retry_after)),
+        ),
+        _ => Box::new(app::jobs::MemoryQueueDriver::new()),
+    };
+    tracing::info!("Queue connection: {}", queue_driver.driver_name());
+    let queued = queue_driver.driver_name() == "database";
+    app::jobs::init_job_dispatcher(queue_driver).await;
+
+    // Wire app listeners to their events
+    app::providers::event_service_provider::register_global_listeners().await;
+    let events = app::events::event_dispatcher().await;
+    if queued {
+        events.set_queueable_handler(std::sync::Arc::new(app::events::queued_listener::JobQueueHandler::new(app::jobs::job_dispatcher().await))).await;
+    } else {
+        tracing::info!("Queued listeners run inline; use QUEUE_CONNECTION=database with queue:work to queue them");
+    }
+    tracing::info!("Event listeners registered");
+}
+
+/// Register mail drivers; the SMTP driver keeps one connection pool for every send
+pub async fn boot_mail(config: &config::Config) {
+    let mail_manager = app::mail::init_mail_manager(config.mail.mailer.clone()).await;
+    let mut manager = mail_manager.write().await;
+    manager.register_driver("smtp".to_string(), Box::new(app::mail::drivers::SmtpDriver::from_config(&config.mail)));
+    if config.mail.has_mailgun() {
+        manager.register_driver("mailgun".to_string(), Box::new(app::mail::drivers::MailgunDriver::from_config(&config.mail)));
+    }
+    manager.register_driver("log".to_string(), Box::new(app::mail::drivers::LogDriver::new()));
+    manager.set_defaults(&config.mail);
+    app::mail::template::init_mail_templates(&config.mail.templates_path).await;
+    tracing::info!("Mail drivers registered");
+}
+
 pub async fn create_app() -> anyhow::Result<Router> {
     tracing::debug!("Starting application creation process");
 
@@ -25,6 +69,12 @@ pub async fn create_app() -> anyhow::Result<Router> {
     let config = config::Config::load()?;
     tracing::info!("Configuration loaded successfully");
 
+    // Count queries and warn about slow ones on every connection opened from here on
+    database::query_timing::QueryTimer::install(&config.performance)?;
+
+    // Wait for the database, which may still be starting alongside the app
+    database::wait_for_database(&config.database).await?;
+
     // Create database pool
     tracing::debug!("Creating database connection pool...");
     let pool = database::create_pool(&config)?;
@@ -56,10 +106,7 @@ pub async fn create_app() -> anyhow::Result<Router> {
 
         // Register Redis driver
         if broadcasting_config.redis_enabled {
-            let redis_driver = app::broadcasting::RedisDriver::new(
-                broadcasting_config.redis_host.clone(),
-                broadcasting_config.redis_port,
-            );
+            let redis_driver = app::broadcasting::RedisDriver::from_config(&broadcasting_config);
             manager.register_driver("redis".to_string(), Box::new(redis_driver));
             tracing::info!("Redis broadcast driver registered");
         }
@@ -70,6 +117,42 @@ pub async fn create_app() -> anyhow::Result<Router> {
         tracing::info!("Log broadcast driver registered");
     }
 
+    boot_queue(&config, &pool).await;
+
+    // Register model policies with the gate
+    app::providers::auth_service_provider::register_policies(app::gate::gate());
+    tracing::info!("Policies registered");
+
+    boot_mail(&config).await;
+
+    // Mark devices offline when their WebSocket heartbeats stop
+    if broadcasting_config.websocket_enabled {
+        app::services::device_presence_service::DevicePresenceService::with_config(config.messaging.clone())
+            .spawn(pool.clone());
+        tracing::info!("Device presence sweep started");
+    }
+
+    // Deliver scheduled messages as they come due
+    if config.messaging.scheduled_dispatch_interval_secs > 0 {
+        app::services::scheduled_message_service::ScheduledMessageService::spawn(
+            pool.clone(),
+            std::time::Duration::from_secs(config.messaging.scheduled_dispatch_interval_secs),
+            config.messaging.scheduled_dispatch_limit,
+        );
+        tracing::info!("Scheduled message dispatcher started");
+    }
+
+    // Prime expensive cache keys in the background so startup does not wait on them
+    if config.cache.warm_on_boot {
+        match cache::shared_cache().await {
+            Ok(store) => {
+                app::cache_warmers::registry(&pool, &config.cache).spawn(store, config.cache.warm_interval());
+                tracing::info!("Cache warmers started");
+            },
+            Err(e) => tracing::warn!("Cache warmers not started: {}", e),
+        }
+    }
+
     // Get WebSocket manager for routes
     let websocket_manager = app::broadcasting::websocket::websocket_manager().await;
 
@@ -79,14 +162,60 @@ pub async fn create_app() -> anyhow::Result<Router> {
         .merge(routes::web::routes())
         .merge(routes::oauth::oauth_routes())
         // Add WebSocket routes
-        .nest("/ws", app::broadcasting::websocket::websocket_routes().with_state(websocket_manager))
-        .with_state(pool.clone())
+        .nest("/ws", app::broadcasting::websocket::websocket_routes().with_state(websocket_manager.clone()))
+        // Server-Sent Events for clients that cannot use WebSockets
+        .nest("/sse", app::broadcasting::sse::sse_routes().with_state(websocket_manager));
+
+    // Request and query counters are only exposed while debugging
+    let app = if config.app.debug {
+        app.route("/metrics", axum::routing::get(app::http::controllers::metrics_controller::show))
+    } else {
+        app
+    };
+
+    let app = app.with_state(pool.clone());
+
+    // Route tenant requests to their own database when tenancy is enabled. Added
+    // before the layers below so it runs after the session middleware and can
+    // check the tenant against the signed-in user.
+    let app = if config.tenancy.enabled {
+        let tenancy = app::http::middleware::tenant_middleware::TenancyState::from_config(pool.clone(), &config.tenancy);
+        tracing::info!("Per-tenant database connections enabled");
+        app.layer(middleware::from_fn_with_state(tenancy, app::http::middleware::tenant_middleware::tenant_middleware))
+    } else {
+        app
+    };
+
+    let app = app
         .layer(
             ServiceBuilder::new()
-                .layer(middleware::from_fn_with_state(pool, session_middleware))
+                // 405 with an Allow header for known paths, and bodiless HEAD responses
+                .layer(middleware::from_fn(app::http::middleware::method_middleware::method_middleware))
+                // Resolve the real client behind load balancers before anything reads it
+                .layer(middleware::from_fn_with_state(
+                    app::http::middleware::trusted_proxy_middleware::TrustedProxies::from_config(&config.trusted_proxies),
+                    app::http::middleware::trusted_proxy_middleware::trusted_proxy_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(pool.clone(), session_middleware))
                 // .layer(middleware::from_fn(csrf_middleware))
                 .layer(middleware::from_fn(app::http::middleware::correlation_middleware::correlation_middleware))
+                .layer(middleware::from_fn(app::http::middleware::id_format_middleware::id_format_middleware))
+                .layer(middleware::from_fn(app::http::middleware::timestamp_format_middleware::timestamp_format_middleware))
+                .layer(middleware::from_fn_with_state(
+                    app::http::middleware::request_timing_middleware::RequestTimingState::from_config(&config.performance),
+                    app::http::middleware::request_timing_middleware::request_timing_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    app::http::middleware::access_log_middleware::AccessLogState::from_config(&config.access_log),
+                    app::http::middleware::access_log_middleware::access_log_middleware,
+                ))
                 .layer(middleware::from_fn(app::http::middleware::activity_logging_middleware::activity_logging_middleware))
+                // Cancel handlers that run past REQUEST_TIMEOUT_SECS and answer 504
+                .layer(middleware::from_fn_with_state(
+                    app::http::middleware::timeout_middleware::RequestTimeout::from_config(&config.request_timeout)
+                        .check_statement_timeout(config.database.statement_timeout()),
+                    app::http::middleware::timeout_middleware::timeout_middleware,
+                ))
                 .layer(TraceLayer::new_for_http())
                 .layer(CorsLayer::permissive())
         );
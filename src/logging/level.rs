@@ -0,0 +1,151 @@
+//! Runtime log level changes
+//!
+//! The tracing subscriber is initialized with a reloadable `EnvFilter`, so
+//! directives such as `rustaxum::app::services=debug` can be added to a live
+//! process and later reverted to the startup filter without a restart.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::{Mutex, OnceLock};
+use tracing_subscriber::filter::{Directive, EnvFilter};
+use tracing_subscriber::{reload, Registry};
+use utoipa::ToSchema;
+
+/// Errors raised while changing the log level at runtime
+#[derive(Debug, thiserror::Error)]
+pub enum LogLevelError {
+    #[error("Invalid log directive '{0}'; expected <level> or <target>=<level>")]
+    InvalidDirective(String),
+
+    #[error("Runtime log level changes are not enabled in this process")]
+    NotInstalled,
+}
+
+impl LogLevelError {
+    /// HTTP status for an error returned while changing the log level
+    pub fn status_code(error: &anyhow::Error) -> axum::http::StatusCode {
+        use axum::http::StatusCode;
+
+        match error.downcast_ref::<LogLevelError>() {
+            Some(LogLevelError::InvalidDirective(_)) => StatusCode::UNPROCESSABLE_ENTITY,
+            Some(LogLevelError::NotInstalled) => StatusCode::SERVICE_UNAVAILABLE,
+            None => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Filter directives in effect, split into startup and runtime parts
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LogLevelStatus {
+    /// Directives the process started with
+    pub startup: String,
+    /// Directives added at runtime, in the order they were applied
+    pub overrides: Vec<String>,
+    /// Effective filter
+    pub current: String,
+}
+
+/// Handle to the reloadable filter the tracing subscriber was built with
+pub struct LogLevelFilter {
+    handle: reload::Handle<EnvFilter, Registry>,
+    startup: String,
+    overrides: Mutex<Vec<String>>,
+}
+
+static LOG_LEVEL: OnceLock<LogLevelFilter> = OnceLock::new();
+
+impl LogLevelFilter {
+    /// Build the filter layer to install on the registry, and its handle
+    pub fn new(directives: &str) -> Result<(reload::Layer<EnvFilter, Registry>, Self)> {
+        let filter = EnvFilter::try_new(directives)
+            .map_err(|_| LogLevelError::InvalidDirective(directives.to_string()))?;
+        let (layer, handle) = reload::Layer::new(filter);
+
+        Ok((layer, Self {
+            handle,
+            startup: directives.to_string(),
+            overrides: Mutex::new(Vec::new()),
+        }))
+    }
+
+    /// Like `new`, but warns and starts with `fallback` when `directives` is invalid
+    ///
+    /// Used for `RUST_LOG`, so a typo in the environment does not stop the
+    /// process from starting. The warning goes to stderr because the
+    /// subscriber this filter belongs to is not installed yet.
+    pub fn with_fallback(directives: &str, fallback: &str) -> Result<(reload::Layer<EnvFilter, Registry>, Self)> {
+        Self::new(directives).or_else(|error| {
+            eprintln!("Warning: {}; using '{}' instead", error, fallback);
+            Self::new(fallback)
+        })
+    }
+
+    /// Add a `<target>=<level>` or `<level>` directive, replacing any earlier override for the same target
+    pub fn set(&self, directive: &str) -> Result<LogLevelStatus> {
+        let directive = directive.trim();
+        directive.parse::<Directive>()
+            .map_err(|_| LogLevelError::InvalidDirective(directive.to_string()))?;
+
+        let mut overrides = self.overrides.lock().unwrap_or_else(|e| e.into_inner());
+        let target = Self::target(directive);
+        overrides.retain(|existing| Self::target(existing) != target);
+        overrides.push(directive.to_string());
+
+        self.apply(&overrides)?;
+        tracing::info!("Log filter override applied: {}", directive);
+
+        Ok(self.status_with(&overrides))
+    }
+
+    /// Drop all runtime overrides and return to the startup filter
+    pub fn reset(&self) -> Result<LogLevelStatus> {
+        let mut overrides = self.overrides.lock().unwrap_or_else(|e| e.into_inner());
+        overrides.clear();
+
+        self.apply(&overrides)?;
+        tracing::info!("Log filter reset to startup directives: {}", self.startup);
+
+        Ok(self.status_with(&overrides))
+    }
+
+    pub fn status(&self) -> LogLevelStatus {
+        let overrides = self.overrides.lock().unwrap_or_else(|e| e.into_inner());
+        self.status_with(&overrides)
+    }
+
+    fn apply(&self, overrides: &[String]) -> Result<()> {
+        let directives = std::iter::once(self.startup.as_str())
+            .chain(overrides.iter().map(String::as_str))
+            .filter(|directive| !directive.is_empty())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        self.handle.reload(EnvFilter::try_new(&directives)?)?;
+        Ok(())
+    }
+
+    fn status_with(&self, overrides: &[String]) -> LogLevelStatus {
+        LogLevelStatus {
+            startup: self.startup.clone(),
+            overrides: overrides.to_vec(),
+            current: self.handle.with_current(|filter| filter.to_string()).unwrap_or_default(),
+        }
+    }
+
+    /// Target a directive applies to; bare levels apply to every target
+    fn target(directive: &str) -> &str {
+        directive.rsplit_once('=').map(|(target, _)| target).unwrap_or("")
+    }
+}
+
+/// Make a filter the process-wide target of runtime log level changes
+pub fn install(filter: LogLevelFilter) {
+    if LOG_LEVEL.set(filter).is_err() {
+        tracing::warn!("Log level filter already installed; keeping the first one");
+    }
+}
+
+/// The installed filter, if the subscriber was built with one
+pub fn log_level() -> Result<&'static LogLevelFilter> {
+    LOG_LEVEL.get().ok_or_else(|| LogLevelError::NotInstalled.into())
+}
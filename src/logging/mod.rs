@@ -2,6 +2,8 @@ pub mod facade;
 pub mod channels;
 pub mod formatters;
 pub mod writers;
+pub mod level;
 
 pub use facade::Log;
-pub use channels::{Channel, ChannelManager};
\ No newline at end of file
+pub use channels::{Channel, ChannelManager};
+pub use level::LogLevelFilter;
\ No newline at end of file
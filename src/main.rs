@@ -1,4 +1,4 @@
-use rustaxum::{create_app, config, logging::Log};
+use rustaxum::{create_app, config, logging::{self, Log, LogLevelFilter}};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -24,13 +24,14 @@ async fn main() -> anyhow::Result<()> {
     };
 
     tracing::debug!("Setting up tracing subscriber with level: {}", log_level);
+    // RUST_LOG still wins; the filter stays adjustable at runtime through /api/admin/log-level
+    let directives = std::env::var("RUST_LOG").unwrap_or_else(|_| log_level.clone());
+    let (filter, level_filter) = LogLevelFilter::with_fallback(&directives, &log_level)?;
     tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| log_level.into()),
-        )
+        .with(filter)
         .with(tracing_subscriber::fmt::layer())
         .init();
+    logging::level::install(level_filter);
 
     tracing::info!("Starting application creation...");
     let app = create_app().await?;
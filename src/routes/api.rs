@@ -1,12 +1,13 @@
 use axum::{
-    routing::{get, post, put, delete},
+    routing::{get, post, put, patch, delete},
     Router,
     middleware,
 };
 use crate::database::DbPool;
 use crate::app::http::middleware::auth_guard::auth_guard;
+use crate::app::http::middleware::signed_middleware::validate_signature;
 
-use crate::app::http::controllers::{auth_controller, user_controller, country_controller, province_controller, city_controller, district_controller, village_controller, role_controller, permission_controller, docs_controller, organization_domain_controller, organization_type_controller, user_organization_controller, organization_position_level_controller, organization_position_controller, sys_model_has_permission_controller, sys_model_has_role_controller, activity_log_controller, session_controller, web_push_controller};
+use crate::app::http::controllers::{auth_controller, user_controller, country_controller, province_controller, city_controller, district_controller, village_controller, role_controller, permission_controller, docs_controller, organization_domain_controller, organization_type_controller, user_organization_controller, organization_position_level_controller, organization_position_controller, sys_model_has_permission_controller, sys_model_has_role_controller, activity_log_controller, session_controller, web_push_controller, message_controller, conversation_controller, poll_controller, presence_controller, prekey_controller, security_incident_controller, session_backup_controller, broadcasting_controller, log_level_controller, impersonation_controller};
 
 pub fn routes() -> Router<DbPool> {
     tracing::debug!("Creating API routes...");
@@ -17,6 +18,7 @@ pub fn routes() -> Router<DbPool> {
         .route("/api/auth/register", post(auth_controller::register))
         .route("/api/auth/login", post(auth_controller::login))
         .route("/api/auth/mfa-login", post(auth_controller::complete_mfa_login))
+        .route("/api/auth/confirm-device", post(auth_controller::confirm_device))
         .route("/api/auth/forgot-password", post(auth_controller::forgot_password))
         .route("/api/auth/reset-password", post(auth_controller::reset_password))
         .route("/api/auth/refresh-token", post(auth_controller::refresh_token))
@@ -30,13 +32,20 @@ pub fn routes() -> Router<DbPool> {
         .route("/api/auth/session/logout", post(auth_controller::logout_session))
         .route("/api/auth/session/user", get(auth_controller::user_session))
         .route("/api/me", get(auth_controller::me))
+        .route("/api/auth/email/verification-notification", post(auth_controller::resend_email_verification))
         .route_layer(middleware::from_fn(auth_guard));
 
+    // Signed link routes (the URL signature is the credential)
+    let signed_routes = Router::new()
+        .route("/email/verify/{id}", get(auth_controller::verify_email))
+        .route_layer(middleware::from_fn(validate_signature));
+
     // Protected routes (require authentication)
     let protected_routes = Router::new()
         // User routes
         .route("/api/users", get(user_controller::index))
         .route("/api/users/{id}", get(user_controller::show))
+        .route("/api/users/{id}/presence", get(presence_controller::show))
         // Country routes
         .route("/api/countries", get(country_controller::index))
         .route("/api/countries", post(country_controller::store))
@@ -103,6 +112,7 @@ pub fn routes() -> Router<DbPool> {
         .route("/api/organization-domains", post(organization_domain_controller::store))
         .route("/api/organization-domains/{id}", get(organization_domain_controller::show))
         .route("/api/organization-domains/{id}", put(organization_domain_controller::update))
+        .route("/api/organization-domains/{id}", patch(organization_domain_controller::patch))
         .route("/api/organization-domains/{id}", delete(organization_domain_controller::destroy))
         // Organization Type routes
         .route("/api/organization-types", get(organization_type_controller::index))
@@ -135,6 +145,45 @@ pub fn routes() -> Router<DbPool> {
         .route("/api/web-push/test", post(web_push_controller::send_test_notification))
         .route("/api/web-push/status", get(web_push_controller::get_status))
         .route("/api/web-push/cleanup", post(web_push_controller::cleanup_subscriptions))
+        // Conversation routes
+        .route("/api/conversations", get(conversation_controller::index))
+        .route("/api/conversations/{id}/messages", post(message_controller::store))
+        .route("/api/conversations/{id}/typing", post(conversation_controller::typing))
+        .route("/api/conversations/{id}/polls", post(poll_controller::store))
+        .route("/api/conversations/{id}/pins", get(conversation_controller::pins))
+        .route("/api/conversations/{id}/pins", post(conversation_controller::pin))
+        .route("/api/conversations/{id}/pins/{message_id}", delete(conversation_controller::unpin))
+        // Device key routes
+        .route("/api/devices/{id}/keys", get(prekey_controller::status))
+        .route("/api/devices/{id}/keys", post(prekey_controller::upload))
+        .route("/api/devices/{id}/prekey-bundle", post(prekey_controller::bundle))
+        // Session backup routes
+        .route("/api/devices/{id}/session-backups", post(session_backup_controller::store))
+        .route("/api/session-backups", get(session_backup_controller::index))
+        .route("/api/session-backups/{id}", delete(session_backup_controller::destroy))
+        .route("/api/session-backups/{id}/key", post(session_backup_controller::key))
+        .route("/api/session-backups/{id}/restore", post(session_backup_controller::restore))
+        // Broadcast channel authorization
+        .route("/broadcasting/auth", post(broadcasting_controller::authorize))
+        // Runtime log level
+        .route("/api/admin/log-level", get(log_level_controller::show))
+        .route("/api/admin/log-level", put(log_level_controller::update))
+        .route("/api/admin/log-level", delete(log_level_controller::destroy))
+        // Impersonation routes
+        .route("/api/impersonation/{user_id}", post(impersonation_controller::start))
+        .route("/api/impersonation", delete(impersonation_controller::stop))
+        // Poll routes
+        .route("/api/polls/{id}/votes", post(poll_controller::vote))
+        .route("/api/polls/{id}/close", post(poll_controller::close))
+        .route("/api/polls/{id}/results", get(poll_controller::results))
+        // Message routes
+        .route("/api/messages/{id}/forward", post(message_controller::forward))
+        .route("/api/messages/{id}/delivered", post(message_controller::mark_delivered))
+        .route("/api/messages/{id}/read", post(message_controller::mark_read))
+        .route("/api/messages/{id}/delivery-status", get(message_controller::delivery_status))
+        .route("/api/messages/{id}/reactions", get(message_controller::reactions))
+        .route("/api/messages/{id}/reactions", post(message_controller::add_reaction))
+        .route("/api/messages/{id}/reactions/{reaction_id}", delete(message_controller::remove_reaction))
         // Sys Model Has Permission routes
         .route("/api/sys-model-has-permissions", get(sys_model_has_permission_controller::index))
         .route("/api/sys-model-has-permissions", post(sys_model_has_permission_controller::store))
@@ -158,6 +207,8 @@ pub fn routes() -> Router<DbPool> {
         .route("/api/activity-logs/batch/{batch_uuid}", get(activity_log_controller::get_activities_by_batch))
         .route("/api/activity-logs/subject/{subject_type}/{subject_id}", get(activity_log_controller::get_activities_by_subject))
         .route("/api/activity-logs/causer/{causer_type}/{causer_id}", get(activity_log_controller::get_activities_by_causer))
+        // Security incident routes
+        .route("/api/security-incidents", get(security_incident_controller::index))
         // Session routes
         .route("/api/session", get(session_controller::get_session))
         .route("/api/session", post(session_controller::put_session))
@@ -180,6 +231,7 @@ pub fn routes() -> Router<DbPool> {
     let router = Router::new()
         .merge(auth_routes)
         .merge(protected_auth_routes)
+        .merge(signed_routes)
         .merge(protected_routes)
         .merge(public_routes);
 
@@ -2,6 +2,7 @@ use axum::{routing::{get, post}, Router, response::Html, middleware};
 use crate::database::DbPool;
 use crate::app::http::controllers::home_controller;
 use crate::app::http::controllers::csrf_controller;
+use crate::app::http::controllers::health_controller;
 use crate::app::http::controllers::web_auth_controller;
 use crate::app::http::controllers::mfa_controller;
 use crate::app::http::controllers::mfa_controller_extensions;
@@ -15,6 +16,8 @@ pub fn routes() -> Router<DbPool> {
         .route("/auth/login", get(web_auth_controller::show_login))
         .route("/auth/login", post(web_auth_controller::login))
         .route("/auth/mfa-verify", post(web_auth_controller::verify_mfa_web))
+        .route("/auth/confirm-device", get(web_auth_controller::show_confirm_device))
+        .route("/auth/confirm-device", post(web_auth_controller::confirm_device))
         .route("/auth/register", get(web_auth_controller::show_register))
         .route("/auth/register", post(web_auth_controller::register))
         .route("/auth/forgot-password", get(web_auth_controller::show_forgot_password))
@@ -66,6 +69,7 @@ pub fn routes() -> Router<DbPool> {
     let public_routes = Router::new()
         .route("/", get(home_controller::index))
         .route("/health", get(health_check))
+        .route("/ready", get(health_controller::ready))
         .route("/web-push-demo", get(web_push_demo))
         // CSRF test routes
         .route("/csrf/token", get(csrf_controller::token))
@@ -250,6 +250,7 @@ diesel::table! {
         auto_offline_after_minutes -> Nullable<Int4>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        chosen_status -> Nullable<Varchar>,
     }
 }
 
@@ -295,6 +296,8 @@ diesel::table! {
         is_verified -> Bool,
         verification_failed_at -> Nullable<Timestamptz>,
         updated_at -> Timestamptz,
+        #[max_length = 26]
+        backup_key_id -> Nullable<Bpchar>,
     }
 }
 
@@ -399,6 +402,34 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    feature_flag_overrides (id) {
+        #[max_length = 26]
+        id -> Bpchar,
+        #[max_length = 26]
+        feature_flag_id -> Bpchar,
+        #[max_length = 26]
+        user_id -> Bpchar,
+        enabled -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    feature_flags (id) {
+        #[max_length = 26]
+        id -> Bpchar,
+        #[max_length = 100]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        enabled -> Bool,
+        rollout_percentage -> Nullable<Int4>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     forward_history (id) {
         #[max_length = 26]
@@ -438,6 +469,25 @@ diesel::table! {
         timeout_seconds -> Nullable<Int4>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        #[max_length = 26]
+        correlation_id -> Nullable<Bpchar>,
+    }
+}
+
+diesel::table! {
+    login_fingerprints (id) {
+        #[max_length = 26]
+        id -> Bpchar,
+        #[max_length = 26]
+        user_id -> Bpchar,
+        #[max_length = 64]
+        fingerprint_hash -> Varchar,
+        user_agent -> Nullable<Text>,
+        ip_address -> Nullable<Text>,
+        confirmed_at -> Nullable<Timestamptz>,
+        last_seen_at -> Timestamptz,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
     }
 }
 
@@ -1144,6 +1194,8 @@ diesel::table! {
         updated_by_id -> Bpchar,
         #[max_length = 26]
         deleted_by_id -> Nullable<Bpchar>,
+        #[max_length = 26]
+        delete_batch_id -> Nullable<Bpchar>,
     }
 }
 
@@ -1218,6 +1270,8 @@ diesel::table! {
         updated_by_id -> Bpchar,
         #[max_length = 26]
         deleted_by_id -> Nullable<Bpchar>,
+        #[max_length = 26]
+        delete_batch_id -> Nullable<Bpchar>,
     }
 }
 
@@ -1258,6 +1312,9 @@ diesel::table! {
         updated_by_id -> Bpchar,
         #[max_length = 26]
         deleted_by_id -> Nullable<Bpchar>,
+        #[max_length = 26]
+        delete_batch_id -> Nullable<Bpchar>,
+        lock_version -> Int4,
     }
 }
 
@@ -1293,6 +1350,7 @@ diesel::table! {
         vote_algorithm -> Varchar,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        option_indexes -> Array<Nullable<Int4>>,
     }
 }
 
@@ -1312,6 +1370,7 @@ diesel::table! {
         is_closed -> Bool,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        option_count -> Int4,
     }
 }
 
@@ -1454,6 +1513,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    revoked_access_tokens (jti) {
+        #[max_length = 26]
+        jti -> Bpchar,
+        expires_at -> Timestamptz,
+        revoked_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     scheduled_message_edits (id) {
         #[max_length = 26]
@@ -1520,6 +1588,7 @@ diesel::table! {
         resolution_notes -> Nullable<Text>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        metadata -> Jsonb,
     }
 }
 
@@ -1795,6 +1864,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    tenant_users (id) {
+        #[max_length = 26]
+        id -> Bpchar,
+        #[max_length = 63]
+        tenant -> Varchar,
+        #[max_length = 26]
+        user_id -> Bpchar,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     typing_indicators (id) {
         #[max_length = 26]
@@ -1853,12 +1934,16 @@ diesel::joinable!(device_key_rotations -> devices (device_id));
 diesel::joinable!(device_presence -> devices (device_id));
 diesel::joinable!(device_push_tokens -> devices (device_id));
 diesel::joinable!(device_session_backups -> devices (device_id));
+diesel::joinable!(device_session_backups -> encrypted_backup_keys (backup_key_id));
 diesel::joinable!(device_session_backups -> sys_users (user_id));
 diesel::joinable!(devices -> sys_users (user_id));
 diesel::joinable!(encrypted_backup_keys -> devices (device_id));
 diesel::joinable!(encrypted_backup_keys -> sys_users (user_id));
+diesel::joinable!(feature_flag_overrides -> feature_flags (feature_flag_id));
+diesel::joinable!(feature_flag_overrides -> sys_users (user_id));
 diesel::joinable!(forward_history -> devices (forwarded_by_device_id));
 diesel::joinable!(forward_history -> sys_users (forwarded_by_user_id));
+diesel::joinable!(login_fingerprints -> sys_users (user_id));
 diesel::joinable!(message_delivery_status -> devices (recipient_device_id));
 diesel::joinable!(message_delivery_status -> messages (message_id));
 diesel::joinable!(message_device_keys -> devices (recipient_device_id));
@@ -1944,6 +2029,7 @@ diesel::joinable!(sys_model_has_permissions -> sys_permissions (permission_id));
 diesel::joinable!(sys_model_has_roles -> sys_roles (role_id));
 diesel::joinable!(sys_permissions -> organizations (organization_id));
 diesel::joinable!(sys_roles -> organizations (organization_id));
+diesel::joinable!(tenant_users -> sys_users (user_id));
 diesel::joinable!(typing_indicators -> conversations (conversation_id));
 diesel::joinable!(typing_indicators -> devices (device_id));
 diesel::joinable!(typing_indicators -> sys_users (user_id));
@@ -1969,8 +2055,11 @@ diesel::allow_tables_to_appear_in_same_query!(
     devices,
     encrypted_backup_keys,
     events,
+    feature_flag_overrides,
+    feature_flags,
     forward_history,
     jobs,
+    login_fingerprints,
     message_delivery_status,
     message_device_keys,
     message_expiry_queue,
@@ -2020,6 +2109,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     ref_geo_districts,
     ref_geo_provinces,
     ref_geo_villages,
+    revoked_access_tokens,
     scheduled_message_edits,
     scheduled_messages,
     security_incidents,
@@ -2033,6 +2123,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     sys_permissions,
     sys_roles,
     sys_users,
+    tenant_users,
     typing_indicators,
     user_organizations,
 );
@@ -320,9 +320,10 @@ pub async fn disk(name: &str) -> Result<FilesystemDriver> {
     let _disk = manager.disk(name).await?;
 
     // We need to return an owned value, so we recreate the disk
-    let disk_config = manager.config.get_disk(name).ok_or_else(|| {
+    let disk_name = if name == "default" { manager.config.default.clone() } else { name.to_string() };
+    let disk_config = manager.config.get_disk(&disk_name).ok_or_else(|| {
         FilesystemError::Config {
-            message: format!("Disk '{}' not found in configuration", name),
+            message: format!("Disk '{}' not found in configuration", disk_name),
         }
     })?;
 
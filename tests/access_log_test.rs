@@ -0,0 +1,174 @@
+//! Access Log Tests
+//!
+//! These tests verify that the access log masks configured headers and
+//! fields, logs bodies it cannot parse only by size, truncates bodies at the
+//! configured size, that capturing bodies
+//! leaves requests and responses intact, and that streamed or oversized
+//! bodies are passed on without being buffered.
+
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, HeaderValue, Request, StatusCode},
+    middleware,
+    routing::post,
+    Json, Router,
+};
+use rustaxum::app::http::middleware::access_log_middleware::{access_log_middleware, AccessLogState, REDACTED};
+use rustaxum::config::access_log::AccessLogConfig;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+fn config(max_body_bytes: usize) -> AccessLogConfig {
+    AccessLogConfig {
+        enabled: true,
+        capture_bodies: false,
+        capture_paths: vec!["/api/webhooks".to_string()],
+        max_body_bytes,
+        max_capture_bytes: 64,
+        redact_headers: vec!["authorization".to_string(), "cookie".to_string()],
+        redact_fields: vec!["password".to_string(), "access_token".to_string()],
+    }
+}
+
+#[test]
+fn test_redacts_configured_headers() {
+    let access_log = AccessLogState::new(config(1024));
+    let mut headers = HeaderMap::new();
+    headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer secret-token"));
+    headers.insert("Cookie", HeaderValue::from_static("session=abc"));
+    headers.insert(header::USER_AGENT, HeaderValue::from_static("integration-test"));
+
+    let logged = access_log.redact_headers(&headers);
+
+    assert_eq!(logged["authorization"], REDACTED);
+    assert_eq!(logged["cookie"], REDACTED);
+    assert_eq!(logged["user-agent"], "integration-test");
+}
+
+#[test]
+fn test_redacts_json_fields_at_any_depth() {
+    let access_log = AccessLogState::new(config(1024));
+    let body = json!({
+        "email": "user@example.com",
+        "Password": "hunter2",
+        "tokens": [{ "access_token": "abc", "scope": "read" }],
+    });
+
+    let logged = access_log.body_for_log(Some("application/json"), body.to_string().as_bytes());
+    let logged: Value = serde_json::from_str(&logged).unwrap();
+
+    assert_eq!(logged["email"], "user@example.com");
+    assert_eq!(logged["Password"], REDACTED);
+    assert_eq!(logged["tokens"][0]["access_token"], REDACTED);
+    assert_eq!(logged["tokens"][0]["scope"], "read");
+}
+
+#[test]
+fn test_redacts_form_and_query_fields() {
+    let access_log = AccessLogState::new(config(1024));
+
+    let logged = access_log.body_for_log(
+        Some("application/x-www-form-urlencoded"),
+        b"email=user%40example.com&password=hunter2",
+    );
+
+    assert_eq!(logged, format!("email=user%40example.com&password={}", REDACTED));
+    assert_eq!(access_log.redact_query("access_token=abc&page=2"), format!("access_token={}&page=2", REDACTED));
+
+    // Encoded keys are matched after decoding
+    assert_eq!(access_log.redact_query("pass%77ord=hunter2"), format!("pass%77ord={}", REDACTED));
+}
+
+#[test]
+fn test_unparsed_bodies_are_logged_by_size_only() {
+    let access_log = AccessLogState::new(config(1024));
+
+    let logged = access_log.body_for_log(Some("text/plain"), b"password=hunter2");
+    assert_eq!(logged, "<unparsed 16 bytes>");
+
+    let logged = access_log.body_for_log(Some("application/json"), b"{\"password\": \"hunter2\"");
+    assert_eq!(logged, "<unparsed 22 bytes>");
+
+    let logged = access_log.body_for_log(None, b"hunter2");
+    assert_eq!(logged, "<unparsed 7 bytes>");
+}
+
+#[test]
+fn test_truncates_body_at_cap() {
+    let access_log = AccessLogState::new(config(16));
+
+    let logged = access_log.body_for_log(Some("application/x-www-form-urlencoded"), "a".repeat(100).as_bytes());
+
+    assert!(logged.starts_with(&"a".repeat(16)));
+    assert!(!logged.starts_with(&"a".repeat(17)));
+    assert!(logged.ends_with("...[truncated 84 bytes]"));
+
+    // Multi-byte characters are never split
+    let access_log = AccessLogState::new(config(15));
+    let logged = access_log.body_for_log(Some("application/x-www-form-urlencoded"), "é".repeat(20).as_bytes());
+    assert_eq!(logged, format!("{}...[truncated 26 bytes]", "é".repeat(7)));
+}
+
+#[test]
+fn test_redaction_happens_before_truncation() {
+    let access_log = AccessLogState::new(config(30));
+    let body = json!({ "password": "a-very-long-secret-that-would-otherwise-leak" });
+
+    let logged = access_log.body_for_log(Some("application/json"), body.to_string().as_bytes());
+
+    assert!(!logged.contains("a-very-long"));
+}
+
+#[test]
+fn test_body_capture_is_opt_in_per_path() {
+    let access_log = AccessLogState::new(config(1024));
+    assert!(access_log.captures_bodies("/api/webhooks/stripe"));
+    assert!(!access_log.captures_bodies("/api/users"));
+
+    let access_log = AccessLogState::new(AccessLogConfig { capture_bodies: true, ..config(1024) });
+    assert!(access_log.captures_bodies("/api/users"));
+}
+
+#[tokio::test]
+async fn test_captured_bodies_pass_through_unchanged() {
+    let app = Router::new()
+        .route("/api/webhooks/echo", post(|Json(body): Json<Value>| async move { Json(body) }))
+        .layer(middleware::from_fn_with_state(AccessLogState::new(config(8)), access_log_middleware));
+
+    let body = json!({ "event": "invoice.paid", "password": "hunter2" });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/webhooks/echo")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let echoed: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(echoed, body);
+}
+
+#[tokio::test]
+async fn test_streamed_and_oversized_bodies_are_not_buffered() {
+    let access_log = AccessLogState::new(config(1024));
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    let chunks = vec![Ok::<_, std::io::Error>("[1,"), Ok("2]")];
+    let (body, logged) = access_log.capture(&headers, Body::from_stream(futures::stream::iter(chunks))).await;
+    assert_eq!(logged.as_deref(), Some("[body not captured: streamed]"));
+    let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+    assert_eq!(&bytes[..], b"[1,2]");
+
+    let large = json!({ "data": "x".repeat(100) }).to_string();
+    let (body, logged) = access_log.capture(&headers, Body::from(large.clone())).await;
+    assert_eq!(logged, Some(format!("[body not captured: {} bytes]", large.len())));
+    let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+    assert_eq!(&bytes[..], large.as_bytes());
+
+    let (_, logged) = access_log.capture(&headers, Body::from(r#"{"ok":true}"#)).await;
+    assert_eq!(logged.as_deref(), Some(r#"{"ok":true}"#));
+}
@@ -0,0 +1,153 @@
+//! Activity Log Query Tests
+//!
+//! These tests verify that `GET /api/activity-logs` filters by causer and by a
+//! `created_at` range, attaches the causer when included without exposing
+//! fields the user model keeps private, pages with a cursor, and requires
+//! authentication.
+
+mod common;
+
+use anyhow::Result;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use rustaxum::app::models::activity_log::ActivityLog;
+use rustaxum::app::models::user::User;
+use rustaxum::app::models::DieselUlid;
+use rustaxum::app::services::auth_service::AuthService;
+use rustaxum::database::DbPool;
+use rustaxum::schema::activity_log;
+use serde_json::Value;
+use serial_test::serial;
+use tower::ServiceExt;
+
+/// Log name unique to one test, so activity recorded elsewhere never matches
+fn log_name() -> String {
+    format!("query_test_{}", ulid::Ulid::new().to_string().to_lowercase())
+}
+
+fn record(pool: &DbPool, log_name: &str, causer: &User, event: &str, at: &str) -> Result<ActivityLog> {
+    let created_at: DateTime<Utc> = at.parse()?;
+    let log = ActivityLog {
+        id: DieselUlid::new(),
+        log_name: Some(log_name.to_string()),
+        description: format!("{} happened", event),
+        subject_type: Some("User".to_string()),
+        subject_id: Some(causer.id.to_string()),
+        causer_type: Some("User".to_string()),
+        causer_id: Some(causer.id.to_string()),
+        properties: None,
+        correlation_id: None,
+        batch_uuid: None,
+        event: Some(event.to_string()),
+        created_at,
+        updated_at: created_at,
+    };
+
+    diesel::insert_into(activity_log::table).values(&log).execute(&mut pool.get()?)?;
+    Ok(log)
+}
+
+async fn get(pool: &DbPool, uri: &str, user: Option<&User>) -> Result<(StatusCode, Value)> {
+    let app = rustaxum::routes::api::routes().with_state(pool.clone());
+    let mut request = Request::builder().uri(uri);
+    if let Some(user) = user {
+        let token = AuthService::generate_access_token(&user.id.to_string(), 3600)?;
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+
+    let response = app.oneshot(request.body(Body::empty())?).await?;
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok((status, serde_json::from_slice(&body).unwrap_or(Value::Null)))
+}
+
+fn events(body: &Value) -> Vec<&str> {
+    body["data"].as_array().unwrap().iter().map(|row| row["event"].as_str().unwrap()).collect()
+}
+
+#[tokio::test]
+#[serial]
+async fn test_filter_by_causer_and_include_causer() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let log_name = log_name();
+    let alice = common::create_user(&pool)?;
+    let bob = common::create_user(&pool)?;
+    record(&pool, &log_name, &alice, "alice.first", "2024-03-01T10:00:00Z")?;
+    record(&pool, &log_name, &alice, "alice.second", "2024-03-02T10:00:00Z")?;
+    record(&pool, &log_name, &bob, "bob.first", "2024-03-03T10:00:00Z")?;
+
+    let uri = format!(
+        "/api/activity-logs?filter[log_name]={}&filter[causer_type]=User&filter[causer_id]={}&include=causer",
+        log_name, alice.id
+    );
+    let (status, body) = get(&pool, &uri, Some(&alice)).await?;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(events(&body), vec!["alice.second", "alice.first"]);
+    for row in body["data"].as_array().unwrap() {
+        assert_eq!(row["causer"]["id"], alice.id.to_string());
+        assert_eq!(row["causer"]["email"], alice.email);
+        assert!(row["causer"].get("password").is_none());
+        assert!(row.get("subject").is_none(), "only requested relationships are attached");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_filter_by_date_range() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let log_name = log_name();
+    let user = common::create_user(&pool)?;
+    record(&pool, &log_name, &user, "before", "2024-01-01T00:00:00Z")?;
+    record(&pool, &log_name, &user, "inside", "2024-01-05T00:00:00Z")?;
+    record(&pool, &log_name, &user, "after", "2024-01-10T00:00:00Z")?;
+
+    let uri = format!(
+        "/api/activity-logs?filter[log_name]={}&filter[created_at][gte]=2024-01-03T00:00:00Z&filter[created_at][lte]=2024-01-07T00:00:00Z",
+        log_name
+    );
+    let (status, body) = get(&pool, &uri, Some(&user)).await?;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(events(&body), vec!["inside"]);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cursor_pagination_walks_every_page() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let log_name = log_name();
+    let user = common::create_user(&pool)?;
+    record(&pool, &log_name, &user, "first", "2024-02-01T00:00:00Z")?;
+    record(&pool, &log_name, &user, "second", "2024-02-02T00:00:00Z")?;
+    record(&pool, &log_name, &user, "third", "2024-02-03T00:00:00Z")?;
+
+    let uri = format!("/api/activity-logs?filter[log_name]={}&per_page=2&pagination_type=cursor", log_name);
+    let (status, body) = get(&pool, &uri, Some(&user)).await?;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(events(&body), vec!["third", "second"]);
+    assert_eq!(body["pagination"]["has_more_pages"], true);
+
+    let cursor = body["pagination"]["next_cursor"].as_str().unwrap();
+    let (status, body) = get(&pool, &format!("{}&cursor={}", uri, cursor), Some(&user)).await?;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(events(&body), vec!["first"]);
+    assert_eq!(body["pagination"]["has_more_pages"], false);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_requires_authentication() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+
+    let (status, _) = get(&pool, "/api/activity-logs", None).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    Ok(())
+}
@@ -0,0 +1,128 @@
+//! Algorithm Negotiation Integration Tests
+//!
+//! These tests verify that new sessions use the strongest suite both devices
+//! support that the compatibility matrix allows, and fail without an overlap.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use diesel::prelude::*;
+use rustaxum::app::models::algorithm_compatibility_matrix::{AlgorithmCompatibilityMatrix, CompatibilityLevel};
+use rustaxum::app::models::device::Device;
+use rustaxum::app::services::algorithm_negotiation_service::{AlgorithmNegotiationError, AlgorithmNegotiationService};
+use rustaxum::app::services::prekey_service::{FetchBundleRequest, OneTimePrekey, PrekeyError, PrekeyService, UploadKeysRequest};
+use rustaxum::database::DbPool;
+use rustaxum::schema::{algorithm_compatibility_matrix, devices};
+use serial_test::serial;
+
+fn algorithms(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+fn matrix_entry(encryption: &str, key_exchange: &str, is_compatible: bool) -> AlgorithmCompatibilityMatrix {
+    let level = if is_compatible { CompatibilityLevel::Full } else { CompatibilityLevel::None };
+    AlgorithmCompatibilityMatrix::new(
+        encryption.to_string(),
+        encryption.to_string(),
+        key_exchange.to_string(),
+        key_exchange.to_string(),
+        is_compatible,
+        level,
+        None,
+        None,
+    )
+}
+
+fn set_algorithms(pool: &DbPool, device: &Device, names: &[&str]) -> Result<()> {
+    let mut conn = pool.get()?;
+    diesel::update(devices::table.find(device.id.to_string()))
+        .set(devices::supported_algorithms.eq(names.iter().map(|name| Some(name.to_string())).collect::<Vec<_>>()))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+#[test]
+fn test_overlap_selects_preferred_suite() -> Result<()> {
+    let local = algorithms(&["chacha20-poly1305", "aes-256-gcm", "curve25519", "p256-ecdh"]);
+    let remote = algorithms(&["aes-128-gcm", "aes-256-gcm", "chacha20-poly1305", "p256-ecdh", "curve25519"]);
+
+    let suite = AlgorithmNegotiationService::negotiate(&local, &remote, &[])?;
+    assert_eq!(suite.encryption_algorithm, "aes-256-gcm");
+    assert_eq!(suite.key_exchange_algorithm, "curve25519");
+    assert_eq!(suite.compatibility_level, "full");
+
+    // Pairs the matrix marks incompatible are skipped for the next strongest
+    let matrix = vec![matrix_entry("aes-256-gcm", "curve25519", false)];
+    let suite = AlgorithmNegotiationService::negotiate(&local, &remote, &matrix)?;
+    assert_eq!(suite.encryption_algorithm, "aes-256-gcm");
+    assert_eq!(suite.key_exchange_algorithm, "p256-ecdh");
+
+    Ok(())
+}
+
+#[test]
+fn test_no_overlap_fails() {
+    let local = algorithms(&["aes-256-gcm", "curve25519"]);
+    let remote = algorithms(&["chacha20-poly1305", "curve25519"]);
+
+    let error = AlgorithmNegotiationService::negotiate(&local, &remote, &[])
+        .expect_err("devices without a shared cipher cannot negotiate");
+    assert_eq!(AlgorithmNegotiationError::status_code(&error), StatusCode::CONFLICT);
+
+    // A shared suite the matrix rules out is no overlap either
+    let remote = algorithms(&["aes-256-gcm", "curve25519"]);
+    let matrix = vec![matrix_entry("aes-256-gcm", "curve25519", false)];
+    AlgorithmNegotiationService::negotiate(&local, &remote, &matrix)
+        .expect_err("incompatible pairs must not be negotiated");
+}
+
+#[tokio::test]
+#[serial]
+async fn test_prekey_bundle_carries_negotiated_suite() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let service = PrekeyService::new()?;
+
+    let owner = common::create_user(&pool)?;
+    let owner_device = common::create_device(&pool, &owner)?;
+    let requester = common::create_user(&pool)?;
+    let requester_device = common::create_device(&pool, &requester)?;
+    let owner_device_id = owner_device.id.to_string();
+
+    service.upload(&pool, &owner.id.to_string(), &owner_device_id, UploadKeysRequest {
+        identity_public_key: None,
+        signed_prekey: None,
+        one_time_prekeys: vec![OneTimePrekey { key_id: 1, public_key: "prekey_public_1".to_string() }],
+    })?;
+
+    set_algorithms(&pool, &requester_device, &["aes-256-gcm", "curve25519"])?;
+    let mut conn = pool.get()?;
+    diesel::insert_into(algorithm_compatibility_matrix::table)
+        .values(&matrix_entry("aes-256-gcm", "curve25519", false))
+        .on_conflict_do_nothing()
+        .execute(&mut conn)?;
+
+    // The only shared suite is ruled out, so no prekey is consumed
+    let error = service.fetch_bundle(&pool, &requester.id.to_string(), &owner_device_id, FetchBundleRequest {
+        device_id: requester_device.id,
+    }).await.expect_err("incompatible devices cannot start a session");
+    assert_eq!(PrekeyError::status_code(&error), StatusCode::CONFLICT);
+    assert_eq!(service.status(&pool, &owner.id.to_string(), &owner_device_id)?.available_one_time_prekeys, 1);
+
+    set_algorithms(&pool, &requester_device, &["aes-256-gcm", "chacha20-poly1305", "curve25519"])?;
+    let bundle = service.fetch_bundle(&pool, &requester.id.to_string(), &owner_device_id, FetchBundleRequest {
+        device_id: requester_device.id,
+    }).await?;
+    assert_eq!(bundle.suite.encryption_algorithm, "chacha20-poly1305");
+    assert_eq!(bundle.suite.key_exchange_algorithm, "curve25519");
+    assert!(bundle.one_time_prekey.is_some());
+
+    // The matrix is shared, so leave it as other tests expect
+    diesel::delete(
+        algorithm_compatibility_matrix::table
+            .filter(algorithm_compatibility_matrix::encryption_algorithm_a.eq("aes-256-gcm"))
+            .filter(algorithm_compatibility_matrix::key_exchange_algorithm_a.eq("curve25519"))
+    ).execute(&mut conn)?;
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! Array Session Driver Tests
+//!
+//! These tests verify that the in-memory session driver reads back what was
+//! written, forgets destroyed sessions, and expires sessions by their last
+//! write on `read` and on `gc`.
+
+use anyhow::Result;
+use rustaxum::app::services::session::drivers::ArraySessionHandler;
+use rustaxum::app::services::session::SessionHandler;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_write_read_and_destroy() -> Result<()> {
+    let handler = ArraySessionHandler::with_lifetime(3600);
+
+    assert_eq!(handler.read("missing").await?, None);
+
+    handler.write("abc", r#"{"user_id":1}"#).await?;
+    assert_eq!(handler.read("abc").await?.as_deref(), Some(r#"{"user_id":1}"#));
+
+    handler.write("abc", r#"{"user_id":2}"#).await?;
+    assert_eq!(handler.read("abc").await?.as_deref(), Some(r#"{"user_id":2}"#));
+
+    handler.destroy("abc").await?;
+    assert_eq!(handler.read("abc").await?, None);
+    assert!(handler.is_empty().await);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_expires_after_lifetime() -> Result<()> {
+    let handler = ArraySessionHandler::with_lifetime(60);
+    handler.write("abc", "data").await?;
+
+    handler.travel(Duration::from_secs(60));
+    assert_eq!(handler.read("abc").await?.as_deref(), Some("data"));
+
+    handler.travel(Duration::from_secs(2));
+    assert_eq!(handler.read("abc").await?, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_write_extends_lifetime() -> Result<()> {
+    let handler = ArraySessionHandler::with_lifetime(60);
+    handler.write("abc", "first").await?;
+
+    handler.travel(Duration::from_secs(45));
+    handler.write("abc", "second").await?;
+    handler.travel(Duration::from_secs(45));
+
+    assert_eq!(handler.read("abc").await?.as_deref(), Some("second"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_gc_removes_sessions_older_than_lifetime() -> Result<()> {
+    let handler = ArraySessionHandler::with_lifetime(3600);
+    handler.write("stale", "old").await?;
+
+    handler.travel(Duration::from_secs(120));
+    handler.write("fresh", "new").await?;
+
+    handler.travel(Duration::from_secs(30));
+    handler.gc(100).await?;
+
+    assert_eq!(handler.len().await, 1);
+    assert_eq!(handler.read("stale").await?, None);
+    assert_eq!(handler.read("fresh").await?.as_deref(), Some("new"));
+    Ok(())
+}
@@ -0,0 +1,163 @@
+//! AuthUser Extractor Tests
+//!
+//! These tests verify that a handler taking `AuthUser` resolves the user from
+//! an OAuth token, a first-party JWT or an authenticated session, answers 401
+//! when no guard accepts the request, and that `AuthUserVia` only accepts the
+//! guard it names.
+
+use anyhow::Result;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    routing::get,
+    Json, Router,
+};
+use rustaxum::app::http::middleware::auth_guard::{AuthUser, AuthUserVia, SessionGuard, TokenGuard};
+use rustaxum::app::models::DieselUlid;
+use rustaxum::app::services::auth_service::AuthService;
+use rustaxum::app::services::oauth::TokenClaims;
+use rustaxum::app::services::session::{SessionManager, SessionStore};
+use rustaxum::config::session::SessionConfig;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+fn app() -> Router {
+    Router::new()
+        .route("/me", get(|user: AuthUser| async move {
+            Json(json!({ "user_id": user.user_id, "auth_method": user.auth_method }))
+        }))
+        .route("/token-only", get(|user: AuthUserVia<TokenGuard>| async move {
+            Json(json!({ "user_id": user.user_id, "auth_method": user.auth_method }))
+        }))
+        .route("/session-only", get(|user: AuthUserVia<SessionGuard>| async move {
+            Json(json!({ "user_id": user.user_id, "auth_method": user.auth_method }))
+        }))
+}
+
+/// Claims as `oauth_middleware` leaves them on the request
+fn oauth_claims(user_id: DieselUlid) -> TokenClaims {
+    TokenClaims {
+        sub: user_id,
+        aud: DieselUlid::new(),
+        exp: None,
+        iat: 0,
+        jti: DieselUlid::new(),
+        iss: None,
+        scopes: vec!["read".to_string()],
+    }
+}
+
+async fn signed_in_session(user_id: &str) -> Result<SessionStore> {
+    let mut config = SessionConfig::from_env()?;
+    config.driver = "array".to_string();
+
+    let store = SessionStore::new(SessionManager::new(config.clone(), None, None).await?, config);
+    store.start(None).await?;
+    store.put("user_id", json!(user_id)).await;
+    store.put("authenticated", json!(true)).await;
+    Ok(store)
+}
+
+async fn send(request: Request<Body>) -> Result<(StatusCode, Value)> {
+    let response = app().oneshot(request).await?;
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok((status, serde_json::from_slice(&body)?))
+}
+
+#[tokio::test]
+async fn test_resolves_user_from_oauth_token() -> Result<()> {
+    let user_id = DieselUlid::new();
+    let mut request = Request::builder().uri("/me").body(Body::empty())?;
+    request.extensions_mut().insert(oauth_claims(user_id));
+
+    let (status, body) = send(request).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["user_id"], user_id.to_string());
+    assert_eq!(body["auth_method"], "oauth");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resolves_user_from_jwt() -> Result<()> {
+    let user_id = DieselUlid::new().to_string();
+    let token = AuthService::generate_access_token(&user_id, 3600)?;
+    let request = Request::builder()
+        .uri("/me")
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())?;
+
+    let (status, body) = send(request).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["user_id"], user_id);
+    assert_eq!(body["auth_method"], "jwt");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resolves_user_from_session() -> Result<()> {
+    let user_id = DieselUlid::new().to_string();
+    let mut request = Request::builder().uri("/me").body(Body::empty())?;
+    request.extensions_mut().insert(signed_in_session(&user_id).await?);
+
+    let (status, body) = send(request).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["user_id"], user_id);
+    assert_eq!(body["auth_method"], "session");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_token_is_tried_before_session() -> Result<()> {
+    let token_user = DieselUlid::new();
+    let mut request = Request::builder().uri("/me").body(Body::empty())?;
+    request.extensions_mut().insert(oauth_claims(token_user));
+    request.extensions_mut().insert(signed_in_session(&DieselUlid::new().to_string()).await?);
+
+    let (status, body) = send(request).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["user_id"], token_user.to_string());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unauthenticated_request_gets_401() -> Result<()> {
+    let store = signed_in_session(&DieselUlid::new().to_string()).await?;
+    store.put("authenticated", json!(false)).await;
+
+    let mut request = Request::builder()
+        .uri("/me")
+        .header(header::AUTHORIZATION, "Bearer not-a-token")
+        .body(Body::empty())?;
+    request.extensions_mut().insert(store);
+
+    let (status, body) = send(request).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"], "Unauthorized");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_handler_can_require_a_guard() -> Result<()> {
+    let user_id = DieselUlid::new();
+
+    let mut request = Request::builder().uri("/session-only").body(Body::empty())?;
+    request.extensions_mut().insert(oauth_claims(user_id));
+    let (status, body) = send(request).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["message"], "Authentication required. Please sign in.");
+
+    let mut request = Request::builder().uri("/token-only").body(Body::empty())?;
+    request.extensions_mut().insert(signed_in_session(&user_id.to_string()).await?);
+    let (status, _) = send(request).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let mut request = Request::builder().uri("/session-only").body(Body::empty())?;
+    request.extensions_mut().insert(oauth_claims(DieselUlid::new()));
+    request.extensions_mut().insert(signed_in_session(&user_id.to_string()).await?);
+    let (status, body) = send(request).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["user_id"], user_id.to_string());
+    assert_eq!(body["auth_method"], "session");
+    Ok(())
+}
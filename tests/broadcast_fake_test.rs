@@ -0,0 +1,90 @@
+//! Broadcast Fake Tests
+//!
+//! These tests verify that `BroadcastFacade::fake()` records messages per
+//! channel instead of delivering them, whether they are sent through the
+//! broadcast manager or the helper functions, and that `restore` undoes it.
+
+use anyhow::Result;
+use rustaxum::app::broadcasting::helpers::{broadcast_to_user, BroadcastFacade};
+use rustaxum::app::broadcasting::{BroadcastManager, Broadcastable, LogDriver};
+use serde_json::json;
+use serial_test::serial;
+
+#[derive(Debug)]
+struct InvoicePaid;
+
+impl Broadcastable for InvoicePaid {
+    fn broadcast_channel(&self) -> String {
+        "invoices".to_string()
+    }
+
+    fn broadcast_data(&self) -> serde_json::Value {
+        json!({ "invoice_id": "inv-1" })
+    }
+
+    fn is_private(&self) -> bool {
+        true
+    }
+
+    fn private_channel(&self) -> Option<String> {
+        Some("org.acme".to_string())
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_fake_records_helper_broadcasts() -> Result<()> {
+    BroadcastFacade::fake().await;
+    assert!(BroadcastFacade::assert_nothing_broadcast().await);
+
+    broadcast_to_user("ada", "message.mentioned", json!({ "message_id": "message-1" })).await?;
+    BroadcastFacade::to_team("core").event("deployed").send().await?;
+
+    assert!(BroadcastFacade::assert_broadcast_on("user.ada").await);
+    assert!(BroadcastFacade::assert_broadcast_event_on("user.ada", "message.mentioned").await);
+    assert!(!BroadcastFacade::assert_broadcast_event_on("user.ada", "deployed").await);
+    assert!(BroadcastFacade::assert_not_broadcast_on("user.grace").await);
+    assert!(!BroadcastFacade::assert_broadcast_on("user.grace").await);
+    assert!(BroadcastFacade::assert_broadcast_times("team.core", 1).await);
+    assert!(!BroadcastFacade::assert_nothing_broadcast().await);
+
+    let broadcasts = BroadcastFacade::get_broadcasts().await;
+    assert_eq!(broadcasts.len(), 2);
+    assert_eq!(broadcasts[0].data["message_id"], "message-1");
+
+    BroadcastFacade::restore().await;
+    assert!(!rustaxum::app::broadcasting::is_faking().await);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_fake_records_manager_broadcasts() -> Result<()> {
+    BroadcastFacade::fake().await;
+    rustaxum::app::broadcasting::broadcast(&InvoicePaid).await?;
+    rustaxum::app::broadcasting::broadcast_to_channel("system", json!({ "level": "info" })).await?;
+
+    assert!(BroadcastFacade::assert_broadcast_on("private:org.acme").await);
+    assert!(BroadcastFacade::assert_not_broadcast_on("invoices").await);
+    assert!(BroadcastFacade::assert_broadcast_times("system", 1).await);
+
+    BroadcastFacade::restore().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restore_delivers_through_drivers_again() -> Result<()> {
+    let mut manager = BroadcastManager::new("log".to_string());
+    let fake = manager.fake();
+    manager.broadcast_with_driver(&InvoicePaid, "pusher").await?;
+    assert!(fake.assert_broadcast_times("private:org.acme", 1).await);
+
+    manager.restore();
+    assert!(!manager.is_faking());
+    assert!(manager.broadcast(&InvoicePaid).await.is_err(), "no log driver is registered");
+
+    manager.register_driver("log".to_string(), Box::new(LogDriver::new()));
+    manager.broadcast(&InvoicePaid).await?;
+    assert!(fake.assert_broadcast_times("private:org.acme", 1).await, "the fake no longer records");
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! Broadcast Fan-Out Tests
+//!
+//! These tests verify that `BroadcastManager::broadcast_to_many` sends the same
+//! data to every channel through the default driver, and that a channel whose
+//! broadcast fails is reported without stopping the others.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rustaxum::app::broadcasting::{BroadcastDriver, BroadcastManager};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Rejects the `broken` channel and records the rest
+#[derive(Default)]
+struct FlakyDriver {
+    delivered: Arc<RwLock<Vec<String>>>,
+}
+
+#[async_trait]
+impl BroadcastDriver for FlakyDriver {
+    async fn broadcast(&self, channel: &str, _data: serde_json::Value) -> Result<()> {
+        if channel == "broken" {
+            return Err(anyhow::anyhow!("channel rejected"));
+        }
+        self.delivered.write().await.push(channel.to_string());
+        Ok(())
+    }
+
+    async fn broadcast_private(&self, channel: &str, data: serde_json::Value) -> Result<()> {
+        self.broadcast(&format!("private:{}", channel), data).await
+    }
+
+    fn driver_name(&self) -> &'static str {
+        "flaky"
+    }
+}
+
+fn channels(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[tokio::test]
+async fn test_failed_channel_does_not_stop_the_others() -> Result<()> {
+    let driver = FlakyDriver::default();
+    let delivered = driver.delivered.clone();
+    let mut manager = BroadcastManager::new("flaky".to_string());
+    manager.register_driver("flaky".to_string(), Box::new(driver));
+
+    let summary = manager
+        .broadcast_to_many(&channels(&["user.1", "broken", "team.9"]), serde_json::json!({ "status": "online" }))
+        .await?;
+
+    assert!(!summary.is_success());
+    assert_eq!(summary.succeeded(), vec!["user.1", "team.9"]);
+    let failed = summary.failed();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].0, "broken");
+
+    let mut delivered = delivered.read().await.clone();
+    delivered.sort();
+    assert_eq!(delivered, vec!["team.9", "user.1"]);
+
+    let error = summary.into_result().expect_err("one channel failed");
+    assert!(error.to_string().contains("1 of 3 channels"));
+    assert!(error.to_string().contains("broken: channel rejected"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_every_channel_receives_the_data() -> Result<()> {
+    let mut manager = BroadcastManager::new("log".to_string());
+    let fake = manager.fake();
+
+    let summary = manager
+        .broadcast_to_many(&channels(&["presence.1", "presence.2"]), serde_json::json!({ "user_id": 5 }))
+        .await?;
+
+    assert!(summary.is_success());
+    summary.into_result()?;
+    assert!(fake.assert_broadcast_on("presence.1").await);
+    assert!(fake.assert_broadcast_on("presence.2").await);
+    assert!(fake.broadcasts().await.iter().all(|message| message.data["user_id"] == 5));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_missing_default_driver_fails_the_call() {
+    let manager = BroadcastManager::new("redis".to_string());
+    assert!(manager.broadcast_to_many(&channels(&["user.1"]), serde_json::json!({})).await.is_err());
+}
@@ -0,0 +1,88 @@
+//! Broadcast Monitor Tests
+//!
+//! These tests verify the per-channel aggregation behind `broadcast monitor`
+//! and the run summary printed by `broadcast ping`.
+
+use chrono::{Duration as ChronoDuration, Utc};
+use rustaxum::app::broadcasting::monitor::{ChannelActivity, PingSummary};
+use rustaxum::app::broadcasting::BroadcastMessage;
+use std::time::Duration;
+
+fn message(channel: &str, event: &str, offset_secs: i64) -> BroadcastMessage {
+    BroadcastMessage {
+        channel: channel.to_string(),
+        event: event.to_string(),
+        data: serde_json::json!({}),
+        timestamp: Utc::now() + ChronoDuration::seconds(offset_secs),
+        id: None,
+    }
+}
+
+#[test]
+fn test_activity_counts_messages_per_channel() {
+    let mut activity = ChannelActivity::new();
+    let stream = [
+        message("general", "ping", 0),
+        message("orders", "created", 1),
+        message("general", "ping", 2),
+        message("general", "alert", 3),
+        message("orders", "updated", 4),
+        message("user.1", "notification", 5),
+    ];
+    for msg in &stream {
+        activity.record(msg);
+    }
+
+    assert_eq!(activity.total(), 6);
+
+    let channels = activity.channels();
+    let counts: Vec<(&str, u64)> = channels.iter().map(|s| (s.channel.as_str(), s.messages)).collect();
+    assert_eq!(counts, vec![("general", 3), ("orders", 2), ("user.1", 1)]);
+    assert_eq!(channels[0].last_event, "alert");
+    assert_eq!(channels[1].last_event, "updated");
+}
+
+#[test]
+fn test_activity_keeps_latest_event_for_out_of_order_messages() {
+    let mut activity = ChannelActivity::new();
+    activity.record(&message("general", "late", 10));
+    activity.record(&message("general", "early", 0));
+
+    let channels = activity.channels();
+    assert_eq!(channels[0].messages, 2);
+    assert_eq!(channels[0].last_event, "late");
+}
+
+#[test]
+fn test_render_table_lists_channels_and_total() {
+    let mut activity = ChannelActivity::new();
+    assert_eq!(activity.render_table(), "No broadcast messages observed.");
+
+    activity.record(&message("general", "ping", 0));
+    activity.record(&message("general", "ping", 1));
+    activity.record(&message("orders", "created", 2));
+
+    let table = activity.render_table();
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].starts_with("Channel"));
+    assert!(lines[2].starts_with("general") && lines[2].contains(" 2 "));
+    assert!(lines[3].starts_with("orders") && lines[3].contains(" 1 "));
+    assert!(lines[4].starts_with("Total") && lines[4].ends_with('3'));
+}
+
+#[test]
+fn test_ping_summary() {
+    let mut summary = PingSummary::default();
+    summary.record_sent();
+    summary.record_sent();
+    summary.record_failed();
+    summary.record_sent();
+
+    assert_eq!(summary.sent, 3);
+    assert_eq!(summary.failed, 1);
+    assert_eq!(
+        summary.render(Duration::from_secs(12)),
+        "Pings sent: 3 | Failed: 1 | Duration: 12s"
+    );
+}
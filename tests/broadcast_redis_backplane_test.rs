@@ -0,0 +1,72 @@
+//! Redis Broadcast Backplane Integration Tests
+//!
+//! These tests verify that messages published through the broadcast manager
+//! with the Redis driver reach clients of the standalone WebSocket server,
+//! and that a blank channel prefix never widens the subscription to every
+//! Redis channel. A Redis server must be reachable using the BROADCAST_REDIS_* settings.
+
+use anyhow::Result;
+use futures::StreamExt;
+use rustaxum::app::broadcasting::{websocket::serve_websocket, BroadcastManager, BroadcastMessage, RedisDriver};
+use rustaxum::config::broadcasting::DEFAULT_CHANNELS_PREFIX;
+use rustaxum::config::Config;
+use serial_test::serial;
+use std::time::Duration;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+#[tokio::test]
+#[serial]
+async fn test_standalone_server_receives_redis_broadcasts() -> Result<()> {
+    let mut config = Config::load()?.broadcasting;
+    config.default_driver = "redis".to_string();
+    config.channels_prefix = format!("test-{}:", ulid::Ulid::new());
+
+    // Start the standalone server on an ephemeral port
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(serve_websocket(listener, config.clone()));
+
+    let (mut socket, _) = connect_async(format!("ws://{}/ws/public", addr)).await?;
+
+    // First frame is the welcome message
+    let welcome = socket.next().await.expect("welcome frame")?;
+    let welcome: BroadcastMessage = serde_json::from_str(welcome.to_text()?)?;
+    assert_eq!(welcome.event, "connected");
+
+    // Publish through the manager as the API process would
+    let mut manager = BroadcastManager::new("redis".to_string());
+    manager.register_driver("redis".to_string(), Box::new(RedisDriver::from_config(&config)));
+
+    let data = serde_json::json!({ "message": "hello from the api" });
+    let received = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            // The subscriber may still be connecting, so keep publishing until it relays
+            manager.broadcast_to_channel("public", data.clone()).await?;
+
+            if let Ok(Some(frame)) = tokio::time::timeout(Duration::from_millis(200), socket.next()).await {
+                if let Message::Text(text) = frame? {
+                    return Ok::<BroadcastMessage, anyhow::Error>(serde_json::from_str(&text)?);
+                }
+            }
+        }
+    })
+    .await??;
+
+    assert_eq!(received.channel, "public");
+    assert_eq!(received.data["message"], "hello from the api");
+
+    Ok(())
+}
+
+#[test]
+fn test_blank_channels_prefix_falls_back_to_default() -> Result<()> {
+    let mut config = Config::load()?.broadcasting;
+    config.channels_prefix = "  ".to_string();
+
+    assert_eq!(config.channels_pattern(), format!("{}*", DEFAULT_CHANNELS_PREFIX));
+
+    let driver = RedisDriver::from_config(&config);
+    assert_eq!(driver.channels_prefix, DEFAULT_CHANNELS_PREFIX);
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+//! Broadcast Replay Tests
+//!
+//! These tests verify that a WebSocket client reconnecting with a
+//! `last_event_id` first receives the buffered messages it missed, that the
+//! buffer honours its size and TTL, and that with the Redis driver messages
+//! published before a server relayed them are replayed from Redis, with
+//! concurrent publishers numbering messages without gaps or reordering. The
+//! Redis tests need a server reachable using the BROADCAST_REDIS_* settings.
+
+use anyhow::Result;
+use futures::StreamExt;
+use rustaxum::app::broadcasting::replay::{RedisReplayBuffer, ReplayOptions};
+use rustaxum::app::broadcasting::websocket::{serve_websocket, websocket_routes, WebSocketManager};
+use rustaxum::app::broadcasting::{BroadcastDriver, BroadcastMessage, RedisDriver};
+use rustaxum::config::Config;
+use serial_test::serial;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+type Socket = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+fn message(channel: &str, n: i64) -> BroadcastMessage {
+    BroadcastMessage {
+        channel: channel.to_string(),
+        event: "tick".to_string(),
+        data: serde_json::json!({ "n": n }),
+        timestamp: chrono::Utc::now(),
+        id: None,
+    }
+}
+
+async fn serve(manager: Arc<WebSocketManager>) -> Result<SocketAddr> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move { axum::serve(listener, websocket_routes().with_state(manager)).await });
+    Ok(addr)
+}
+
+/// Connect and skip the welcome frame
+async fn connect(url: &str) -> Result<Socket> {
+    let (mut socket, _) = connect_async(url).await?;
+    let welcome: BroadcastMessage = serde_json::from_str(socket.next().await.expect("welcome frame")?.to_text()?)?;
+    assert_eq!(welcome.event, "connected");
+    Ok(socket)
+}
+
+async fn receive(socket: &mut Socket, count: usize) -> Result<Vec<BroadcastMessage>> {
+    let mut messages = Vec::new();
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while messages.len() < count {
+            if let Message::Text(text) = socket.next().await.expect("socket closed")? {
+                messages.push(serde_json::from_str(&text)?);
+            }
+        }
+        Ok::<(), anyhow::Error>(())
+    })
+    .await??;
+    Ok(messages)
+}
+
+fn numbers(messages: &[BroadcastMessage]) -> Vec<i64> {
+    messages.iter().map(|message| message.data["n"].as_i64().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn test_reconnecting_websocket_receives_missed_messages() -> Result<()> {
+    let manager = Arc::new(WebSocketManager::new());
+    let addr = serve(manager.clone()).await?;
+
+    let mut first = connect(&format!("ws://{}/ws/public", addr)).await?;
+    manager.broadcast(message("public", 1)).await?;
+    let seen = receive(&mut first, 1).await?;
+    let last_event_id = seen[0].id.expect("broadcasts carry an id");
+    drop(first);
+
+    // Sent while the client was away
+    manager.broadcast(message("public", 2)).await?;
+    manager.broadcast(message("public", 3)).await?;
+
+    let mut second = connect(&format!("ws://{}/ws/public?last_event_id={}", addr, last_event_id)).await?;
+    manager.broadcast(message("public", 4)).await?;
+
+    let received = receive(&mut second, 3).await?;
+    assert_eq!(numbers(&received), vec![2, 3, 4]);
+    assert_eq!(received.iter().map(|message| message.id).collect::<Vec<_>>(), vec![Some(2), Some(3), Some(4)]);
+
+    // `since` is accepted as well
+    let mut third = connect(&format!("ws://{}/ws/public?since=3", addr)).await?;
+    assert_eq!(numbers(&receive(&mut third, 1).await?), vec![4]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_buffer_keeps_only_the_configured_size() -> Result<()> {
+    let manager = WebSocketManager::new().with_replay(ReplayOptions::new(2, None));
+    manager.subscribe("public").await;
+
+    for n in 1..=5 {
+        manager.broadcast(message("public", n)).await?;
+    }
+
+    let subscription = manager.subscribe_since("public", Some(0)).await;
+    assert_eq!(numbers(&subscription.missed), vec![4, 5]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_expired_messages_are_not_replayed() -> Result<()> {
+    let manager = WebSocketManager::new().with_replay(ReplayOptions::new(10, Some(Duration::from_millis(50))));
+    manager.subscribe("public").await;
+
+    manager.broadcast(message("public", 1)).await?;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    manager.broadcast(message("public", 2)).await?;
+
+    let subscription = manager.subscribe_since("public", Some(0)).await;
+    assert_eq!(numbers(&subscription.missed), vec![2]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_disabled_buffer_replays_nothing() -> Result<()> {
+    let manager = WebSocketManager::new().with_replay(ReplayOptions::new(0, None));
+    manager.subscribe("public").await;
+    manager.broadcast(message("public", 1)).await?;
+
+    assert!(manager.subscribe_since("public", Some(0)).await.missed.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_redis_driver_replays_from_the_shared_buffer() -> Result<()> {
+    let mut config = Config::load()?.broadcasting;
+    config.default_driver = "redis".to_string();
+    config.channels_prefix = format!("test-{}:", ulid::Ulid::new());
+    config.replay_buffer_size = 10;
+    config.replay_ttl_secs = 60;
+
+    // Published before any server relayed them, so only Redis holds them
+    let driver = RedisDriver::from_config(&config);
+    for n in 1..=3 {
+        driver.broadcast("public", serde_json::json!({ "n": n })).await?;
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(serve_websocket(listener, config.clone()));
+
+    let mut socket = connect(&format!("ws://{}/ws/public?last_event_id=1", addr)).await?;
+    let replayed = receive(&mut socket, 2).await?;
+    assert_eq!(numbers(&replayed), vec![2, 3]);
+    assert_eq!(replayed.iter().map(|message| message.id).collect::<Vec<_>>(), vec![Some(2), Some(3)]);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_concurrent_redis_pushes_are_numbered_in_order() -> Result<()> {
+    let mut config = Config::load()?.broadcasting;
+    config.channels_prefix = format!("test-{}:", ulid::Ulid::new());
+    config.replay_buffer_size = 50;
+    config.replay_ttl_secs = 60;
+    let buffer = RedisReplayBuffer::from_config(&config);
+
+    let pushes = (1..=20).map(|n| {
+        let buffer = buffer.clone();
+        tokio::spawn(async move {
+            let mut pushed = message("public", n);
+            buffer.push(&mut pushed).await.map(|_| pushed)
+        })
+    });
+    let mut ids = Vec::new();
+    for push in futures::future::join_all(pushes).await {
+        ids.push(push??.id.unwrap());
+    }
+    ids.sort();
+    assert_eq!(ids, (1..=20).collect::<Vec<u64>>());
+
+    let replayed = buffer.since("public", 0).await?;
+    assert_eq!(replayed.iter().map(|message| message.id).collect::<Vec<_>>(), (1..=20).map(Some).collect::<Vec<_>>());
+    Ok(())
+}
@@ -0,0 +1,155 @@
+//! Broadcasting Auth Integration Tests
+//!
+//! These tests verify that `/broadcasting/auth` signs subscriptions for the
+//! channels a user's authorization callbacks allow and refuses the rest, and
+//! that a native allow token joins a presence channel as the user it was
+//! issued to.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use futures::StreamExt;
+use hmac::{Hmac, Mac};
+use rustaxum::app::broadcasting::websocket::{websocket_routes, WebSocketManager};
+use rustaxum::app::broadcasting::BroadcastMessage;
+use rustaxum::app::services::broadcast_auth_service::{BroadcastAuthError, BroadcastAuthRequest, BroadcastAuthService};
+use rustaxum::config::broadcasting::BroadcastingConfig;
+use serial_test::serial;
+use sha2::Sha256;
+use std::sync::Arc;
+use tokio_tungstenite::connect_async;
+
+const APP_KEY: &str = "test-app-key";
+const APP_SECRET: &str = "test-app-secret";
+const SOCKET_ID: &str = "1234.5678";
+
+fn service(auth_protocol: &str) -> Result<BroadcastAuthService> {
+    Ok(BroadcastAuthService::with_config(BroadcastingConfig {
+        auth_protocol: auth_protocol.to_string(),
+        app_key: APP_KEY.to_string(),
+        app_secret: Some(APP_SECRET.to_string()),
+        ..BroadcastingConfig::from_env()?
+    }))
+}
+
+fn request(channel_name: &str, socket_id: Option<&str>) -> BroadcastAuthRequest {
+    BroadcastAuthRequest {
+        socket_id: socket_id.map(String::from),
+        channel_name: channel_name.to_string(),
+    }
+}
+
+fn pusher_signature(payload: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(APP_SECRET.as_bytes()).unwrap();
+    mac.update(payload.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_authorized_channels_are_signed() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let user_id = user.id.to_string();
+    let conversation = common::create_conversation(&pool, &user)?;
+    common::add_participant(&pool, &conversation, &user)?;
+
+    // Pusher-compatible private channel signature
+    let pusher = service("pusher")?;
+    let channel = format!("private-user.{}", user_id);
+    let auth = pusher.authorize(&pool, &user_id, request(&channel, Some(SOCKET_ID)))?;
+    assert_eq!(auth.auth, format!("{}:{}", APP_KEY, pusher_signature(&format!("{}:{}", SOCKET_ID, channel))));
+    assert!(auth.channel_data.is_none());
+
+    // Presence channels also sign the member data
+    let channel = format!("presence-conversation.{}", conversation.id);
+    let auth = pusher.authorize(&pool, &user_id, request(&channel, Some(SOCKET_ID)))?;
+    let channel_data = auth.channel_data.expect("presence channels carry member data");
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&channel_data)?["user_id"], user_id);
+    assert_eq!(auth.auth, format!("{}:{}", APP_KEY, pusher_signature(&format!("{}:{}:{}", SOCKET_ID, channel, channel_data))));
+
+    // Native allow tokens are only valid for the channel they were issued for
+    let native = service("native")?;
+    let channel = format!("conversation.{}", conversation.id);
+    let auth = native.authorize(&pool, &user_id, request(&channel, None))?;
+    assert_eq!(native.verify_native_token(&channel, &auth.auth), Some(user_id.clone()));
+    assert_eq!(native.verify_native_token(&format!("user.{}", user_id), &auth.auth), None);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_unauthorized_channels_are_rejected() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let other = common::create_user(&pool)?;
+    let conversation = common::create_conversation(&pool, &other)?;
+    common::add_participant(&pool, &conversation, &other)?;
+    let user_id = user.id.to_string();
+    let pusher = service("pusher")?;
+
+    let error = pusher.authorize(&pool, &user_id, request(&format!("private-user.{}", other.id), Some(SOCKET_ID)))
+        .expect_err("users cannot listen on another user's channel");
+    assert_eq!(BroadcastAuthError::status_code(&error), StatusCode::FORBIDDEN);
+
+    let error = pusher.authorize(&pool, &user_id, request(&format!("presence-conversation.{}", conversation.id), Some(SOCKET_ID)))
+        .expect_err("non-participants cannot join a conversation channel");
+    assert_eq!(BroadcastAuthError::status_code(&error), StatusCode::FORBIDDEN);
+
+    // The Pusher protocol binds the signature to a socket
+    let error = pusher.authorize(&pool, &user_id, request(&format!("private-user.{}", user_id), None))
+        .expect_err("a socket_id is required");
+    assert_eq!(BroadcastAuthError::status_code(&error), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Nothing is signed without a configured secret
+    let unconfigured = BroadcastAuthService::with_config(BroadcastingConfig {
+        app_secret: None,
+        ..BroadcastingConfig::from_env()?
+    });
+    unconfigured.authorize(&pool, &user_id, request(&format!("user.{}", user_id), None))
+        .expect_err("authorization requires BROADCAST_APP_SECRET");
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_native_token_joins_presence_channel_as_its_user() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let user_id = user.id.to_string();
+    let conversation = common::create_conversation(&pool, &user)?;
+    common::add_participant(&pool, &conversation, &user)?;
+
+    // The WebSocket endpoint verifies tokens with the configured secret
+    let previous = std::env::var("BROADCAST_APP_SECRET").ok();
+    std::env::set_var("BROADCAST_APP_SECRET", APP_SECRET);
+
+    let result = async {
+        let channel = format!("presence:conversation.{}", conversation.id);
+        let auth = service("native")?.authorize(&pool, &user_id, request(&channel, None))?;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let manager = Arc::new(WebSocketManager::new());
+        tokio::spawn(async move { axum::serve(listener, websocket_routes().with_state(manager)).await });
+
+        let url = format!("ws://{}/ws/{}?channel_auth={}", addr, channel, auth.auth);
+        let (mut socket, _) = connect_async(url).await?;
+
+        let welcome = socket.next().await.expect("welcome frame")?;
+        let welcome: BroadcastMessage = serde_json::from_str(welcome.to_text()?)?;
+        assert_eq!(welcome.event, "connected");
+        assert_eq!(welcome.data["members"][0]["user_id"], user_id);
+
+        Ok::<_, anyhow::Error>(())
+    }.await;
+
+    match previous {
+        Some(secret) => std::env::set_var("BROADCAST_APP_SECRET", secret),
+        None => std::env::remove_var("BROADCAST_APP_SECRET"),
+    }
+    result
+}
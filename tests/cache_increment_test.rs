@@ -0,0 +1,80 @@
+//! Cache Counter Tests
+//!
+//! These tests verify that `increment` and `decrement` start a missing key
+//! at the delta, like Redis `INCRBY`/`DECRBY`, and that many tasks
+//! incrementing one key concurrently never lose an update. The Redis test
+//! only runs when `REDIS_URL` is set.
+
+use anyhow::Result;
+use rustaxum::cache::drivers::{MemoryCache, RedisCache};
+use rustaxum::cache::Cache;
+use std::sync::Arc;
+
+const TASKS: i64 = 50;
+const INCREMENTS_PER_TASK: i64 = 20;
+
+async fn increment_concurrently<C: Cache + 'static>(cache: Arc<C>, key: &str) -> Result<i64> {
+    let mut handles = Vec::new();
+    for _ in 0..TASKS {
+        let cache = cache.clone();
+        let key = key.to_string();
+        handles.push(tokio::spawn(async move {
+            for _ in 0..INCREMENTS_PER_TASK {
+                cache.increment(&key, 1).await?;
+            }
+            anyhow::Ok(())
+        }));
+    }
+    for handle in handles {
+        handle.await??;
+    }
+
+    Ok(cache.get::<i64>(key).await?.unwrap_or_default())
+}
+
+#[tokio::test]
+async fn test_missing_key_starts_at_the_delta() -> Result<()> {
+    let cache = MemoryCache::default();
+
+    assert_eq!(cache.increment("hits", 5).await?, 5);
+    assert_eq!(cache.increment("hits", 2).await?, 7);
+    assert_eq!(cache.decrement("misses", 3).await?, -3);
+    assert_eq!(cache.decrement("hits", 7).await?, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_overflow_is_an_error() -> Result<()> {
+    let cache = MemoryCache::default();
+    cache.increment("big", i64::MAX).await?;
+
+    assert!(cache.increment("big", 1).await.is_err());
+    assert!(cache.decrement("other", i64::MIN).await.is_err());
+    assert_eq!(cache.get::<i64>("big").await?, Some(i64::MAX));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_memory_concurrent_increments_are_exact() -> Result<()> {
+    let cache = Arc::new(MemoryCache::default());
+
+    let total = increment_concurrently(cache, "concurrent").await?;
+    assert_eq!(total, TASKS * INCREMENTS_PER_TASK);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_redis_concurrent_increments_are_exact() -> Result<()> {
+    let Ok(url) = std::env::var("REDIS_URL") else {
+        return Ok(());
+    };
+    let cache = Arc::new(RedisCache::new(&url, Some(format!("test:{}", ulid::Ulid::new()))).await?);
+
+    assert_eq!(cache.decrement("fresh", 4).await?, -4);
+    let total = increment_concurrently(cache.clone(), "concurrent").await?;
+    assert_eq!(total, TASKS * INCREMENTS_PER_TASK);
+
+    cache.forget("fresh").await?;
+    cache.forget("concurrent").await?;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! Cache Stampede Tests
+//!
+//! These tests verify that `CacheDriver::remember` runs the callback once
+//! when many tasks miss the same key together, with every task getting the
+//! computed value, and that a failed callback lets the next caller compute.
+
+use anyhow::Result;
+use rustaxum::cache::drivers::MemoryCache;
+use rustaxum::cache::manager::CacheDriver;
+use rustaxum::cache::Cache;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const CALLERS: usize = 25;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_misses_run_the_callback_once() -> Result<()> {
+    let cache = CacheDriver::Memory(Arc::new(MemoryCache::default()));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let key = format!("expensive:{}", ulid::Ulid::new());
+
+    let mut handles = Vec::new();
+    for _ in 0..CALLERS {
+        let (cache, calls, key) = (cache.clone(), calls.clone(), key.clone());
+        handles.push(tokio::spawn(async move {
+            cache.remember(&key, Some(Duration::from_secs(60)), || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(42_i64)
+            }).await
+        }));
+    }
+
+    for handle in handles {
+        assert_eq!(handle.await??, 42);
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_callback_lets_the_next_caller_compute() -> Result<()> {
+    let cache = CacheDriver::Memory(Arc::new(MemoryCache::default()));
+    let key = format!("flaky:{}", ulid::Ulid::new());
+
+    let failed = cache.remember::<i64, _, _>(&key, None, || async { anyhow::bail!("upstream unavailable") }).await;
+    assert!(failed.is_err());
+    assert!(!cache.has(&key).await?);
+
+    assert_eq!(cache.remember(&key, None, || async { Ok(7_i64) }).await?, 7);
+    assert_eq!(cache.remember(&key, None, || async { Ok(8_i64) }).await?, 7);
+    Ok(())
+}
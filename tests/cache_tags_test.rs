@@ -0,0 +1,118 @@
+//! Cache Tag Tests
+//!
+//! These tests verify that a tagged view keeps its entries apart from the
+//! untagged store, that `flush_tags` evicts every entry stored under a tag,
+//! including entries that also carry other tags, and that entries under
+//! other tags survive the flush. Using the `ArrayCache` double, they also
+//! check that a tag's key set keeps every key written concurrently, lives as
+//! long as its longest-lived entry and drops forgotten keys.
+
+use anyhow::Result;
+use rustaxum::cache::drivers::{ArrayCache, MemoryCache};
+use rustaxum::cache::{Cache, TaggableCache};
+use std::time::Duration;
+
+const USERS_INDEX: &str = "tag:users:keys";
+
+#[tokio::test]
+async fn test_tagged_entries_live_in_their_own_namespace() -> Result<()> {
+    let cache = MemoryCache::default();
+    cache.put("user:1", &"untagged", None).await?;
+
+    let users = cache.tags(&["users"]);
+    users.put("user:1", &"tagged", None).await?;
+    assert_eq!(users.get::<String>("user:1").await?.as_deref(), Some("tagged"));
+    assert_eq!(cache.get::<String>("user:1").await?.as_deref(), Some("untagged"));
+
+    assert!(users.forget("user:1").await?);
+    assert!(!users.has("user:1").await?);
+    assert_eq!(cache.get::<String>("user:1").await?.as_deref(), Some("untagged"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_flush_tags_evicts_every_entry_under_the_tag() -> Result<()> {
+    let cache = MemoryCache::default();
+    cache.tags(&["users"]).put("index", &1, None).await?;
+    cache.tags(&["users", "posts"]).put("feed", &2, None).await?;
+    cache.tags(&["posts"]).put("index", &3, None).await?;
+
+    cache.flush_tags(&["users"]).await?;
+
+    assert_eq!(cache.tags(&["users"]).get::<i64>("index").await?, None);
+    assert_eq!(cache.tags(&["users", "posts"]).get::<i64>("feed").await?, None);
+    assert_eq!(cache.tags(&["posts"]).get::<i64>("index").await?, Some(3));
+
+    // With the tag's version reset the old keys are readable again, so nothing
+    // found there means the entries themselves were evicted
+    cache.forget("tag:users:version").await?;
+    assert_eq!(cache.tags(&["users"]).get::<i64>("index").await?, None);
+    assert_eq!(cache.tags(&["users", "posts"]).get::<i64>("feed").await?, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_remember_stores_under_the_tags() -> Result<()> {
+    let cache = MemoryCache::default();
+    let users = cache.tags(&["users"]);
+
+    let value = users.remember("count", None, || async { Ok(42_i64) }).await?;
+    assert_eq!(value, 42);
+    assert_eq!(users.remember("count", None, || async { Ok(0_i64) }).await?, 42);
+
+    users.flush().await?;
+    assert!(!users.has("count").await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_puts_keep_every_key_in_the_tag_set() -> Result<()> {
+    let cache = ArrayCache::new();
+
+    let mut handles = Vec::new();
+    for i in 0..25 {
+        let users = cache.tags(&["users"]);
+        handles.push(tokio::spawn(async move { users.put(&format!("user:{}", i), &i, None).await }));
+    }
+    for handle in handles {
+        handle.await??;
+    }
+
+    assert_eq!(cache.set_members(USERS_INDEX).await?.len(), 25);
+    cache.flush_tags(&["users"]).await?;
+    assert_eq!(cache.keys(), vec!["tag:users:version".to_string()]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tag_set_lives_as_long_as_its_longest_entry() -> Result<()> {
+    let cache = ArrayCache::new();
+    let users = cache.tags(&["users"]);
+
+    users.put("profile", &1, Some(Duration::from_secs(3600))).await?;
+    users.put("session", &2, Some(Duration::from_secs(5))).await?;
+    let expires_in = cache.expires_in(USERS_INDEX).expect("the set expires");
+    assert!(expires_in > Duration::from_secs(3590), "a short TTL does not cut the set short");
+
+    users.put("settings", &3, None).await?;
+    assert_eq!(cache.expires_in(USERS_INDEX), None);
+    assert!(cache.has(USERS_INDEX).await?, "an entry kept forever keeps the set forever");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_forget_removes_the_key_from_the_tag_set() -> Result<()> {
+    let cache = ArrayCache::new();
+    let users = cache.tags(&["users"]);
+    users.put("a", &1, None).await?;
+    users.put("b", &2, None).await?;
+
+    assert!(users.forget("a").await?);
+    let members = cache.set_members(USERS_INDEX).await?;
+    assert_eq!(members.len(), 1);
+    assert!(members[0].ends_with(":b"));
+
+    assert!(users.forget("b").await?);
+    assert!(!cache.has(USERS_INDEX).await?, "an empty set is removed");
+    Ok(())
+}
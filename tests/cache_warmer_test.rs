@@ -0,0 +1,115 @@
+//! Cache Warmer Tests
+//!
+//! These tests verify that registered warmers populate their keys, that a
+//! slow or failing warmer does not hold up the others, and that the
+//! countries warmer primes the country index page served by `QueryCache`
+//! until the country tag is flushed.
+
+mod common;
+
+use anyhow::Result;
+use rustaxum::app::cache_warmers::CountriesWarmer;
+use rustaxum::app::models::country::{Country, CreateCountry};
+use rustaxum::app::models::HasModelType;
+use rustaxum::app::query_builder::{CacheStatus, QueryCache, QueryParams};
+use rustaxum::app::services::country_service::CountryService;
+use rustaxum::cache::drivers::MemoryCache;
+use rustaxum::cache::manager::CacheDriver;
+use rustaxum::cache::{Cache, CacheWarmerRegistry, WarmStatus};
+use serial_test::serial;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn memory_cache() -> CacheDriver {
+    CacheDriver::Memory(Arc::new(MemoryCache::new(None)))
+}
+
+#[tokio::test]
+async fn test_registered_warmer_populates_its_key() -> Result<()> {
+    let cache = memory_cache();
+    let mut registry = CacheWarmerRegistry::new(Duration::from_secs(1));
+    registry.register_fn("reference", |cache| async move {
+        cache.put("reference:currencies", &vec!["EUR", "USD"], None).await
+    });
+
+    let results = registry.warm(&cache).await;
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].name, "reference");
+    assert_eq!(results[0].status, WarmStatus::Warmed);
+    assert_eq!(
+        cache.get::<Vec<String>>("reference:currencies").await?,
+        Some(vec!["EUR".to_string(), "USD".to_string()])
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_slow_warmer_times_out_without_blocking_others() -> Result<()> {
+    let cache = memory_cache();
+    let mut registry = CacheWarmerRegistry::new(Duration::from_millis(200));
+    registry
+        .register_fn("slow", |cache| async move {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            cache.put("slow", &1, None).await
+        })
+        .register_fn("failing", |_| async move { Err::<(), _>(anyhow::anyhow!("source unavailable")) })
+        .register_fn("fast", |cache| async move { cache.put("fast", &1, None).await });
+
+    let start = Instant::now();
+    let results = registry.warm(&cache).await;
+
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(registry.names(), vec!["slow", "failing", "fast"]);
+    assert_eq!(results[0].status, WarmStatus::TimedOut);
+    assert_eq!(results[1].status, WarmStatus::Failed("source unavailable".to_string()));
+    assert_eq!(results[2].status, WarmStatus::Warmed);
+    assert!(!cache.has("slow").await?);
+    assert!(cache.has("fast").await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_spawned_warmers_run_in_background() -> Result<()> {
+    let cache = memory_cache();
+    let mut registry = CacheWarmerRegistry::new(Duration::from_secs(1));
+    registry.register_fn("counter", |cache| async move {
+        cache.increment("warm:runs", 1).await.map(|_| ())
+    });
+
+    let handle = registry.spawn(cache.clone(), Some(Duration::from_millis(50)));
+    tokio::time::sleep(Duration::from_millis(180)).await;
+    handle.abort();
+
+    let runs = cache.get::<i64>("warm:runs").await?.unwrap_or(0);
+    assert!(runs >= 2, "expected repeated warming, got {} run(s)", runs);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_countries_warmer_primes_country_index() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let cache = memory_cache();
+    let mut registry = CacheWarmerRegistry::new(Duration::from_secs(10));
+    registry.register(CountriesWarmer::new(pool.clone()));
+    let results = registry.warm(&cache).await;
+    assert!(results[0].is_warmed(), "{:?}", results[0].status);
+
+    let query_cache = QueryCache::with_cache(cache.clone(), Duration::from_secs(60));
+    let (_, status) = query_cache.index::<Country>(QueryParams::default(), &pool).await?;
+    assert_eq!(status, CacheStatus::Hit);
+
+    let suffix = ulid::Ulid::new().to_string();
+    CountryService::create(&pool, CreateCountry {
+        name: format!("Warmed {}", suffix),
+        iso_code: format!("W{}", &suffix[suffix.len() - 6..]),
+        phone_code: None,
+    }, &user.id.to_string()).await?;
+    query_cache.invalidate(Country::model_type()).await?;
+
+    let (_, status) = query_cache.index::<Country>(QueryParams::default(), &pool).await?;
+    assert_eq!(status, CacheStatus::Miss);
+    Ok(())
+}
@@ -0,0 +1,161 @@
+//! Cascading Soft Delete Integration Tests
+//!
+//! These tests verify that deleting an organization domain soft-deletes its
+//! types and organizations in the same operation, and that restoring it brings
+//! back only the children deleted along with it.
+
+mod common;
+
+use anyhow::Result;
+use diesel::prelude::*;
+use rustaxum::app::models::organization::{CreateOrganization, Organization};
+use rustaxum::app::models::organization_domain::{CreateOrganizationDomain, OrganizationDomain};
+use rustaxum::app::models::organization_type::{CreateOrganizationType, OrganizationType};
+use rustaxum::app::models::DieselUlid;
+use rustaxum::app::services::organization_domain_service::OrganizationDomainService;
+use rustaxum::app::services::organization_service::OrganizationService;
+use rustaxum::app::services::organization_type_service::OrganizationTypeService;
+use rustaxum::database::DbPool;
+use rustaxum::schema::{organization_types, organizations};
+use serial_test::serial;
+
+struct Tree {
+    domain: OrganizationDomain,
+    department: OrganizationType,
+    division: OrganizationType,
+    organization: Organization,
+}
+
+async fn create_tree(pool: &DbPool, user_id: &str) -> Result<Tree> {
+    let domain = OrganizationDomainService::create(pool, CreateOrganizationDomain {
+        code: Some(ulid::Ulid::new().to_string()),
+        name: "Cascade Domain".to_string(),
+        description: None,
+    }, user_id).await?;
+
+    let mut types = Vec::new();
+    for name in ["Department", "Division"] {
+        types.push(OrganizationTypeService::create(pool, CreateOrganizationType {
+            domain_id: domain.id,
+            code: Some(ulid::Ulid::new().to_string()),
+            name: name.to_string(),
+            description: None,
+            level: 1,
+        }, user_id).await?);
+    }
+    let division = types.pop().unwrap();
+    let department = types.pop().unwrap();
+
+    let organization = OrganizationService::create(pool, CreateOrganization {
+        domain_id: domain.id,
+        type_id: department.id,
+        name: "Cascade Organization".to_string(),
+        parent_id: None,
+        code: Some(ulid::Ulid::new().to_string()),
+        address: None,
+        authorized_capital: None,
+        business_activities: None,
+        contact_persons: None,
+        description: None,
+        email: None,
+        establishment_date: None,
+        governance_structure: None,
+        legal_status: None,
+        paid_capital: None,
+        path: None,
+        phone: None,
+        registration_number: None,
+        tax_number: None,
+        website: None,
+    }, user_id).await?;
+
+    Ok(Tree { domain, department, division, organization })
+}
+
+fn organization_type(pool: &DbPool, id: DieselUlid) -> Result<OrganizationType> {
+    let mut conn = pool.get()?;
+    Ok(organization_types::table
+        .find(id.to_string())
+        .select(OrganizationType::as_select())
+        .first(&mut conn)?)
+}
+
+fn organization(pool: &DbPool, id: DieselUlid) -> Result<Organization> {
+    let mut conn = pool.get()?;
+    Ok(organizations::table
+        .find(id.to_string())
+        .select(Organization::as_select())
+        .first(&mut conn)?)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_deleting_domain_cascades_to_types_and_organizations() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let user_id = user.id.to_string();
+    let tree = create_tree(&pool, &user_id).await?;
+
+    OrganizationDomainService::delete(&pool, tree.domain.id.to_string(), &user_id).await?;
+
+    assert!(OrganizationDomainService::find_by_id(&pool, tree.domain.id.to_string())?.is_none());
+
+    let department = organization_type(&pool, tree.department.id)?;
+    let organization = organization(&pool, tree.organization.id)?;
+    assert!(department.deleted_at.is_some());
+    assert!(organization.deleted_at.is_some());
+    assert_eq!(department.deleted_by_id, Some(user.id));
+    assert_eq!(organization.deleted_by_id, Some(user.id));
+
+    // Everything removed by one delete shares a batch
+    assert!(department.delete_batch_id.is_some());
+    assert_eq!(department.delete_batch_id, organization.delete_batch_id);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_restore_brings_back_only_children_deleted_with_the_domain() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let user_id = user.id.to_string();
+    let tree = create_tree(&pool, &user_id).await?;
+
+    // Deleted on its own before the domain, so a cascading restore skips it
+    OrganizationTypeService::delete(&pool, tree.division.id.to_string(), &user_id).await?;
+    OrganizationDomainService::delete(&pool, tree.domain.id.to_string(), &user_id).await?;
+
+    OrganizationDomainService::restore(&pool, tree.domain.id.to_string(), &user_id, true)?;
+
+    assert!(OrganizationDomainService::find_by_id(&pool, tree.domain.id.to_string())?.is_some());
+    let department = organization_type(&pool, tree.department.id)?;
+    assert!(department.deleted_at.is_none());
+    assert!(department.deleted_by_id.is_none());
+    assert!(department.delete_batch_id.is_none());
+    assert!(organization(&pool, tree.organization.id)?.deleted_at.is_none());
+    assert!(organization_type(&pool, tree.division.id)?.deleted_at.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_restore_without_cascade_leaves_children_deleted() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let user_id = user.id.to_string();
+    let tree = create_tree(&pool, &user_id).await?;
+
+    OrganizationDomainService::delete(&pool, tree.domain.id.to_string(), &user_id).await?;
+    OrganizationDomainService::restore(&pool, tree.domain.id.to_string(), &user_id, false)?;
+
+    assert!(OrganizationDomainService::find_by_id(&pool, tree.domain.id.to_string())?.is_some());
+    assert!(organization_type(&pool, tree.department.id)?.deleted_at.is_some());
+    assert!(organization(&pool, tree.organization.id)?.deleted_at.is_some());
+
+    // Restoring a domain that is not deleted is an error
+    assert!(OrganizationDomainService::restore(&pool, tree.domain.id.to_string(), &user_id, true).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+//! Shared fixtures for messaging integration tests
+
+#![allow(dead_code)]
+
+use anyhow::Result;
+use diesel::prelude::*;
+use rustaxum::app::models::conversation::{Conversation, ConversationType};
+use rustaxum::app::models::conversation_participant::{ConversationParticipant, ParticipantRole};
+use rustaxum::app::models::device::{Device, DeviceType};
+use rustaxum::app::models::message::Message;
+use rustaxum::app::models::scheduled_messages::ScheduledMessage;
+use rustaxum::app::models::user::User;
+use rustaxum::config::Config;
+use rustaxum::database::{create_pool, run_migrations, DbPool};
+use rustaxum::schema::{conversation_participants, conversations, devices, messages, scheduled_messages, sys_users};
+
+pub async fn setup_test_db() -> Result<DbPool> {
+    let config = Config::load()?;
+    let pool = create_pool(&config)?;
+    run_migrations(&pool)?;
+    // Code without a pool in reach, like token revocation checks, reads the global one
+    rustaxum::database::connection::initialize_pool(pool.clone());
+    Ok(pool)
+}
+
+pub fn create_user(pool: &DbPool) -> Result<User> {
+    let id = ulid::Ulid::new().to_string();
+    let mut user = User::new(
+        "Test User".to_string(),
+        format!("{}@example.com", id.to_lowercase()),
+        "hashed_password".to_string(),
+        &id,
+    );
+    // Test users are their own creator so the audit foreign keys resolve
+    user.created_by_id = user.id;
+    user.updated_by_id = user.id;
+
+    let mut conn = pool.get()?;
+    let user = diesel::insert_into(sys_users::table)
+        .values(&user)
+        .get_result::<User>(&mut conn)?;
+    Ok(user)
+}
+
+pub fn create_device(pool: &DbPool, user: &User) -> Result<Device> {
+    let device = Device::new(
+        user.id,
+        "Test Device".to_string(),
+        DeviceType::Desktop,
+        "identity_public_key".to_string(),
+        "signed_prekey_public".to_string(),
+        "signed_prekey_signature".to_string(),
+        1,
+        1,
+    );
+
+    let mut conn = pool.get()?;
+    let device = diesel::insert_into(devices::table)
+        .values(&device)
+        .get_result::<Device>(&mut conn)?;
+    Ok(device)
+}
+
+pub fn create_conversation(pool: &DbPool, creator: &User) -> Result<Conversation> {
+    let conversation = Conversation::new(ConversationType::Group, Some(creator.id));
+
+    let mut conn = pool.get()?;
+    let conversation = diesel::insert_into(conversations::table)
+        .values(&conversation)
+        .get_result::<Conversation>(&mut conn)?;
+    Ok(conversation)
+}
+
+pub fn add_participant(pool: &DbPool, conversation: &Conversation, user: &User) -> Result<ConversationParticipant> {
+    add_participant_with_role(pool, conversation, user, ParticipantRole::Member)
+}
+
+pub fn add_participant_with_role(
+    pool: &DbPool,
+    conversation: &Conversation,
+    user: &User,
+    role: ParticipantRole,
+) -> Result<ConversationParticipant> {
+    let participant = ConversationParticipant::new(conversation.id, user.id, role);
+
+    let mut conn = pool.get()?;
+    let participant = diesel::insert_into(conversation_participants::table)
+        .values(&participant)
+        .get_result::<ConversationParticipant>(&mut conn)?;
+    Ok(participant)
+}
+
+pub fn create_message(pool: &DbPool, conversation: &Conversation, sender: &User, device: &Device) -> Result<Message> {
+    let message = Message::new(
+        conversation.id,
+        sender.id,
+        device.id,
+        "encrypted_content".to_string(),
+        "aes-256-gcm".to_string(),
+    );
+
+    let mut conn = pool.get()?;
+    let message = diesel::insert_into(messages::table)
+        .values(&message)
+        .get_result::<Message>(&mut conn)?;
+    Ok(message)
+}
+
+pub fn create_scheduled_message(
+    pool: &DbPool,
+    message: &Message,
+    device: &Device,
+    scheduled_for: chrono::DateTime<chrono::Utc>,
+) -> Result<ScheduledMessage> {
+    let scheduled = ScheduledMessage::new(
+        message.id,
+        message.conversation_id,
+        message.sender_user_id,
+        device.id,
+        scheduled_for,
+        "UTC".to_string(),
+        None,
+    );
+
+    let mut conn = pool.get()?;
+    let scheduled = diesel::insert_into(scheduled_messages::table)
+        .values(&scheduled)
+        .get_result::<ScheduledMessage>(&mut conn)?;
+    Ok(scheduled)
+}
@@ -0,0 +1,105 @@
+//! CSV Import Integration Tests
+//!
+//! These tests verify that `import:csv` validates each row with the model's
+//! request rules, inserts the valid rows and reports the invalid ones by line.
+
+mod common;
+
+use anyhow::Result;
+use diesel::prelude::*;
+use rustaxum::app::models::country::Country;
+use rustaxum::app::services::csv_import_service::{CsvImportOptions, CsvImportService};
+use rustaxum::database::DbPool;
+use rustaxum::schema::ref_geo_countries;
+use serial_test::serial;
+use std::path::PathBuf;
+
+const ISO_CODES: [&str; 3] = ["QXA", "QXB", "QXC"];
+
+/// Remove countries left behind by earlier runs, as ISO codes are unique
+fn clear_countries(pool: &DbPool) -> Result<()> {
+    let mut conn = pool.get()?;
+    diesel::delete(ref_geo_countries::table.filter(ref_geo_countries::iso_code.eq_any(ISO_CODES)))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+fn imported(pool: &DbPool) -> Result<Vec<Country>> {
+    let mut conn = pool.get()?;
+    let countries = ref_geo_countries::table
+        .filter(ref_geo_countries::iso_code.eq_any(ISO_CODES))
+        .order(ref_geo_countries::iso_code.asc())
+        .select(Country::as_select())
+        .load(&mut conn)?;
+    Ok(countries)
+}
+
+/// Write a CSV with two valid rows around one whose ISO code is invalid
+fn write_csv() -> Result<PathBuf> {
+    let path = std::env::temp_dir().join(format!("countries-{}.csv", ulid::Ulid::new()));
+    std::fs::write(
+        &path,
+        "Country Name,iso_code,phone_code\n\
+         Qx Alpha,qxa,+901\n\
+         Qx Invalid,Q1,abc\n\
+         Qx Charlie,QXC,\n",
+    )?;
+    Ok(path)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_import_inserts_valid_rows_and_reports_invalid_ones() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    clear_countries(&pool)?;
+    let user = common::create_user(&pool)?;
+    let path = write_csv()?;
+
+    let options = CsvImportOptions::new(user.id.to_string())
+        .with_batch_size(1)
+        .with_column("Country Name", "name");
+    let report = CsvImportService::import_file::<Country>(&pool, &path, &options).await?;
+    std::fs::remove_file(&path)?;
+
+    assert_eq!(report.processed, 3);
+    assert_eq!(report.inserted, 2);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].line, 3);
+    assert!(report.failed[0].errors.contains_key("iso_code"));
+    assert!(report.failed[0].errors.contains_key("phone_code"));
+
+    // Rows are prepared like requests: the ISO code is uppercased and a blank
+    // phone code is stored as NULL
+    let countries = imported(&pool)?;
+    assert_eq!(countries.len(), 2);
+    assert_eq!(countries[0].name, "Qx Alpha");
+    assert_eq!(countries[0].iso_code, "QXA");
+    assert_eq!(countries[0].phone_code.as_deref(), Some("+901"));
+    assert_eq!(countries[1].iso_code, "QXC");
+    assert_eq!(countries[1].phone_code, None);
+    assert_eq!(countries[1].created_by_id, user.id);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_dry_run_validates_without_inserting() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    clear_countries(&pool)?;
+    let user = common::create_user(&pool)?;
+    let path = write_csv()?;
+
+    let options = CsvImportOptions::new(user.id.to_string())
+        .with_dry_run(true)
+        .with_column("Country Name", "name");
+    let report = CsvImportService::import_file::<Country>(&pool, &path, &options).await?;
+    std::fs::remove_file(&path)?;
+
+    assert_eq!(report.valid, 2);
+    assert_eq!(report.inserted, 0);
+    assert_eq!(report.failed.len(), 1);
+    assert!(imported(&pool)?.is_empty());
+
+    Ok(())
+}
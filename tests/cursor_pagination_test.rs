@@ -0,0 +1,91 @@
+//! Cursor Pagination Integration Tests
+//!
+//! These tests verify that cursor pages continue after the sort values of
+//! the previous page's last row, so deleting that row, or rows before it,
+//! between requests neither skips nor repeats records, and that pagination
+//! still reaches the last page.
+
+mod common;
+
+use anyhow::Result;
+use diesel::prelude::*;
+use rustaxum::app::models::conversation::Conversation;
+use rustaxum::app::models::message::Message;
+use rustaxum::app::query_builder::{PaginationResult, QueryBuilderExt, QueryExecutor, QueryParams};
+use rustaxum::database::DbPool;
+use rustaxum::schema::messages;
+use serial_test::serial;
+
+fn page(pool: &DbPool, conversation: &Conversation, cursor: Option<String>) -> Result<PaginationResult<serde_json::Value>> {
+    let mut params = QueryParams::from_query_string("pagination_type=cursor&per_page=3&sort=created_at")?;
+    params.filter.insert("conversation_id".to_string(), serde_json::json!(conversation.id.to_string()));
+    params.cursor = cursor;
+
+    let mut conn = pool.get()?;
+    QueryExecutor::execute_paginated(Message::from_params(params)?, &mut conn)
+}
+
+fn ids(result: &PaginationResult<serde_json::Value>) -> Vec<String> {
+    result.data.iter().map(|row| row["id"].as_str().unwrap_or_default().to_string()).collect()
+}
+
+fn delete_message(pool: &DbPool, id: &str) -> Result<()> {
+    let mut conn = pool.get()?;
+    diesel::delete(messages::table.filter(messages::id.eq(id))).execute(&mut conn)?;
+    Ok(())
+}
+
+/// Seven messages in the order cursor pages return them
+fn seed_messages(pool: &DbPool) -> Result<(Conversation, Vec<String>)> {
+    let user = common::create_user(pool)?;
+    let device = common::create_device(pool, &user)?;
+    let conversation = common::create_conversation(pool, &user)?;
+
+    let mut messages = Vec::new();
+    for _ in 0..7 {
+        messages.push(common::create_message(pool, &conversation, &user, &device)?);
+    }
+    messages.sort_by_key(|message| (message.created_at, message.id.to_string()));
+
+    Ok((conversation, messages.iter().map(|message| message.id.to_string()).collect()))
+}
+
+#[tokio::test]
+#[serial]
+async fn test_deleted_cursor_anchor_does_not_skip_rows() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let (conversation, expected) = seed_messages(&pool)?;
+
+    let first = page(&pool, &conversation, None)?;
+    assert_eq!(ids(&first), expected[0..3]);
+    assert!(first.pagination.has_more_pages);
+
+    // The last row of the first page is the cursor's anchor
+    delete_message(&pool, &expected[2])?;
+
+    let second = page(&pool, &conversation, first.pagination.next_cursor.clone())?;
+    assert_eq!(ids(&second), expected[3..6], "the page starts right after the deleted anchor");
+    assert!(second.pagination.has_more_pages);
+
+    let third = page(&pool, &conversation, second.pagination.next_cursor.clone())?;
+    assert_eq!(ids(&third), expected[6..7]);
+    assert!(!third.pagination.has_more_pages);
+    assert!(third.pagination.next_cursor.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_rows_deleted_before_cursor_do_not_shift_next_page() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let (conversation, expected) = seed_messages(&pool)?;
+
+    let first = page(&pool, &conversation, None)?;
+    for id in &expected[0..3] {
+        delete_message(&pool, id)?;
+    }
+
+    let second = page(&pool, &conversation, first.pagination.next_cursor.clone())?;
+    assert_eq!(ids(&second), expected[3..6]);
+    Ok(())
+}
@@ -0,0 +1,67 @@
+//! Database Connect Retry Tests
+//!
+//! These tests verify that startup keeps checking an unreachable database
+//! with bounded exponential backoff and proceeds once it becomes reachable.
+
+use anyhow::{anyhow, Result};
+use rustaxum::database::ConnectRetry;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+fn retry(retries: u32) -> ConnectRetry {
+    ConnectRetry {
+        retries,
+        backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(4),
+    }
+}
+
+#[tokio::test]
+async fn test_retries_until_database_is_reachable() -> Result<()> {
+    let attempts = &AtomicU32::new(0);
+
+    // Unreachable for the first two checks, then up
+    let result = retry(5)
+        .run(|| async move {
+            match attempts.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(anyhow!("connection refused")),
+                _ => Ok("connected"),
+            }
+        })
+        .await?;
+
+    assert_eq!(result, "connected");
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_gives_up_after_retries() {
+    let attempts = &AtomicU32::new(0);
+
+    let error = retry(2)
+        .run(|| async move {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(anyhow!("connection refused"))
+        })
+        .await
+        .unwrap_err();
+
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    assert!(format!("{:#}", error).contains("connection refused"));
+}
+
+#[test]
+fn test_backoff_doubles_up_to_the_max() {
+    let retry = ConnectRetry {
+        retries: 10,
+        backoff: Duration::from_millis(500),
+        max_backoff: Duration::from_secs(3),
+    };
+
+    assert_eq!(retry.delay_for(1), Duration::from_millis(500));
+    assert_eq!(retry.delay_for(2), Duration::from_millis(1000));
+    assert_eq!(retry.delay_for(3), Duration::from_millis(2000));
+    assert_eq!(retry.delay_for(4), Duration::from_secs(3));
+    assert_eq!(retry.delay_for(40), Duration::from_secs(3));
+}
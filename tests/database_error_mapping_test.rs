@@ -0,0 +1,198 @@
+//! Database Error Mapping Tests
+//!
+//! These tests verify that constraint violations are answered with the
+//! validation error envelope instead of a server error: unique violations
+//! with 409 and the offending field, foreign-key and not-null violations
+//! with 422. `ServiceErrorResponse`, which controllers answer service errors
+//! with, applies the same mapping and otherwise keeps the service's status.
+
+mod common;
+
+use anyhow::Result;
+use axum::{
+    body::Body,
+    response::IntoResponse,
+    http::{header, Request, StatusCode},
+    routing::post,
+    Router,
+};
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorInformation, DatabaseErrorKind, Error as DieselError};
+use rustaxum::app::http::controllers::country_controller;
+use rustaxum::app::http::responses::{ConstraintViolation, DatabaseErrorResponse, ServiceErrorResponse};
+use rustaxum::app::models::user::User;
+use rustaxum::database::DbPool;
+use rustaxum::schema::{ref_geo_countries, sys_users};
+use serde_json::{json, Value};
+use serial_test::serial;
+use tower::ServiceExt;
+
+/// Error information as Postgres reports it
+struct PgErrorInfo {
+    message: &'static str,
+    details: Option<&'static str>,
+    table: Option<&'static str>,
+    column: Option<&'static str>,
+    constraint: Option<&'static str>,
+}
+
+impl DatabaseErrorInformation for PgErrorInfo {
+    fn message(&self) -> &str {
+        self.message
+    }
+
+    fn details(&self) -> Option<&str> {
+        self.details
+    }
+
+    fn hint(&self) -> Option<&str> {
+        None
+    }
+
+    fn table_name(&self) -> Option<&str> {
+        self.table
+    }
+
+    fn column_name(&self) -> Option<&str> {
+        self.column
+    }
+
+    fn constraint_name(&self) -> Option<&str> {
+        self.constraint
+    }
+
+    fn statement_position(&self) -> Option<i32> {
+        None
+    }
+}
+
+fn database_error(kind: DatabaseErrorKind, info: PgErrorInfo) -> anyhow::Error {
+    DieselError::DatabaseError(kind, Box::new(info)).into()
+}
+
+/// The countries controller records changes as the seeded system user
+fn ensure_system_user(pool: &DbPool) -> Result<()> {
+    let mut conn = pool.get()?;
+    let exists = sys_users::table
+        .filter(sys_users::email.eq("system@seeder.internal"))
+        .select(sys_users::id)
+        .first::<String>(&mut conn)
+        .optional()?
+        .is_some();
+
+    if !exists {
+        let id = ulid::Ulid::new().to_string();
+        let mut user = User::new("System".to_string(), "system@seeder.internal".to_string(), "password".to_string(), &id);
+        user.created_by_id = user.id;
+        user.updated_by_id = user.id;
+        diesel::insert_into(sys_users::table).values(&user).execute(&mut conn)?;
+    }
+    Ok(())
+}
+
+async fn create_country(pool: &DbPool, iso_code: &str) -> Result<(StatusCode, Value)> {
+    let app = Router::new()
+        .route("/api/countries", post(country_controller::store))
+        .with_state(pool.clone());
+    let body = json!({ "name": "Constraint Test", "iso_code": iso_code, "phone_code": "+999" });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/countries")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))?;
+
+    let response = app.oneshot(request).await?;
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok((status, serde_json::from_slice(&bytes)?))
+}
+
+#[tokio::test]
+#[serial]
+async fn test_duplicate_insert_answers_409_with_field() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    ensure_system_user(&pool)?;
+    diesel::delete(ref_geo_countries::table.filter(ref_geo_countries::iso_code.eq("QZX")))
+        .execute(&mut pool.get()?)?;
+
+    let (status, _) = create_country(&pool, "QZX").await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, body) = create_country(&pool, "QZX").await?;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["message"], "The iso code has already been taken.");
+    assert_eq!(body["errors"]["iso_code"]["unique"], "The iso code has already been taken.");
+    assert!(!body.to_string().contains("QZX"), "the conflicting value is not echoed back");
+
+    diesel::delete(ref_geo_countries::table.filter(ref_geo_countries::iso_code.eq("QZX")))
+        .execute(&mut pool.get()?)?;
+    Ok(())
+}
+
+#[test]
+fn test_foreign_key_violation_is_unprocessable() {
+    let error = database_error(DatabaseErrorKind::ForeignKeyViolation, PgErrorInfo {
+        message: "insert or update on table \"ref_geo_provinces\" violates foreign key constraint \"ref_geo_provinces_country_id_fkey\"",
+        details: Some("Key (country_id)=(01ARZ3NDEKTSV4RRFFQ69G5FAV) is not present in table \"ref_geo_countries\"."),
+        table: Some("ref_geo_provinces"),
+        column: None,
+        constraint: Some("ref_geo_provinces_country_id_fkey"),
+    });
+
+    let mapped = DatabaseErrorResponse::from_error(&error).expect("foreign key violations are mapped");
+    assert_eq!(mapped.violation, ConstraintViolation::ForeignKey);
+    assert_eq!(mapped.violation.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(mapped.fields, vec!["country_id".to_string()]);
+    assert_eq!(mapped.message(), "The selected country id is invalid.");
+}
+
+#[test]
+fn test_not_null_violation_names_the_column() {
+    let error = database_error(DatabaseErrorKind::NotNullViolation, PgErrorInfo {
+        message: "null value in column \"name\" of relation \"ref_geo_countries\" violates not-null constraint",
+        details: None,
+        table: Some("ref_geo_countries"),
+        column: None,
+        constraint: None,
+    });
+
+    let mapped = DatabaseErrorResponse::from_error(&error).expect("not-null violations are mapped");
+    assert_eq!(mapped.violation.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(mapped.body().errors["name"]["required"], "The name field is required.");
+}
+
+#[test]
+fn test_unique_field_falls_back_to_constraint_name() {
+    let error = database_error(DatabaseErrorKind::UniqueViolation, PgErrorInfo {
+        message: "duplicate key value violates unique constraint \"sys_users_email_key\"",
+        details: None,
+        table: Some("sys_users"),
+        column: None,
+        constraint: Some("sys_users_email_key"),
+    });
+
+    let mapped = DatabaseErrorResponse::from_error(&error).expect("unique violations are mapped");
+    assert_eq!(mapped.fields, vec!["email".to_string()]);
+    assert!(DatabaseErrorResponse::from_error(&anyhow::anyhow!("Country not found")).is_none());
+}
+
+#[tokio::test]
+async fn test_service_errors_map_constraint_violations() -> Result<()> {
+    let error = database_error(DatabaseErrorKind::UniqueViolation, PgErrorInfo {
+        message: "duplicate key value violates unique constraint \"sys_users_email_key\"",
+        details: Some("Key (email)=(taken@example.com) already exists."),
+        table: Some("sys_users"),
+        column: None,
+        constraint: Some("sys_users_email_key"),
+    });
+    let response = ServiceErrorResponse::new(StatusCode::BAD_REQUEST, error.context("Failed to create user")).into_response();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body: Value = serde_json::from_slice(&axum::body::to_bytes(response.into_body(), usize::MAX).await?)?;
+    assert_eq!(body["errors"]["email"]["unique"], "The email has already been taken.");
+
+    let response = ServiceErrorResponse::new(StatusCode::NOT_FOUND, anyhow::anyhow!("Poll not found")).into_response();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body: Value = serde_json::from_slice(&axum::body::to_bytes(response.into_body(), usize::MAX).await?)?;
+    assert_eq!(body, json!({ "error": "Poll not found" }));
+    Ok(())
+}
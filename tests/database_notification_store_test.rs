@@ -0,0 +1,103 @@
+//! Database Notification Store Tests
+//!
+//! These tests verify that `DatabaseNotificationStore` lists and counts only
+//! a notifiable's unread notifications, that marking one or all of them as
+//! read sets `read_at` without deleting the rows, and that a read
+//! notification can be marked unread again.
+
+mod common;
+
+use anyhow::Result;
+use diesel::prelude::*;
+use rustaxum::app::models::notification::Notification as NotificationModel;
+use rustaxum::app::notifications::DatabaseNotificationStore;
+use rustaxum::database::DbPool;
+use rustaxum::schema::notifications;
+use serial_test::serial;
+
+fn store_notification(pool: &DbPool, notifiable_id: &str, message: &str) -> Result<String> {
+    let notification = NotificationModel::new(
+        "InvoicePaid".to_string(),
+        notifiable_id.to_string(),
+        "App\\Models\\User".to_string(),
+        serde_json::json!({ "message": message }),
+    );
+
+    let mut conn = pool.get()?;
+    diesel::insert_into(notifications::table)
+        .values(&notification)
+        .execute(&mut conn)?;
+    Ok(notification.id.to_string())
+}
+
+fn remaining_rows(pool: &DbPool, notifiable_id: &str) -> Result<i64> {
+    let mut conn = pool.get()?;
+    Ok(notifications::table
+        .filter(notifications::notifiable_id.eq(notifiable_id))
+        .count()
+        .get_result(&mut conn)?)
+}
+
+fn clear(pool: &DbPool, notifiable_id: &str) -> Result<()> {
+    let mut conn = pool.get()?;
+    diesel::delete(notifications::table.filter(notifications::notifiable_id.eq(notifiable_id))).execute(&mut conn)?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_mark_as_read_and_unread() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let store = DatabaseNotificationStore::new(pool.clone());
+    let notifiable_id = format!("User_{}", ulid::Ulid::new());
+
+    let first = store_notification(&pool, &notifiable_id, "Invoice 1 paid")?;
+    let second = store_notification(&pool, &notifiable_id, "Invoice 2 paid")?;
+    assert_eq!(store.unread_count(&notifiable_id)?, 2);
+
+    assert!(store.mark_as_read(&first)?);
+    assert!(!store.mark_as_read(&first)?, "already read");
+
+    let unread = store.unread(&notifiable_id)?;
+    assert_eq!(unread.len(), 1);
+    assert_eq!(unread[0].id, second);
+    assert_eq!(unread[0].data["message"], "Invoice 2 paid");
+    assert_eq!(store.unread_count(&notifiable_id)?, 1);
+
+    let read = store.find(&first)?.expect("read notifications are kept");
+    assert!(read.read_at.is_some());
+    assert_eq!(remaining_rows(&pool, &notifiable_id)?, 2);
+
+    assert!(store.mark_as_unread(&first)?);
+    assert!(store.find(&first)?.expect("still stored").read_at.is_none());
+    assert_eq!(store.unread_count(&notifiable_id)?, 2);
+
+    clear(&pool, &notifiable_id)?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_mark_all_as_read_only_touches_the_notifiable() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let store = DatabaseNotificationStore::new(pool.clone());
+    let ada = format!("User_{}", ulid::Ulid::new());
+    let grace = format!("User_{}", ulid::Ulid::new());
+
+    for message in ["Invoice 1 paid", "Invoice 2 paid", "Invoice 3 paid"] {
+        store_notification(&pool, &ada, message)?;
+    }
+    store_notification(&pool, &grace, "Invoice 4 paid")?;
+
+    assert_eq!(store.mark_all_as_read(&ada)?, 3);
+    assert_eq!(store.mark_all_as_read(&ada)?, 0);
+
+    assert_eq!(store.unread_count(&ada)?, 0);
+    assert!(store.unread(&ada)?.is_empty());
+    assert_eq!(remaining_rows(&pool, &ada)?, 3);
+    assert_eq!(store.unread_count(&grace)?, 1);
+
+    clear(&pool, &ada)?;
+    clear(&pool, &grace)?;
+    Ok(())
+}
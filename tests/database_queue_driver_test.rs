@@ -0,0 +1,192 @@
+//! Database Queue Driver Tests
+//!
+//! These tests verify that `DatabaseQueueDriver` keeps jobs in the `jobs`
+//! table, hands each job to exactly one of several concurrent workers, pops by
+//! priority while holding back jobs whose `scheduled_at` has not passed,
+//! reclaims jobs a crashed worker left processing, and can release, fail and
+//! retry jobs. Each test uses its own queue name and removes its rows
+//! afterwards.
+
+mod common;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use rustaxum::app::jobs::database_queue_driver::DatabaseQueueDriver;
+use rustaxum::app::jobs::{JobMetadata, JobStatus, QueueDriver};
+use rustaxum::database::DbPool;
+use rustaxum::schema::jobs;
+use serial_test::serial;
+use std::collections::HashSet;
+
+fn unique_queue() -> String {
+    format!("test-{}", ulid::Ulid::new())
+}
+
+fn job_on(queue: &str, priority: i32) -> JobMetadata {
+    JobMetadata::new("SyncInventory".to_string(), queue.to_string(), "{\"sku\":\"A-1\"}".to_string(), priority, 3)
+}
+
+fn clear_queue(pool: &DbPool, queue: &str) -> Result<()> {
+    let mut conn = pool.get()?;
+    diesel::delete(jobs::table.filter(jobs::queue_name.eq(queue))).execute(&mut conn)?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[serial]
+async fn test_concurrent_workers_never_pop_the_same_job() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let queue = unique_queue();
+    let driver = DatabaseQueueDriver::new(pool.clone());
+
+    let mut pushed = HashSet::new();
+    for _ in 0..20 {
+        let job = job_on(&queue, 0);
+        pushed.insert(job.id.clone());
+        driver.push(job).await?;
+    }
+
+    let workers = (0..4).map(|_| {
+        let driver = driver.clone();
+        let queue = queue.clone();
+        tokio::spawn(async move {
+            let mut popped = Vec::new();
+            while let Some(job) = driver.pop(&queue).await? {
+                popped.push(job.id);
+            }
+            anyhow::Ok(popped)
+        })
+    });
+
+    let mut popped = Vec::new();
+    for worker in futures::future::join_all(workers).await {
+        popped.extend(worker??);
+    }
+
+    let unique: HashSet<String> = popped.iter().cloned().collect();
+    assert_eq!(popped.len(), 20, "every job is popped exactly once");
+    assert_eq!(unique, pushed);
+
+    clear_queue(&pool, &queue)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_pop_orders_by_priority_and_skips_scheduled_jobs() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let queue = unique_queue();
+    let driver = DatabaseQueueDriver::new(pool.clone());
+
+    let routine = job_on(&queue, 5);
+    let urgent = job_on(&queue, -5);
+    let mut later = job_on(&queue, -10);
+    later.scheduled_at = Some(Utc::now() + Duration::hours(1));
+    driver.push(routine.clone()).await?;
+    driver.push(urgent.clone()).await?;
+    driver.push(later.clone()).await?;
+
+    let first = driver.pop(&queue).await?.expect("urgent job is available");
+    assert_eq!(first.id, urgent.id);
+    assert_eq!(first.status, JobStatus::Processing);
+    assert_eq!(first.payload, "{\"sku\":\"A-1\"}");
+    assert_eq!(driver.pop(&queue).await?.map(|job| job.id), Some(routine.id));
+    assert!(driver.pop(&queue).await?.is_none(), "the scheduled job is held back");
+    assert_eq!(driver.size(&queue).await?, 1);
+
+    clear_queue(&pool, &queue)
+}
+
+/// Backdate a job's reservation as if its worker died `seconds` ago
+fn reserve_ago(pool: &DbPool, job_id: &str, seconds: i64) -> Result<()> {
+    let mut conn = pool.get()?;
+    diesel::update(jobs::table.filter(jobs::id.eq(job_id)))
+        .set(jobs::reserved_at.eq(Some(Utc::now() - Duration::seconds(seconds))))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_abandoned_reservation_is_reclaimed_with_an_attempt() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let queue = unique_queue();
+    let driver = DatabaseQueueDriver::new(pool.clone()).retry_after(std::time::Duration::from_secs(60));
+    driver.push(job_on(&queue, 0)).await?;
+
+    let job = driver.pop(&queue).await?.expect("job is available");
+    assert_eq!(job.attempts, 0);
+
+    // A reservation younger than retry_after belongs to a live worker
+    reserve_ago(&pool, &job.id, 30)?;
+    assert!(driver.pop(&queue).await?.is_none());
+
+    // Past retry_after the worker is presumed dead and the job is handed out again
+    reserve_ago(&pool, &job.id, 61)?;
+    let reclaimed = driver.pop(&queue).await?.expect("abandoned job is reclaimed");
+    assert_eq!(reclaimed.id, job.id);
+    assert_eq!(reclaimed.status, JobStatus::Processing);
+    assert_eq!(reclaimed.attempts, 1);
+    assert!(reclaimed.reserved_at.unwrap() > Utc::now() - Duration::seconds(10));
+
+    // Abandoned on its final attempt, it fails instead of running a fourth time
+    reserve_ago(&pool, &job.id, 61)?;
+    assert_eq!(driver.pop(&queue).await?.map(|job| job.attempts), Some(2));
+    reserve_ago(&pool, &job.id, 61)?;
+    assert!(driver.pop(&queue).await?.is_none());
+
+    let failed = driver.failed_jobs(None).await?;
+    let failed = failed.iter().find(|failed| failed.id == job.id).expect("exhausted job is failed");
+    assert_eq!(failed.attempts, 3);
+
+    clear_queue(&pool, &queue)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_released_job_is_popped_again_with_its_attempts() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let queue = unique_queue();
+    let driver = DatabaseQueueDriver::new(pool.clone());
+    driver.push(job_on(&queue, 0)).await?;
+
+    let mut job = driver.pop(&queue).await?.expect("job is available");
+    job.mark_retrying();
+    job.scheduled_at = Some(Utc::now() - Duration::seconds(1));
+    job.error_message = Some("Inventory service unavailable".to_string());
+    driver.release(job.clone()).await?;
+
+    let retry = driver.pop(&queue).await?.expect("released job is available again");
+    assert_eq!(retry.id, job.id);
+    assert_eq!(retry.attempts, 1);
+    assert_eq!(retry.error_message.as_deref(), Some("Inventory service unavailable"));
+
+    clear_queue(&pool, &queue)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_failed_job_is_listed_and_can_be_retried() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let queue = unique_queue();
+    let driver = DatabaseQueueDriver::new(pool.clone());
+    driver.push(job_on(&queue, 0)).await?;
+
+    let mut job = driver.pop(&queue).await?.expect("job is available");
+    job.mark_failed("Inventory service unavailable");
+    driver.update(&job).await?;
+
+    let failed = driver.failed_jobs(None).await?;
+    assert!(failed.iter().any(|failed| failed.id == job.id));
+    assert!(driver.pop(&queue).await?.is_none());
+
+    driver.retry_job(&job.id).await?;
+    let retried = driver.pop(&queue).await?.expect("retried job is available");
+    assert_eq!(retried.id, job.id);
+    assert_eq!(retried.attempts, 0);
+    assert!(retried.error_message.is_none());
+
+    driver.delete(&job.id).await?;
+    assert_eq!(driver.size(&queue).await?, 0);
+    clear_queue(&pool, &queue)
+}
@@ -0,0 +1,97 @@
+//! Delayed Dispatch Tests
+//!
+//! These tests verify that `JobDispatcher::dispatch_after` and `dispatch_at`
+//! set `scheduled_at` on the pushed job, that a worker popping the queue skips
+//! the job until that time has passed, and that the `dispatch_after!` macro
+//! goes through the global dispatcher.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use rustaxum::app::jobs::{FakeQueueDriver, Job, JobDispatcher, MemoryQueueDriver, QueueDriver, QueueFacade};
+use serde::{Deserialize, Serialize};
+use serial_test::serial;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SendReminderJob;
+
+#[async_trait]
+impl Job for SendReminderJob {
+    fn job_name(&self) -> &'static str {
+        "SendReminderJob"
+    }
+
+    fn queue_name(&self) -> &str {
+        "reminders"
+    }
+
+    async fn handle(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+#[tokio::test]
+async fn test_dispatch_after_holds_the_job_until_its_delay_passes() -> Result<()> {
+    let driver = FakeQueueDriver::recording(MemoryQueueDriver::new());
+    let dispatcher = JobDispatcher::new(Box::new(driver.clone()));
+
+    let dispatched_at = Utc::now();
+    let job_id = dispatcher.dispatch_after(&SendReminderJob, Duration::hours(1)).await?;
+
+    let job = driver.job(&job_id).await.expect("the job was pushed");
+    let available_at = job.scheduled_at.expect("delayed job has an available time");
+    assert!(available_at >= dispatched_at + Duration::hours(1));
+    assert!(available_at <= Utc::now() + Duration::hours(1));
+
+    assert_eq!(driver.size("reminders").await?, 1);
+    assert!(driver.pop("reminders").await?.is_none(), "workers skip the job until it is due");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dispatch_at_only_hands_out_due_jobs() -> Result<()> {
+    let driver = FakeQueueDriver::recording(MemoryQueueDriver::new());
+    let dispatcher = JobDispatcher::new(Box::new(driver.clone()));
+
+    let tomorrow = Utc::now() + Duration::days(1);
+    let future_id = dispatcher.dispatch_at(&SendReminderJob, tomorrow).await?;
+    let due_id = dispatcher.dispatch_at(&SendReminderJob, Utc::now() - Duration::seconds(1)).await?;
+
+    assert_eq!(driver.job(&future_id).await.and_then(|job| job.scheduled_at), Some(tomorrow));
+    assert_eq!(driver.pop("reminders").await?.map(|job| job.id), Some(due_id));
+    assert!(driver.pop("reminders").await?.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dispatch_without_delay_is_available_immediately() -> Result<()> {
+    let driver = MemoryQueueDriver::new();
+    let dispatcher = JobDispatcher::new(Box::new(driver.clone()));
+
+    let job_id = dispatcher.dispatch(&SendReminderJob).await?;
+
+    assert_eq!(driver.pop("reminders").await?.map(|job| job.id), Some(job_id));
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_dispatch_after_macro_uses_the_global_dispatcher() -> Result<()> {
+    QueueFacade::fake().await;
+
+    let dispatched_at = Utc::now();
+    rustaxum::dispatch_after!(SendReminderJob, Duration::minutes(5))?;
+
+    let pushed = QueueFacade::get_pushed_jobs().await;
+    QueueFacade::restore().await;
+
+    assert_eq!(pushed.len(), 1);
+    assert_eq!(pushed[0].job_name, "SendReminderJob");
+    let available_at = pushed[0].scheduled_at.expect("delayed job has an available time");
+    assert!(available_at >= dispatched_at + Duration::minutes(5));
+    Ok(())
+}
@@ -0,0 +1,161 @@
+//! Device Presence Integration Tests
+//!
+//! These tests verify that devices go online when they connect, offline
+//! when their heartbeats time out, that a status the user chose comes back
+//! when the device reconnects, and that presence changes are broadcast.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use chrono::Utc;
+use diesel::prelude::*;
+use rustaxum::app::broadcasting::websocket::{websocket_manager, WebSocketManager};
+use rustaxum::app::models::device_presence::PresenceStatus;
+use rustaxum::app::services::conversation_service::ConversationError;
+use rustaxum::app::services::device_presence_service::DevicePresenceService;
+use rustaxum::config::messaging::MessagingConfig;
+use rustaxum::schema::device_presence;
+use serial_test::serial;
+use std::time::Duration;
+
+fn service() -> Result<DevicePresenceService> {
+    Ok(DevicePresenceService::with_config(MessagingConfig {
+        presence_timeout_secs: 60,
+        ..MessagingConfig::from_env()?
+    }))
+}
+
+#[tokio::test]
+#[serial]
+async fn test_connect_marks_device_online() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let service = service()?;
+
+    let user = common::create_user(&pool)?;
+    let contact = common::create_user(&pool)?;
+    let device = common::create_device(&pool, &user)?;
+    let conversation = common::create_conversation(&pool, &user)?;
+    common::add_participant(&pool, &conversation, &user)?;
+    common::add_participant(&pool, &conversation, &contact)?;
+
+    let mut receiver = websocket_manager().await.subscribe(&format!("conversation.{}", conversation.id)).await;
+
+    let presence = service.connect(&pool, &device.id.to_string()).await?;
+    assert!(presence.is_online());
+
+    let event = tokio::time::timeout(Duration::from_secs(2), receiver.recv()).await??;
+    assert_eq!(event.event, "presence.updated");
+    assert_eq!(event.data["user_id"], user.id.to_string());
+    assert_eq!(event.data["device_id"], device.id.to_string());
+    assert_eq!(event.data["status"], "online");
+
+    // A heartbeat without a status change refreshes last_seen_at but is not rebroadcast
+    let refreshed = service.heartbeat(&pool, &device.id.to_string(), PresenceStatus::Online).await?;
+    assert!(refreshed.last_seen_at >= presence.last_seen_at);
+    let repeated = tokio::time::timeout(Duration::from_millis(200), receiver.recv()).await;
+    assert!(repeated.is_err(), "unchanged presence must not be rebroadcast");
+
+    let contact_view = service.for_user(&pool, &contact.id.to_string(), &user.id.to_string())?;
+    assert_eq!(contact_view.status, "online");
+    assert_eq!(contact_view.devices.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_heartbeat_timeout_marks_device_offline() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let service = service()?;
+
+    let user = common::create_user(&pool)?;
+    let active = common::create_device(&pool, &user)?;
+    let stale = common::create_device(&pool, &user)?;
+
+    service.connect(&pool, &active.id.to_string()).await?;
+    service.connect(&pool, &stale.id.to_string()).await?;
+
+    // The stale device's last heartbeat was well past the timeout
+    let mut conn = pool.get()?;
+    diesel::update(device_presence::table.filter(device_presence::device_id.eq(stale.id.to_string())))
+        .set(device_presence::last_seen_at.eq(Utc::now() - chrono::Duration::minutes(5)))
+        .execute(&mut conn)?;
+
+    let swept = service.mark_stale_offline(&pool).await?;
+    let swept_ids: Vec<_> = swept.iter().map(|p| p.device_id).collect();
+    assert!(swept_ids.contains(&stale.id));
+    assert!(!swept_ids.contains(&active.id));
+
+    let presence = service.for_user(&pool, &user.id.to_string(), &user.id.to_string())?;
+    let stale_status = presence.devices.iter().find(|d| d.device_id == stale.id).map(|d| d.status.clone());
+    assert_eq!(stale_status.as_deref(), Some("offline"));
+    assert_eq!(presence.status, "online");
+
+    let offline = service.disconnect(&pool, &active.id.to_string()).await?;
+    assert!(offline.is_offline());
+    assert_eq!(service.for_user(&pool, &user.id.to_string(), &user.id.to_string())?.status, "offline");
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_chosen_status_survives_disconnect() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let service = service()?;
+
+    let user = common::create_user(&pool)?;
+    let device = common::create_device(&pool, &user)?;
+    let device_id = device.id.to_string();
+
+    service.connect(&pool, &device_id).await?;
+    let busy = service.set_status(&pool, &device_id, PresenceStatus::DoNotDisturb).await?;
+    assert!(busy.is_do_not_disturb());
+
+    assert!(service.disconnect(&pool, &device_id).await?.is_offline());
+
+    diesel::update(device_presence::table.filter(device_presence::device_id.eq(&device_id)))
+        .set(device_presence::last_seen_at.eq(Utc::now() - chrono::Duration::minutes(5)))
+        .execute(&mut pool.get()?)?;
+    service.mark_stale_offline(&pool).await?;
+
+    let reconnected = service.connect(&pool, &device_id).await?;
+    assert!(reconnected.is_do_not_disturb());
+    let heartbeat = service.heartbeat(&pool, &device_id, PresenceStatus::Online).await?;
+    assert!(heartbeat.is_do_not_disturb());
+
+    let cleared = service.set_status(&pool, &device_id, PresenceStatus::Online).await?;
+    assert!(cleared.is_online());
+    service.disconnect(&pool, &device_id).await?;
+    assert!(service.connect(&pool, &device_id).await?.is_online());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_presence_requires_shared_conversation() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let service = service()?;
+
+    let user = common::create_user(&pool)?;
+    let stranger = common::create_user(&pool)?;
+
+    let error = service.for_user(&pool, &stranger.id.to_string(), &user.id.to_string())
+        .expect_err("strangers must not see presence");
+    assert_eq!(ConversationError::status_code(&error), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_device_stays_connected_until_last_connection_closes() {
+    let manager = WebSocketManager::new();
+
+    assert_eq!(manager.add_device_connection("device").await, 1);
+    assert_eq!(manager.add_device_connection("device").await, 2);
+    assert_eq!(manager.remove_device_connection("device").await, 1);
+    assert_eq!(manager.remove_device_connection("device").await, 0);
+    assert_eq!(manager.remove_device_connection("device").await, 0);
+}
@@ -0,0 +1,208 @@
+//! Email Verification Tests
+//!
+//! These tests verify that the signed link sent after registration marks the
+//! user's email as verified, that tampered or expired links are rejected, and
+//! that the `verified` guard only lets verified users through, including on
+//! the routes that send messages.
+
+mod common;
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    routing::get,
+    Router,
+};
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use rustaxum::app::http::middleware::auth_guard::{verified_guard, AuthUser};
+use rustaxum::app::models::user::User;
+use rustaxum::app::services::auth_service::AuthService;
+use rustaxum::app::notifications::send_email_verification::SendEmailVerification;
+use rustaxum::app::services::user_service::UserService;
+use rustaxum::app::utils::{SignatureError, UrlSigner};
+use rustaxum::database::DbPool;
+use rustaxum::schema::sys_users;
+use serial_test::serial;
+use tower::ServiceExt;
+
+const TEST_APP_KEY: &str = "email-verification-test-key";
+
+async fn setup() -> (DbPool, User) {
+    std::env::set_var("APP_KEY", TEST_APP_KEY);
+    let pool = common::setup_test_db().await.expect("Failed to set up test database");
+    let user = common::create_user(&pool).expect("Failed to create user");
+
+    let mut conn = pool.get().unwrap();
+    diesel::update(sys_users::table.filter(sys_users::id.eq(user.id.to_string())))
+        .set(sys_users::email_verified_at.eq(None::<chrono::DateTime<Utc>>))
+        .execute(&mut conn)
+        .unwrap();
+
+    (pool, user)
+}
+
+fn verification_path(user: &User, expire_minutes: i64) -> String {
+    let url = SendEmailVerification::verification_url(user, &UrlSigner::new(TEST_APP_KEY), "", expire_minutes);
+    assert!(url.starts_with("/email/verify/"));
+    url
+}
+
+async fn get_status(pool: &DbPool, uri: &str) -> StatusCode {
+    let app = rustaxum::routes::api::routes().with_state(pool.clone());
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    app.oneshot(request).await.unwrap().status()
+}
+
+fn is_verified(pool: &DbPool, user: &User) -> bool {
+    UserService::find_by_id(pool, user.id.to_string())
+        .unwrap()
+        .unwrap()
+        .email_verified_at
+        .is_some()
+}
+
+#[tokio::test]
+#[serial]
+async fn test_valid_link_marks_email_verified() {
+    let (pool, user) = setup().await;
+
+    let status = get_status(&pool, &verification_path(&user, 60)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(is_verified(&pool, &user));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_tampered_link_is_rejected() {
+    let (pool, user) = setup().await;
+    let other = common::create_user(&pool).unwrap();
+    let path = verification_path(&user, 60);
+
+    // Pointing a signed link at another user breaks the signature
+    let forged = path.replace(&user.id.to_string(), &other.id.to_string());
+    assert_eq!(get_status(&pool, &forged).await, StatusCode::FORBIDDEN);
+
+    // So does editing the hash or the expiry
+    let forged = path.replace("hash=", "hash=0");
+    assert_eq!(get_status(&pool, &forged).await, StatusCode::FORBIDDEN);
+    let forged = path.replace("expires=", "expires=9");
+    assert_eq!(get_status(&pool, &forged).await, StatusCode::FORBIDDEN);
+
+    // A missing signature is rejected outright
+    let unsigned = path.split("&signature=").next().unwrap();
+    assert_eq!(get_status(&pool, unsigned).await, StatusCode::FORBIDDEN);
+
+    assert!(!is_verified(&pool, &user));
+    assert!(!is_verified(&pool, &other));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_link_for_previous_email_is_rejected() {
+    let (pool, user) = setup().await;
+    let path = verification_path(&user, 60);
+
+    let mut conn = pool.get().unwrap();
+    diesel::update(sys_users::table.filter(sys_users::id.eq(user.id.to_string())))
+        .set(sys_users::email.eq(format!("changed-{}", user.email)))
+        .execute(&mut conn)
+        .unwrap();
+
+    assert_eq!(get_status(&pool, &path).await, StatusCode::FORBIDDEN);
+    assert!(!is_verified(&pool, &user));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_expired_link_is_rejected() {
+    let (pool, user) = setup().await;
+
+    let status = get_status(&pool, &verification_path(&user, -1)).await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(!is_verified(&pool, &user));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_verified_guard_requires_verified_email() {
+    let (pool, user) = setup().await;
+
+    let user_id = user.id.to_string();
+    let app = Router::new()
+        .route("/dashboard", get(|| async { "ok" }))
+        .route_layer(middleware::from_fn_with_state(pool.clone(), verified_guard))
+        .route_layer(middleware::from_fn(move |mut request: Request, next: Next| {
+            let user_id = user_id.clone();
+            async move {
+                request.extensions_mut().insert(AuthUser { user_id, auth_method: "jwt".to_string(), impersonator_id: None });
+                next.run(request).await
+            }
+        }));
+
+    let request = || Request::builder().uri("/dashboard").body(Body::empty()).unwrap();
+
+    let response = app.clone().oneshot(request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    UserService::mark_email_verified(&pool, user.id).unwrap();
+
+    let response = app.oneshot(request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_sending_a_message_requires_a_verified_email() {
+    let (pool, user) = setup().await;
+    let device = common::create_device(&pool, &user).unwrap();
+    let conversation = common::create_conversation(&pool, &user).unwrap();
+    common::add_participant(&pool, &conversation, &user).unwrap();
+
+    let token = AuthService::generate_access_token(&user.id.to_string(), 3600).unwrap();
+    let send = || {
+        let body = serde_json::json!({
+            "device_id": device.id.to_string(),
+            "encrypted_content": "hello",
+            "content_algorithm": "none"
+        });
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/api/conversations/{}/messages", conversation.id))
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        rustaxum::routes::api::routes().with_state(pool.clone()).oneshot(request)
+    };
+
+    let response = send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["message"], "Your email address is not verified.");
+
+    UserService::mark_email_verified(&pool, user.id).unwrap();
+
+    let response = send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
+
+#[test]
+fn test_url_signer_round_trip() {
+    let signer = UrlSigner::new("key");
+    let signed = signer.sign("/files/report", &[("name", "q3 report")]);
+    let (path, query) = signed.split_once('?').unwrap();
+    assert_eq!(signer.verify(path, Some(query)), Ok(()));
+
+    // A different key does not accept the signature
+    assert_eq!(UrlSigner::new("other").verify(path, Some(query)), Err(SignatureError::Invalid));
+
+    let expired = signer.sign_temporary("/files/report", &[], Utc::now() - Duration::minutes(1));
+    let (path, query) = expired.split_once('?').unwrap();
+    assert_eq!(signer.verify(path, Some(query)), Err(SignatureError::Expired));
+}
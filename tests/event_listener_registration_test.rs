@@ -0,0 +1,46 @@
+//! Event Listener Registration Tests
+//!
+//! These tests verify that the app's listeners are wired to their events by
+//! the event service provider, both on a fresh dispatcher and on the global
+//! dispatcher after `create_app`, where building the app again does not
+//! register them twice.
+
+mod common;
+
+use anyhow::Result;
+use rustaxum::app::events::user_registered_event::UserRegisteredEvent;
+use rustaxum::app::events::{EventDispatcher, EventFacade};
+use rustaxum::app::providers::event_service_provider::register_listeners;
+use serial_test::serial;
+
+fn user_registered() -> String {
+    std::any::type_name::<UserRegisteredEvent>().to_string()
+}
+
+#[tokio::test]
+async fn test_provider_registers_app_listeners() {
+    let dispatcher = EventDispatcher::new();
+    assert!(dispatcher.get_listeners().await.is_empty());
+
+    register_listeners(&dispatcher).await;
+
+    assert_eq!(dispatcher.get_listeners().await.get(&user_registered()), Some(&1));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_create_app_registers_listeners() -> Result<()> {
+    common::setup_test_db().await?;
+
+    rustaxum::create_app().await?;
+    rustaxum::create_app().await?;
+
+    let listeners = EventFacade::get_listeners().await;
+    assert_eq!(
+        listeners.get(&user_registered()),
+        Some(&1),
+        "UserRegisteredEvent should have one listener after create_app: {:?}",
+        listeners,
+    );
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! Pattern Event Listener Tests
+//!
+//! These tests verify that a listener registered with `listen_pattern`
+//! receives only events whose name matches its pattern.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rustaxum::app::events::{Event, EventDispatcher, EventListener};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug)]
+struct NamedEvent(&'static str);
+
+impl Event for NamedEvent {
+    fn event_name(&self) -> &'static str {
+        self.0
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({ "name": self.0 })
+    }
+}
+
+/// Remembers the name of every event it handles
+#[derive(Default)]
+struct AuditListener {
+    received: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl EventListener for AuditListener {
+    async fn handle(&self, event: Arc<dyn Event>) -> Result<()> {
+        self.received.lock().unwrap().push(event.event_name().to_string());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_pattern_listener_receives_only_matching_events() -> Result<()> {
+    let dispatcher = EventDispatcher::new();
+    let audit = Arc::new(AuditListener::default());
+    dispatcher.listen_pattern("billing.*", audit.clone()).await;
+
+    dispatcher.dispatch(Arc::new(NamedEvent("billing.invoice_paid"))).await?;
+    dispatcher.dispatch(Arc::new(NamedEvent("user.registered"))).await?;
+    dispatcher.dispatch(Arc::new(NamedEvent("billing.subscription.renewed"))).await?;
+
+    assert_eq!(
+        *audit.received.lock().unwrap(),
+        vec!["billing.invoice_paid".to_string(), "billing.subscription.renewed".to_string()],
+    );
+    assert!(dispatcher.has_listeners("billing.refund_issued").await);
+    assert!(!dispatcher.has_listeners("user.registered").await);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_forgetting_a_pattern_removes_its_listeners() -> Result<()> {
+    let dispatcher = EventDispatcher::new();
+    let audit = Arc::new(AuditListener::default());
+    dispatcher.listen_pattern("billing.*", audit.clone()).await;
+
+    dispatcher.forget("billing.*").await;
+    dispatcher.dispatch(Arc::new(NamedEvent("billing.invoice_paid"))).await?;
+
+    assert!(audit.received.lock().unwrap().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_pattern_matching() {
+    assert!(EventDispatcher::matches_pattern("billing.*", "billing.invoice_paid"));
+    assert!(!EventDispatcher::matches_pattern("billing.*", "user.registered"));
+    assert!(!EventDispatcher::matches_pattern("billing.*", "billing"));
+    assert!(EventDispatcher::matches_pattern("*.created", "order.created"));
+    assert!(EventDispatcher::matches_pattern("user.*.failed", "user.login.failed"));
+    assert!(!EventDispatcher::matches_pattern("user.*.failed", "user.login.succeeded"));
+    assert!(EventDispatcher::matches_pattern("*", "anything"));
+    assert!(EventDispatcher::matches_pattern("user.registered", "user.registered"));
+    assert!(!EventDispatcher::matches_pattern("user.registered", "user.registered.twice"));
+}
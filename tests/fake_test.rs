@@ -0,0 +1,43 @@
+//! Fake Data Tests
+//!
+//! These tests verify the fake data helpers produce usable values, in
+//! particular that emails stay valid and unique across a seeding-sized run.
+
+use std::collections::{HashMap, HashSet};
+
+use rustaxum::app::utils::fake;
+use rustaxum::app::validation::rules::{EmailRule, Rule};
+use serde_json::Value;
+
+#[tokio::test]
+async fn test_emails_are_valid_and_unique() {
+    let data = HashMap::new();
+    let mut seen = HashSet::new();
+
+    for _ in 0..1000 {
+        let email = fake::email();
+        assert!(
+            EmailRule.validate("email", &Value::String(email.clone()), &data, None).await.is_ok(),
+            "{} is not a valid email",
+            email
+        );
+        assert!(seen.insert(email.clone()), "{} was generated twice", email);
+    }
+}
+
+#[test]
+fn test_helpers_produce_values() {
+    assert!(!fake::name().is_empty());
+    assert!(!fake::first_name().is_empty());
+    assert!(!fake::last_name().is_empty());
+    assert!(!fake::username().is_empty());
+    assert!(!fake::phone_number().is_empty());
+    assert!(!fake::sentence().is_empty());
+
+    let address = fake::address();
+    assert!(!address.street.is_empty());
+    assert!(!address.city.is_empty());
+    assert!(address.to_string().contains(&address.postal_code));
+
+    assert_ne!(fake::ulid(), fake::ulid());
+}
@@ -0,0 +1,144 @@
+//! Feature Flag Tests
+//!
+//! These tests verify that percentage rollouts put each user in a stable
+//! bucket, that overrides win over the flag's own setting, and that the
+//! feature middleware gates routes.
+
+mod common;
+
+use anyhow::Result;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::get,
+    Router,
+};
+use rustaxum::app::features::{Feature, FeatureContext};
+use rustaxum::app::http::middleware::feature_middleware::{require_feature, FeatureGate};
+use rustaxum::app::models::feature_flag::FeatureFlag;
+use rustaxum::app::services::feature_flag_service::FeatureFlagService;
+use serial_test::serial;
+use tower::ServiceExt;
+
+fn flag_name() -> String {
+    format!("test-flag-{}", ulid::Ulid::new().to_string().to_lowercase())
+}
+
+#[test]
+fn test_rollout_bucket_is_stable_per_user() {
+    let flag = FeatureFlag::new("new-dashboard".to_string(), true, Some(30));
+    let users: Vec<String> = (0..1000).map(|_| ulid::Ulid::new().to_string()).collect();
+
+    for user in &users {
+        let again = FeatureFlag::new("new-dashboard".to_string(), true, Some(30));
+        assert_eq!(flag.rollout_bucket(user), again.rollout_bucket(user));
+        assert_eq!(flag.is_enabled_for(Some(user)), again.is_enabled_for(Some(user)));
+    }
+
+    // Roughly the requested share of users is in the rollout
+    let enabled = users.iter().filter(|user| flag.is_enabled_for(Some(user))).count();
+    assert!((200..400).contains(&enabled), "{} of 1000 users enabled", enabled);
+}
+
+#[test]
+fn test_rollout_grows_without_reshuffling_users() {
+    let users: Vec<String> = (0..200).map(|_| ulid::Ulid::new().to_string()).collect();
+    let at_10 = FeatureFlag::new("checkout-v2".to_string(), true, Some(10));
+    let at_50 = FeatureFlag::new("checkout-v2".to_string(), true, Some(50));
+
+    for user in &users {
+        if at_10.is_enabled_for(Some(user)) {
+            assert!(at_50.is_enabled_for(Some(user)));
+        }
+    }
+}
+
+#[test]
+fn test_switch_and_rollout_edges() {
+    let user = Some("01ARZ3NDEKTSV4RRFFQ69G5FAV");
+
+    assert!(!FeatureFlag::new("a".to_string(), false, None).is_enabled_for(user));
+    assert!(FeatureFlag::new("a".to_string(), true, None).is_enabled_for(user));
+    assert!(FeatureFlag::new("a".to_string(), true, None).is_enabled_for(None));
+    assert!(FeatureFlag::new("a".to_string(), true, Some(100)).is_enabled_for(None));
+    assert!(!FeatureFlag::new("a".to_string(), true, Some(0)).is_enabled_for(user));
+    // Guests have no bucket, so a partial rollout is off for them
+    assert!(!FeatureFlag::new("a".to_string(), true, Some(99)).is_enabled_for(None));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_percentage_rollout_is_stable_per_user() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let name = flag_name();
+    let flag = FeatureFlagService::set(&pool, &name, Some(true), Some(50), None).await?;
+
+    for _ in 0..5 {
+        let user = common::create_user(&pool)?;
+        let context = FeatureContext::for_user(user.id);
+        let expected = flag.is_enabled_for(Some(&user.id.to_string()));
+
+        for _ in 0..3 {
+            assert_eq!(Feature::enabled(&pool, &name, &context).await, expected);
+        }
+    }
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_override_wins_over_flag() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let other = common::create_user(&pool)?;
+    let name = flag_name();
+
+    FeatureFlagService::set(&pool, &name, Some(false), None, None).await?;
+    FeatureFlagService::set_override(&pool, &name, user.id, Some(true)).await?;
+
+    assert!(Feature::enabled(&pool, &name, &FeatureContext::for_user(user.id)).await);
+    assert!(!Feature::enabled(&pool, &name, &FeatureContext::for_user(other.id)).await);
+
+    // An "off" override beats a flag that is on for everyone
+    FeatureFlagService::set(&pool, &name, Some(true), None, None).await?;
+    FeatureFlagService::set_override(&pool, &name, user.id, Some(false)).await?;
+    assert!(!Feature::enabled(&pool, &name, &FeatureContext::for_user(user.id)).await);
+    assert!(Feature::enabled(&pool, &name, &FeatureContext::for_user(other.id)).await);
+
+    FeatureFlagService::set_override(&pool, &name, user.id, None).await?;
+    assert!(Feature::enabled(&pool, &name, &FeatureContext::for_user(user.id)).await);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_unknown_flag_is_off() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+
+    assert!(Feature::disabled(&pool, &flag_name(), &FeatureContext::guest()).await);
+    assert!(FeatureFlagService::set(&pool, &flag_name(), None, Some(101), None).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_middleware_gates_routes() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let name = flag_name();
+    let app = Router::new()
+        .route("/beta", get(|| async { "beta" }))
+        .route_layer(middleware::from_fn_with_state(FeatureGate::new(pool.clone(), &name), require_feature));
+    let status = |app: Router| async move {
+        app.oneshot(Request::builder().uri("/beta").body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    };
+
+    assert_eq!(status(app.clone()).await, StatusCode::NOT_FOUND);
+
+    FeatureFlagService::set(&pool, &name, Some(true), None, None).await?;
+    assert_eq!(status(app).await, StatusCode::OK);
+    Ok(())
+}
@@ -0,0 +1,113 @@
+//! Field Selection Tests
+//!
+//! These tests verify that `fields[table]` limits rows to the requested
+//! fields when every one is in the model's `allowed_fields`, and that naming
+//! any other field is rejected with a 400 keyed by the `fields[...]`
+//! parameter instead of the field being dropped or selected. The router
+//! tests check that index routes read bracketed `filter[...]` and
+//! `fields[...]` keys from the query string.
+
+mod common;
+
+use anyhow::Result;
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use rustaxum::app::models::organization_domain::{CreateOrganizationDomain, OrganizationDomain};
+use rustaxum::app::query_builder::{QueryBuilderExt, QueryExecutor, QueryParams, QueryParamsError};
+use rustaxum::app::models::user::User;
+use rustaxum::app::services::auth_service::AuthService;
+use rustaxum::app::services::organization_domain_service::OrganizationDomainService;
+use rustaxum::database::DbPool;
+use serde_json::{json, Value};
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn get(pool: &DbPool, uri: &str, user: &User) -> Result<(StatusCode, Value)> {
+    let app = rustaxum::routes::api::routes().with_state(pool.clone());
+    let token = AuthService::generate_access_token(&user.id.to_string(), 3600)?;
+    let request = Request::builder()
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())?;
+
+    let response = app.oneshot(request).await?;
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok((status, serde_json::from_slice(&body).unwrap_or(Value::Null)))
+}
+
+#[tokio::test]
+#[serial]
+async fn test_allowed_fields_limit_rows() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let domain = OrganizationDomainService::create(&pool, CreateOrganizationDomain {
+        code: Some(ulid::Ulid::new().to_string()),
+        name: "Selected Domain".to_string(),
+        description: Some("Not selected".to_string()),
+    }, &user.id.to_string()).await?;
+
+    let params = QueryParams::from_query_string(&format!(
+        "fields[organization_domains]=id,code,name&filter[id]={}",
+        domain.id
+    ))?;
+    let mut conn = pool.get()?;
+    let result = QueryExecutor::execute_paginated(OrganizationDomain::from_params(params)?, &mut conn)?;
+
+    assert_eq!(result.data.len(), 1);
+    let mut keys: Vec<&str> = result.data[0].as_object().unwrap().keys().map(String::as_str).collect();
+    keys.sort();
+    assert_eq!(keys, vec!["code", "id", "name"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_disallowed_field_is_rejected() -> Result<()> {
+    let params = QueryParams::from_query_string("fields[organization_domains]=id,secret_column")?;
+
+    let error = OrganizationDomain::from_params(params).unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<QueryParamsError>(),
+        Some(QueryParamsError::FieldNotAllowed { resource, field })
+            if resource == "organization_domains" && field == "secret_column"
+    ));
+
+    let response = QueryParamsError::response(&error);
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    let body: Value = serde_json::from_slice(&bytes)?;
+    assert_eq!(
+        body["errors"],
+        json!({ "fields[organization_domains]": ["field 'secret_column' is not allowed on 'organization_domains'"] })
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_index_route_reads_bracketed_keys() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let domain = OrganizationDomainService::create(&pool, CreateOrganizationDomain {
+        code: Some(ulid::Ulid::new().to_string()),
+        name: "Routed Domain".to_string(),
+        description: Some("Not selected".to_string()),
+    }, &user.id.to_string()).await?;
+
+    let uri = format!(
+        "/api/organization-domains?filter[id]={}&fields[organization_domains]=id,name",
+        domain.id
+    );
+    let (status, body) = get(&pool, &uri, &user).await?;
+    assert_eq!(status, StatusCode::OK);
+    let rows = body["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    let mut keys: Vec<&str> = rows[0].as_object().unwrap().keys().map(String::as_str).collect();
+    keys.sort();
+    assert_eq!(keys, vec!["id", "name"]);
+
+    let (status, body) = get(&pool, "/api/organization-domains?fields[organization_domains]=id,secret_column", &user).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["errors"]["fields[organization_domains]"].is_array());
+    Ok(())
+}
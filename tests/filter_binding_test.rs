@@ -0,0 +1,83 @@
+//! Filter Value Binding Tests
+//!
+//! These tests verify that `QueryExecutor` sends filter values as bound
+//! parameters: a name with a quote matches exactly, a value carrying a
+//! `; DROP TABLE` is only ever compared as text, and values for timestamp
+//! and integer columns are still read as those types.
+
+mod common;
+
+use anyhow::Result;
+use diesel::prelude::*;
+use rustaxum::app::models::user::User;
+use rustaxum::app::query_builder::{QueryBuilderExt, QueryExecutor, QueryParams};
+use rustaxum::database::DbPool;
+use rustaxum::schema::sys_users;
+use serial_test::serial;
+
+fn rename_user(pool: &DbPool, user: &User, name: &str) -> Result<()> {
+    let mut conn = pool.get()?;
+    diesel::update(sys_users::table.filter(sys_users::id.eq(user.id.to_string())))
+        .set(sys_users::name.eq(name))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+fn matching_ids(pool: &DbPool, query: &str) -> Result<Vec<String>> {
+    let params = QueryParams::from_query_string(query)?;
+    let mut conn = pool.get()?;
+    let rows = QueryExecutor::execute_all(User::from_params(params)?, &mut conn)?;
+    Ok(rows.iter().map(|row| row["id"].as_str().unwrap_or_default().to_string()).collect())
+}
+
+fn user_count(pool: &DbPool) -> Result<i64> {
+    let mut conn = pool.get()?;
+    Ok(sys_users::table.count().get_result(&mut conn)?)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_quoted_value_matches_exactly() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let token = ulid::Ulid::new().to_string();
+    let user = common::create_user(&pool)?;
+    rename_user(&pool, &user, &format!("O'Brien {}", token))?;
+
+    let query = format!("filter[name]=O%27Brien%20{}", token);
+    assert_eq!(matching_ids(&pool, &query)?, vec![user.id.to_string()]);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_injected_sql_is_compared_as_text() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let payload = "x'; DROP TABLE sys_users; --";
+    rename_user(&pool, &user, payload)?;
+    let before = user_count(&pool)?;
+
+    let encoded: String = url::form_urlencoded::byte_serialize(payload.as_bytes()).collect();
+    let ids = matching_ids(&pool, &format!("filter[name]={}", encoded))?;
+    assert!(ids.contains(&user.id.to_string()), "the payload is matched as a plain name");
+
+    let ids = matching_ids(&pool, &format!("filter[name][in]=a,{}&filter[email][contains]={}", encoded, encoded))?;
+    assert!(ids.is_empty());
+
+    assert_eq!(user_count(&pool)?, before, "sys_users is untouched");
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_non_text_columns_are_cast() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+
+    let query = format!(
+        "filter[id]={}&filter[failed_login_attempts][lte]=0&filter[created_at][gte]=2000-01-01T00:00:00Z",
+        user.id
+    );
+    assert_eq!(matching_ids(&pool, &query)?, vec![user.id.to_string()]);
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! Filter Group Tests
+//!
+//! These tests verify that `filter[or][i][field][op]` query parameters parse
+//! into an OR `FilterGroup`, with one level of nested groups, without also
+//! being read as flat filters, that strict parsing reports malformed group
+//! keys, and that the executor matches rows satisfying any condition.
+
+mod common;
+
+use anyhow::Result;
+use diesel::prelude::*;
+use rustaxum::app::models::user::User;
+use rustaxum::app::query_builder::{
+    FilterCombinator, FilterCondition, FilterError, FilterOperator, QueryBuilderExt, QueryExecutor, QueryParams,
+    QueryParamsError,
+};
+use rustaxum::database::DbPool;
+use rustaxum::schema::sys_users;
+use serial_test::serial;
+
+const ALLOWED: &[&str] = &["name", "email", "failed_login_attempts"];
+
+#[test]
+fn test_or_group_parses_from_query_string() -> Result<()> {
+    let params = QueryParams::from_query_string("filter[or][0][name][contains]=jo&filter[or][1][email][contains]=jo&filter[name]=Jo")?;
+
+    let groups = params.validate_filter_groups(ALLOWED)?;
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].combinator, FilterCombinator::Or);
+
+    let filters = groups[0].filters();
+    assert_eq!(filters.len(), 2);
+    assert_eq!((filters[0].field.as_str(), &filters[0].operator), ("name", &FilterOperator::Contains));
+    assert_eq!((filters[1].field.as_str(), &filters[1].operator), ("email", &FilterOperator::Contains));
+
+    let flat = params.validate_filters(ALLOWED)?;
+    assert_eq!(flat.len(), 1, "grouped terms are not also read as flat filters");
+    assert_eq!(flat[0].field, "name");
+    Ok(())
+}
+
+#[test]
+fn test_nested_group_parses_one_level_deep() -> Result<()> {
+    let params = QueryParams::from_query_string(
+        "filter[or][0][name][contains]=jo&filter[or][1][and][0][email][ends_with]=@example.com&filter[or][1][and][1][failed_login_attempts][gte]=3",
+    )?;
+
+    let groups = params.validate_filter_groups(ALLOWED)?;
+    assert_eq!(groups[0].conditions.len(), 2);
+    let FilterCondition::Group(nested) = &groups[0].conditions[1] else {
+        panic!("expected a nested group");
+    };
+    assert_eq!(nested.combinator, FilterCombinator::And);
+    assert_eq!(nested.filters().len(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_strict_parsing_rejects_malformed_groups() -> Result<()> {
+    let params = QueryParams::from_query_string(
+        "filter[or][first][name]=jo&filter[or][0][and][0][or][0][name]=jo&filter[or][1][password]=secret",
+    )?;
+
+    let Err(QueryParamsError::MalformedFilters(errors)) = params.validate_filters(ALLOWED) else {
+        panic!("expected malformed filters");
+    };
+    assert_eq!(errors.len(), 3);
+    assert!(errors.contains(&FilterError::MalformedGroup {
+        filter: "filter[or][first][name]".to_string(),
+        reason: "expected a position after 'or'".to_string(),
+    }));
+    assert!(errors.contains(&FilterError::MalformedGroup {
+        filter: "filter[or][0][and][0][or][0][name]".to_string(),
+        reason: "filter groups nest one level deep".to_string(),
+    }));
+    assert!(errors.contains(&FilterError::FieldNotAllowed {
+        filter: "filter[or][1][password]".to_string(),
+        field: "password".to_string(),
+    }));
+    Ok(())
+}
+
+fn rename_user(pool: &DbPool, user: &User, name: &str) -> Result<()> {
+    let mut conn = pool.get()?;
+    diesel::update(sys_users::table.filter(sys_users::id.eq(user.id.to_string())))
+        .set(sys_users::name.eq(name))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_or_group_matches_any_condition() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let token = ulid::Ulid::new().to_string().to_lowercase();
+
+    let by_name = common::create_user(&pool)?;
+    rename_user(&pool, &by_name, &format!("Jo {}", token))?;
+    let by_email = common::create_user(&pool)?;
+    let neither = common::create_user(&pool)?;
+    rename_user(&pool, &neither, &format!("Grace {}", token))?;
+
+    let email_token = by_email.email.split('@').next().unwrap_or_default().to_string();
+    let params = QueryParams::from_query_string(&format!(
+        "filter[or][0][name][contains]=jo%20{token}&filter[or][1][email][contains]={email_token}",
+    ))?;
+
+    let mut conn = pool.get()?;
+    let rows = QueryExecutor::execute_all(User::from_params(params)?, &mut conn)?;
+
+    let mut ids: Vec<String> = rows.iter().map(|row| row["id"].as_str().unwrap_or_default().to_string()).collect();
+    ids.sort();
+    let mut expected = vec![by_name.id.to_string(), by_email.id.to_string()];
+    expected.sort();
+    assert_eq!(ids, expected);
+    Ok(())
+}
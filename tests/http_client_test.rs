@@ -0,0 +1,121 @@
+//! Shared HTTP Client Tests
+//!
+//! These tests run the client against a local stub server to verify retry
+//! with backoff and correlation ID propagation.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, http::{HeaderMap, StatusCode}, routing::get, Router};
+use rustaxum::app::http::middleware::correlation_middleware::with_correlation_id;
+use rustaxum::app::models::DieselUlid;
+use rustaxum::app::utils::HttpClient;
+use rustaxum::config::http_client::HttpClientConfig;
+
+/// Serve a stub on an ephemeral port and return its base URL
+async fn serve(router: Router) -> Result<String> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.ok();
+    });
+    Ok(format!("http://{}", addr))
+}
+
+fn client(max_retries: u32) -> Result<HttpClient> {
+    HttpClient::with_config(HttpClientConfig {
+        max_retries,
+        retry_delay_ms: 1,
+        ..HttpClientConfig::from_env()?
+    })
+}
+
+/// Fails with 503 until the given number of calls have been made
+fn flaky_router(failures: usize, hits: Arc<AtomicUsize>) -> Router {
+    let handler = move |State(hits): State<Arc<AtomicUsize>>| async move {
+        if hits.fetch_add(1, Ordering::SeqCst) < failures {
+            (StatusCode::SERVICE_UNAVAILABLE, "unavailable")
+        } else {
+            (StatusCode::OK, "ok")
+        }
+    };
+
+    Router::new()
+        .route("/flaky", get(handler.clone()).post(handler))
+        .with_state(hits)
+}
+
+#[tokio::test]
+async fn test_retries_503_until_success() -> Result<()> {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let base_url = serve(flaky_router(2, hits.clone())).await?;
+    let client = client(3)?;
+
+    let response = client.send(client.get(&format!("{}/flaky", base_url))).await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().await?, "ok");
+    assert_eq!(hits.load(Ordering::SeqCst), 3, "two failed attempts then a success");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_gives_up_after_max_retries() -> Result<()> {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let base_url = serve(flaky_router(10, hits.clone())).await?;
+    let client = client(2)?;
+
+    let response = client.send(client.get(&format!("{}/flaky", base_url))).await?;
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(hits.load(Ordering::SeqCst), 3, "one attempt plus two retries");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_non_idempotent_requests_are_not_retried() -> Result<()> {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let base_url = serve(flaky_router(1, hits.clone())).await?;
+    let client = client(3)?;
+
+    let response = client.send(client.post(&format!("{}/flaky", base_url)).body("payload")).await?;
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_correlation_id_is_propagated() -> Result<()> {
+    let router = Router::new().route("/echo", get(|headers: HeaderMap| async move {
+        headers.get("x-correlation-id")
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("")
+            .to_string()
+    }));
+    let base_url = serve(router).await?;
+    let client = client(0)?;
+    let url = format!("{}/echo", base_url);
+
+    let correlation_id = DieselUlid::new();
+    let echoed = with_correlation_id(correlation_id, async {
+        client.send(client.get(&url)).await?.text().await.map_err(anyhow::Error::from)
+    }).await?;
+    assert_eq!(echoed, correlation_id.to_string());
+
+    // Outside a request scope no header is added
+    let echoed = client.send(client.get(&url)).await?.text().await?;
+    assert_eq!(echoed, "");
+
+    // An explicit header is left untouched
+    let echoed = with_correlation_id(correlation_id, async {
+        client.send(client.get(&url).header("X-Correlation-ID", "upstream-id")).await?.text().await.map_err(anyhow::Error::from)
+    }).await?;
+    assert_eq!(echoed, "upstream-id");
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+//! ULID Serialization Format Tests
+//!
+//! These tests verify that `DieselUlid` formats as a canonical ULID, a UUID
+//! or a lowercase ULID, that every form deserializes back to the same id,
+//! that a request can pick the format for the ids of response types, and
+//! that `DieselUlid` values outside response types stay canonical.
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::get,
+    Json, Router,
+};
+use rustaxum::app::http::middleware::id_format_middleware::id_format_middleware;
+use rustaxum::app::models::diesel_ulid::with_id_format;
+use rustaxum::app::models::DieselUlid;
+use rustaxum::config::response::IdFormat;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+const ULID: &str = "01ARZ3NDEKTSV4RRFFQ69G5FAV";
+const UUID: &str = "01563e3a-b5d3-d676-4c61-efb99302bd5b";
+
+fn id() -> DieselUlid {
+    DieselUlid::from_string(ULID).unwrap()
+}
+
+fn serialize_as(format: IdFormat, id: DieselUlid) -> String {
+    id.format(format)
+}
+
+#[test]
+fn test_round_trip_canonical_ulid() {
+    let json = serialize_as(IdFormat::Ulid, id());
+
+    assert_eq!(json, ULID);
+    assert_eq!(serde_json::from_value::<DieselUlid>(Value::String(json)).unwrap(), id());
+}
+
+#[test]
+fn test_round_trip_uuid() {
+    let json = serialize_as(IdFormat::Uuid, id());
+
+    assert_eq!(json, UUID);
+    assert_eq!(serde_json::from_value::<DieselUlid>(Value::String(json)).unwrap(), id());
+}
+
+#[test]
+fn test_round_trip_lowercase_ulid() {
+    let json = serialize_as(IdFormat::Lowercase, id());
+
+    assert_eq!(json, ULID.to_lowercase());
+    assert_eq!(serde_json::from_value::<DieselUlid>(Value::String(json)).unwrap(), id());
+}
+
+#[test]
+fn test_round_trip_random_ids_in_every_format() {
+    for _ in 0..100 {
+        let id = DieselUlid::new();
+        for format in [IdFormat::Ulid, IdFormat::Uuid, IdFormat::Lowercase] {
+            let json = serialize_as(format, id);
+            assert_eq!(DieselUlid::parse(&json).unwrap(), id);
+        }
+    }
+}
+
+#[test]
+fn test_deserialization_accepts_simple_uuid_and_rejects_garbage() {
+    let simple = "01563e3ab5d3d6764c61efb99302bd5b";
+    assert_eq!(serde_json::from_value::<DieselUlid>(Value::String(simple.to_string())).unwrap(), id());
+
+    assert!(serde_json::from_value::<DieselUlid>(Value::String("not-an-id".to_string())).is_err());
+}
+
+#[test]
+fn test_default_format_is_canonical() {
+    assert_eq!(serde_json::to_value(id()).unwrap(), ULID);
+}
+
+#[derive(Serialize)]
+struct UserResponse {
+    #[serde(with = "rustaxum::app::models::diesel_ulid::response")]
+    id: DieselUlid,
+    #[serde(with = "rustaxum::app::models::diesel_ulid::response::option")]
+    owner_id: Option<DieselUlid>,
+    /// Plain `DieselUlid`s, as in cached values or job payloads, stay canonical
+    payload: Value,
+}
+
+async fn get_json(uri: &str, header: Option<&str>) -> Value {
+    let app = Router::new()
+        .route("/users", get(|| async {
+            Json(UserResponse {
+                id: id(),
+                owner_id: Some(id()),
+                payload: json!({ "id": id() }),
+            })
+        }))
+        .layer(middleware::from_fn(id_format_middleware));
+
+    let mut request = Request::builder().uri(uri);
+    if let Some(format) = header {
+        request = request.header("X-Id-Format", format);
+    }
+
+    let response = app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_request_selects_format() {
+    assert_eq!(get_json("/users", None).await["id"], ULID);
+    assert_eq!(get_json("/users?id_format=uuid", None).await["id"], UUID);
+    assert_eq!(get_json("/users", Some("lowercase")).await["id"], ULID.to_lowercase());
+
+    // The query string wins over the header
+    assert_eq!(get_json("/users?id_format=ulid", Some("uuid")).await["id"], ULID);
+}
+
+#[tokio::test]
+async fn test_only_response_fields_follow_the_requested_format() {
+    let body = get_json("/users?id_format=uuid", None).await;
+
+    assert_eq!(body["id"], UUID);
+    assert_eq!(body["owner_id"], UUID);
+    assert_eq!(body["payload"]["id"], ULID);
+}
+
+#[tokio::test]
+async fn test_ids_serialize_canonically_inside_a_requested_format() {
+    let json = with_id_format(IdFormat::Uuid, async { serde_json::to_value(id()).unwrap() }).await;
+
+    assert_eq!(json, ULID);
+}
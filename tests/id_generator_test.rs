@@ -0,0 +1,105 @@
+//! Id Generator Tests
+//!
+//! These tests verify that models keep random ULIDs by default, that a model
+//! can opt into prefixed, monotonic or Snowflake-style ids, and that prefixed
+//! ids parse back and filter like the stored ULID.
+
+use rustaxum::app::models::id_generator::{
+    MonotonicUlidGenerator, PrefixedIdGenerator, SnowflakeGenerator, UlidGenerator,
+};
+use rustaxum::app::models::{DieselUlid, HasIdGenerator, IdGenerator};
+use rustaxum::app::query_builder::{Filter, FilterValue};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+struct DefaultModel;
+
+impl HasIdGenerator for DefaultModel {}
+
+struct PrefixedModel;
+
+static ORG_IDS: OnceLock<PrefixedIdGenerator> = OnceLock::new();
+
+impl HasIdGenerator for PrefixedModel {
+    fn id_generator() -> &'static dyn IdGenerator {
+        ORG_IDS.get_or_init(|| PrefixedIdGenerator::new("org"))
+    }
+}
+
+#[test]
+fn test_default_generator_keeps_random_ulids() {
+    let first = DefaultModel::new_id();
+    let second = DefaultModel::new_id();
+
+    assert_ne!(first, second);
+    assert!(DefaultModel::id_generator().prefix().is_none());
+    assert_eq!(DefaultModel::display_id(&first), first.to_string());
+    assert_eq!(DieselUlid::parse(&first.to_string()).unwrap(), first);
+    assert_eq!(UlidGenerator.generate().to_string().len(), 26);
+}
+
+#[test]
+fn test_prefixed_generator_shows_prefix_and_parses_back() {
+    let id = PrefixedModel::new_id();
+    let shown = PrefixedModel::display_id(&id);
+
+    assert_eq!(shown, format!("org_{}", id));
+    assert_eq!(DieselUlid::parse(&shown).unwrap(), id);
+    assert_eq!(serde_json::from_value::<DieselUlid>(Value::String(shown.clone())).unwrap(), id);
+
+    // The prefix is presentation only; the stored form stays a plain ULID
+    assert_eq!(id.to_string().len(), 26);
+    assert_eq!(DieselUlid::normalize(&shown), Some(id.to_string()));
+    assert_eq!(DieselUlid::normalize(&shown.to_lowercase()), Some(id.to_string()));
+}
+
+#[test]
+fn test_prefix_parsing_rejects_non_ids() {
+    assert_eq!(DieselUlid::strip_prefix("org_not-a-ulid"), "org_not-a-ulid");
+    assert_eq!(DieselUlid::normalize("org_not-a-ulid"), None);
+    assert_eq!(DieselUlid::normalize("01563e3a-b5d3-d676-4c61-efb99302bd5b"), None);
+    assert!(DieselUlid::parse("org_").is_err());
+}
+
+#[test]
+fn test_query_builder_filters_match_stored_ids() {
+    let id = PrefixedModel::new_id();
+    let mut params = HashMap::new();
+    params.insert("organization_id".to_string(), json!(PrefixedModel::display_id(&id)));
+    params.insert("id[in]".to_string(), json!(format!("org_{},{}", id, id.to_string().to_lowercase())));
+    params.insert("name".to_string(), json!("org_keep-as-is"));
+
+    let filters = Filter::from_params(&params);
+    let value_of = |field: &str| filters.iter().find(|filter| filter.field == field).unwrap().value.clone();
+
+    assert_eq!(value_of("organization_id"), FilterValue::Single(json!(id.to_string())));
+    assert_eq!(
+        value_of("id"),
+        FilterValue::Multiple(vec![json!(id.to_string()), json!(id.to_string())])
+    );
+    assert_eq!(value_of("name"), FilterValue::Single(json!("org_keep-as-is")));
+}
+
+#[test]
+fn test_monotonic_ids_strictly_increase() {
+    let generator = MonotonicUlidGenerator::new();
+    let ids: Vec<DieselUlid> = (0..1000).map(|_| generator.generate()).collect();
+
+    assert!(ids.windows(2).all(|pair| pair[0].0 < pair[1].0));
+}
+
+#[test]
+fn test_snowflake_ids_carry_worker_and_sort_in_order() {
+    let generator = SnowflakeGenerator::new(42);
+    let ids: Vec<DieselUlid> = (0..5000).map(|_| generator.generate()).collect();
+
+    assert!(ids.iter().all(|id| SnowflakeGenerator::worker_id_of(*id) == 42));
+    assert!(ids.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert_eq!(SnowflakeGenerator::sequence_of(ids[0]), 0);
+
+    // A prefixed wrapper keeps the inner strategy's ids
+    let prefixed = PrefixedIdGenerator::wrapping("evt", SnowflakeGenerator::new(7));
+    assert_eq!(SnowflakeGenerator::worker_id_of(prefixed.generate()), 7);
+    assert_eq!(prefixed.prefix(), Some("evt"));
+}
@@ -0,0 +1,172 @@
+//! Impersonation Tests
+//!
+//! These tests verify that an administrator can act as another user, that
+//! requests made while impersonating record both ids in the activity log, and
+//! that stopping returns a token for the administrator and revokes the
+//! impersonation token in the database.
+
+mod common;
+
+use anyhow::Result;
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+};
+use diesel::prelude::*;
+use rustaxum::app::http::middleware::auth_middleware::validate_jwt_claims;
+use rustaxum::app::models::activity_log::ActivityLog;
+use rustaxum::app::models::role::Role;
+use rustaxum::app::models::sys_model_has_role::SysModelHasRole;
+use rustaxum::app::models::user::User;
+use rustaxum::app::services::auth_service::AuthService;
+use rustaxum::database::DbPool;
+use rustaxum::schema::{activity_log, revoked_access_tokens, sys_model_has_roles, sys_roles};
+use serde_json::Value;
+use serial_test::serial;
+use tower::ServiceExt;
+
+fn make_admin(pool: &DbPool, user: &User) -> Result<()> {
+    let role = Role::new("admin".to_string(), None, None, user.id);
+    let assignment = SysModelHasRole::new("User".to_string(), user.id, role.id, None, None, user.id);
+
+    let mut conn = pool.get()?;
+    diesel::insert_into(sys_roles::table).values(&role).execute(&mut conn)?;
+    diesel::insert_into(sys_model_has_roles::table).values(&assignment).execute(&mut conn)?;
+    Ok(())
+}
+
+async fn send(pool: &DbPool, method: Method, uri: &str, token: &str) -> (StatusCode, Value) {
+    let app = rustaxum::routes::api::routes().with_state(pool.clone());
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn impersonation_events(pool: &DbPool, user: &User, event: &str) -> Result<Vec<ActivityLog>> {
+    let mut conn = pool.get()?;
+    let events = activity_log::table
+        .filter(activity_log::log_name.eq("impersonation"))
+        .filter(activity_log::event.eq(event))
+        .filter(activity_log::subject_id.eq(user.id.to_string()))
+        .select(ActivityLog::as_select())
+        .load(&mut conn)?;
+    Ok(events)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_admin_can_start_impersonation() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let admin = common::create_user(&pool)?;
+    make_admin(&pool, &admin)?;
+    let user = common::create_user(&pool)?;
+
+    let admin_token = AuthService::generate_access_token(&admin.id.to_string(), 3600)?;
+    let (status, body) = send(&pool, Method::POST, &format!("/api/impersonation/{}", user.id), &admin_token).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["user"]["id"], user.id.to_string());
+    assert_eq!(body["impersonator_id"], admin.id.to_string());
+
+    let claims = validate_jwt_claims(body["access_token"].as_str().unwrap()).unwrap();
+    assert_eq!(claims.sub, user.id.to_string());
+    assert_eq!(claims.impersonator_id, Some(admin.id.to_string()));
+
+    let started = impersonation_events(&pool, &user, "impersonation.started")?;
+    assert_eq!(started.len(), 1);
+    assert_eq!(started[0].causer_id, Some(admin.id.to_string()));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_non_admin_cannot_impersonate() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let actor = common::create_user(&pool)?;
+    let user = common::create_user(&pool)?;
+
+    let token = AuthService::generate_access_token(&actor.id.to_string(), 3600)?;
+    let (status, _) = send(&pool, Method::POST, &format!("/api/impersonation/{}", user.id), &token).await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(impersonation_events(&pool, &user, "impersonation.started")?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_action_while_impersonating_records_both_ids() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let admin = common::create_user(&pool)?;
+    let user = common::create_user(&pool)?;
+
+    let token = AuthService::generate_impersonation_token(&user.id.to_string(), &admin.id.to_string(), 3600)?;
+    let (status, body) = send(&pool, Method::GET, "/api/me", &token).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["impersonator_id"], admin.id.to_string());
+
+    let requests = impersonation_events(&pool, &user, "impersonation.request")?;
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].causer_id, Some(admin.id.to_string()));
+
+    let properties = requests[0].properties.clone().unwrap();
+    assert_eq!(properties["user_id"], user.id.to_string());
+    assert_eq!(properties["impersonator_id"], admin.id.to_string());
+    assert_eq!(properties["path"], "/api/me");
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_stop_returns_token_for_impersonator() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let admin = common::create_user(&pool)?;
+    let user = common::create_user(&pool)?;
+
+    let token = AuthService::generate_impersonation_token(&user.id.to_string(), &admin.id.to_string(), 3600)?;
+    let (status, body) = send(&pool, Method::DELETE, "/api/impersonation", &token).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["user"]["id"], admin.id.to_string());
+    assert!(body["impersonator_id"].is_null());
+
+    let claims = validate_jwt_claims(body["access_token"].as_str().unwrap()).unwrap();
+    assert_eq!(claims.sub, admin.id.to_string());
+    assert_eq!(claims.impersonator_id, None);
+
+    assert_eq!(impersonation_events(&pool, &user, "impersonation.stopped")?.len(), 1);
+
+    // The revocation is stored in the database, so every instance sees it
+    let impersonation_claims = validate_jwt_claims(&token).unwrap();
+    let mut conn = pool.get()?;
+    let revoked: i64 = revoked_access_tokens::table
+        .filter(revoked_access_tokens::jti.eq(&impersonation_claims.jti))
+        .count()
+        .get_result(&mut conn)?;
+    assert_eq!(revoked, 1);
+    assert!(AuthService::is_access_token_revoked(&impersonation_claims).await);
+
+    // The impersonation token no longer authenticates
+    let (status, _) = send(&pool, Method::GET, "/api/me", &token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&pool, Method::DELETE, "/api/impersonation", &token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // A regular token has nothing to stop
+    let (status, _) = send(&pool, Method::DELETE, "/api/impersonation", body["access_token"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+//! Include Field Selection Tests
+//!
+//! These tests verify that `fields[relation]` on an included relationship is
+//! checked against the related model's allowed fields, rejected with a 400
+//! when it names a field that isn't allowed, and limits the included rows to
+//! the requested fields. Includes loaded through `Includable`, such as an
+//! organization's positions, are checked the same way.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use rustaxum::app::models::conversation::Conversation;
+use rustaxum::app::models::organization::Organization;
+use rustaxum::app::query_builder::{Include, QueryBuilderExt, QueryExecutor, QueryParams, QueryParamsError};
+use serde_json::json;
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn test_nested_field_selection_limits_included_rows() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let device = common::create_device(&pool, &user)?;
+    let conversation = common::create_conversation(&pool, &user)?;
+    for _ in 0..3 {
+        common::create_message(&pool, &conversation, &user, &device)?;
+    }
+
+    let mut params = QueryParams::from_query_string("include=messages&fields[messages]=id,message_type")?;
+    params.filter.insert("id".to_string(), serde_json::json!(conversation.id.to_string()));
+
+    let mut conn = pool.get()?;
+    let result = QueryExecutor::execute_paginated(Conversation::from_params(params)?, &mut conn)?;
+
+    let messages = result.data[0]["messages"]["data"].as_array().unwrap();
+    assert_eq!(messages.len(), 3);
+    for message in messages {
+        let mut keys: Vec<&str> = message.as_object().unwrap().keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, vec!["id", "message_type"]);
+    }
+    assert_eq!(result.data[0]["messages"]["pagination"]["total"], 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_disallowed_nested_field_is_rejected() -> Result<()> {
+    let params = QueryParams::from_query_string("include=messages&fields[messages]=id,secret_key")?;
+
+    let error = Conversation::from_params(params).unwrap_err();
+    assert_eq!(QueryParamsError::status_code(&error), StatusCode::BAD_REQUEST);
+    assert!(matches!(
+        error.downcast_ref::<QueryParamsError>(),
+        Some(QueryParamsError::IncludeFieldNotAllowed { relation, field }) if relation == "messages" && field == "secret_key"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_nested_fields_are_parsed_from_query_string() -> Result<()> {
+    let params = QueryParams::from_query_string("include=messages&fields[messages]=id, sent_at")?;
+    assert_eq!(params.get_fields("messages"), Some(vec!["id".to_string(), "sent_at".to_string()]));
+    Ok(())
+}
+
+#[test]
+fn test_includable_relation_fields_are_checked() -> Result<()> {
+    let params = QueryParams::from_query_string("include=positions&fields[positions]=id,name")?;
+    let builder = Organization::from_params(params)?;
+    let positions = builder.get_includes().iter().find(|include| include.relation == "positions").unwrap();
+    assert_eq!(positions.fields, Some(vec!["id".to_string(), "name".to_string()]));
+
+    let params = QueryParams::from_query_string("include=positions&fields[positions]=id,password")?;
+    let error = Organization::from_params(params).unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<QueryParamsError>(),
+        Some(QueryParamsError::IncludeFieldNotAllowed { relation, field }) if relation == "positions" && field == "password"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_includable_rows_are_restricted_to_selected_fields() {
+    let include = Include::new("positions").with_fields(vec!["id", "name"]);
+    let mut data = json!({
+        "org-1": [{ "id": "p1", "name": "Lead", "min_salary": 10 }],
+        "org-2": { "id": "p2", "name": "Clerk", "qualifications": "none" },
+    });
+
+    include.restrict_fields(&mut data);
+
+    assert_eq!(data, json!({
+        "org-1": [{ "id": "p1", "name": "Lead" }],
+        "org-2": { "id": "p2", "name": "Clerk" },
+    }));
+}
@@ -0,0 +1,58 @@
+//! Include Limit Tests
+//!
+//! These tests verify that includes nested deeper than the configured depth,
+//! or more includes than the configured count, are rejected with a 400 while
+//! the allowed-include validation keeps dropping unknown relationships.
+
+use axum::http::StatusCode;
+use rustaxum::app::models::country::Country;
+use rustaxum::app::query_builder::{QueryBuilderExt, QueryParams, QueryParamsError};
+use rustaxum::config::query_builder::QueryBuilderConfig;
+
+fn params(include: &str) -> QueryParams {
+    QueryParams {
+        include: Some(include.to_string()),
+        ..QueryParams::default()
+    }
+}
+
+/// An include `depth` relationships deep rooted at `createdBy`
+fn nested(depth: usize) -> String {
+    std::iter::once("createdBy".to_string())
+        .chain((1..depth).map(|level| format!("level{}", level)))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[test]
+fn test_over_deep_include_is_rejected() {
+    let error = params("createdBy.organizations.position.level").validate_includes(3, 10).unwrap_err();
+    assert!(matches!(error, QueryParamsError::IncludeTooDeep { depth: 4, max: 3, .. }));
+    assert_eq!(QueryParamsError::status_code(&error.into()), StatusCode::BAD_REQUEST);
+
+    assert!(params("createdBy.organizations.position").validate_includes(3, 10).is_ok());
+
+    let max_depth = QueryBuilderConfig::global().max_include_depth;
+    let error = params(&nested(max_depth + 1)).parse_includes(&["createdBy"]).unwrap_err();
+    assert!(matches!(error, QueryParamsError::IncludeTooDeep { .. }));
+    assert_eq!(params(&nested(max_depth)).parse_includes(&["createdBy"]).unwrap().len(), 1);
+}
+
+#[test]
+fn test_over_count_include_is_rejected() {
+    let error = params("a,b,c").validate_includes(3, 2).unwrap_err();
+    assert!(matches!(error, QueryParamsError::TooManyIncludes { count: 3, max: 2 }));
+    assert_eq!(QueryParamsError::status_code(&error.into()), StatusCode::BAD_REQUEST);
+
+    let max_includes = QueryBuilderConfig::global().max_includes;
+    let includes: Vec<String> = (0..=max_includes).map(|n| format!("relation{}", n)).collect();
+    let error = Country::from_params(params(&includes.join(","))).unwrap_err();
+    assert_eq!(QueryParamsError::status_code(&error), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn test_unknown_includes_are_still_dropped() {
+    let includes = params("createdBy,secrets").parse_includes(&["createdBy"]).unwrap();
+    assert_eq!(includes.len(), 1);
+    assert_eq!(includes[0].relation, "createdBy");
+}
@@ -0,0 +1,121 @@
+//! Include Pagination Integration Tests
+//!
+//! These tests verify that eager-loaded has-many relationships are loaded a
+//! page at a time, with relationship-level pagination meta next to the data,
+//! and that `GET /api/conversations` reads `page[...]`/`per_page[...]` and
+//! lists only the user's own conversations.
+
+mod common;
+
+use anyhow::Result;
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use rustaxum::app::models::conversation::Conversation;
+use rustaxum::app::models::user::User;
+use rustaxum::app::query_builder::{QueryBuilderExt, QueryExecutor, QueryParams, MAX_INCLUDE_PER_PAGE};
+use rustaxum::app::services::auth_service::AuthService;
+use rustaxum::database::DbPool;
+use serde_json::Value;
+use serial_test::serial;
+use tower::ServiceExt;
+
+fn params_for(conversation: &Conversation, query: &str) -> Result<QueryParams> {
+    let mut params = QueryParams::from_query_string(query)?;
+    params.filter.insert("id".to_string(), serde_json::json!(conversation.id.to_string()));
+    Ok(params)
+}
+
+async fn get(pool: &DbPool, uri: &str, user: &User) -> Result<(StatusCode, Value)> {
+    let app = rustaxum::routes::api::routes().with_state(pool.clone());
+    let token = AuthService::generate_access_token(&user.id.to_string(), 3600)?;
+    let request = Request::builder()
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())?;
+
+    let response = app.oneshot(request).await?;
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok((status, serde_json::from_slice(&body).unwrap_or(Value::Null)))
+}
+
+#[tokio::test]
+#[serial]
+async fn test_paginated_include_loads_only_first_page() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let device = common::create_device(&pool, &user)?;
+    let conversation = common::create_conversation(&pool, &user)?;
+    for _ in 0..12 {
+        common::create_message(&pool, &conversation, &user, &device)?;
+    }
+
+    let params = params_for(&conversation, "include=messages&page[messages]=1&per_page[messages]=5")?;
+    let mut conn = pool.get()?;
+    let result = QueryExecutor::execute_paginated(Conversation::from_params(params)?, &mut conn)?;
+
+    assert_eq!(result.data.len(), 1);
+    let messages = &result.data[0]["messages"];
+    assert_eq!(messages["data"].as_array().map(Vec::len), Some(5));
+    assert_eq!(messages["pagination"]["total"], 12);
+    assert_eq!(messages["pagination"]["per_page"], 5);
+    assert_eq!(messages["pagination"]["total_pages"], 3);
+    assert!(messages["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|m| m["conversation_id"] == conversation.id.to_string()));
+
+    // The last page holds the remainder
+    let params = params_for(&conversation, "include=messages&page[messages]=3&per_page[messages]=5")?;
+    let result = QueryExecutor::execute_paginated(Conversation::from_params(params)?, &mut conn)?;
+    assert_eq!(result.data[0]["messages"]["data"].as_array().map(Vec::len), Some(2));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_conversation_index_pages_included_messages() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let stranger = common::create_user(&pool)?;
+    let device = common::create_device(&pool, &user)?;
+    let conversation = common::create_conversation(&pool, &user)?;
+    common::add_participant(&pool, &conversation, &user)?;
+    let other = common::create_conversation(&pool, &stranger)?;
+    common::add_participant(&pool, &other, &stranger)?;
+    for _ in 0..7 {
+        common::create_message(&pool, &conversation, &user, &device)?;
+    }
+
+    let (status, body) = get(&pool, "/api/conversations?include=messages&page[messages]=2&per_page[messages]=5", &user).await?;
+    assert_eq!(status, StatusCode::OK);
+    let rows = body["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["id"], conversation.id.to_string());
+    assert_eq!(rows[0]["messages"]["data"].as_array().map(Vec::len), Some(2));
+    assert_eq!(rows[0]["messages"]["pagination"]["total"], 7);
+
+    let (status, body) = get(&pool, "/api/conversations", &common::create_user(&pool)?).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().map(Vec::len), Some(0));
+
+    Ok(())
+}
+
+#[test]
+fn test_include_pagination_is_parsed_and_capped() -> Result<()> {
+    let params = QueryParams::from_query_string("include=messages&page[messages]=2&per_page[messages]=500&page=3")?;
+
+    assert_eq!(params.page, Some(3));
+    let pagination = params.get_include_pagination("messages");
+    assert_eq!(pagination.page, 2);
+    assert_eq!(pagination.per_page, MAX_INCLUDE_PER_PAGE);
+
+    let defaults = QueryParams::default().get_include_pagination("messages");
+    assert_eq!(defaults.page, 1);
+    assert_eq!(defaults.per_page, 20);
+
+    Ok(())
+}
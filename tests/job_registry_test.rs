@@ -0,0 +1,105 @@
+//! Job Registry Tests
+//!
+//! These tests verify that `JobRegistry` rebuilds registered job types from
+//! the name and payload they were queued with, that unknown names and broken
+//! payloads are errors, and that a worker using the app's registry runs a job
+//! pushed through a `JobDispatcher` to completion. They also check that a
+//! `queue:work` process can run the listeners `register_listeners` queues.
+
+use anyhow::Result;
+use rustaxum::app::events::queued_listener::queued_listener;
+use rustaxum::app::events::{EventDispatcher, EventListener};
+use rustaxum::app::jobs::call_queued_listener_job::CallQueuedListenerJob;
+use rustaxum::app::jobs::job_registry::JobRegistry;
+use rustaxum::app::jobs::process_payment_job::ProcessPaymentJob;
+use rustaxum::app::jobs::queue_worker::{QueueWorker, WorkerConfig};
+use rustaxum::app::jobs::send_email_job::SendEmailJob;
+use rustaxum::app::jobs::{FakeQueueDriver, Job, JobDispatcher, JobMetadata, JobStatus, MemoryQueueDriver, NamedJob, QueueDriver};
+use rustaxum::app::listeners::send_welcome_email_listener::SendWelcomeEmailListener;
+use rustaxum::app::providers::{event_service_provider, job_service_provider};
+use std::sync::Arc;
+
+fn payment() -> ProcessPaymentJob {
+    ProcessPaymentJob::new("pay_1".to_string(), 49.5, "USD".to_string(), "cus_1".to_string())
+}
+
+#[tokio::test]
+async fn test_registered_jobs_round_trip_through_their_payload() -> Result<()> {
+    let registry = job_service_provider::job_registry();
+    assert!(registry.contains(ProcessPaymentJob::JOB_NAME));
+    assert!(registry.contains(SendEmailJob::JOB_NAME));
+
+    let job = registry.deserialize("ProcessPaymentJob", &Job::serialize(&payment())?)?;
+    assert_eq!(job.job_name(), "ProcessPaymentJob");
+    assert_eq!(job.queue_name(), "payments");
+    job.handle().await?;
+
+    let email = SendEmailJob::password_reset("ada@example.com".to_string(), "Ada".to_string(), "token".to_string());
+    let job = registry.deserialize("SendEmailJob", &Job::serialize(&email)?)?;
+    assert_eq!(job.job_name(), "SendEmailJob");
+    assert_eq!(job.priority(), email.priority());
+    assert_eq!(Job::serialize(&*job)?, Job::serialize(&email)?);
+    Ok(())
+}
+
+#[test]
+fn test_unknown_job_and_broken_payload_are_errors() {
+    let mut registry = JobRegistry::new();
+    registry.register::<ProcessPaymentJob>();
+
+    let unknown = registry.deserialize("ChargeCardJob", "{}").expect_err("not registered");
+    assert!(unknown.to_string().contains("ChargeCardJob"));
+
+    let broken = registry.deserialize("ProcessPaymentJob", "{\"payment_id\":1}").expect_err("payload does not match");
+    assert!(broken.to_string().contains("ProcessPaymentJob"));
+    assert_eq!(registry.job_names(), vec!["ProcessPaymentJob"]);
+}
+
+#[tokio::test]
+async fn test_worker_runs_a_dispatched_job_from_the_registry() -> Result<()> {
+    let driver = FakeQueueDriver::recording(MemoryQueueDriver::new());
+    let dispatcher = JobDispatcher::new(Box::new(driver.clone()));
+    let job_id = dispatcher.dispatch(&payment()).await?;
+
+    let worker = QueueWorker::with_registry(
+        WorkerConfig { queue_name: "payments".to_string(), ..Default::default() },
+        Arc::new(driver.clone()),
+        job_service_provider::job_registry(),
+    );
+    assert!(worker.work_once().await?);
+
+    assert_eq!(driver.job(&job_id).await.map(|job| job.status), Some(JobStatus::Completed));
+    assert_eq!(worker.get_stats().await.jobs_succeeded, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_worker_fails_jobs_it_cannot_rebuild() -> Result<()> {
+    let driver = MemoryQueueDriver::new();
+    let metadata = JobMetadata::new("ChargeCardJob".to_string(), "payments".to_string(), "{}".to_string(), 0, 3);
+    driver.push(metadata).await?;
+
+    let worker = QueueWorker::with_registry(
+        WorkerConfig { queue_name: "payments".to_string(), ..Default::default() },
+        Arc::new(driver.clone()),
+        job_service_provider::job_registry(),
+    );
+    assert!(worker.work_once().await?);
+
+    let failed = driver.failed_jobs(None).await?;
+    assert_eq!(failed.len(), 1);
+    assert!(failed[0].error_message.as_deref().unwrap_or_default().contains("No factory registered"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_worker_registry_resolves_registered_queued_listeners() -> Result<()> {
+    assert!(job_service_provider::job_registry().contains(CallQueuedListenerJob::JOB_NAME));
+
+    event_service_provider::register_listeners(&EventDispatcher::new()).await;
+
+    let name = SendWelcomeEmailListener::new().listener_name();
+    let listener = queued_listener(name).expect("welcome email listener is registered for workers");
+    assert!(listener.should_queue());
+    Ok(())
+}
@@ -0,0 +1,170 @@
+//! Job and Listener Tracing Span Tests
+//!
+//! These tests verify that logs emitted while a queued job runs carry the
+//! job's name and id and the correlation ID captured when it was dispatched,
+//! and that logs from event listeners carry the event name and correlation ID.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rustaxum::app::events::{Event, EventDispatcher, EventListener};
+use rustaxum::app::http::middleware::correlation_middleware::{current_correlation_id, with_correlation_id};
+use rustaxum::app::jobs::queue_worker::{QueueWorker, SimpleJobFactory, WorkerConfig};
+use rustaxum::app::jobs::{Job, JobMetadata, MemoryQueueDriver, QueueDriver};
+use rustaxum::app::models::DieselUlid;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+type Fields = HashMap<String, String>;
+type Logs = Arc<Mutex<Vec<Fields>>>;
+
+struct FieldVisitor<'a>(&'a mut Fields);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+/// Keeps every event's fields merged with the fields of the spans it was emitted in
+struct SpanFieldsLayer(Logs);
+
+impl<S> Layer<S> for SpanFieldsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = Fields::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(fields);
+        }
+    }
+
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = Fields::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if let Some(span_fields) = span.extensions().get::<Fields>() {
+                    fields.extend(span_fields.clone());
+                }
+            }
+        }
+        event.record(&mut FieldVisitor(&mut fields));
+        self.0.lock().unwrap().push(fields);
+    }
+}
+
+fn capture_logs() -> (Logs, tracing::subscriber::DefaultGuard) {
+    let logs = Logs::default();
+    let subscriber = tracing_subscriber::registry().with(SpanFieldsLayer(logs.clone()));
+    (logs, tracing::subscriber::set_default(subscriber))
+}
+
+fn logs_marked(logs: &Logs, marker: &str) -> Vec<Fields> {
+    logs.lock().unwrap().iter().filter(|fields| fields.get("marker").map(String::as_str) == Some(marker)).cloned().collect()
+}
+
+/// Logs a marked line and the correlation ID it sees while running
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TracedJob;
+
+#[async_trait]
+impl Job for TracedJob {
+    fn job_name(&self) -> &'static str {
+        "TracedJob"
+    }
+
+    async fn handle(&self) -> Result<()> {
+        let seen = current_correlation_id().map(|id| id.to_string()).unwrap_or_default();
+        tracing::info!(marker = "job", seen = %seen, "handling traced job");
+        Ok(())
+    }
+
+    fn serialize(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+#[derive(Debug)]
+struct OrderShipped;
+
+impl Event for OrderShipped {
+    fn event_name(&self) -> &'static str {
+        "order.shipped"
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({})
+    }
+}
+
+struct TracedListener;
+
+#[async_trait]
+impl EventListener for TracedListener {
+    async fn handle(&self, _event: Arc<dyn Event>) -> Result<()> {
+        tracing::info!(marker = "listener", "handling order.shipped");
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_job_logs_carry_dispatching_correlation_id() -> Result<()> {
+    let (logs, _guard) = capture_logs();
+    let correlation_id = DieselUlid::new();
+
+    // Dispatched while serving a request
+    let job = TracedJob;
+    let metadata = with_correlation_id(correlation_id, async {
+        JobMetadata::new(job.job_name().to_string(), "default".to_string(), job.serialize().unwrap(), 0, 1)
+    }).await;
+    assert_eq!(metadata.correlation_id, Some(correlation_id.to_string()));
+
+    // The correlation ID survives the trip through the queue's serialized metadata
+    let metadata: JobMetadata = serde_json::from_str(&serde_json::to_string(&metadata)?)?;
+    let job_id = metadata.id.clone();
+
+    let driver: Arc<dyn QueueDriver> = Arc::new(MemoryQueueDriver::new());
+    driver.push(metadata).await?;
+
+    // Processed later, outside of any request
+    let worker = QueueWorker::new(WorkerConfig::default(), driver);
+    worker.register_job::<TracedJob>("TracedJob", SimpleJobFactory::<TracedJob>::new()).await;
+    assert!(current_correlation_id().is_none());
+    assert!(worker.work_once().await?);
+
+    let job_logs = logs_marked(&logs, "job");
+    assert_eq!(job_logs.len(), 1);
+    assert_eq!(job_logs[0]["correlation_id"], correlation_id.to_string());
+    assert_eq!(job_logs[0]["job_name"], "TracedJob");
+    assert_eq!(job_logs[0]["job_id"], job_id);
+    assert_eq!(job_logs[0]["seen"], correlation_id.to_string());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_listener_logs_carry_event_name_and_correlation_id() -> Result<()> {
+    let (logs, _guard) = capture_logs();
+    let correlation_id = DieselUlid::new();
+
+    let dispatcher = EventDispatcher::new();
+    dispatcher.listen_pattern("order.*", Arc::new(TracedListener)).await;
+    with_correlation_id(correlation_id, dispatcher.dispatch(Arc::new(OrderShipped))).await?;
+
+    let listener_logs = logs_marked(&logs, "listener");
+    assert_eq!(listener_logs.len(), 1);
+    assert_eq!(listener_logs[0]["event_name"], "order.shipped");
+    assert_eq!(listener_logs[0]["correlation_id"], correlation_id.to_string());
+    Ok(())
+}
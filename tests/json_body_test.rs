@@ -0,0 +1,161 @@
+//! JSON Body Tests
+//!
+//! These tests verify that request bodies failing to deserialize are answered
+//! with 422 and the validation envelope, naming the offending field by its dot
+//! path and the type it expected, and that form requests can override those
+//! messages.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::Json,
+    routing::post,
+    Router,
+};
+use rustaxum::app::http::form_request::FormRequest;
+use rustaxum::app::http::JsonBody;
+use rustaxum::app::validation::ValidationRules;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tower::ServiceExt;
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct OrderLine {
+    sku: String,
+    price: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct CreateOrder {
+    name: String,
+    quantity: u32,
+    #[serde(default)]
+    lines: Vec<OrderLine>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CreateTagRequest {
+    name: String,
+    weight: i32,
+}
+
+#[async_trait]
+impl FormRequest for CreateTagRequest {
+    fn rules() -> ValidationRules {
+        rustaxum::validation_rules! {
+            "name" => ["required", "string"]
+        }
+    }
+
+    fn messages() -> HashMap<&'static str, &'static str> {
+        HashMap::from([("weight.integer", "Weight must be a whole number.")])
+    }
+}
+
+rustaxum::impl_form_request_extractor!(CreateTagRequest);
+
+fn app() -> Router {
+    Router::new()
+        .route("/orders", post(|JsonBody(order): JsonBody<CreateOrder>| async move {
+            Json(json!({ "name": order.name, "quantity": order.quantity }))
+        }))
+        .route("/tags", post(|request: CreateTagRequest| async move {
+            Json(json!({ "name": request.name, "weight": request.weight }))
+        }))
+}
+
+async fn send(uri: &str, content_type: &str, body: &str) -> Result<(StatusCode, Value)> {
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header(header::CONTENT_TYPE, content_type)
+        .body(Body::from(body.to_string()))?;
+
+    let response = app().oneshot(request).await?;
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok((status, serde_json::from_slice(&body)?))
+}
+
+async fn post_json(uri: &str, body: Value) -> Result<(StatusCode, Value)> {
+    send(uri, "application/json", &body.to_string()).await
+}
+
+#[tokio::test]
+async fn test_type_mismatch_names_field_and_expected_type() -> Result<()> {
+    let (status, body) = post_json("/orders", json!({ "name": "Widgets", "quantity": "three" })).await?;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"]["quantity"]["integer"], "The quantity must be an integer, got string \"three\".");
+    assert_eq!(body["message"], body["errors"]["quantity"]["integer"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_missing_required_field() -> Result<()> {
+    let (status, body) = post_json("/orders", json!({ "quantity": 3 })).await?;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"]["name"]["required"], "name is required.");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_nested_fields_are_keyed_by_dot_path() -> Result<()> {
+    let (status, body) = post_json("/orders", json!({
+        "name": "Widgets",
+        "quantity": 3,
+        "lines": [
+            { "sku": "A-1", "price": 9.5 },
+            { "sku": "A-2", "price": "cheap" }
+        ]
+    })).await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"]["lines.1.price"]["numeric"], "The lines.1.price must be a number, got string \"cheap\".");
+
+    let (status, body) = post_json("/orders", json!({
+        "name": "Widgets",
+        "quantity": 3,
+        "lines": [{ "price": 9.5 }]
+    })).await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"]["lines.0.sku"]["required"], "lines.0.sku is required.");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_valid_body_is_extracted() -> Result<()> {
+    let (status, body) = post_json("/orders", json!({ "name": "Widgets", "quantity": 3 })).await?;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "name": "Widgets", "quantity": 3 }));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_malformed_json_and_wrong_content_type() -> Result<()> {
+    let (status, body) = send("/orders", "application/json", "{\"name\": ").await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["message"], "Invalid JSON format.");
+
+    let (status, _) = send("/orders", "text/plain", "{}").await?;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_form_request_messages_override_defaults() -> Result<()> {
+    let (status, body) = post_json("/tags", json!({ "name": "urgent", "weight": 1.5 })).await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"]["weight"]["integer"], "Weight must be a whole number.");
+
+    let (status, body) = post_json("/tags", json!({ "weight": 1 })).await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"]["name"]["required"], "name is required.");
+    Ok(())
+}
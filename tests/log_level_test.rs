@@ -0,0 +1,83 @@
+//! Runtime Log Level Tests
+//!
+//! These tests verify that directives applied through the reload handle
+//! change which events reach the subscriber, that reset restores the
+//! startup filter, and that an invalid startup filter falls back to the
+//! default instead of failing.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use rustaxum::logging::level::LogLevelError;
+use rustaxum::logging::LogLevelFilter;
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::Layer;
+
+/// Counts every event that passes the filter
+struct CountingLayer(Arc<AtomicUsize>);
+
+impl<S: Subscriber> Layer<S> for CountingLayer {
+    fn on_event(&self, _event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_reload_handle_changes_effective_level() {
+    let (filter, level) = LogLevelFilter::new("info").unwrap();
+    let count = Arc::new(AtomicUsize::new(0));
+    let subscriber = tracing_subscriber::registry()
+        .with(filter)
+        .with(CountingLayer(count.clone()));
+
+    tracing::subscriber::with_default(subscriber, || {
+        let emit = || {
+            count.store(0, Ordering::SeqCst);
+            tracing::debug!(target: "log_level_test", "debug event");
+            tracing::debug!(target: "other_target", "debug event");
+            count.load(Ordering::SeqCst)
+        };
+
+        assert_eq!(emit(), 0, "debug events are filtered at the startup level");
+
+        let status = level.set("log_level_test=debug").unwrap();
+        assert_eq!(status.overrides, vec!["log_level_test=debug".to_string()]);
+        assert_eq!(emit(), 1, "only the overridden target is lowered to debug");
+
+        // A second directive for the same target replaces the first
+        let status = level.set("log_level_test=warn").unwrap();
+        assert_eq!(status.overrides, vec!["log_level_test=warn".to_string()]);
+        assert_eq!(emit(), 0);
+
+        level.set("debug").unwrap();
+        level.set("log_level_test=debug").unwrap();
+        assert_eq!(emit(), 2);
+
+        let status = level.reset().unwrap();
+        assert!(status.overrides.is_empty());
+        assert_eq!(status.startup, "info");
+        assert_eq!(emit(), 0, "reset restores the startup filter");
+    });
+}
+
+#[test]
+fn test_invalid_directive_is_rejected() {
+    let (_filter, level) = LogLevelFilter::new("info").unwrap();
+
+    let error = level.set("log_level_test=loud").expect_err("unknown levels are rejected");
+    assert_eq!(LogLevelError::status_code(&error), StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(level.status().overrides.is_empty(), "a rejected directive is not persisted");
+}
+
+#[test]
+fn test_invalid_startup_directives_fall_back() {
+    assert!(LogLevelFilter::new("rustaxum=loud").is_err());
+
+    let (_filter, level) = LogLevelFilter::with_fallback("rustaxum=loud", "info").unwrap();
+    assert_eq!(level.status().startup, "info");
+
+    let (_filter, level) = LogLevelFilter::with_fallback("rustaxum=debug", "info").unwrap();
+    assert_eq!(level.status().startup, "rustaxum=debug");
+}
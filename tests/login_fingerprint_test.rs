@@ -0,0 +1,209 @@
+//! Login Fingerprint Integration Tests
+//!
+//! These tests verify that logins from recognized clients go straight
+//! through while logins from new clients must be confirmed first, both
+//! through the API and through the web confirm-device form, and that the
+//! recorded IP is the trusted-proxy client address rather than a raw
+//! forwarding header.
+
+mod common;
+
+use anyhow::Result;
+use axum::extract::{Extension, Form, State};
+use axum::http::{header, HeaderMap, HeaderValue};
+use axum::response::IntoResponse;
+use diesel::prelude::*;
+use rustaxum::app::http::controllers::web_auth_controller::{self, ConfirmDeviceForm, LoginForm};
+use rustaxum::app::models::user::{LoginRequest, User};
+use rustaxum::app::services::auth_service::{AuthService, LoginResponse};
+use rustaxum::app::services::login_fingerprint_service::{ClientFingerprint, FingerprintCheck, LoginFingerprintService};
+use rustaxum::app::services::session::{SessionManager, SessionStore};
+use rustaxum::config::session::SessionConfig;
+use rustaxum::config::auth::NewDeviceChallenge;
+use rustaxum::database::DbPool;
+use rustaxum::schema::{mfa_email_codes, sys_users};
+use serial_test::serial;
+
+const PASSWORD: &str = "correct horse battery staple";
+
+fn create_user_with_password(pool: &DbPool) -> Result<User> {
+    let user = common::create_user(pool)?;
+
+    let mut conn = pool.get()?;
+    let user = diesel::update(sys_users::table.find(user.id.to_string()))
+        .set(sys_users::password.eq(AuthService::hash_password(PASSWORD)?))
+        .get_result::<User>(&mut conn)?;
+    Ok(user)
+}
+
+fn client_headers(user_agent: &'static str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("user-agent", HeaderValue::from_static(user_agent));
+    headers.insert("accept-language", HeaderValue::from_static("en-US,en;q=0.9"));
+    headers
+}
+
+fn client(user_agent: &'static str) -> ClientFingerprint {
+    ClientFingerprint::from_headers(&client_headers(user_agent), None)
+}
+
+async fn array_session() -> Result<SessionStore> {
+    let mut config = SessionConfig::from_env()?;
+    config.driver = "array".to_string();
+    let session = SessionStore::new(SessionManager::new(config.clone(), None, None).await?, config);
+    session.start(None).await?;
+    Ok(session)
+}
+
+fn location(response: impl IntoResponse) -> String {
+    let response = response.into_response();
+    assert!(response.status().is_redirection());
+    response.headers()[header::LOCATION].to_str().unwrap().to_string()
+}
+
+fn latest_email_code(pool: &DbPool, user: &User) -> Result<String> {
+    let mut conn = pool.get()?;
+    Ok(mfa_email_codes::table
+        .filter(mfa_email_codes::user_id.eq(user.id.to_string()))
+        .filter(mfa_email_codes::is_used.eq(false))
+        .order(mfa_email_codes::created_at.desc())
+        .select(mfa_email_codes::code)
+        .first::<String>(&mut conn)?)
+}
+
+fn credentials(user: &User) -> LoginRequest {
+    LoginRequest {
+        email: user.email.clone(),
+        password: PASSWORD.to_string(),
+    }
+}
+
+#[test]
+fn test_fingerprint_depends_on_client_headers() {
+    let laptop = client("Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0");
+    assert_eq!(laptop.hash, client("Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0").hash);
+    assert_ne!(laptop.hash, client("Mozilla/5.0 (iPhone) Safari/604.1").hash);
+    assert_eq!(laptop.hash.len(), 64);
+}
+
+#[test]
+fn test_fingerprint_ip_comes_from_the_resolved_client() {
+    let mut headers = client_headers("Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0");
+    headers.insert("x-forwarded-for", HeaderValue::from_static("203.0.113.7"));
+    headers.insert("x-real-ip", HeaderValue::from_static("203.0.113.8"));
+
+    assert_eq!(ClientFingerprint::from_headers(&headers, None).ip_address, None);
+
+    let resolved = ClientFingerprint::from_headers(&headers, Some("198.51.100.4".parse().unwrap()));
+    assert_eq!(resolved.ip_address.as_deref(), Some("198.51.100.4"));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_known_device_login_is_not_challenged() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = create_user_with_password(&pool)?;
+    let laptop = client("Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0");
+
+    // The first client a user logs in from is recognized from then on
+    for _ in 0..2 {
+        let response = AuthService::login(&pool, credentials(&user), &laptop).await?;
+        assert!(matches!(response, LoginResponse::Success(_)));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_new_device_login_requires_confirmation() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = create_user_with_password(&pool)?;
+    let laptop = client("Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0");
+    let phone = client("Mozilla/5.0 (iPhone) Safari/604.1");
+
+    AuthService::login(&pool, credentials(&user), &laptop).await?;
+
+    let challenge = match AuthService::login(&pool, credentials(&user), &phone).await? {
+        LoginResponse::DeviceConfirmationRequired(challenge) => challenge,
+        _ => panic!("a login from a new device must be confirmed"),
+    };
+    assert!(challenge.requires_device_confirmation);
+    assert_eq!(challenge.methods, vec!["email".to_string()]);
+
+    let code = latest_email_code(&pool, &user)?;
+
+    AuthService::confirm_new_device(&pool, user.id.to_string(), &challenge.challenge_id, "000000x")
+        .await
+        .expect_err("a wrong code must not confirm the device");
+
+    let confirmed = AuthService::confirm_new_device(&pool, user.id.to_string(), &challenge.challenge_id, &code).await?;
+    assert_eq!(confirmed.user.id, user.id);
+
+    // Once confirmed the phone is recognized too
+    let response = AuthService::login(&pool, credentials(&user), &phone).await?;
+    assert!(matches!(response, LoginResponse::Success(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_challenge_strictness() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let laptop = client("Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0");
+
+    let user = create_user_with_password(&pool)?;
+    let strict = LoginFingerprintService::with_mode(NewDeviceChallenge::Strict);
+    assert!(matches!(strict.check(&pool, user.id, &laptop)?, FingerprintCheck::Unrecognized(_)));
+
+    let other = create_user_with_password(&pool)?;
+    let off = LoginFingerprintService::with_mode(NewDeviceChallenge::Off);
+    let phone = client("Mozilla/5.0 (iPhone) Safari/604.1");
+    assert!(matches!(off.check(&pool, other.id, &laptop)?, FingerprintCheck::Recognized(_)));
+    assert!(matches!(off.check(&pool, other.id, &phone)?, FingerprintCheck::Recognized(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_web_login_from_new_device_is_confirmed_through_the_form() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = create_user_with_password(&pool)?;
+    AuthService::login(&pool, credentials(&user), &client("Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0")).await?;
+
+    let session = array_session().await?;
+    let page = web_auth_controller::show_confirm_device(Extension(session.clone())).await;
+    assert_eq!(location(page), "/auth/login");
+
+    let form = LoginForm {
+        email: user.email.clone(),
+        password: PASSWORD.to_string(),
+        remember_me: None,
+        redirect: None,
+    };
+    let response = web_auth_controller::login(
+        State(pool.clone()),
+        Extension(session.clone()),
+        None,
+        client_headers("Mozilla/5.0 (iPhone) Safari/604.1"),
+        Form(form),
+    ).await;
+    assert_eq!(location(response), "/auth/confirm-device");
+    assert!(!session.get_bool("authenticated").await.unwrap_or(false));
+
+    let wrong = ConfirmDeviceForm { code: "000000x".to_string() };
+    let response = web_auth_controller::confirm_device(State(pool.clone()), Extension(session.clone()), Form(wrong)).await;
+    assert_eq!(location(response), "/auth/confirm-device");
+    assert!(!session.get_bool("authenticated").await.unwrap_or(false));
+
+    let code = ConfirmDeviceForm { code: latest_email_code(&pool, &user)? };
+    let response = web_auth_controller::confirm_device(State(pool.clone()), Extension(session.clone()), Form(code)).await;
+    assert_eq!(location(response), "/dashboard");
+    assert_eq!(session.get_bool("authenticated").await, Some(true));
+    assert_eq!(session.get_string("user_id").await, Some(user.id.to_string()));
+    assert!(session.get_string("device_confirmation_challenge_id").await.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Mail Attachment Tests
+//!
+//! These tests verify that every `AttachmentData` variant resolves to its raw
+//! bytes, with `Path` read through the storage module and `Base64` decoded,
+//! that malformed base64 is a descriptive error, and that `AttachmentPolicy`
+//! rejects disallowed MIME types and attachments over the size limit, both on
+//! its own and when the SMTP driver builds a message.
+
+use anyhow::Result;
+use rustaxum::app::mail::attachments::{AttachmentError, AttachmentPolicy};
+use rustaxum::app::mail::drivers::SmtpDriver;
+use rustaxum::app::mail::{Attachment, MailContent, MailMessage};
+
+fn policy(max_total_bytes: u64, allowed_types: &[&str]) -> AttachmentPolicy {
+    AttachmentPolicy {
+        max_total_bytes,
+        allowed_types: allowed_types.iter().map(|allowed| allowed.to_string()).collect(),
+    }
+}
+
+fn pdf(bytes: Vec<u8>) -> Attachment {
+    Attachment::from_bytes("invoice.pdf".to_string(), bytes, Some("application/pdf".to_string()))
+}
+
+#[tokio::test]
+async fn test_each_variant_resolves_to_its_bytes() -> Result<()> {
+    let path = std::env::temp_dir().join(format!("invoice-{}.txt", ulid::Ulid::new()));
+    std::fs::write(&path, b"from disk")?;
+
+    let attachments = vec![
+        Attachment::from_path("disk.txt".to_string(), path.to_string_lossy().to_string(), Some("text/plain".to_string())),
+        Attachment::from_bytes("memory.txt".to_string(), b"from memory".to_vec(), Some("text/plain".to_string())),
+        Attachment::from_base64("encoded.txt".to_string(), "ZnJvbSBiYXNlNjQ=".to_string(), Some("text/plain".to_string())),
+    ];
+
+    let resolved = AttachmentPolicy::default().resolve(&attachments).await?;
+    let contents: Vec<&[u8]> = resolved.iter().map(|attachment| attachment.bytes.as_slice()).collect();
+    assert_eq!(contents, vec![&b"from disk"[..], &b"from memory"[..], &b"from base64"[..]]);
+    assert_eq!(resolved[2].filename, "encoded.txt");
+
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_malformed_base64_is_a_validation_error() {
+    let attachment = Attachment::from_base64("scan.png".to_string(), "not base64!".to_string(), Some("image/png".to_string()));
+
+    let error = AttachmentPolicy::default().resolve(&[attachment]).await.expect_err("cannot decode");
+    assert!(matches!(&error, AttachmentError::InvalidBase64 { filename, .. } if filename == "scan.png"));
+    assert!(error.to_string().contains("'scan.png' is not valid base64"));
+}
+
+#[tokio::test]
+async fn test_missing_file_is_an_error() {
+    let attachment = Attachment::from_path("gone.pdf".to_string(), "/nonexistent/gone.pdf".to_string(), Some("application/pdf".to_string()));
+
+    let error = AttachmentPolicy::default().resolve(&[attachment]).await.expect_err("no such file");
+    assert!(matches!(error, AttachmentError::Unreadable { .. }));
+}
+
+#[tokio::test]
+async fn test_attachments_over_the_size_limit_are_rejected() {
+    let attachments = vec![pdf(vec![0; 600]), pdf(vec![0; 600])];
+
+    let error = policy(1000, &[]).resolve(&attachments).await.expect_err("1200 bytes is over the limit");
+    assert!(matches!(error, AttachmentError::TooLarge { total: 1200, limit: 1000 }));
+
+    assert!(policy(0, &[]).resolve(&attachments).await.is_ok(), "0 turns the limit off");
+    assert!(policy(1200, &[]).resolve(&attachments).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_disallowed_types_are_rejected() {
+    let policy = policy(0, &["application/pdf", "image/*"]);
+    assert!(policy.allows("image/png"));
+    assert!(policy.allows("Application/PDF; name=invoice.pdf"));
+    assert!(!policy.allows("application/x-msdownload"));
+
+    let installer = Attachment::from_bytes("setup.exe".to_string(), vec![0; 4], Some("application/x-msdownload".to_string()));
+    let error = policy.resolve(&[pdf(vec![1]), installer]).await.expect_err("executables are not allowed");
+    assert_eq!(error.to_string(), "Attachment 'setup.exe' has type 'application/x-msdownload', which is not allowed");
+}
+
+#[tokio::test]
+async fn test_smtp_driver_refuses_oversized_attachments() -> Result<()> {
+    let driver = SmtpDriver::new("localhost".to_string(), 1025, "Billing".to_string(), "billing@example.com".to_string())
+        .with_attachment_policy(policy(10, &["application/pdf"]));
+    let message = MailMessage::new()
+        .to("ada@example.com".to_string())
+        .subject("Your invoice".to_string())
+        .content(MailContent::Text("Attached.".to_string()))
+        .attach(pdf(vec![0; 11]));
+
+    let error = driver.build_email(message).await.expect_err("attachment is too large");
+    assert!(matches!(error.downcast_ref::<AttachmentError>(), Some(AttachmentError::TooLarge { .. })));
+    Ok(())
+}
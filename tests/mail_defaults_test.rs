@@ -0,0 +1,110 @@
+//! Mail Defaults Tests
+//!
+//! These tests verify that `MailManager::send` gives a mailable that sets no
+//! `from` or `reply_to` the configured defaults, that a driver's own defaults
+//! take precedence over the global ones, that an explicit `from` is kept, and
+//! that an invalid configured address is rejected when the config loads.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rustaxum::app::mail::{MailContent, MailManager, MailMessage, Mailable};
+use rustaxum::config::mail::{MailAddress, MailConfig, MailDefaults};
+use serial_test::serial;
+
+struct InvoiceMail {
+    from: Option<String>,
+}
+
+#[async_trait]
+impl Mailable for InvoiceMail {
+    async fn build(&self) -> Result<MailMessage> {
+        Ok(MailMessage::new()
+            .to("ada@example.com".to_string())
+            .subject("Your invoice".to_string())
+            .content(MailContent::Text("Thanks for your order.".to_string())))
+    }
+
+    fn from(&self) -> Option<String> {
+        self.from.clone()
+    }
+}
+
+fn config() -> Result<MailConfig> {
+    let mut config = MailConfig::from_env()?;
+    config.from_address = "noreply@example.com".to_string();
+    config.from_name = "Example".to_string();
+    config.reply_to = Some(MailAddress::new("support@example.com", None)?);
+    config.driver_defaults.clear();
+    Ok(config)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_mailable_without_from_gets_configured_default() -> Result<()> {
+    let mut manager = MailManager::new("smtp".to_string());
+    manager.set_defaults(&config()?);
+    let fake = manager.fake();
+
+    manager.send(&InvoiceMail { from: None }).await?;
+
+    let sent = fake.sent().await;
+    assert_eq!(sent[0].from.as_deref(), Some("Example <noreply@example.com>"));
+    assert_eq!(sent[0].reply_to.as_deref(), Some("support@example.com"));
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_explicit_from_is_kept() -> Result<()> {
+    let mut manager = MailManager::new("smtp".to_string());
+    manager.set_defaults(&config()?);
+    let fake = manager.fake();
+
+    manager.send(&InvoiceMail { from: Some("Billing <billing@example.com>".to_string()) }).await?;
+
+    let sent = fake.sent().await;
+    assert_eq!(sent[0].from.as_deref(), Some("Billing <billing@example.com>"));
+    assert_eq!(sent[0].reply_to.as_deref(), Some("support@example.com"));
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_driver_defaults_override_global_defaults() -> Result<()> {
+    let mut config = config()?;
+    config.driver_defaults.insert("log".to_string(), MailDefaults {
+        from: Some(MailAddress::new("debug@example.com", Some("Debug"))?),
+        reply_to: None,
+    });
+
+    let mut manager = MailManager::new("smtp".to_string());
+    manager.set_defaults(&config);
+    let fake = manager.fake();
+
+    manager.send_with_driver(&InvoiceMail { from: None }, "log").await?;
+    manager.send_with_driver(&InvoiceMail { from: None }, "smtp").await?;
+
+    let sent = fake.sent().await;
+    assert_eq!(sent[0].from.as_deref(), Some("Debug <debug@example.com>"));
+    assert_eq!(sent[0].reply_to.as_deref(), Some("support@example.com"));
+    assert_eq!(sent[1].from.as_deref(), Some("Example <noreply@example.com>"));
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_invalid_configured_address_fails_at_load() -> Result<()> {
+    assert!(MailAddress::new("not-an-email", None).is_err());
+
+    let original = std::env::var("MAIL_FROM_ADDRESS").ok();
+    std::env::set_var("MAIL_FROM_ADDRESS", "not-an-email");
+    let loaded = MailConfig::from_env();
+    match original {
+        Some(address) => std::env::set_var("MAIL_FROM_ADDRESS", address),
+        None => std::env::remove_var("MAIL_FROM_ADDRESS"),
+    }
+
+    let error = loaded.expect_err("invalid MAIL_FROM_ADDRESS is rejected");
+    assert!(error.to_string().contains("MAIL_FROM_ADDRESS"));
+    Ok(())
+}
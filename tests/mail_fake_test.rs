@@ -0,0 +1,90 @@
+//! Mail Fake Tests
+//!
+//! These tests verify that `MailFacade::fake()` records the messages a code
+//! path sends instead of handing them to a mail driver, that the send
+//! assertions see those messages, and that `restore` sends for real again.
+
+use anyhow::Result;
+use rustaxum::app::jobs::send_email_job::SendEmailJob;
+use rustaxum::app::jobs::Job;
+use rustaxum::app::mail::welcome_mail::WelcomeMail;
+use rustaxum::app::mail::{MailFacade, MailManager, MailMessage};
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn test_fake_records_welcome_email() -> Result<()> {
+    MailFacade::fake().await;
+    assert!(MailFacade::assert_nothing_sent().await);
+
+    // No SMTP driver is registered, so this only succeeds while faking
+    SendEmailJob::welcome("ada@example.com".to_string(), "Ada".to_string(), None).handle().await?;
+
+    assert!(MailFacade::assert_sent_to("ada@example.com").await);
+    assert!(MailFacade::assert_sent_to("ADA@example.com").await);
+    assert!(!MailFacade::assert_sent_to("grace@example.com").await);
+    assert!(MailFacade::assert_sent(|message| {
+        message.to == vec!["ada@example.com".to_string()] && message.subject == "Welcome, Ada!"
+    }).await);
+    assert!(MailFacade::assert_sent_times(|message| message.subject.starts_with("Welcome"), 1).await);
+    assert!(MailFacade::assert_not_sent(|message| message.subject.contains("Password")).await);
+    assert!(!MailFacade::assert_nothing_sent().await);
+
+    let sent = MailFacade::get_sent_messages().await;
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].subject, "Welcome, Ada!");
+
+    MailFacade::restore().await;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_fake_starts_empty_each_time() -> Result<()> {
+    MailFacade::fake().await;
+    rustaxum::app::mail::mail(&WelcomeMail::new("ada@example.com".to_string(), "Ada".to_string())).await?;
+    assert!(MailFacade::assert_sent_to("ada@example.com").await);
+
+    MailFacade::fake().await;
+    assert!(MailFacade::assert_nothing_sent().await);
+
+    MailFacade::restore().await;
+    assert!(!rustaxum::app::mail::is_faking().await);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_assertions_cover_cc_bcc_and_counts() -> Result<()> {
+    let mut manager = MailManager::new("smtp".to_string());
+    let fake = manager.fake();
+
+    for _ in 0..2 {
+        let message = MailMessage::new()
+            .to("ada@example.com".to_string())
+            .cc("grace@example.com".to_string())
+            .bcc("audit@example.com".to_string())
+            .subject("Invoice".to_string());
+        manager.send_message(message).await?;
+    }
+
+    assert!(fake.assert_sent_to("grace@example.com").await);
+    assert!(fake.assert_sent_to("audit@example.com").await);
+    assert!(fake.assert_sent_times(|message| message.subject == "Invoice", 2).await);
+    assert!(!fake.assert_sent_times(|message| message.subject == "Invoice", 1).await);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restore_sends_through_drivers_again() -> Result<()> {
+    let mut manager = MailManager::new("smtp".to_string());
+    manager.fake();
+    let welcome = WelcomeMail::new("ada@example.com".to_string(), "Ada".to_string());
+    manager.send_with_driver(&welcome, "ses").await?;
+    assert!(manager.assert_sent_to("ada@example.com").await);
+
+    manager.restore();
+    assert!(!manager.is_faking());
+    assert!(!manager.assert_sent_to("ada@example.com").await);
+    assert!(manager.send(&welcome).await.is_err(), "no smtp driver is registered");
+    Ok(())
+}
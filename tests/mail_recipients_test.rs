@@ -0,0 +1,93 @@
+//! Mail Recipients Tests
+//!
+//! These tests verify that the CC and BCC recipients a `Mailable` declares
+//! reach the `MailMessage` a driver receives, without duplicating recipients
+//! the built message already has, and that the SMTP driver delivers to BCC
+//! recipients through the envelope while leaving them out of the headers.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rustaxum::app::mail::drivers::{LogDriver, SmtpDriver};
+use rustaxum::app::mail::{MailContent, MailManager, MailMessage, Mailable};
+
+struct QuarterlyReportMail;
+
+#[async_trait]
+impl Mailable for QuarterlyReportMail {
+    async fn build(&self) -> Result<MailMessage> {
+        Ok(MailMessage::new()
+            .to("ceo@example.com".to_string())
+            .cc("finance@example.com".to_string())
+            .subject(self.subject())
+            .content(MailContent::Text("The Q3 report is attached.".to_string())))
+    }
+
+    fn to(&self) -> Vec<String> {
+        vec!["ceo@example.com".to_string()]
+    }
+
+    fn cc(&self) -> Vec<String> {
+        vec!["FINANCE@example.com".to_string(), "cfo@example.com".to_string()]
+    }
+
+    fn bcc(&self) -> Vec<String> {
+        vec!["audit@example.com".to_string()]
+    }
+
+    fn subject(&self) -> String {
+        "Q3 report".to_string()
+    }
+}
+
+#[tokio::test]
+async fn test_mailable_cc_and_bcc_reach_the_message() -> Result<()> {
+    let mut manager = MailManager::new("log".to_string());
+    let fake = manager.fake();
+
+    manager.send(&QuarterlyReportMail).await?;
+
+    let sent = fake.sent().await;
+    assert_eq!(sent[0].to, vec!["ceo@example.com"]);
+    assert_eq!(sent[0].cc, vec!["finance@example.com", "cfo@example.com"]);
+    assert_eq!(sent[0].bcc, vec!["audit@example.com"]);
+    assert!(fake.assert_sent_to("audit@example.com").await);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_log_driver_records_cc_and_bcc() -> Result<()> {
+    let log_path = std::env::temp_dir().join(format!("mail-{}.log", ulid::Ulid::new()));
+    let mut manager = MailManager::new("log".to_string());
+    manager.register_driver(
+        "log".to_string(),
+        Box::new(LogDriver::new().with_file(log_path.to_string_lossy().to_string())),
+    );
+
+    manager.send(&QuarterlyReportMail).await?;
+
+    let logged = std::fs::read_to_string(&log_path)?;
+    assert!(logged.contains(r#"CC: ["finance@example.com", "cfo@example.com"]"#), "logged: {}", logged);
+    assert!(logged.contains(r#"BCC: ["audit@example.com"]"#), "logged: {}", logged);
+
+    std::fs::remove_file(log_path)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_smtp_envelope_includes_bcc_but_headers_do_not() -> Result<()> {
+    let driver = SmtpDriver::new("localhost".to_string(), 1025, "Reports".to_string(), "reports@example.com".to_string());
+    let mut message = QuarterlyReportMail.build().await?;
+    message.add_recipients(Vec::new(), QuarterlyReportMail.cc(), QuarterlyReportMail.bcc());
+
+    let email = driver.build_email(message).await?;
+
+    let envelope: Vec<String> = email.envelope().to().iter().map(|address| address.to_string()).collect();
+    for recipient in ["ceo@example.com", "finance@example.com", "cfo@example.com", "audit@example.com"] {
+        assert!(envelope.contains(&recipient.to_string()), "{} missing from {:?}", recipient, envelope);
+    }
+
+    let headers = String::from_utf8(email.formatted())?;
+    assert!(headers.contains("Cc: finance@example.com, cfo@example.com"));
+    assert!(!headers.contains("audit@example.com"), "BCC recipient leaked into the headers");
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! Mail Template Tests
+//!
+//! These tests verify that `MailTemplates` renders a named Handlebars file
+//! with variables, `{{#if}}` and `{{#each}}`, escapes HTML in the data, keeps
+//! compiled templates cached until cleared, reports a missing file as an
+//! error, and that `MailContent::Template` renders through the global
+//! templates when a message is turned into HTML.
+
+use anyhow::Result;
+use rustaxum::app::mail::template::{init_mail_templates, MailTemplates};
+use rustaxum::app::mail::MailContent;
+use std::path::PathBuf;
+
+const RECEIPT: &str = "<h1>Hi {{name}}</h1>\
+{{#if vip}}<p>Thanks for being a VIP</p>{{/if}}\
+<ul>{{#each items}}<li>{{this.title}} x{{this.quantity}}</li>{{/each}}</ul>";
+
+fn templates_dir(templates: &[(&str, &str)]) -> Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("mail_templates_{}", ulid::Ulid::new()));
+    std::fs::create_dir_all(&dir)?;
+    for (name, source) in templates {
+        std::fs::write(dir.join(name), source)?;
+    }
+    Ok(dir)
+}
+
+fn receipt_data() -> serde_json::Value {
+    serde_json::json!({
+        "name": "Ada <Admin>",
+        "vip": true,
+        "items": [
+            { "title": "Keyboard", "quantity": 1 },
+            { "title": "Cable", "quantity": 3 },
+        ],
+    })
+}
+
+#[tokio::test]
+async fn test_renders_variables_conditionals_and_loops() -> Result<()> {
+    let dir = templates_dir(&[("receipt.hbs", RECEIPT)])?;
+    let templates = MailTemplates::new(&dir);
+
+    let html = templates.render("receipt", &receipt_data()).await?;
+    assert_eq!(
+        html,
+        "<h1>Hi Ada &lt;Admin&gt;</h1><p>Thanks for being a VIP</p><ul><li>Keyboard x1</li><li>Cable x3</li></ul>"
+    );
+
+    let html = templates.render("receipt", &serde_json::json!({ "name": "Grace", "items": [] })).await?;
+    assert_eq!(html, "<h1>Hi Grace</h1><ul></ul>");
+
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_compiled_template_is_cached_until_cleared() -> Result<()> {
+    let dir = templates_dir(&[("notice.hbs", "Hello {{name}}")])?;
+    let templates = MailTemplates::new(&dir);
+
+    assert!(!templates.is_cached("notice").await);
+    templates.render("notice", &serde_json::json!({ "name": "Ada" })).await?;
+    assert!(templates.is_cached("notice").await);
+
+    templates.clear().await;
+    assert!(!templates.is_cached("notice").await);
+
+    std::fs::write(dir.join("notice.hbs"), "Goodbye {{name}}")?;
+    let html = templates.render("notice", &serde_json::json!({ "name": "Ada" })).await?;
+    assert_eq!(html, "Goodbye Ada");
+
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_missing_template_is_a_clear_error() -> Result<()> {
+    let dir = templates_dir(&[])?;
+    let templates = MailTemplates::new(&dir);
+
+    let error = templates.render("password-changed", &serde_json::json!({})).await.expect_err("no such file");
+    assert!(error.to_string().contains("Mail template 'password-changed' not found"), "unexpected error: {}", error);
+
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_template_content_renders_through_the_global_templates() -> Result<()> {
+    let dir = templates_dir(&[("receipt.hbs", RECEIPT)])?;
+    init_mail_templates(dir.to_str().expect("temp dir is UTF-8")).await;
+
+    let mut content = MailContent::Template { name: "receipt".to_string(), data: receipt_data() };
+    let html = content.to_html().await?;
+
+    assert!(html.contains("<li>Cable x3</li>"));
+    assert!(content.to_text().contains("Thanks for being a VIP"));
+    Ok(())
+}
@@ -0,0 +1,122 @@
+//! Mailgun Driver Tests
+//!
+//! These tests verify that `MailgunDriver` maps a message's recipients,
+//! subject, HTML and text bodies, reply-to and attachments onto Mailgun's
+//! form fields, posts them to the domain's messages endpoint with the API key,
+//! and surfaces Mailgun's error body when a send is rejected.
+
+use anyhow::Result;
+use axum::{http::{HeaderMap, StatusCode}, routing::post, Router};
+use rustaxum::app::mail::attachments::{AttachmentError, AttachmentPolicy};
+use rustaxum::app::mail::drivers::MailgunDriver;
+use rustaxum::app::mail::{Attachment, MailContent, MailDriver, MailMessage};
+use std::sync::{Arc, Mutex};
+
+fn driver() -> MailgunDriver {
+    MailgunDriver::new(
+        "mg.example.com".to_string(),
+        "key-test".to_string(),
+        "Billing".to_string(),
+        "billing@example.com".to_string(),
+    )
+}
+
+fn invoice_message() -> MailMessage {
+    MailMessage::new()
+        .to("ada@example.com".to_string())
+        .to("grace@example.com".to_string())
+        .cc("finance@example.com".to_string())
+        .bcc("audit@example.com".to_string())
+        .subject("Your invoice".to_string())
+        .content(MailContent::Html("<p>Invoice &amp; receipt attached</p>".to_string()))
+}
+
+// Start a fake Mailgun API that records the Authorization header and answers with `status` and `body`
+async fn start_mailgun(status: StatusCode, body: &'static str) -> Result<(String, Arc<Mutex<Vec<String>>>)> {
+    let authorizations = Arc::new(Mutex::new(Vec::new()));
+    let recorded = authorizations.clone();
+    let app = Router::new().route("/v3/{domain}/messages", post(move |headers: HeaderMap| {
+        let recorded = recorded.clone();
+        async move {
+            if let Some(value) = headers.get("authorization").and_then(|value| value.to_str().ok()) {
+                recorded.lock().unwrap().push(value.to_string());
+            }
+            (status, body)
+        }
+    }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.ok();
+    });
+
+    Ok((format!("http://{}", addr), authorizations))
+}
+
+#[test]
+fn test_messages_url_uses_endpoint_and_domain() {
+    assert_eq!(driver().messages_url(), "https://api.mailgun.net/v3/mg.example.com/messages");
+
+    let eu = driver().with_endpoint("https://api.eu.mailgun.net/".to_string());
+    assert_eq!(eu.messages_url(), "https://api.eu.mailgun.net/v3/mg.example.com/messages");
+}
+
+#[tokio::test]
+async fn test_message_maps_onto_form_fields() -> Result<()> {
+    let message = invoice_message()
+        .reply_to("support@example.com".to_string())
+        .attach(Attachment::from_bytes("invoice.pdf".to_string(), b"%PDF".to_vec(), Some("application/pdf".to_string())));
+
+    let request = driver().build_request(message).await?;
+
+    assert_eq!(request.values("from"), vec!["Billing <billing@example.com>"]);
+    assert_eq!(request.values("to"), vec!["ada@example.com", "grace@example.com"]);
+    assert_eq!(request.values("cc"), vec!["finance@example.com"]);
+    assert_eq!(request.values("bcc"), vec!["audit@example.com"]);
+    assert_eq!(request.values("subject"), vec!["Your invoice"]);
+    assert_eq!(request.values("html"), vec!["<p>Invoice &amp; receipt attached</p>"]);
+    assert_eq!(request.values("text"), vec!["Invoice & receipt attached"]);
+    assert_eq!(request.values("h:Reply-To"), vec!["support@example.com"]);
+
+    assert_eq!(request.attachments.len(), 1);
+    assert_eq!(request.attachments[0].filename, "invoice.pdf");
+    assert_eq!(request.attachments[0].bytes, b"%PDF");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_attachment_policy_applies_before_sending() {
+    let driver = driver().with_attachment_policy(AttachmentPolicy {
+        max_total_bytes: 0,
+        allowed_types: vec!["application/pdf".to_string()],
+    });
+    let message = invoice_message()
+        .attach(Attachment::from_bytes("setup.exe".to_string(), vec![0; 4], Some("application/x-msdownload".to_string())));
+
+    let error = driver.build_request(message).await.expect_err("executables are not allowed");
+    assert!(matches!(error.downcast_ref::<AttachmentError>(), Some(AttachmentError::DisallowedType { .. })));
+}
+
+#[tokio::test]
+async fn test_send_posts_with_the_api_key() -> Result<()> {
+    let (endpoint, authorizations) = start_mailgun(StatusCode::OK, r#"{"id":"<1@mg.example.com>","message":"Queued. Thank you."}"#).await?;
+
+    driver().with_endpoint(endpoint).send(invoice_message()).await?;
+
+    // base64("api:key-test")
+    assert_eq!(*authorizations.lock().unwrap(), vec!["Basic YXBpOmtleS10ZXN0".to_string()]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rejected_send_surfaces_mailgun_error() -> Result<()> {
+    let (endpoint, _) = start_mailgun(StatusCode::BAD_REQUEST, r#"{"message":"'to' parameter is not a valid address"}"#).await?;
+
+    let error = driver().with_endpoint(endpoint).send(invoice_message()).await.expect_err("Mailgun rejected the message");
+
+    let error = error.to_string();
+    assert!(error.contains("400"), "unexpected error: {}", error);
+    assert!(error.contains("'to' parameter is not a valid address"), "unexpected error: {}", error);
+    Ok(())
+}
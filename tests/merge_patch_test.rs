@@ -0,0 +1,199 @@
+//! JSON Merge Patch Tests
+//!
+//! These tests verify that `PATCH` with `application/merge-patch+json`
+//! clears a field sent as `null`, leaves omitted fields unchanged, validates
+//! only the fields sent, and that patches merge into JSON as RFC 7396 says.
+//! `PUT` with the same payload treats `null` and omitted fields the same way.
+
+mod common;
+
+use anyhow::Result;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::Response,
+    routing::{patch, put},
+    Extension, Router,
+};
+use rustaxum::app::http::merge_patch::{self, MERGE_PATCH_CONTENT_TYPE};
+use rustaxum::app::http::middleware::auth_guard::AuthUser;
+use rustaxum::app::http::controllers::organization_domain_controller;
+use rustaxum::app::models::organization_domain::{CreateOrganizationDomain, OrganizationDomain, UpdateOrganizationDomain};
+use rustaxum::app::services::organization_domain_service::OrganizationDomainService;
+use rustaxum::database::DbPool;
+use serde_json::{json, Value};
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn create_domain(pool: &DbPool, user_id: &str) -> Result<OrganizationDomain> {
+    OrganizationDomainService::create(pool, CreateOrganizationDomain {
+        code: Some("MP".to_string()),
+        name: "Merge Patch Domain".to_string(),
+        description: Some("Described".to_string()),
+    }, user_id).await
+}
+
+async fn send_patch(pool: &DbPool, user_id: &str, id: &str, content_type: &str, body: Value) -> Result<Response> {
+    let app = Router::new()
+        .route("/api/organization-domains/{id}", patch(organization_domain_controller::patch))
+        .with_state(pool.clone())
+        .layer(Extension(AuthUser {
+            user_id: user_id.to_string(),
+            auth_method: "jwt".to_string(),
+            impersonator_id: None,
+        }));
+
+    let request = Request::builder()
+        .method("PATCH")
+        .uri(format!("/api/organization-domains/{}", id))
+        .header(header::CONTENT_TYPE, content_type)
+        .body(Body::from(body.to_string()))?;
+    Ok(app.oneshot(request).await?)
+}
+
+async fn send_put(pool: &DbPool, user_id: &str, id: &str, body: Value) -> Result<Response> {
+    let app = Router::new()
+        .route("/api/organization-domains/{id}", put(organization_domain_controller::update))
+        .with_state(pool.clone())
+        .layer(Extension(AuthUser {
+            user_id: user_id.to_string(),
+            auth_method: "jwt".to_string(),
+            impersonator_id: None,
+        }));
+
+    let request = Request::builder()
+        .method("PUT")
+        .uri(format!("/api/organization-domains/{}", id))
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))?;
+    Ok(app.oneshot(request).await?)
+}
+
+async fn json_body(response: Response) -> Result<Value> {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_null_clears_field() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let domain = create_domain(&pool, &user.id.to_string()).await?;
+
+    let response = send_patch(&pool, &user.id.to_string(), &domain.id.to_string(), MERGE_PATCH_CONTENT_TYPE, json!({
+        "description": null
+    })).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = json_body(response).await?;
+    assert_eq!(body["description"], Value::Null);
+    assert_eq!(body["code"], "MP");
+    assert_eq!(body["name"], "Merge Patch Domain");
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_omitted_field_is_unchanged() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let domain = create_domain(&pool, &user.id.to_string()).await?;
+
+    let response = send_patch(&pool, &user.id.to_string(), &domain.id.to_string(), MERGE_PATCH_CONTENT_TYPE, json!({
+        "name": "Renamed Domain"
+    })).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = json_body(response).await?;
+    assert_eq!(body["name"], "Renamed Domain");
+    assert_eq!(body["description"], "Described");
+    assert_eq!(body["code"], "MP");
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_put_clears_null_and_keeps_omitted_fields() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let domain = create_domain(&pool, &user.id.to_string()).await?;
+
+    let response = send_put(&pool, &user.id.to_string(), &domain.id.to_string(), json!({
+        "name": "Put Domain",
+        "description": null
+    })).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = json_body(response).await?;
+    assert_eq!(body["name"], "Put Domain");
+    assert_eq!(body["description"], Value::Null);
+    assert_eq!(body["code"], "MP");
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_only_sent_fields_are_validated() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let domain = create_domain(&pool, &user.id.to_string()).await?;
+    let id = domain.id.to_string();
+
+    // `name` is required, but only when the patch sends it
+    let response = send_patch(&pool, &user.id.to_string(), &id, MERGE_PATCH_CONTENT_TYPE, json!({ "code": "MQ" })).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send_patch(&pool, &user.id.to_string(), &id, MERGE_PATCH_CONTENT_TYPE, json!({ "name": null })).await?;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(json_body(response).await?["errors"]["name"]["required"].is_string());
+
+    let response = send_patch(&pool, &user.id.to_string(), &id, MERGE_PATCH_CONTENT_TYPE, json!(["not", "an", "object"])).await?;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let response = send_patch(&pool, &user.id.to_string(), &id, "text/plain", json!({ "code": "MR" })).await?;
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    Ok(())
+}
+
+#[test]
+fn test_nullable_fields_tell_null_from_omitted() {
+    let cleared: UpdateOrganizationDomain = serde_json::from_value(json!({ "description": null })).unwrap();
+    assert_eq!(cleared.description, Some(None));
+    assert_eq!(cleared.code, None);
+
+    let omitted: UpdateOrganizationDomain = serde_json::from_value(json!({})).unwrap();
+    assert_eq!(omitted.description, None);
+
+    let set: UpdateOrganizationDomain = serde_json::from_value(json!({ "description": "New" })).unwrap();
+    assert_eq!(set.description, Some(Some("New".to_string())));
+}
+
+#[test]
+fn test_apply_follows_rfc_7396() {
+    let mut target = json!({
+        "title": "Goodbye!",
+        "author": { "givenName": "John", "familyName": "Doe" },
+        "tags": ["example", "sample"],
+        "content": "This will be unchanged"
+    });
+
+    merge_patch::apply(&mut target, &json!({
+        "title": "Hello!",
+        "phoneNumber": "+01-123-456-7890",
+        "author": { "familyName": null },
+        "tags": ["example"]
+    }));
+
+    assert_eq!(target, json!({
+        "title": "Hello!",
+        "author": { "givenName": "John" },
+        "tags": ["example"],
+        "content": "This will be unchanged",
+        "phoneNumber": "+01-123-456-7890"
+    }));
+
+    let mut scalar = json!({ "a": "b" });
+    merge_patch::apply(&mut scalar, &json!(["c"]));
+    assert_eq!(scalar, json!(["c"]));
+}
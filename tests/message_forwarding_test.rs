@@ -0,0 +1,142 @@
+//! Message Forwarding Integration Tests
+//!
+//! These tests verify that forwarding creates a message in the destination
+//! conversation and records the forward chain back to the original message.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use diesel::prelude::*;
+use rustaxum::app::broadcasting::websocket::websocket_manager;
+use rustaxum::app::models::forward_history::ForwardHistory;
+use rustaxum::app::services::message_service::{ForwardMessageRequest, MessageError, MessageService};
+use rustaxum::schema::{conversations, forward_history};
+use serial_test::serial;
+use std::time::Duration;
+
+#[tokio::test]
+#[serial]
+async fn test_forward_links_source_to_destination() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+
+    let author = common::create_user(&pool)?;
+    let forwarder = common::create_user(&pool)?;
+    let author_device = common::create_device(&pool, &author)?;
+    let forwarder_device = common::create_device(&pool, &forwarder)?;
+
+    let source = common::create_conversation(&pool, &author)?;
+    common::add_participant(&pool, &source, &author)?;
+    common::add_participant(&pool, &source, &forwarder)?;
+    let destination = common::create_conversation(&pool, &forwarder)?;
+    common::add_participant(&pool, &destination, &forwarder)?;
+    let second_destination = common::create_conversation(&pool, &forwarder)?;
+    common::add_participant(&pool, &second_destination, &forwarder)?;
+
+    let original = common::create_message(&pool, &source, &author, &author_device)?;
+    let forwarder_id = forwarder.id.to_string();
+
+    let mut receiver = websocket_manager().await.subscribe(&format!("conversation.{}", destination.id)).await;
+
+    let forwarded = MessageService::forward(&pool, &original.id.to_string(), &forwarder_id, ForwardMessageRequest {
+        conversation_id: destination.id,
+        device_id: forwarder_device.id,
+        encrypted_content: None,
+        content_algorithm: None,
+    }).await?;
+
+    assert_eq!(forwarded.message.conversation_id, destination.id);
+    assert_eq!(forwarded.message.sender_user_id, forwarder.id);
+    assert_eq!(forwarded.message.forward_from_message_id, Some(original.id));
+    assert_eq!(forwarded.message.encrypted_content, original.encrypted_content);
+    assert_eq!(forwarded.forward.original_message_id, original.id);
+    assert_eq!(forwarded.forward.forward_depth, 1);
+    assert_eq!(forwarded.forwarded_from_user_id, author.id);
+
+    let event = tokio::time::timeout(Duration::from_secs(2), receiver.recv()).await??;
+    assert_eq!(event.event, "message.sent");
+    assert_eq!(event.data["message"]["id"], forwarded.message.id.to_string());
+
+    // Forwarding the forward keeps pointing at the original message
+    let reforwarded = MessageService::forward(&pool, &forwarded.message.id.to_string(), &forwarder_id, ForwardMessageRequest {
+        conversation_id: second_destination.id,
+        device_id: forwarder_device.id,
+        encrypted_content: None,
+        content_algorithm: None,
+    }).await?;
+    assert_eq!(reforwarded.message.forward_from_message_id, Some(forwarded.message.id));
+    assert_eq!(reforwarded.forward.original_message_id, original.id);
+    assert_eq!(reforwarded.forward.forward_depth, 2);
+
+    let mut conn = pool.get()?;
+    let history = forward_history::table
+        .filter(forward_history::original_message_id.eq(original.id.to_string()))
+        .order(forward_history::forward_depth.asc())
+        .select(ForwardHistory::as_select())
+        .load::<ForwardHistory>(&mut conn)?;
+    let linked: Vec<_> = history.iter().map(|h| h.message_id).collect();
+    assert_eq!(linked, vec![forwarded.message.id, reforwarded.message.id]);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_forward_requires_participation_and_reencryption() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+
+    let author = common::create_user(&pool)?;
+    let outsider = common::create_user(&pool)?;
+    let author_device = common::create_device(&pool, &author)?;
+    let outsider_device = common::create_device(&pool, &outsider)?;
+
+    let source = common::create_conversation(&pool, &author)?;
+    common::add_participant(&pool, &source, &author)?;
+    let outsider_conversation = common::create_conversation(&pool, &outsider)?;
+    common::add_participant(&pool, &outsider_conversation, &outsider)?;
+    let encrypted = common::create_conversation(&pool, &author)?;
+    common::add_participant(&pool, &encrypted, &author)?;
+
+    let mut conn = pool.get()?;
+    diesel::update(conversations::table.find(encrypted.id.to_string()))
+        .set(conversations::is_encrypted.eq(true))
+        .execute(&mut conn)?;
+
+    let original = common::create_message(&pool, &source, &author, &author_device)?;
+
+    // Users cannot forward out of conversations they are not in
+    let error = MessageService::forward(&pool, &original.id.to_string(), &outsider.id.to_string(), ForwardMessageRequest {
+        conversation_id: outsider_conversation.id,
+        device_id: outsider_device.id,
+        encrypted_content: None,
+        content_algorithm: None,
+    }).await.expect_err("outsiders must not forward");
+    assert_eq!(MessageError::status_code(&error), StatusCode::FORBIDDEN);
+
+    // Nor into conversations they are not in
+    let error = MessageService::forward(&pool, &original.id.to_string(), &author.id.to_string(), ForwardMessageRequest {
+        conversation_id: outsider_conversation.id,
+        device_id: author_device.id,
+        encrypted_content: None,
+        content_algorithm: None,
+    }).await.expect_err("destination must be joined");
+    assert_eq!(MessageError::status_code(&error), StatusCode::FORBIDDEN);
+
+    let error = MessageService::forward(&pool, &original.id.to_string(), &author.id.to_string(), ForwardMessageRequest {
+        conversation_id: encrypted.id,
+        device_id: author_device.id,
+        encrypted_content: None,
+        content_algorithm: None,
+    }).await.expect_err("encrypted destinations need re-encrypted content");
+    assert_eq!(MessageError::status_code(&error), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let forwarded = MessageService::forward(&pool, &original.id.to_string(), &author.id.to_string(), ForwardMessageRequest {
+        conversation_id: encrypted.id,
+        device_id: author_device.id,
+        encrypted_content: Some("reencrypted_content".to_string()),
+        content_algorithm: Some("aes-256-gcm".to_string()),
+    }).await?;
+    assert_eq!(forwarded.message.encrypted_content, "reencrypted_content");
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+//! Message Mention Integration Tests
+//!
+//! These tests verify that mentions are extracted from message text,
+//! stored only for conversation participants, and notify the mentioned users.
+
+mod common;
+
+use anyhow::Result;
+use diesel::prelude::*;
+use rustaxum::app::models::user::User;
+use rustaxum::app::services::mention_service::{MentionService, ParsedMention};
+use rustaxum::app::services::message_service::{MessageService, SendMessageRequest};
+use rustaxum::database::DbPool;
+use rustaxum::schema::{conversations, message_mentions, notifications, sys_users};
+use serial_test::serial;
+
+fn set_username(pool: &DbPool, user: &User, username: &str) -> Result<()> {
+    let mut conn = pool.get()?;
+    diesel::update(sys_users::table.find(user.id.to_string()))
+        .set(sys_users::username.eq(Some(username)))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+fn mention_notification_count(pool: &DbPool, user: &User) -> Result<i64> {
+    let mut conn = pool.get()?;
+    let count = notifications::table
+        .filter(notifications::notifiable_id.eq(format!("User_{}", user.id)))
+        .filter(notifications::type_.eq("MessageMentionNotification"))
+        .count()
+        .get_result(&mut conn)?;
+    Ok(count)
+}
+
+#[test]
+fn test_extract_mentions() {
+    let mentions = MentionService::extract("@alice hi, ping @01ARZ3NDEKTSV4RRFFQ69G5FAV and mail bob@example.com @carol.");
+
+    assert_eq!(mentions, vec![
+        ParsedMention { token: "alice".to_string(), start: 0, length: 6 },
+        ParsedMention { token: "01ARZ3NDEKTSV4RRFFQ69G5FAV".to_string(), start: 16, length: 27 },
+        ParsedMention { token: "carol".to_string(), start: 69, length: 6 },
+    ]);
+
+    // Offsets are in characters, not bytes
+    let mentions = MentionService::extract("héllo @dave");
+    assert_eq!(mentions[0].start, 6);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_mentions_are_stored_and_notified() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+
+    let sender = common::create_user(&pool)?;
+    let mentioned = common::create_user(&pool)?;
+    let by_id = common::create_user(&pool)?;
+    let outsider = common::create_user(&pool)?;
+    let sender_device = common::create_device(&pool, &sender)?;
+    let conversation = common::create_conversation(&pool, &sender)?;
+    common::add_participant(&pool, &conversation, &sender)?;
+    common::add_participant(&pool, &conversation, &mentioned)?;
+    common::add_participant(&pool, &conversation, &by_id)?;
+
+    let mentioned_name = format!("member_{}", mentioned.id.to_string().to_lowercase());
+    let outsider_name = format!("outsider_{}", outsider.id.to_string().to_lowercase());
+    set_username(&pool, &mentioned, &mentioned_name)?;
+    set_username(&pool, &outsider, &outsider_name)?;
+
+    let body = format!("hey @{} and @{}, also @{}", mentioned_name, by_id.id, outsider_name);
+    let sent = MessageService::send(&pool, &conversation.id.to_string(), &sender.id.to_string(), SendMessageRequest {
+        device_id: sender_device.id,
+        message_type: None,
+        encrypted_content: body,
+        content_algorithm: "none".to_string(),
+        reply_to_message_id: None,
+        mentions: None,
+    }).await?;
+
+    // The outsider is not a participant and is ignored
+    let mentioned_ids: Vec<_> = sent.mentions.iter().map(|m| m.mentioned_user_id).collect();
+    assert_eq!(mentioned_ids, vec![mentioned.id, by_id.id]);
+    assert_eq!(sent.mentions[0].mention_start_pos, Some(4));
+    assert_eq!(sent.mentions[0].mention_length, Some(mentioned_name.len() as i32 + 1));
+
+    let mut conn = pool.get()?;
+    let stored: i64 = message_mentions::table
+        .filter(message_mentions::message_id.eq(sent.message.id.to_string()))
+        .count()
+        .get_result(&mut conn)?;
+    assert_eq!(stored, 2);
+
+    assert_eq!(mention_notification_count(&pool, &mentioned)?, 1);
+    assert_eq!(mention_notification_count(&pool, &by_id)?, 1);
+    assert_eq!(mention_notification_count(&pool, &outsider)?, 0);
+    assert_eq!(mention_notification_count(&pool, &sender)?, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_encrypted_conversation_uses_client_mentions() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+
+    let sender = common::create_user(&pool)?;
+    let mentioned = common::create_user(&pool)?;
+    let sender_device = common::create_device(&pool, &sender)?;
+    let conversation = common::create_conversation(&pool, &sender)?;
+    common::add_participant(&pool, &conversation, &sender)?;
+    common::add_participant(&pool, &conversation, &mentioned)?;
+
+    let mut conn = pool.get()?;
+    diesel::update(conversations::table.find(conversation.id.to_string()))
+        .set(conversations::is_encrypted.eq(true))
+        .execute(&mut conn)?;
+
+    // Ciphertext is never parsed, even when it happens to contain an @token
+    let sent = MessageService::send(&pool, &conversation.id.to_string(), &sender.id.to_string(), SendMessageRequest {
+        device_id: sender_device.id,
+        message_type: None,
+        encrypted_content: format!("ciphertext @{}", sender.id),
+        content_algorithm: "aes-256-gcm".to_string(),
+        reply_to_message_id: None,
+        mentions: Some(vec![format!("@{}", mentioned.id), format!("@{}", sender.id)]),
+    }).await?;
+
+    // Self-mentions are dropped, and client mentions carry no positions
+    assert_eq!(sent.mentions.len(), 1);
+    assert_eq!(sent.mentions[0].mentioned_user_id, mentioned.id);
+    assert_eq!(sent.mentions[0].mention_start_pos, None);
+    assert_eq!(mention_notification_count(&pool, &mentioned)?, 1);
+
+    Ok(())
+}
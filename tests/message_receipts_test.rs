@@ -0,0 +1,158 @@
+//! Message Receipt and Reaction Integration Tests
+//!
+//! These tests verify that read receipts and reactions are persisted,
+//! restricted to conversation participants, and broadcast on the
+//! conversation channel, and that a participant's read marker never moves
+//! back to an older message.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use diesel::prelude::*;
+use rustaxum::app::broadcasting::websocket::websocket_manager;
+use rustaxum::app::models::conversation_participant::ConversationParticipant;
+use rustaxum::app::services::conversation_service::ConversationError;
+use rustaxum::app::services::message_service::{AddReactionRequest, MessageService};
+use rustaxum::schema::conversation_participants;
+use serial_test::serial;
+use std::time::Duration;
+
+#[tokio::test]
+#[serial]
+async fn test_mark_read_records_receipt_and_broadcasts() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+
+    let sender = common::create_user(&pool)?;
+    let recipient = common::create_user(&pool)?;
+    let sender_device = common::create_device(&pool, &sender)?;
+    let recipient_device = common::create_device(&pool, &recipient)?;
+    let conversation = common::create_conversation(&pool, &sender)?;
+    common::add_participant(&pool, &conversation, &sender)?;
+    common::add_participant(&pool, &conversation, &recipient)?;
+    let message = common::create_message(&pool, &conversation, &sender, &sender_device)?;
+
+    let mut receiver = websocket_manager().await
+        .subscribe(&format!("conversation.{}", conversation.id))
+        .await;
+
+    let receipt = MessageService::mark_read(
+        &pool,
+        &message.id.to_string(),
+        &recipient.id.to_string(),
+        &recipient_device.id.to_string(),
+    ).await?;
+
+    assert_eq!(receipt.status, "read");
+    assert!(receipt.delivered_at.is_some());
+    assert!(receipt.read_at.is_some());
+
+    // A later delivery receipt must not downgrade the read status
+    let receipt = MessageService::mark_delivered(
+        &pool,
+        &message.id.to_string(),
+        &recipient.id.to_string(),
+        &recipient_device.id.to_string(),
+    ).await?;
+    assert_eq!(receipt.status, "read");
+
+    let statuses = MessageService::delivery_status(&pool, &message.id.to_string(), &sender.id.to_string())?;
+    assert_eq!(statuses.len(), 1);
+
+    let event = tokio::time::timeout(Duration::from_secs(2), receiver.recv()).await??;
+    assert_eq!(event.event, "message.read");
+    assert_eq!(event.data["message_id"], message.id.to_string());
+    assert_eq!(event.data["user_id"], recipient.id.to_string());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_read_marker_only_moves_forward() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+
+    let sender = common::create_user(&pool)?;
+    let recipient = common::create_user(&pool)?;
+    let sender_device = common::create_device(&pool, &sender)?;
+    let recipient_device = common::create_device(&pool, &recipient)?;
+    let conversation = common::create_conversation(&pool, &sender)?;
+    common::add_participant(&pool, &conversation, &sender)?;
+    let participant = common::add_participant(&pool, &conversation, &recipient)?;
+    let older = common::create_message(&pool, &conversation, &sender, &sender_device)?;
+    let newer = common::create_message(&pool, &conversation, &sender, &sender_device)?;
+
+    // The newer message is read first, then a late receipt for the older one arrives
+    for message in [&newer, &older] {
+        MessageService::mark_read(
+            &pool,
+            &message.id.to_string(),
+            &recipient.id.to_string(),
+            &recipient_device.id.to_string(),
+        ).await?;
+    }
+
+    let mut conn = pool.get()?;
+    let participant = conversation_participants::table
+        .find(participant.id.to_string())
+        .select(ConversationParticipant::as_select())
+        .first::<ConversationParticipant>(&mut conn)?;
+    assert_eq!(participant.last_read_message_id, Some(newer.id));
+    assert!(participant.last_read_at.is_some());
+
+    // The older message still has its own read receipt
+    let statuses = MessageService::delivery_status(&pool, &older.id.to_string(), &sender.id.to_string())?;
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0].status, "read");
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_non_participant_cannot_react() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+
+    let sender = common::create_user(&pool)?;
+    let outsider = common::create_user(&pool)?;
+    let sender_device = common::create_device(&pool, &sender)?;
+    let outsider_device = common::create_device(&pool, &outsider)?;
+    let conversation = common::create_conversation(&pool, &sender)?;
+    common::add_participant(&pool, &conversation, &sender)?;
+    let message = common::create_message(&pool, &conversation, &sender, &sender_device)?;
+
+    let result = MessageService::add_reaction(
+        &pool,
+        &message.id.to_string(),
+        &outsider.id.to_string(),
+        AddReactionRequest {
+            device_id: outsider_device.id,
+            encrypted_reaction: "encrypted_reaction".to_string(),
+            reaction_algorithm: "aes-256-gcm".to_string(),
+        },
+    ).await;
+
+    let error = result.expect_err("outsiders must not react");
+    assert_eq!(ConversationError::status_code(&error), StatusCode::FORBIDDEN);
+
+    // Reacting twice with the same reaction keeps a single row
+    let request = || AddReactionRequest {
+        device_id: sender_device.id,
+        encrypted_reaction: "encrypted_reaction".to_string(),
+        reaction_algorithm: "aes-256-gcm".to_string(),
+    };
+    let first = MessageService::add_reaction(&pool, &message.id.to_string(), &sender.id.to_string(), request()).await?;
+    let second = MessageService::add_reaction(&pool, &message.id.to_string(), &sender.id.to_string(), request()).await?;
+    assert_eq!(first.id, second.id);
+
+    let removed = MessageService::remove_reaction(
+        &pool,
+        &message.id.to_string(),
+        &first.id.to_string(),
+        &sender.id.to_string(),
+    ).await?;
+    assert!(removed);
+    assert!(MessageService::reactions(&pool, &message.id.to_string(), &sender.id.to_string())?.is_empty());
+
+    Ok(())
+}
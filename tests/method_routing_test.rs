@@ -0,0 +1,82 @@
+//! Method Routing Tests
+//!
+//! These tests verify that HEAD on a GET route returns the GET headers
+//! without a body, that an unsupported method on a known path answers 405
+//! with an `Allow` header, and that plain OPTIONS lists the allowed methods.
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    middleware,
+    response::Response,
+    routing::get,
+    Router,
+};
+use rustaxum::app::http::middleware::method_middleware::method_middleware;
+use serde_json::Value;
+use tower::ServiceExt;
+
+fn app() -> Router {
+    Router::new()
+        .route("/countries", get(|| async { ([("x-total-count", "2")], "[\"ID\",\"MY\"]") }))
+        .route("/countries/{id}", get(|| async { "ID" }).put(|| async { "updated" }))
+        .layer(middleware::from_fn(method_middleware))
+}
+
+async fn send(method: Method, uri: &str) -> Response {
+    app()
+        .oneshot(Request::builder().method(method).uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn body(response: Response) -> Vec<u8> {
+    axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()
+}
+
+#[tokio::test]
+async fn test_head_on_get_route_returns_headers_without_body() {
+    let response = send(Method::HEAD, "/countries").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-total-count"], "2");
+    assert!(response.headers()[header::CONTENT_TYPE].to_str().unwrap().starts_with("text/plain"));
+    assert!(body(response).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_post_to_get_only_route_is_405_with_allow() {
+    let response = send(Method::POST, "/countries").await;
+
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()[header::ALLOW], "GET, HEAD");
+
+    let json: Value = serde_json::from_slice(&body(response).await).unwrap();
+    assert_eq!(json["error"], "Method Not Allowed");
+}
+
+#[tokio::test]
+async fn test_allow_lists_every_method_of_the_path() {
+    let response = send(Method::DELETE, "/countries/ID").await;
+
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    let allow = response.headers()[header::ALLOW].to_str().unwrap().to_string();
+    let mut methods: Vec<&str> = allow.split(", ").collect();
+    methods.sort();
+    assert_eq!(methods, vec!["GET", "HEAD", "PUT"]);
+}
+
+#[tokio::test]
+async fn test_options_lists_allowed_methods() {
+    let response = send(Method::OPTIONS, "/countries").await;
+
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.headers()[header::ALLOW], "GET, HEAD, OPTIONS");
+    assert!(body(response).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_unknown_path_is_still_404() {
+    assert_eq!(send(Method::POST, "/nowhere").await.status(), StatusCode::NOT_FOUND);
+    assert_eq!(send(Method::HEAD, "/nowhere").await.status(), StatusCode::NOT_FOUND);
+}
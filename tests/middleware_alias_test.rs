@@ -0,0 +1,129 @@
+//! Middleware Alias Tests
+//!
+//! These tests verify that `make:middleware` generates a `from_fn`-compatible
+//! middleware and registers it under its snake_case alias, and that aliased
+//! middleware can be resolved and applied to routes in the order given.
+
+use anyhow::Result;
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use rustaxum::app::http::middleware::aliases::{MiddlewareAliases, REGISTRATION_MARKER};
+use rustaxum::cli::generators::middleware::{generate_middleware_content, insert_alias_registration, middleware_alias};
+use tower::ServiceExt;
+
+const ALIASES_SOURCE: &str = include_str!("../src/app/http/middleware/aliases.rs");
+
+/// What a generated `EnsureTokenIsValid` middleware looks like once filled in
+async fn ensure_token_is_valid(request: Request, next: Next) -> Response {
+    if request.headers().get("x-token").and_then(|token| token.to_str().ok()) != Some("secret") {
+        return (StatusCode::FORBIDDEN, "Invalid token").into_response();
+    }
+    next.run(request).await
+}
+
+async fn tag_first(request: Request, next: Next) -> Response {
+    tag(request, next, "first").await
+}
+
+async fn tag_second(request: Request, next: Next) -> Response {
+    tag(request, next, "second").await
+}
+
+/// Append `name` to the `x-order` header on the way in
+async fn tag(mut request: Request, next: Next, name: &str) -> Response {
+    let order = request.headers().get("x-order")
+        .and_then(|value| value.to_str().ok())
+        .map(|value| format!("{},{}", value, name))
+        .unwrap_or_else(|| name.to_string());
+    request.headers_mut().insert("x-order", HeaderValue::from_str(&order).unwrap());
+    next.run(request).await
+}
+
+async fn echo_order(request: Request) -> String {
+    request.headers().get("x-order")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+async fn send(app: Router, token: Option<&str>) -> Result<Response> {
+    let mut request = axum::http::Request::builder().uri("/protected");
+    if let Some(token) = token {
+        request = request.header("x-token", token);
+    }
+    Ok(app.oneshot(request.body(Body::empty())?).await?)
+}
+
+#[test]
+fn test_generated_middleware_has_from_fn_signature() {
+    assert_eq!(middleware_alias("EnsureTokenIsValid"), "ensure_token_is_valid");
+    assert_eq!(middleware_alias("EnsureTokenIsValidMiddleware"), "ensure_token_is_valid");
+
+    let content = generate_middleware_content("EnsureTokenIsValidMiddleware");
+    assert!(content.contains("pub async fn ensure_token_is_valid(\n    request: Request,\n    next: Next,\n) -> Response {"));
+    assert!(content.contains("next.run(request).await"));
+}
+
+#[test]
+fn test_generator_registers_alias_above_marker() {
+    let updated = insert_alias_registration(ALIASES_SOURCE, "EnsureTokenIsValid").unwrap();
+    let registration = "aliases.alias(\"ensure_token_is_valid\", |request, next| Box::pin(crate::app::http::middleware::ensure_token_is_valid_middleware::ensure_token_is_valid(request, next)));";
+
+    let registered_at = updated.find(registration).unwrap();
+    assert!(registered_at < updated.find(REGISTRATION_MARKER).unwrap());
+
+    // Running the generator twice does not register the alias twice
+    assert!(insert_alias_registration(&updated, "EnsureTokenIsValid").is_none());
+    assert!(insert_alias_registration("fn register_aliases() {}", "EnsureTokenIsValid").is_none());
+}
+
+#[tokio::test]
+async fn test_registered_middleware_resolves_and_applies_by_alias() -> Result<()> {
+    let mut aliases = MiddlewareAliases::new();
+    aliases.alias("ensure_token_is_valid", |request, next| Box::pin(ensure_token_is_valid(request, next)));
+    assert!(aliases.has("ensure_token_is_valid"));
+
+    let app = aliases.apply(Router::new().route("/protected", get(|| async { "ok" })), &["ensure_token_is_valid"])?;
+
+    assert_eq!(send(app.clone(), None).await?.status(), StatusCode::FORBIDDEN);
+    assert_eq!(send(app.clone(), Some("wrong")).await?.status(), StatusCode::FORBIDDEN);
+    assert_eq!(send(app, Some("secret")).await?.status(), StatusCode::OK);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_aliases_run_in_the_order_given() -> Result<()> {
+    let mut aliases = MiddlewareAliases::new();
+    aliases
+        .alias("first", |request, next| Box::pin(tag_first(request, next)))
+        .alias("second", |request, next| Box::pin(tag_second(request, next)));
+
+    let app = aliases.apply(Router::new().route("/protected", get(echo_order)), &["first", "second"])?;
+
+    let body = axum::body::to_bytes(send(app, None).await?.into_body(), usize::MAX).await?;
+    assert_eq!(&body[..], b"first,second");
+    Ok(())
+}
+
+#[test]
+fn test_unknown_alias_is_an_error() {
+    let error = MiddlewareAliases::global()
+        .apply(Router::<()>::new().route("/protected", get(|| async { "ok" })), &["auth", "missing"])
+        .unwrap_err();
+    assert!(error.to_string().contains("[missing]"));
+}
+
+#[test]
+fn test_global_registry_has_app_aliases() {
+    let aliases = MiddlewareAliases::global();
+    for alias in ["auth", "guest", "mfa", "signed"] {
+        assert!(aliases.has(alias), "missing alias {}", alias);
+    }
+}
@@ -0,0 +1,107 @@
+//! Notification Batch Tests
+//!
+//! These tests verify that sending one notification to many users splits
+//! the recipients into chunked queue jobs, builds each channel's message
+//! once, and staggers the jobs on the notifications queue.
+
+use anyhow::Result;
+use rustaxum::app::jobs::send_notification_batch_job::SendNotificationBatchJob;
+use rustaxum::app::jobs::{JobDispatcher, MemoryQueueDriver};
+use rustaxum::app::models::user::User;
+use rustaxum::app::notifications::{
+    DatabaseMessage, MailContent, MailMessage, Notifiable, Notification, NotificationChannel,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+struct Announcement {
+    mail_builds: Arc<AtomicUsize>,
+    database_builds: Arc<AtomicUsize>,
+}
+
+impl Notification for Announcement {
+    fn via(&self, _notifiable: &dyn Notifiable) -> Vec<NotificationChannel> {
+        vec![NotificationChannel::Mail, NotificationChannel::Database, NotificationChannel::Mail]
+    }
+
+    fn to_mail(&self, _notifiable: &dyn Notifiable) -> Result<MailMessage> {
+        self.mail_builds.fetch_add(1, Ordering::SeqCst);
+        Ok(MailMessage::new(
+            String::new(),
+            "Scheduled maintenance".to_string(),
+            MailContent::Text("The service will be down on Sunday.".to_string()),
+        ))
+    }
+
+    fn to_database(&self, _notifiable: &dyn Notifiable) -> Result<DatabaseMessage> {
+        self.database_builds.fetch_add(1, Ordering::SeqCst);
+        Ok(DatabaseMessage::new(serde_json::json!({ "title": "Scheduled maintenance" })))
+    }
+
+    fn notification_type(&self) -> &'static str {
+        "Announcement"
+    }
+}
+
+fn users(count: usize) -> Vec<User> {
+    (0..count)
+        .map(|i| User::new(
+            format!("User {}", i),
+            format!("user{}@example.com", i),
+            "password".to_string(),
+            "system",
+        ))
+        .collect()
+}
+
+fn announcement() -> Announcement {
+    Announcement {
+        mail_builds: Arc::new(AtomicUsize::new(0)),
+        database_builds: Arc::new(AtomicUsize::new(0)),
+    }
+}
+
+#[tokio::test]
+async fn test_thousand_recipients_are_split_into_chunks() -> Result<()> {
+    let users = users(1000);
+    let notification = announcement();
+
+    let jobs = SendNotificationBatchJob::chunks(&users, &notification, 100).await?;
+
+    assert_eq!(jobs.len(), 10);
+    assert!(jobs.iter().all(|job| job.recipients.len() == 100));
+    assert_eq!(jobs[0].recipients[0].email.as_deref(), Some("user0@example.com"));
+    assert_eq!(jobs[9].recipients[99].key, users[999].get_key());
+
+    // Each message is built once, and the repeated mail channel is deduplicated
+    assert_eq!(notification.mail_builds.load(Ordering::SeqCst), 1);
+    assert_eq!(notification.database_builds.load(Ordering::SeqCst), 1);
+    assert!(jobs.iter().all(|job| job.mail.is_some() && job.database.is_some() && job.broadcast.is_none()));
+    assert_eq!(
+        jobs[0].recipients[0].channels,
+        vec![NotificationChannel::Mail, NotificationChannel::Database]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_last_chunk_holds_the_remainder() -> Result<()> {
+    let jobs = SendNotificationBatchJob::chunks(&users(1000), &announcement(), 300).await?;
+
+    let sizes: Vec<usize> = jobs.iter().map(|job| job.recipients.len()).collect();
+    assert_eq!(sizes, vec![300, 300, 300, 100]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_chunks_are_queued_on_notifications_queue() -> Result<()> {
+    let dispatcher = JobDispatcher::new(Box::new(MemoryQueueDriver::new()));
+    let jobs = SendNotificationBatchJob::chunks(&users(1000), &announcement(), 100).await?;
+
+    let queued = SendNotificationBatchJob::dispatch_all(&dispatcher, &jobs, 5).await?;
+
+    assert_eq!(queued, 10);
+    assert_eq!(dispatcher.stats("notifications").await?.pending_jobs, 10);
+    Ok(())
+}
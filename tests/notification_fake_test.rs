@@ -0,0 +1,77 @@
+//! Notification Fake Tests
+//!
+//! These tests verify that `NotificationFacade::fake()` records each
+//! notification per notifiable and channel instead of delivering it, and that
+//! the send assertions see those records.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rustaxum::app::notifications::message_mention_notification::MessageMentionNotification;
+use rustaxum::app::notifications::{notify, Notifiable, NotificationChannel, NotificationFacade};
+use serial_test::serial;
+
+/// A notifiable that is not a `User`, so no preferences are loaded
+struct Member {
+    id: &'static str,
+}
+
+#[async_trait]
+impl Notifiable for Member {
+    async fn route_notification_for(&self, _channel: &NotificationChannel) -> Option<String> {
+        Some(self.id.to_string())
+    }
+
+    fn get_key(&self) -> String {
+        format!("Member_{}", self.id)
+    }
+}
+
+fn mention() -> MessageMentionNotification {
+    MessageMentionNotification::new("message-1".to_string(), "conversation-1".to_string(), "sender-1".to_string())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_mention_notifies_the_mentioned_member() -> Result<()> {
+    let ada = Member { id: "ada" };
+    let grace = Member { id: "grace" };
+
+    NotificationFacade::fake().await;
+    assert!(NotificationFacade::assert_nothing_sent().await);
+
+    notify(&ada, mention()).await?;
+
+    assert!(NotificationFacade::assert_sent_to(&ada, "MessageMentionNotification").await);
+    assert!(NotificationFacade::assert_sent_to_via(&ada, "MessageMentionNotification", NotificationChannel::Database).await);
+    assert!(!NotificationFacade::assert_sent_to_via(&ada, "MessageMentionNotification", NotificationChannel::Mail).await);
+    assert!(NotificationFacade::assert_not_sent_to(&grace, "MessageMentionNotification").await);
+    assert!(!NotificationFacade::assert_sent_to(&grace, "MessageMentionNotification").await);
+    // One record per channel: database and web push
+    assert!(NotificationFacade::assert_sent_times("MessageMentionNotification", 2).await);
+    assert!(!NotificationFacade::assert_nothing_sent().await);
+
+    let sent = NotificationFacade::get_sent_notifications().await;
+    assert_eq!(sent[0].notifiable_key, "Member_ada");
+    assert_eq!(sent[0].data.as_ref().unwrap()["conversation_id"], "conversation-1");
+
+    NotificationFacade::restore().await;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_fake_starts_empty_and_restores() -> Result<()> {
+    let ada = Member { id: "ada" };
+
+    let first = NotificationFacade::fake().await;
+    notify(&ada, mention()).await?;
+    assert!(first.assert_sent_to(&ada, "MessageMentionNotification").await);
+
+    NotificationFacade::fake().await;
+    assert!(NotificationFacade::assert_nothing_sent().await);
+
+    NotificationFacade::restore().await;
+    assert!(!NotificationFacade::is_faking().await);
+    assert!(!NotificationFacade::assert_nothing_sent().await, "assertions fail when not faking");
+    Ok(())
+}
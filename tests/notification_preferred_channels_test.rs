@@ -0,0 +1,101 @@
+//! Preferred Notification Channel Tests
+//!
+//! These tests verify that `Notifiable::preferred_channels` narrows the
+//! channels a notification declares before it is dispatched, keeping
+//! `Custom` channels, and that a notifiable without preferences receives the
+//! notification on every declared channel.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rustaxum::app::notifications::{
+    filter_preferred_channels, notify, Notifiable, Notification, NotificationChannel, NotificationFacade,
+};
+use serial_test::serial;
+
+struct OrderShipped;
+
+impl Notification for OrderShipped {
+    fn via(&self, _notifiable: &dyn Notifiable) -> Vec<NotificationChannel> {
+        vec![
+            NotificationChannel::Mail,
+            NotificationChannel::Sms,
+            NotificationChannel::Custom("teams".to_string()),
+            NotificationChannel::Database,
+        ]
+    }
+
+    fn notification_type(&self) -> &'static str {
+        "OrderShipped"
+    }
+}
+
+/// A customer who has turned SMS off, or set no preferences when `preferred` is `None`
+struct Customer {
+    id: &'static str,
+    preferred: Option<Vec<NotificationChannel>>,
+}
+
+#[async_trait]
+impl Notifiable for Customer {
+    async fn route_notification_for(&self, _channel: &NotificationChannel) -> Option<String> {
+        Some(self.id.to_string())
+    }
+
+    fn get_key(&self) -> String {
+        format!("Customer_{}", self.id)
+    }
+
+    fn preferred_channels(&self, _notification: &dyn Notification) -> Option<Vec<NotificationChannel>> {
+        self.preferred.clone()
+    }
+}
+
+fn without_sms(id: &'static str) -> Customer {
+    Customer {
+        id,
+        preferred: Some(vec![NotificationChannel::Mail, NotificationChannel::Database]),
+    }
+}
+
+#[test]
+fn test_preferences_intersect_declared_channels() {
+    let customer = without_sms("ada");
+
+    let channels = filter_preferred_channels(&OrderShipped, &customer, OrderShipped.via(&customer));
+
+    assert_eq!(
+        channels,
+        vec![
+            NotificationChannel::Mail,
+            NotificationChannel::Custom("teams".to_string()),
+            NotificationChannel::Database,
+        ]
+    );
+}
+
+#[test]
+fn test_no_preferences_keeps_every_channel() {
+    let customer = Customer { id: "grace", preferred: None };
+
+    let channels = filter_preferred_channels(&OrderShipped, &customer, OrderShipped.via(&customer));
+
+    assert_eq!(channels, OrderShipped.via(&customer));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_opted_out_channel_is_not_sent() -> Result<()> {
+    let customer = without_sms("ada");
+
+    NotificationFacade::fake().await;
+    notify(&customer, OrderShipped).await?;
+
+    assert!(NotificationFacade::assert_sent_to_via(&customer, "OrderShipped", NotificationChannel::Mail).await);
+    assert!(NotificationFacade::assert_sent_to_via(&customer, "OrderShipped", NotificationChannel::Database).await);
+    assert!(NotificationFacade::assert_sent_to_via(&customer, "OrderShipped", NotificationChannel::Custom("teams".to_string())).await);
+    assert!(!NotificationFacade::assert_sent_to_via(&customer, "OrderShipped", NotificationChannel::Sms).await);
+    assert!(NotificationFacade::assert_sent_times("OrderShipped", 3).await);
+
+    NotificationFacade::restore().await;
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! Required Notification Channel Tests
+//!
+//! These tests verify that a notification fails when one of its
+//! `required_channels` is not configured, while unconfigured optional
+//! channels are skipped as before.
+
+use anyhow::Result;
+use rustaxum::app::models::user::User;
+use rustaxum::app::notifications::{
+    ChannelError, ChannelManager, Notifiable, Notification, NotificationChannel, SlackChannel,
+};
+
+struct SecurityAlert {
+    required: Vec<NotificationChannel>,
+}
+
+impl Notification for SecurityAlert {
+    fn via(&self, _notifiable: &dyn Notifiable) -> Vec<NotificationChannel> {
+        vec![NotificationChannel::Slack, NotificationChannel::WebPush]
+    }
+
+    fn required_channels(&self) -> Vec<NotificationChannel> {
+        self.required.clone()
+    }
+
+    fn notification_type(&self) -> &'static str {
+        "SecurityAlert"
+    }
+}
+
+fn user() -> User {
+    User::new(
+        "Alert Recipient".to_string(),
+        "alerts@example.com".to_string(),
+        "password".to_string(),
+        "system",
+    )
+}
+
+#[tokio::test]
+async fn test_required_unconfigured_channel_errors() -> Result<()> {
+    let manager = ChannelManager::with_optional_channels(None, None, None);
+    let alert = SecurityAlert { required: vec![NotificationChannel::Slack] };
+    let user = user();
+
+    let error = manager.send(&alert, &user, alert.via(&user)).await.unwrap_err();
+    match error.downcast_ref::<ChannelError>() {
+        Some(ChannelError::NotConfigured { notification, channel }) => {
+            assert_eq!(notification, "SecurityAlert");
+            assert_eq!(channel, "slack");
+        }
+        None => panic!("expected ChannelError::NotConfigured, got {}", error),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_slack_without_webhook_is_not_configured() -> Result<()> {
+    let slack = SlackChannel::new()?;
+    let manager = ChannelManager::with_optional_channels(None, None, Some(slack.clone()));
+
+    assert_eq!(manager.is_configured(&NotificationChannel::Slack), slack.is_configured());
+    assert!(manager.is_configured(&NotificationChannel::Mail));
+    assert!(!manager.is_configured(&NotificationChannel::WebPush));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_optional_unconfigured_channel_is_skipped() -> Result<()> {
+    let manager = ChannelManager::with_optional_channels(None, None, None);
+    let alert = SecurityAlert { required: vec![] };
+    let user = user();
+
+    manager.send(&alert, &user, alert.via(&user)).await?;
+
+    // A required channel the notification is not sent on does not matter
+    let alert = SecurityAlert { required: vec![NotificationChannel::Sms] };
+    manager.send(&alert, &user, alert.via(&user)).await?;
+    Ok(())
+}
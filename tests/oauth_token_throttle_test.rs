@@ -0,0 +1,189 @@
+//! OAuth Token Throttling Tests
+//!
+//! These tests verify that `/oauth/token` locks a client out for an IP after
+//! repeated bad client secrets, answering `invalid_client` with `Retry-After`,
+//! that lockouts back off exponentially and are recorded as security
+//! incidents, and that per-IP limits throttle token requests.
+
+mod common;
+
+use anyhow::Result;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::Response,
+    Extension, Router,
+};
+use diesel::prelude::*;
+use rustaxum::app::http::middleware::trusted_proxy_middleware::ClientInfo;
+use rustaxum::app::models::oauth::Client;
+use rustaxum::app::models::security_incidents::SecurityIncident;
+use rustaxum::app::services::oauth::client_service::ClientService;
+use rustaxum::app::services::oauth::{TokenThrottleReason, TokenThrottleService};
+use rustaxum::cache::drivers::MemoryCache;
+use rustaxum::cache::manager::CacheDriver;
+use rustaxum::config::oauth::OAuthConfig;
+use rustaxum::database::DbPool;
+use rustaxum::schema::security_incidents;
+use serde_json::Value;
+use serial_test::serial;
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+
+const SECRET: &str = "correct-client-secret";
+
+fn app(pool: &DbPool, ip: &str) -> Router {
+    rustaxum::routes::oauth::oauth_routes()
+        .with_state(pool.clone())
+        .layer(Extension(ClientInfo {
+            ip: Some(ip.parse().unwrap()),
+            scheme: "http".to_string(),
+            host: None,
+        }))
+}
+
+async fn request_token(pool: &DbPool, ip: &str, client_id: &str, secret: &str) -> Result<Response> {
+    let body = format!("grant_type=client_credentials&client_id={}&client_secret={}", client_id, secret);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/oauth/token")
+        .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(Body::from(body))?;
+    Ok(app(pool, ip).oneshot(request).await?)
+}
+
+async fn json(response: Response) -> Result<Value> {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+fn create_client(pool: &DbPool) -> Result<String> {
+    let owner = common::create_user(pool)?;
+    let client = ClientService::create_client_record(pool, Client::new(
+        None,
+        Some(owner.id),
+        "Throttle Test Client".to_string(),
+        Some(SECRET.to_string()),
+        "http://localhost/callback".to_string(),
+        false,
+        false,
+        owner.id,
+    ))?;
+    Ok(client.id.to_string())
+}
+
+fn incidents_for(pool: &DbPool, client_id: &str) -> Result<Vec<SecurityIncident>> {
+    let mut conn = pool.get()?;
+    let incidents = security_incidents::table
+        .filter(security_incidents::incident_type.eq("multiple_failed_auth"))
+        .select(SecurityIncident::as_select())
+        .load::<SecurityIncident>(&mut conn)?;
+    Ok(incidents.into_iter().filter(|incident| incident.metadata["client_id"] == client_id).collect())
+}
+
+fn throttle(config: OAuthConfig) -> TokenThrottleService {
+    TokenThrottleService::new(CacheDriver::Memory(Arc::new(MemoryCache::new(None))), config)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_bad_client_secrets_lock_out_with_retry_after() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let client_id = create_client(&pool)?;
+    let max_failures = OAuthConfig::from_env()?.client_auth_max_failures;
+
+    for _ in 0..max_failures {
+        let response = request_token(&pool, "203.0.113.10", &client_id, "wrong-secret").await?;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(response.headers().get(header::RETRY_AFTER).is_none());
+    }
+
+    // Locked out, even with the right secret
+    let response = request_token(&pool, "203.0.113.10", &client_id, SECRET).await?;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let retry_after: u64 = response.headers()[header::RETRY_AFTER].to_str()?.parse()?;
+    assert!(retry_after > 0);
+    assert!(response.headers().contains_key(header::WWW_AUTHENTICATE));
+    assert_eq!(json(response).await?["error"], "invalid_client");
+
+    // The lockout is per IP, so the client still works elsewhere
+    let response = request_token(&pool, "198.51.100.20", &client_id, SECRET).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let incidents = incidents_for(&pool, &client_id)?;
+    assert_eq!(incidents.len(), 1);
+    assert_eq!(incidents[0].metadata["ip"], "203.0.113.10");
+    assert_eq!(incidents[0].metadata["failed_attempts"], max_failures);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_successful_authentication_resets_failures() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let client_id = create_client(&pool)?;
+    let max_failures = OAuthConfig::from_env()?.client_auth_max_failures;
+
+    for _ in 0..max_failures - 1 {
+        request_token(&pool, "203.0.113.30", &client_id, "wrong-secret").await?;
+    }
+    assert_eq!(request_token(&pool, "203.0.113.30", &client_id, SECRET).await?.status(), StatusCode::OK);
+
+    let response = request_token(&pool, "203.0.113.30", &client_id, "wrong-secret").await?;
+    assert!(response.headers().get(header::RETRY_AFTER).is_none());
+    assert!(incidents_for(&pool, &client_id)?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_lockouts_back_off_exponentially() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let throttle = throttle(OAuthConfig {
+        client_auth_max_failures: 3,
+        client_auth_lockout_secs: 30,
+        client_auth_lockout_max_secs: 200,
+        ..OAuthConfig::from_env()?
+    });
+
+    assert_eq!(throttle.lockout_for(2), None);
+    assert_eq!(throttle.lockout_for(3), Some(Duration::from_secs(30)));
+    assert_eq!(throttle.lockout_for(4), Some(Duration::from_secs(60)));
+    assert_eq!(throttle.lockout_for(5), Some(Duration::from_secs(120)));
+    assert_eq!(throttle.lockout_for(6), Some(Duration::from_secs(200)));
+    assert_eq!(throttle.lockout_for(90), Some(Duration::from_secs(200)));
+
+    let client_id = ulid::Ulid::new().to_string();
+    let mut lockouts = Vec::new();
+    for _ in 0..4 {
+        lockouts.push(throttle.record_failure(&pool, &client_id, "203.0.113.40").await);
+    }
+    assert_eq!(lockouts, vec![None, None, Some(Duration::from_secs(30)), Some(Duration::from_secs(60))]);
+
+    let throttled = throttle.attempt(&client_id, "203.0.113.40").await.unwrap_err();
+    assert_eq!(throttled.reason, TokenThrottleReason::ClientAuthLockout);
+    assert!((1..=60).contains(&throttled.retry_after));
+    assert_eq!(incidents_for(&pool, &client_id)?.len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ip_rate_limit_throttles_token_requests() -> Result<()> {
+    let throttle = throttle(OAuthConfig {
+        token_rate_limit_per_ip: 3,
+        ..OAuthConfig::from_env()?
+    });
+
+    for n in 0..3 {
+        assert!(throttle.attempt(&format!("client-{}", n), "203.0.113.50").await.is_ok());
+    }
+
+    let throttled = throttle.attempt("client-4", "203.0.113.50").await.unwrap_err();
+    assert_eq!(throttled.reason, TokenThrottleReason::IpRateLimit);
+    assert!((1..=60).contains(&throttled.retry_after));
+
+    // Other addresses keep their own budget
+    assert!(throttle.attempt("client-4", "203.0.113.51").await.is_ok());
+    Ok(())
+}
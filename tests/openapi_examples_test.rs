@@ -0,0 +1,58 @@
+//! OpenAPI Example Tests
+//!
+//! These tests verify that factory-backed models get generated examples in
+//! the OpenAPI output and that hand-written examples still take precedence.
+
+use rustaxum::app::docs::examples::FactoryExamples;
+use rustaxum::app::docs::ApiDoc;
+use rustaxum::app::models::country::CountryResponse;
+use rustaxum::database::factories::Factory;
+use serde_json::{json, Value};
+use utoipa::openapi::schema::{ObjectBuilder, Type};
+use utoipa::OpenApi;
+
+fn schema(spec: &Value, name: &str) -> Value {
+    spec["components"]["schemas"][name].clone()
+}
+
+#[test]
+fn test_factory_backed_model_has_generated_example() {
+    let spec: Value = serde_json::from_str(&ApiDoc::openapi_json()).unwrap();
+    let example = &schema(&spec, "CountryResponse")["examples"][0];
+
+    assert!(example.is_object(), "CountryResponse has no generated example");
+    assert!(!example["name"].as_str().unwrap().is_empty());
+    assert_eq!(example["iso_code"].as_str().unwrap().len(), 2);
+    assert!(example["phone_code"].as_str().unwrap().starts_with('+'));
+    assert_eq!(example["id"].as_str().unwrap().len(), 26);
+}
+
+#[test]
+fn test_field_examples_override_generated_values() {
+    let spec: Value = serde_json::from_str(&ApiDoc::openapi_json()).unwrap();
+    let example = &schema(&spec, "CreateCountryRequest")["examples"][0];
+
+    assert_eq!(example["name"], "United States");
+    assert_eq!(example["iso_code"], "US");
+    assert_eq!(example["phone_code"], "+1");
+}
+
+#[test]
+fn test_schema_example_is_kept() {
+    let mut object = ObjectBuilder::new()
+        .schema_type(Type::Object)
+        .examples([json!({ "name": "Hand written" })])
+        .build();
+
+    FactoryExamples::apply(&mut object, CountryResponse::example());
+
+    assert_eq!(object.examples, vec![json!({ "name": "Hand written" })]);
+}
+
+#[test]
+fn test_models_without_factory_are_untouched() {
+    let spec: Value = serde_json::from_str(&ApiDoc::openapi_json()).unwrap();
+
+    assert!(schema(&spec, "Province").get("examples").is_none());
+    assert_eq!(CountryResponse::make_many(3).len(), 3);
+}
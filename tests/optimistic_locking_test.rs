@@ -0,0 +1,160 @@
+//! Optimistic Locking Tests
+//!
+//! These tests verify that updating an organization bumps its lock version,
+//! that an update based on an older version is rejected as stale without
+//! changing the row, and that `If-Match` versions are parsed.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use rustaxum::app::models::organization::{CreateOrganization, Organization, UpdateOrganization};
+use rustaxum::app::models::organization_domain::CreateOrganizationDomain;
+use rustaxum::app::models::organization_type::CreateOrganizationType;
+use rustaxum::app::services::organization_domain_service::OrganizationDomainService;
+use rustaxum::app::services::organization_service::OrganizationService;
+use rustaxum::app::services::organization_type_service::OrganizationTypeService;
+use rustaxum::app::traits::optimistic_locking::if_match_version;
+use rustaxum::app::traits::{OptimisticLocking, StaleModelError};
+use rustaxum::database::DbPool;
+use serial_test::serial;
+
+async fn create_organization(pool: &DbPool, user_id: &str) -> Result<Organization> {
+    let domain = OrganizationDomainService::create(pool, CreateOrganizationDomain {
+        code: Some(ulid::Ulid::new().to_string()),
+        name: "Locking Domain".to_string(),
+        description: None,
+    }, user_id).await?;
+
+    let organization_type = OrganizationTypeService::create(pool, CreateOrganizationType {
+        domain_id: domain.id,
+        code: Some(ulid::Ulid::new().to_string()),
+        name: "Department".to_string(),
+        description: None,
+        level: 1,
+    }, user_id).await?;
+
+    OrganizationService::create(pool, CreateOrganization {
+        domain_id: domain.id,
+        type_id: organization_type.id,
+        name: "Locking Organization".to_string(),
+        parent_id: None,
+        code: Some(ulid::Ulid::new().to_string()),
+        address: None,
+        authorized_capital: None,
+        business_activities: None,
+        contact_persons: None,
+        description: None,
+        email: None,
+        establishment_date: None,
+        governance_structure: None,
+        legal_status: None,
+        paid_capital: None,
+        path: None,
+        phone: None,
+        registration_number: None,
+        tax_number: None,
+        website: None,
+    }, user_id).await
+}
+
+fn rename(name: &str, lock_version: i32) -> UpdateOrganization {
+    UpdateOrganization {
+        domain_id: None,
+        type_id: None,
+        name: Some(name.to_string()),
+        parent_id: None,
+        code: None,
+        address: None,
+        authorized_capital: None,
+        business_activities: None,
+        contact_persons: None,
+        description: None,
+        email: None,
+        establishment_date: None,
+        governance_structure: None,
+        legal_status: None,
+        paid_capital: None,
+        path: None,
+        phone: None,
+        registration_number: None,
+        tax_number: None,
+        website: None,
+        is_active: None,
+        lock_version: Some(lock_version),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_versioned_update_bumps_lock_version() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let organization = create_organization(&pool, &user.id.to_string()).await?;
+    assert_eq!(organization.lock_version, 1);
+
+    let updated = OrganizationService::update(
+        &pool,
+        organization.id.to_string(),
+        rename("Renamed Organization", organization.lock_version),
+        &user.id.to_string(),
+    ).await?;
+
+    assert_eq!(updated.name, "Renamed Organization");
+    assert_eq!(updated.lock_version, 2);
+    assert_eq!(updated.to_response().lock_version, 2);
+    assert_eq!(updated.etag(), HeaderValue::from_static("\"2\""));
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_stale_version_is_rejected_with_conflict() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let organization = create_organization(&pool, &user.id.to_string()).await?;
+    let id = organization.id.to_string();
+
+    // Two clients read version 1; the first one to write wins
+    OrganizationService::update(&pool, id.clone(), rename("First Edit", 1), &user.id.to_string()).await?;
+    let error = OrganizationService::update(&pool, id.clone(), rename("Second Edit", 1), &user.id.to_string())
+        .await
+        .unwrap_err();
+
+    assert_eq!(StaleModelError::status_code(&error), Some(StatusCode::CONFLICT));
+    assert_eq!(error.downcast_ref::<StaleModelError>().unwrap().current_version, 2);
+
+    let stored = OrganizationService::find_by_id(&pool, id)?.unwrap();
+    assert_eq!(stored.name, "First Edit");
+    assert_eq!(stored.lock_version, 2);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_missing_organization_is_not_a_conflict() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+
+    let error = OrganizationService::update(&pool, ulid::Ulid::new().to_string(), rename("Nobody", 1), &user.id.to_string())
+        .await
+        .unwrap_err();
+
+    assert_eq!(StaleModelError::status_code(&error), None);
+    Ok(())
+}
+
+#[test]
+fn test_if_match_versions_are_parsed() {
+    let version = |value: &'static str| {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_static(value));
+        if_match_version(&headers)
+    };
+
+    assert_eq!(version("\"3\""), Some(3));
+    assert_eq!(version("W/\"4\""), Some(4));
+    assert_eq!(version("5"), Some(5));
+    assert_eq!(version("*"), None);
+    assert_eq!(if_match_version(&HeaderMap::new()), None);
+}
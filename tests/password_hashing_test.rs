@@ -0,0 +1,133 @@
+//! Password Hashing Tests
+//!
+//! These tests verify Argon2id and bcrypt round-trips, detection of hashes
+//! that need rehashing, and that a bcrypt password is upgraded to Argon2id
+//! when the user logs in.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::HeaderMap;
+use diesel::prelude::*;
+use rustaxum::app::models::user::LoginRequest;
+use rustaxum::app::services::auth_service::AuthService;
+use rustaxum::app::services::login_fingerprint_service::ClientFingerprint;
+use rustaxum::app::services::user_service::UserService;
+use rustaxum::app::utils::Hash;
+use rustaxum::config::hashing::{HashDriver, HashingConfig};
+use rustaxum::schema::sys_users;
+use serial_test::serial;
+
+fn config(driver: HashDriver) -> HashingConfig {
+    HashingConfig {
+        driver,
+        bcrypt_cost: 4,
+        argon_memory: 19456,
+        argon_time: 2,
+        argon_threads: 1,
+    }
+}
+
+#[test]
+fn test_argon2id_round_trip() -> Result<()> {
+    let hash = Hash::new(config(HashDriver::Argon2id));
+
+    let hashed = hash.make("correct horse")?;
+
+    assert!(hashed.starts_with("$argon2id$"));
+    assert!(hash.check("correct horse", &hashed)?);
+    assert!(!hash.check("wrong horse", &hashed)?);
+    assert!(!hash.needs_rehash(&hashed));
+    Ok(())
+}
+
+#[test]
+fn test_bcrypt_round_trip() -> Result<()> {
+    let hash = Hash::new(config(HashDriver::Bcrypt));
+
+    let hashed = hash.make("correct horse")?;
+
+    assert!(hashed.starts_with("$2b$04$"));
+    assert!(hash.check("correct horse", &hashed)?);
+    assert!(!hash.check("wrong horse", &hashed)?);
+    assert!(!hash.needs_rehash(&hashed));
+    Ok(())
+}
+
+#[test]
+fn test_check_accepts_either_algorithm() -> Result<()> {
+    let bcrypt = Hash::new(config(HashDriver::Bcrypt)).make("secret")?;
+    let argon = Hash::new(config(HashDriver::Argon2id)).make("secret")?;
+
+    assert!(Hash::new(config(HashDriver::Argon2id)).check("secret", &bcrypt)?);
+    assert!(Hash::new(config(HashDriver::Bcrypt)).check("secret", &argon)?);
+    assert!(Hash::new(config(HashDriver::Argon2id)).check("secret", "plaintext").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_needs_rehash_detects_weaker_algorithm_and_old_parameters() -> Result<()> {
+    let argon = Hash::new(config(HashDriver::Argon2id));
+    let bcrypt = Hash::new(config(HashDriver::Bcrypt));
+
+    // Another algorithm
+    assert!(argon.needs_rehash(&bcrypt.make("secret")?));
+    assert!(bcrypt.needs_rehash(&argon.make("secret")?));
+
+    // Same algorithm, stronger configuration
+    let stronger_argon = Hash::new(HashingConfig { argon_time: 3, ..config(HashDriver::Argon2id) });
+    assert!(stronger_argon.needs_rehash(&argon.make("secret")?));
+    let stronger_bcrypt = Hash::new(HashingConfig { bcrypt_cost: 5, ..config(HashDriver::Bcrypt) });
+    assert!(stronger_bcrypt.needs_rehash(&bcrypt.make("secret")?));
+
+    assert!(argon.needs_rehash("not a hash"));
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_login_upgrades_bcrypt_password_to_argon2id() -> Result<()> {
+    std::env::set_var("HASH_DRIVER", "argon2id");
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+
+    let bcrypt_hash = Hash::new(config(HashDriver::Bcrypt)).make("password123")?;
+    let mut conn = pool.get()?;
+    diesel::update(sys_users::table.filter(sys_users::id.eq(user.id.to_string())))
+        .set(sys_users::password.eq(&bcrypt_hash))
+        .execute(&mut conn)?;
+
+    let login = LoginRequest {
+        email: user.email.clone(),
+        password: "password123".to_string(),
+    };
+    AuthService::login(&pool, login, &ClientFingerprint::from_headers(&HeaderMap::new(), None)).await?;
+
+    let stored = UserService::find_by_id(&pool, user.id.to_string())?.unwrap().password;
+    assert!(stored.starts_with("$argon2id$"));
+    assert!(AuthService::verify_password("password123", &stored)?);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_failed_login_keeps_stored_hash() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+
+    let bcrypt_hash = Hash::new(config(HashDriver::Bcrypt)).make("password123")?;
+    let mut conn = pool.get()?;
+    diesel::update(sys_users::table.filter(sys_users::id.eq(user.id.to_string())))
+        .set(sys_users::password.eq(&bcrypt_hash))
+        .execute(&mut conn)?;
+
+    let login = LoginRequest {
+        email: user.email.clone(),
+        password: "wrong".to_string(),
+    };
+    assert!(AuthService::login(&pool, login, &ClientFingerprint::from_headers(&HeaderMap::new(), None)).await.is_err());
+
+    let stored = UserService::find_by_id(&pool, user.id.to_string())?.unwrap().password;
+    assert_eq!(stored, bcrypt_hash);
+    Ok(())
+}
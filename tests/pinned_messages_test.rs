@@ -0,0 +1,91 @@
+//! Pinned Message Integration Tests
+//!
+//! These tests verify pinning, the per-conversation pin limit, role
+//! checks, and unpinning.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use rustaxum::app::models::conversation_participant::ParticipantRole;
+use rustaxum::app::services::pinned_message_service::{PinError, PinMessageRequest, PinnedMessageService};
+use rustaxum::config::messaging::MessagingConfig;
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn test_pin_limit_and_unpin() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let service = PinnedMessageService::with_config(MessagingConfig {
+        max_pinned_messages: 2,
+        pin_requires_moderator: true,
+        ..MessagingConfig::from_env()?
+    });
+
+    let admin = common::create_user(&pool)?;
+    let member = common::create_user(&pool)?;
+    let admin_device = common::create_device(&pool, &admin)?;
+    let member_device = common::create_device(&pool, &member)?;
+    let conversation = common::create_conversation(&pool, &admin)?;
+    common::add_participant_with_role(&pool, &conversation, &admin, ParticipantRole::Admin)?;
+    common::add_participant(&pool, &conversation, &member)?;
+
+    let conversation_id = conversation.id.to_string();
+    let admin_id = admin.id.to_string();
+
+    let mut messages = Vec::new();
+    for _ in 0..3 {
+        messages.push(common::create_message(&pool, &conversation, &admin, &admin_device)?);
+    }
+
+    // Regular members cannot pin in a group conversation
+    let error = service.pin(&pool, &conversation_id, &member.id.to_string(), PinMessageRequest {
+        message_id: messages[0].id,
+        device_id: member_device.id,
+    }).await.expect_err("members must not pin");
+    assert_eq!(PinError::status_code(&error), StatusCode::FORBIDDEN);
+
+    for message in &messages[..2] {
+        let (_, created) = service.pin(&pool, &conversation_id, &admin_id, PinMessageRequest {
+            message_id: message.id,
+            device_id: admin_device.id,
+        }).await?;
+        assert!(created);
+    }
+
+    // Pinning an already pinned message is not a new pin
+    let (repinned, created) = service.pin(&pool, &conversation_id, &admin_id, PinMessageRequest {
+        message_id: messages[0].id,
+        device_id: admin_device.id,
+    }).await?;
+    assert!(repinned.is_active);
+    assert!(!created);
+
+    let error = service.pin(&pool, &conversation_id, &admin_id, PinMessageRequest {
+        message_id: messages[2].id,
+        device_id: admin_device.id,
+    }).await.expect_err("third pin must exceed the limit");
+    assert_eq!(PinError::status_code(&error), StatusCode::CONFLICT);
+
+    let unpinned = service.unpin(&pool, &conversation_id, &messages[0].id.to_string(), &admin_id).await?;
+    assert!(!unpinned.is_active);
+    assert!(unpinned.unpinned_at.is_some());
+
+    // Unpinning frees a slot
+    service.pin(&pool, &conversation_id, &admin_id, PinMessageRequest {
+        message_id: messages[2].id,
+        device_id: admin_device.id,
+    }).await?;
+
+    let pins = service.list(&pool, &conversation_id, &member.id.to_string())?;
+    let pinned_ids: Vec<_> = pins.iter().map(|p| p.message_id).collect();
+    assert_eq!(pinned_ids.len(), 2);
+    assert!(pinned_ids.contains(&messages[1].id));
+    assert!(pinned_ids.contains(&messages[2].id));
+
+    let error = service.unpin(&pool, &conversation_id, &messages[0].id.to_string(), &admin_id).await
+        .expect_err("message is no longer pinned");
+    assert_eq!(PinError::status_code(&error), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
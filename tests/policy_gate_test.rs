@@ -0,0 +1,166 @@
+//! Policy Gate Tests
+//!
+//! These tests verify that `make:policy --model=...` generates a policy with a
+//! method for every ability the gate dispatches and registers it with the
+//! gate, and that a controller guarded by `gate().authorize(...)` answers 403
+//! when the registered policy denies the ability.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    extract::Path,
+    http::{header, Method, Request, StatusCode},
+    response::Json,
+    routing::delete,
+    Router,
+};
+use rustaxum::app::gate::{gate, Gate, Policy, ABILITIES};
+use rustaxum::app::http::middleware::auth_guard::AuthUser;
+use rustaxum::app::models::{DieselUlid, HasModelType};
+use rustaxum::app::providers::auth_service_provider::REGISTRATION_MARKER;
+use rustaxum::app::services::auth_service::AuthService;
+use rustaxum::cli::generators::policy::generate_policy;
+use serde_json::{json, Value};
+use serial_test::serial;
+use tower::ServiceExt;
+
+#[derive(Debug, Clone)]
+struct Post {
+    id: String,
+    user_id: String,
+}
+
+impl HasModelType for Post {
+    fn model_type() -> &'static str {
+        "Post"
+    }
+}
+
+/// `make:policy Post --model=Post` output with `update` filled in and `delete` left denying
+#[derive(Debug, Clone, Default)]
+struct PostPolicy;
+
+#[async_trait]
+impl Policy<Post> for PostPolicy {
+    async fn view(&self, _user: &AuthUser, _model: &Post) -> Result<bool> {
+        Ok(true)
+    }
+
+    async fn update(&self, user: &AuthUser, model: &Post) -> Result<bool> {
+        Ok(user.user_id == model.user_id)
+    }
+
+    async fn delete(&self, _user: &AuthUser, _model: &Post) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+fn find_post(id: String, owner: &str) -> Post {
+    Post { id, user_id: owner.to_string() }
+}
+
+async fn update_post(auth_user: AuthUser, Path(id): Path<String>) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let post = find_post(id, &auth_user.user_id);
+    gate().authorize(&auth_user, "update", &post).await?;
+    Ok(Json(json!({ "id": post.id, "updated": true })))
+}
+
+async fn destroy_post(auth_user: AuthUser, Path(id): Path<String>) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let post = find_post(id, &auth_user.user_id);
+    gate().authorize(&auth_user, "delete", &post).await?;
+    Ok(Json(json!({ "id": post.id, "deleted": true })))
+}
+
+async fn send(method: Method, uri: &str) -> Result<(StatusCode, Value)> {
+    let app = Router::new().route("/posts/{id}", delete(destroy_post).put(update_post));
+    let token = AuthService::generate_access_token(&DieselUlid::new().to_string(), 3600)?;
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())?;
+
+    let response = app.oneshot(request).await?;
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok((status, serde_json::from_slice(&body)?))
+}
+
+#[tokio::test]
+#[serial]
+async fn test_denied_delete_answers_403_through_the_controller_guard() -> Result<()> {
+    gate().policy::<Post, _>(PostPolicy);
+
+    let (status, body) = send(Method::DELETE, "/posts/42").await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error"], "This action is unauthorized.");
+
+    let (status, body) = send(Method::PUT, "/posts/42").await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["updated"], true);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_model_without_policy_is_denied() -> Result<()> {
+    let gate = Gate::new();
+    let user = AuthUser { user_id: "owner".to_string(), auth_method: "jwt".to_string(), impersonator_id: None };
+    let post = find_post("1".to_string(), "owner");
+
+    assert!(gate.denies(&user, "view", &post).await);
+
+    gate.policy::<Post, _>(PostPolicy);
+    assert!(gate.allows(&user, "view", &post).await);
+    assert!(gate.allows(&user, "update", &post).await);
+    assert!(gate.denies(&user, "delete", &post).await);
+    assert!(gate.authorize_for::<Post>(&user, "create").await.is_err());
+    assert!(gate.check(&user, "publish", &post).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_make_policy_generates_and_registers_the_policy() -> Result<()> {
+    let original_dir = std::env::current_dir()?;
+    let project = std::env::temp_dir().join(format!("make_policy_{}", ulid::Ulid::new()));
+    std::fs::create_dir_all(project.join("src/app/providers"))?;
+    std::fs::create_dir_all(project.join("src/app/models"))?;
+    std::fs::write(
+        project.join("src/app/providers/auth_service_provider.rs"),
+        include_str!("../src/app/providers/auth_service_provider.rs"),
+    )?;
+    std::fs::write(project.join("src/app/models/post.rs"), "pub struct Post;\n")?;
+
+    std::env::set_current_dir(&project)?;
+    let generated = generate_policy("Post", Some("Post".to_string())).await;
+    std::env::set_current_dir(&original_dir)?;
+    generated?;
+
+    let policy = std::fs::read_to_string(project.join("src/app/policies/post_policy.rs"))?;
+    assert!(policy.contains("use crate::app::models::post::Post;"));
+    assert!(policy.contains("impl Policy<Post> for PostPolicy"));
+    for ability in ABILITIES {
+        assert!(policy.contains(&format!("async fn {}(", ability)), "no {} method in:\n{}", ability, policy);
+    }
+
+    let modules = std::fs::read_to_string(project.join("src/app/policies/mod.rs"))?;
+    assert!(modules.contains("pub mod post_policy;"));
+
+    let provider = std::fs::read_to_string(project.join("src/app/providers/auth_service_provider.rs"))?;
+    let registration = "gate.policy::<crate::app::models::post::Post, _>(crate::app::policies::post_policy::PostPolicy::new());";
+    let registered_at = provider.find(registration).expect("policy registered with the gate");
+    assert!(registered_at < provider.find(REGISTRATION_MARKER).unwrap());
+
+    // Running it again does not register the policy twice
+    std::env::set_current_dir(&project)?;
+    let generated = generate_policy("Post", Some("Post".to_string())).await;
+    std::env::set_current_dir(&original_dir)?;
+    generated?;
+    let provider = std::fs::read_to_string(project.join("src/app/providers/auth_service_provider.rs"))?;
+    assert_eq!(provider.matches(registration).count(), 1);
+
+    std::fs::remove_dir_all(project)?;
+    Ok(())
+}
@@ -0,0 +1,116 @@
+//! Poll Voting Integration Tests
+//!
+//! These tests verify vote casting, vote changes, single-choice
+//! enforcement, and rejection of votes after a poll is closed.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use rustaxum::app::models::polls::Poll;
+use rustaxum::app::services::poll_service::{CastVoteRequest, CreatePollRequest, PollError, PollService};
+use serial_test::serial;
+
+struct PollFixture {
+    pool: rustaxum::database::DbPool,
+    creator: rustaxum::app::models::user::User,
+    voter: rustaxum::app::models::user::User,
+    voter_device: rustaxum::app::models::device::Device,
+    poll: Poll,
+}
+
+async fn setup_poll(allows_multiple_votes: bool) -> Result<PollFixture> {
+    let pool = common::setup_test_db().await?;
+
+    let creator = common::create_user(&pool)?;
+    let voter = common::create_user(&pool)?;
+    let creator_device = common::create_device(&pool, &creator)?;
+    let voter_device = common::create_device(&pool, &voter)?;
+    let conversation = common::create_conversation(&pool, &creator)?;
+    common::add_participant(&pool, &conversation, &creator)?;
+    common::add_participant(&pool, &conversation, &voter)?;
+    let message = common::create_message(&pool, &conversation, &creator, &creator_device)?;
+
+    let poll = PollService::create(
+        &pool,
+        &conversation.id.to_string(),
+        &creator.id.to_string(),
+        CreatePollRequest {
+            message_id: message.id,
+            encrypted_question: "encrypted_question".to_string(),
+            encrypted_options: "encrypted_options".to_string(),
+            option_count: 3,
+            allows_multiple_votes,
+            is_anonymous: false,
+            expires_at: None,
+        },
+    ).await?;
+
+    Ok(PollFixture { pool, creator, voter, voter_device, poll })
+}
+
+fn vote_request(fixture: &PollFixture, option_indexes: Vec<i32>) -> CastVoteRequest {
+    CastVoteRequest {
+        device_id: fixture.voter_device.id,
+        option_indexes,
+        encrypted_vote_data: "encrypted_vote".to_string(),
+        vote_algorithm: "aes-256-gcm".to_string(),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_cast_and_change_vote() -> Result<()> {
+    let fixture = setup_poll(false).await?;
+    let poll_id = fixture.poll.id.to_string();
+    let voter_id = fixture.voter.id.to_string();
+
+    let results = PollService::vote(&fixture.pool, &poll_id, &voter_id, vote_request(&fixture, vec![0])).await?;
+    assert_eq!(results.option_counts, vec![1, 0, 0]);
+    assert_eq!(results.my_selection, vec![0]);
+
+    let results = PollService::vote(&fixture.pool, &poll_id, &voter_id, vote_request(&fixture, vec![2])).await?;
+    assert_eq!(results.total_voters, 1);
+    assert_eq!(results.option_counts, vec![0, 0, 1]);
+    assert_eq!(results.my_selection, vec![2]);
+
+    // Single-choice polls reject multiple selections
+    let error = PollService::vote(&fixture.pool, &poll_id, &voter_id, vote_request(&fixture, vec![0, 1])).await
+        .expect_err("single-choice poll must reject two options");
+    assert_eq!(PollError::status_code(&error), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // The creator has not voted, so their selection is empty
+    let results = PollService::results(&fixture.pool, &poll_id, &fixture.creator.id.to_string())?;
+    assert!(results.my_selection.is_empty());
+    assert_eq!(results.option_counts, vec![0, 0, 1]);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_vote_after_close_is_rejected() -> Result<()> {
+    let fixture = setup_poll(true).await?;
+    let poll_id = fixture.poll.id.to_string();
+    let voter_id = fixture.voter.id.to_string();
+
+    let results = PollService::vote(&fixture.pool, &poll_id, &voter_id, vote_request(&fixture, vec![0, 1])).await?;
+    assert_eq!(results.option_counts, vec![1, 1, 0]);
+
+    // Only the creator or an admin may close the poll
+    let error = PollService::close(&fixture.pool, &poll_id, &voter_id).await
+        .expect_err("regular participants must not close the poll");
+    assert_eq!(PollError::status_code(&error), StatusCode::FORBIDDEN);
+
+    let results = PollService::close(&fixture.pool, &poll_id, &fixture.creator.id.to_string()).await?;
+    assert!(results.poll.is_closed);
+
+    let error = PollService::vote(&fixture.pool, &poll_id, &voter_id, vote_request(&fixture, vec![2])).await
+        .expect_err("votes after close must be rejected");
+    assert_eq!(PollError::status_code(&error), StatusCode::CONFLICT);
+
+    let results = PollService::results(&fixture.pool, &poll_id, &voter_id)?;
+    assert_eq!(results.option_counts, vec![1, 1, 0]);
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+//! Prekey Bundle Integration Tests
+//!
+//! These tests verify key upload, that each one-time prekey is handed
+//! out exactly once, and the behaviour once a device runs out.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use rustaxum::app::broadcasting::websocket::websocket_manager;
+use rustaxum::app::services::prekey_service::{
+    FetchBundleRequest, OneTimePrekey, PrekeyError, PrekeyService, SignedPrekey, UploadKeysRequest,
+};
+use rustaxum::config::messaging::MessagingConfig;
+use serial_test::serial;
+use std::time::Duration;
+
+fn service() -> Result<PrekeyService> {
+    Ok(PrekeyService::with_config(MessagingConfig {
+        prekey_low_threshold: 2,
+        ..MessagingConfig::from_env()?
+    }))
+}
+
+fn prekeys(ids: std::ops::Range<i32>) -> Vec<OneTimePrekey> {
+    ids.map(|key_id| OneTimePrekey { key_id, public_key: format!("prekey_public_{}", key_id) }).collect()
+}
+
+#[tokio::test]
+#[serial]
+async fn test_upload_keys() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let service = service()?;
+
+    let user = common::create_user(&pool)?;
+    let device = common::create_device(&pool, &user)?;
+    let user_id = user.id.to_string();
+    let device_id = device.id.to_string();
+
+    let status = service.upload(&pool, &user_id, &device_id, UploadKeysRequest {
+        identity_public_key: Some(device.identity_public_key.clone()),
+        signed_prekey: Some(SignedPrekey {
+            key_id: 2,
+            public_key: "signed_prekey_public_2".to_string(),
+            signature: "signature_2".to_string(),
+        }),
+        one_time_prekeys: prekeys(1..4),
+    })?;
+    assert_eq!(status.signed_prekey_id, 2);
+    assert_eq!(status.available_one_time_prekeys, 3);
+    assert!(!status.replenish);
+
+    // Re-uploading a key ID is rejected
+    let error = service.upload(&pool, &user_id, &device_id, UploadKeysRequest {
+        identity_public_key: None,
+        signed_prekey: None,
+        one_time_prekeys: prekeys(3..5),
+    }).expect_err("prekey IDs must not be reused");
+    assert_eq!(PrekeyError::status_code(&error), StatusCode::CONFLICT);
+    assert_eq!(service.status(&pool, &user_id, &device_id)?.available_one_time_prekeys, 3);
+
+    let error = service.upload(&pool, &user_id, &device_id, UploadKeysRequest {
+        identity_public_key: Some("another_identity_key".to_string()),
+        signed_prekey: None,
+        one_time_prekeys: Vec::new(),
+    }).expect_err("identity key must not change");
+    assert_eq!(PrekeyError::status_code(&error), StatusCode::CONFLICT);
+
+    // Other users cannot publish keys for the device
+    let other = common::create_user(&pool)?;
+    let error = service.upload(&pool, &other.id.to_string(), &device_id, UploadKeysRequest {
+        identity_public_key: None,
+        signed_prekey: None,
+        one_time_prekeys: prekeys(10..11),
+    }).expect_err("only the owner can upload");
+    assert_eq!(PrekeyError::status_code(&error), StatusCode::UNPROCESSABLE_ENTITY);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_fetch_consumes_each_prekey_once_until_exhausted() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let service = service()?;
+
+    let owner = common::create_user(&pool)?;
+    let owner_device = common::create_device(&pool, &owner)?;
+    let requester = common::create_user(&pool)?;
+    let requester_device = common::create_device(&pool, &requester)?;
+    let owner_device_id = owner_device.id.to_string();
+    let requester_id = requester.id.to_string();
+
+    service.upload(&pool, &owner.id.to_string(), &owner_device_id, UploadKeysRequest {
+        identity_public_key: None,
+        signed_prekey: None,
+        one_time_prekeys: prekeys(1..4),
+    })?;
+
+    let mut receiver = websocket_manager().await.subscribe(&format!("user.{}", owner.id)).await;
+
+    let mut handed_out = Vec::new();
+    for _ in 0..3 {
+        let bundle = service.fetch_bundle(&pool, &requester_id, &owner_device_id, FetchBundleRequest {
+            device_id: requester_device.id,
+        }).await?;
+        assert_eq!(bundle.identity_public_key, owner_device.identity_public_key);
+        assert_eq!(bundle.signed_prekey.key_id, owner_device.signed_prekey_id);
+        handed_out.push(bundle.one_time_prekey.expect("a one-time prekey is available").key_id);
+    }
+    assert_eq!(handed_out, vec![1, 2, 3]);
+
+    // The owner is warned once the supply drops below the threshold
+    let event = tokio::time::timeout(Duration::from_secs(2), receiver.recv()).await??;
+    assert_eq!(event.event, "prekeys.low");
+    assert_eq!(event.data["device_id"], owner_device_id);
+
+    // Exhausted devices still return a bundle, just without a one-time prekey
+    let bundle = service.fetch_bundle(&pool, &requester_id, &owner_device_id, FetchBundleRequest {
+        device_id: requester_device.id,
+    }).await?;
+    assert!(bundle.one_time_prekey.is_none());
+
+    let status = service.status(&pool, &owner.id.to_string(), &owner_device_id)?;
+    assert_eq!(status.available_one_time_prekeys, 0);
+    assert!(status.replenish);
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+//! Presence Channel Tests
+//!
+//! These tests verify that `WebSocketManager` tracks who is subscribed to a
+//! presence channel, that other subscribers get `member_added` and
+//! `member_removed`, that a user connected from two devices is listed once
+//! with a connection count, and that leaving cleans up membership.
+
+use anyhow::Result;
+use rustaxum::app::broadcasting::websocket::WebSocketManager;
+use rustaxum::app::broadcasting::{BroadcastDriver, BroadcastMessage, WebSocketDriver};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::Receiver;
+
+const CHANNEL: &str = "presence:team.1";
+
+async fn next_event(receiver: &mut Receiver<BroadcastMessage>) -> Result<BroadcastMessage> {
+    Ok(tokio::time::timeout(Duration::from_secs(1), receiver.recv()).await??)
+}
+
+#[tokio::test]
+async fn test_members_are_announced_and_listed() -> Result<()> {
+    let manager = WebSocketManager::new();
+    let mut watcher = manager.subscribe(CHANNEL).await;
+
+    manager.join_presence(CHANNEL, "conn-1", "ada", serde_json::json!({ "name": "Ada" })).await;
+    manager.join_presence(CHANNEL, "conn-2", "grace", serde_json::json!({ "name": "Grace" })).await;
+
+    let added = next_event(&mut watcher).await?;
+    assert_eq!(added.event, "member_added");
+    assert_eq!(added.data["user_id"], "ada");
+    assert_eq!(added.data["info"]["name"], "Ada");
+    assert_eq!(next_event(&mut watcher).await?.data["user_id"], "grace");
+
+    let members = manager.members(CHANNEL).await;
+    assert_eq!(members.iter().map(|member| member.user_id.as_str()).collect::<Vec<_>>(), vec!["ada", "grace"]);
+
+    manager.leave_presence(CHANNEL, "conn-1", "ada").await;
+    let removed = next_event(&mut watcher).await?;
+    assert_eq!(removed.event, "member_removed");
+    assert_eq!(removed.data["user_id"], "ada");
+    assert_eq!(manager.members(CHANNEL).await.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_same_user_on_two_devices_appears_once() -> Result<()> {
+    let manager = WebSocketManager::new();
+    let mut watcher = manager.subscribe(CHANNEL).await;
+
+    manager.join_presence(CHANNEL, "phone", "ada", serde_json::json!({})).await;
+    let member = manager.join_presence(CHANNEL, "laptop", "ada", serde_json::json!({})).await;
+    assert_eq!(member.connections, 2);
+
+    let members = manager.members(CHANNEL).await;
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0].connections, 2);
+
+    // Only the first connection announces the member
+    assert_eq!(next_event(&mut watcher).await?.event, "member_added");
+
+    // Closing one device keeps the member
+    manager.leave_presence(CHANNEL, "phone", "ada").await;
+    assert_eq!(manager.members(CHANNEL).await[0].connections, 1);
+
+    manager.leave_presence(CHANNEL, "laptop", "ada").await;
+    assert_eq!(next_event(&mut watcher).await?.event, "member_removed");
+    assert!(manager.members(CHANNEL).await.is_empty());
+    assert!(watcher.try_recv().is_err(), "no events beyond one added and one removed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repeated_leave_does_not_remove_other_connections() -> Result<()> {
+    let manager = WebSocketManager::new();
+    manager.join_presence(CHANNEL, "phone", "ada", serde_json::json!({})).await;
+    manager.join_presence(CHANNEL, "laptop", "ada", serde_json::json!({})).await;
+
+    manager.leave_presence(CHANNEL, "phone", "ada").await;
+    manager.leave_presence(CHANNEL, "phone", "ada").await;
+    manager.leave_presence(CHANNEL, "tablet", "ada").await;
+
+    assert_eq!(manager.members(CHANNEL).await[0].connections, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_driver_broadcasts_on_the_presence_channel() -> Result<()> {
+    let manager = Arc::new(WebSocketManager::new());
+    let mut watcher = manager.subscribe(CHANNEL).await;
+
+    WebSocketDriver::with_manager(manager.clone())
+        .broadcast_presence("team.1", serde_json::json!({ "typing": "ada" }))
+        .await?;
+
+    let message = next_event(&mut watcher).await?;
+    assert_eq!(message.channel, CHANNEL);
+    assert_eq!(message.data["typing"], "ada");
+    Ok(())
+}
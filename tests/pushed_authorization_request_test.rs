@@ -0,0 +1,179 @@
+//! Pushed Authorization Request Integration Tests
+//!
+//! These tests verify that a request_uri from the PAR endpoint hydrates the
+//! authorization request exactly once, and that expired or reused request
+//! URIs and plain parameters for PAR-only clients are rejected.
+
+mod common;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use rustaxum::app::http::controllers::oauth::oauth_controller::AuthorizeQuery;
+use rustaxum::app::models::DieselUlid;
+use rustaxum::app::models::oauth::Client;
+use rustaxum::app::services::oauth::{ClientService, PARError, PARService, PushedAuthRequest, StoredPushedRequest};
+use rustaxum::database::DbPool;
+use rustaxum::schema::{oauth_clients, oauth_pushed_requests};
+use serial_test::serial;
+
+const REDIRECT_URI: &str = "http://localhost/callback";
+
+fn create_client(pool: &DbPool) -> Result<Client> {
+    let user = common::create_user(pool)?;
+    ClientService::create_client_record(pool, Client::new(
+        None,
+        Some(user.id),
+        "PAR Test Client".to_string(),
+        None,
+        REDIRECT_URI.to_string(),
+        false,
+        false,
+        user.id,
+    ))
+}
+
+fn pushed_request(client: &Client) -> PushedAuthRequest {
+    PushedAuthRequest {
+        response_type: "code".to_string(),
+        client_id: client.id.to_string(),
+        redirect_uri: REDIRECT_URI.to_string(),
+        scope: Some("openid".to_string()),
+        state: Some("pushed_state".to_string()),
+        code_challenge: Some("E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM".to_string()),
+        code_challenge_method: Some("S256".to_string()),
+        response_mode: None,
+        nonce: None,
+        display: None,
+        prompt: None,
+        max_age: None,
+        ui_locales: None,
+        id_token_hint: None,
+        login_hint: None,
+        acr_values: None,
+        authorization_details: None,
+        request: None,
+        request_uri: None,
+    }
+}
+
+fn authorize_query(client: &Client, request_uri: Option<String>) -> AuthorizeQuery {
+    AuthorizeQuery {
+        response_type: String::new(),
+        client_id: client.id.to_string(),
+        redirect_uri: String::new(),
+        scope: None,
+        state: None,
+        code_challenge: None,
+        code_challenge_method: None,
+        request_uri,
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_request_uri_is_consumed_once() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let client = create_client(&pool)?;
+    let client_id = client.id.to_string();
+
+    let pushed = PARService::create_pushed_request(&pool, &client_id, pushed_request(&client)).await?;
+
+    // The authorization endpoint hydrates the stored parameters
+    let params = authorize_query(&client, Some(pushed.request_uri.clone())).resolve(&pool).await?;
+    assert_eq!(params.response_type, "code");
+    assert_eq!(params.redirect_uri, REDIRECT_URI);
+    assert_eq!(params.state.as_deref(), Some("pushed_state"));
+    assert_eq!(params.code_challenge_method.as_deref(), Some("S256"));
+
+    let consumed = PARService::consume_pushed_request(&pool, &pushed.request_uri, &client_id).await?;
+    assert_eq!(consumed.redirect_uri, REDIRECT_URI);
+
+    // A reused request_uri is rejected
+    let error = PARService::consume_pushed_request(&pool, &pushed.request_uri, &client_id)
+        .await
+        .expect_err("a request_uri is single use");
+    assert!(matches!(error.downcast_ref::<PARError>(), Some(PARError::AlreadyUsed)));
+
+    let error = authorize_query(&client, Some(pushed.request_uri.clone()))
+        .resolve(&pool)
+        .await
+        .err()
+        .expect("a used request_uri cannot start another authorization");
+    assert!(matches!(error.downcast_ref::<PARError>(), Some(PARError::AlreadyUsed)));
+
+    // Another client cannot use the request_uri either
+    let other = create_client(&pool)?;
+    let fresh = PARService::create_pushed_request(&pool, &client_id, pushed_request(&client)).await?;
+    let error = PARService::consume_pushed_request(&pool, &fresh.request_uri, &other.id.to_string())
+        .await
+        .expect_err("request_uri is bound to its client");
+    assert!(matches!(error.downcast_ref::<PARError>(), Some(PARError::NotFound)));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_expired_request_uri_is_rejected() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let client = create_client(&pool)?;
+    let client_id = client.id.to_string();
+
+    let request_uri = format!("urn:ietf:params:oauth:request_uri:{}", DieselUlid::new());
+    let mut conn = pool.get()?;
+    diesel::insert_into(oauth_pushed_requests::table)
+        .values(StoredPushedRequest {
+            id: DieselUlid::new(),
+            request_uri: request_uri.clone(),
+            client_id: client_id.clone(),
+            request_data: serde_json::to_string(&pushed_request(&client))?,
+            expires_at: Utc::now() - Duration::minutes(1),
+            used: false,
+            created_at: Utc::now() - Duration::minutes(11),
+            updated_at: Utc::now() - Duration::minutes(11),
+        })
+        .execute(&mut conn)?;
+
+    let error = authorize_query(&client, Some(request_uri.clone()))
+        .resolve(&pool)
+        .await
+        .err()
+        .expect("an expired request_uri cannot be hydrated");
+    assert!(matches!(error.downcast_ref::<PARError>(), Some(PARError::Expired)));
+
+    let error = PARService::consume_pushed_request(&pool, &request_uri, &client_id)
+        .await
+        .expect_err("an expired request_uri cannot be consumed");
+    assert!(matches!(error.downcast_ref::<PARError>(), Some(PARError::Expired)));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_par_only_client_rejects_plain_parameters() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let client = create_client(&pool)?;
+
+    // Plain parameters are fine until the client requires PAR
+    authorize_query(&client, None).resolve(&pool).await?;
+
+    let mut conn = pool.get()?;
+    diesel::update(oauth_clients::table.find(client.id.to_string()))
+        .set(oauth_clients::require_pushed_authorization_requests.eq(true))
+        .execute(&mut conn)?;
+
+    let error = authorize_query(&client, None)
+        .resolve(&pool)
+        .await
+        .err()
+        .expect("PAR-only clients must push their parameters");
+    assert!(matches!(error.downcast_ref::<PARError>(), Some(PARError::Required)));
+
+    let pushed = PARService::create_pushed_request(&pool, &client.id.to_string(), pushed_request(&client)).await?;
+    let params = authorize_query(&client, Some(pushed.request_uri)).resolve(&pool).await?;
+    assert_eq!(params.redirect_uri, REDIRECT_URI);
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Query Cache Integration Tests
+//!
+//! These tests verify that repeated index queries are served from cache, that
+//! a write through the model-event layer invalidates the cached pages, and
+//! that each tenant's results are cached under their own keys.
+
+mod common;
+
+use anyhow::Result;
+use rustaxum::app::models::organization_domain::CreateOrganizationDomain;
+use rustaxum::app::models::organization_type::{CreateOrganizationType, OrganizationType};
+use rustaxum::app::query_builder::{CacheStatus, QueryCache, QueryParams};
+use rustaxum::app::services::organization_domain_service::OrganizationDomainService;
+use rustaxum::app::services::organization_type_service::OrganizationTypeService;
+use rustaxum::app::models::DieselUlid;
+use serial_test::serial;
+
+fn params_for_domain(domain_id: DieselUlid, page: Option<u32>) -> QueryParams {
+    let mut params = QueryParams::default();
+    params.filter.insert("domain_id".to_string(), serde_json::json!(domain_id.to_string()));
+    params.page = page;
+    params
+}
+
+fn organization_type(domain_id: DieselUlid, name: &str) -> CreateOrganizationType {
+    CreateOrganizationType {
+        domain_id,
+        code: Some(ulid::Ulid::new().to_string()),
+        name: name.to_string(),
+        description: None,
+        level: 1,
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_repeated_query_hits_cache_until_write() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let user_id = user.id.to_string();
+    let domain = OrganizationDomainService::create(&pool, CreateOrganizationDomain {
+        code: Some(ulid::Ulid::new().to_string()),
+        name: "Query Cache Domain".to_string(),
+        description: None,
+    }, &user_id).await?;
+    OrganizationTypeService::create(&pool, organization_type(domain.id, "Department"), &user_id).await?;
+
+    let cache = QueryCache::new().await?;
+
+    let (result, status) = cache.index::<OrganizationType>(params_for_domain(domain.id, Some(1)), &pool).await?;
+    assert_eq!(status, CacheStatus::Miss);
+    assert_eq!(result.data.len(), 1);
+
+    // Defaults are normalized, so an omitted page is the same query
+    let (result, status) = cache.index::<OrganizationType>(params_for_domain(domain.id, None), &pool).await?;
+    assert_eq!(status, CacheStatus::Hit);
+    assert_eq!(result.data.len(), 1);
+
+    // Creating a type flushes the model's tag through the model-event layer
+    OrganizationTypeService::create(&pool, organization_type(domain.id, "Division"), &user_id).await?;
+
+    let (result, status) = cache.index::<OrganizationType>(params_for_domain(domain.id, Some(1)), &pool).await?;
+    assert_eq!(status, CacheStatus::Miss);
+    assert_eq!(result.data.len(), 2);
+
+    let (_, status) = cache.index::<OrganizationType>(params_for_domain(domain.id, Some(1)), &pool).await?;
+    assert_eq!(status, CacheStatus::Hit);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_key_normalizes_params() {
+    let domain_id = DieselUlid::new();
+
+    let mut params = params_for_domain(domain_id, None);
+    params.filter.insert("level".to_string(), serde_json::json!({"gte": "1"}));
+    let mut reordered = params_for_domain(domain_id, Some(1));
+    reordered.filter.insert("level".to_string(), serde_json::json!({"gte": "1"}));
+
+    assert_eq!(QueryCache::key::<OrganizationType>(None, &params), QueryCache::key::<OrganizationType>(None, &reordered));
+
+    reordered.page = Some(2);
+    assert_ne!(QueryCache::key::<OrganizationType>(None, &params), QueryCache::key::<OrganizationType>(None, &reordered));
+}
+
+#[test]
+fn test_cache_key_depends_on_tenant() {
+    let params = params_for_domain(DieselUlid::new(), None);
+
+    let central = QueryCache::key::<OrganizationType>(None, &params);
+    let acme = QueryCache::key::<OrganizationType>(Some("acme"), &params);
+    let globex = QueryCache::key::<OrganizationType>(Some("globex"), &params);
+
+    assert_ne!(central, acme);
+    assert_ne!(acme, globex);
+    assert_eq!(acme, QueryCache::key::<OrganizationType>(Some("acme"), &params));
+}
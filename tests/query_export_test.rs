@@ -0,0 +1,105 @@
+//! Query Export Integration Tests
+//!
+//! These tests verify that query results stream as CSV and NDJSON with the
+//! selected fields, respecting filters and sorts but not pagination.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::{header, HeaderMap, HeaderValue};
+use rustaxum::app::models::country::{Country, CreateCountry};
+use rustaxum::app::query_builder::{ExportFormat, QueryExport, QueryParams};
+use rustaxum::app::services::country_service::CountryService;
+use rustaxum::database::DbPool;
+use serial_test::serial;
+
+/// Create countries sharing a unique name prefix, returning the prefix
+async fn create_countries(pool: &DbPool, count: usize) -> Result<String> {
+    let user = common::create_user(pool)?;
+    let prefix = format!("Export {}", ulid::Ulid::new());
+
+    for i in 0..count {
+        CountryService::create(pool, CreateCountry {
+            name: format!("{} {:02}", prefix, i),
+            iso_code: format!("E{}{:02}", &prefix[prefix.len() - 6..], i),
+            phone_code: if i % 2 == 0 { Some(format!("+{}", i)) } else { None },
+        }, &user.id.to_string()).await?;
+    }
+
+    Ok(prefix)
+}
+
+fn params(prefix: &str) -> Result<QueryParams> {
+    let mut params = QueryParams::from_query_string("sort=name&per_page=2")?;
+    params.filter.insert("name[like]".to_string(), serde_json::json!(format!("{}%", prefix)));
+    params.fields.insert("ref_geo_countries".to_string(), "name,phone_code".to_string());
+    Ok(params)
+}
+
+async fn body(response: axum::response::Response) -> Result<String> {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok(String::from_utf8(bytes.to_vec())?)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_csv_export_streams_selected_fields() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let prefix = create_countries(&pool, 5).await?;
+
+    let response = QueryExport::new()
+        .with_chunk_size(2)
+        .response::<Country>(params(&prefix)?, ExportFormat::Csv, &pool)?;
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv; charset=utf-8");
+    assert_eq!(response.headers()["x-export-total"], "5");
+
+    let csv = body(response).await?;
+    let mut reader = csv::Reader::from_reader(csv.as_bytes());
+    assert_eq!(reader.headers()?, vec!["name", "phone_code"]);
+
+    // per_page=2 is ignored: every matching row is exported across chunks
+    let rows: Vec<csv::StringRecord> = reader.records().collect::<Result<_, _>>()?;
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[0], vec![format!("{} 00", prefix), "+0".to_string()]);
+    assert_eq!(rows[1], vec![format!("{} 01", prefix), String::new()]);
+    assert_eq!(rows[4], vec![format!("{} 04", prefix), "+4".to_string()]);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_ndjson_export_respects_row_cap() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let prefix = create_countries(&pool, 4).await?;
+
+    let response = QueryExport::new()
+        .with_max_rows(3)
+        .response::<Country>(params(&prefix)?, ExportFormat::Ndjson, &pool)?;
+    assert_eq!(response.headers()["x-export-truncated"], "true");
+
+    let ndjson = body(response).await?;
+    let rows: Vec<serde_json::Value> = ndjson
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[2], serde_json::json!({"name": format!("{} 02", prefix), "phone_code": "+2"}));
+
+    Ok(())
+}
+
+#[test]
+fn test_format_negotiation() -> Result<()> {
+    let mut headers = HeaderMap::new();
+    assert_eq!(ExportFormat::negotiate(&QueryParams::default(), &headers), None);
+
+    headers.insert(header::ACCEPT, HeaderValue::from_static("application/json, text/csv;q=0.9"));
+    assert_eq!(ExportFormat::negotiate(&QueryParams::default(), &headers), Some(ExportFormat::Csv));
+
+    // The query parameter wins over the Accept header
+    let params = QueryParams::from_query_string("format=ndjson")?;
+    assert_eq!(ExportFormat::negotiate(&params, &headers), Some(ExportFormat::Ndjson));
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Query Page Size Tests
+//!
+//! These tests verify that `per_page` falls back to the model's default page
+//! size, is clamped to the model's maximum, and is rejected with a 400 when a
+//! client exceeds the maximum in strict mode.
+
+use axum::http::StatusCode;
+use rustaxum::app::query_builder::{QueryParams, QueryParamsError, Queryable};
+use rustaxum::config::query_builder::QueryBuilderConfig;
+
+struct Report;
+
+impl Queryable for Report {
+    fn table_name() -> &'static str {
+        "reports"
+    }
+
+    fn allowed_filters() -> Vec<&'static str> {
+        vec!["name"]
+    }
+
+    fn allowed_sorts() -> Vec<&'static str> {
+        vec!["name"]
+    }
+
+    fn allowed_fields() -> Vec<&'static str> {
+        vec!["id", "name"]
+    }
+
+    fn default_per_page() -> u32 {
+        50
+    }
+
+    fn max_per_page() -> u32 {
+        500
+    }
+}
+
+struct Note;
+
+impl Queryable for Note {
+    fn table_name() -> &'static str {
+        "notes"
+    }
+
+    fn allowed_filters() -> Vec<&'static str> {
+        vec![]
+    }
+
+    fn allowed_sorts() -> Vec<&'static str> {
+        vec![]
+    }
+
+    fn allowed_fields() -> Vec<&'static str> {
+        vec!["id"]
+    }
+}
+
+fn params(query: &str) -> QueryParams {
+    QueryParams::from_query_string(query).unwrap()
+}
+
+#[test]
+fn test_per_page_defaults_come_from_config_and_model() {
+    let config = QueryBuilderConfig::global();
+
+    assert_eq!(params("").get_pagination_for::<Note>().per_page, config.default_per_page);
+    assert_eq!(params("").get_pagination().per_page, config.default_per_page);
+    assert_eq!(params("").get_pagination_for::<Report>().per_page, 50);
+}
+
+#[test]
+fn test_per_page_is_clamped_to_model_max() {
+    let config = QueryBuilderConfig::global();
+
+    assert_eq!(params("per_page=100000").get_pagination_for::<Note>().per_page, config.max_per_page);
+    assert_eq!(params("per_page=100000").get_pagination_for::<Report>().per_page, 500);
+    assert_eq!(params("per_page=250").get_pagination_for::<Report>().per_page, 250);
+    assert_eq!(params("per_page=0").get_pagination_for::<Report>().per_page, 1);
+
+    let cursor = params("per_page=100000&pagination_type=cursor").get_pagination_for::<Report>();
+    assert!(cursor.is_cursor());
+    assert_eq!(cursor.per_page, 500);
+}
+
+#[test]
+fn test_strict_mode_rejects_per_page_over_max() {
+    let error = params("per_page=501").validate_per_page::<Report>(true).unwrap_err();
+    assert!(matches!(error, QueryParamsError::PerPageTooLarge { requested: 501, max: 500 }));
+    assert_eq!(QueryParamsError::status_code(&error.into()), StatusCode::BAD_REQUEST);
+
+    assert!(params("per_page=500").validate_per_page::<Report>(true).is_ok());
+    assert!(params("").validate_per_page::<Report>(true).is_ok());
+    assert!(params("per_page=501").validate_per_page::<Report>(false).is_ok());
+
+    let other = anyhow::anyhow!("connection refused");
+    assert_eq!(QueryParamsError::status_code(&other), StatusCode::INTERNAL_SERVER_ERROR);
+}
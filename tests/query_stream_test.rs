@@ -0,0 +1,63 @@
+//! Query Stream Tests
+//!
+//! These tests verify that `QueryService::stream` rejects the same filters as
+//! an index request before reading anything, and that a stream read in small
+//! chunks returns every matching row once, in order, even when the sort
+//! column ties and is left out of the selected fields.
+
+mod common;
+
+use anyhow::Result;
+use axum::extract::Query;
+use diesel::prelude::*;
+use futures::TryStreamExt;
+use rustaxum::app::models::user::User;
+use rustaxum::app::query_builder::{QueryBuilderExt, QueryParams, QueryService};
+use rustaxum::database::DbPool;
+use rustaxum::schema::sys_users;
+use serial_test::serial;
+
+fn rename_user(pool: &DbPool, user: &User, name: &str) -> Result<()> {
+    let mut conn = pool.get()?;
+    diesel::update(sys_users::table.filter(sys_users::id.eq(user.id.to_string())))
+        .set(sys_users::name.eq(name))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_stream_rejects_unknown_operator() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let params = QueryParams::from_query_string("filter[name][nonsense]=x")?;
+
+    assert!(QueryService::stream::<User>(Query(params), &pool).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_stream_reads_every_row_across_chunks() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let token = ulid::Ulid::new().to_string();
+
+    let mut expected = Vec::new();
+    for _ in 0..5 {
+        let user = common::create_user(&pool)?;
+        rename_user(&pool, &user, &format!("Streamed {}", token))?;
+        expected.push(user.id.to_string());
+    }
+    expected.sort();
+
+    let params = QueryParams::from_query_string(&format!(
+        "filter[name]=Streamed%20{}&sort=name&fields[sys_users]=id,email",
+        token
+    ))?;
+    let stream = QueryService::stream_builder(User::from_params(params)?, &pool, 2);
+    let rows: Vec<serde_json::Value> = stream.try_collect().await?;
+
+    let ids: Vec<String> = rows.iter().map(|row| row["id"].as_str().unwrap_or_default().to_string()).collect();
+    assert_eq!(ids, expected, "ties on name are broken by id");
+    assert!(rows.iter().all(|row| row.get("name").is_none()), "the sort column is not added to the rows");
+    Ok(())
+}
@@ -0,0 +1,123 @@
+//! Queue Fake Tests
+//!
+//! These tests verify that `QueueFacade::fake()` records the jobs a code path
+//! dispatches without queueing or running them, that the push assertions
+//! see those jobs, and that `restore` puts the real driver back.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rustaxum::app::jobs::send_email_job::SendEmailJob;
+use rustaxum::app::jobs::{job_dispatcher, Job, JobDispatcher, MemoryQueueDriver, QueueDriver, QueueFacade};
+use serde::{Deserialize, Serialize};
+use serial_test::serial;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static REPORT_RAN: AtomicBool = AtomicBool::new(false);
+
+/// Marks that it ran, so tests can tell a faked job was never handled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GenerateReportJob;
+
+#[async_trait]
+impl Job for GenerateReportJob {
+    fn job_name(&self) -> &'static str {
+        "GenerateReportJob"
+    }
+
+    fn queue_name(&self) -> &str {
+        "reports"
+    }
+
+    async fn handle(&self) -> Result<()> {
+        REPORT_RAN.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn serialize(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+/// The code path under test: welcomes a user and queues two reports
+async fn onboard(email: &str) -> Result<()> {
+    rustaxum::dispatch!(SendEmailJob::welcome(email.to_string(), "Ada".to_string(), None))?;
+    rustaxum::dispatch!(GenerateReportJob)?;
+    rustaxum::dispatch!(GenerateReportJob)?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_fake_records_pushed_jobs_without_running_them() -> Result<()> {
+    QueueFacade::fake().await;
+    assert!(QueueFacade::assert_nothing_pushed().await);
+
+    onboard("ada@example.com").await?;
+
+    assert!(QueueFacade::assert_pushed("SendEmailJob").await);
+    assert!(QueueFacade::assert_pushed_times("SendEmailJob", 1).await);
+    assert!(QueueFacade::assert_pushed_times("GenerateReportJob", 2).await);
+    assert!(QueueFacade::assert_pushed_on("reports", "GenerateReportJob").await);
+    assert!(!QueueFacade::assert_pushed_on("default", "GenerateReportJob").await);
+    assert!(QueueFacade::assert_not_pushed("SendNotificationBatchJob").await);
+    assert!(!QueueFacade::assert_nothing_pushed().await);
+
+    let pushed = QueueFacade::get_pushed_jobs().await;
+    assert_eq!(pushed.len(), 3);
+    assert!(pushed[0].payload.contains("ada@example.com"));
+
+    // Nothing is handed to a worker, so the jobs never run
+    let dispatcher = job_dispatcher().await;
+    assert_eq!(dispatcher.read().await.stats("reports").await?.pending_jobs, 2);
+    assert!(!REPORT_RAN.load(Ordering::SeqCst));
+
+    QueueFacade::restore().await;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_fake_starts_empty_each_time() -> Result<()> {
+    QueueFacade::fake().await;
+    rustaxum::dispatch!(GenerateReportJob)?;
+    assert!(QueueFacade::assert_pushed("GenerateReportJob").await);
+
+    QueueFacade::fake().await;
+    assert!(QueueFacade::assert_nothing_pushed().await);
+
+    QueueFacade::restore().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restore_puts_the_real_driver_back() -> Result<()> {
+    let mut dispatcher = JobDispatcher::new(Box::new(MemoryQueueDriver::new()));
+
+    let fake = dispatcher.fake();
+    dispatcher.fake();
+    dispatcher.dispatch(&GenerateReportJob).await?;
+    assert!(dispatcher.is_faking());
+    assert!(fake.assert_nothing_pushed().await, "a second fake replaces the first");
+    assert!(dispatcher.assert_pushed("GenerateReportJob").await);
+
+    dispatcher.restore();
+    assert!(!dispatcher.is_faking());
+    assert!(!dispatcher.assert_pushed("GenerateReportJob").await);
+
+    dispatcher.dispatch(&GenerateReportJob).await?;
+    let stats = dispatcher.stats("reports").await?;
+    assert_eq!(stats.pending_jobs, 1, "the memory driver queues jobs again");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fake_driver_never_hands_out_jobs() -> Result<()> {
+    let mut dispatcher = JobDispatcher::new(Box::new(MemoryQueueDriver::new()));
+    let fake = dispatcher.fake();
+    dispatcher.dispatch(&GenerateReportJob).await?;
+
+    assert_eq!(fake.pushed().await.len(), 1);
+    assert!(fake.pop("reports").await?.is_none());
+    assert_eq!(fake.driver_name(), "fake");
+    Ok(())
+}
@@ -0,0 +1,171 @@
+//! Queue Worker Retry Tests
+//!
+//! These tests verify that a queue worker releases a failed job back onto its
+//! queue until the job's `max_attempts` is used up, that the retry waits the
+//! job's `retry_delay` times the attempt (capped by `max_retry_delay`), and
+//! that a job which keeps failing or timing out is moved to the failed jobs
+//! and has its `failed` hook called once. Jobs count their runs in a global
+//! counter, so these run serially.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use rustaxum::app::jobs::queue_worker::{QueueWorker, SimpleJobFactory, WorkerConfig};
+use rustaxum::app::jobs::{FakeQueueDriver, Job, JobMetadata, JobStatus, MemoryQueueDriver, QueueDriver};
+use serde::{Deserialize, Serialize};
+use serial_test::serial;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+static RUNS: AtomicU32 = AtomicU32::new(0);
+static FAILED_CALLS: AtomicU32 = AtomicU32::new(0);
+
+/// Fails its first `failures` runs, like an API call during a short outage,
+/// or never finishes when `hangs` is set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SyncInventoryJob {
+    failures: u32,
+    retry_delay: u64,
+    #[serde(default)]
+    hangs: bool,
+}
+
+#[async_trait]
+impl Job for SyncInventoryJob {
+    fn job_name(&self) -> &'static str {
+        "SyncInventory"
+    }
+
+    async fn handle(&self) -> Result<()> {
+        let run = RUNS.fetch_add(1, Ordering::SeqCst) + 1;
+        if self.hangs {
+            std::future::pending::<()>().await;
+        }
+        if run <= self.failures {
+            return Err(anyhow::anyhow!("Inventory service unavailable"));
+        }
+        Ok(())
+    }
+
+    fn retry_delay(&self) -> u64 {
+        self.retry_delay
+    }
+
+    async fn failed(&self, _error: &anyhow::Error) {
+        FAILED_CALLS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn serialize(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+async fn worker_with_job(config: WorkerConfig, job: SyncInventoryJob) -> Result<(QueueWorker, FakeQueueDriver, String)> {
+    RUNS.store(0, Ordering::SeqCst);
+    FAILED_CALLS.store(0, Ordering::SeqCst);
+
+    let driver = FakeQueueDriver::recording(MemoryQueueDriver::new());
+    let metadata = JobMetadata::new("SyncInventory".to_string(), "default".to_string(), Job::serialize(&job)?, 0, 3);
+    let job_id = metadata.id.clone();
+    driver.push(metadata).await?;
+
+    let worker = QueueWorker::new(config, Arc::new(driver.clone()));
+    worker.register_job::<SyncInventoryJob>("SyncInventory", SimpleJobFactory::<SyncInventoryJob>::new()).await;
+    Ok((worker, driver, job_id))
+}
+
+#[tokio::test]
+#[serial]
+async fn test_job_failing_twice_completes_on_third_attempt() -> Result<()> {
+    let (worker, driver, job_id) = worker_with_job(
+        WorkerConfig::default(),
+        SyncInventoryJob { failures: 2, retry_delay: 0, hangs: false },
+    ).await?;
+
+    while worker.work_once().await? {}
+
+    let job = driver.job(&job_id).await.expect("the job is known to the driver");
+    assert_eq!(job.status, JobStatus::Completed);
+    assert_eq!(job.attempts, 2);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 3);
+
+    let stats = worker.get_stats().await;
+    assert_eq!(stats.jobs_retried, 2);
+    assert_eq!(stats.jobs_succeeded, 1);
+    assert!(driver.failed_jobs(None).await?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_job_exhausting_its_attempts_is_failed() -> Result<()> {
+    let (worker, driver, job_id) = worker_with_job(
+        WorkerConfig::default(),
+        SyncInventoryJob { failures: 10, retry_delay: 0, hangs: false },
+    ).await?;
+
+    while worker.work_once().await? {}
+
+    assert_eq!(RUNS.load(Ordering::SeqCst), 3, "runs exactly max_attempts times");
+    let failed = driver.failed_jobs(None).await?;
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].id, job_id);
+    assert_eq!(failed[0].status, JobStatus::Failed);
+    assert_eq!(failed[0].error_message.as_deref(), Some("Inventory service unavailable"));
+    assert_eq!(driver.size("default").await?, 0);
+    assert_eq!(FAILED_CALLS.load(Ordering::SeqCst), 1, "the failed hook runs once, after the last attempt");
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_job_timing_out_on_its_last_attempt_is_failed() -> Result<()> {
+    let config = WorkerConfig { max_runtime: std::time::Duration::from_millis(20), ..Default::default() };
+    let (worker, driver, job_id) = worker_with_job(
+        config,
+        SyncInventoryJob { failures: 0, retry_delay: 0, hangs: true },
+    ).await?;
+
+    while worker.work_once().await? {}
+
+    assert_eq!(RUNS.load(Ordering::SeqCst), 3);
+    let job = driver.job(&job_id).await.expect("the job is known to the driver");
+    assert_eq!(job.status, JobStatus::Failed);
+    assert!(job.error_message.as_deref().is_some_and(|message| message.starts_with("Job timed out")));
+    assert_eq!(FAILED_CALLS.load(Ordering::SeqCst), 1, "a final timeout calls the failed hook like an error does");
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_retry_waits_until_its_scheduled_time() -> Result<()> {
+    let (worker, driver, job_id) = worker_with_job(
+        WorkerConfig::default(),
+        SyncInventoryJob { failures: 1, retry_delay: 60, hangs: false },
+    ).await?;
+
+    let failed_at = Utc::now();
+    assert!(worker.work_once().await?);
+
+    // Released, but not handed out again before its delay has passed
+    assert_eq!(driver.size("default").await?, 1);
+    assert!(!worker.work_once().await?);
+
+    let job = driver.job(&job_id).await.expect("the job is known to the driver");
+    assert_eq!(job.status, JobStatus::Retrying);
+    assert_eq!(job.attempts, 1);
+    let available_at = job.scheduled_at.expect("retry has an available time");
+    assert!(available_at >= failed_at + Duration::seconds(60));
+    assert!(available_at <= Utc::now() + Duration::seconds(60));
+    Ok(())
+}
+
+#[test]
+fn test_retry_delay_grows_with_the_attempt_up_to_the_cap() {
+    let config = WorkerConfig { max_retry_delay: std::time::Duration::from_secs(150), ..Default::default() };
+    let job = SyncInventoryJob { failures: 0, retry_delay: 60, hangs: false };
+
+    assert_eq!(QueueWorker::retry_delay(&config, &job, 1), Duration::seconds(60));
+    assert_eq!(QueueWorker::retry_delay(&config, &job, 2), Duration::seconds(120));
+    assert_eq!(QueueWorker::retry_delay(&config, &job, 3), Duration::seconds(150));
+}
@@ -0,0 +1,166 @@
+//! Queued Listener Tests
+//!
+//! These tests verify that a listener whose `should_queue()` is true is pushed
+//! onto the job queue on its declared queue and after its delay instead of
+//! running inline, and that a queue worker releases a failing queued
+//! listener with the listener's `retry_after` backoff. Queued listeners are resolved by name from a global registry, so these run serially.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use rustaxum::app::events::queued_listener::JobQueueHandler;
+use rustaxum::app::events::{Event, EventDispatcher, EventListener, ShouldQueueListener};
+use rustaxum::app::jobs::call_queued_listener_job::CallQueuedListenerJob;
+use rustaxum::app::jobs::queue_worker::{QueueWorker, SimpleJobFactory, WorkerConfig};
+use rustaxum::app::jobs::{FakeQueueDriver, JobDispatcher, JobStatus, MemoryQueueDriver, QueueDriver};
+use serial_test::serial;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+#[derive(Debug)]
+struct InvoicePaid;
+
+#[async_trait]
+impl Event for InvoicePaid {
+    fn event_name(&self) -> &'static str {
+        "InvoicePaid"
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({ "invoice_id": "inv_1" })
+    }
+}
+
+/// Fails its first `failures` runs, like a notification provider that is briefly down
+#[derive(Debug, Default)]
+struct NotifyAccountingListener {
+    failures: u32,
+    delay_seconds: i64,
+    retry_after_seconds: i64,
+    runs: AtomicU32,
+}
+
+#[async_trait]
+impl EventListener for NotifyAccountingListener {
+    async fn handle(&self, event: Arc<dyn Event>) -> Result<()> {
+        assert_eq!(event.event_name(), "InvoicePaid");
+        assert_eq!(event.to_json()["invoice_id"], "inv_1");
+
+        let run = self.runs.fetch_add(1, Ordering::SeqCst) + 1;
+        if run <= self.failures {
+            return Err(anyhow::anyhow!("Accounting webhook unavailable"));
+        }
+        Ok(())
+    }
+
+    fn should_queue(&self) -> bool {
+        true
+    }
+
+    fn as_should_queue(&self) -> Option<&dyn ShouldQueueListener> {
+        Some(self)
+    }
+}
+
+impl ShouldQueueListener for NotifyAccountingListener {
+    fn queue(&self) -> Option<&str> {
+        Some("notifications")
+    }
+
+    fn delay(&self) -> Option<Duration> {
+        (self.delay_seconds > 0).then(|| Duration::seconds(self.delay_seconds))
+    }
+
+    fn tries(&self) -> Option<u32> {
+        Some(3)
+    }
+
+    fn retry_after(&self, attempt: u32) -> Option<Duration> {
+        Some(Duration::seconds(attempt as i64 * self.retry_after_seconds))
+    }
+
+    fn via_connection(self, _connection: &str) -> Self {
+        self
+    }
+
+    fn via_queue(self, _queue: &str) -> Self {
+        self
+    }
+
+    fn with_delay(self, _delay: Duration) -> Self {
+        self
+    }
+}
+
+async fn queueing_dispatcher(jobs: JobDispatcher, listener: Arc<NotifyAccountingListener>) -> (EventDispatcher, Arc<RwLock<JobDispatcher>>) {
+    let jobs = Arc::new(RwLock::new(jobs));
+    let events = EventDispatcher::new();
+    events.listen_pattern("InvoicePaid", listener).await;
+    events.set_queueable_handler(Arc::new(JobQueueHandler::new(jobs.clone()))).await;
+    (events, jobs)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_queued_listener_is_pushed_on_its_queue_with_its_delay() -> Result<()> {
+    let listener = Arc::new(NotifyAccountingListener { delay_seconds: 30, retry_after_seconds: 15, ..Default::default() });
+    let mut jobs = JobDispatcher::new(Box::new(MemoryQueueDriver::new()));
+    let fake = jobs.fake();
+    let (events, _jobs) = queueing_dispatcher(jobs, listener.clone()).await;
+
+    let dispatched_at = Utc::now();
+    events.dispatch(Arc::new(InvoicePaid)).await?;
+
+    assert_eq!(listener.runs.load(Ordering::SeqCst), 0, "the listener does not run inline");
+    assert!(fake.assert_pushed_on("notifications", "CallQueuedListener").await);
+
+    let pushed = fake.pushed().await;
+    assert_eq!(pushed.len(), 1);
+    assert_eq!(pushed[0].max_attempts, 3);
+    let available_at = pushed[0].scheduled_at.expect("delayed job has an available time");
+    assert!(available_at >= dispatched_at + Duration::seconds(30));
+    assert!(available_at <= Utc::now() + Duration::seconds(30));
+
+    let job: CallQueuedListenerJob = serde_json::from_str(&pushed[0].payload)?;
+    assert_eq!(job.event_name, "InvoicePaid");
+    assert_eq!(job.backoff_seconds, vec![15, 30, 45]);
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_failing_queued_listener_retries_with_its_backoff() -> Result<()> {
+    let listener = Arc::new(NotifyAccountingListener { failures: 1, retry_after_seconds: 15, ..Default::default() });
+    let driver = FakeQueueDriver::recording(MemoryQueueDriver::new());
+    let (events, _jobs) = queueing_dispatcher(JobDispatcher::new(Box::new(driver.clone())), listener.clone()).await;
+
+    // Queueing succeeds even though the listener will fail its first run
+    events.dispatch(Arc::new(InvoicePaid)).await?;
+    let job_id = driver.pushed().await[0].id.clone();
+
+    let worker = QueueWorker::new(
+        WorkerConfig { queue_name: "notifications".to_string(), ..Default::default() },
+        Arc::new(driver.clone()),
+    );
+    worker.register_job::<CallQueuedListenerJob>("CallQueuedListener", SimpleJobFactory::<CallQueuedListenerJob>::new()).await;
+
+    let failed_at = Utc::now();
+    assert!(worker.work_once().await?);
+    assert_eq!(listener.runs.load(Ordering::SeqCst), 1);
+    assert_eq!(worker.get_stats().await.jobs_retried, 1);
+
+    // Released with the listener's 15 second backoff, not the job's 60 second default
+    let retry = driver.job(&job_id).await.expect("the job is known to the driver");
+    assert_eq!(retry.status, JobStatus::Retrying);
+    assert_eq!(retry.attempts, 1);
+    let available_at = retry.scheduled_at.expect("retry has an available time");
+    assert!(available_at >= failed_at + Duration::seconds(15));
+    assert!(available_at <= Utc::now() + Duration::seconds(15));
+
+    // Still queued, but not handed out again before its backoff has passed
+    assert_eq!(driver.size("notifications").await?, 1);
+    assert!(!worker.work_once().await?);
+    assert_eq!(listener.runs.load(Ordering::SeqCst), 1);
+    Ok(())
+}
\ No newline at end of file
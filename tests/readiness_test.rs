@@ -0,0 +1,83 @@
+//! Readiness Tests
+//!
+//! These tests verify that `/ready` answers 503 while a migration is pending,
+//! lists that migration, answers 200 once it is applied, and skips the
+//! migration check when it is turned off.
+
+mod common;
+
+use anyhow::Result;
+use axum::{body::Body, http::{Request, StatusCode}, routing::get, Router};
+use diesel::prelude::*;
+use rustaxum::app::http::controllers::health_controller::ReadinessCheck;
+use rustaxum::database::migration_runner::MigrationRunner;
+use rustaxum::database::DbPool;
+use rustaxum::schema::migrations;
+use serde_json::Value;
+use serial_test::serial;
+use std::path::PathBuf;
+use tower::ServiceExt;
+
+/// A migrations directory holding one migration no database has applied yet
+fn pending_migration_dir() -> Result<(PathBuf, String)> {
+    let name = format!("2099_01_01_000000_readiness_probe_{}", ulid::Ulid::new().to_string().to_lowercase());
+    let dir = std::env::temp_dir().join(&name);
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join(format!("{}.up.sql", name)), "SELECT 1;")?;
+    std::fs::write(dir.join(format!("{}.down.sql", name)), "SELECT 1;")?;
+    Ok((dir, name))
+}
+
+async fn get_ready(pool: &DbPool, check: ReadinessCheck) -> Result<(StatusCode, Value)> {
+    let app = Router::new()
+        .route("/ready", get(move |axum::extract::State(pool): axum::extract::State<DbPool>| async move {
+            axum::response::IntoResponse::into_response(check.check(&pool))
+        }))
+        .with_state(pool.clone());
+
+    let response = app.oneshot(Request::builder().uri("/ready").body(Body::empty())?).await?;
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok((status, serde_json::from_slice(&body)?))
+}
+
+#[tokio::test]
+#[serial]
+async fn test_pending_migration_holds_traffic_until_applied() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let (dir, name) = pending_migration_dir()?;
+    let check = ReadinessCheck::new(true, dir.to_string_lossy());
+
+    let (status, body) = get_ready(&pool, check.clone()).await?;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "not_ready");
+    assert_eq!(body["checks"]["database"]["status"], "ok");
+    assert_eq!(body["checks"]["migrations"]["status"], "pending");
+    assert_eq!(body["checks"]["migrations"]["pending"], serde_json::json!([name]));
+
+    MigrationRunner::new(pool.clone(), dir.to_string_lossy().to_string()).run_migrations()?;
+
+    let (status, body) = get_ready(&pool, check).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ready");
+    assert_eq!(body["checks"]["migrations"]["status"], "ok");
+
+    diesel::delete(migrations::table.filter(migrations::migration.eq(&name))).execute(&mut pool.get()?)?;
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_migration_check_can_be_turned_off() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let (dir, _) = pending_migration_dir()?;
+
+    let (status, body) = get_ready(&pool, ReadinessCheck::new(false, dir.to_string_lossy())).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["checks"]["database"]["status"], "ok");
+    assert!(body["checks"].get("migrations").is_none());
+
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
@@ -0,0 +1,194 @@
+//! Redis Batch Tests
+//!
+//! These tests verify that `RedisCache::many` reads every key with a single
+//! `MGET` and that `put_many` writes every key in one pipeline with its TTL,
+//! with the store's prefix applied to each key, each batch taking a single
+//! round trip. A sub-second TTL must still be a valid expiry. They run
+//! against a fake Redis server that records the commands it receives and
+//! counts how often it answers.
+
+use anyhow::Result;
+use rustaxum::cache::drivers::RedisCache;
+use rustaxum::cache::Cache;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+#[derive(Clone, Default)]
+struct FakeRedis {
+    commands: Arc<Mutex<Vec<Vec<String>>>>,
+    store: Arc<Mutex<HashMap<String, String>>>,
+    /// Times the server wrote replies back and waited for the client
+    round_trips: Arc<AtomicUsize>,
+}
+
+impl FakeRedis {
+    async fn start() -> Result<(Self, String)> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("redis://{}", listener.local_addr()?);
+        let server = Self::default();
+
+        let handle = server.clone();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(handle.clone().serve(socket));
+            }
+        });
+        Ok((server, url))
+    }
+
+    async fn serve(self, socket: TcpStream) {
+        let (reader, mut writer) = socket.into_split();
+        let mut reader = BufReader::with_capacity(1 << 16, reader);
+
+        // Replies are held back until every command the client has sent so
+        // far is read, so a pipeline is answered in one write
+        let mut replies = String::new();
+        while let Some(command) = Self::read_command(&mut reader).await {
+            replies.push_str(&self.reply(&command));
+            self.commands.lock().unwrap().push(command);
+            if reader.buffer().is_empty() {
+                self.round_trips.fetch_add(1, Ordering::SeqCst);
+                if writer.write_all(replies.as_bytes()).await.is_err() {
+                    break;
+                }
+                replies.clear();
+            }
+        }
+    }
+
+    async fn read_command(reader: &mut BufReader<tokio::net::tcp::OwnedReadHalf>) -> Option<Vec<String>> {
+        let mut line = String::new();
+        reader.read_line(&mut line).await.ok().filter(|read| *read > 0)?;
+        let count: usize = line.trim().strip_prefix('*')?.parse().ok()?;
+
+        let mut args = Vec::with_capacity(count);
+        for _ in 0..count {
+            line.clear();
+            reader.read_line(&mut line).await.ok()?;
+            let len: usize = line.trim().strip_prefix('$')?.parse().ok()?;
+            let mut arg = vec![0; len + 2];
+            reader.read_exact(&mut arg).await.ok()?;
+            arg.truncate(len);
+            args.push(String::from_utf8(arg).ok()?);
+        }
+        Some(args)
+    }
+
+    fn reply(&self, command: &[String]) -> String {
+        let mut store = self.store.lock().unwrap();
+        match command[0].to_uppercase().as_str() {
+            "MGET" => {
+                let mut reply = format!("*{}\r\n", command.len() - 1);
+                for key in &command[1..] {
+                    match store.get(key) {
+                        Some(value) => reply.push_str(&format!("${}\r\n{}\r\n", value.len(), value)),
+                        None => reply.push_str("$-1\r\n"),
+                    }
+                }
+                reply
+            }
+            "SET" => {
+                store.insert(command[1].clone(), command[2].clone());
+                "+OK\r\n".to_string()
+            }
+            "SETEX" | "PSETEX" if command[2] == "0" => {
+                format!("-ERR invalid expire time in '{}' command\r\n", command[0].to_lowercase())
+            }
+            "SETEX" | "PSETEX" => {
+                store.insert(command[1].clone(), command[3].clone());
+                "+OK\r\n".to_string()
+            }
+            _ => "+OK\r\n".to_string(),
+        }
+    }
+
+    fn commands_named(&self, name: &str) -> Vec<Vec<String>> {
+        self.commands
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|command| command[0].eq_ignore_ascii_case(name))
+            .cloned()
+            .collect()
+    }
+
+    fn round_trips(&self) -> usize {
+        self.round_trips.load(Ordering::SeqCst)
+    }
+}
+
+#[tokio::test]
+async fn test_many_reads_every_key_with_one_mget() -> Result<()> {
+    let (server, url) = FakeRedis::start().await?;
+    let cache = RedisCache::new(&url, Some("app".to_string())).await?;
+
+    let keys: Vec<String> = (0..100).map(|i| format!("key:{}", i)).collect();
+    let key_refs: Vec<&str> = keys.iter().map(String::as_str).collect();
+    cache.put("key:7", &7_i64, None).await?;
+
+    let round_trips = server.round_trips();
+    let results = cache.many::<i64>(&key_refs).await?;
+    assert_eq!(server.round_trips() - round_trips, 1, "one round trip for the whole batch");
+    assert_eq!(results.len(), 100);
+    assert_eq!(results[7], ("key:7".to_string(), Some(7)));
+    assert_eq!(results.iter().filter(|(_, value)| value.is_none()).count(), 99);
+
+    let mgets = server.commands_named("MGET");
+    assert_eq!(mgets.len(), 1);
+    assert_eq!(mgets[0].len(), 101);
+    assert!(mgets[0][1..].iter().all(|key| key.starts_with("app:key:")));
+    assert!(server.commands_named("GET").is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_single_key_many_still_returns_a_list() -> Result<()> {
+    let (_server, url) = FakeRedis::start().await?;
+    let cache = RedisCache::new(&url, None).await?;
+    cache.put("only", &"value", None).await?;
+
+    let results = cache.many::<String>(&["only"]).await?;
+    assert_eq!(results, vec![("only".to_string(), Some("value".to_string()))]);
+    assert!(cache.many::<String>(&[]).await?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_put_many_sets_every_key_with_its_ttl() -> Result<()> {
+    let (server, url) = FakeRedis::start().await?;
+    let cache = RedisCache::new(&url, Some("app".to_string())).await?;
+
+    let values: Vec<(String, i64)> = (0..100).map(|i| (format!("key:{}", i), i)).collect();
+    let value_refs: Vec<(&str, &i64)> = values.iter().map(|(key, value)| (key.as_str(), value)).collect();
+    let round_trips = server.round_trips();
+    cache.put_many(&value_refs, Some(Duration::from_secs(30))).await?;
+    assert_eq!(server.round_trips() - round_trips, 1, "one round trip for the whole batch");
+
+    let sets = server.commands_named("PSETEX");
+    assert_eq!(sets.len(), 100);
+    for (set, (key, value)) in sets.iter().zip(&values) {
+        assert_eq!(set[1..], [format!("app:{}", key), "30000".to_string(), value.to_string()]);
+    }
+
+    let stored = cache.many::<i64>(&["key:0", "key:99"]).await?;
+    assert_eq!(stored, vec![("key:0".to_string(), Some(0)), ("key:99".to_string(), Some(99))]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sub_second_ttl_is_a_valid_expiry() -> Result<()> {
+    let (server, url) = FakeRedis::start().await?;
+    let cache = RedisCache::new(&url, None).await?;
+
+    cache.put_many(&[("short", &1_i64), ("shorter", &2_i64)], Some(Duration::from_millis(250))).await?;
+    cache.put("shortest", &3_i64, Some(Duration::from_micros(10))).await?;
+
+    let ttls: Vec<String> = server.commands_named("PSETEX").iter().map(|set| set[2].clone()).collect();
+    assert_eq!(ttls, vec!["250", "250", "1"]);
+    assert!(server.commands_named("SETEX").is_empty());
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! Redis Broadcast Driver Tests
+//!
+//! These tests verify that `RedisDriver::broadcast` publishes the serialized
+//! `BroadcastMessage` to Redis subscribers, that `health_check` PINGs the
+//! server, and that an unreachable server fails with an error once the
+//! publish retries are used up. The publish and health tests need a server
+//! reachable using the BROADCAST_REDIS_* settings.
+
+use anyhow::Result;
+use futures::StreamExt;
+use rustaxum::app::broadcasting::{BroadcastDriver, BroadcastMessage, RedisDriver};
+use rustaxum::config::Config;
+use std::time::Duration;
+
+fn configured_driver() -> Result<RedisDriver> {
+    let mut config = Config::load()?.broadcasting;
+    config.channels_prefix = format!("test-{}:", ulid::Ulid::new());
+    config.replay_buffer_size = 0;
+    Ok(RedisDriver::from_config(&config))
+}
+
+#[tokio::test]
+async fn test_broadcast_publishes_to_redis_subscribers() -> Result<()> {
+    let driver = configured_driver()?;
+    driver.health_check().await?;
+
+    let client = redis::Client::open(driver.redis_url())?;
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(format!("{}orders", driver.channels_prefix)).await?;
+    pubsub.subscribe(format!("{}private:user.1", driver.channels_prefix)).await?;
+    let mut messages = pubsub.on_message();
+
+    driver.broadcast("orders", serde_json::json!({ "order_id": 7 })).await?;
+    driver.broadcast_private("user.1", serde_json::json!({ "unread": 2 })).await?;
+
+    let mut received = Vec::new();
+    for _ in 0..2 {
+        let message = tokio::time::timeout(Duration::from_secs(5), messages.next()).await?.expect("pubsub closed");
+        let payload: String = message.get_payload()?;
+        received.push(serde_json::from_str::<BroadcastMessage>(&payload)?);
+    }
+
+    assert_eq!(received[0].channel, "orders");
+    assert_eq!(received[0].data["order_id"], 7);
+    assert_eq!(received[1].channel, "private:user.1");
+    assert_eq!(received[1].data["unread"], 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unreachable_server_fails_after_retries() -> Result<()> {
+    // Nothing listens on port 1
+    let driver = RedisDriver::new("127.0.0.1".to_string(), 1);
+
+    assert!(driver.health_check().await.is_err());
+
+    let error = driver.broadcast("orders", serde_json::json!({})).await.expect_err("publish cannot succeed");
+    assert!(
+        error.to_string().contains(&format!("after {} attempts", RedisDriver::PUBLISH_ATTEMPTS)),
+        "unexpected error: {}",
+        error,
+    );
+    Ok(())
+}
@@ -0,0 +1,127 @@
+//! Request Timeout Tests
+//!
+//! These tests verify that handlers running past the request timeout are
+//! cancelled and answered with 504, that fast handlers are unaffected, that
+//! route groups can override the limit, that streaming routes are exempt,
+//! and that a query cancelled by `statement_timeout` is also a 504 with the
+//! same error body.
+
+use anyhow::Result;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    response::IntoResponse,
+    routing::get,
+    Extension, Router,
+};
+use rustaxum::app::http::middleware::timeout_middleware::{
+    timeout_middleware, RequestCancellation, RequestTimeout,
+};
+use rustaxum::app::http::responses::ServiceErrorResponse;
+use rustaxum::database::StatementTimedOut;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+
+async fn slow() -> &'static str {
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    "done"
+}
+
+fn app(cancelled: Arc<AtomicBool>) -> Router {
+    let timeout = RequestTimeout::new(Some(Duration::from_millis(100)))
+        .with_override("/exports", Some(Duration::from_secs(5)))
+        .exclude("/sse");
+
+    Router::new()
+        .route("/fast", get(|| async { "ok" }))
+        .route("/slow", get(slow))
+        .route("/exports/report", get(slow))
+        .route("/sse/updates", get(slow))
+        .route("/spawns", get(move |Extension(RequestCancellation(token)): Extension<RequestCancellation>| {
+            let cancelled = cancelled.clone();
+            async move {
+                tokio::spawn(async move {
+                    token.cancelled().await;
+                    cancelled.store(true, Ordering::SeqCst);
+                });
+                slow().await
+            }
+        }))
+        .layer(middleware::from_fn_with_state(timeout, timeout_middleware))
+}
+
+async fn get_status(app: Router, uri: &str) -> Result<(StatusCode, Vec<u8>)> {
+    let response = app.oneshot(Request::builder().uri(uri).body(Body::empty())?).await?;
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok((status, bytes.to_vec()))
+}
+
+#[tokio::test]
+async fn test_slow_handler_times_out_with_504() -> Result<()> {
+    let (status, body) = get_status(app(Arc::default()), "/slow").await?;
+
+    assert_eq!(status, StatusCode::GATEWAY_TIMEOUT);
+    let body: Value = serde_json::from_slice(&body)?;
+    assert!(body["error"].as_str().unwrap().starts_with("Request timed out"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fast_handler_succeeds() -> Result<()> {
+    let (status, body) = get_status(app(Arc::default()), "/fast").await?;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, b"ok");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_route_group_override_and_streaming_exclusion() -> Result<()> {
+    assert_eq!(get_status(app(Arc::default()), "/exports/report").await?.0, StatusCode::OK);
+    assert_eq!(get_status(app(Arc::default()), "/sse/updates").await?.0, StatusCode::OK);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_timeout_cancels_spawned_work() -> Result<()> {
+    let cancelled = Arc::new(AtomicBool::new(false));
+
+    let (status, _) = get_status(app(cancelled.clone()), "/spawns").await?;
+    assert_eq!(status, StatusCode::GATEWAY_TIMEOUT);
+
+    tokio::time::timeout(Duration::from_secs(1), async {
+        while !cancelled.load(Ordering::SeqCst) {
+            tokio::task::yield_now().await;
+        }
+    })
+    .await?;
+    Ok(())
+}
+
+#[test]
+fn test_longest_override_wins() {
+    let timeout = RequestTimeout::new(Some(Duration::from_secs(30)))
+        .with_override("/api", Some(Duration::from_secs(10)))
+        .with_override("/api/exports", None);
+    let headers = axum::http::HeaderMap::new();
+
+    assert_eq!(timeout.timeout_for("/health", &headers), Some(Duration::from_secs(30)));
+    assert_eq!(timeout.timeout_for("/api/users", &headers), Some(Duration::from_secs(10)));
+    assert_eq!(timeout.timeout_for("/api/exports/users", &headers), None);
+}
+
+#[tokio::test]
+async fn test_statement_timeout_answers_504() -> Result<()> {
+    let error = anyhow::Error::new(StatementTimedOut).context("Failed to list users");
+    let response = ServiceErrorResponse::new(StatusCode::BAD_REQUEST, error).into_response();
+
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    let body: Value = serde_json::from_slice(&axum::body::to_bytes(response.into_body(), usize::MAX).await?)?;
+    assert_eq!(body, json!({ "error": "The query took too long and was cancelled" }));
+    Ok(())
+}
@@ -0,0 +1,132 @@
+//! Request Timing Tests
+//!
+//! These tests verify that requests slower than `slow_request_ms` are logged
+//! as warnings with their route and duration, that every response carries a
+//! `Server-Timing` header, and that slow queries are logged by shape only.
+
+use anyhow::Result;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::get,
+    Router,
+};
+use rustaxum::app::http::middleware::request_timing_middleware::{
+    request_timing_middleware, RequestTimingState, SERVER_TIMING_HEADER,
+};
+use rustaxum::app::utils::PerformanceMetrics;
+use rustaxum::database::query_timing::{sql_shape, QueryTimer};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tower::ServiceExt;
+use tracing::field::{Field, Visit};
+use tracing::{Level, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::Layer;
+
+type Warnings = Arc<Mutex<Vec<HashMap<String, String>>>>;
+
+/// Keeps the fields of every warning
+struct WarningLayer(Warnings);
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+impl<S: Subscriber> Layer<S> for WarningLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        if *event.metadata().level() == Level::WARN {
+            let mut fields = HashMap::new();
+            event.record(&mut FieldVisitor(&mut fields));
+            self.0.lock().unwrap().push(fields);
+        }
+    }
+}
+
+fn capture_warnings() -> (Warnings, tracing::subscriber::DefaultGuard) {
+    let warnings = Warnings::default();
+    let subscriber = tracing_subscriber::registry().with(WarningLayer(warnings.clone()));
+    (warnings, tracing::subscriber::set_default(subscriber))
+}
+
+fn app(slow_request: Duration) -> Router {
+    Router::new()
+        .route("/reports/{id}", get(|| async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            "done"
+        }))
+        .route("/ping", get(|| async { "pong" }))
+        .layer(middleware::from_fn_with_state(
+            RequestTimingState::new(Some(slow_request)),
+            request_timing_middleware,
+        ))
+}
+
+#[tokio::test]
+async fn test_slow_request_logs_warning_with_route_and_duration() -> Result<()> {
+    let (warnings, _guard) = capture_warnings();
+    let slow_before = PerformanceMetrics::global().snapshot().slow_requests;
+
+    let response = app(Duration::from_millis(20))
+        .oneshot(Request::builder().uri("/reports/01ARZ3NDEKTSV4RRFFQ69G5FAV").body(Body::empty())?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[SERVER_TIMING_HEADER].to_str()?.starts_with("app;dur="));
+
+    let warnings = warnings.lock().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0]["route"], "/reports/{id}");
+    assert_eq!(warnings[0]["method"], "GET");
+    assert!(warnings[0]["duration_ms"].parse::<f64>()? >= 50.0);
+    assert!(PerformanceMetrics::global().snapshot().slow_requests > slow_before);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fast_request_is_not_logged() -> Result<()> {
+    let (warnings, _guard) = capture_warnings();
+
+    let response = app(Duration::from_secs(5))
+        .oneshot(Request::builder().uri("/ping").body(Body::empty())?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().contains_key(SERVER_TIMING_HEADER));
+    assert!(warnings.lock().unwrap().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_slow_query_logs_shape_without_values() {
+    let (warnings, _guard) = capture_warnings();
+    let timer = QueryTimer::new(Some(Duration::from_millis(100)));
+
+    let query = "SELECT * FROM \"users\" WHERE \"users\".\"email\" = $1 AND \"age\" > 30 AND name = 'O''Brien' LIMIT $2 -- binds: [\"alice@example.com\", 1]";
+    assert!(timer.finish(Duration::from_millis(250), || query.to_string()));
+    assert!(!timer.finish(Duration::from_millis(5), || query.to_string()));
+
+    let warnings = warnings.lock().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0]["binds"], "2");
+    let sql = &warnings[0]["sql"];
+    assert!(!sql.contains("alice@example.com"));
+    assert!(!sql.contains("Brien"));
+    assert!(!sql.contains("30"));
+}
+
+#[test]
+fn test_sql_shape_replaces_literals() {
+    assert_eq!(
+        sql_shape("SELECT id FROM t1 WHERE a = 'x' AND b IN (1, 2.5) AND c = $1 AND d = $1 -- binds: [7]"),
+        ("SELECT id FROM t1 WHERE a = ? AND b IN (?, ?) AND c = $1 AND d = $1".to_string(), 1),
+    );
+}
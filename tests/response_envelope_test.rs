@@ -0,0 +1,112 @@
+//! Response Envelope Tests
+//!
+//! These tests verify that list and single-resource responses can be returned
+//! with or without the `data` wrapper, that without a preference lists stay
+//! wrapped and single resources stay bare, and that pagination is always
+//! sent in the `Link` and `X-Total-Count` headers.
+
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, HeaderValue, Request, StatusCode},
+    response::Response,
+    routing::get,
+    Router,
+};
+use rustaxum::app::http::responses::Envelope;
+use rustaxum::app::query_builder::Pagination;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn countries(envelope: Envelope) -> Response {
+    let names = vec![json!({"name": "Chile"}), json!({"name": "Peru"})];
+    envelope.collection(Pagination::page_based(2, 2).paginate(5, names))
+}
+
+async fn country(envelope: Envelope) -> Response {
+    envelope.resource(StatusCode::OK, json!({"name": "Chile"}))
+}
+
+fn app() -> Router {
+    Router::new()
+        .route("/api/countries", get(countries))
+        .route("/api/countries/chile", get(country))
+}
+
+async fn request(uri: &str, accept: Option<&str>) -> (HeaderMap, Value) {
+    let mut builder = Request::builder().uri(uri);
+    if let Some(accept) = accept {
+        builder = builder.header(header::ACCEPT, accept);
+    }
+
+    let response = app().oneshot(builder.body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers().clone();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (headers, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_wrapped_list_keeps_pagination_in_body_and_headers() {
+    let (headers, body) = request("/api/countries?page=2&per_page=2&wrap=true", None).await;
+
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+    assert_eq!(body["pagination"]["total"], 5);
+    assert_eq!(headers["x-total-count"], "5");
+    assert!(headers.contains_key(header::LINK));
+}
+
+#[tokio::test]
+async fn test_unwrapped_list_moves_pagination_to_headers() {
+    let (headers, body) = request("/api/countries?page=2&per_page=2&sort=name&wrap=false", None).await;
+
+    assert_eq!(body, json!([{"name": "Chile"}, {"name": "Peru"}]));
+    assert_eq!(headers["x-total-count"], "5");
+
+    // Other query parameters are kept, and the page is replaced
+    let link = headers[header::LINK].to_str().unwrap();
+    assert_eq!(link, [
+        "</api/countries?per_page=2&sort=name&wrap=false&page=1>; rel=\"first\"",
+        "</api/countries?per_page=2&sort=name&wrap=false&page=1>; rel=\"prev\"",
+        "</api/countries?per_page=2&sort=name&wrap=false&page=3>; rel=\"next\"",
+        "</api/countries?per_page=2&sort=name&wrap=false&page=3>; rel=\"last\"",
+    ].join(", "));
+}
+
+#[tokio::test]
+async fn test_accept_profile_controls_single_resource_wrapping() {
+    let (_, body) = request("/api/countries/chile?wrap=true", None).await;
+    assert_eq!(body, json!({"data": {"name": "Chile"}}));
+
+    let (_, body) = request("/api/countries/chile", Some("application/json; profile=\"bare\"")).await;
+    assert_eq!(body, json!({"name": "Chile"}));
+
+    // The query parameter wins over the Accept header
+    let (_, body) = request("/api/countries/chile?wrap=1", Some("application/json; profile=bare")).await;
+    assert_eq!(body, json!({"data": {"name": "Chile"}}));
+}
+
+#[test]
+fn test_negotiation_falls_back_to_configured_default() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(Envelope::negotiate("/api/countries", None, &headers, Some(true)).wrap, Some(true));
+    assert_eq!(Envelope::negotiate("/api/countries", None, &headers, Some(false)).wrap, Some(false));
+    assert_eq!(Envelope::negotiate("/api/countries", None, &headers, None).wrap, None);
+
+    headers.insert(header::ACCEPT, HeaderValue::from_static("application/json; profile=wrapped"));
+    assert_eq!(Envelope::negotiate("/api/countries", Some("page=1"), &headers, Some(false)).wrap, Some(true));
+}
+
+#[tokio::test]
+async fn test_without_a_preference_existing_shapes_are_kept() {
+    let envelope = Envelope::negotiate("/api/countries", None, &HeaderMap::new(), None);
+
+    let names = vec![json!({"name": "Chile"}), json!({"name": "Peru"})];
+    let response = envelope.collection(Pagination::page_based(1, 2).paginate(5, names));
+    let body: Value = serde_json::from_slice(&axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+    assert_eq!(body["pagination"]["total"], 5);
+
+    let response = envelope.resource(StatusCode::OK, json!({"name": "Chile"}));
+    let body: Value = serde_json::from_slice(&axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(body, json!({"name": "Chile"}));
+}
@@ -0,0 +1,74 @@
+//! Scheduled Message Delivery Integration Tests
+//!
+//! These tests verify that the dispatcher delivers due scheduled messages
+//! exactly once and leaves future ones untouched.
+
+mod common;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use rustaxum::app::models::scheduled_messages::ScheduledMessage;
+use rustaxum::app::services::scheduled_message_service::ScheduledMessageService;
+use rustaxum::schema::{message_delivery_status, scheduled_messages};
+use serial_test::serial;
+
+fn reload(pool: &rustaxum::database::DbPool, scheduled: &ScheduledMessage) -> Result<ScheduledMessage> {
+    let mut conn = pool.get()?;
+    Ok(scheduled_messages::table
+        .find(scheduled.id.to_string())
+        .select(ScheduledMessage::as_select())
+        .first::<ScheduledMessage>(&mut conn)?)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_due_message_is_delivered_once_and_future_is_skipped() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+
+    let sender = common::create_user(&pool)?;
+    let recipient = common::create_user(&pool)?;
+    let sender_device = common::create_device(&pool, &sender)?;
+    let recipient_device = common::create_device(&pool, &recipient)?;
+    let conversation = common::create_conversation(&pool, &sender)?;
+    common::add_participant(&pool, &conversation, &sender)?;
+    common::add_participant(&pool, &conversation, &recipient)?;
+
+    let due_message = common::create_message(&pool, &conversation, &sender, &sender_device)?;
+    let future_message = common::create_message(&pool, &conversation, &sender, &sender_device)?;
+    let due = common::create_scheduled_message(&pool, &due_message, &sender_device, Utc::now() - Duration::minutes(1))?;
+    let future = common::create_scheduled_message(&pool, &future_message, &sender_device, Utc::now() + Duration::hours(1))?;
+
+    ScheduledMessageService::dispatch_due(&pool, 1000).await?;
+
+    let due = reload(&pool, &due)?;
+    assert!(due.is_sent);
+    assert!(due.sent_at.is_some());
+
+    let future = reload(&pool, &future)?;
+    assert!(!future.is_sent);
+
+    let mut conn = pool.get()?;
+    let receipts = message_delivery_status::table
+        .filter(message_delivery_status::message_id.eq(due_message.id.to_string()))
+        .select(message_delivery_status::recipient_device_id)
+        .load::<String>(&mut conn)?;
+    assert_eq!(receipts, vec![recipient_device.id.to_string()]);
+
+    // A second run must not deliver the same message again
+    ScheduledMessageService::dispatch_due(&pool, 1000).await?;
+
+    let receipt_count: i64 = message_delivery_status::table
+        .filter(message_delivery_status::message_id.eq(due_message.id.to_string()))
+        .count()
+        .get_result(&mut conn)?;
+    assert_eq!(receipt_count, 1);
+
+    let future_receipts: i64 = message_delivery_status::table
+        .filter(message_delivery_status::message_id.eq(future_message.id.to_string()))
+        .count()
+        .get_result(&mut conn)?;
+    assert_eq!(future_receipts, 0);
+
+    Ok(())
+}
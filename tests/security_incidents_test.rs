@@ -0,0 +1,150 @@
+//! Security Incident Integration Tests
+//!
+//! These tests verify that suspicious activity is recorded as a security
+//! incident and that high severity incidents alert administrators.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use diesel::prelude::*;
+use rustaxum::app::broadcasting::websocket::websocket_manager;
+use rustaxum::app::models::oauth::{Client, CreateAccessToken};
+use rustaxum::app::models::role::Role;
+use rustaxum::app::models::security_incidents::{IncidentSeverity, IncidentType, SecurityIncident};
+use rustaxum::app::models::sys_model_has_role::SysModelHasRole;
+use rustaxum::app::models::user::User;
+use rustaxum::app::services::oauth::client_service::ClientService;
+use rustaxum::app::services::oauth::token_service::TokenService;
+use rustaxum::app::services::security_incident_service::{
+    IncidentSubject, SecurityIncidentError, SecurityIncidentService, ADMIN_SECURITY_CHANNEL,
+};
+use rustaxum::database::DbPool;
+use rustaxum::schema::{notifications, oauth_access_tokens, security_incidents, sys_model_has_roles, sys_roles};
+use serde_json::json;
+use serial_test::serial;
+use std::time::Duration;
+
+fn make_admin(pool: &DbPool, user: &User) -> Result<()> {
+    let role = Role::new("admin".to_string(), None, None, user.id);
+    let assignment = SysModelHasRole::new("User".to_string(), user.id, role.id, None, None, user.id);
+
+    let mut conn = pool.get()?;
+    diesel::insert_into(sys_roles::table).values(&role).execute(&mut conn)?;
+    diesel::insert_into(sys_model_has_roles::table).values(&assignment).execute(&mut conn)?;
+    Ok(())
+}
+
+fn incident_notification_count(pool: &DbPool, user: &User) -> Result<i64> {
+    let mut conn = pool.get()?;
+    let count = notifications::table
+        .filter(notifications::notifiable_id.eq(format!("User_{}", user.id)))
+        .filter(notifications::type_.eq("SecurityIncidentNotification"))
+        .count()
+        .get_result(&mut conn)?;
+    Ok(count)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_refresh_token_reuse_records_high_severity_incident() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+
+    let admin = common::create_user(&pool)?;
+    make_admin(&pool, &admin)?;
+    let user = common::create_user(&pool)?;
+
+    let client = ClientService::create_client_record(&pool, Client::new(
+        None,
+        Some(user.id),
+        "Incident Test Client".to_string(),
+        None,
+        "http://localhost/callback".to_string(),
+        false,
+        false,
+        user.id,
+    ))?;
+    let client_id = client.id.to_string();
+
+    let access_token = TokenService::create_access_token(&pool, CreateAccessToken {
+        user_id: Some(user.id.to_string()),
+        client_id: client_id.clone(),
+        name: None,
+        scopes: Vec::new(),
+        expires_at: None,
+        jwk_thumbprint: None,
+    }, Some(3600), None).await?;
+    let refresh_token = TokenService::create_refresh_token(&pool, access_token.id.to_string(), Some(604800))?;
+
+    // Rotation revokes the refresh token; a second access token is still live
+    TokenService::revoke_access_token(&pool, access_token.id.to_string())?;
+    let live_token = TokenService::create_access_token(&pool, CreateAccessToken {
+        user_id: Some(user.id.to_string()),
+        client_id: client_id.clone(),
+        name: None,
+        scopes: Vec::new(),
+        expires_at: None,
+        jwk_thumbprint: None,
+    }, Some(3600), None).await?;
+
+    let mut receiver = websocket_manager().await.subscribe(ADMIN_SECURITY_CHANNEL).await;
+
+    TokenService::refresh_access_token(&pool, &refresh_token.id.to_string(), client_id, None)
+        .await
+        .expect_err("a rotated refresh token must be rejected");
+
+    let mut conn = pool.get()?;
+    let incident = security_incidents::table
+        .filter(security_incidents::user_id.eq(user.id.to_string()))
+        .filter(security_incidents::incident_type.eq("token_reuse"))
+        .select(SecurityIncident::as_select())
+        .first::<SecurityIncident>(&mut conn)?;
+    assert_eq!(incident.severity, "high");
+    assert_eq!(incident.metadata["refresh_token_id"], refresh_token.id.to_string());
+
+    // Every token of the user is revoked once reuse is detected
+    let live_revoked = oauth_access_tokens::table
+        .find(live_token.id.to_string())
+        .select(oauth_access_tokens::revoked)
+        .first::<bool>(&mut conn)?;
+    assert!(live_revoked);
+
+    assert_eq!(incident_notification_count(&pool, &admin)?, 1);
+
+    let event = tokio::time::timeout(Duration::from_secs(2), receiver.recv()).await??;
+    assert_eq!(event.event, "security.incident");
+    assert_eq!(event.data["id"], incident.id.to_string());
+    assert_eq!(event.data["incident_type"], "token_reuse");
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_low_severity_incidents_do_not_alert() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+
+    let admin = common::create_user(&pool)?;
+    make_admin(&pool, &admin)?;
+    let user = common::create_user(&pool)?;
+
+    let incident = SecurityIncidentService::record_incident(
+        &pool,
+        IncidentType::MultipleFailedAuth,
+        IncidentSeverity::Medium,
+        IncidentSubject::user(user.id),
+        json!({ "failed_attempts": 5 }),
+    ).await?;
+
+    assert_eq!(incident.incident_type, "multiple_failed_auth");
+    assert_eq!(incident.metadata["failed_attempts"], 5);
+    assert_eq!(incident_notification_count(&pool, &admin)?, 0);
+
+    // Only administrators may list incidents
+    SecurityIncidentService::ensure_admin(&pool, &admin.id.to_string())?;
+    let error = SecurityIncidentService::ensure_admin(&pool, &user.id.to_string())
+        .expect_err("regular users cannot list incidents");
+    assert_eq!(SecurityIncidentError::status_code(&error), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
@@ -0,0 +1,203 @@
+//! Session Backup Integration Tests
+//!
+//! These tests verify that a device can upload an encrypted session backup
+//! and that only the user's own devices holding the backup key can restore it,
+//! and that uploads outside the size, quota and expiry limits are rejected.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use diesel::prelude::*;
+use rustaxum::app::services::session_backup_service::{
+    BackupKeyRequest, RestoreSessionBackupRequest, SessionBackupError, SessionBackupService, UploadSessionBackupRequest,
+    MAX_BACKUP_EXPIRY_DAYS,
+};
+use rustaxum::config::messaging::MessagingConfig;
+use rustaxum::schema::{encrypted_backup_keys, security_incidents};
+use serial_test::serial;
+use sha2::{Digest, Sha256};
+
+const BACKUP_KEY: &str = "client-side backup key";
+
+fn service() -> Result<SessionBackupService> {
+    Ok(SessionBackupService::with_config(MessagingConfig {
+        session_backup_max_bytes: 64,
+        session_backup_quota: 2,
+        ..MessagingConfig::from_env()?
+    }))
+}
+
+fn key_hash(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
+
+fn backup_request(sessions: &str) -> UploadSessionBackupRequest {
+    UploadSessionBackupRequest {
+        backup_name: "Laptop sessions".to_string(),
+        backup_type: Some("full".to_string()),
+        encrypted_sessions_data: sessions.to_string(),
+        backup_algorithm: "aes-256-gcm".to_string(),
+        encrypted_backup_key: "wrapped_backup_key".to_string(),
+        key_algorithm: "argon2id+aes-256-gcm".to_string(),
+        backup_key_hash: key_hash(BACKUP_KEY),
+        sessions_count: 3,
+        conversations_count: 2,
+        expires_in_days: None,
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_upload_and_restore_on_new_device() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let service = service()?;
+
+    let user = common::create_user(&pool)?;
+    let laptop = common::create_device(&pool, &user)?;
+    let phone = common::create_device(&pool, &user)?;
+    let user_id = user.id.to_string();
+
+    let backup = service.upload(&pool, &user_id, &laptop.id.to_string(), backup_request("encrypted_sessions"))?;
+    assert_eq!(backup.device_id, laptop.id);
+    assert_eq!(backup.backup_type, "full");
+    assert_eq!(backup.sessions_count, 3);
+    let backup_key_id = backup.backup_key_id.expect("backup is linked to its key");
+
+    // Only the wrapped key is stored, never the backup key itself
+    let mut conn = pool.get()?;
+    let stored_key = encrypted_backup_keys::table
+        .find(backup_key_id.to_string())
+        .select(encrypted_backup_keys::encrypted_backup_data)
+        .first::<String>(&mut conn)?;
+    assert_eq!(stored_key, "wrapped_backup_key");
+
+    let key = service.backup_key(&pool, &user_id, &backup.id.to_string(), BackupKeyRequest { device_id: phone.id })?;
+    assert_eq!(key.encrypted_backup_key, "wrapped_backup_key");
+
+    let restored = service.restore(&pool, &user_id, &backup.id.to_string(), RestoreSessionBackupRequest {
+        device_id: phone.id,
+        backup_key_hash: key_hash(BACKUP_KEY),
+    }).await?;
+    assert_eq!(restored.encrypted_sessions_data, "encrypted_sessions");
+    assert!(restored.backup.is_verified);
+    assert!(restored.backup.last_accessed_at.is_some());
+
+    let listed = service.list(&pool, &user_id)?;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, backup.id);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_unauthorized_restore_is_rejected() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let service = service()?;
+
+    let user = common::create_user(&pool)?;
+    let laptop = common::create_device(&pool, &user)?;
+    let phone = common::create_device(&pool, &user)?;
+    let attacker = common::create_user(&pool)?;
+    let attacker_device = common::create_device(&pool, &attacker)?;
+    let user_id = user.id.to_string();
+
+    let backup = service.upload(&pool, &user_id, &laptop.id.to_string(), backup_request("encrypted_sessions"))?;
+    let backup_id = backup.id.to_string();
+
+    // Another user cannot see the backup, even with the right key proof
+    let error = service.restore(&pool, &attacker.id.to_string(), &backup_id, RestoreSessionBackupRequest {
+        device_id: attacker_device.id,
+        backup_key_hash: key_hash(BACKUP_KEY),
+    }).await.expect_err("other users must not restore the backup");
+    assert_eq!(SessionBackupError::status_code(&error), StatusCode::NOT_FOUND);
+
+    // Nor restore it through their device under the owner's account
+    let error = service.restore(&pool, &user_id, &backup_id, RestoreSessionBackupRequest {
+        device_id: attacker_device.id,
+        backup_key_hash: key_hash(BACKUP_KEY),
+    }).await.expect_err("devices of other users must be rejected");
+    assert_eq!(SessionBackupError::status_code(&error), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // The owner's device must prove it holds the backup key
+    let error = service.restore(&pool, &user_id, &backup_id, RestoreSessionBackupRequest {
+        device_id: phone.id,
+        backup_key_hash: key_hash("guessed key"),
+    }).await.expect_err("a wrong key proof must be rejected");
+    assert_eq!(SessionBackupError::status_code(&error), StatusCode::FORBIDDEN);
+
+    let mut conn = pool.get()?;
+    let incidents: i64 = security_incidents::table
+        .filter(security_incidents::device_id.eq(phone.id.to_string()))
+        .filter(security_incidents::incident_type.eq("unauthorized_access"))
+        .count()
+        .get_result(&mut conn)?;
+    assert_eq!(incidents, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_size_limit_and_quota() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let service = service()?;
+
+    let user = common::create_user(&pool)?;
+    let laptop = common::create_device(&pool, &user)?;
+    let user_id = user.id.to_string();
+    let device_id = laptop.id.to_string();
+
+    let error = service.upload(&pool, &user_id, &device_id, backup_request(&"x".repeat(65)))
+        .expect_err("oversized backups must be rejected");
+    assert_eq!(SessionBackupError::status_code(&error), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let first = service.upload(&pool, &user_id, &device_id, backup_request("first"))?;
+    service.upload(&pool, &user_id, &device_id, backup_request("second"))?;
+
+    let error = service.upload(&pool, &user_id, &device_id, backup_request("third"))
+        .expect_err("the quota must be enforced");
+    assert_eq!(SessionBackupError::status_code(&error), StatusCode::CONFLICT);
+
+    // Deleting a backup frees quota and removes its wrapped key
+    service.delete(&pool, &user_id, &first.id.to_string())?;
+    service.upload(&pool, &user_id, &device_id, backup_request("third"))?;
+
+    let mut conn = pool.get()?;
+    let keys: i64 = encrypted_backup_keys::table
+        .filter(encrypted_backup_keys::user_id.eq(&user_id))
+        .count()
+        .get_result(&mut conn)?;
+    assert_eq!(keys, 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_expiry_must_be_within_bounds() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let service = service()?;
+
+    let user = common::create_user(&pool)?;
+    let laptop = common::create_device(&pool, &user)?;
+    let user_id = user.id.to_string();
+    let device_id = laptop.id.to_string();
+
+    for days in [0, MAX_BACKUP_EXPIRY_DAYS + 1, i32::MAX] {
+        let error = service.upload(&pool, &user_id, &device_id, UploadSessionBackupRequest {
+            expires_in_days: Some(days),
+            ..backup_request("sessions")
+        }).expect_err("expiry outside the bounds must be rejected");
+        assert_eq!(SessionBackupError::status_code(&error), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    let backup = service.upload(&pool, &user_id, &device_id, UploadSessionBackupRequest {
+        expires_in_days: Some(MAX_BACKUP_EXPIRY_DAYS),
+        ..backup_request("sessions")
+    })?;
+    assert!(backup.expires_at > chrono::Utc::now() + chrono::Duration::days(364));
+
+    Ok(())
+}
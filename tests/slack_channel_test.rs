@@ -0,0 +1,141 @@
+//! Slack Channel Tests
+//!
+//! These tests verify that `SlackChannel` posts a notification's
+//! `SlackMessage` to an incoming webhook as Slack's JSON payload, returns an
+//! error when Slack answers with a non-2xx status, and skips a notifiable
+//! without a Slack route instead of failing.
+
+use anyhow::Result;
+use axum::{http::StatusCode, routing::post, Json, Router};
+use rustaxum::app::models::user::User;
+use rustaxum::app::notifications::channels::Channel;
+use rustaxum::app::notifications::notification::OnDemandRecipient;
+use rustaxum::app::notifications::{
+    DatabaseMessage, Notifiable, Notification, NotificationChannel, SlackAttachment, SlackChannel, SlackField, SlackMessage,
+};
+use std::sync::{Arc, Mutex};
+
+struct DeploymentFinished;
+
+impl Notification for DeploymentFinished {
+    fn via(&self, _notifiable: &dyn Notifiable) -> Vec<NotificationChannel> {
+        vec![NotificationChannel::Slack]
+    }
+
+    fn to_database(&self, _notifiable: &dyn Notifiable) -> Result<DatabaseMessage> {
+        Ok(DatabaseMessage::new(serde_json::json!({ "message": "Deployed v2.4.0" })))
+    }
+
+    fn to_slack(&self, _notifiable: &dyn Notifiable) -> Result<SlackMessage> {
+        Ok(SlackMessage::new("Deployed v2.4.0".to_string())
+            .username("Deploy Bot".to_string())
+            .attachment(
+                SlackAttachment::new()
+                    .color("#36a64f".to_string())
+                    .title("Production".to_string())
+                    .field(SlackField::new("Version".to_string(), "2.4.0".to_string(), true)),
+            ))
+    }
+
+    fn notification_type(&self) -> &'static str {
+        "DeploymentFinished"
+    }
+}
+
+// Start a fake incoming webhook that records each payload and answers with `status` and `body`
+async fn start_webhook(status: StatusCode, body: &'static str) -> Result<(String, Arc<Mutex<Vec<serde_json::Value>>>)> {
+    let payloads = Arc::new(Mutex::new(Vec::new()));
+    let recorded = payloads.clone();
+    let app = Router::new().route("/services/T000/B000/XXXX", post(move |Json(payload): Json<serde_json::Value>| {
+        let recorded = recorded.clone();
+        async move {
+            recorded.lock().unwrap().push(payload);
+            (status, body)
+        }
+    }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.ok();
+    });
+
+    Ok((format!("http://{}/services/T000/B000/XXXX", addr), payloads))
+}
+
+#[test]
+fn test_payload_leaves_out_unset_fields() {
+    let payload = SlackChannel::payload(&SlackMessage::new("Hello".to_string()));
+    assert_eq!(payload, serde_json::json!({ "text": "Hello" }));
+}
+
+#[tokio::test]
+async fn test_send_posts_payload_to_webhook() -> Result<()> {
+    let (webhook_url, payloads) = start_webhook(StatusCode::OK, "ok").await?;
+    let channel = SlackChannel::with_webhook_url(webhook_url);
+    let recipient = OnDemandRecipient::new(NotificationChannel::Slack, "#deploys".to_string());
+
+    channel.send(&DeploymentFinished, &recipient).await?;
+
+    let payloads = payloads.lock().unwrap();
+    assert_eq!(payloads.len(), 1);
+    assert_eq!(
+        payloads[0],
+        serde_json::json!({
+            "text": "Deployed v2.4.0",
+            "username": "Deploy Bot",
+            "channel": "#deploys",
+            "attachments": [{
+                "color": "#36a64f",
+                "title": "Production",
+                "fields": [{ "title": "Version", "value": "2.4.0", "short": true }],
+            }],
+        })
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_route_can_be_a_webhook_url() -> Result<()> {
+    let (webhook_url, payloads) = start_webhook(StatusCode::OK, "ok").await?;
+    let channel = SlackChannel::with_webhook_url("http://127.0.0.1:9/unused".to_string());
+    let recipient = OnDemandRecipient::new(NotificationChannel::Slack, webhook_url);
+
+    channel.send(&DeploymentFinished, &recipient).await?;
+
+    let payloads = payloads.lock().unwrap();
+    assert_eq!(payloads.len(), 1);
+    assert!(payloads[0].get("channel").is_none(), "the webhook's own channel is used");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_non_success_response_is_an_error() -> Result<()> {
+    let (webhook_url, _) = start_webhook(StatusCode::NOT_FOUND, "channel_not_found").await?;
+    let channel = SlackChannel::with_webhook_url(webhook_url);
+    let recipient = OnDemandRecipient::new(NotificationChannel::Slack, "#archived".to_string());
+
+    let error = channel.send(&DeploymentFinished, &recipient).await.expect_err("Slack rejected the message");
+
+    let error = error.to_string();
+    assert!(error.contains("404"), "unexpected error: {}", error);
+    assert!(error.contains("channel_not_found"), "unexpected error: {}", error);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_notifiable_without_slack_route_is_skipped() -> Result<()> {
+    let (webhook_url, payloads) = start_webhook(StatusCode::OK, "ok").await?;
+    let channel = SlackChannel::with_webhook_url(webhook_url);
+    let user = User::new(
+        "No Slack".to_string(),
+        "noslack@example.com".to_string(),
+        "password".to_string(),
+        "system",
+    );
+
+    channel.send(&DeploymentFinished, &user).await?;
+
+    assert!(payloads.lock().unwrap().is_empty());
+    Ok(())
+}
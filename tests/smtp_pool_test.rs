@@ -0,0 +1,140 @@
+//! SMTP Connection Pool Tests
+//!
+//! These tests run the SMTP driver against a minimal in-process SMTP server
+//! and verify that consecutive sends reuse one connection, and that the
+//! connections are reopened after the configured number of messages.
+
+use anyhow::Result;
+use rustaxum::app::mail::drivers::{SmtpDriver, SmtpPoolConfig};
+use rustaxum::app::mail::drivers::smtp_driver::SmtpEncryption;
+use rustaxum::app::mail::{MailContent, MailDriver, MailMessage};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+#[derive(Clone, Default)]
+struct MockSmtpServer {
+    connections: Arc<AtomicUsize>,
+    messages: Arc<AtomicUsize>,
+}
+
+impl MockSmtpServer {
+    async fn start(&self) -> Result<u16> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let port = listener.local_addr()?.port();
+        let server = self.clone();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                server.connections.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(Self::serve(stream, server.messages.clone()));
+            }
+        });
+
+        Ok(port)
+    }
+
+    async fn serve(stream: TcpStream, messages: Arc<AtomicUsize>) -> Result<()> {
+        let (read, mut write) = stream.into_split();
+        let mut lines = BufReader::new(read).lines();
+        let mut in_data = false;
+
+        write.write_all(b"220 localhost ESMTP mock\r\n").await?;
+
+        while let Some(line) = lines.next_line().await? {
+            if in_data {
+                if line == "." {
+                    in_data = false;
+                    messages.fetch_add(1, Ordering::SeqCst);
+                    write.write_all(b"250 2.0.0 Queued\r\n").await?;
+                }
+                continue;
+            }
+
+            let command = line.to_uppercase();
+            if command.starts_with("DATA") {
+                in_data = true;
+                write.write_all(b"354 End data with <CR><LF>.<CR><LF>\r\n").await?;
+            } else if command.starts_with("QUIT") {
+                write.write_all(b"221 2.0.0 Bye\r\n").await?;
+                break;
+            } else {
+                write.write_all(b"250 OK\r\n").await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn driver(port: u16, max_messages: u32) -> SmtpDriver {
+    SmtpDriver::new(
+        "127.0.0.1".to_string(),
+        port,
+        "RustAxum".to_string(),
+        "noreply@rustaxum.com".to_string(),
+    )
+    .with_encryption(SmtpEncryption::None)
+    .with_pool(SmtpPoolConfig {
+        max_size: 1,
+        idle_timeout: Duration::from_secs(60),
+        max_messages,
+    })
+}
+
+fn message(i: usize) -> MailMessage {
+    MailMessage::new()
+        .to(format!("user{}@example.com", i))
+        .subject(format!("Message {}", i))
+        .content(MailContent::Text("Hello".to_string()))
+}
+
+async fn send_all(driver: &SmtpDriver, count: usize) -> Result<()> {
+    for i in 0..count {
+        driver.send(message(i)).await?;
+        // Let the pool take the connection back before the next send
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_consecutive_sends_reuse_one_connection() -> Result<()> {
+    let server = MockSmtpServer::default();
+    let port = server.start().await?;
+    let driver = driver(port, 0);
+
+    send_all(&driver, 5).await?;
+
+    assert_eq!(server.messages.load(Ordering::SeqCst), 5);
+    assert_eq!(server.connections.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_connections_reopen_after_max_messages() -> Result<()> {
+    let server = MockSmtpServer::default();
+    let port = server.start().await?;
+    let driver = driver(port, 2);
+
+    send_all(&driver, 5).await?;
+
+    assert_eq!(server.messages.load(Ordering::SeqCst), 5);
+    assert_eq!(server.connections.load(Ordering::SeqCst), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_clones_share_the_pool() -> Result<()> {
+    let server = MockSmtpServer::default();
+    let port = server.start().await?;
+    let driver = driver(port, 0);
+
+    send_all(&driver, 2).await?;
+    send_all(&driver.clone(), 2).await?;
+
+    assert_eq!(server.connections.load(Ordering::SeqCst), 1);
+    Ok(())
+}
@@ -0,0 +1,176 @@
+//! Server-Sent Events Tests
+//!
+//! These tests verify that broadcasts to a channel reach clients of
+//! `GET /sse/{channel}`, that a reconnecting client resumes from
+//! `Last-Event-ID`, that private channels need credentials, and that a
+//! zero heartbeat interval is raised to one second.
+
+use anyhow::Result;
+use axum::{
+    body::{Body, BodyDataStream},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use futures::StreamExt;
+use rustaxum::app::broadcasting::sse::sse_routes;
+use rustaxum::app::broadcasting::websocket::WebSocketManager;
+use rustaxum::app::broadcasting::BroadcastMessage;
+use rustaxum::config::broadcasting::BroadcastingConfig;
+use serial_test::serial;
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+
+/// One parsed `text/event-stream` frame
+#[derive(Debug)]
+struct SseEvent {
+    id: Option<String>,
+    event: String,
+    data: BroadcastMessage,
+}
+
+fn app(manager: Arc<WebSocketManager>) -> Router {
+    Router::new().nest("/sse", sse_routes().with_state(manager))
+}
+
+fn message(channel: &str, event: &str, data: serde_json::Value) -> BroadcastMessage {
+    BroadcastMessage {
+        channel: channel.to_string(),
+        event: event.to_string(),
+        data,
+        timestamp: chrono::Utc::now(),
+        id: None,
+    }
+}
+
+async fn open(manager: Arc<WebSocketManager>, uri: &str, last_event_id: Option<&str>) -> Result<(StatusCode, BodyDataStream)> {
+    let mut request = Request::builder().uri(uri);
+    if let Some(last_event_id) = last_event_id {
+        request = request.header("Last-Event-ID", last_event_id);
+    }
+
+    let response = app(manager).oneshot(request.body(Body::empty())?).await?;
+    if response.status() == StatusCode::OK {
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+    }
+    Ok((response.status(), response.into_body().into_data_stream()))
+}
+
+/// Read frames until `count` events (not heartbeats) have arrived
+async fn read_events(stream: &mut BodyDataStream, buffer: &mut String, count: usize) -> Result<Vec<SseEvent>> {
+    let mut events = Vec::new();
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while events.len() < count {
+            while let Some(end) = buffer.find("\n\n") {
+                let frame: String = buffer.drain(..end + 2).collect();
+                if let Some(event) = parse(&frame)? {
+                    events.push(event);
+                }
+            }
+            if events.len() >= count {
+                break;
+            }
+
+            let chunk = stream.next().await.expect("stream ended")?;
+            buffer.push_str(std::str::from_utf8(&chunk)?);
+        }
+        Ok::<(), anyhow::Error>(())
+    })
+    .await??;
+
+    Ok(events)
+}
+
+fn parse(frame: &str) -> Result<Option<SseEvent>> {
+    let (mut id, mut event, mut data) = (None, None, None);
+    for line in frame.lines() {
+        if let Some(value) = line.strip_prefix("id:") {
+            id = Some(value.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("event:") {
+            event = Some(value.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("data:") {
+            data = Some(value.trim().to_string());
+        }
+    }
+
+    match (event, data) {
+        (Some(event), Some(data)) if event != "connected" => Ok(Some(SseEvent { id, event, data: serde_json::from_str(&data)? })),
+        _ => Ok(None),
+    }
+}
+
+#[tokio::test]
+async fn test_broadcast_reaches_sse_stream() -> Result<()> {
+    let manager = Arc::new(WebSocketManager::new());
+    let (status, mut stream) = open(manager.clone(), "/sse/general", None).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    manager.broadcast(message("general", "order.shipped", serde_json::json!({ "order": 42 }))).await?;
+
+    let events = read_events(&mut stream, &mut String::new(), 1).await?;
+    assert_eq!(events[0].event, "order.shipped");
+    assert_eq!(events[0].data.channel, "general");
+    assert_eq!(events[0].data.data["order"], 42);
+    assert_eq!(events[0].id.as_deref(), Some("1"));
+    assert_eq!(manager.connection_count("general").await, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reconnect_resumes_from_last_event_id() -> Result<()> {
+    let manager = Arc::new(WebSocketManager::new());
+    let (_, mut first) = open(manager.clone(), "/sse/public", None).await?;
+
+    for n in 1..=3 {
+        manager.broadcast(message("public", "tick", serde_json::json!({ "n": n }))).await?;
+    }
+    let seen = read_events(&mut first, &mut String::new(), 1).await?;
+    let last_event_id = seen[0].id.clone().expect("broadcasts carry an id");
+    drop(first);
+
+    // The client saw the first tick, so the second and third are replayed before live events
+    let (_, mut second) = open(manager.clone(), "/sse/public", Some(&last_event_id)).await?;
+    manager.broadcast(message("public", "tick", serde_json::json!({ "n": 4 }))).await?;
+
+    let replayed = read_events(&mut second, &mut String::new(), 3).await?;
+    let numbers: Vec<i64> = replayed.iter().map(|event| event.data.data["n"].as_i64().unwrap()).collect();
+    assert_eq!(numbers, vec![2, 3, 4]);
+
+    // Clients that cannot set headers pass the id in the query instead
+    let (_, mut third) = open(manager.clone(), "/sse/public?last_event_id=3", None).await?;
+    let replayed = read_events(&mut third, &mut String::new(), 1).await?;
+    assert_eq!(replayed[0].data.data["n"], 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_private_channel_requires_credentials() -> Result<()> {
+    let manager = Arc::new(WebSocketManager::new());
+
+    let (status, _) = open(manager.clone(), "/sse/user.01ARZ3NDEKTSV4RRFFQ69G5FAV", None).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = open(manager.clone(), "/sse/admin?channel_auth=forged.0.00", None).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(manager.connection_count("admin").await, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_zero_heartbeat_is_clamped() -> Result<()> {
+    let original = std::env::var("BROADCAST_SSE_HEARTBEAT_SECS").ok();
+    std::env::set_var("BROADCAST_SSE_HEARTBEAT_SECS", "0");
+    let loaded = BroadcastingConfig::from_env();
+    match original {
+        Some(secs) => std::env::set_var("BROADCAST_SSE_HEARTBEAT_SECS", secs),
+        None => std::env::remove_var("BROADCAST_SSE_HEARTBEAT_SECS"),
+    }
+
+    assert_eq!(loaded?.sse_heartbeat_secs, 1);
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! Statement Timeout Tests
+//!
+//! These tests verify that pooled connections carry the configured
+//! `statement_timeout`, that a transaction can raise it without leaking the
+//! change back into the pool, and that a query running past it is cancelled
+//! by Postgres and mapped to a 504 instead of hanging.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use diesel::pg::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sql_types::Text;
+use diesel::RunQueryDsl;
+use rustaxum::app::query_builder::QueryParamsError;
+use rustaxum::config::Config;
+use rustaxum::database::{with_statement_timeout, StatementTimedOut, StatementTimeout};
+use serial_test::serial;
+use std::time::{Duration, Instant};
+
+fn current_timeout(conn: &mut PgConnection) -> Result<String> {
+    Ok(diesel::select(diesel::dsl::sql::<Text>("current_setting('statement_timeout')")).get_result(conn)?)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_query_past_the_timeout_is_cancelled() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let mut conn = pool.get()?;
+
+    let started = Instant::now();
+    let error = with_statement_timeout(&mut conn, Some(Duration::from_millis(100)), |conn| {
+        diesel::sql_query("SELECT pg_sleep(5)").execute(conn).map_err(anyhow::Error::from)
+    }).unwrap_err();
+
+    assert!(started.elapsed() < Duration::from_secs(4), "the query was not cancelled");
+    assert_eq!(StatementTimedOut::from_error(&error), Some(StatementTimedOut));
+    assert_eq!(QueryParamsError::status_code(&error), StatusCode::GATEWAY_TIMEOUT);
+
+    let response = QueryParamsError::response(&error);
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    let body: serde_json::Value = serde_json::from_slice(&body)?;
+    assert_eq!(body["error"], "The query took too long and was cancelled");
+
+    // The connection is still usable once the transaction has rolled back
+    diesel::sql_query("SELECT 1").execute(&mut conn)?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_pool_default_and_transaction_override() -> Result<()> {
+    let config = Config::load()?;
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_customizer(Box::new(StatementTimeout::new(Some(Duration::from_millis(250)))))
+        .build(ConnectionManager::<PgConnection>::new(&config.database.url))?;
+
+    let mut conn = pool.get()?;
+    assert_eq!(current_timeout(&mut conn)?, "250ms");
+
+    let raised = with_statement_timeout(&mut conn, Some(Duration::from_secs(120)), current_timeout)?;
+    assert_eq!(raised, "2min");
+    let unlimited = with_statement_timeout(&mut conn, None, |conn| {
+        diesel::sql_query("SELECT pg_sleep(0.5)").execute(conn)?;
+        current_timeout(conn)
+    })?;
+    assert_eq!(unlimited, "0");
+
+    // The override ends with its transaction, so the pooled connection keeps the default
+    drop(conn);
+    assert_eq!(current_timeout(&mut pool.get()?)?, "250ms");
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_other_database_errors_are_not_timeouts() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let mut conn = pool.get()?;
+
+    let error = anyhow::Error::from(diesel::sql_query("SELECT * FROM no_such_table").execute(&mut conn).unwrap_err());
+    assert_eq!(StatementTimedOut::from_error(&error), None);
+    assert_eq!(QueryParamsError::status_code(&error), StatusCode::INTERNAL_SERVER_ERROR);
+    Ok(())
+}
+
+#[test]
+fn test_timeout_error_maps_to_504() {
+    let error = anyhow::Error::from(StatementTimedOut).context("Export of countries failed");
+    assert_eq!(QueryParamsError::status_code(&error), StatusCode::GATEWAY_TIMEOUT);
+}
@@ -0,0 +1,105 @@
+//! Strict Filter Parsing Tests
+//!
+//! These tests verify that strict parsing reports unknown operators, `between`
+//! with the wrong number of values and disallowed fields as a 400 listing
+//! each malformed filter, while lenient parsing keeps its old behavior.
+
+use axum::http::StatusCode;
+use rustaxum::app::query_builder::{Filter, FilterError, FilterOperator, FilterValue, QueryParams, QueryParamsError};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+const ALLOWED: &[&str] = &["name", "created_at", "iso_code"];
+
+fn params(filters: &[(&str, Value)]) -> QueryParams {
+    QueryParams {
+        filter: filters.iter().map(|(key, value)| (key.to_string(), value.clone())).collect(),
+        ..QueryParams::default()
+    }
+}
+
+async fn body(error: QueryParamsError) -> (StatusCode, Value) {
+    let response = QueryParamsError::response(&error.into());
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_unknown_operator_is_rejected() {
+    let error = params(&[("name[equals]", json!("Iceland"))]).validate_filters(ALLOWED).unwrap_err();
+    let QueryParamsError::MalformedFilters(filters) = &error else {
+        panic!("expected malformed filters, got {:?}", error);
+    };
+    assert_eq!(filters, &vec![FilterError::UnknownOperator {
+        filter: "filter[name][equals]".to_string(),
+        operator: "equals".to_string(),
+    }]);
+
+    let (status, body) = body(error).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["filters"][0]["filter"], "filter[name][equals]");
+    assert_eq!(body["filters"][0]["reason"], "unknown_operator");
+    assert!(body["error"].as_str().unwrap().contains("unknown operator 'equals'"));
+
+    // The nested object form is checked the same way
+    let error = params(&[("name", json!({ "equals": "Iceland" }))]).validate_filters(ALLOWED).unwrap_err();
+    assert!(matches!(&error, QueryParamsError::MalformedFilters(filters) if matches!(filters[0], FilterError::UnknownOperator { .. })));
+}
+
+#[tokio::test]
+async fn test_between_with_one_value_is_rejected() {
+    let error = params(&[("created_at[between]", json!("2024-01-01"))]).validate_filters(ALLOWED).unwrap_err();
+    let QueryParamsError::MalformedFilters(filters) = &error else {
+        panic!("expected malformed filters, got {:?}", error);
+    };
+    assert_eq!(filters, &vec![FilterError::WrongArgumentCount {
+        filter: "filter[created_at][between]".to_string(),
+        operator: "between".to_string(),
+        expected: "2",
+        given: 1,
+    }]);
+
+    let (status, body) = body(error).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["filters"][0]["reason"], "wrong_argument_count");
+    assert_eq!(body["filters"][0]["given"], 1);
+}
+
+#[tokio::test]
+async fn test_every_malformed_filter_is_listed() {
+    let error = params(&[
+        ("created_at[between]", json!(["2024-01-01", "2024-02-01", "2024-03-01"])),
+        ("iso_code[in]", json!("")),
+        ("name[equals]", json!("Iceland")),
+        ("password", json!("secret")),
+    ]).validate_filters(ALLOWED).unwrap_err();
+
+    let (status, body) = body(error).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let reasons: Vec<&str> = body["filters"].as_array().unwrap().iter().map(|filter| filter["reason"].as_str().unwrap()).collect();
+    assert_eq!(reasons, vec!["wrong_argument_count", "wrong_argument_count", "unknown_operator", "field_not_allowed"]);
+}
+
+#[test]
+fn test_well_formed_filters_parse() {
+    let mut filters = params(&[
+        ("created_at[between]", json!("2024-01-01,2024-02-01")),
+        ("iso_code[in]", json!(["IS", "NO"])),
+        ("name", json!("Iceland")),
+    ]).validate_filters(ALLOWED).unwrap();
+    filters.sort_by(|a, b| a.field.cmp(&b.field));
+
+    assert_eq!(filters[0].operator, FilterOperator::Between);
+    assert_eq!(filters[0].value, FilterValue::Range(json!("2024-01-01"), json!("2024-02-01")));
+    assert_eq!(filters[1].operator, FilterOperator::In);
+    assert_eq!(filters[2].operator, FilterOperator::Eq);
+    assert_eq!(filters[2].value, FilterValue::single("Iceland"));
+}
+
+#[test]
+fn test_lenient_parsing_is_unchanged() {
+    let filters = Filter::from_params(&HashMap::from([("name[equals]".to_string(), json!("Iceland"))]));
+    assert_eq!(filters.len(), 1);
+    assert_eq!(filters[0].operator, FilterOperator::Eq);
+}
@@ -0,0 +1,161 @@
+//! Timestamp Serialization Format Tests
+//!
+//! These tests verify that timestamps of response types are written as RFC
+//! 3339, epoch seconds or epoch milliseconds, that models stay RFC 3339
+//! whatever the request asked for, that payloads accept any of those forms,
+//! and that a request can pick the format.
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    middleware,
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use rustaxum::app::http::middleware::timestamp_format_middleware::timestamp_format_middleware;
+use rustaxum::app::models::country::Country;
+use rustaxum::app::models::DieselUlid;
+use rustaxum::app::utils::timestamp::with_timestamp_format;
+use rustaxum::config::response::TimestampFormat;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+const RFC3339: &str = "2024-01-15T09:30:00.250Z";
+const EPOCH_SECONDS: i64 = 1_705_311_000;
+const EPOCH_MILLIS: i64 = 1_705_311_000_250;
+
+fn timestamp() -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(EPOCH_MILLIS).unwrap()
+}
+
+fn country() -> Country {
+    Country {
+        id: DieselUlid::new(),
+        name: "Iceland".to_string(),
+        iso_code: "IS".to_string(),
+        phone_code: Some("+354".to_string()),
+        created_at: timestamp(),
+        updated_at: timestamp(),
+        deleted_at: None,
+        created_by_id: DieselUlid::new(),
+        updated_by_id: DieselUlid::new(),
+        deleted_by_id: None,
+    }
+}
+
+#[derive(Serialize)]
+struct BanResponse {
+    #[serde(with = "rustaxum::app::utils::timestamp::response")]
+    created_at: DateTime<Utc>,
+    #[serde(with = "rustaxum::app::utils::timestamp::response::option")]
+    lifted_at: Option<DateTime<Utc>>,
+    #[serde(with = "rustaxum::app::utils::timestamp::response::option")]
+    expires_at: Option<DateTime<Utc>>,
+}
+
+fn ban() -> BanResponse {
+    BanResponse { created_at: timestamp(), lifted_at: Some(timestamp()), expires_at: None }
+}
+
+async fn serialize_as<T: Serialize>(format: TimestampFormat, value: &T) -> Value {
+    with_timestamp_format(format, async { serde_json::to_value(value).unwrap() }).await
+}
+
+#[tokio::test]
+async fn test_rfc3339_format() {
+    let json = serialize_as(TimestampFormat::Rfc3339, &ban()).await;
+
+    assert_eq!(json["created_at"], RFC3339);
+    assert_eq!(json["lifted_at"], RFC3339);
+    assert_eq!(json["expires_at"], Value::Null);
+}
+
+#[tokio::test]
+async fn test_epoch_seconds_format() {
+    let json = serialize_as(TimestampFormat::EpochSeconds, &ban()).await;
+
+    assert_eq!(json["created_at"], EPOCH_SECONDS);
+    assert_eq!(json["lifted_at"], EPOCH_SECONDS);
+    assert_eq!(json["expires_at"], Value::Null);
+}
+
+#[tokio::test]
+async fn test_epoch_millis_format() {
+    let json = serialize_as(TimestampFormat::EpochMillis, &country().to_response()).await;
+
+    assert_eq!(json["created_at"], EPOCH_MILLIS);
+    assert_eq!(json["updated_at"], EPOCH_MILLIS);
+}
+
+#[tokio::test]
+async fn test_models_stay_rfc3339_inside_a_requested_format() {
+    let json = serialize_as(TimestampFormat::EpochMillis, &country()).await;
+
+    assert_eq!(json["created_at"], RFC3339);
+    assert_eq!(json["deleted_at"], Value::Null);
+    assert_eq!(serde_json::from_value::<Country>(json).unwrap().created_at, timestamp());
+}
+
+#[test]
+fn test_deserialization_accepts_every_form() {
+    let mut json = serde_json::to_value(country()).unwrap();
+
+    for (value, expected) in [
+        (json!(RFC3339), timestamp()),
+        (json!("2024-01-15T11:30:00.250+02:00"), timestamp()),
+        (json!(EPOCH_MILLIS), timestamp()),
+        (json!(EPOCH_MILLIS.to_string()), timestamp()),
+        (json!(EPOCH_SECONDS), Utc.timestamp_opt(EPOCH_SECONDS, 0).unwrap()),
+    ] {
+        json["created_at"] = value.clone();
+        json["deleted_at"] = value;
+        let country = serde_json::from_value::<Country>(json.clone()).unwrap();
+        assert_eq!(country.created_at, expected);
+        assert_eq!(country.deleted_at, Some(expected));
+    }
+
+    // Omitted optional timestamps stay empty
+    json.as_object_mut().unwrap().remove("deleted_at");
+    assert_eq!(serde_json::from_value::<Country>(json.clone()).unwrap().deleted_at, None);
+
+    json["created_at"] = json!("yesterday");
+    assert!(serde_json::from_value::<Country>(json).is_err());
+}
+
+#[test]
+fn test_format_names() {
+    assert_eq!(TimestampFormat::parse("rfc3339"), Some(TimestampFormat::Rfc3339));
+    assert_eq!(TimestampFormat::parse("epoch"), Some(TimestampFormat::EpochSeconds));
+    assert_eq!(TimestampFormat::parse("epoch-millis"), Some(TimestampFormat::EpochMillis));
+    assert_eq!(TimestampFormat::parse("fortnights"), None);
+    assert_eq!(TimestampFormat::from("fortnights"), TimestampFormat::Rfc3339);
+}
+
+async fn get_created_at(uri: &str, accept: Option<&str>) -> Value {
+    let app = Router::new()
+        .route("/country", get(|| async { Json(country().to_response()) }))
+        .layer(middleware::from_fn(timestamp_format_middleware));
+
+    let mut request = Request::builder().uri(uri);
+    if let Some(accept) = accept {
+        request = request.header(header::ACCEPT, accept);
+    }
+
+    let response = app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice::<Value>(&body).unwrap()["created_at"].clone()
+}
+
+#[tokio::test]
+async fn test_request_selects_format() {
+    assert_eq!(get_created_at("/country", None).await, RFC3339);
+    assert_eq!(get_created_at("/country?timestamp_format=epoch_seconds", None).await, EPOCH_SECONDS);
+    assert_eq!(get_created_at("/country", Some("application/json; profile=epoch_millis")).await, EPOCH_MILLIS);
+    assert_eq!(get_created_at("/country", Some("application/json; profile=\"bare epoch_millis\"")).await, EPOCH_MILLIS);
+
+    // The query string wins over the Accept profile
+    assert_eq!(get_created_at("/country?timestamp_format=rfc3339", Some("application/json; profile=epoch_millis")).await, RFC3339);
+}
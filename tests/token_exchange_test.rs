@@ -0,0 +1,199 @@
+//! Token Exchange Integration Tests
+//!
+//! These tests verify that a delegation exchange issues a token naming its
+//! actor in an `act` claim chain, that clients not configured for token
+//! exchange or for the requested audience are refused, and that a
+//! confidential client naming itself without its secret is `invalid_client`.
+
+mod common;
+
+use anyhow::Result;
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use diesel::prelude::*;
+use jsonwebtoken::{decode, DecodingKey, Validation};
+use serde_json::{json, Map, Value};
+use rustaxum::app::models::oauth::{Client, CreateAccessToken};
+use rustaxum::app::services::oauth::{
+    ClientService, TokenExchangeError, TokenExchangeRequest, TokenExchangeService, TokenService, TOKEN_EXCHANGE_GRANT_TYPE,
+};
+use rustaxum::database::DbPool;
+use rustaxum::schema::oauth_clients;
+use serial_test::serial;
+use tower::ServiceExt;
+
+const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+fn create_client(pool: &DbPool, metadata: Value) -> Result<Client> {
+    let user = common::create_user(pool)?;
+    let client = ClientService::create_client_record(pool, Client::new(
+        None,
+        Some(user.id),
+        "Token Exchange Test Client".to_string(),
+        Some("exchange_secret".to_string()),
+        "http://localhost/callback".to_string(),
+        false,
+        false,
+        user.id,
+    ))?;
+
+    let mut conn = pool.get()?;
+    diesel::update(oauth_clients::table.find(client.id.to_string()))
+        .set(oauth_clients::metadata.eq(Some(metadata)))
+        .execute(&mut conn)?;
+
+    Ok(client)
+}
+
+/// Issue an access token JWT for a new user through the given client
+async fn issue_token(pool: &DbPool, client: &Client, scopes: &[&str]) -> Result<(String, String)> {
+    let user = common::create_user(pool)?;
+    let client_id = client.id.to_string();
+
+    let access_token = TokenService::create_access_token(pool, CreateAccessToken {
+        user_id: Some(user.id.to_string()),
+        client_id: client_id.clone(),
+        name: None,
+        scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+        expires_at: None,
+        jwk_thumbprint: None,
+    }, Some(3600), None).await?;
+
+    let jwt = TokenService::generate_jwt_token(pool, &access_token, &client_id)?;
+    Ok((user.id.to_string(), jwt))
+}
+
+fn exchange_request(subject_token: &str, actor_token: Option<&str>, audience: Option<&str>) -> TokenExchangeRequest {
+    TokenExchangeRequest {
+        grant_type: TOKEN_EXCHANGE_GRANT_TYPE.to_string(),
+        resource: None,
+        audience: audience.map(String::from),
+        scope: Some("read".to_string()),
+        requested_token_type: None,
+        subject_token: subject_token.to_string(),
+        subject_token_type: ACCESS_TOKEN_TYPE.to_string(),
+        actor_token: actor_token.map(String::from),
+        actor_token_type: actor_token.map(|_| ACCESS_TOKEN_TYPE.to_string()),
+    }
+}
+
+fn claims(token: &str) -> Result<Map<String, Value>> {
+    let secret = std::env::var("OAUTH_JWT_SECRET").unwrap_or_else(|_| "oauth-jwt-secret".to_string());
+    let mut validation = Validation::default();
+    validation.validate_aud = false;
+    Ok(decode::<Map<String, Value>>(token, &DecodingKey::from_secret(secret.as_ref()), &validation)?.claims)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_delegation_exchange_adds_act_claim() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let issuer = create_client(&pool, json!({}))?;
+    let service = create_client(&pool, json!({
+        "token_exchange_scenarios": ["delegation"],
+        "token_exchange_audiences": ["*.example.com"],
+    }))?;
+    let service_id = service.id.to_string();
+
+    let (subject_user, subject_token) = issue_token(&pool, &issuer, &["read", "write"]).await?;
+    let (actor_user, actor_token) = issue_token(&pool, &issuer, &["read"]).await?;
+
+    let response = TokenExchangeService::exchange_token(
+        &pool,
+        &service_id,
+        exchange_request(&subject_token, Some(&actor_token), Some("https://api.example.com")),
+    ).await?;
+    assert_eq!(response.scope.as_deref(), Some("read"));
+
+    let exchanged = claims(&response.access_token)?;
+    assert_eq!(exchanged["sub"], json!(subject_user));
+    assert_eq!(exchanged["aud"], json!("https://api.example.com"));
+    assert_eq!(exchanged["act"], json!({ "sub": actor_user, "client_id": issuer.id.to_string() }));
+
+    // Delegating the delegated token again nests the earlier actor
+    let (next_actor_user, next_actor_token) = issue_token(&pool, &issuer, &["read"]).await?;
+    let response = TokenExchangeService::exchange_token(
+        &pool,
+        &service_id,
+        exchange_request(&response.access_token, Some(&next_actor_token), None),
+    ).await?;
+
+    let chained = claims(&response.access_token)?;
+    assert_eq!(chained["sub"], json!(subject_user));
+    assert_eq!(chained["act"]["sub"], json!(next_actor_user));
+    assert_eq!(chained["act"]["act"]["sub"], json!(actor_user));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_unauthorized_client_is_rejected() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let issuer = create_client(&pool, json!({}))?;
+    let (_, subject_token) = issue_token(&pool, &issuer, &["read"]).await?;
+    let (_, actor_token) = issue_token(&pool, &issuer, &["read"]).await?;
+
+    // Clients must opt in to token exchange
+    let error = TokenExchangeService::exchange_token(
+        &pool,
+        &issuer.id.to_string(),
+        exchange_request(&subject_token, Some(&actor_token), None),
+    ).await.expect_err("clients without token exchange scenarios must be rejected");
+    assert_eq!(TokenExchangeError::error_code(&error), "unauthorized_client");
+
+    // And may only perform the scenarios they are configured for
+    let impersonator = create_client(&pool, json!({ "token_exchange_scenarios": ["impersonation"] }))?;
+    let error = TokenExchangeService::exchange_token(
+        &pool,
+        &impersonator.id.to_string(),
+        exchange_request(&subject_token, Some(&actor_token), None),
+    ).await.expect_err("delegation must be configured for the client");
+    assert_eq!(TokenExchangeError::error_code(&error), "unauthorized_client");
+
+    // Audiences outside the client's allow-list are refused
+    let delegator = create_client(&pool, json!({
+        "token_exchange_scenarios": ["delegation"],
+        "token_exchange_audiences": ["*.example.com"],
+    }))?;
+    let error = TokenExchangeService::exchange_token(
+        &pool,
+        &delegator.id.to_string(),
+        exchange_request(&subject_token, Some(&actor_token), Some("https://api.example.com.evil.test")),
+    ).await.expect_err("audiences outside the allow-list must be rejected");
+    assert_eq!(TokenExchangeError::error_code(&error), "invalid_target");
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_confidential_client_without_secret_is_rejected() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let issuer = create_client(&pool, json!({}))?;
+    let service = create_client(&pool, json!({ "token_exchange_scenarios": ["delegation"] }))?;
+    let (_, subject_token) = issue_token(&pool, &issuer, &["read"]).await?;
+
+    let body = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("grant_type", TOKEN_EXCHANGE_GRANT_TYPE)
+        .append_pair("subject_token", &subject_token)
+        .append_pair("subject_token_type", ACCESS_TOKEN_TYPE)
+        .append_pair("client_id", &service.id.to_string())
+        .finish();
+    let request = Request::builder()
+        .method("POST")
+        .uri("/oauth/token-exchange")
+        .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(Body::from(body))?;
+
+    let response = rustaxum::routes::oauth::oauth_routes()
+        .with_state(pool.clone())
+        .oneshot(request)
+        .await?;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    let body: Value = serde_json::from_slice(&bytes)?;
+    assert_eq!(body["error"], "invalid_client");
+
+    Ok(())
+}
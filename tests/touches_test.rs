@@ -0,0 +1,78 @@
+//! Parent Touching Integration Tests
+//!
+//! These tests verify that writing a message bumps its conversation's
+//! `updated_at`, so conversations sort by their latest activity.
+
+mod common;
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use diesel::prelude::*;
+use rustaxum::app::models::conversation::Conversation;
+use rustaxum::app::services::message_service::{MessageService, SendMessageRequest};
+use rustaxum::app::traits::Touches;
+use rustaxum::database::DbPool;
+use rustaxum::schema::conversations;
+use serial_test::serial;
+
+/// Move the conversation's timestamp into the past so a touch is observable
+fn backdate(pool: &DbPool, conversation: &Conversation) -> Result<DateTime<Utc>> {
+    let past = Utc::now() - Duration::hours(1);
+    let mut conn = pool.get()?;
+    diesel::update(conversations::table.find(conversation.id.to_string()))
+        .set(conversations::updated_at.eq(past))
+        .execute(&mut conn)?;
+    Ok(past)
+}
+
+fn updated_at(pool: &DbPool, conversation: &Conversation) -> Result<DateTime<Utc>> {
+    let mut conn = pool.get()?;
+    let updated_at = conversations::table
+        .find(conversation.id.to_string())
+        .select(conversations::updated_at)
+        .first(&mut conn)?;
+    Ok(updated_at)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_sending_a_message_touches_its_conversation() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let sender = common::create_user(&pool)?;
+    let device = common::create_device(&pool, &sender)?;
+    let conversation = common::create_conversation(&pool, &sender)?;
+    common::add_participant(&pool, &conversation, &sender)?;
+    let past = backdate(&pool, &conversation)?;
+
+    let sent = MessageService::send(&pool, &conversation.id.to_string(), &sender.id.to_string(), SendMessageRequest {
+        device_id: device.id,
+        message_type: None,
+        encrypted_content: "hello".to_string(),
+        content_algorithm: "none".to_string(),
+        reply_to_message_id: None,
+        mentions: None,
+    }).await?;
+
+    let touched_at = updated_at(&pool, &conversation)?;
+    assert!(touched_at > past);
+    assert!(touched_at >= sent.message.created_at - Duration::seconds(1));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_touch_parents_updates_each_touched_relation() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let sender = common::create_user(&pool)?;
+    let device = common::create_device(&pool, &sender)?;
+    let conversation = common::create_conversation(&pool, &sender)?;
+    let message = common::create_message(&pool, &conversation, &sender, &device)?;
+    let past = backdate(&pool, &conversation)?;
+
+    let mut conn = pool.get()?;
+    assert_eq!(message.touch_parents(&mut conn)?, 1);
+    assert!(updated_at(&pool, &conversation)? > past);
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+//! Trusted Proxy Tests
+//!
+//! These tests verify that forwarding headers are only believed when the
+//! socket peer is a trusted proxy, that the client is the rightmost untrusted
+//! address in `X-Forwarded-For`, and that the resolved client reaches
+//! handlers through the request extensions.
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, Extension},
+    http::{HeaderMap, HeaderValue, Request, StatusCode},
+    middleware,
+    routing::get,
+    Json, Router,
+};
+use rustaxum::app::http::middleware::trusted_proxy_middleware::{
+    trusted_proxy_middleware, ClientInfo, TrustedProxies,
+};
+use serde_json::{json, Value};
+use std::net::{IpAddr, SocketAddr};
+use tower::ServiceExt;
+
+fn ip(value: &str) -> IpAddr {
+    value.parse().unwrap()
+}
+
+fn forwarded(forwarded_for: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-forwarded-for", HeaderValue::from_str(forwarded_for).unwrap());
+    headers.insert("x-forwarded-proto", HeaderValue::from_static("https"));
+    headers.insert("x-forwarded-host", HeaderValue::from_static("app.example.com"));
+    headers.insert("host", HeaderValue::from_static("10.0.0.5:3000"));
+    headers
+}
+
+#[test]
+fn test_untrusted_peer_headers_are_ignored() {
+    let proxies = TrustedProxies::new(&["10.0.0.0/8"]);
+
+    let client = proxies.resolve(Some(ip("203.0.113.9")), &forwarded("198.51.100.1"));
+
+    assert_eq!(client.ip, Some(ip("203.0.113.9")));
+    assert_eq!(client.scheme, "http");
+    assert_eq!(client.host.as_deref(), Some("10.0.0.5:3000"));
+}
+
+#[test]
+fn test_trusted_peer_resolves_forwarded_client() {
+    let proxies = TrustedProxies::new(&["10.0.0.0/8"]);
+
+    let client = proxies.resolve(Some(ip("10.0.0.1")), &forwarded("198.51.100.1"));
+
+    assert_eq!(client.ip, Some(ip("198.51.100.1")));
+    assert_eq!(client.scheme, "https");
+    assert_eq!(client.root_url().as_deref(), Some("https://app.example.com"));
+}
+
+#[test]
+fn test_rightmost_untrusted_address_is_the_client() {
+    let proxies = TrustedProxies::new(&["10.0.0.0/8", "192.0.2.10"]);
+
+    // The client spoofed 1.1.1.1; 198.51.100.1 is the address our edge proxy saw
+    let client = proxies.resolve(
+        Some(ip("10.0.0.1")),
+        &forwarded("1.1.1.1, 198.51.100.1, 192.0.2.10, 10.1.2.3"),
+    );
+
+    assert_eq!(client.ip, Some(ip("198.51.100.1")));
+}
+
+#[test]
+fn test_chain_of_only_trusted_proxies_uses_leftmost() {
+    let proxies = TrustedProxies::new(&["10.0.0.0/8"]);
+
+    let client = proxies.resolve(Some(ip("10.0.0.1")), &forwarded("10.0.0.9, 10.0.0.8"));
+
+    assert_eq!(client.ip, Some(ip("10.0.0.9")));
+}
+
+#[test]
+fn test_malformed_hop_ends_the_chain() {
+    let proxies = TrustedProxies::new(&["10.0.0.0/8"]);
+
+    let client = proxies.resolve(Some(ip("10.0.0.1")), &forwarded("198.51.100.1, not-an-ip, 10.0.0.2"));
+
+    assert_eq!(client.ip, Some(ip("10.0.0.2")));
+}
+
+#[test]
+fn test_proxy_ranges() {
+    let proxies = TrustedProxies::new(&["192.168.0.0/16", "2001:db8::/32", "invalid", "10.0.0.0/40"]);
+
+    assert!(proxies.is_trusted(ip("192.168.44.1")));
+    assert!(!proxies.is_trusted(ip("192.169.0.1")));
+    assert!(proxies.is_trusted(ip("2001:db8::1")));
+    assert!(!proxies.is_trusted(ip("2001:db9::1")));
+    assert!(proxies.is_trusted(ip("::ffff:192.168.1.1")));
+    assert!(!proxies.is_trusted(ip("10.0.0.1")));
+
+    assert!(TrustedProxies::new(&["*"]).is_trusted(ip("203.0.113.9")));
+    assert!(!TrustedProxies::new::<&str>(&[]).is_trusted(ip("127.0.0.1")));
+}
+
+async fn client_seen_by_handler(peer: &str, forwarded_for: &str) -> Value {
+    let app = Router::new()
+        .route("/client", get(|Extension(client): Extension<ClientInfo>| async move {
+            Json(json!({ "ip": client.ip_string(), "scheme": client.scheme }))
+        }))
+        .layer(middleware::from_fn_with_state(TrustedProxies::new(&["10.0.0.0/8"]), trusted_proxy_middleware));
+
+    let mut request = Request::builder()
+        .uri("/client")
+        .header("X-Forwarded-For", forwarded_for)
+        .header("X-Forwarded-Proto", "https")
+        .body(Body::empty())
+        .unwrap();
+    let peer: SocketAddr = format!("{}:40000", peer).parse().unwrap();
+    request.extensions_mut().insert(ConnectInfo(peer));
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_middleware_exposes_resolved_client() {
+    assert_eq!(
+        client_seen_by_handler("10.0.0.1", "198.51.100.1").await,
+        json!({ "ip": "198.51.100.1", "scheme": "https" })
+    );
+    assert_eq!(
+        client_seen_by_handler("203.0.113.9", "198.51.100.1").await,
+        json!({ "ip": "203.0.113.9", "scheme": "http" })
+    );
+}
@@ -0,0 +1,80 @@
+//! Typing Indicator Integration Tests
+//!
+//! These tests verify that typing notifications reach the other
+//! participants of a conversation but are not echoed to the sender.
+
+mod common;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use rustaxum::app::broadcasting::websocket::websocket_manager;
+use rustaxum::app::services::conversation_service::ConversationError;
+use rustaxum::app::services::typing_indicator_service::{TypingIndicatorService, TypingRequest};
+use serial_test::serial;
+use std::time::Duration;
+
+#[tokio::test]
+#[serial]
+async fn test_typing_is_broadcast_to_others_but_not_sender() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+
+    let sender = common::create_user(&pool)?;
+    let recipient = common::create_user(&pool)?;
+    let sender_device = common::create_device(&pool, &sender)?;
+    let conversation = common::create_conversation(&pool, &sender)?;
+    common::add_participant(&pool, &conversation, &sender)?;
+    common::add_participant(&pool, &conversation, &recipient)?;
+
+    let manager = websocket_manager().await;
+    let mut sender_receiver = manager.subscribe(&format!("user.{}", sender.id)).await;
+    let mut recipient_receiver = manager.subscribe(&format!("user.{}", recipient.id)).await;
+
+    let state = TypingIndicatorService::typing(
+        &pool,
+        &conversation.id.to_string(),
+        &sender.id.to_string(),
+        TypingRequest {
+            device_id: sender_device.id,
+            is_typing: true,
+        },
+    ).await?;
+    assert!(state.is_typing);
+
+    let event = tokio::time::timeout(Duration::from_secs(2), recipient_receiver.recv()).await??;
+    assert_eq!(event.event, "typing");
+    assert_eq!(event.data["conversation_id"], conversation.id.to_string());
+    assert_eq!(event.data["user_id"], sender.id.to_string());
+    assert_eq!(event.data["is_typing"], true);
+
+    let echoed = tokio::time::timeout(Duration::from_millis(200), sender_receiver.recv()).await;
+    assert!(echoed.is_err(), "sender must not receive their own typing event");
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_non_participant_cannot_send_typing() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+
+    let owner = common::create_user(&pool)?;
+    let outsider = common::create_user(&pool)?;
+    let outsider_device = common::create_device(&pool, &outsider)?;
+    let conversation = common::create_conversation(&pool, &owner)?;
+    common::add_participant(&pool, &conversation, &owner)?;
+
+    let result = TypingIndicatorService::typing(
+        &pool,
+        &conversation.id.to_string(),
+        &outsider.id.to_string(),
+        TypingRequest {
+            device_id: outsider_device.id,
+            is_typing: true,
+        },
+    ).await;
+
+    let error = result.expect_err("outsiders must not send typing indicators");
+    assert_eq!(ConversationError::status_code(&error), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
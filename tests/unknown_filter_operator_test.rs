@@ -0,0 +1,79 @@
+//! Unknown Filter Operator Tests
+//!
+//! These tests verify that a filter naming an operator `FilterOperator` does
+//! not know is rejected with a structured 400 whether or not strict filtering
+//! is on, and that `Filterable` binds filter values as placeholders rather
+//! than writing them into SQL.
+
+use axum::http::StatusCode;
+use rustaxum::app::models::organization_domain::OrganizationDomain;
+use rustaxum::app::query_builder::{Bindings, Filter, Filterable, QueryBuilderExt, QueryParams, QueryParamsError};
+use serde_json::{json, Value};
+
+const ALLOWED: &[&str] = &["name", "code"];
+
+#[tokio::test]
+async fn test_unknown_operator_is_a_bad_request() -> anyhow::Result<()> {
+    let params = QueryParams::from_query_string("filter[name][nonsense]=x")?;
+
+    let error = OrganizationDomain::from_params(params).expect_err("unknown operator");
+    assert_eq!(QueryParamsError::status_code(&error), StatusCode::BAD_REQUEST);
+
+    let response = QueryParamsError::response(&error);
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    let body: Value = serde_json::from_slice(&bytes)?;
+    assert_eq!(
+        body["filters"],
+        json!([{
+            "reason": "unknown_operator",
+            "filter": "filter[name][nonsense]",
+            "operator": "nonsense",
+            "message": "filter[name][nonsense]: unknown operator 'nonsense'",
+        }])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_lenient_validation_only_reports_unknown_operators() -> anyhow::Result<()> {
+    let params = QueryParams::from_query_string("filter[name][nonsense]=x&filter[secret]=y&filter[code][between]=a")?;
+
+    let Err(QueryParamsError::MalformedFilters(errors)) = params.validate_filter_operators(ALLOWED) else {
+        panic!("expected the unknown operator to be reported");
+    };
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].to_string(), "filter[name][nonsense]: unknown operator 'nonsense'");
+
+    let params = QueryParams::from_query_string("filter[name][contains]=x&filter[secret]=y")?;
+    assert!(params.validate_filter_operators(ALLOWED).is_ok());
+    Ok(())
+}
+
+#[test]
+fn test_filterable_binds_values_instead_of_writing_them_into_sql() {
+    let mut bindings = Bindings::new();
+
+    let raw = Filter::raw("1=1", vec![]);
+    assert_eq!(OrganizationDomain::apply_filter(&raw, &mut bindings), "");
+
+    let injection = Filter::eq("name", "x' OR 1=1 --");
+    assert_eq!(OrganizationDomain::apply_filter(&injection, &mut bindings), "name = $1");
+    assert_eq!(OrganizationDomain::apply_filter(&Filter::gte("name", "M"), &mut bindings), "name >= $2");
+
+    // Pattern operators keep their wildcards instead of becoming exact matches
+    assert_eq!(OrganizationDomain::apply_filter(&Filter::contains("name", "gov"), &mut bindings), "name ILIKE $3");
+    assert_eq!(OrganizationDomain::apply_filter(&Filter::starts_with("name", "gov"), &mut bindings), "name ILIKE $4");
+    assert_eq!(OrganizationDomain::apply_filter(&Filter::ends_with("name", "ment"), &mut bindings), "name ILIKE $5");
+
+    assert_eq!(
+        bindings.values(),
+        &[
+            Some("x' OR 1=1 --".to_string()),
+            Some("M".to_string()),
+            Some("%gov%".to_string()),
+            Some("gov%".to_string()),
+            Some("%ment".to_string()),
+        ]
+    );
+}
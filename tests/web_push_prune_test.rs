@@ -0,0 +1,104 @@
+//! Web Push Pruning Integration Tests
+//!
+//! These tests verify that subscriptions reported as gone by the push
+//! service are removed and recorded in the web push metrics.
+
+mod common;
+
+use anyhow::Result;
+use axum::{http::StatusCode, routing::post, Router};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use diesel::prelude::*;
+use rustaxum::app::notifications::channels::web_push_channel::{WebPushChannel, WebPushMessage, WebPushSendError};
+use rustaxum::app::utils::{generate_vapid_keys, web_push_metrics, VapidKeyRing, VapidTokenGenerator};
+use rustaxum::schema::push_subscriptions;
+use serial_test::serial;
+
+// Start a fake push service that answers every request with the given status
+async fn start_push_service(status: StatusCode) -> Result<String> {
+    let app = Router::new().route("/push/{id}", post(move || async move { status }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.ok();
+    });
+
+    Ok(format!("http://{}/push/{}", addr, ulid::Ulid::new()))
+}
+
+// Browser-side subscription keys (p256dh, auth)
+fn subscription_keys() -> (String, String) {
+    use p256::elliptic_curve::rand_core::{OsRng, RngCore};
+    use p256::elliptic_curve::sec1::ToEncodedPoint;
+
+    let secret = p256::SecretKey::random(&mut OsRng);
+    let p256dh = URL_SAFE_NO_PAD.encode(secret.public_key().to_encoded_point(false).as_bytes());
+
+    let mut auth = [0u8; 16];
+    OsRng.fill_bytes(&mut auth);
+
+    (p256dh, URL_SAFE_NO_PAD.encode(auth))
+}
+
+fn test_channel() -> Result<WebPushChannel> {
+    let (private_key, public_key) = generate_vapid_keys()?;
+    let generator = VapidTokenGenerator::new(private_key, public_key, "mailto:test@example.com".to_string());
+    WebPushChannel::with_key_ring(VapidKeyRing::new(generator))
+}
+
+#[tokio::test]
+#[serial]
+async fn test_gone_response_prunes_subscription() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let endpoint = start_push_service(StatusCode::GONE).await?;
+    let (p256dh, auth) = subscription_keys();
+    let user_id = ulid::Ulid::new().to_string();
+
+    let subscription = WebPushChannel::save_subscription(&user_id, &endpoint, &p256dh, &auth, None)?;
+    let pruned_before = web_push_metrics::get_metrics().get_snapshot().await.total_subscriptions_pruned;
+
+    let channel = test_channel()?;
+    let message = WebPushMessage::new("Title".to_string(), "Body".to_string());
+    let result = channel.send_to_subscription(&subscription, &message).await;
+
+    let error = result.expect_err("410 response should fail the send");
+    assert!(matches!(error.downcast_ref::<WebPushSendError>(), Some(WebPushSendError::SubscriptionGone)));
+
+    let mut conn = pool.get()?;
+    let remaining: i64 = push_subscriptions::table
+        .filter(push_subscriptions::id.eq(&subscription.id))
+        .count()
+        .get_result(&mut conn)?;
+    assert_eq!(remaining, 0);
+
+    let pruned_after = web_push_metrics::get_metrics().get_snapshot().await.total_subscriptions_pruned;
+    assert_eq!(pruned_after, pruned_before + 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_successful_response_keeps_subscription() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let endpoint = start_push_service(StatusCode::CREATED).await?;
+    let (p256dh, auth) = subscription_keys();
+    let user_id = ulid::Ulid::new().to_string();
+
+    let subscription = WebPushChannel::save_subscription(&user_id, &endpoint, &p256dh, &auth, None)?;
+
+    let channel = test_channel()?;
+    let message = WebPushMessage::new("Title".to_string(), "Body".to_string());
+    channel.send_to_subscription(&subscription, &message).await?;
+
+    let mut conn = pool.get()?;
+    let remaining: i64 = push_subscriptions::table
+        .filter(push_subscriptions::id.eq(&subscription.id))
+        .count()
+        .get_result(&mut conn)?;
+    assert_eq!(remaining, 1);
+
+    WebPushChannel::remove_subscription(&user_id, &endpoint)?;
+    Ok(())
+}
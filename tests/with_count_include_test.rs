@@ -0,0 +1,102 @@
+//! Count Include Tests
+//!
+//! These tests verify that `include={relation}_count` adds the number of
+//! related rows to each listed record without loading the relation itself,
+//! and that soft-deleted children are left out of the count.
+
+mod common;
+
+use anyhow::Result;
+use chrono::Utc;
+use diesel::prelude::*;
+use rustaxum::app::models::organization::{CreateOrganization, Organization};
+use rustaxum::app::models::organization_domain::{CreateOrganizationDomain, OrganizationDomain};
+use rustaxum::app::models::organization_type::{CreateOrganizationType, OrganizationType};
+use rustaxum::app::query_builder::{QueryBuilderExt, QueryExecutor, QueryParams, Queryable};
+use rustaxum::app::services::organization_domain_service::OrganizationDomainService;
+use rustaxum::app::services::organization_service::OrganizationService;
+use rustaxum::app::services::organization_type_service::OrganizationTypeService;
+use rustaxum::database::DbPool;
+use rustaxum::schema::organizations;
+use serial_test::serial;
+
+async fn create_organization(pool: &DbPool, domain: &OrganizationDomain, organization_type: &OrganizationType, user_id: &str) -> Result<Organization> {
+    OrganizationService::create(pool, CreateOrganization {
+        domain_id: domain.id,
+        type_id: organization_type.id,
+        name: "Counted Organization".to_string(),
+        parent_id: None,
+        code: Some(ulid::Ulid::new().to_string()),
+        address: None,
+        authorized_capital: None,
+        business_activities: None,
+        contact_persons: None,
+        description: None,
+        email: None,
+        establishment_date: None,
+        governance_structure: None,
+        legal_status: None,
+        paid_capital: None,
+        path: None,
+        phone: None,
+        registration_number: None,
+        tax_number: None,
+        website: None,
+    }, user_id).await
+}
+
+fn soft_delete_organization(pool: &DbPool, organization: &Organization) -> Result<()> {
+    let mut conn = pool.get()?;
+    diesel::update(organizations::table.find(organization.id.to_string()))
+        .set(organizations::deleted_at.eq(Some(Utc::now())))
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+#[test]
+fn test_count_include_is_allowed() {
+    assert!(OrganizationDomain::is_include_allowed("organizations_count"));
+    assert!(OrganizationDomain::is_include_allowed("types_count"));
+    assert!(!OrganizationDomain::is_include_allowed("createdBy_count"));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_count_include_skips_soft_deleted_children() -> Result<()> {
+    let pool = common::setup_test_db().await?;
+    let user = common::create_user(&pool)?;
+    let user_id = user.id.to_string();
+
+    let domain = OrganizationDomainService::create(&pool, CreateOrganizationDomain {
+        code: Some(ulid::Ulid::new().to_string()),
+        name: "Counted Domain".to_string(),
+        description: None,
+    }, &user_id).await?;
+    let organization_type = OrganizationTypeService::create(&pool, CreateOrganizationType {
+        domain_id: domain.id,
+        code: Some(ulid::Ulid::new().to_string()),
+        name: "Department".to_string(),
+        description: None,
+        level: 1,
+    }, &user_id).await?;
+
+    let mut created = Vec::new();
+    for _ in 0..3 {
+        created.push(create_organization(&pool, &domain, &organization_type, &user_id).await?);
+    }
+    soft_delete_organization(&pool, &created[0])?;
+
+    let params = QueryParams::from_query_string(&format!(
+        "include=organizations_count,types_count&filter[id]={}",
+        domain.id
+    ))?;
+    let mut conn = pool.get()?;
+    let result = QueryExecutor::execute_paginated(OrganizationDomain::from_params(params)?, &mut conn)?;
+
+    assert_eq!(result.data.len(), 1);
+    let row = &result.data[0];
+    assert_eq!(row["organizations_count"], 2);
+    assert_eq!(row["types_count"], 1);
+    assert!(row.get("organizations").is_none(), "the relation itself is not loaded");
+    Ok(())
+}